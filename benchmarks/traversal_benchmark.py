@@ -0,0 +1,94 @@
+"""
+Benchmark of the shared single-pass traversal against running each detector on its own
+
+Usage:
+    python benchmarks/traversal_benchmark.py [PATH ...] [--repeat N]
+
+PATH defaults to the test_contracts directory. Directories are searched for
+.rs files recursively. Every built-in detector runs: one strategy runs each
+detector with its own traversal, the other runs the node-hook detectors over a
+single shared traversal and the rest on their own, as the analyzer does. Both
+strategies run over the same parsed trees, with a fresh context for each file
+and run, so the timings only include detection.
+"""
+import argparse
+import logging
+import sys
+import time
+from pathlib import Path
+from typing import Callable, List, Tuple
+
+from tree_sitter import Tree
+
+sys.path.insert(0, str(Path(__file__).resolve().parent.parent))
+
+from stylus_analyzer.detectors.detector_base import AnalysisContext  # noqa: E402
+from stylus_analyzer.detectors.registry import DetectorRegistry  # noqa: E402
+from stylus_analyzer.detectors.traversal import run_node_detectors  # noqa: E402
+from stylus_analyzer.file_utils import generate_rust_ast, read_file_content  # noqa: E402
+
+DEFAULT_PATH = Path(__file__).resolve().parent.parent / "test_contracts"
+
+
+def collect_sources(paths: List[Path]) -> List[Tuple[Tree, str, str]]:
+    """Parse every .rs file under the given paths"""
+    sources = []
+    for path in paths:
+        files = sorted(path.rglob("*.rs")) if path.is_dir() else [path]
+        for file_path in files:
+            code = read_file_content(str(file_path))
+            tree = generate_rust_ast(code) if code else None
+            if tree is not None:
+                sources.append((tree, code, str(file_path)))
+    return sources
+
+
+def best_time(run: Callable[[], None], repeat: int) -> float:
+    """Get the fastest of several timed runs, in seconds"""
+    timings = []
+    for _ in range(repeat):
+        start = time.perf_counter()
+        run()
+        timings.append(time.perf_counter() - start)
+    return min(timings)
+
+
+def main() -> None:
+    parser = argparse.ArgumentParser(description=__doc__.strip().splitlines()[0])
+    parser.add_argument("paths", nargs="*", type=Path, default=[DEFAULT_PATH], help="Files or directories to analyze")
+    parser.add_argument("--repeat", type=int, default=5, help="Number of timed runs of each strategy")
+    args = parser.parse_args()
+    logging.disable(logging.CRITICAL)
+
+    sources = collect_sources(args.paths)
+    if not sources:
+        sys.exit("No Rust files to analyze")
+    detectors = list(DetectorRegistry.with_builtin_detectors())
+    node_detectors = [detector for detector in detectors if detector.node_types]
+    other_detectors = [detector for detector in detectors if not detector.node_types]
+
+    def per_detector() -> None:
+        for tree, code, file_path in sources:
+            context = AnalysisContext(tree=tree, code=code, file_path=file_path)
+            for detector in detectors:
+                detector.run(context)
+
+    def single_pass() -> None:
+        for tree, code, file_path in sources:
+            context = AnalysisContext(tree=tree, code=code, file_path=file_path)
+            run_node_detectors(node_detectors, context)
+            for detector in other_detectors:
+                detector.run(context)
+
+    separate = best_time(per_detector, args.repeat)
+    shared = best_time(single_pass, args.repeat)
+    print(f"{len(sources)} files, {len(detectors)} detectors: {len(node_detectors)} node-hook, "
+          f"{len(other_detectors)} whole-function or whole-file")
+    print(f"Node-hook detectors: {', '.join(detector.id for detector in node_detectors)}")
+    print(f"Per-detector traversals: {separate * 1000:8.1f} ms")
+    print(f"Shared traversal:        {shared * 1000:8.1f} ms")
+    print(f"Speedup:                 {separate / shared:8.2f}x")
+
+
+if __name__ == "__main__":
+    main()
@@ -53,10 +53,12 @@ setup(
         "tree-sitter==0.20.2",
         "setuptools>=42.0.0",
         "reportlab>=3.0.0",
+        "tomli>=1.1.0; python_version < '3.11'",
     ],
     entry_points={
         "console_scripts": [
             "stylus-analyzer=stylus_analyzer.cli:main",
+            "cargo-stylus-analyzer=stylus_analyzer.cli:cargo_main",
         ],
     },
     description="AI-powered bug detection tool for Stylus/Rust contracts",
@@ -0,0 +1,202 @@
+"""
+Utility functions for computing Solidity ABI signatures and selectors of Stylus methods
+"""
+import re
+from typing import List, Optional
+
+# Keccak-f[1600] round constants
+_ROUND_CONSTANTS = [
+    0x0000000000000001, 0x0000000000008082, 0x800000000000808A, 0x8000000080008000,
+    0x000000000000808B, 0x0000000080000001, 0x8000000080008081, 0x8000000000008009,
+    0x000000000000008A, 0x0000000000000088, 0x0000000080008009, 0x000000008000000A,
+    0x000000008000808B, 0x800000000000008B, 0x8000000000008089, 0x8000000000008003,
+    0x8000000000008002, 0x8000000000000080, 0x000000000000800A, 0x800000008000000A,
+    0x8000000080008081, 0x8000000000008080, 0x0000000080000001, 0x8000000080008008,
+]
+
+# Rotation offsets indexed by lane position x + 5 * y
+_ROTATIONS = [
+    0, 1, 62, 28, 27,
+    36, 44, 6, 55, 20,
+    3, 10, 43, 25, 39,
+    41, 45, 15, 21, 8,
+    18, 2, 61, 56, 14,
+]
+
+_MASK = (1 << 64) - 1
+
+# Rate of Keccak-256 in bytes
+_RATE = 136
+
+# Rust types that map directly onto a Solidity ABI type
+RUST_ABI_TYPES = {
+    "Address": "address",
+    "bool": "bool",
+    "String": "string",
+    "str": "string",
+    "Bytes": "bytes",
+    "B256": "bytes32",
+    "u8": "uint8",
+    "u16": "uint16",
+    "u32": "uint32",
+    "u64": "uint64",
+    "u128": "uint128",
+    "i8": "int8",
+    "i16": "int16",
+    "i32": "int32",
+    "i64": "int64",
+    "i128": "int128",
+}
+
+_SIZED_TYPE_PATTERN = re.compile(r"^(U|I|B)(\d+)$")
+_GENERIC_TYPE_PATTERN = re.compile(r"^(\w+)<(.*)>$")
+_ARRAY_TYPE_PATTERN = re.compile(r"^\[(.*);\s*(\d+)\]$")
+
+
+def _rotate(value: int, shift: int) -> int:
+    return ((value << shift) | (value >> (64 - shift))) & _MASK if shift else value
+
+
+def _keccak_f(lanes: List[int]) -> None:
+    """Apply the Keccak-f[1600] permutation in place"""
+    for constant in _ROUND_CONSTANTS:
+        columns = [lanes[x] ^ lanes[x + 5] ^ lanes[x + 10] ^ lanes[x + 15] ^ lanes[x + 20] for x in range(5)]
+        for x in range(5):
+            delta = columns[(x - 1) % 5] ^ _rotate(columns[(x + 1) % 5], 1)
+            for y in range(0, 25, 5):
+                lanes[x + y] ^= delta
+
+        rotated = [0] * 25
+        for x in range(5):
+            for y in range(5):
+                rotated[y + 5 * ((2 * x + 3 * y) % 5)] = _rotate(lanes[x + 5 * y], _ROTATIONS[x + 5 * y])
+
+        for y in range(0, 25, 5):
+            for x in range(5):
+                lanes[x + y] = rotated[x + y] ^ (~rotated[(x + 1) % 5 + y] & rotated[(x + 2) % 5 + y])
+        lanes[0] ^= constant
+
+
+def keccak256(data: bytes) -> bytes:
+    """
+    Compute the Keccak-256 hash used by Ethereum.
+
+    This is the original Keccak padding, not the NIST SHA3-256 provided by
+    hashlib, so the two produce different digests.
+    """
+    padded = bytearray(data)
+    padded.append(0x01)
+    padded.extend(b"\x00" * (-len(padded) % _RATE))
+    padded[-1] |= 0x80
+
+    lanes = [0] * 25
+    for offset in range(0, len(padded), _RATE):
+        block = padded[offset:offset + _RATE]
+        for index in range(_RATE // 8):
+            lanes[index] ^= int.from_bytes(block[index * 8:index * 8 + 8], "little")
+        _keccak_f(lanes)
+    return b"".join(lane.to_bytes(8, "little") for lane in lanes[:4])
+
+
+def compute_selector(signature: str) -> str:
+    """Compute the 4-byte function selector of a signature such as `transfer(address,uint256)`"""
+    return "0x" + keccak256(signature.encode()).hex()[:8]
+
+
+def to_camel_case(name: str) -> str:
+    """Convert a Rust method name to the lowerCamelCase name the Stylus SDK exports"""
+    words = []
+    for part in re.split(r"[\W_]+", name):
+        words.extend(_split_words(part))
+    if not words:
+        return name
+    return words[0].lower() + "".join(word[:1].upper() + word[1:].lower() for word in words[1:])
+
+
+def _split_words(part: str) -> List[str]:
+    """
+    Split an alphanumeric run into words where heck, which the Stylus SDK
+    renames methods with, does: before an uppercase letter following a
+    lowercase one, and before the last capital of an acronym followed by a
+    lowercase letter. Digits belong to the word around them, so `foo2bar`
+    stays one word and `foo2Bar` splits after the digit.
+    """
+    words, start, mode = [], 0, None
+    for index, char in enumerate(part[:-1]):
+        following = part[index + 1]
+        # Digits keep the case of the letters before them
+        next_mode = "lower" if char.islower() else "upper" if char.isupper() else mode
+        if next_mode == "lower" and following.isupper():
+            words.append(part[start:index + 1])
+            start, mode = index + 1, None
+        elif mode == "upper" and char.isupper() and following.islower():
+            words.append(part[start:index])
+            start, mode = index, None
+        else:
+            mode = next_mode
+    if part[start:]:
+        words.append(part[start:])
+    return words
+
+
+def _split_type_arguments(text: str) -> List[str]:
+    """Split comma-separated type arguments at the top nesting level"""
+    arguments, depth, current = [], 0, ""
+    for char in text:
+        if char in "<([":
+            depth += 1
+        elif char in ">)]":
+            depth -= 1
+        if char == "," and depth == 0:
+            arguments.append(current.strip())
+            current = ""
+        else:
+            current += char
+    if current.strip():
+        arguments.append(current.strip())
+    return arguments
+
+
+def rust_type_to_abi(type_text: str) -> Optional[str]:
+    """
+    Map a Rust parameter type to its Solidity ABI type string, e.g. `Vec<Address>`
+    to `address[]`. Returns None for types without a known ABI encoding.
+    """
+    text = re.sub(r"\s+", " ", type_text).strip()
+    text = re.sub(r"^&\s*('\w+\s*)?(mut\s+)?", "", text).strip()
+    if text.startswith("(") and text.endswith(")"):
+        elements = [rust_type_to_abi(element) for element in _split_type_arguments(text[1:-1])]
+        return None if None in elements else f"({','.join(elements)})"
+
+    array = _ARRAY_TYPE_PATTERN.match(text)
+    if array:
+        element = rust_type_to_abi(array.group(1))
+        return f"{element}[{array.group(2)}]" if element else None
+    if text.startswith("[") and text.endswith("]"):
+        element = rust_type_to_abi(text[1:-1])
+        return "bytes" if element == "uint8" else (f"{element}[]" if element else None)
+
+    generic = _GENERIC_TYPE_PATTERN.match(text)
+    if generic:
+        name = generic.group(1).split("::")[-1]
+        arguments = _split_type_arguments(generic.group(2))
+        if name == "Vec" and len(arguments) == 1:
+            element = rust_type_to_abi(arguments[0])
+            return "bytes" if element == "uint8" else (f"{element}[]" if element else None)
+        if name in ("Uint", "Signed") and arguments and arguments[0].isdigit():
+            return f"{'uint' if name == 'Uint' else 'int'}{arguments[0]}"
+        if name == "FixedBytes" and len(arguments) == 1 and arguments[0].isdigit():
+            return f"bytes{arguments[0]}"
+        return None
+
+    name = text.split("::")[-1]
+    if name in RUST_ABI_TYPES:
+        return RUST_ABI_TYPES[name]
+    sized = _SIZED_TYPE_PATTERN.match(name)
+    if sized:
+        kind, bits = sized.group(1), int(sized.group(2))
+        if kind == "B":
+            return f"bytes{bits // 8}" if bits % 8 == 0 and 0 < bits <= 256 else None
+        if bits % 8 == 0 and 0 < bits <= 256:
+            return f"{'uint' if kind == 'U' else 'int'}{bits}"
+    return None
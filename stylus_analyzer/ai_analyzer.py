@@ -7,8 +7,6 @@ from typing import Dict, List, Optional, Any
 from openai import OpenAI
 from dotenv import load_dotenv
 
-# Configure logging
-logging.basicConfig(level=logging.INFO, format='%(asctime)s - %(name)s - %(levelname)s - %(message)s')
 logger = logging.getLogger(__name__)
 
 class AIAnalyzer:
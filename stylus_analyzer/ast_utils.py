@@ -0,0 +1,41 @@
+"""
+Helpers for walking the tree-sitter AST of a Rust file
+"""
+from typing import Iterator, Optional
+
+from tree_sitter import Node
+
+from stylus_analyzer.source_map import SourceMap
+
+
+def iter_nodes(node: Node) -> Iterator[Node]:
+    """Iterate over a node and all of its descendants in source order"""
+    yield node
+    for child in node.children:
+        yield from iter_nodes(child)
+
+
+def node_text(node: Node, code: str) -> str:
+    """Get the source text of a node"""
+    return SourceMap.of(code).node_text(node)
+
+
+def text_between(code: str, start_byte: int, end_byte: int) -> str:
+    """Get the source text between two byte offsets reported by tree-sitter"""
+    return SourceMap.of(code).text(start_byte, end_byte)
+
+
+def get_macro_name(node: Node, code: str) -> str:
+    """Get the name of a macro invocation, e.g. `sol` for `sol! { ... }`"""
+    macro = node.child_by_field_name("macro")
+    if not macro:
+        return ""
+    return node_text(macro, code).split("::")[-1]
+
+
+def get_parent_impl(node: Node) -> Optional[Node]:
+    """Find the impl block directly containing a function item"""
+    parent = node.parent
+    if parent and parent.type == "declaration_list" and parent.parent and parent.parent.type == "impl_item":
+        return parent.parent
+    return None
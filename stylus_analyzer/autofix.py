@@ -0,0 +1,108 @@
+"""
+Applying the safe fixes of findings to source files, for `static-analyze --fix`
+"""
+from dataclasses import dataclass, field
+from typing import Callable, List, Optional, Tuple
+
+from stylus_analyzer.findings import Finding, TextEdit
+
+
+@dataclass
+class FixResult:
+    """
+    The outcome of fixing one file.
+
+    `applied` holds the findings whose fix was applied and `skipped` the
+    fixable ones left alone because their edits overlap an applied fix or
+    fall outside the text.
+    """
+    code: str
+    applied: List[Finding] = field(default_factory=list)
+    skipped: List[Finding] = field(default_factory=list)
+
+
+def edit_offsets(line_starts: List[int], code: str, edit: TextEdit) -> Optional[Tuple[int, int]]:
+    """Get the character offsets an edit spans, or None if it lies outside the text"""
+    offsets = []
+    for line, column in ((edit.line, edit.column), (edit.line_end, edit.column_end)):
+        if not 1 <= line <= len(line_starts) or column < 1:
+            return None
+        line_end = line_starts[line] - 1 if line < len(line_starts) else len(code)
+        offset = line_starts[line - 1] + column - 1
+        if offset > line_end:
+            return None
+        offsets.append(offset)
+    start, end = offsets
+    return (start, end) if start <= end else None
+
+
+def apply_fixes(code: str, findings: List[Finding]) -> FixResult:
+    """
+    Apply the safe fixes of findings to a file's text
+
+    Fixes are taken in source order, and one whose edits overlap those of a
+    fix already taken is skipped, as are two insertions at the same place,
+    whose order would be ambiguous. The edits are then applied bottom-up so
+    the offsets of the remaining ones stay valid.
+    """
+    line_starts = [0]
+    for index, char in enumerate(code):
+        if char == "\n":
+            line_starts.append(index + 1)
+
+    result = FixResult(code=code)
+    taken: List[Tuple[int, int, str]] = []
+    fixable = [finding for finding in findings if finding.fix is not None and finding.fix.safe]
+    for finding in sorted(fixable, key=lambda finding: (finding.line, finding.column, finding.detector_id)):
+        spans = [edit_offsets(line_starts, code, edit) for edit in finding.fix.edits]
+        # The edits of one fix mustn't overlap each other either
+        conflicting = not spans or None in spans or \
+            _overlaps(spans, [(start, end) for start, end, _ in taken]) or \
+            any(_overlaps([span], spans[index + 1:]) for index, span in enumerate(spans))
+        if conflicting:
+            result.skipped.append(finding)
+            continue
+        taken.extend((start, end, edit.replacement) for (start, end), edit in zip(spans, finding.fix.edits))
+        result.applied.append(finding)
+
+    for start, end, replacement in sorted(taken, reverse=True):
+        code = code[:start] + replacement + code[end:]
+    result.code = code
+    return result
+
+
+def fix_file(analyzer, file_path: str,
+             select: Optional[Callable[[List[Finding]], List[Finding]]] = None) -> Optional[FixResult]:
+    """
+    Analyze a file and rewrite it in place with the safe fixes of its findings
+
+    The file's own text is analyzed, without macro expansion, so the fixes
+    line up with it, and its line endings are kept.
+
+    Args:
+        analyzer: The StaticAnalyzer running the enabled detectors
+        file_path: The file to fix
+        select: Narrows down the findings to fix, e.g. to those above a severity threshold
+
+    Returns:
+        The outcome, or None if the file can't be read
+    """
+    try:
+        with open(file_path, 'r', encoding='utf-8', newline='') as f:
+            code = f.read()
+    except (OSError, UnicodeDecodeError):
+        return None
+    findings = analyzer.analyze(code).findings
+    for finding in findings:
+        finding.file = file_path
+    result = apply_fixes(code, select(findings) if select else findings)
+    if result.applied:
+        with open(file_path, 'w', encoding='utf-8', newline='') as f:
+            f.write(result.code)
+    return result
+
+
+def _overlaps(spans: List[Tuple[int, int]], others: List[Tuple[int, int]]) -> bool:
+    """Check if any span overlaps, or starts at the same offset as, one of the others"""
+    return any((start < other_end and other_start < end) or start == other_start
+               for start, end in spans for other_start, other_end in others)
@@ -0,0 +1,129 @@
+"""
+Baselines of known findings, so only newly introduced issues are reported
+"""
+import hashlib
+import json
+import os
+from dataclasses import dataclass, field
+from typing import Dict, Iterable, List, Optional, Tuple
+
+from stylus_analyzer.findings import Finding, sort_findings
+
+# Version of the baseline file schema, bumped on incompatible changes
+BASELINE_SCHEMA_VERSION = 1
+
+DEFAULT_BASELINE_FILE = ".stylus-analyzer-baseline.json"
+
+
+def fingerprint(finding: Finding) -> str:
+    """
+    Get a fingerprint identifying a finding across runs
+
+    It hashes the detector id, the file and the code snippet with its
+    whitespace collapsed, so the finding keeps its fingerprint when lines are
+    added above it or the code is re-indented. The message is used for
+    findings without a snippet.
+    """
+    snippet = " ".join((finding.code_snippet or finding.message).split())
+    key = "\0".join((finding.detector_id, _normalize_path(finding.file), snippet))
+    return hashlib.sha256(key.encode("utf-8")).hexdigest()
+
+
+class Baseline:
+    """Fingerprints of known findings, with how often each occurs"""
+
+    def __init__(self, entries: Optional[Dict[str, Dict]] = None):
+        self.entries: Dict[str, Dict] = entries or {}
+
+    @classmethod
+    def from_findings(cls, findings: Iterable[Finding]) -> "Baseline":
+        """Create a baseline holding the given findings"""
+        entries: Dict[str, Dict] = {}
+        for finding in findings:
+            entry = entries.setdefault(fingerprint(finding), {
+                "detector_id": finding.detector_id,
+                "file": _normalize_path(finding.file),
+                "count": 0,
+            })
+            entry["count"] += 1
+        return cls(entries)
+
+    @classmethod
+    def load(cls, path: str) -> "Baseline":
+        """
+        Load a baseline file
+
+        Raises:
+            ValueError: If the file isn't a baseline of a supported schema version
+        """
+        with open(path, 'r', encoding='utf-8') as f:
+            data = json.load(f)
+        if not isinstance(data, dict) or data.get("schema_version") != BASELINE_SCHEMA_VERSION:
+            raise ValueError(f"{path}: not a baseline file of schema version {BASELINE_SCHEMA_VERSION}")
+        return cls({entry["fingerprint"]: {key: value for key, value in entry.items() if key != "fingerprint"}
+                    for entry in data.get("findings", [])})
+
+    def to_json(self) -> str:
+        """Serialize the baseline, ordered so regenerating it gives a small diff"""
+        findings = [{"fingerprint": key, **entry} for key, entry in
+                    sorted(self.entries.items(), key=lambda item: (item[1]["file"], item[1]["detector_id"], item[0]))]
+        return json.dumps({"schema_version": BASELINE_SCHEMA_VERSION, "findings": findings}, indent=2)
+
+    def save(self, path: str) -> None:
+        """Write the baseline to a file"""
+        with open(path, 'w', encoding='utf-8') as f:
+            f.write(self.to_json() + "\n")
+
+    def filter(self, findings: Iterable[Finding]) -> List[Finding]:
+        """Drop the findings recorded in the baseline, keeping any occurrences beyond the recorded count"""
+        remaining = {key: entry["count"] for key, entry in self.entries.items()}
+        new_findings = []
+        for finding in findings:
+            key = fingerprint(finding)
+            if remaining.get(key, 0) > 0:
+                remaining[key] -= 1
+            else:
+                new_findings.append(finding)
+        return new_findings
+
+
+@dataclass
+class FindingDiff:
+    """
+    The findings of two runs matched by fingerprint.
+
+    `unchanged` pairs each finding of the old run with its match in the new
+    one, whose line may have moved.
+    """
+    added: List[Finding] = field(default_factory=list)
+    removed: List[Finding] = field(default_factory=list)
+    unchanged: List[Tuple[Finding, Finding]] = field(default_factory=list)
+
+
+def diff_findings(old: Iterable[Finding], new: Iterable[Finding]) -> FindingDiff:
+    """
+    Compare the findings of two runs, e.g. the reports of a branch and of the pull request on top of it
+
+    Findings are matched by fingerprint, like baselines, so a finding whose
+    lines shifted is unchanged. A fingerprint occurring more often in one run
+    than in the other counts the extra occurrences as added or removed.
+    """
+    unmatched: Dict[str, List[Finding]] = {}
+    for finding in sort_findings(old):
+        unmatched.setdefault(fingerprint(finding), []).append(finding)
+    diff = FindingDiff()
+    for finding in sort_findings(new):
+        matches = unmatched.get(fingerprint(finding))
+        if matches:
+            diff.unchanged.append((matches.pop(0), finding))
+        else:
+            diff.added.append(finding)
+    diff.removed = sort_findings(finding for matches in unmatched.values() for finding in matches)
+    return diff
+
+
+def _normalize_path(path: Optional[str]) -> str:
+    """Normalize a file path relative to the working directory, with forward slashes"""
+    if not path:
+        return ""
+    return os.path.relpath(os.path.abspath(path)).replace(os.sep, "/")
@@ -0,0 +1,129 @@
+"""
+On-disk cache of per-file analysis results, so unchanged files aren't re-analyzed
+"""
+import dataclasses
+import hashlib
+import json
+import logging
+import os
+import shutil
+import tempfile
+from typing import TYPE_CHECKING, Optional
+
+from stylus_analyzer import __version__
+from stylus_analyzer.crate_modules import storage_dependencies
+from stylus_analyzer.findings import Confidence, Finding, Fix, ParseError, Severity
+from stylus_analyzer.suppressions import Suppression
+
+if TYPE_CHECKING:
+    from stylus_analyzer.static_analyzer import StaticAnalysisResult, StaticAnalyzer
+
+logger = logging.getLogger(__name__)
+
+# Version of the cache entry schema, bumped on incompatible changes
+CACHE_SCHEMA_VERSION = 4
+
+DEFAULT_CACHE_DIR = ".stylus-analyzer-cache"
+
+
+class AnalysisCache:
+    """
+    Analysis results stored as one JSON file per cache key.
+
+    A key covers everything a result depends on: the file contents, the
+    Cargo.toml next to the file, the files of the crate declaring storage
+    structs the file implements, the enabled detectors with their severity
+    overrides and the analyzer version. Changing any of them misses the cache.
+    """
+
+    def __init__(self, directory: str = DEFAULT_CACHE_DIR):
+        self.directory = directory
+
+    def key(self, code: str, file_path: str, analyzer: "StaticAnalyzer") -> str:
+        """Get the cache key of a file's analysis with an analyzer's detector configuration"""
+        registry = analyzer.registry
+        detectors = [(detector_id, str(registry.severity_override(detector_id) or ""),
+                      registry.detector_options(detector_id))
+                     for detector_id in registry.enabled_ids()]
+        cargo_toml_path = os.path.join(os.path.dirname(file_path), 'Cargo.toml')
+        cargo_toml = ""
+        if os.path.isfile(cargo_toml_path):
+            with open(cargo_toml_path, 'r', encoding='utf-8', errors='replace') as f:
+                cargo_toml = f.read()
+        dependencies = []
+        for dependency in storage_dependencies(file_path, code):
+            with open(dependency, 'r', encoding='utf-8', errors='replace') as f:
+                dependencies.append(f.read())
+        data = json.dumps([CACHE_SCHEMA_VERSION, __version__, detectors, cargo_toml, code, dependencies])
+        return hashlib.sha256(data.encode("utf-8")).hexdigest()
+
+    def get(self, key: str, file_path: str) -> Optional["StaticAnalysisResult"]:
+        """Load the cached result for a key, with its findings attributed to `file_path`"""
+        from stylus_analyzer.static_analyzer import StaticAnalysisResult
+
+        try:
+            with open(self._entry_path(key), 'r', encoding='utf-8') as f:
+                data = json.load(f)
+        except (OSError, ValueError):
+            return None
+        if data.get("schema_version") != CACHE_SCHEMA_VERSION:
+            return None
+
+        result = StaticAnalysisResult()
+        for finding in data["findings"]:
+            fix = Fix.from_dict(finding["fix"]) if finding["fix"] else None
+            confidence = Confidence.parse(finding["confidence"]) if finding["confidence"] else None
+            result.findings.append(Finding(**{**finding, "severity": Severity.parse(finding["severity"]),
+                                              "confidence": confidence, "file": file_path, "fix": fix}))
+        result.errors = data["errors"]
+        result.unused_suppressions = [Suppression(**suppression) for suppression in data["unused_suppressions"]]
+        if data["parse_error"]:
+            result.parse_error = ParseError(**{**data["parse_error"], "file": file_path})
+        result.analysis_time = data["analysis_time"]
+        return result
+
+    def put(self, key: str, result: "StaticAnalysisResult") -> None:
+        """Store a result, written atomically so concurrent workers never see partial entries"""
+        findings = []
+        for finding in result.findings:
+            entry = dataclasses.asdict(finding)
+            entry["severity"] = str(finding.severity)
+            entry["confidence"] = str(finding.confidence) if finding.confidence is not None else None
+            entry["fix"] = finding.fix.to_dict() if finding.fix else None
+            # The file is restored from the path being analyzed, so identical files share an entry
+            del entry["file"]
+            findings.append(entry)
+        data = {
+            "schema_version": CACHE_SCHEMA_VERSION,
+            "findings": findings,
+            "errors": result.errors,
+            "unused_suppressions": [dataclasses.asdict(suppression) for suppression in result.unused_suppressions],
+            "parse_error": result.parse_error.to_dict() if result.parse_error else None,
+            "analysis_time": result.analysis_time
+        }
+        try:
+            self._ensure_directory()
+            fd, temp_path = tempfile.mkstemp(dir=self.directory, suffix=".tmp")
+            with os.fdopen(fd, 'w', encoding='utf-8') as f:
+                json.dump(data, f)
+            os.replace(temp_path, self._entry_path(key))
+        except OSError as e:
+            logger.warning(f"Could not write analysis cache entry: {e}")
+
+    def clear(self) -> bool:
+        """Delete the cache directory, returning whether there was one"""
+        if not os.path.isdir(self.directory):
+            return False
+        shutil.rmtree(self.directory)
+        return True
+
+    def _entry_path(self, key: str) -> str:
+        return os.path.join(self.directory, f"{key}.json")
+
+    def _ensure_directory(self) -> None:
+        """Create the cache directory, with a .gitignore keeping it out of version control"""
+        if os.path.isdir(self.directory):
+            return
+        os.makedirs(self.directory, exist_ok=True)
+        with open(os.path.join(self.directory, ".gitignore"), 'w', encoding='utf-8') as f:
+            f.write("# Created by stylus-analyzer\n*\n")
@@ -0,0 +1,100 @@
+"""
+Intra-contract call graph of the methods of each impl'd type
+"""
+from collections import deque
+from dataclasses import dataclass, field
+from typing import Dict, Iterable, Optional, Set
+
+from tree_sitter import Node
+
+from stylus_analyzer.ast_utils import get_parent_impl, iter_nodes, node_text
+from stylus_analyzer.stylus_attributes import is_public_method
+
+
+@dataclass
+class CallGraph:
+    """
+    Calls between the methods of one type, through `self.method(...)` or `Self::method(...)`.
+
+    Methods of every impl block of the type are merged, so trait impls are
+    included. Calls to names that aren't methods of the type are kept as
+    edges, since the callee may be defined in another file.
+    """
+    type_name: str
+    methods: Dict[str, Node] = field(default_factory=dict)
+    public_methods: Set[str] = field(default_factory=set)
+    calls: Dict[str, Set[str]] = field(default_factory=dict)
+
+    def callees(self, method: str) -> Set[str]:
+        """Get the methods a method calls directly"""
+        return set(self.calls.get(method, ()))
+
+    def callers(self, method: str) -> Set[str]:
+        """Get the methods calling a method directly"""
+        return {caller for caller, callees in self.calls.items() if method in callees}
+
+    def reachable_from(self, methods: Iterable[str]) -> Set[str]:
+        """Get the methods called directly or transitively from the given ones, which are included"""
+        reached = set(methods)
+        queue = deque(reached)
+        while queue:
+            for callee in self.calls.get(queue.popleft(), ()):
+                if callee not in reached:
+                    reached.add(callee)
+                    queue.append(callee)
+        return reached
+
+    def is_reachable_from_public(self, method: str) -> bool:
+        """Check if a method is public or called, directly or transitively, from a public method"""
+        return method in self.reachable_from(self.public_methods)
+
+    def is_recursive(self, method: str) -> bool:
+        """Check if a method can call itself, directly or through other methods"""
+        return method in self.reachable_from(self.callees(method))
+
+
+def build_call_graphs(root: Node, code: str) -> Dict[str, CallGraph]:
+    """Build the call graph of every type with methods defined in impl blocks below a node"""
+    graphs: Dict[str, CallGraph] = {}
+    for node in iter_nodes(root):
+        if node.type != "function_item":
+            continue
+        impl_node = get_parent_impl(node)
+        impl_type = impl_node.child_by_field_name("type") if impl_node else None
+        name = node.child_by_field_name("name")
+        if impl_type is None or name is None:
+            continue
+        type_name = node_text(impl_type, code).split("<")[0].strip()
+        graph = graphs.setdefault(type_name, CallGraph(type_name))
+        method = node_text(name, code)
+        graph.methods[method] = node
+        if is_public_method(node, code):
+            graph.public_methods.add(method)
+        callees = graph.calls.setdefault(method, set())
+        for child in iter_nodes(node):
+            callee = get_internal_callee(child, code)
+            if callee:
+                callees.add(callee)
+    return graphs
+
+
+def get_internal_callee(node: Node, code: str) -> Optional[str]:
+    """Get the method name of a `self.method(...)` or `Self::method(...)` call"""
+    if node.type != "call_expression":
+        return None
+    function = node.child_by_field_name("function")
+    if function is not None and function.type == "generic_function":
+        function = function.child_by_field_name("function")
+    if function is None:
+        return None
+    if function.type == "field_expression":
+        receiver = function.child_by_field_name("value")
+        method = function.child_by_field_name("field")
+        if receiver is not None and receiver.type == "self" and method is not None:
+            return node_text(method, code)
+    elif function.type == "scoped_identifier":
+        path = function.child_by_field_name("path")
+        method = function.child_by_field_name("name")
+        if path is not None and method is not None and node_text(path, code) == "Self":
+            return node_text(method, code)
+    return None
@@ -6,25 +6,49 @@ import sys
 import json
 import click
 import logging
-from typing import Optional, Dict, Any
+from typing import Optional, Dict, Any, List, Tuple
 import time
 import subprocess
 
 from stylus_analyzer.ai_analyzer import AIAnalyzer
-from stylus_analyzer.static_analyzer import StaticAnalyzer
-from stylus_analyzer.file_utils import collect_project_files, read_file_content, find_rust_contracts
-from stylus_analyzer.output_utils import format_analysis_results, generate_pdf_report
+from stylus_analyzer.static_analyzer import DetectorProfile, StaticAnalyzer, StaticAnalysisResult
+from stylus_analyzer.findings import Confidence, Finding, FindingSummary, Severity, exit_code_for, filter_findings, sort_findings
+from stylus_analyzer.config import Config, ConfigError, load_config, load_config_for
+from stylus_analyzer.baseline import DEFAULT_BASELINE_FILE, Baseline, diff_findings
+from stylus_analyzer.cache import DEFAULT_CACHE_DIR, AnalysisCache
+from stylus_analyzer.autofix import fix_file
+from stylus_analyzer.file_filter import FileFilter
+from stylus_analyzer.file_utils import collect_project_files, read_file_content
+from stylus_analyzer.logging_utils import DEFAULT, QUIET, VERBOSE, configure_logging
+from stylus_analyzer.lsp import LanguageServer
+from stylus_analyzer.workspace import WorkspaceError, collect_contract_files, contract_root
+from stylus_analyzer.output_utils import (
+    format_analysis_results, format_detector_explanation, format_detector_list, format_file_header, format_finding_diff,
+    format_profile, format_summary, generate_detector_explanation_json, generate_detector_list_json,
+    generate_finding_diff_json, generate_github_annotations, generate_json_report, generate_junit_report,
+    generate_markdown_report, generate_pdf_report, generate_sarif_report, generate_security_report, load_json_report, use_color
+)
 
-# Configure logging
-logging.basicConfig(level=logging.INFO,
-                    format='%(asctime)s - %(name)s - %(levelname)s - %(message)s')
 logger = logging.getLogger(__name__)
 
+# Severity names accepted by --severity-threshold and --fail-on
+SEVERITY_CHOICES = ['info', 'low', 'medium', 'high']
+
+# Confidence names accepted by --min-confidence
+CONFIDENCE_CHOICES = ['low', 'medium', 'high']
+
+# Report formats of static-analyze other than text, by the name --no-filter's help lists them under
+REPORT_FORMATS = {'json': 'JSON', 'sarif': 'SARIF', 'markdown': 'Markdown', 'github': 'GitHub', 'junit': 'JUnit',
+                  'security-report': 'security'}
+
+# Name cargo passes as the first argument when run as `cargo stylus-analyzer`
+CARGO_SUBCOMMAND = "stylus-analyzer"
+
 
 @click.group()
 def cli():
     """Stylus Analyzer - Bug detection tool for Stylus/Rust contracts"""
-    pass
+    _configure_logging(DEFAULT)
 
 
 def preprocess_with_cargo_expand(file_path: str) -> Optional[str]:
@@ -51,7 +75,7 @@ def analyze(project_dir: str, output: Optional[str], model: str, verbose: bool):
     Analyze Rust contracts in the specified Stylus project directory using AI
     """
     if verbose:
-        logging.getLogger().setLevel(logging.DEBUG)
+        _configure_logging(VERBOSE)
 
     logger.info(f"Analyzing Stylus project in: {project_dir}")
 
@@ -149,23 +173,113 @@ def version():
 @click.argument('target', type=click.Path(exists=True))
 @click.option('--output', '-o', type=click.Path(), help='Output file to save the analysis results as JSON')
 @click.option('--pdf', '-p', type=click.Path(), help='Output file to save the analysis results as PDF')
-@click.option('--verbose', '-v', is_flag=True, help='Enable verbose output')
-def static_analyze(target: str, output: Optional[str], pdf: Optional[str], verbose: bool):
+@click.option('--verbose', '-v', is_flag=True,
+              help='Show the description and code of each finding, per-detector timings and skipped files')
+@click.option('--quiet', '-q', is_flag=True, help='Only print findings, without progress, timings or the summary')
+@click.option('--format', '-f', 'output_format', type=click.Choice(['text', *REPORT_FORMATS]),
+              default='text',
+              help='Print results as human-readable text, a versioned JSON report, a SARIF 2.1.0 log, a Markdown '
+                   'report for pull request comments, GitHub Actions annotations, JUnit XML for CI test reporters '
+                   'or a security report for finding aggregators')
+@click.option('--severity-threshold', type=click.Choice(SEVERITY_CHOICES, case_sensitive=False),
+              help='Only report findings of at least this severity')
+@click.option('--fail-on', type=click.Choice(SEVERITY_CHOICES, case_sensitive=False),
+              help='Exit with status 1 if a reported finding has at least this severity')
+@click.option('--min-confidence', type=click.Choice(CONFIDENCE_CHOICES, case_sensitive=False),
+              help='Only report findings of at least this confidence, e.g. high to leave out heuristic guesses')
+@click.option('--no-filter', is_flag=True,
+              help=f"Keep findings below --severity-threshold in {', '.join(list(REPORT_FORMATS.values())[:-1])} "
+                   f"and {list(REPORT_FORMATS.values())[-1]} reports")
+@click.option('--config', '-c', 'config_path', type=click.Path(exists=True, dir_okay=False),
+              help='Config file to use instead of the nearest .stylus-analyzer.toml')
+@click.option('--enable', multiple=True, metavar='ID', help='Run a detector even if the config disables it')
+@click.option('--disable', multiple=True, metavar='ID', help='Do not run a detector')
+@click.option('--detectors', 'selected_detectors', multiple=True, metavar='IDS',
+              help='Only run these comma-separated detectors, regardless of the config')
+@click.option('--exclude-detectors', 'excluded_detectors', multiple=True, metavar='IDS',
+              help='Do not run these comma-separated detectors')
+@click.option('--severity-override', multiple=True, metavar='ID=SEVERITY',
+              help='Report every finding of a detector at this severity')
+@click.option('--warn-unused-suppressions', is_flag=True,
+              help='Warn about stylus-analyzer-disable comments that did not suppress anything')
+@click.option('--baseline', 'baseline_path', type=click.Path(exists=True, dir_okay=False),
+              help='Only report findings not recorded in this baseline file')
+@click.option('--jobs', '-j', type=click.IntRange(min=1), help='Number of files to analyze in parallel [default: CPU count]')
+@click.option('--no-cache', is_flag=True, help=f'Re-analyze every file instead of reusing results from {DEFAULT_CACHE_DIR}/')
+@click.option('--include', multiple=True, metavar='GLOB',
+              help='Only analyze the files of a directory or workspace matching a glob, e.g. "**/token.rs"')
+@click.option('--exclude', multiple=True, metavar='GLOB', help='Do not analyze files matching a glob, e.g. "vendor/**"')
+@click.option('--no-gitignore', is_flag=True, help='Also analyze files ignored by .gitignore')
+@click.option('--fix', is_flag=True,
+              help='Rewrite files in place with the safe fixes of the reported findings before reporting the rest')
+@click.option('--no-snippet', is_flag=True, help='Do not show the source lines of each finding in text output')
+@click.option('--color', type=click.Choice(['auto', 'always', 'never']), default='auto', show_default=True,
+              help='Color text output; auto colors only on a terminal without NO_COLOR set')
+@click.option('--profile', is_flag=True,
+              help='Print the time each detector took across all files on stderr, slowest first; implies --no-cache')
+def static_analyze(target: str, output: Optional[str], pdf: Optional[str], verbose: bool, quiet: bool,
+                   output_format: str,
+                   severity_threshold: Optional[str], fail_on: Optional[str], min_confidence: Optional[str],
+                   no_filter: bool, config_path: Optional[str], enable: Tuple[str, ...], disable: Tuple[str, ...],
+                   selected_detectors: Tuple[str, ...], excluded_detectors: Tuple[str, ...], severity_override: Tuple[str, ...], warn_unused_suppressions: bool,
+                   baseline_path: Optional[str], jobs: Optional[int], no_cache: bool, include: Tuple[str, ...],
+                   exclude: Tuple[str, ...], no_gitignore: bool, fix: bool, no_snippet: bool, color: str,
+                   profile: bool):
     """
     Perform static analysis on Rust contracts to detect common issues.
-    The target can be a file or a directory.
+    The target can be a file, a directory or the Cargo.toml of a workspace.
     """
-    analyzer = StaticAnalyzer()
-
-    # Track total issues found across all files
-    total_issues = 0
-
-    if os.path.isdir(target):
-        contract_files = find_rust_contracts(target)
+    if quiet and verbose:
+        raise click.UsageError("--quiet and --verbose can't be used together")
+    _configure_logging(QUIET if quiet else VERBOSE if verbose else DEFAULT)
+
+    def progress(text: str = "", **kwargs) -> None:
+        # Progress, timings and summaries are left out with --quiet
+        if not quiet:
+            click.echo(text, **kwargs)
+
+    analyzer = _configure_analyzer(target, config_path, enable, disable + _split_detector_ids(excluded_detectors),
+                                   severity_override, cache=not no_cache and not profile,
+                                   selected=_split_detector_ids(selected_detectors) if selected_detectors else None)
+    try:
+        baseline = Baseline.load(baseline_path) if baseline_path else None
+    except ValueError as e:
+        raise click.ClickException(str(e))
+    threshold = Severity.parse(severity_threshold) if severity_threshold else None
+    fail_severity = Severity.parse(fail_on) if fail_on else None
+    confidence = Confidence.parse(min_confidence) if min_confidence else None
+    file_filter = FileFilter(include=list(include), exclude=list(exclude), gitignore=not no_gitignore)
+    # Cached results carry no timings, so profiling re-analyzes every file
+    detector_profile = DetectorProfile() if profile else None
+
+    if fix:
+        fix_files = _collect_contract_files(target, file_filter) if contract_root(target) is not None else [target]
+        _apply_fixes(analyzer, fix_files, threshold, baseline, confidence)
+
+    if output_format in REPORT_FORMATS:
+        reported = _static_analyze_report(analyzer, target, output, pdf, output_format,
+                                          None if no_filter else threshold, baseline, warn_unused_suppressions, jobs,
+                                          file_filter, confidence, detector_profile)
+        if detector_profile is not None:
+            click.echo(format_profile(detector_profile), err=True)
+        _exit_for_findings(reported, fail_severity)
+        return
+
+    colored = use_color(color)
+    started = time.perf_counter()
+
+    # Tally the reported findings across all files for the summary
+    files_scanned = 0
+    parse_errors = 0
+    reported: List[Finding] = []
+
+    root = contract_root(target)
+    if root is not None:
+        contract_files = _collect_contract_files(target, file_filter)
         if not contract_files:
-            click.echo("No Rust contract files found in the directory.")
+            progress("No Rust contract files found in the directory.")
             return
-        if analyzer.check_reentrancy_feature(target):
+        if analyzer.check_reentrancy_feature(root):
             click.echo("\nHigh severity issues:")
             click.echo("  [1] Reentrancy feature status")
             click.echo("      Status: You have disabled for stylus-sdk")
@@ -175,30 +289,31 @@ def static_analyze(target: str, output: Optional[str], pdf: Optional[str], verbo
                 "  Recommendation: You can remove reentrant from features so it can handle automatically by stylus-sdk.")
             click.echo(
                 "  Ensure that your contract logic is designed to handle reentrancy appropriately.")
-
-            # Print summary
-            click.echo(f"\n===== Analysis Summary =====")
-            click.echo(f"Analyzed {len(contract_files)} files")
-            click.echo(f"Found {total_issues} total issues")
         else:
-            click.echo("Reentrancy feature is enabled for stylus-sdk.")
+            progress("Reentrancy feature is enabled for stylus-sdk.")
 
         all_results = {}
-        for file_path in contract_files:
-            relative_path = os.path.relpath(file_path, target)
-            click.echo(f"\n===== Static Analysis for {relative_path} =====")
-
-            code = read_file_content(file_path)
-            if code:
-                analysis_result = analyzer.analyze(code, file_path)
+        for file_path, analysis_result in analyzer.analyze_files(contract_files, jobs).items():
+            relative_path = os.path.relpath(file_path, root)
+            if not quiet or analysis_result is None or _has_report(analysis_result):
+                click.echo(format_file_header(relative_path, colored), color=colored or None)
+
+            if analysis_result is not None:
+                if detector_profile is not None:
+                    detector_profile.add(analysis_result)
+                _filter_result(analysis_result, threshold, baseline, confidence)
+                if warn_unused_suppressions:
+                    _warn_unused_suppressions(relative_path, analysis_result)
+                reported.extend(analysis_result.findings)
                 all_results[relative_path] = analysis_result.to_dict()
-                total_issues += len(analysis_result.issues)
+                files_scanned += 1
+                parse_errors += analysis_result.parse_error is not None
 
-                format_analysis_results(
-                    relative_path, analysis_result, verbose)
+                if not quiet or _has_report(analysis_result):
+                    format_analysis_results(
+                        relative_path, analysis_result, verbose, snippet=not no_snippet, color=colored)
 
-                click.echo(
-                    f"Analysis completed in {analysis_result.analysis_time:.2f} seconds")
+                progress(f"Analysis completed in {analysis_result.analysis_time:.2f} seconds")
             else:
                 click.echo(f"Could not read file: {file_path}")
 
@@ -210,18 +325,24 @@ def static_analyze(target: str, output: Optional[str], pdf: Optional[str], verbo
         if pdf:
             generate_pdf_report(all_results, pdf)
 
-
     else:
-        code = read_file_content(target)
-        if not code:
+        analysis_result = analyzer.analyze_file(target)
+        if analysis_result is None:
             click.echo(f"Could not read file: {target}")
             return
 
-        analysis_result = analyzer.analyze(code, file_path=target)
+        if detector_profile is not None:
+            detector_profile.add(analysis_result)
+        _filter_result(analysis_result, threshold, baseline, confidence)
+        if warn_unused_suppressions:
+            _warn_unused_suppressions(target, analysis_result)
+        reported.extend(analysis_result.findings)
+        files_scanned += 1
+        parse_errors += analysis_result.parse_error is not None
 
-        format_analysis_results(target, analysis_result, verbose)
-        click.echo(
-            f"Analysis completed in {analysis_result.analysis_time:.2f} seconds")
+        if not quiet or _has_report(analysis_result):
+            format_analysis_results(target, analysis_result, verbose, snippet=not no_snippet, color=colored)
+        progress(f"Analysis completed in {analysis_result.analysis_time:.2f} seconds")
 
         if output:
             with open(output, 'w', encoding='utf-8') as f:
@@ -241,25 +362,323 @@ def static_analyze(target: str, output: Optional[str], pdf: Optional[str], verbo
                 "  Recommendation: You can remove reentrant from features so it can handle automatically by stylus-sdk.")
             click.echo(
                 "  Ensure that your contract logic is designed to handle reentrancy appropriately.")
+        else:
+            progress("Reentrancy feature is enabled for stylus-sdk.")
+
+    summary = FindingSummary.of(reported, files_scanned, time.perf_counter() - started, parse_errors)
+    progress(format_summary(summary, colored), color=colored or None)
+    if detector_profile is not None:
+        click.echo(format_profile(detector_profile), err=True)
+    _exit_for_findings(reported, fail_severity)
+
+
+@cli.command()
+@click.option('--format', '-f', 'output_format', type=click.Choice(['text', 'json']), default='text',
+              help='Print the detectors as a table or as JSON')
+def list_detectors(output_format: str):
+    """List the available detectors with their default severity and description"""
+    detectors = list(StaticAnalyzer().registry)
+    if output_format == 'json':
+        click.echo(generate_detector_list_json(detectors))
+    else:
+        click.echo(format_detector_list(detectors))
+
 
-            # Print summary
-            click.echo(f"\n===== Analysis Summary =====")
-            click.echo(f"Analyzed {len(contract_files)} files")
-            click.echo(f"Found {total_issues} total issues")
+@cli.command()
+@click.argument('detector_name', metavar='DETECTOR')
+@click.option('--format', '-f', 'output_format', type=click.Choice(['text', 'json']), default='text',
+              help='Print the documentation as text or as JSON')
+def explain(detector_name: str, output_format: str):
+    """
+    Explain what a detector catches, why it matters and how to fix it
+
+    DETECTOR is a detector id such as missing_access_control, or its class
+    name such as MissingAccessControl.
+    """
+    detector = StaticAnalyzer().registry.find(detector_name)
+    if detector is None:
+        raise click.BadParameter(f"Unknown detector '{detector_name}', see list-detectors",
+                                 param_hint="'DETECTOR'")
+    if output_format == 'json':
+        click.echo(generate_detector_explanation_json(detector))
+    else:
+        click.echo(format_detector_explanation(detector))
+
+
+@cli.group()
+def baseline():
+    """Manage baselines of known findings"""
+    pass
+
+
+@baseline.command('generate')
+@click.argument('target', type=click.Path(exists=True))
+@click.option('--output', '-o', type=click.Path(), default=DEFAULT_BASELINE_FILE, show_default=True,
+              help='Baseline file to write')
+@click.option('--config', '-c', 'config_path', type=click.Path(exists=True, dir_okay=False),
+              help='Config file to use instead of the nearest .stylus-analyzer.toml')
+@click.option('--jobs', '-j', type=click.IntRange(min=1), help='Number of files to analyze in parallel [default: CPU count]')
+@click.option('--no-cache', is_flag=True, help=f'Re-analyze every file instead of reusing results from {DEFAULT_CACHE_DIR}/')
+@click.option('--include', multiple=True, metavar='GLOB',
+              help='Only analyze the files of a directory or workspace matching a glob, e.g. "**/token.rs"')
+@click.option('--exclude', multiple=True, metavar='GLOB', help='Do not analyze files matching a glob, e.g. "vendor/**"')
+@click.option('--no-gitignore', is_flag=True, help='Also analyze files ignored by .gitignore')
+def generate_baseline(target: str, output: str, config_path: Optional[str], jobs: Optional[int], no_cache: bool,
+                      include: Tuple[str, ...], exclude: Tuple[str, ...], no_gitignore: bool):
+    """
+    Record the current findings of a file, directory or workspace, so
+    that `static-analyze --baseline` only reports new ones.
+    """
+    analyzer = _configure_analyzer(target, config_path, cache=not no_cache)
+    file_filter = FileFilter(include=list(include), exclude=list(exclude), gitignore=not no_gitignore)
+    contract_files = _collect_contract_files(target, file_filter)
+    findings = []
+    for file_path, analysis_result in analyzer.analyze_files(contract_files, jobs).items():
+        if analysis_result is not None:
+            findings.extend(analysis_result.findings)
         else:
-            click.echo("Reentrancy feature is enabled for stylus-sdk.")
+            logger.warning(f"Could not read file: {file_path}")
+    Baseline.from_findings(findings).save(output)
+    click.echo(f"Recorded {len(findings)} findings in {output}")
+
+
+@cli.command()
+@click.argument('old_report', type=click.Path(exists=True, dir_okay=False))
+@click.argument('new_report', type=click.Path(exists=True, dir_okay=False))
+@click.option('--format', '-f', 'output_format', type=click.Choice(['text', 'json']), default='text',
+              help='Print the differences as text or as JSON')
+def diff(old_report: str, new_report: str, output_format: str):
+    """
+    Compare two JSON reports of `static-analyze --format json` and print
+    the findings that were added, removed or left unchanged.
 
+    Findings are matched by the same fingerprint as baselines, so a finding
+    that only moved to another line is unchanged.
+    """
+    reports = []
+    for path in (old_report, new_report):
+        try:
+            reports.append(load_json_report(read_file_content(path) or ""))
+        except ValueError as e:
+            raise click.ClickException(f"Could not read report {path}: {e}")
+    finding_diff = diff_findings(*reports)
+    if output_format == 'json':
+        click.echo(generate_finding_diff_json(finding_diff))
+    else:
+        click.echo(format_finding_diff(finding_diff))
 
 
-def main():
+@cli.command()
+@click.option('--config', '-c', 'config_path', type=click.Path(exists=True, dir_okay=False),
+              help='Config file to use instead of the nearest .stylus-analyzer.toml')
+def lsp(config_path: Optional[str]):
+    """
+    Run a Language Server Protocol server on stdin and stdout that
+    publishes findings as diagnostics of the open documents.
+    """
+    analyzer = _configure_analyzer(".", config_path, cache=False)
+    server = LanguageServer(analyzer, sys.stdin.buffer, sys.stdout.buffer)
+    # stdout carries the protocol, so stray prints go to stderr like the logs
+    sys.stdout = sys.stderr
+    sys.exit(server.serve())
+
+
+@cli.command()
+def clean():
+    """Delete the analysis cache in the current directory"""
+    if AnalysisCache().clear():
+        click.echo(f"Removed {DEFAULT_CACHE_DIR}/")
+    else:
+        click.echo("No analysis cache to remove.")
+
+
+def _parse_severity_overrides(values: Tuple[str, ...]) -> Dict[str, Severity]:
+    """Parse `--severity-override ID=SEVERITY` values"""
+    overrides = {}
+    for value in values:
+        detector_id, separator, severity = value.partition("=")
+        if not separator or not detector_id.strip():
+            raise ConfigError(f"Invalid --severity-override '{value}', expected ID=SEVERITY")
+        try:
+            overrides[detector_id.strip()] = Severity.parse(severity)
+        except ValueError:
+            raise ConfigError(f"Invalid severity '{severity}' in --severity-override '{value}'") from None
+    return overrides
+
+
+def _split_detector_ids(values: Tuple[str, ...]) -> Tuple[str, ...]:
+    """Split comma-separated detector id options such as `--detectors a,b` into ids"""
+    return tuple(detector_id.strip() for value in values for detector_id in value.split(",") if detector_id.strip())
+
+
+def _configure_analyzer(target: str, config_path: Optional[str], enable: Tuple[str, ...] = (),
+                        disable: Tuple[str, ...] = (), severity_override: Tuple[str, ...] = (),
+                        cache: bool = True, selected: Optional[Tuple[str, ...]] = None) -> StaticAnalyzer:
+    """Create an analyzer configured by the config file and the detector options, caching results unless disabled"""
+    analyzer = StaticAnalyzer()
+    if cache:
+        analyzer.cache = AnalysisCache()
+    try:
+        file_config = load_config(config_path) if config_path else load_config_for(target)
+        cli_config = Config(enabled=list(enable), disabled=list(disable),
+                            selected=list(selected) if selected is not None else None,
+                            severity_overrides=_parse_severity_overrides(severity_override))
+        # Validated separately so errors point at the file or at the flags
+        for config in (file_config, cli_config):
+            config.validate(analyzer.registry.ids())
+        file_config.merge(cli_config).apply(analyzer.registry)
+    except ConfigError as e:
+        raise click.ClickException(str(e))
+    return analyzer
+
+
+def _configure_logging(level: int) -> None:
+    """Set up logging at a level, see `configure_logging`"""
+    try:
+        configure_logging(level)
+    except ValueError as e:
+        raise click.UsageError(str(e))
+
+
+def _has_report(analysis_result: StaticAnalysisResult) -> bool:
+    """Check if a file's result has something to show with --quiet: findings, errors or a parse error"""
+    return analysis_result.has_issues() or analysis_result.has_errors() or analysis_result.parse_error is not None
+
+
+def _collect_contract_files(target: str, file_filter: Optional[FileFilter] = None) -> List[str]:
+    """Resolve an analysis target to its contract files, see `collect_contract_files`"""
+    try:
+        return collect_contract_files(target, file_filter)
+    except WorkspaceError as e:
+        raise click.ClickException(str(e))
+
+
+def _apply_fixes(analyzer: StaticAnalyzer, file_paths: List[str], threshold: Optional[Severity],
+                 baseline: Optional[Baseline], min_confidence: Optional[Confidence] = None) -> None:
+    """Fix the findings above the thresholds and not in the baseline in place, reporting on stderr"""
+    def select(findings: List[Finding]) -> List[Finding]:
+        return filter_findings(baseline.filter(findings) if baseline else findings, threshold, min_confidence)
+
+    for file_path in file_paths:
+        result = fix_file(analyzer, file_path, select)
+        if result is None:
+            logger.warning(f"Could not read file: {file_path}")
+            continue
+        for finding in result.skipped:
+            click.echo(f"Warning: {file_path}:{finding.line}: skipped the fix for {finding.detector_id}, "
+                       "which overlaps another fix", err=True)
+        if result.applied:
+            click.echo(f"Fixed {len(result.applied)} issue(s) in {file_path}", err=True)
+
+
+def _filter_result(analysis_result: StaticAnalysisResult, threshold: Optional[Severity],
+                   baseline: Optional[Baseline], min_confidence: Optional[Confidence] = None) -> None:
+    """
+    Drop the findings recorded in the baseline or below the severity threshold or the minimum confidence from an
+    analysis result
+    """
+    findings = baseline.filter(analysis_result.findings) if baseline else analysis_result.findings
+    analysis_result.findings = filter_findings(findings, threshold, min_confidence)
+
+
+def _warn_unused_suppressions(file_path: str, analysis_result: StaticAnalysisResult) -> None:
+    """Print a warning for each suppression comment that matched no finding"""
+    for suppression in analysis_result.unused_suppressions:
+        click.echo(f"Warning: {file_path}: unused suppression {suppression.describe()}", err=True)
+
+
+def _exit_for_findings(findings: List[Finding], fail_on: Optional[Severity]) -> None:
+    """Exit with a non-zero status if a reported finding meets the --fail-on severity"""
+    exit_code = exit_code_for(findings, fail_on)
+    if exit_code:
+        sys.exit(exit_code)
+
+
+def _static_analyze_report(analyzer: StaticAnalyzer, target: str, output: Optional[str], pdf: Optional[str],
+                           output_format: str, threshold: Optional[Severity], baseline: Optional[Baseline] = None,
+                           warn_unused_suppressions: bool = False, jobs: Optional[int] = None,
+                           file_filter: Optional[FileFilter] = None,
+                           min_confidence: Optional[Confidence] = None,
+                           profile: Optional[DetectorProfile] = None) -> List[Finding]:
+    """
+    Analyze a file or directory and print or save all findings as one JSON, SARIF, Markdown or JUnit report, or
+    as GitHub Actions annotations, adding the detector timings of each file to the profile if given
+
+    Returns:
+        The findings included in the report
+    """
+    started = time.perf_counter()
+    contract_files = _collect_contract_files(target, file_filter)
+    root = contract_root(target)
+    findings = []
+    parse_errors = []
+    all_results = {}
+    for file_path, analysis_result in analyzer.analyze_files(contract_files, jobs).items():
+        if analysis_result is None:
+            logger.warning(f"Could not read file: {file_path}")
+            continue
+        if profile is not None:
+            profile.add(analysis_result)
+        _filter_result(analysis_result, threshold, baseline, min_confidence)
+        if warn_unused_suppressions:
+            _warn_unused_suppressions(file_path, analysis_result)
+        findings.extend(analysis_result.findings)
+        if analysis_result.parse_error:
+            parse_errors.append(analysis_result.parse_error)
+        all_results[os.path.relpath(file_path, root) if root is not None else file_path] = analysis_result.to_dict()
+
+    # Report in the same order however the files were listed or scheduled
+    findings = sort_findings(findings)
+    parse_errors.sort(key=lambda parse_error: (parse_error.file or "", parse_error.line, parse_error.column))
+    all_results = dict(sorted(all_results.items()))
+    summary = FindingSummary.of(findings, len(all_results), time.perf_counter() - started, len(parse_errors))
+    if output_format == 'sarif':
+        report = generate_sarif_report(findings, analyzer.detectors, parse_errors=parse_errors)
+    elif output_format == 'security-report':
+        report = generate_security_report(findings, analyzer.detectors, parse_errors=parse_errors)
+    elif output_format == 'markdown':
+        report = generate_markdown_report(findings, summary, parse_errors, base_dir=root)
+    elif output_format == 'github':
+        report = generate_github_annotations(findings, parse_errors, base_dir=os.getcwd())
+    elif output_format == 'junit':
+        report = generate_junit_report(findings, parse_errors, base_dir=root)
+    else:
+        report = generate_json_report(findings, summary, parse_errors)
+    if output:
+        with open(output, 'w', encoding='utf-8') as f:
+            f.write(report)
+        logger.info(f"Static analysis results saved to: {output}")
+    else:
+        click.echo(report)
+
+    if pdf:
+        generate_pdf_report(all_results, pdf)
+    return findings
+
+
+def main(args: Optional[List[str]] = None, prog_name: Optional[str] = None):
     """Main entry point for the CLI"""
     try:
-        cli()
+        cli(args=args, prog_name=prog_name)
     except Exception as e:
         logger.error(f"Error: {str(e)}")
         sys.exit(1)
 
 
+def cargo_main(args: Optional[List[str]] = None):
+    """
+    Entry point for `cargo stylus-analyzer`
+
+    Cargo runs `cargo-stylus-analyzer stylus-analyzer <args>`, so the
+    subcommand name is dropped before parsing. The binary also works when
+    run directly.
+    """
+    args = list(sys.argv[1:] if args is None else args)
+    if args and args[0] == CARGO_SUBCOMMAND:
+        args = args[1:]
+    main(args, prog_name=f"cargo {CARGO_SUBCOMMAND}")
+
+
 if __name__ == "__main__":
     main()
 
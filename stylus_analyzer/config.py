@@ -0,0 +1,184 @@
+"""
+Project configuration for Stylus Analyzer, read from `.stylus-analyzer.toml`
+"""
+import os
+from dataclasses import dataclass, field
+from typing import Any, Dict, List, Optional
+
+try:
+    import tomllib
+except ImportError:  # Python < 3.11
+    import tomli as tomllib
+
+from stylus_analyzer.findings import Severity
+
+CONFIG_FILE_NAME = ".stylus-analyzer.toml"
+
+
+class ConfigError(ValueError):
+    """Raised for configuration files or options that can't be applied"""
+
+
+@dataclass
+class Config:
+    """
+    Which detectors run and at which severity.
+
+    `enabled`, when set, is the only set of detectors that run. Detectors in
+    `disabled` never run, and `severity_overrides` replaces the severity of
+    every finding of a detector. `selected`, set by `--detectors`, replaces
+    the `enabled` list of the configs it is merged over, and its detectors
+    run even if those configs disable them. `options` holds the constructor
+    options of each detector, from the `[options.<detector id>]` tables.
+    """
+    enabled: Optional[List[str]] = None
+    disabled: List[str] = field(default_factory=list)
+    selected: Optional[List[str]] = None
+    severity_overrides: Dict[str, Severity] = field(default_factory=dict)
+    options: Dict[str, Dict[str, Any]] = field(default_factory=dict)
+    path: Optional[str] = None
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any], path: Optional[str] = None) -> "Config":
+        """
+        Build a config from parsed TOML data
+
+        Raises:
+            ConfigError: If a section or value has the wrong shape
+        """
+        source = path or "configuration"
+        unknown = set(data) - {"detectors", "severity", "options"}
+        if unknown:
+            raise ConfigError(f"{source}: unknown section(s) {', '.join(sorted(unknown))}")
+
+        detectors = data.get("detectors", {})
+        if not isinstance(detectors, dict):
+            raise ConfigError(f"{source}: [detectors] must be a table")
+        unknown = set(detectors) - {"enabled", "disabled"}
+        if unknown:
+            raise ConfigError(f"{source}: unknown key(s) in [detectors]: {', '.join(sorted(unknown))}")
+        enabled = detectors.get("enabled")
+        disabled = detectors.get("disabled", [])
+        for key, value in (("enabled", enabled), ("disabled", disabled)):
+            if value is not None and (not isinstance(value, list) or
+                                      not all(isinstance(item, str) for item in value)):
+                raise ConfigError(f"{source}: detectors.{key} must be a list of detector ids")
+
+        severities = data.get("severity", {})
+        if not isinstance(severities, dict):
+            raise ConfigError(f"{source}: [severity] must be a table of detector ids to severities")
+        overrides = {}
+        for detector_id, value in severities.items():
+            try:
+                overrides[detector_id] = Severity.parse(str(value))
+            except ValueError:
+                raise ConfigError(f"{source}: invalid severity '{value}' for detector '{detector_id}'") from None
+
+        options = data.get("options", {})
+        if not isinstance(options, dict) or not all(isinstance(value, dict) for value in options.values()):
+            raise ConfigError(f"{source}: [options] must hold one table of options per detector id, "
+                              f"e.g. [options.fallback_with_logic]")
+
+        return cls(enabled=enabled, disabled=disabled, severity_overrides=overrides, options=options, path=path)
+
+    def merge(self, override: "Config") -> "Config":
+        """
+        Combine this config with one taking precedence, e.g. CLI flags over the config file
+
+        Detectors the override enables run even if this config disables them or
+        leaves them out of its `enabled` list, and detectors it disables never run.
+        When the override selects detectors, only those and the ones it enables run.
+        """
+        base = override.selected if override.selected is not None else self.active_enabled()
+        enabled = None
+        if base is not None:
+            enabled = base + [detector_id for detector_id in override.enabled or [] if detector_id not in base]
+        force_enabled = (override.selected or []) + (override.enabled or [])
+        disabled = [detector_id for detector_id in self.disabled if detector_id not in force_enabled]
+        disabled += [detector_id for detector_id in override.disabled if detector_id not in disabled]
+        return Config(
+            enabled=enabled,
+            disabled=disabled,
+            severity_overrides={**self.severity_overrides, **override.severity_overrides},
+            options={detector_id: {**self.options.get(detector_id, {}), **override.options.get(detector_id, {})}
+                     for detector_id in {**self.options, **override.options}},
+            path=override.path or self.path
+        )
+
+    def validate(self, known_ids: List[str]) -> None:
+        """
+        Check that every detector id the config names exists
+
+        Raises:
+            ConfigError: Listing the unknown ids
+        """
+        referenced = list(self.enabled or []) + list(self.disabled) + list(self.selected or []) + \
+            list(self.severity_overrides) + list(self.options)
+        unknown = sorted({detector_id for detector_id in referenced if detector_id not in known_ids})
+        if unknown:
+            source = f" in {self.path}" if self.path else ""
+            raise ConfigError(f"Unknown detector id(s){source}: {', '.join(unknown)}. "
+                              f"Valid ids: {', '.join(sorted(known_ids))}.")
+
+    def active_enabled(self) -> Optional[List[str]]:
+        """The detectors allowed to run before `disabled` is applied, or None for all of them"""
+        return self.selected if self.selected is not None else self.enabled
+
+    def apply(self, registry) -> None:
+        """
+        Configure, enable, disable and re-rate the detectors of a registry
+
+        Raises:
+            ConfigError: If the config names a detector that isn't registered,
+                or an option the detector doesn't have
+        """
+        self.validate(registry.ids())
+        for detector_id, options in self.options.items():
+            try:
+                registry.configure(detector_id, options)
+            except ValueError as e:
+                raise ConfigError(f"{self.path}: {e}" if self.path else str(e)) from None
+        enabled = self.active_enabled()
+        for detector_id in registry.ids():
+            if (enabled is None or detector_id in enabled) and detector_id not in self.disabled:
+                registry.enable(detector_id)
+            else:
+                registry.disable(detector_id)
+        for detector_id, severity in self.severity_overrides.items():
+            registry.override_severity(detector_id, severity)
+
+
+def find_config_file(start_path: str) -> Optional[str]:
+    """Search a path and its parent directories for `.stylus-analyzer.toml`"""
+    directory = os.path.abspath(start_path)
+    if not os.path.isdir(directory):
+        directory = os.path.dirname(directory)
+    while True:
+        candidate = os.path.join(directory, CONFIG_FILE_NAME)
+        if os.path.isfile(candidate):
+            return candidate
+        parent = os.path.dirname(directory)
+        if parent == directory:
+            return None
+        directory = parent
+
+
+def load_config(path: str) -> Config:
+    """
+    Load a config file
+
+    Raises:
+        ConfigError: If the file isn't valid TOML or doesn't describe a config
+    """
+    try:
+        with open(path, 'rb') as f:
+            data = tomllib.load(f)
+    except tomllib.TOMLDecodeError as e:
+        raise ConfigError(f"{path}: invalid TOML: {e}") from None
+    return Config.from_dict(data, path)
+
+
+def load_config_for(target: str) -> Config:
+    """Load the config file governing an analyzed path, or an empty config if there is none"""
+    path = find_config_file(target)
+    return load_config(path) if path else Config()
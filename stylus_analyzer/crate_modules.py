@@ -0,0 +1,303 @@
+"""
+Resolution of the modules of a crate, so contracts split across files can be joined up
+"""
+import dataclasses
+import os
+from dataclasses import dataclass, field
+from typing import Dict, List, Optional, Set, Tuple
+
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.ast_utils import iter_nodes, node_text
+from stylus_analyzer.file_utils import generate_rust_ast, read_file_content
+from stylus_analyzer.storage_layout import StorageStruct, collect_storage_layout
+
+MANIFEST_NAME = "Cargo.toml"
+
+# Files a crate's module tree starts at, relative to its manifest
+CRATE_ROOT_FILES = (os.path.join("src", "lib.rs"), os.path.join("src", "main.rs"))
+
+# Imports followed through re-exports before giving up, guarding against `use` cycles
+MAX_IMPORT_DEPTH = 16
+
+# Parsed module files by path, with the modification time and size they were parsed at
+_parsed_files: Dict[str, Tuple[Tuple[int, int], Optional[str], Optional[Tree]]] = {}
+
+
+@dataclass
+class Module:
+    """
+    A module of a crate: a file, or an inline `mod name { ... }` inside one.
+
+    `items` is the node holding the module's items, the file's root or the
+    inline module's body, and `directory` is where the files of its
+    `mod child;` declarations live.
+    """
+    path: Tuple[str, ...]
+    file: str
+    code: str
+    items: Node
+    directory: str
+    parent: Optional["Module"] = None
+    children: Dict[str, "Module"] = field(default_factory=dict)
+
+    @property
+    def name(self) -> str:
+        """The module path as written in Rust, e.g. `crate::state`"""
+        return "::".join(self.path)
+
+
+class CrateModules:
+    """
+    The module tree of a crate, built by following `mod` declarations from
+    `src/lib.rs` or `src/main.rs`.
+
+    `mod name;` is looked up in `name.rs` and `name/mod.rs` next to the
+    declaring module, inline modules become modules of their own, and
+    names are resolved through `use` imports, `pub use` re-exports and
+    `use path::*` globs, relative to `crate`, `self`, `super` or the
+    importing module. Paths to other crates resolve to nothing.
+    """
+
+    def __init__(self, root_file: str, sources: Optional[Dict[str, str]] = None):
+        """
+        Args:
+            root_file: The crate root, `src/lib.rs` or `src/main.rs`
+            sources: Code of files to use instead of reading them, by path, e.g. unsaved editor buffers
+        """
+        self.sources = {os.path.abspath(path): code for path, code in (sources or {}).items()}
+        self.modules: Dict[str, Module] = {}
+        self.root = self._load_module(("crate",), os.path.abspath(root_file), None, set())
+
+    @classmethod
+    def for_file(cls, file_path: str, sources: Optional[Dict[str, str]] = None) -> Optional["CrateModules"]:
+        """Get the module tree of the crate a file belongs to, or None if it isn't part of one"""
+        directory = os.path.dirname(os.path.abspath(file_path))
+        while True:
+            if os.path.isfile(os.path.join(directory, MANIFEST_NAME)):
+                for root_file in CRATE_ROOT_FILES:
+                    if os.path.isfile(os.path.join(directory, root_file)):
+                        crate = cls(os.path.join(directory, root_file), sources)
+                        return crate if crate.root is not None else None
+                return None
+            parent = os.path.dirname(directory)
+            if parent == directory:
+                return None
+            directory = parent
+
+    def module_of(self, file_path: str) -> Optional[Module]:
+        """Get the module a file defines, or None if no `mod` declaration reaches it"""
+        return self.modules.get(os.path.abspath(file_path))
+
+    def resolve(self, module: Module, path: List[str], depth: int = 0) -> List[Tuple[Module, str]]:
+        """
+        Resolve a path as written in a module to the modules declaring its last segment
+
+        Returns:
+            Each candidate module and the name the item is declared under there,
+            following imports and re-exports, nearest first
+        """
+        if depth > MAX_IMPORT_DEPTH or not path:
+            return []
+        scope = self._resolve_module(module, path[:-1], depth)
+        if scope is None:
+            return []
+        name = path[-1]
+        candidates = [(scope, name)]
+        imports, globs = self._collect_imports(scope)
+        if name in imports:
+            candidates.extend(self.resolve(scope, imports[name], depth + 1))
+        for glob_path in globs:
+            target = self._resolve_module(scope, glob_path, depth + 1)
+            if target is not None and target is not scope:
+                candidates.extend(self.resolve(target, [name], depth + 1))
+        return candidates
+
+    def _resolve_module(self, module: Module, path: List[str], depth: int) -> Optional[Module]:
+        """Follow a module path such as `crate::state` or `super::token` from a module"""
+        current: Optional[Module] = module
+        for index, segment in enumerate(path):
+            if current is None:
+                return None
+            if segment == "crate" and index == 0:
+                current = self.root
+            elif segment == "self" and index == 0:
+                continue
+            elif segment == "super":
+                current = current.parent
+            elif segment in current.children:
+                current = current.children[segment]
+            else:
+                # A module imported under this name, e.g. `use crate::state; state::Token`
+                imports, _ = self._collect_imports(current)
+                if segment not in imports or depth > MAX_IMPORT_DEPTH:
+                    return None
+                current = self._resolve_module(current, imports[segment], depth + 1)
+        return current
+
+    def _collect_imports(self, module: Module) -> Tuple[Dict[str, List[str]], List[List[str]]]:
+        """Map the names a module's `use` declarations bring into scope to their path, with its glob imports"""
+        imports: Dict[str, List[str]] = {}
+        globs: List[List[str]] = []
+        for item in module.items.named_children:
+            argument = item.child_by_field_name("argument") if item.type == "use_declaration" else None
+            if argument is not None:
+                self._collect_use_clause(argument, [], module.code, imports, globs)
+        return imports, globs
+
+    def _collect_use_clause(self, clause: Node, prefix: List[str], code: str, imports: Dict[str, List[str]],
+                            globs: List[List[str]]) -> None:
+        """Collect the names of one clause of a `use` declaration, e.g. `state::{Token, Vault as Store}`"""
+        if clause.type == "use_list":
+            for child in clause.named_children:
+                self._collect_use_clause(child, prefix, code, imports, globs)
+        elif clause.type == "scoped_use_list":
+            path = clause.child_by_field_name("path")
+            scope = prefix + (_split_path(node_text(path, code)) if path is not None else [])
+            self._collect_use_clause(clause.child_by_field_name("list"), scope, code, imports, globs)
+        elif clause.type == "use_wildcard":
+            path = clause.named_children[0] if clause.named_children else None
+            globs.append(prefix + (_split_path(node_text(path, code)) if path is not None else []))
+        elif clause.type == "use_as_clause":
+            path = prefix + _split_path(node_text(clause.child_by_field_name("path"), code))
+            alias = node_text(clause.child_by_field_name("alias"), code)
+            if alias != "_":
+                imports[alias] = path
+        elif clause.type in ("identifier", "scoped_identifier", "crate", "self", "super"):
+            path = prefix + _split_path(node_text(clause, code))
+            # `use state::{self}` imports the module itself
+            if path[-1] == "self" and len(path) > 1:
+                path = path[:-1]
+            imports[path[-1]] = path
+
+    def _load_module(self, path: Tuple[str, ...], file_path: str, parent: Optional[Module],
+                     loading: Set[str]) -> Optional[Module]:
+        """Parse a module file and the modules it declares"""
+        if file_path in loading:
+            return None
+        if file_path in self.sources:
+            code = self.sources[file_path]
+            tree = generate_rust_ast(code)
+        else:
+            code, tree = _parse_file(file_path)
+        if tree is None:
+            return None
+        base = os.path.dirname(file_path)
+        stem = os.path.splitext(os.path.basename(file_path))[0]
+        # `lib.rs`, `main.rs` and `mod.rs` declare their modules next to them, `a.rs` in `a/`
+        directory = base if parent is None or stem == "mod" else os.path.join(base, stem)
+        module = Module(path=path, file=file_path, code=code, items=tree.root_node, directory=directory,
+                        parent=parent)
+        self.modules[file_path] = module
+        self._load_children(module, loading | {file_path})
+        return module
+
+    def _load_children(self, module: Module, loading: Set[str]) -> None:
+        """Load the modules declared by `mod` items of a module"""
+        for item in module.items.named_children:
+            if item.type != "mod_item" or item.child_by_field_name("name") is None:
+                continue
+            name = node_text(item.child_by_field_name("name"), module.code)
+            body = item.child_by_field_name("body")
+            if body is not None:
+                child = Module(path=module.path + (name,), file=module.file, code=module.code, items=body,
+                               directory=os.path.join(module.directory, name), parent=module)
+                self._load_children(child, loading)
+            else:
+                child = None
+                for candidate in (os.path.join(module.directory, f"{name}.rs"),
+                                  os.path.join(module.directory, name, "mod.rs")):
+                    if os.path.isfile(candidate) or candidate in self.sources:
+                        child = self._load_module(module.path + (name,), candidate, module, loading)
+                        break
+            if child is not None:
+                module.children[name] = child
+
+
+def _parse_file(file_path: str) -> Tuple[Optional[str], Optional[Tree]]:
+    """Read and parse a file, reusing the tree of an earlier call while the file is unchanged"""
+    try:
+        stat = os.stat(file_path)
+    except OSError:
+        return None, None
+    version = (stat.st_mtime_ns, stat.st_size)
+    cached = _parsed_files.get(file_path)
+    if cached is not None and cached[0] == version:
+        return cached[1], cached[2]
+    code = read_file_content(file_path)
+    tree = generate_rust_ast(code) if code is not None else None
+    _parsed_files[file_path] = (version, code, tree)
+    return code, tree
+
+
+def resolve_imported_structs(file_path: str, root: Node, code: str,
+                             crate: Optional[CrateModules] = None) -> List[StorageStruct]:
+    """
+    Find the storage structs declared in other files of the crate that a file implements
+
+    Each `impl Name` of the file whose `Name` isn't a storage struct of the
+    file itself is resolved through the file's imports, e.g. `impl Token`
+    after `use crate::state::Token`. The structs are returned under the name
+    the file uses for them, with `file` set to the file declaring them.
+
+    Args:
+        file_path: The file being analyzed
+        root: The root of its syntax tree
+        code: Its source code
+        crate: The crate's module tree, found from `file_path` when not given
+    """
+    local = {struct.name for struct in collect_storage_layout(root, code).structs}
+    implemented = []
+    for node in iter_nodes(root):
+        impl_type = node.child_by_field_name("type") if node.type == "impl_item" else None
+        if impl_type is not None and node.child_by_field_name("trait") is None:
+            # `impl<T> Token<T>` implements `Token`
+            name = node_text(impl_type, code).split("<")[0].strip()
+            if _split_path(name)[-1] not in local and name not in implemented:
+                implemented.append(name)
+    if not implemented:
+        return []
+    crate = crate or CrateModules.for_file(file_path, {file_path: code})
+    module = crate.module_of(file_path) if crate is not None else None
+    if module is None:
+        return []
+
+    structs = []
+    for name in implemented:
+        for candidate, declared_name in crate.resolve(module, _split_path(name)):
+            if candidate.file == module.file:
+                continue
+            struct = _get_declared_struct(candidate, declared_name)
+            if struct is not None:
+                structs.append(dataclasses.replace(struct, name=_split_path(name)[-1], file=candidate.file))
+                break
+    return structs
+
+
+def _get_declared_struct(module: Module, name: str) -> Optional[StorageStruct]:
+    """Get a storage struct declared directly in a module, not in one of its inline modules"""
+    for struct in collect_storage_layout(module.items, module.code).structs:
+        if struct.name == name and _enclosing_items(struct.node) == module.items:
+            return struct
+    return None
+
+
+def _enclosing_items(node: Node) -> Optional[Node]:
+    """Get the file root or inline module body an item is declared in"""
+    current = node.parent
+    while current is not None and current.type not in ("source_file", "declaration_list"):
+        current = current.parent
+    return current
+
+
+def _split_path(text: str) -> List[str]:
+    """Split a path such as `crate::state::Token` into its segments"""
+    return [segment.strip() for segment in text.split("::") if segment.strip()]
+
+
+def storage_dependencies(file_path: str, code: str) -> List[str]:
+    """Get the other files of the crate whose storage structs the analysis of a file depends on"""
+    tree = generate_rust_ast(code)
+    if tree is None:
+        return []
+    return sorted({struct.file for struct in resolve_imported_structs(file_path, tree.root_node, code)})
@@ -15,6 +15,63 @@ from stylus_analyzer.detectors.unwrap_detector import UnwrapDetector
 from stylus_analyzer.detectors.panic_detector import PanicDetector
 from stylus_analyzer.detectors.encode_packed_detector import EncodePackedDetector
 from stylus_analyzer.detectors.locked_ether_detector import LockedEtherDetector
+from stylus_analyzer.detectors.reentrancy_detector import ReentrancyDetector
+from stylus_analyzer.detectors.unchecked_arithmetic_detector import UncheckedArithmeticDetector
+from stylus_analyzer.detectors.tx_origin_auth_detector import TxOriginAuthDetector
+from stylus_analyzer.detectors.missing_access_control_detector import MissingAccessControlDetector
+from stylus_analyzer.detectors.unsafe_cast_detector import UnsafeCastTruncationDetector
+from stylus_analyzer.detectors.storage_write_in_loop_detector import StorageWriteInLoopDetector
+from stylus_analyzer.detectors.length_in_loop_condition_detector import ArrayLengthInLoopConditionDetector
+from stylus_analyzer.detectors.redundant_storage_read_detector import RedundantStorageReadDetector
+from stylus_analyzer.detectors.missing_event_detector import MissingEventOnStateChangeDetector
+from stylus_analyzer.detectors.division_before_multiplication_detector import DivisionBeforeMultiplicationDetector
+from stylus_analyzer.detectors.unbounded_loop_detector import UnboundedLoopOverCallerInputDetector
+from stylus_analyzer.detectors.zero_address_check_detector import ZeroAddressCheckDetector
+from stylus_analyzer.detectors.block_timestamp_detector import BlockTimestampDependenceDetector
+from stylus_analyzer.detectors.weak_randomness_detector import WeakRandomnessDetector
+from stylus_analyzer.detectors.self_transfer_detector import SelfTransferNoOpDetector
+from stylus_analyzer.detectors.approve_race_detector import ApproveRaceConditionDetector
+from stylus_analyzer.detectors.unchecked_low_level_call_detector import UncheckedLowLevelCallDetector
+from stylus_analyzer.detectors.delegatecall_untrusted_detector import DelegateCallToUntrustedDetector
+from stylus_analyzer.detectors.msg_value_non_payable_detector import MsgValueWithoutPayableDetector
+from stylus_analyzer.detectors.panic_in_loop_detector import PanicInLoopDetector
+from stylus_analyzer.detectors.expect_detector import ExpectDetector
+from stylus_analyzer.detectors.constructor_overflow_detector import IntegerOverflowInConstructorDetector
+from stylus_analyzer.detectors.uninitialized_storage_detector import UninitializedStorageFieldDetector
+from stylus_analyzer.detectors.missing_owner_init_detector import MissingOwnerInitializationDetector
+from stylus_analyzer.detectors.duplicate_function_detector import DuplicateFunctionDefinitionDetector
+from stylus_analyzer.detectors.selector_collision_detector import SelectorCollisionDetector
+from stylus_analyzer.detectors.hardcoded_address_detector import HardcodedAddressDetector
+from stylus_analyzer.detectors.shadowed_storage_field_detector import ShadowedStorageFieldDetector
+from stylus_analyzer.detectors.clone_in_loop_detector import CloneInLoopDetector
+from stylus_analyzer.detectors.string_format_panic_detector import StringFormatPanicDetector
+from stylus_analyzer.detectors.receive_fallback_locked_ether_detector import ReceiveFallbackPayableNoWithdrawDetector
+from stylus_analyzer.detectors.outbound_transfer_gating_detector import OutboundTransferGatingDetector
+from stylus_analyzer.detectors.constant_suggestion_detector import ConstantImmutableSuggestionDetector
+from stylus_analyzer.detectors.public_could_be_external_detector import PublicFunctionCouldBeExternalDetector
+from stylus_analyzer.detectors.dead_code_after_revert_detector import DeadCodeAfterRevertDetector
+from stylus_analyzer.detectors.ignored_result_detector import IgnoredResultDetector
+from stylus_analyzer.detectors.empty_catch_arm_detector import EmptyCatchArmDetector
+from stylus_analyzer.detectors.msg_sender_in_view_detector import MsgSenderInViewDetector
+from stylus_analyzer.detectors.unused_storage_field_detector import UnusedStorageFieldDetector
+from stylus_analyzer.detectors.inconsistent_decimals_detector import InconsistentDecimalsMathDetector
+from stylus_analyzer.detectors.require_without_message_detector import RequireWithoutMessageDetector
+from stylus_analyzer.detectors.interface_bool_return_detector import MissingReturnBoolCheckOnInterfaceDetector
+from stylus_analyzer.detectors.unreleased_guard_detector import UnreleasedReentrancyGuardDetector
+from stylus_analyzer.detectors.payable_constructor_detector import PayableConstructorMismatchDetector
+from stylus_analyzer.detectors.require_after_state_change_detector import RequireAfterStateChangeDetector
+from stylus_analyzer.detectors.mapping_default_detector import MappingDefaultAssumptionDetector
+from stylus_analyzer.detectors.keccak_encode_packed_detector import KeccakOnEncodePackedDynamicDetector
+from stylus_analyzer.detectors.unvalidated_decode_detector import UnvalidatedExternalDataDecodeDetector
+from stylus_analyzer.detectors.fallback_logic_detector import FallbackWithLogicDetector
+from stylus_analyzer.detectors.integer_underflow_detector import IntegerUnderflowOnSubtractionDetector
+from stylus_analyzer.detectors.constructor_reentry_detector import ConstructorReentryDetector
+from stylus_analyzer.detectors.lossy_address_detector import LossyU256ToAddressDetector
+from stylus_analyzer.detectors.missing_reentrancy_guard_detector import PublicMutWithoutReentrancyGuardDetector
+from stylus_analyzer.detectors.transfer_amount_check_detector import MissingRequireOnTransferAmountDetector
+from stylus_analyzer.detectors.hardcoded_gas_detector import HardcodedGasOrValueInCallDetector
+from stylus_analyzer.detectors.signed_unsigned_comparison_detector import SignedUnsignedComparisonDetector
+from stylus_analyzer.detectors.overpowered_owner_detector import OverpoweredOwnerDetector
 
 # Logger for this module
 logger = logging.getLogger(__name__)
@@ -25,7 +82,64 @@ AVAILABLE_DETECTORS = [
     UnwrapDetector,
     PanicDetector,
     EncodePackedDetector,
-    LockedEtherDetector
+    LockedEtherDetector,
+    ReentrancyDetector,
+    UncheckedArithmeticDetector,
+    TxOriginAuthDetector,
+    MissingAccessControlDetector,
+    UnsafeCastTruncationDetector,
+    StorageWriteInLoopDetector,
+    ArrayLengthInLoopConditionDetector,
+    RedundantStorageReadDetector,
+    MissingEventOnStateChangeDetector,
+    DivisionBeforeMultiplicationDetector,
+    UnboundedLoopOverCallerInputDetector,
+    ZeroAddressCheckDetector,
+    BlockTimestampDependenceDetector,
+    WeakRandomnessDetector,
+    SelfTransferNoOpDetector,
+    ApproveRaceConditionDetector,
+    UncheckedLowLevelCallDetector,
+    DelegateCallToUntrustedDetector,
+    MsgValueWithoutPayableDetector,
+    PanicInLoopDetector,
+    ExpectDetector,
+    IntegerOverflowInConstructorDetector,
+    UninitializedStorageFieldDetector,
+    MissingOwnerInitializationDetector,
+    DuplicateFunctionDefinitionDetector,
+    SelectorCollisionDetector,
+    HardcodedAddressDetector,
+    ShadowedStorageFieldDetector,
+    CloneInLoopDetector,
+    StringFormatPanicDetector,
+    ReceiveFallbackPayableNoWithdrawDetector,
+    OutboundTransferGatingDetector,
+    ConstantImmutableSuggestionDetector,
+    PublicFunctionCouldBeExternalDetector,
+    DeadCodeAfterRevertDetector,
+    IgnoredResultDetector,
+    EmptyCatchArmDetector,
+    MsgSenderInViewDetector,
+    UnusedStorageFieldDetector,
+    InconsistentDecimalsMathDetector,
+    RequireWithoutMessageDetector,
+    MissingReturnBoolCheckOnInterfaceDetector,
+    UnreleasedReentrancyGuardDetector,
+    PayableConstructorMismatchDetector,
+    RequireAfterStateChangeDetector,
+    MappingDefaultAssumptionDetector,
+    KeccakOnEncodePackedDynamicDetector,
+    UnvalidatedExternalDataDecodeDetector,
+    FallbackWithLogicDetector,
+    IntegerUnderflowOnSubtractionDetector,
+    ConstructorReentryDetector,
+    LossyU256ToAddressDetector,
+    PublicMutWithoutReentrancyGuardDetector,
+    MissingRequireOnTransferAmountDetector,
+    HardcodedGasOrValueInCallDetector,
+    SignedUnsignedComparisonDetector,
+    OverpoweredOwnerDetector
 ]
 
 def get_available_detectors() -> List[Type[BaseDetector]]:
@@ -0,0 +1,173 @@
+"""
+Detector for the ERC20 approve front-running race in Stylus Rust contracts
+"""
+import re
+from typing import Dict, Optional, Set
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# Keys that are the caller itself, e.g. `allowances[msg.sender][spender]`
+SENDER_KEY_PATTERN = re.compile(r"^(msg\s*::\s*sender\s*\(\s*\)|msg\s*\.\s*sender|.*msg_sender\s*\(\s*\))$")
+
+# Helpers that let holders adjust an allowance without overwriting it
+ALLOWANCE_HELPER_PATTERN = re.compile(r"(increase|decrease)_?allowance", re.IGNORECASE)
+
+# Guards requiring the previous or new allowance to be zero
+ZERO_GUARD_PATTERN = re.compile(r"==\s*(0\b|U256::ZERO)|is_zero\s*\(")
+
+
+class ApproveRaceConditionDetector(BaseDetector):
+    """
+    Detector for `approve` implementations that overwrite the allowance.
+
+    A spender watching the mempool can spend the old allowance before the
+    new one lands and then spend the new one too.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.MEDIUM
+    cwe = 362
+
+    documentation = DetectorDoc(
+        catches=(
+            "`approve` implementations overwriting an allowance without requiring the current or new value to be "
+            "zero, in contracts without `increaseAllowance`/`decreaseAllowance`."
+        ),
+        rationale=(
+            "A spender watching the mempool can spend the old allowance before the new one lands, and then spend "
+            "the new one as well."
+        ),
+        vulnerable_example="""
+            pub fn approve(&mut self, spender: Address, value: U256) -> bool {
+                self.allowances.setter(msg::sender()).setter(spender).set(value);
+                true
+            }
+        """,
+        fixed_example="""
+            pub fn approve(&mut self, spender: Address, value: U256) -> Result<bool, Vec<u8>> {
+                let current = self.allowances.getter(msg::sender()).get(spender);
+                if current != U256::ZERO && value != U256::ZERO {
+                    return Err(b"reset the allowance to zero first".to_vec());
+                }
+                self.allowances.setter(msg::sender()).setter(spender).set(value);
+                Ok(true)
+            }
+        """,
+        references=["https://eips.ethereum.org/EIPS/eip-20"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="approve_race_condition",
+            description="Detects approve functions that overwrite allowances without race protection"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect direct allowance overwrites"""
+        if ALLOWANCE_HELPER_PATTERN.search(code):
+            return
+        for function_node in self._iter_nodes(tree.root_node):
+            if function_node.type == "function_item":
+                self._check_function(function_node, code, results)
+
+        for function in self._get_sol_functions(tree.root_node, code):
+            self._check_sol_function(function, code, results)
+
+    def _check_function(self, function_node: Node, code: str, results) -> None:
+        """Check a Rust function for `self.<map>.setter(sender).setter(spender).set(value)`"""
+        body = function_node.child_by_field_name("body")
+        if not body:
+            return
+        parameters = self._collect_parameter_names(function_node, code)
+        sender_aliases = self._collect_sender_aliases(body, code)
+        for node in self._iter_nodes(body):
+            guard = self._get_guard_text(node, code)
+            if guard is not None and ZERO_GUARD_PATTERN.search(guard):
+                return
+            overwrite = self._get_allowance_overwrite(node, code, parameters, sender_aliases)
+            if overwrite:
+                field, spender = overwrite
+                line_start, line_end = self._get_line_for_node(node)
+                self._add_issue(line_start, line_end, self._get_column_for_node(node, code),
+                                self._get_node_text(node, code), field, spender,
+                                f"function '{self._get_function_name(function_node, code)}'", results)
+                return
+
+    def _check_sol_function(self, function: Dict, code: str, results) -> None:
+        """Check a sol! function for `allowance[msg.sender][spender] = value`"""
+        parameters = self._get_sol_parameter_names(function["params"], code)
+        for statement in self._split_sol_statements(function["body"]):
+            text = self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte)
+            if self._get_node_text(statement[0], code) in ("require", "if"):
+                if ZERO_GUARD_PATTERN.search(text):
+                    return
+                continue
+            if len(statement) < 4 or statement[0].type != "identifier" or statement[3].type != "=":
+                continue
+            owner, spender = statement[1], statement[2]
+            if not self._is_token_tree(owner, "[") or not self._is_token_tree(spender, "["):
+                continue
+            owner_text = self._get_node_text(owner, code)[1:-1].strip()
+            spender_text = self._get_node_text(spender, code)[1:-1].strip()
+            if SENDER_KEY_PATTERN.match(owner_text) and spender_text in parameters:
+                line_start, _ = self._get_line_for_node(statement[0])
+                _, line_end = self._get_line_for_node(statement[-1])
+                self._add_issue(line_start, line_end, self._get_column_for_node(statement[0], code), text,
+                                self._get_node_text(statement[0], code), spender_text, f"sol! function '{function['name']}'", results)
+                return
+
+    def _get_call_parts(self, node: Optional[Node], code: str):
+        """Split a method call into its receiver, method name and arguments"""
+        if node is None or node.type != "call_expression":
+            return None, "", []
+        function = node.child_by_field_name("function")
+        arguments = node.child_by_field_name("arguments")
+        if not function or function.type != "field_expression" or not arguments:
+            return None, "", []
+        method = function.child_by_field_name("field")
+        return function.child_by_field_name("value"), self._get_node_text(method, code) if method else "", \
+            list(arguments.named_children)
+
+    def _get_allowance_overwrite(self, node: Node, code: str, parameters: Set[str],
+                                 sender_aliases: Set[str]) -> Optional[tuple]:
+        """Get the field and spender of a nested map entry keyed by the sender that is overwritten"""
+        receiver, method, arguments = self._get_call_parts(node, code)
+        if method == "set":
+            inner, inner_method, inner_arguments = self._get_call_parts(receiver, code)
+            if inner_method != "setter" or len(inner_arguments) != 1:
+                return None
+            spender = inner_arguments[0]
+            owner_call = inner
+        elif method == "insert" and len(arguments) == 2:
+            spender = arguments[0]
+            owner_call = receiver
+        else:
+            return None
+
+        base, owner_method, owner_arguments = self._get_call_parts(owner_call, code)
+        if owner_method != "setter" or len(owner_arguments) != 1 or base is None:
+            return None
+        owner_text = self._get_node_text(owner_arguments[0], code)
+        if not (SENDER_KEY_PATTERN.match(owner_text) or owner_text in sender_aliases):
+            return None
+        spender_text = self._get_node_text(spender, code)
+        field = self._get_self_field(base, code)
+        if not field or spender_text not in parameters:
+            return None
+        return field, spender_text
+
+    def _add_issue(self, line_start: int, line_end: int, column: int, snippet: str, field: str, spender: str,
+                   location: str, results) -> None:
+        """Add an approve race condition issue"""
+        results.add_issue(
+            issue_type="approve_race_condition",
+            severity="Low",
+            description=f"In {location}, the allowance '{field}' for '{spender}' is overwritten directly. The spender can front-run the change and spend both the old and the new allowance.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation="Add increaseAllowance/decreaseAllowance helpers, or require that either the current allowance or the new value is zero."
+        )
@@ -0,0 +1,131 @@
+"""
+Detector for control flow depending on the block timestamp in Stylus Rust contracts
+"""
+import re
+from typing import Optional, Set
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# Block timestamp accessors of the Stylus SDK and Solidity
+TIMESTAMP_PATTERN = re.compile(r"\bblock\s*::\s*timestamp\s*\(|\bblock_timestamp\s*\(")
+SOL_TIMESTAMP_PATTERN = re.compile(r"\bblock\s*\.\s*timestamp\b|\bnow\b")
+
+
+class BlockTimestampDependenceDetector(BaseDetector):
+    """
+    Detector for `block::timestamp()` flowing into an `if`, guard macro or
+    `match` discriminant.
+
+    The sequencer chooses the block timestamp within bounds, so deadlines,
+    unlocks and anything derived from the timestamp can be nudged. Logging or
+    returning the timestamp is not reported.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.LOW
+    cwe = 829
+    node_types = ("if_expression", "while_expression", "match_expression", "macro_invocation")
+
+    documentation = DetectorDoc(
+        catches="`block::timestamp()` or `block.timestamp` flowing into a condition, guard macro or `match`.",
+        rationale=(
+            "The sequencer can influence the timestamp within bounds, so deadlines and unlocks that depend on it "
+            "precisely can be nudged."
+        ),
+        vulnerable_example="""
+            if block::timestamp() % 15 == 0 {
+                self.winner.set(msg::sender());
+            }
+        """,
+        fixed_example="""
+            // Only coarse-grained deadlines rely on the timestamp
+            if block::timestamp() >= self.unlock_time.get().to::<u64>() + ONE_DAY {
+                self.unlocked.set(true);
+            }
+        """,
+        references=["https://swcregistry.io/docs/SWC-116"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="block_timestamp_dependence",
+            description="Detects control flow that depends on the block timestamp"
+        )
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if a condition in the body of a function depends on the timestamp"""
+        condition = self._get_condition(node, code)
+        function_node = self._find_parent_function(node) if condition is not None else None
+        body = function_node.child_by_field_name("body") if function_node is not None else None
+        if body is None or node.start_byte < body.start_byte:
+            return
+        aliases = self._get_function_fact(f"{self.id}.aliases", function_node, code,
+                                          lambda: self._collect_timestamp_aliases(body, code))
+        if not self._depends_on_timestamp(condition, code, aliases):
+            return
+        line_start, line_end = self._get_line_for_node(condition)
+        self._add_issue(line_start, line_end, self._get_column_for_node(condition, code),
+                        self._get_node_text(condition, code),
+                        f"function '{self._get_function_name(function_node, code)}'", results)
+
+    def finish(self, tree: Tree, code: str, results) -> None:
+        """Check the conditions of sol! function bodies"""
+        for function in self._get_sol_functions(tree.root_node, code):
+            for statement in self._split_sol_statements(function["body"]):
+                if self._get_node_text(statement[0], code) not in ("require", "if"):
+                    continue
+                text = self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte)
+                if SOL_TIMESTAMP_PATTERN.search(text):
+                    line_start, _ = self._get_line_for_node(statement[0])
+                    _, line_end = self._get_line_for_node(statement[-1])
+                    self._add_issue(line_start, line_end, self._get_column_for_node(statement[0], code), text,
+                                    f"sol! function '{function['name']}'", results)
+
+    def _get_condition(self, node: Node, code: str) -> Optional[Node]:
+        """Get the condition, guard macro or match discriminant a node branches on"""
+        if node.type in ("if_expression", "while_expression"):
+            return node.child_by_field_name("condition")
+        if node.type == "match_expression":
+            return node.child_by_field_name("value")
+        if node.type == "macro_invocation" and self._get_guard_text(node, code) is not None:
+            return node
+        return None
+
+    def _collect_timestamp_aliases(self, body: Node, code: str) -> Set[str]:
+        """Collect locals holding the timestamp or a value computed from it"""
+        aliases: Set[str] = set()
+        for node in self._iter_nodes(body):
+            if node.type != "let_declaration":
+                continue
+            pattern = node.child_by_field_name("pattern")
+            value = node.child_by_field_name("value")
+            if not pattern or not value:
+                continue
+            if pattern.type == "mut_pattern" and pattern.named_children:
+                pattern = pattern.named_children[-1]
+            if pattern.type == "identifier" and self._depends_on_timestamp(value, code, aliases):
+                aliases.add(self._get_node_text(pattern, code))
+        return aliases
+
+    def _depends_on_timestamp(self, node: Node, code: str, aliases: Set[str]) -> bool:
+        """Check if an expression reads the timestamp directly or through a local"""
+        text = self._get_node_text(node, code)
+        if TIMESTAMP_PATTERN.search(text):
+            return True
+        return any(re.search(rf"\b{re.escape(alias)}\b", text) for alias in aliases)
+
+    def _add_issue(self, line_start: int, line_end: int, column: int, snippet: str, location: str,
+                   results) -> None:
+        """Add a timestamp dependence issue"""
+        results.add_issue(
+            issue_type="block_timestamp_dependence",
+            severity="Low",
+            description=f"Control flow in {location} depends on the block timestamp. The sequencer can influence the timestamp within bounds.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation="Only rely on the timestamp for coarse-grained deadlines that tolerate a few seconds of drift, and never as a source of randomness."
+        )
@@ -0,0 +1,189 @@
+"""
+Detector for heap-allocating clones inside loops in Stylus Rust contracts
+"""
+import re
+from typing import Optional, Set
+from tree_sitter import Node
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# Methods that copy their receiver into a new heap allocation
+CLONING_METHODS = ("clone", "to_vec", "to_string")
+
+LOOP_TYPES = ("for_expression", "while_expression", "loop_expression")
+
+HEAP_TYPE_PATTERN = re.compile(r"\b(Vec|String|Bytes)\b|\bvec!")
+
+# Methods that change a value in place, making each iteration's copy distinct
+MUTATING_METHODS = ("push", "push_str", "extend", "extend_from_slice", "insert", "remove", "clear",
+                    "truncate", "append", "retain", "pop")
+
+
+class CloneInLoopDetector(BaseDetector):
+    """
+    Detector for `.clone()`, `.to_vec()` and `.to_string()` calls on `Vec`,
+    `String` and `Bytes` values inside loop bodies, including closures
+    nested in the body.
+
+    Each call allocates and copies on every iteration, which costs gas in a
+    Stylus contract. Copies of loop variables, of closure parameters, of
+    locals bound inside the loop and of values mutated inside the loop
+    differ between iterations and are not reported.
+    """
+
+    default_severity = Severity.INFO
+    default_confidence = Confidence.HIGH
+    node_types = ("call_expression",)
+
+    documentation = DetectorDoc(
+        catches="`.clone()`, `.to_vec()` and `.to_string()` on the same value inside a loop body.",
+        rationale="Every iteration allocates and copies the same value, spending gas for nothing.",
+        vulnerable_example="""
+            for recipient in recipients {
+                self.notify(recipient, data.clone());
+            }
+        """,
+        fixed_example="""
+            for recipient in recipients {
+                self.notify(recipient, &data);
+            }
+        """,
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="clone_in_loop",
+            description="Detects heap-allocating clones repeated on every loop iteration"
+        )
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if a call clones a loop-invariant heap value inside a loop"""
+        method = self._get_cloning_method(node, code)
+        if method is None:
+            return
+        loop = self._find_enclosing_loop(node)
+        function_node = self._find_parent_function(node)
+        if loop is None or function_node is None:
+            return
+        receiver = node.child_by_field_name("function").child_by_field_name("value")
+        if method == "clone" and not self._is_heap_value(receiver, function_node, code):
+            return
+        root_name = self._get_root_name(receiver, code)
+        if root_name and root_name in self._collect_iteration_names(loop, node, code):
+            return
+
+        function_name = self._get_function_name(function_node, code)
+        loop_line, _ = self._get_line_for_node(loop)
+        receiver_text = self._get_node_text(receiver, code)
+        line_start, line_end = self._get_line_for_node(node)
+        results.add_issue(
+            issue_type="clone_in_loop",
+            severity="Info",
+            description=f"'{receiver_text}.{method}()' inside the loop at line {loop_line} in function '{function_name}' allocates and copies the same value on every iteration.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=self._get_node_text(node, code),
+            recommendation=f"Hoist the copy out of the loop, e.g. `let copy = {receiver_text}.{method}();`, or borrow `&{receiver_text}` instead."
+        )
+
+    def _get_cloning_method(self, node: Node, code: str) -> Optional[str]:
+        """Get the name of a cloning method call without arguments"""
+        if node.type != "call_expression":
+            return None
+        function = node.child_by_field_name("function")
+        arguments = node.child_by_field_name("arguments")
+        if not function or function.type != "field_expression" or not arguments or arguments.named_children:
+            return None
+        method = function.child_by_field_name("field")
+        name = self._get_node_text(method, code) if method else ""
+        return name if name in CLONING_METHODS else None
+
+    def _find_enclosing_loop(self, node: Node) -> Optional[Node]:
+        """Find the innermost loop around a node, looking through closures"""
+        current = node.parent
+        while current is not None and current.type != "function_item":
+            if current.type in LOOP_TYPES:
+                return current
+            current = current.parent
+        return None
+
+    def _is_heap_value(self, receiver: Node, function_node: Node, code: str) -> bool:
+        """Check if a cloned value is a Vec, String or Bytes"""
+        if receiver.type == "string_literal":
+            return True
+        root_name = self._get_root_name(receiver, code)
+        if root_name is None or receiver.type != "identifier":
+            return False
+        type_text = self._collect_local_type_texts(function_node, code).get(root_name)
+        if type_text is not None:
+            return bool(HEAP_TYPE_PATTERN.search(type_text))
+        for node in self._iter_nodes(function_node):
+            if node.type != "let_declaration" or node.start_byte >= receiver.start_byte:
+                continue
+            if self._get_pattern_names(node.child_by_field_name("pattern"), code) == {root_name}:
+                value = node.child_by_field_name("value")
+                value_text = self._get_node_text(value, code) if value else ""
+                return bool(HEAP_TYPE_PATTERN.search(value_text) or re.search(r"\.(to_vec|to_string)\(\)", value_text)
+                            or value_text.startswith("format!"))
+        return False
+
+    def _get_root_name(self, node: Node, code: str) -> Optional[str]:
+        """Get the variable an expression such as `item.data[0]` starts from"""
+        current = node
+        while current is not None:
+            if current.type in ("identifier", "self"):
+                return self._get_node_text(current, code)
+            if current.type == "field_expression":
+                current = current.child_by_field_name("value")
+            elif current.type == "call_expression":
+                current = current.child_by_field_name("function")
+            elif current.type in ("index_expression", "reference_expression", "parenthesized_expression",
+                                  "unary_expression", "try_expression"):
+                current = current.named_children[0] if current.named_children else None
+            else:
+                return None
+        return None
+
+    def _collect_iteration_names(self, loop: Node, call: Node, code: str) -> Set[str]:
+        """Collect the names whose value can differ between iterations of a loop"""
+        names: Set[str] = set()
+        if loop.type == "for_expression":
+            names |= self._get_pattern_names(loop.child_by_field_name("pattern"), code)
+        body = loop.child_by_field_name("body")
+        for node in self._iter_nodes(body) if body else []:
+            if node.type == "let_declaration":
+                names |= self._get_pattern_names(node.child_by_field_name("pattern"), code)
+            elif node.type == "closure_expression" and \
+                    node.start_byte <= call.start_byte and call.end_byte <= node.end_byte:
+                parameters = node.child_by_field_name("parameters")
+                names |= self._get_pattern_names(parameters, code)
+            elif node.type in ("assignment_expression", "compound_assignment_expr"):
+                left = node.child_by_field_name("left")
+                name = self._get_root_name(left, code) if left else None
+                if name:
+                    names.add(name)
+            elif node.type == "call_expression":
+                function = node.child_by_field_name("function")
+                if function and function.type == "field_expression":
+                    method = function.child_by_field_name("field")
+                    if method and self._get_node_text(method, code) in MUTATING_METHODS:
+                        name = self._get_root_name(function.child_by_field_name("value"), code)
+                        if name and name != "self":
+                            names.add(name)
+        return names
+
+    def _get_pattern_names(self, pattern: Optional[Node], code: str) -> Set[str]:
+        """Collect the identifiers bound by a pattern or closure parameter list"""
+        if pattern is None:
+            return set()
+        if pattern.type == "identifier":
+            return {self._get_node_text(pattern, code)}
+        names = set()
+        for node in self._iter_nodes(pattern):
+            if node.type == "identifier" and (node.parent is None or node.parent.type != "scoped_identifier") and \
+                    not (node.parent and node.parent.type in ("tuple_struct_pattern", "struct_pattern") and
+                         node.start_byte == node.parent.start_byte):
+                names.add(self._get_node_text(node, code))
+        return names
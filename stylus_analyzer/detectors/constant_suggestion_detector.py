@@ -0,0 +1,151 @@
+"""
+Detector for storage fields that could be constants in Stylus Rust contracts
+"""
+from typing import Dict, List, Optional, Set
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc, STORAGE_WRITE_METHODS
+from stylus_analyzer.detectors.uninitialized_storage_detector import COLLECTION_TYPE_PATTERN, PLACEHOLDER_PATTERN
+from stylus_analyzer.findings import Confidence, Severity
+
+# Accessors handing out a mutable view of a field, treated as a possible write
+MUTABLE_ACCESSORS = ("setter", "get_mut")
+
+
+class ConstantImmutableSuggestionDetector(BaseDetector):
+    """
+    Detector for storage fields written exactly once, by the constructor, and
+    only read afterwards.
+
+    Every read of such a field is an SLOAD of a value that never changes.
+    Modeling it as a `const`, or a value baked in at deployment, avoids the
+    storage read. Fields written or mutably borrowed in any other method, and
+    collections, are not suggested.
+    """
+
+    default_severity = Severity.INFO
+    default_confidence = Confidence.MEDIUM
+
+    documentation = DetectorDoc(
+        catches="Storage fields written once, in the constructor, and read elsewhere.",
+        rationale=(
+            "Each read is an SLOAD of a value that never changes after deployment, which a constant would make "
+            "free."
+        ),
+        vulnerable_example="""
+            #[constructor]
+            pub fn constructor(&mut self) {
+                self.fee_basis_points.set(U256::from(30));
+            }
+
+            pub fn fee(&self, amount: U256) -> U256 {
+                amount * self.fee_basis_points.get() / U256::from(10_000)
+            }
+        """,
+        fixed_example="""
+            const FEE_BASIS_POINTS: U256 = U256::from_limbs([30, 0, 0, 0]);
+
+            pub fn fee(&self, amount: U256) -> U256 {
+                amount * FEE_BASIS_POINTS / U256::from(10_000)
+            }
+        """,
+        references=["https://github.com/crytic/slither/wiki/Detector-Documentation"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="storage_field_could_be_constant",
+            description="Detects storage fields only written once in the constructor"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect write-once storage fields of each storage struct"""
+        impls = self._collect_impl_methods(tree.root_node, code)
+        for struct in self._collect_storage_structs(tree.root_node, code):
+            methods = impls.get(struct["name"], {})
+            if not any(self._is_constructor(method, code) for method in methods.values()):
+                continue
+            constructor_writes: Dict[str, int] = {}
+            other_writes: Set[str] = set()
+            reads: Dict[str, int] = {}
+            for method in methods.values():
+                writes = self._count_writes(method, code)
+                if self._is_constructor(method, code):
+                    for field, count in writes.items():
+                        constructor_writes[field] = constructor_writes.get(field, 0) + count
+                else:
+                    other_writes.update(writes)
+                    for field, count in self._count_reads(method, code).items():
+                        reads[field] = reads.get(field, 0) + count
+
+            candidates = [field for field, field_type in struct["fields"].items()
+                          if constructor_writes.get(field) == 1 and field not in other_writes and
+                          reads.get(field) and not COLLECTION_TYPE_PATTERN.search(field_type)]
+            if not candidates:
+                continue
+
+            line_start, _ = self._get_line_for_node(struct["node"])
+            _, line_end = self._get_line_for_node(struct["body"])
+            listing = ", ".join(f"'{field}' ({reads[field]} read{'s' if reads[field] > 1 else ''})"
+                                for field in candidates)
+            results.add_issue(
+                issue_type="storage_field_could_be_constant",
+                severity="Info",
+                description=f"Storage struct '{struct['name']}' has fields that are only set once in the constructor and never written afterwards: {listing}. Each read costs an SLOAD for a value that never changes.",
+                line_start=line_start,
+                line_end=line_end,
+                column=self._get_column_for_node(struct["node"], code),
+                code_snippet=self._get_source_text(code, struct["node"].start_byte, struct["body"].start_byte).strip(),
+                recommendation="Replace these fields with constants, or values fixed at deployment, if they are never meant to change."
+            )
+
+    def _count_writes(self, method: Node, code: str) -> Dict[str, int]:
+        """Count the write sites of each field in a method, including struct literal initializers"""
+        counts: Dict[str, int] = {}
+        setter_aliases = self._collect_setter_aliases(method, code)
+        for node in self._iter_nodes(method):
+            fields: List[str] = []
+            field = self._get_storage_write_field(node, code, setter_aliases)
+            if field:
+                fields.append(field)
+            elif node.type == "field_initializer":
+                name = node.child_by_field_name("name")
+                value = node.child_by_field_name("value")
+                if name and value and not PLACEHOLDER_PATTERN.match(self._get_node_text(value, code)):
+                    fields.append(self._get_node_text(name, code))
+            elif node.type == "shorthand_field_initializer":
+                fields.append(self._get_node_text(node, code))
+            elif self._get_accessed_method(node, code) in MUTABLE_ACCESSORS:
+                fields.append(self._get_node_text(node.child_by_field_name("field"), code))
+            for field in fields:
+                counts[field] = counts.get(field, 0) + 1
+        return counts
+
+    def _count_reads(self, method: Node, code: str) -> Dict[str, int]:
+        """Count the `self.<field>` accesses in a method that don't write the field"""
+        counts: Dict[str, int] = {}
+        for node in self._iter_nodes(method):
+            accessed = self._get_accessed_method(node, code)
+            if accessed is None or accessed in STORAGE_WRITE_METHODS or accessed in MUTABLE_ACCESSORS:
+                continue
+            field = self._get_node_text(node.child_by_field_name("field"), code)
+            counts[field] = counts.get(field, 0) + 1
+        return counts
+
+    def _get_accessed_method(self, node: Node, code: str) -> Optional[str]:
+        """For a `self.<field>` node, get the method called on it, or "" when the field is used directly"""
+        if node.type != "field_expression":
+            return None
+        value = node.child_by_field_name("value")
+        if value is None or value.type != "self" or node.child_by_field_name("field") is None:
+            return None
+        parent = node.parent
+        if parent is not None and parent.type == "field_expression" and \
+                parent.child_by_field_name("value").start_byte == node.start_byte:
+            method = parent.child_by_field_name("field")
+            return self._get_node_text(method, code) if method else ""
+        if parent is not None and parent.type in ("assignment_expression", "compound_assignment_expr") and \
+                parent.child_by_field_name("left").start_byte == node.start_byte:
+            # Counted as a write by the assignment itself
+            return "set"
+        return ""
@@ -0,0 +1,239 @@
+"""
+Detector for overflowing arithmetic on constructor parameters in Stylus Rust contracts
+"""
+import re
+from typing import Dict, List, Optional, Set, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# sol! functions treated as constructors or one-off initializers
+SOL_CONSTRUCTOR_NAMES = ("constructor", "initialize")
+
+COMPARISON_PATTERN = re.compile(r"<=?|>=?|\.min\s*\(")
+
+# Tokens that may form the exponent following `**` in sol!
+SOL_OPERAND_TOKENS = ("identifier", "primitive_type", "integer_literal", "token_tree", ".")
+
+
+class IntegerOverflowInConstructorDetector(BaseDetector):
+    """
+    Detector for exponentiation and multiplication on unbounded constructor
+    parameters, such as `_initialSupply * 10 ** uint256(_decimals)`.
+
+    Constructors run once with deployer-supplied values, so an unexpected
+    `decimals` or supply overflows the initial state and can only be fixed by
+    redeploying. Checked variants, exponents compared against a limit
+    beforehand and products with only one unbounded factor are not reported.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.MEDIUM
+    cwe = 190
+
+    documentation = DetectorDoc(
+        catches=(
+            "Exponentiation and multiplication on unbounded constructor parameters, such as `initial_supply * 10 "
+            "** decimals`."
+        ),
+        rationale=(
+            "A large exponent or two large factors overflow the initial state, which can only be fixed by "
+            "redeploying the contract."
+        ),
+        vulnerable_example="""
+            #[constructor]
+            pub fn constructor(&mut self, supply: U256, decimals: u8) {
+                self.total_supply.set(supply * U256::from(10).pow(U256::from(decimals)));
+            }
+        """,
+        fixed_example="""
+            #[constructor]
+            pub fn constructor(&mut self, supply: U256, decimals: u8) -> Result<(), Vec<u8>> {
+                let scale = U256::from(10).checked_pow(U256::from(decimals)).ok_or(b"decimals too large".to_vec())?;
+                self.total_supply.set(supply.checked_mul(scale).ok_or(b"supply too large".to_vec())?);
+                Ok(())
+            }
+        """,
+        references=["https://swcregistry.io/docs/SWC-101"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="constructor_integer_overflow",
+            description="Detects unchecked exponentiation and multiplication on constructor parameters"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect overflowing initial computations"""
+        for function_node in self._iter_nodes(tree.root_node):
+            if function_node.type == "function_item" and self._is_constructor(function_node, code):
+                self._check_function(function_node, code, results)
+
+        for function in self._get_sol_functions(tree.root_node, code):
+            if function["name"] in SOL_CONSTRUCTOR_NAMES:
+                self._check_sol_function(function, code, results)
+
+    def _check_function(self, function_node: Node, code: str, results) -> None:
+        """Check a Rust constructor for `.pow()` and `*` on parameter-derived values"""
+        body = function_node.child_by_field_name("body")
+        if not body:
+            return
+        tainted = self._collect_tainted_names(function_node, body, code)
+        if not tainted:
+            return
+        function_name = self._get_function_name(function_node, code)
+
+        reported: List[Tuple[int, int]] = []
+        for node in self._iter_nodes(body):
+            if any(start <= node.start_byte and node.end_byte <= end for start, end in reported):
+                continue
+            finding = self._get_overflow(node, body, code, tainted)
+            if finding is None:
+                continue
+            operation, parameter = finding
+            reported.append((node.start_byte, node.end_byte))
+            line_start, line_end = self._get_line_for_node(node)
+            self._add_issue(line_start, line_end, self._get_column_for_node(node, code), self._get_node_text(node, code),
+                            operation, parameter,
+                            f"constructor '{function_name}'", results)
+
+    def _collect_tainted_names(self, function_node: Node, body: Node, code: str) -> Dict[str, str]:
+        """Map parameters, and locals computed from them, to the parameter they derive from"""
+        tainted = {name: name for name in self._collect_parameter_names(function_node, code)}
+        for node in self._iter_nodes(body):
+            if node.type != "let_declaration":
+                continue
+            pattern = node.child_by_field_name("pattern")
+            value = node.child_by_field_name("value")
+            if pattern and pattern.type == "mut_pattern" and pattern.named_children:
+                pattern = pattern.named_children[-1]
+            if not pattern or not value or pattern.type != "identifier":
+                continue
+            source = self._find_tainted(self._get_node_text(value, code), tainted)
+            if source:
+                tainted[self._get_node_text(pattern, code)] = tainted[source]
+        return tainted
+
+    def _find_tainted(self, text: str, tainted: Dict[str, str]) -> Optional[str]:
+        """Find the first tainted name referenced by source text"""
+        for name in tainted:
+            if re.search(rf"\b{re.escape(name)}\b", text):
+                return name
+        return None
+
+    def _get_overflow(self, node: Node, body: Node, code: str, tainted: Dict[str, str]) -> Optional[Tuple[str, str]]:
+        """Get the operation and parameter of an overflowing `.pow()` or `*`, if any"""
+        if node.type == "call_expression":
+            function = node.child_by_field_name("function")
+            arguments = node.child_by_field_name("arguments")
+            if not function or function.type != "field_expression" or not arguments:
+                return None
+            method = function.child_by_field_name("field")
+            if not method or self._get_node_text(method, code) != "pow":
+                return None
+            parameter = self._get_unbounded(arguments, body, code, tainted)
+            return ("exponentiation", parameter) if parameter else None
+
+        if node.type == "binary_expression":
+            operator = node.child_by_field_name("operator")
+            left = node.child_by_field_name("left")
+            right = node.child_by_field_name("right")
+            if not operator or operator.type != "*" or not left or not right:
+                return None
+            for operand in (left, right):
+                pow_call = self._find_unbounded_pow(operand, body, code, tainted)
+                if pow_call:
+                    return "exponentiation", pow_call
+            # Scaling by a literal or a bounded value is expected; only two unbounded factors are reported
+            parameter = self._get_unbounded(left, body, code, tainted)
+            if parameter and self._get_unbounded(right, body, code, tainted):
+                return "multiplication", parameter
+            return None
+        return None
+
+    def _find_unbounded_pow(self, node: Node, body: Node, code: str, tainted: Dict[str, str]) -> Optional[str]:
+        """Find an unbounded `.pow()` inside an operand and return its parameter"""
+        for child in self._iter_nodes(node):
+            finding = self._get_overflow(child, body, code, tainted) if child.type == "call_expression" else None
+            if finding:
+                return finding[1]
+        return None
+
+    def _get_unbounded(self, node: Node, body: Node, code: str, tainted: Dict[str, str]) -> Optional[str]:
+        """Get the parameter an expression derives from if it isn't bounded before the expression"""
+        name = self._find_tainted(self._get_node_text(node, code), tainted)
+        if name is None:
+            return None
+        names = {candidate for candidate, source in tainted.items() if source == tainted[name]}
+        if self._is_bounded_before(body, node.start_byte, names, code):
+            return None
+        return tainted[name]
+
+    def _is_bounded_before(self, body: Node, position: int, names: Set[str], code: str) -> bool:
+        """Check if any of the names is compared against a limit before a position"""
+        for node in self._iter_nodes(body):
+            if node.start_byte >= position:
+                break
+            guard = self._get_guard_text(node, code)
+            if guard and COMPARISON_PATTERN.search(guard.replace("->", "").replace("=>", "")) and \
+                    self._find_tainted(guard, {name: name for name in names}):
+                return True
+        return False
+
+    def _check_sol_function(self, function: Dict, code: str, results) -> None:
+        """Check a sol! constructor for `**` and `*` on unbounded parameters"""
+        parameters = self._get_sol_parameter_names(function["params"], code)
+        bounded: Set[str] = set()
+        for statement in self._split_sol_statements(function["body"]):
+            text = self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte)
+            if self._get_node_text(statement[0], code) in ("require", "if"):
+                if COMPARISON_PATTERN.search(text):
+                    bounded.update(name for name in parameters if re.search(rf"\b{re.escape(name)}\b", text))
+                continue
+            unbounded = {name: name for name in parameters - bounded}
+            finding = self._get_sol_overflow(statement, code, unbounded)
+            if finding:
+                operation, parameter = finding
+                line_start, _ = self._get_line_for_node(statement[0])
+                _, line_end = self._get_line_for_node(statement[-1])
+                self._add_issue(line_start, line_end, self._get_column_for_node(statement[0], code), text,
+                                operation, parameter,
+                                f"sol! constructor '{function['name']}'", results)
+
+    def _get_sol_overflow(self, statement: List[Node], code: str,
+                          unbounded: Dict[str, str]) -> Optional[Tuple[str, str]]:
+        """Get the operation and parameter of an overflowing `**` or `*` in a sol! statement"""
+        for index, token in enumerate(statement):
+            if token.type != "**":
+                continue
+            exponent = []
+            for following in statement[index + 1:]:
+                if following.type not in SOL_OPERAND_TOKENS:
+                    break
+                exponent.append(following)
+            if exponent:
+                name = self._find_tainted(self._get_source_text(code, exponent[0].start_byte, exponent[-1].end_byte), unbounded)
+                if name:
+                    return "exponentiation", name
+        for index, token in enumerate(statement[1:-1], start=1):
+            if token.type != "*":
+                continue
+            name = self._find_tainted(self._get_node_text(statement[index - 1], code), unbounded)
+            if name and self._find_tainted(self._get_node_text(statement[index + 1], code), unbounded):
+                return "multiplication", name
+        return None
+
+    def _add_issue(self, line_start: int, line_end: int, column: int, snippet: str, operation: str, parameter: str,
+                   location: str, results) -> None:
+        """Add a constructor overflow issue"""
+        results.add_issue(
+            issue_type="constructor_integer_overflow",
+            severity="Medium",
+            description=f"Unchecked {operation} in {location} depends on parameter '{parameter}' with no upper bound. A large value overflows the initial state, which can only be fixed by redeploying.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation=f"Bound '{parameter}' (e.g. require decimals <= 18) before the computation, or use checked_pow/checked_mul and revert on overflow."
+        )
@@ -0,0 +1,275 @@
+"""
+Detector for external calls made while a Stylus Rust contract is being constructed
+"""
+import re
+from typing import Dict, List, Optional, Set, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.delegatecall_untrusted_detector import CONSTANT_ADDRESS_PATTERN
+from stylus_analyzer.detectors.detector_base import LOW_LEVEL_CALLS, BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+from stylus_analyzer.sol_parser import SolFunction
+
+# Helpers followed from a constructor before giving up
+MAX_HELPER_DEPTH = 8
+
+# Free functions taking the target after the call context, e.g. `call(Call::new(), target, &data)`
+CONTEXT_FIRST_CALLS = ("call", "delegate_call", "static_call")
+
+# Solidity low-level calls, e.g. `target.call(data)` or `target.call{value: 1}(data)`
+SOL_LOW_LEVEL_CALL = re.compile(r"([\w.\[\]]+(?:\s*\(\s*[\w.]+\s*\))?)\s*\.\s*(call|delegatecall|staticcall)\s*[({]")
+
+# Solidity calls through an interface cast, e.g. `IOracle(oracle).latestPrice()`
+SOL_INTERFACE_CALL = re.compile(r"\b([A-Z]\w*)\s*\(\s*([^()]+?)\s*\)\s*\.\s*(\w+)\s*[({]")
+
+# Solidity address constants, e.g. `address constant REGISTRY = 0x...`
+SOL_ADDRESS_CONSTANT = re.compile(r"\baddress\s+(?:(?:public|private|internal)\s+)*constant\s+(\w+)")
+
+# Literal Solidity addresses, e.g. `0x...` or `address(0x...)`
+SOL_ADDRESS_LITERAL = re.compile(r"^(?:address\s*\(\s*)?0x[0-9a-fA-F]{40}\s*\)?$")
+
+
+class ConstructorReentryDetector(BaseDetector):
+    """
+    Detector for external calls made during construction.
+
+    A constructor, `#[constructor]` or `new`/`init`-style, and the `deploy`
+    entrypoint run before the contract's storage is fully set up, so a
+    contract they call can reenter, or read and act on, a half-initialized
+    contract. Low-level calls, `transfer_eth` and methods of `sol_interface!`
+    handles are reported, including those made by `self` helpers the
+    constructor calls. Calls to a `const`/`static` address or an
+    `address!(...)` literal are trusted. In `sol!` contracts, `.call`,
+    `.delegatecall`, `.staticcall` and `IFoo(target).method()` in the
+    `constructor` are reported the same way.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.MEDIUM
+    cwe = 696
+
+    documentation = DetectorDoc(
+        catches=(
+            "Low-level calls and `sol_interface!` method calls in constructors, `deploy` methods and the helpers "
+            "they call, and in `sol!` constructors, unless the target is a compile-time constant address."
+        ),
+        rationale=(
+            "During construction the contract's storage is only partly written. A called contract can call back "
+            "into it, or read its state, before invariants such as the owner or the supply are in place."
+        ),
+        vulnerable_example="""
+            #[constructor]
+            pub fn constructor(&mut self, registry: Address) -> Result<(), Vec<u8>> {
+                IRegistry::new(registry).register(Call::new_in(self))?;
+                self.owner.set(msg::sender());
+                Ok(())
+            }
+        """,
+        fixed_example="""
+            #[constructor]
+            pub fn constructor(&mut self, registry: Address) {
+                self.owner.set(msg::sender());
+                self.registry.set(registry);
+            }
+
+            // Called by the owner once the contract is deployed
+            pub fn register(&mut self) -> Result<(), Vec<u8>> {
+                IRegistry::new(self.registry.get()).register(Call::new_in(self))?;
+                Ok(())
+            }
+        """,
+        references=["https://docs.arbitrum.io/stylus/reference/rust-sdk-guide#constructor"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="constructor_external_call",
+            description="Detects external calls made by constructors and deploy methods"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect external calls in Rust constructors, their helpers and sol! constructors"""
+        root = tree.root_node
+        interfaces = self._collect_interface_names(root, code)
+        constants = self._collect_constants(root, code)
+        for function_node in self._iter_nodes(root):
+            if function_node.type != "function_item" or not self._is_deploy_entrypoint(function_node, code):
+                continue
+            methods = self._collect_impl_methods(function_node, code)
+            name = self._get_function_name(function_node, code)
+            location = f"constructor '{name}'" if self._is_constructor(function_node, code) \
+                else f"deploy entrypoint '{name}'"
+            for call, helper in self._collect_external_calls(function_node, code, interfaces, constants, methods,
+                                                             {name}):
+                self._add_issue(call, helper, location, code, results)
+
+        sol_constants = self._collect_sol_address_constants(root, code)
+        for function in self._parse_sol_functions(root, code):
+            if function.name == "constructor":
+                self._check_sol_constructor(function, sol_constants, code, results)
+
+    def _collect_impl_methods(self, function_node: Node, code: str) -> Dict[str, Node]:
+        """Map the names of the methods defined next to a function to their nodes"""
+        impl_node = self._get_parent_impl(function_node)
+        body = impl_node.child_by_field_name("body") if impl_node is not None else None
+        return {self._get_function_name(item, code): item
+                for item in (body.named_children if body is not None else []) if item.type == "function_item"}
+
+    def _collect_external_calls(self, function_node: Node, code: str, interfaces: Set[str], constants: Set[str],
+                                methods: Dict[str, Node], visited: Set[str],
+                                depth: int = 0) -> List[Tuple[Node, Optional[str]]]:
+        """Collect the untrusted external calls of a function and its `self` helpers, with the helper making each"""
+        handles = self._collect_interface_handles(function_node, code, interfaces)
+        calls: List[Tuple[Node, Optional[str]]] = []
+        for node in self._iter_nodes(function_node):
+            if self._is_external_call(node, code, interfaces, handles):
+                target = self._get_call_target(node, function_node, code, handles)
+                if target is None or not self._is_trusted_target(target, function_node, code, constants):
+                    calls.append((node, None))
+                continue
+            helper = self._get_self_call(node, code)
+            if helper in methods and helper not in visited and depth < MAX_HELPER_DEPTH:
+                visited.add(helper)
+                calls.extend((call, via or helper) for call, via in
+                             self._collect_external_calls(methods[helper], code, interfaces, constants, methods,
+                                                          visited, depth + 1))
+        return calls
+
+    def _get_call_target(self, call: Node, function_node: Node, code: str, handles: Set[str]) -> Optional[Node]:
+        """
+        Get the address an external call goes to, e.g. `target` in
+        `call(Call::new(), target, &data)` or `IFoo::new(target).method(...)`
+
+        Returns None if the address can't be found, e.g. for an interface
+        handle passed in as a parameter.
+        """
+        function = call.child_by_field_name("function")
+        arguments = call.child_by_field_name("arguments")
+        args = arguments.named_children if arguments is not None else []
+        if function.type in ("identifier", "scoped_identifier"):
+            name = self._get_node_text(function, code).split("::")[-1]
+            index = 1 if name in CONTEXT_FIRST_CALLS else 0
+            return args[index] if len(args) > index else None
+
+        receiver = function.child_by_field_name("value")
+        method = function.child_by_field_name("field")
+        if receiver is None or method is None:
+            return None
+        # `RawCall::new().call(target, data)` passes the target to the call itself
+        if self._get_node_text(method, code) in LOW_LEVEL_CALLS:
+            index = 1 if self._get_node_text(method, code) in CONTEXT_FIRST_CALLS and len(args) > 2 else 0
+            return args[index] if len(args) > index else None
+        if receiver.type == "identifier" and self._get_node_text(receiver, code) in handles:
+            receiver = self._find_binding(function_node, self._get_node_text(receiver, code), code)
+        if receiver is None or receiver.type != "call_expression":
+            return None
+        handle_arguments = receiver.child_by_field_name("arguments")
+        if handle_arguments is None or not handle_arguments.named_children:
+            return None
+        return handle_arguments.named_children[0]
+
+    def _is_trusted_target(self, target: Node, function_node: Node, code: str, constants: Set[str],
+                           depth: int = 0) -> bool:
+        """Check if a call target is a constant address, directly or through the locals it was bound to"""
+        while target.type in ("reference_expression", "parenthesized_expression") and target.named_children:
+            target = target.named_children[-1]
+        text = self._get_node_text(target, code).strip()
+        if CONSTANT_ADDRESS_PATTERN.search(text) or text.split("::")[-1] in constants:
+            return True
+        if target.type != "identifier" or depth > MAX_HELPER_DEPTH:
+            return False
+        binding = self._find_binding(function_node, text, code)
+        return binding is not None and self._is_trusted_target(binding, function_node, code, constants, depth + 1)
+
+    def _find_binding(self, function_node: Node, name: str, code: str) -> Optional[Node]:
+        """Find the value of the `let` binding of a local"""
+        for node in self._iter_nodes(function_node):
+            if node.type != "let_declaration":
+                continue
+            pattern = node.child_by_field_name("pattern")
+            value = node.child_by_field_name("value")
+            if pattern is not None and pattern.type == "mut_pattern" and pattern.named_children:
+                pattern = pattern.named_children[-1]
+            if pattern is not None and value is not None and pattern.type == "identifier" and \
+                    self._get_node_text(pattern, code) == name:
+                return value
+        return None
+
+    def _collect_constants(self, root: Node, code: str) -> Set[str]:
+        """Collect the names of `const` and `static` items"""
+        names = set()
+        for node in self._iter_nodes(root):
+            if node.type in ("const_item", "static_item"):
+                name = node.child_by_field_name("name")
+                if name is not None:
+                    names.add(self._get_node_text(name, code))
+        return names
+
+    def _get_self_call(self, node: Node, code: str) -> Optional[str]:
+        """Get the method name of a `self.method(...)` call"""
+        if node.type != "call_expression":
+            return None
+        function = node.child_by_field_name("function")
+        if function is None or function.type != "field_expression":
+            return None
+        receiver = function.child_by_field_name("value")
+        method = function.child_by_field_name("field")
+        if receiver is None or receiver.type != "self" or method is None:
+            return None
+        return self._get_node_text(method, code)
+
+    def _collect_sol_address_constants(self, root: Node, code: str) -> Set[str]:
+        """Get the address constants declared in sol! macros"""
+        names = set()
+        for node in self._iter_nodes(root):
+            if node.type == "macro_invocation" and self._get_macro_name(node, code) == "sol":
+                names.update(SOL_ADDRESS_CONSTANT.findall(self._get_node_text(node, code)))
+        return names
+
+    def _check_sol_constructor(self, function: SolFunction, constants: Set[str], code: str, results) -> None:
+        """Report the low-level and interface calls of a sol! constructor to non-constant targets"""
+        for statement in function.statements:
+            targets = [match.group(1) for match in SOL_LOW_LEVEL_CALL.finditer(statement.text)]
+            targets += [match.group(2) for match in SOL_INTERFACE_CALL.finditer(statement.text)
+                        if match.group(1) not in ("require", "assert", "address", "payable")]
+            for target in targets:
+                target = re.sub(r"^(?:address|payable)\s*\(\s*(.+?)\s*\)$", r"\1", target.strip())
+                if target in constants or SOL_ADDRESS_LITERAL.match(target):
+                    continue
+                _, line_end = self._get_line_for_node(statement.tokens[-1])
+                snippet = self._get_source_text(code, statement.tokens[0].start_byte, statement.tokens[-1].end_byte)
+                self._add_sol_issue(target, statement.line, line_end, self._get_column_for_node(statement.tokens[0], code),
+                                    snippet, results)
+                break
+
+    def _add_issue(self, call: Node, helper: Optional[str], location: str, code: str, results) -> None:
+        """Add an issue at an external call made during construction"""
+        route = f" through helper '{helper}'" if helper else ""
+        line_start, line_end = self._get_line_for_node(call)
+        results.add_issue(
+            issue_type="constructor_external_call",
+            severity="Medium",
+            description=f"The {location} makes an external call{route} before the contract is fully initialized. "
+                        "The called contract can reenter it or read its half-written state.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(call, code),
+            code_snippet=self._get_node_text(call, code),
+            recommendation="Finish writing storage before calling out, or move the call into a method run once "
+                           "the contract is deployed. Calls to a constant, trusted address are not reported."
+        )
+
+    def _add_sol_issue(self, target: str, line_start: int, line_end: int, column: int, snippet: str, results) -> None:
+        """Add an issue at an external call of a sol! constructor"""
+        results.add_issue(
+            issue_type="constructor_external_call",
+            severity="Medium",
+            description=f"The sol! constructor calls '{target}' before the contract is fully initialized. The "
+                        "called contract can reenter it or read its half-written state.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation="Finish writing storage before calling out, or move the call into a function run once "
+                           "the contract is deployed, unless the target is a trusted constant address."
+        )
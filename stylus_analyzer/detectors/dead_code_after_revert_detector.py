@@ -0,0 +1,140 @@
+"""
+Detector for statements after an unconditional return, revert or panic in Stylus Rust contracts
+"""
+import re
+from typing import List, Optional, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# Macros that never return
+DIVERGING_MACROS = ("panic", "unreachable", "todo", "unimplemented")
+
+# `evm::revert(...)` and a bare `revert(...)` imported from it
+REVERT_PATTERN = re.compile(r"^(?:(?:stylus_sdk\s*::\s*)?evm\s*::\s*)?revert$")
+
+# Block children that aren't executed statements
+NON_STATEMENT_TYPES = ("{", "}", "line_comment", "block_comment", "attribute_item", "empty_statement",
+                       "use_declaration", "macro_definition")
+
+
+class DeadCodeAfterRevertDetector(BaseDetector):
+    """
+    Detector for statements following an unconditional `return`,
+    `evm::revert(...)` or `panic!()` in the same block.
+
+    Such statements never run, which usually means a check or an update
+    was meant to happen before the function exits. An `if`/`else` or a
+    `match` all of whose branches diverge ends the block as well.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.HIGH
+    cwe = 561
+    node_types = ("block",)
+
+    documentation = DetectorDoc(
+        catches="Statements following an unconditional `return`, `evm::revert(...)` or `panic!()` in the same block.",
+        rationale=(
+            "The statements never run. This usually means an update or a check was placed after the function "
+            "already exits, so the contract silently skips it."
+        ),
+        vulnerable_example="""
+            pub fn set_total(&mut self, total: U256) -> Result<(), Vec<u8>> {
+                return Ok(());
+                self.total.set(total);
+                Ok(())
+            }
+        """,
+        fixed_example="""
+            pub fn set_total(&mut self, total: U256) -> Result<(), Vec<u8>> {
+                self.total.set(total);
+                Ok(())
+            }
+        """,
+        references=["https://swcregistry.io/docs/SWC-135"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="dead_code_after_revert",
+            description="Detects unreachable statements after an unconditional return, revert or panic"
+        )
+
+    def start(self, tree: Tree, code: str) -> None:
+        """Forget the dead code found in the previous file"""
+        # Spans already reported, so blocks nested in dead code aren't reported again
+        self.dead_spans: List[Tuple[int, int]] = []
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if a statement of a block follows one that diverges"""
+        if any(start <= node.start_byte < end for start, end in self.dead_spans):
+            return
+        statements = self._get_statements(node)
+        for index, statement in enumerate(statements[:-1]):
+            reason = self._diverges(statement, code)
+            if reason is None:
+                continue
+            dead = statements[index + 1:]
+            self.dead_spans.append((dead[0].start_byte, dead[-1].end_byte))
+            self._report(node, statement, reason, dead[0], code, results)
+            break
+
+    def _report(self, block: Node, diverging: Node, reason: str, unreachable: Node, code: str, results) -> None:
+        """Report the first unreachable statement of a block"""
+        function_node = self._find_parent_function(block)
+        function_name = self._get_function_name(function_node, code) if function_node else "unknown"
+        diverging_line, _ = self._get_line_for_node(diverging)
+        line_start, line_end = self._get_line_for_node(unreachable)
+        results.add_issue(
+            issue_type="dead_code_after_revert",
+            severity="Low",
+            description=f"Unreachable code in function '{function_name}': the statements after {reason} "
+                        f"on line {diverging_line} never run.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(unreachable, code),
+            code_snippet=self._get_node_text(unreachable, code),
+            recommendation="Remove the unreachable statements, or move them before the exit if they were meant to run."
+        )
+
+    def _get_statements(self, block: Node) -> List[Node]:
+        """Get the executed statements of a block, including its tail expression"""
+        return [child for child in block.children
+                if child.type not in NON_STATEMENT_TYPES and not child.type.endswith("_item")]
+
+    def _diverges(self, node: Node, code: str) -> Optional[str]:
+        """Describe how a statement unconditionally leaves the block, or None if it may not"""
+        if node.type == "expression_statement":
+            expression = node.named_children[0] if node.named_children else None
+            return self._diverges(expression, code) if expression is not None else None
+        if node.type == "return_expression":
+            return "the return"
+        if node.type == "macro_invocation":
+            macro = self._get_macro_name(node, code)
+            return f"{macro}!()" if macro in DIVERGING_MACROS else None
+        if node.type == "call_expression":
+            function = node.child_by_field_name("function")
+            if function is not None and REVERT_PATTERN.match(self._get_node_text(function, code)):
+                return "the revert"
+            return None
+        if node.type == "block":
+            return next(filter(None, (self._diverges(statement, code)
+                                      for statement in self._get_statements(node))), None)
+        if node.type == "if_expression":
+            consequence = node.child_by_field_name("consequence")
+            alternative = node.child_by_field_name("alternative")
+            if consequence is None or alternative is None:
+                return None
+            branch = alternative.named_children[0] if alternative.named_children else None
+            if branch is not None and self._diverges(consequence, code) and self._diverges(branch, code):
+                return "an if/else whose branches all exit"
+            return None
+        if node.type == "match_expression":
+            body = node.child_by_field_name("body")
+            arms = [arm for arm in body.named_children if arm.type == "match_arm"] if body else []
+            values = [arm.child_by_field_name("value") for arm in arms]
+            if arms and all(value is not None and self._diverges(value, code) for value in values):
+                return "a match whose arms all exit"
+        return None
@@ -0,0 +1,255 @@
+"""
+Detector for delegatecalls to caller-controlled targets in Stylus Rust contracts
+"""
+import re
+from typing import Dict, List, Optional, Set
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.detectors.missing_access_control_detector import (
+    GUARD_HELPER_PATTERN, ROLE_PATTERN, SENDER_PATTERN
+)
+from stylus_analyzer.findings import Confidence, Severity
+
+# Address expressions fixed at compile time
+CONSTANT_ADDRESS_PATTERN = re.compile(
+    r"^(address!\s*\(|Address::(from|new|from_slice)\s*\(\s*(\[|&\s*\[|\"|0x|hex!)|0x[0-9a-fA-F]{40}$)"
+)
+
+
+class DelegateCallToUntrustedDetector(BaseDetector):
+    """
+    Detector for `delegate_call` targets a caller can choose.
+
+    Delegatecall runs the target's code against this contract's storage and
+    balance, so a target taken from a parameter, or from a storage slot that
+    any account can overwrite, lets the caller execute arbitrary code as this
+    contract. Constant targets and storage slots only an owner can set are
+    not reported.
+    """
+
+    default_severity = Severity.HIGH
+    default_confidence = Confidence.MEDIUM
+    cwe = 829
+    node_types = ("call_expression",)
+
+    documentation = DetectorDoc(
+        catches=(
+            "`delegate_call` and `RawCall::new_delegate()` calls, and `.delegatecall` in `sol!`, whose target "
+            "comes from a parameter or a storage field anyone can write."
+        ),
+        rationale=(
+            "Delegatecall runs the target's code in this contract's storage context, so a caller-chosen target "
+            "can overwrite the owner or drain the contract."
+        ),
+        vulnerable_example="""
+            pub fn execute(&mut self, target: Address, data: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+                unsafe { delegate_call(&mut *self, target, &data) }
+            }
+        """,
+        fixed_example="""
+            pub fn execute(&mut self, data: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+                let target = self.implementation.get();
+                unsafe { delegate_call(&mut *self, target, &data) }
+            }
+        """,
+        references=["https://swcregistry.io/docs/SWC-112"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="delegatecall_untrusted_target",
+            description="Detects delegatecalls to targets taken from parameters or unprotected storage"
+        )
+
+    def start(self, tree: Tree, code: str) -> None:
+        """Collect the constants of the file and the storage fields anyone can write"""
+        self.constants = self._collect_constants(tree.root_node, code)
+        self.writers = self._collect_unguarded_writers(tree.root_node, code)
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if the target of a delegatecall is controlled by the caller"""
+        target = self._get_delegate_target(node, code)
+        if target is None:
+            return
+        function_node = self._find_parent_function(node)
+        if function_node is None:
+            return
+        origin = self._trace_target(target, function_node, code, self.constants, self.writers)
+        if origin:
+            line_start, line_end = self._get_line_for_node(node)
+            self._add_issue(line_start, line_end, self._get_column_for_node(node, code), self._get_node_text(node, code),
+                            self._get_node_text(target, code), origin, f"function '{self._get_function_name(function_node, code)}'", results)
+
+    def finish(self, tree: Tree, code: str, results) -> None:
+        """Check the delegatecalls of sol! function bodies"""
+        root = tree.root_node
+        sol_functions = self._get_sol_functions(root, code)
+        sol_writers = self._collect_sol_unguarded_writers(sol_functions, code)
+        for function in sol_functions:
+            self._check_sol_function(function, code, sol_writers, results)
+
+    def _get_delegate_target(self, node: Node, code: str) -> Optional[Node]:
+        """Get the target of `delegate_call(context, target, data)` or `RawCall::new_delegate().call(target, data)`"""
+        if node.type != "call_expression":
+            return None
+        function = node.child_by_field_name("function")
+        arguments = node.child_by_field_name("arguments")
+        if not function or not arguments:
+            return None
+        if function.type in ("identifier", "scoped_identifier"):
+            if self._get_node_text(function, code).split("::")[-1] == "delegate_call" and \
+                    len(arguments.named_children) > 1:
+                return arguments.named_children[1]
+        elif function.type == "field_expression":
+            method = function.child_by_field_name("field")
+            receiver = function.child_by_field_name("value")
+            if method and receiver and self._get_node_text(method, code) == "call" and \
+                    "new_delegate" in self._get_node_text(receiver, code) and arguments.named_children:
+                return arguments.named_children[0]
+        return None
+
+    def _trace_target(self, target: Node, function_node: Node, code: str, constants: Set[str],
+                      writers: Dict[str, str], depth: int = 0) -> Optional[str]:
+        """Trace a target expression back to a parameter, constant or storage field"""
+        while target.type in ("reference_expression", "parenthesized_expression") and target.named_children:
+            target = target.named_children[-1]
+        text = self._get_node_text(target, code).strip()
+        if CONSTANT_ADDRESS_PATTERN.search(text):
+            return None
+
+        field = self._get_self_field(target, code)
+        if field:
+            if field in writers:
+                return f"is read from storage field '{field}', which public function '{writers[field]}' sets without an owner check"
+            return None
+
+        if target.type != "identifier" or depth > 8:
+            return None
+        if text in constants:
+            return None
+        binding = self._find_binding(function_node, target, text, code)
+        if binding is not None:
+            return self._trace_target(binding, function_node, code, constants, writers, depth + 1)
+        if text in self._collect_parameter_names(function_node, code):
+            return f"comes from parameter '{text}'"
+        return None
+
+    def _find_binding(self, function_node: Node, usage: Node, name: str, code: str) -> Optional[Node]:
+        """Find the value of the last `let` binding of a name before its use"""
+        binding = None
+        for node in self._iter_nodes(function_node):
+            if node.start_byte >= usage.start_byte:
+                break
+            if node.type != "let_declaration":
+                continue
+            pattern = node.child_by_field_name("pattern")
+            value = node.child_by_field_name("value")
+            if pattern and pattern.type == "mut_pattern" and pattern.named_children:
+                pattern = pattern.named_children[-1]
+            if pattern and value and pattern.type == "identifier" and self._get_node_text(pattern, code) == name:
+                binding = value
+        return binding
+
+    def _collect_constants(self, root: Node, code: str) -> Set[str]:
+        """Collect the names of `const` and `static` items"""
+        names = set()
+        for node in self._iter_nodes(root):
+            if node.type in ("const_item", "static_item"):
+                name = node.child_by_field_name("name")
+                if name:
+                    names.add(self._get_node_text(name, code))
+        return names
+
+    def _collect_unguarded_writers(self, root: Node, code: str) -> Dict[str, str]:
+        """Map storage fields to a public method that writes them without checking the caller"""
+        writers: Dict[str, str] = {}
+        for function_node in self._get_public_methods(root, code):
+            if self._get_item_flags(function_node, code).constructor:
+                continue
+            body = function_node.child_by_field_name("body")
+            if not body or self._is_owner_gated(body, code):
+                continue
+            setter_aliases = self._collect_setter_aliases(function_node, code)
+            for node in self._iter_nodes(body):
+                field = self._get_storage_write_field(node, code, setter_aliases)
+                if field and field not in writers:
+                    writers[field] = self._get_function_name(function_node, code)
+        return writers
+
+    def _is_owner_gated(self, body: Node, code: str) -> bool:
+        """Check if a function body compares the caller or calls an owner guard helper"""
+        for node in self._iter_nodes(body):
+            guard = self._get_guard_text(node, code)
+            if guard is not None and (SENDER_PATTERN.search(guard) or ROLE_PATTERN.search(guard)):
+                return True
+            if node.type == "call_expression":
+                function = node.child_by_field_name("function")
+                if function and function.type == "field_expression":
+                    receiver = function.child_by_field_name("value")
+                    method = function.child_by_field_name("field")
+                    if receiver and receiver.type == "self" and method and \
+                            GUARD_HELPER_PATTERN.search(self._get_node_text(method, code)):
+                        return True
+        return False
+
+    def _collect_sol_unguarded_writers(self, functions: List[Dict], code: str) -> Dict[str, str]:
+        """Map sol! state variables to a function assigning them without a caller check"""
+        writers: Dict[str, str] = {}
+        for function in functions:
+            if function["name"] == "constructor":
+                continue
+            if any(GUARD_HELPER_PATTERN.search(modifier) for modifier in function["modifiers"]):
+                continue
+            statements = self._split_sol_statements(function["body"])
+            if any(self._get_node_text(statement[0], code) in ("require", "if") and
+                   (SENDER_PATTERN.search(self._get_statement_text(statement, code)) or
+                    ROLE_PATTERN.search(self._get_statement_text(statement, code)))
+                   for statement in statements):
+                continue
+            for statement in statements:
+                if len(statement) > 2 and statement[0].type == "identifier" and statement[1].type == "=":
+                    writers.setdefault(self._get_node_text(statement[0], code), function["name"])
+        return writers
+
+    def _check_sol_function(self, function: Dict, code: str, writers: Dict[str, str], results) -> None:
+        """Check a sol! function for `target.delegatecall(...)` on a parameter or unprotected state variable"""
+        parameters = self._get_sol_parameter_names(function["params"], code)
+        for statement in self._split_sol_statements(function["body"]):
+            for index, token in enumerate(statement[2:], start=2):
+                if self._get_node_text(token, code) != "delegatecall" or statement[index - 1].type != ".":
+                    continue
+                receiver = statement[index - 2]
+                name = self._get_node_text(receiver, code)
+                if receiver.type != "identifier":
+                    continue
+                if name in parameters:
+                    origin = f"comes from parameter '{name}'"
+                elif name in writers:
+                    origin = f"is read from state variable '{name}', which function '{writers[name]}' sets without an owner check"
+                else:
+                    continue
+                line_start, _ = self._get_line_for_node(statement[0])
+                _, line_end = self._get_line_for_node(statement[-1])
+                self._add_issue(line_start, line_end, self._get_column_for_node(statement[0], code),
+                                self._get_statement_text(statement, code), name, origin,
+                                f"sol! function '{function['name']}'", results)
+                break
+
+    def _get_statement_text(self, statement: List[Node], code: str) -> str:
+        """Get the source text of a sol! statement"""
+        return self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte)
+
+    def _add_issue(self, line_start: int, line_end: int, column: int, snippet: str, target: str, origin: str,
+                   location: str, results) -> None:
+        """Add a delegatecall to untrusted target issue"""
+        results.add_issue(
+            issue_type="delegatecall_untrusted_target",
+            severity="High",
+            description=f"Delegatecall in {location} targets '{target}', which {origin}. The callee runs in this contract's storage context, so the caller can execute arbitrary code as this contract.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation="Only delegatecall constant addresses or implementations stored behind an owner check, and never a caller-supplied address."
+        )
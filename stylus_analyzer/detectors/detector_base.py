@@ -2,20 +2,259 @@
 Base detector class for static analysis
 """
 import logging
-from typing import Tuple
+import re
+import textwrap
+from dataclasses import dataclass, field
+from typing import Any, Callable, Dict, Hashable, Iterator, List, Optional, Set, Tuple, TypeVar, Union
 from tree_sitter import Node, Tree
 
-# Configure logging
-logging.basicConfig(level=logging.INFO, format='%(asctime)s - %(name)s - %(levelname)s - %(message)s')
+from stylus_analyzer.abi_utils import rust_type_to_abi, to_camel_case
+from stylus_analyzer.ast_utils import get_parent_impl
+from stylus_analyzer.call_graph import CallGraph, build_call_graphs
+from stylus_analyzer.findings import Confidence, Finding, Severity, TextEdit
+from stylus_analyzer.guards import ASSERT_MACROS, REQUIRE_MACROS, Guard, extract_guards
+from stylus_analyzer.sol_parser import (
+    SOL_MACROS, SolFunction, SolInterface, is_token_tree, parse_sol_functions, parse_sol_interfaces,
+    sol_parameter_names, split_sol_statements
+)
+from stylus_analyzer.source_map import SourceMap
+from stylus_analyzer.storage_layout import StorageLayout, collect_storage_layout
+# PUBLIC_ATTRIBUTES is re-exported for detectors importing it from here
+from stylus_analyzer.stylus_attributes import (
+    PUBLIC_ATTRIBUTES, ItemFlags, get_attribute_names, get_item_flags, is_public_method
+)
+
 logger = logging.getLogger(__name__)
 
+T = TypeVar("T")
+
+# Storage methods that mutate the underlying slot
+STORAGE_WRITE_METHODS = ("set", "insert", "push", "pop", "erase", "delete", "clear", "replace", "take")
+
+# Low-level call primitives of the Stylus SDK
+LOW_LEVEL_CALLS = ("call", "delegate_call", "static_call", "transfer_eth")
+
+# Macros whose arguments act as a guard that reverts when false
+GUARD_MACROS = ASSERT_MACROS + REQUIRE_MACROS
+
+# Rust methods treated as constructors or one-off initializers
+CONSTRUCTOR_NAMES = ("new", "constructor", "init", "initialize")
+
+# Expressions identifying the caller
+SENDER_PATTERN = re.compile(r"\bmsg\s*::\s*sender\s*\(|\bmsg_sender\s*\(|\bmsg\s*\.\s*sender\b")
+
+# Method some contracts run once at deployment instead of a `#[constructor]`
+DEPLOY_ENTRYPOINT = "deploy"
+
+# Entrypoints that receive plain Ether transfers and unmatched calls
+ETHER_ENTRYPOINTS = ("receive", "fallback")
+
+# `#[selector(name = "...")]` overriding the exported method name
+SELECTOR_ATTRIBUTE_PATTERN = re.compile(r'selector\s*\(\s*name\s*=\s*"([^"]*)"')
+
+
+@dataclass
+class ContractFunction:
+    """
+    A contract function, either a Rust method of an impl block or a function of a sol! macro.
+
+    `body` is the Rust block or the sol! `{ ... }` token tree, and `sol`
+    holds the parsed representation of sol! functions.
+    """
+    name: str
+    language: str
+    node: Node
+    body: Optional[Node]
+    sol: Optional[SolFunction] = None
+
+    @property
+    def line(self) -> int:
+        """1-based line the function starts on"""
+        return self.node.start_point[0] + 1
+
+
+@dataclass
+class AnalysisContext:
+    """
+    The parsed contract a detector runs on, with the facts derived from it.
+
+    The derived facts, such as the storage layout and the call graphs, are
+    computed the first time a detector asks for them and shared with every
+    other detector running on the same context.
+    """
+    tree: Tree
+    code: str
+    file_path: Optional[str] = None
+    _facts: Dict[Hashable, Any] = field(default_factory=dict, init=False, repr=False, compare=False)
+
+    @classmethod
+    def from_source(cls, code: str, file_path: Optional[str] = None) -> Optional["AnalysisContext"]:
+        """Parse a file's text into a context, or return None if it can't be parsed"""
+        # Imported here since file_utils sets up the parser when it is imported
+        from stylus_analyzer.file_utils import generate_rust_ast
+        tree = generate_rust_ast(code)
+        return cls(tree=tree, code=code, file_path=file_path) if tree is not None else None
+
+    def derive(self, key: Hashable, compute: Callable[[], T]) -> T:
+        """Get a derived fact by key, computing it on first request and reusing it afterwards"""
+        if key not in self._facts:
+            self._facts[key] = compute()
+        return self._facts[key]
+
+    @property
+    def source_map(self) -> SourceMap:
+        """The byte offset to line and text mapping of the file"""
+        return SourceMap.of(self.code)
+
+    def storage_layout(self) -> StorageLayout:
+        """
+        The storage structs of the file and their fields, collected on first use
+
+        For a file on disk, the storage structs it implements but imports from
+        other files of its crate are resolved into the layout's `imported`.
+        """
+        return self.derive("storage_layout", self._collect_storage_layout)
+
+    def _collect_storage_layout(self) -> StorageLayout:
+        """Collect the file's storage structs along with the ones it imports from its crate"""
+        # Imported here since file_utils sets up the parser when it is imported
+        from stylus_analyzer.crate_modules import resolve_imported_structs
+        layout = collect_storage_layout(self.tree.root_node, self.code)
+        if self.file_path is not None:
+            layout.imported = resolve_imported_structs(self.file_path, self.tree.root_node, self.code)
+        return layout
+
+    def interfaces(self) -> Dict[str, SolInterface]:
+        """The interfaces declared in the file's sol_interface! macros by name, collected on first use"""
+        return self.derive("interfaces", lambda: {interface.name: interface for interface in
+                                                  parse_sol_interfaces(self.tree.root_node, self.code)})
+
+    def call_graphs(self) -> Dict[str, CallGraph]:
+        """The call graph of each type's methods by type name, built on first use"""
+        return self.derive("call_graphs", lambda: build_call_graphs(self.tree.root_node, self.code))
+
+    def macro_functions(self, macros: Tuple[str, ...] = ("sol",)) -> List[SolFunction]:
+        """The functions defined in the file's given macros, parsed on first use"""
+        return self.derive(("macro_functions", macros),
+                           lambda: parse_sol_functions(self.tree.root_node, self.code, macros))
+
+    def guards(self, function: Union[Node, SolFunction]) -> List[Guard]:
+        """The guards of a Rust `function_item` or a sol! function in source order, extracted on first use"""
+        return self.derive(("guards", function.node.start_byte if isinstance(function, SolFunction)
+                            else function.start_byte), lambda: extract_guards(function, self.code))
+
+    @property
+    def sol_functions(self) -> List[SolFunction]:
+        """The functions defined in the file's sol! and sol_interface! macros, parsed on first use"""
+        return self.macro_functions(SOL_MACROS)
+
+    def iter_functions(self) -> Iterator[ContractFunction]:
+        """Iterate over the Rust impl methods, then the sol! functions, each in source order"""
+        stack = [self.tree.root_node]
+        methods = []
+        while stack:
+            node = stack.pop()
+            if node.type == "function_item" and node.parent and node.parent.type == "declaration_list" and \
+                    node.parent.parent and node.parent.parent.type == "impl_item":
+                name = node.child_by_field_name("name")
+                methods.append(ContractFunction(
+                    name=self.source_map.node_text(name) if name else "unknown",
+                    language="rust",
+                    node=node,
+                    body=node.child_by_field_name("body")
+                ))
+            stack.extend(reversed(node.children))
+        yield from methods
+        for function in self.sol_functions:
+            yield ContractFunction(name=function.name, language="sol", node=function.node,
+                                   body=function.body, sol=function)
+
+
+@dataclass
+class DetectorDoc:
+    """
+    Long-form documentation of a detector, printed by `stylus-analyzer explain`.
+
+    The code samples may be indented to fit the class defining them; they
+    are dedented when the documentation is created.
+    """
+    catches: str
+    rationale: str
+    vulnerable_example: str
+    fixed_example: str
+    references: List[str] = field(default_factory=list)
+
+    def __post_init__(self):
+        self.vulnerable_example = textwrap.dedent(self.vulnerable_example).strip("\n")
+        self.fixed_example = textwrap.dedent(self.fixed_example).strip("\n")
+
 
 class BaseDetector:
     """Base class for all static analysis detectors"""
+
+    # Severity of the detector's findings, or of its most severe ones when it reports several
+    default_severity = Severity.MEDIUM
+
+    # How likely the detector's findings are to be real issues, unless it sets a confidence per finding
+    default_confidence = Confidence.MEDIUM
+
+    # CWE id of the weakness the detector's findings are instances of, for detectors matching one
+    cwe: Optional[int] = None
+
+    # What the detector catches and how to fix it, for detectors that document it
+    documentation: Optional[DetectorDoc] = None
+
+    # Node types passed to `visit_node`, for detectors that look at one node at a time. The analyzer
+    # runs these detectors together in a single traversal instead of calling `detect` on each of them
+    node_types: Tuple[str, ...] = ()
+
+    # Constructor keywords the `[options.<detector id>]` table of the config file may set, with their
+    # types. List options are lists of strings
+    options: Dict[str, type] = {}
+
+    # Ids of broader detectors whose findings at the same line and snippet as this detector's are dropped,
+    # so a narrower variant replaces the general finding rather than repeating it
+    supersedes: Tuple[str, ...] = ()
     
     def __init__(self, name: str, description: str):
         self.name = name
         self.description = description
+        # The context of the file being analyzed, set while the detector runs
+        self.context: Optional[AnalysisContext] = None
+
+    @property
+    def id(self) -> str:
+        """Stable identifier used to enable, disable and list the detector"""
+        return self.name
+
+    def run(self, ctx: AnalysisContext) -> List[Finding]:
+        """
+        Run the detector on an analysis context
+
+        Args:
+            ctx: The parsed contract to analyze
+
+        Returns:
+            The findings of the detector, located in the context's file
+        """
+        # Imported here since the analyzer itself imports the detectors
+        from stylus_analyzer.static_analyzer import StaticAnalysisResult
+        results = StaticAnalysisResult()
+        self.context = ctx
+        try:
+            self.detect(ctx.tree, ctx.code, results)
+        finally:
+            # Not kept past the run, so the detector stays picklable for worker processes
+            self.context = None
+        return self.locate_findings(results.findings, ctx)
+
+    def locate_findings(self, findings: List[Finding], ctx: AnalysisContext) -> List[Finding]:
+        """Place findings in the context's file, filling in the default confidence"""
+        for finding in findings:
+            finding.file = ctx.file_path
+            if finding.confidence is None:
+                finding.confidence = self.default_confidence
+        return findings
         
     def detect(self, tree: Tree, code: str, results: 'StaticAnalysisResult') -> None:
         """
@@ -26,13 +265,101 @@ class BaseDetector:
             code: The source code
             results: The results object to add issues to
         """
-        raise NotImplementedError("Detector classes must implement detect()")
+        if not self.node_types:
+            raise NotImplementedError("Detector classes must implement detect() or declare node_types")
+        # Called directly, the detector gets a context of its own to keep the facts of each function in
+        own_context = self.context is None
+        if own_context:
+            self.context = AnalysisContext(tree=tree, code=code)
+        try:
+            self.start(tree, code)
+            for node in self._iter_nodes(tree.root_node):
+                if node.type in self.node_types:
+                    self.visit_node(node, code, results)
+            self.finish(tree, code, results)
+        finally:
+            if own_context:
+                self.context = None
+
+    def start(self, tree: Tree, code: str) -> None:
+        """Reset the state kept between nodes before a file is traversed"""
+
+    def visit_node(self, node: Node, code: str, results: 'StaticAnalysisResult') -> None:
+        """
+        Check a node of one of the detector's `node_types`
+
+        Args:
+            node: The node, visited in source order
+            code: The source code
+            results: The results object to add issues to
+        """
+        raise NotImplementedError("Detectors declaring node_types must implement visit_node()")
+
+    def finish(self, tree: Tree, code: str, results: 'StaticAnalysisResult') -> None:
+        """Report what needs the whole file once every node has been visited"""
     
+    def _get_shared_context(self, root: Node, code: str) -> Optional[AnalysisContext]:
+        """
+        Get the context the detector runs on when a node is the root of its file
+
+        Facts about the whole file are taken from this context, so each is
+        derived once and shared with the other detectors. Detectors called
+        through `detect` directly have no context and derive them themselves.
+        """
+        ctx = self.context
+        if ctx is not None and ctx.tree.root_node == root and ctx.code == code:
+            return ctx
+        return None
+
+    def _get_storage_layout(self, root: Node, code: str) -> StorageLayout:
+        """Get the storage layout of the structs below a node"""
+        ctx = self._get_shared_context(root, code)
+        return ctx.storage_layout() if ctx is not None else collect_storage_layout(root, code)
+
+    def _get_call_graphs(self, root: Node, code: str) -> Dict[str, CallGraph]:
+        """Get the call graph of each type's methods below a node by type name"""
+        ctx = self._get_shared_context(root, code)
+        return ctx.call_graphs() if ctx is not None else build_call_graphs(root, code)
+
+    def _get_interfaces(self, root: Node, code: str) -> List[SolInterface]:
+        """Get the interfaces declared in sol_interface! macros below a node"""
+        ctx = self._get_shared_context(root, code)
+        return list(ctx.interfaces().values()) if ctx is not None else parse_sol_interfaces(root, code)
+
+    def _get_guards(self, function: Union[Node, SolFunction], code: str) -> List[Guard]:
+        """Get the guards of a Rust `function_item` or a sol! function, see `extract_guards`"""
+        ctx = self.context
+        if ctx is not None and ctx.code == code:
+            return ctx.guards(function)
+        return extract_guards(function, code)
+
+    def _get_function_fact(self, key: str, function_node: Node, code: str, compute: Callable[[], T]) -> T:
+        """
+        Get a fact about a function, such as the types of its locals, derived for
+        the first node `visit_node` is called with in the function and reused for
+        the others
+
+        Facts kept under the same key are shared with the other detectors, so a
+        key is either a fact the base class provides or prefixed with the id of
+        the detector computing it.
+        """
+        ctx = self.context
+        if ctx is None or ctx.code != code:
+            return compute()
+        return ctx.derive((key, function_node.start_byte), compute)
+
+    def _get_local_type_texts(self, function_node: Node, code: str) -> Dict[str, str]:
+        """Get the types of a function's parameters and annotated locals, shared by the detectors visiting it"""
+        return self._get_function_fact("local_type_texts", function_node, code,
+                                       lambda: self._collect_local_type_texts(function_node, code))
+
     def _get_node_text(self, node: Node, code: str) -> str:
         """Get the text of a node from the source code"""
-        if node.start_byte >= len(code) or node.end_byte > len(code):
-            return ""
-        return code[node.start_byte:node.end_byte]
+        return SourceMap.of(code).node_text(node)
+
+    def _get_source_text(self, code: str, start_byte: int, end_byte: int) -> str:
+        """Get the source code between two byte offsets reported by tree-sitter"""
+        return SourceMap.of(code).text(start_byte, end_byte)
     
     # def _get_line_for_node(self, node: Node) -> Tuple[int, int]:
     #     """Get the start and end line numbers for a node"""
@@ -42,3 +369,400 @@ class BaseDetector:
         start_line = node.start_point[0] + 1
         end_line = node.end_point[0] + 1
         return start_line, end_line
+
+    def _get_column_for_node(self, node: Node, code: str) -> int:
+        """Get the 1-based character column a node starts at, the column of a finding reported at it"""
+        return SourceMap.of(code).point_position(node.start_point)[1]
+
+    def _text_edit(self, code: str, start_point: Tuple[int, int], end_point: Tuple[int, int],
+                   replacement: str) -> TextEdit:
+        """Build an edit replacing the text between two tree-sitter points, whose columns are byte offsets"""
+        source_map = SourceMap.of(code)
+        line, column = source_map.point_position(start_point)
+        line_end, column_end = source_map.point_position(end_point)
+        return TextEdit(line=line, column=column, line_end=line_end, column_end=column_end, replacement=replacement)
+
+    def _insert_statement_before(self, code: str, node: Node, statement: str) -> TextEdit:
+        """
+        Build an edit inserting a statement, possibly spanning several lines, before the one a node starts
+
+        The statement gets the indentation of the node's line, or is put on the
+        same line when something else precedes the node there.
+        """
+        source_map = SourceMap.of(code)
+        prefix = source_map.text(source_map.line_starts[node.start_point[0]], node.start_byte)
+        if prefix.strip():
+            return self._text_edit(code, node.start_point, node.start_point, statement + " ")
+        indented = ("\n" + prefix).join(statement.split("\n"))
+        return self._text_edit(code, node.start_point, node.start_point, indented + "\n" + prefix)
+
+    def _iter_nodes(self, node: Node) -> Iterator[Node]:
+        """Iterate over a node and all of its descendants in source order"""
+        yield node
+        for child in node.children:
+            yield from self._iter_nodes(child)
+
+    def _find_parent_function(self, node: Node) -> Optional[Node]:
+        """Find the function item enclosing this node"""
+        parent = node.parent
+        while parent:
+            if parent.type == "function_item":
+                return parent
+            parent = parent.parent
+        return None
+
+    def _get_function_name(self, node: Node, code: str) -> str:
+        """Extract the function name from a function node"""
+        if not node:
+            return "unknown"
+        name_node = node.child_by_field_name("name")
+        if name_node:
+            return self._get_node_text(name_node, code)
+        return "unknown"
+
+    def _get_method_name(self, call: Node, code: str) -> Optional[str]:
+        """Get the method a `receiver.method(...)` call goes to"""
+        function = call.child_by_field_name("function")
+        method = function.child_by_field_name("field") if function is not None and \
+            function.type == "field_expression" else None
+        return self._get_node_text(method, code) if method is not None else None
+
+    def _get_attribute_names(self, node: Node, code: str) -> List[str]:
+        """Get the normalized names of the outer attributes (e.g. `#[public]`) attached to an item"""
+        return get_attribute_names(node, code)
+
+    def _get_item_flags(self, node: Node, code: str) -> ItemFlags:
+        """Get what the Stylus attributes of a struct, impl or function make of it, e.g. payable or storage"""
+        return get_item_flags(node, code)
+
+    def _get_parent_impl(self, node: Node) -> Optional[Node]:
+        """Find the impl block directly containing a function item"""
+        return get_parent_impl(node)
+
+    def _is_public_method(self, node: Node, code: str) -> bool:
+        """Check if a function item is an externally callable contract method"""
+        return is_public_method(node, code)
+
+    def _get_public_methods(self, root: Node, code: str) -> List[Node]:
+        """Collect all externally callable contract methods in the file"""
+        return [node for node in self._iter_nodes(root)
+                if node.type == "function_item" and self._is_public_method(node, code)]
+
+    def _takes_mut_self(self, function_node: Node, code: str) -> bool:
+        """Check if a method takes `&mut self`"""
+        parameters = function_node.child_by_field_name("parameters")
+        for parameter in parameters.named_children if parameters else []:
+            if parameter.type == "self_parameter":
+                return "mut" in self._get_node_text(parameter, code)
+        return False
+
+    def _get_self_field(self, node: Node, code: str) -> Optional[str]:
+        """
+        Get the storage field an expression is rooted at, e.g. `balance` for
+        `self.balance`, `self.balance.get()` or `self.balances.setter(from)`.
+        """
+        current = node
+        while current:
+            if current.type == "field_expression":
+                value = current.child_by_field_name("value")
+                if value and value.type == "self":
+                    field = current.child_by_field_name("field")
+                    return self._get_node_text(field, code) if field else None
+                current = value
+            elif current.type == "call_expression":
+                current = current.child_by_field_name("function")
+            elif current.type in ("index_expression", "parenthesized_expression",
+                                  "unary_expression", "reference_expression", "try_expression"):
+                current = current.named_children[0] if current.named_children else None
+                if current and current.type == "mutable_specifier":
+                    current = current.next_named_sibling
+            else:
+                return None
+        return None
+
+    def _get_storage_write_field(self, node: Node, code: str,
+                                 setter_aliases: Optional[Dict[str, str]] = None) -> Optional[str]:
+        """
+        Get the storage field written by a node, or None if it doesn't write storage.
+
+        Recognizes `self.<field>.set(...)`-style calls, assignments rooted at
+        `self.<field>` and writes through locals bound to `self.<field>.setter(...)`.
+        """
+        if node.type == "call_expression":
+            function = node.child_by_field_name("function")
+            if function and function.type == "field_expression":
+                method = function.child_by_field_name("field")
+                receiver = function.child_by_field_name("value")
+                if method and receiver and self._get_node_text(method, code) in STORAGE_WRITE_METHODS:
+                    field = self._get_self_field(receiver, code)
+                    if field:
+                        return field
+                    if setter_aliases and receiver.type == "identifier":
+                        return setter_aliases.get(self._get_node_text(receiver, code))
+        elif node.type in ("assignment_expression", "compound_assignment_expr"):
+            left = node.child_by_field_name("left")
+            if left:
+                return self._get_self_field(left, code)
+        return None
+
+    def _collect_setter_aliases(self, function_node: Node, code: str) -> Dict[str, str]:
+        """Map locals bound to `self.<field>.setter(...)` to the field they write"""
+        aliases = {}
+        for node in self._iter_nodes(function_node):
+            if node.type != "let_declaration":
+                continue
+            pattern = node.child_by_field_name("pattern")
+            value = node.child_by_field_name("value")
+            if not pattern or not value:
+                continue
+            if pattern.type == "mut_pattern":
+                pattern = pattern.named_children[-1]
+            if pattern.type != "identifier" or ".setter(" not in self._get_node_text(value, code):
+                continue
+            field = self._get_self_field(value, code)
+            if field:
+                aliases[self._get_node_text(pattern, code)] = field
+        return aliases
+
+    def _collect_sender_aliases(self, node: Node, code: str) -> Set[str]:
+        """Collect locals bound to `msg::sender()` in a function or body"""
+        aliases = set()
+        for child in self._iter_nodes(node):
+            if child.type != "let_declaration":
+                continue
+            pattern = child.child_by_field_name("pattern")
+            value = child.child_by_field_name("value")
+            if pattern and value and pattern.type == "identifier" and \
+                    SENDER_PATTERN.search(self._get_node_text(value, code)):
+                aliases.add(self._get_node_text(pattern, code))
+        return aliases
+
+    def _collect_interface_names(self, root: Node, code: str) -> Set[str]:
+        """Collect the interface names declared in `sol_interface!` macros"""
+        ctx = self._get_shared_context(root, code)
+        if ctx is not None:
+            return set(ctx.derive("interface_names", lambda: self._find_interface_names(root, code)))
+        return self._find_interface_names(root, code)
+
+    def _find_interface_names(self, root: Node, code: str) -> Set[str]:
+        """Find the interface names declared in `sol_interface!` macros by their text"""
+        names = set()
+        for node in self._iter_nodes(root):
+            if node.type == "macro_invocation":
+                text = self._get_node_text(node, code)
+                if text.startswith("sol_interface!"):
+                    names.update(re.findall(r"\binterface\s+(\w+)", text))
+        return names
+
+    def _collect_interface_handles(self, function_node: Node, code: str, interfaces: Set[str]) -> Set[str]:
+        """Collect the names of parameters and locals holding an interface handle"""
+        handles = set()
+        if not interfaces:
+            return handles
+        for node in self._iter_nodes(function_node):
+            if node.type == "parameter":
+                param_type = node.child_by_field_name("type")
+                pattern = node.child_by_field_name("pattern")
+                if param_type and pattern and self._get_node_text(param_type, code).lstrip("&").strip() in interfaces:
+                    handles.add(self._get_node_text(pattern, code))
+            elif node.type == "let_declaration":
+                pattern = node.child_by_field_name("pattern")
+                value = node.child_by_field_name("value")
+                if pattern and value and pattern.type == "identifier":
+                    value_text = self._get_node_text(value, code)
+                    if any(value_text.startswith(f"{name}::") or value_text.startswith(f"{name} ")
+                           for name in interfaces):
+                        handles.add(self._get_node_text(pattern, code))
+        return handles
+
+    def _is_external_call(self, node: Node, code: str, interfaces: Set[str], handles: Set[str]) -> bool:
+        """Check if a call expression calls out to another contract"""
+        if node.type != "call_expression":
+            return False
+        function = node.child_by_field_name("function")
+        if not function:
+            return False
+        if function.type == "field_expression":
+            method = function.child_by_field_name("field")
+            receiver = function.child_by_field_name("value")
+            if method and self._get_node_text(method, code) in LOW_LEVEL_CALLS:
+                return True
+            if receiver:
+                receiver_text = self._get_node_text(receiver, code)
+                if receiver.type == "identifier" and receiver_text in handles:
+                    return True
+                if any(receiver_text.startswith(f"{name}::") for name in interfaces):
+                    return True
+        elif function.type in ("identifier", "scoped_identifier"):
+            return self._get_node_text(function, code).split("::")[-1] in LOW_LEVEL_CALLS
+        return False
+
+    def _collect_parameter_names(self, function_node: Node, code: str) -> Set[str]:
+        """Collect the parameter names of a function"""
+        names = set()
+        parameters = function_node.child_by_field_name("parameters")
+        for parameter in parameters.named_children if parameters else []:
+            pattern = parameter.child_by_field_name("pattern") if parameter.type == "parameter" else None
+            if pattern and pattern.type == "mut_pattern" and pattern.named_children:
+                pattern = pattern.named_children[-1]
+            if pattern and pattern.type == "identifier":
+                names.add(self._get_node_text(pattern, code))
+        return names
+
+    def _collect_local_type_texts(self, function_node: Node, code: str) -> Dict[str, str]:
+        """Map parameters and annotated locals of a function to the text of their type"""
+        local_types = {}
+        for node in self._iter_nodes(function_node):
+            if node.type not in ("parameter", "let_declaration"):
+                continue
+            pattern = node.child_by_field_name("pattern")
+            declared_type = node.child_by_field_name("type")
+            if not pattern or not declared_type:
+                continue
+            if pattern.type == "mut_pattern" and pattern.named_children:
+                pattern = pattern.named_children[-1]
+            local_types[self._get_node_text(pattern, code)] = self._get_node_text(declared_type, code)
+        return local_types
+
+    def _get_guard_text(self, node: Node, code: str) -> Optional[str]:
+        """Get the text of an `if` condition or guard macro"""
+        if node.type == "if_expression":
+            condition = node.child_by_field_name("condition")
+            return self._get_node_text(condition, code) if condition else None
+        if node.type == "macro_invocation" and self._get_macro_name(node, code) in GUARD_MACROS:
+            return self._get_node_text(node, code)
+        return None
+
+    def _get_macro_name(self, node: Node, code: str) -> str:
+        """Get the name of a macro invocation, e.g. `sol` for `sol! { ... }`"""
+        macro = node.child_by_field_name("macro")
+        if not macro:
+            return ""
+        return self._get_node_text(macro, code).split("::")[-1]
+
+    def _collect_storage_fields(self, root: Node, code: str) -> Dict[str, str]:
+        """
+        Map the storage fields of the contract to the text of their type.
+
+        Covers structs annotated with `#[storage]`, `#[entrypoint]` or `#[public]`
+        as well as Solidity-style declarations inside `sol_storage!`, including
+        the structs the file implements but imports from another file of its crate.
+        """
+        fields = {}
+        for struct in self._collect_storage_structs(root, code, include_imported=True):
+            fields.update(struct["fields"])
+        return fields
+
+    def _collect_storage_structs(self, root: Node, code: str, include_imported: bool = False) -> List[Dict]:
+        """
+        Collect the storage structs of the contract.
+
+        Args:
+            include_imported: Also collect the structs the file implements but imports from
+                another file of its crate, whose nodes belong to that file
+
+        Returns:
+            Dicts with the struct `name`, the `node` its declaration starts at,
+            its `body`, the `fields` mapping field names to the text of their type
+            and the `file` of imported structs
+        """
+        layout = self._get_storage_layout(root, code)
+        structs = layout.imported + layout.structs if include_imported else layout.structs
+        return [{"name": struct.name, "node": struct.node, "body": struct.body,
+                 "fields": {storage_field.name: storage_field.type for storage_field in struct.fields},
+                 "file": struct.file}
+                for struct in structs]
+
+    def _collect_impl_methods(self, root: Node, code: str) -> Dict[str, Dict[str, Node]]:
+        """Map type names to the methods of their impl blocks"""
+        impls: Dict[str, Dict[str, Node]] = {}
+        for node in self._iter_nodes(root):
+            if node.type != "function_item":
+                continue
+            impl_node = self._get_parent_impl(node)
+            impl_type = impl_node.child_by_field_name("type") if impl_node else None
+            if impl_type is None:
+                continue
+            type_name = self._get_node_text(impl_type, code).split("<")[0].strip()
+            impls.setdefault(type_name, {})[self._get_function_name(node, code)] = node
+        return impls
+
+    def _is_constructor(self, function_node: Node, code: str) -> bool:
+        """Check if a function is a `#[constructor]` or a `new`/`init`-style method"""
+        if self._get_item_flags(function_node, code).constructor:
+            return True
+        return self._get_parent_impl(function_node) is not None and \
+            self._get_function_name(function_node, code) in CONSTRUCTOR_NAMES
+
+    def _is_deploy_entrypoint(self, function_node: Node, code: str) -> bool:
+        """Check if a function runs at deployment: a constructor or a `deploy` method"""
+        return self._is_constructor(function_node, code) or \
+            (self._get_parent_impl(function_node) is not None and
+             self._get_function_name(function_node, code) == DEPLOY_ENTRYPOINT)
+
+    def _is_receive_or_fallback(self, function_node: Node, code: str) -> bool:
+        """Check if a function is a `#[receive]`/`#[fallback]` entrypoint or a `receive`/`fallback` method"""
+        flags = self._get_item_flags(function_node, code)
+        if flags.receive or flags.fallback:
+            return True
+        return self._get_parent_impl(function_node) is not None and \
+            self._get_function_name(function_node, code) in ETHER_ENTRYPOINTS
+
+    def _get_abi_signature(self, function_node: Node, code: str) -> Optional[str]:
+        """
+        Get the Solidity signature a Stylus method is exported under, e.g.
+        `balanceOf(address)` for `fn balance_of(&self, owner: Address)`.
+
+        The name is the lowerCamelCase method name unless overridden with
+        `#[selector(name = "...")]`. Returns None if a parameter type has no
+        known ABI encoding.
+        """
+        name = to_camel_case(self._get_function_name(function_node, code))
+        sibling = function_node.prev_sibling
+        while sibling and sibling.type in ("attribute_item", "line_comment", "block_comment"):
+            match = SELECTOR_ATTRIBUTE_PATTERN.search(self._get_node_text(sibling, code))
+            if sibling.type == "attribute_item" and match:
+                name = match.group(1)
+            sibling = sibling.prev_sibling
+
+        types = []
+        parameters = function_node.child_by_field_name("parameters")
+        for parameter in parameters.named_children if parameters else []:
+            if parameter.type != "parameter":
+                continue
+            parameter_type = parameter.child_by_field_name("type")
+            abi_type = rust_type_to_abi(self._get_node_text(parameter_type, code)) if parameter_type else None
+            if abi_type is None:
+                return None
+            types.append(abi_type)
+        return f"{name}({','.join(types)})"
+
+    def _get_sol_functions(self, root: Node, code: str) -> List[Dict]:
+        """
+        Collect the functions defined inside `sol!` macros.
+
+        Returns:
+            Dicts with the function `name`, its `params` and `body` token trees,
+            the `modifiers` between the parameters and the body, and the `node`
+            the definition starts at
+        """
+        return [{"name": function.name, "node": function.node, "params": function.params,
+                 "body": function.body, "modifiers": function.modifiers}
+                for function in self._parse_sol_functions(root, code)]
+
+    def _parse_sol_functions(self, root: Node, code: str) -> List[SolFunction]:
+        """Get the parsed functions defined inside `sol!` macros below a node"""
+        ctx = self._get_shared_context(root, code)
+        return ctx.macro_functions() if ctx is not None else parse_sol_functions(root, code)
+
+    def _is_token_tree(self, node: Node, opening: str) -> bool:
+        """Check if a node is a token tree opened by the given delimiter"""
+        return is_token_tree(node, opening)
+
+    def _get_sol_parameter_names(self, params: Node, code: str) -> Set[str]:
+        """Get the names of a sol! parameter list such as `(address target, bytes calldata data)`"""
+        return set(sol_parameter_names(params, code))
+
+    def _split_sol_statements(self, body: Node) -> List[List[Node]]:
+        """Split a `{ ... }` token tree into statements, each a list of tokens, see `split_sol_statements`"""
+        return split_sol_statements(body)
@@ -0,0 +1,204 @@
+"""
+Detector for precision loss from dividing before multiplying in Stylus Rust contracts
+"""
+import re
+from typing import Dict, List, Optional
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.detectors.unchecked_arithmetic_detector import INTEGER_TYPE_PATTERN
+from stylus_analyzer.findings import Confidence, Fix, Severity
+
+FLOAT_TYPE_PATTERN = re.compile(r"\bf(32|64)\b")
+
+# Operators with the precedence of `*`, around which reordering a product could change its grouping
+MULTIPLICATIVE_OPERATORS = ("*", "/", "%")
+
+
+class DivisionBeforeMultiplicationDetector(BaseDetector):
+    """
+    Detector for integer expressions like `a / b * c`.
+
+    Integer division rounds towards zero, so multiplying its result amplifies
+    the rounding error. Writing `a * c / b` keeps the precision.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.HIGH
+    cwe = 682
+    node_types = ("binary_expression",)
+
+    documentation = DetectorDoc(
+        catches="Integer expressions multiplying the result of a division, such as `a / b * c`.",
+        rationale=(
+            "Integer division rounds down, and multiplying afterwards amplifies the lost remainder. This is a "
+            "common source of rounding errors in reward and fee calculations."
+        ),
+        vulnerable_example="""
+            let reward = amount / total_supply * reward_pool;
+        """,
+        fixed_example="""
+            let reward = amount * reward_pool / total_supply;
+        """,
+        references=["https://github.com/crytic/slither/wiki/Detector-Documentation"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="division_before_multiplication",
+            description="Detects integer division whose result is multiplied"
+        )
+
+    def start(self, tree: Tree, code: str) -> None:
+        """Collect the storage fields of the file the operands are typed against"""
+        self.storage_fields = self._collect_storage_fields(tree.root_node, code)
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if an integer multiplication in a Rust function multiplies a division result"""
+        if self._get_operator(node) != "*":
+            return
+        division = self._get_division_operand(node)
+        function_node = self._find_parent_function(node) if division is not None else None
+        if function_node is None:
+            return
+        local_types = self._get_local_type_texts(function_node, code)
+        if not self._is_integer_expression(node, code, local_types, self.storage_fields):
+            return
+        self._report(node, division, code, f"function '{self._get_function_name(function_node, code)}'", results)
+
+    def finish(self, tree: Tree, code: str, results) -> None:
+        """Check the statements of sol! function bodies"""
+        for function in self._get_sol_functions(tree.root_node, code):
+            for statement in self._split_sol_statements(function["body"]):
+                self._check_sol_tokens(statement, code, f"sol! function '{function['name']}'", results)
+
+    def _get_operator(self, node: Node) -> Optional[str]:
+        """Get the operator of a binary expression"""
+        if node.type != "binary_expression":
+            return None
+        operator = node.child_by_field_name("operator")
+        return operator.type if operator else None
+
+    def _unwrap_parentheses(self, node: Optional[Node]) -> Optional[Node]:
+        """Strip any parentheses around an expression"""
+        while node is not None and node.type == "parenthesized_expression" and node.named_children:
+            node = node.named_children[0]
+        return node
+
+    def _get_division_operand(self, node: Node) -> Optional[Node]:
+        """Get the division feeding directly into a multiplication, if any"""
+        for field in ("left", "right"):
+            operand = self._unwrap_parentheses(node.child_by_field_name(field))
+            if operand is not None and self._get_operator(operand) == "/":
+                return operand
+        return None
+
+    def _is_integer_expression(self, node: Node, code: str, local_types: Dict[str, str],
+                               storage_fields: Dict[str, str]) -> bool:
+        """Check that an expression works on integers and not floating point values"""
+        has_integer = False
+        for leaf in self._iter_nodes(node):
+            if leaf.type == "float_literal":
+                return False
+            type_text = None
+            if leaf.type == "identifier":
+                type_text = local_types.get(self._get_node_text(leaf, code))
+            elif leaf.type == "field_expression":
+                field = self._get_self_field(leaf, code)
+                type_text = storage_fields.get(field) if field else None
+            elif leaf.type == "type_cast_expression":
+                cast_type = leaf.child_by_field_name("type")
+                type_text = self._get_node_text(cast_type, code) if cast_type else None
+            elif leaf.type == "integer_literal":
+                has_integer = True
+            elif leaf.type == "scoped_identifier":
+                path = leaf.child_by_field_name("path")
+                type_text = self._get_node_text(path, code) if path else None
+            if type_text is None:
+                continue
+            if FLOAT_TYPE_PATTERN.search(type_text):
+                return False
+            if INTEGER_TYPE_PATTERN.search(type_text):
+                has_integer = True
+        return has_integer
+
+    def _report(self, multiplication: Node, division: Node, code: str, location: str, results) -> None:
+        """Report one multiplication of a division result"""
+        line_start, line_end = self._get_line_for_node(multiplication)
+        self._add_issue(line_start, line_end, self._get_column_for_node(multiplication, code),
+                        self._get_node_text(multiplication, code),
+                        self._get_node_text(division, code), location, results,
+                        self._get_fix(multiplication, division, code))
+
+    def _get_fix(self, multiplication: Node, division: Node, code: str) -> Optional[Fix]:
+        """
+        Get a fix multiplying before dividing: `a / b * c` and `c * (a / b)` become `a * c / b` and `c * a / b`
+
+        Products nested in another `*`, `/` or `%` are left alone, as are
+        chained divisions like `a / b / c * d`, where the rewrite would still
+        divide first.
+        """
+        if self._get_operator(multiplication.parent) in MULTIPLICATIVE_OPERATORS:
+            return None
+        dividend = division.child_by_field_name("left")
+        divisor = division.child_by_field_name("right")
+        if not dividend or not divisor or self._get_operator(self._unwrap_parentheses(dividend)) in ("/", "%"):
+            return None
+        left = multiplication.child_by_field_name("left")
+        right = multiplication.child_by_field_name("right")
+        dividend_text = self._get_node_text(dividend, code)
+        divisor_text = self._get_node_text(divisor, code)
+        if self._unwrap_parentheses(left).start_byte == division.start_byte:
+            replacement = f"{dividend_text} * {self._get_node_text(right, code)} / {divisor_text}"
+        else:
+            replacement = f"{self._get_node_text(left, code)} * {dividend_text} / {divisor_text}"
+        return Fix(description="Multiply before dividing",
+                   edits=[self._text_edit(code, multiplication.start_point, multiplication.end_point, replacement)],
+                   safe=True)
+
+    def _check_sol_tokens(self, tokens: List[Node], code: str, location: str, results) -> None:
+        """Check a sol! statement, and the parenthesized groups in it, for `/` followed by `*`"""
+        division_start = None
+        for index, token in enumerate(tokens):
+            if self._is_token_tree(token, "(") and (index == 0 or tokens[index - 1].type != "identifier"):
+                inner = token.children[1:-1]
+                self._check_sol_tokens(inner, code, location, results)
+                if any(child.type == "/" for child in inner) and not any(child.type in ("+", "-") for child in inner):
+                    if index + 1 < len(tokens) and tokens[index + 1].type == "*":
+                        self._report_tokens(tokens, index, index + 2, token, code, location, results)
+                    elif index > 0 and tokens[index - 1].type == "*":
+                        self._report_tokens(tokens, index - 2, index, token, code, location, results)
+            if token.type == "/":
+                division_start = index - 1
+            elif token.type in ("+", "-", "=", "+=", "-=", "*=", "/=", ",", "==", "!=", "<", ">", "<=", ">=", "&&", "||"):
+                division_start = None
+            elif token.type == "*" and division_start is not None and division_start >= 0:
+                self._report_tokens(tokens, division_start, min(index + 1, len(tokens) - 1),
+                                    None, code, location, results)
+                division_start = None
+
+    def _report_tokens(self, tokens: List[Node], start: int, end: int, group: Optional[Node],
+                       code: str, location: str, results) -> None:
+        """Report the tokens of a sol! multiplication of a division result"""
+        start = max(start, 0)
+        end = min(end, len(tokens) - 1)
+        first, last = tokens[start], tokens[end]
+        snippet = self._get_source_text(code, first.start_byte, last.end_byte)
+        division = self._get_node_text(group, code) if group is not None else snippet.rsplit("*", 1)[0].strip()
+        self._add_issue(first.start_point[0] + 1, last.end_point[0] + 1, self._get_column_for_node(first, code), snippet,
+                        division, location, results)
+
+    def _add_issue(self, line_start: int, line_end: int, column: int, snippet: str, division: str, location: str, results,
+                   fix: Optional[Fix] = None) -> None:
+        """Add a division before multiplication issue"""
+        results.add_issue(
+            issue_type="division_before_multiplication",
+            severity="Medium",
+            description=f"In {location}, the result of '{division}' is multiplied after the division has already rounded down, amplifying the precision loss.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation="Multiply before dividing, e.g. write `a * c / b` instead of `a / b * c`, and check that the multiplication can't overflow.",
+            fix=fix
+        )
@@ -0,0 +1,123 @@
+"""
+Detector for duplicate method definitions in Stylus Rust contracts
+"""
+from typing import Dict, List
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.abi_utils import compute_selector
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+
+class DuplicateFunctionDefinitionDetector(BaseDetector):
+    """
+    Detector for methods of one impl block that share a name, or that are
+    exported under the same ABI selector.
+
+    A repeated name fails to compile, and distinct Rust names such as
+    `balance_of` and `balanceOf` both export `balanceOf(address)`, so the
+    router can only dispatch to one of them. Selector clashes between
+    different signatures are reported by the selector collision detector.
+    """
+
+    default_severity = Severity.HIGH
+    default_confidence = Confidence.HIGH
+    cwe = 694
+
+    documentation = DetectorDoc(
+        catches=(
+            "Methods of an `impl` block sharing a name, and `#[public]` methods exported under the same Solidity "
+            "signature, such as `balance_of` and `balanceOf`."
+        ),
+        rationale=(
+            "Only one definition can be reached through the ABI, so callers may be routed to a different "
+            "implementation than intended, and duplicate names don't compile."
+        ),
+        vulnerable_example="""
+            #[public]
+            impl Token {
+                pub fn balance_of(&self, owner: Address) -> U256 { self.balances.get(owner) }
+                pub fn balanceOf(&self, owner: Address) -> U256 { self.shares.get(owner) }
+            }
+        """,
+        fixed_example="""
+            #[public]
+            impl Token {
+                pub fn balance_of(&self, owner: Address) -> U256 { self.balances.get(owner) }
+                pub fn shares_of(&self, owner: Address) -> U256 { self.shares.get(owner) }
+            }
+        """,
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="duplicate_function_definition",
+            description="Detects methods of an impl block sharing a name or an ABI selector"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect duplicate names and selectors within each impl block"""
+        for impl_node in self._iter_nodes(tree.root_node):
+            if impl_node.type != "impl_item":
+                continue
+            body = impl_node.child_by_field_name("body")
+            methods = [child for child in body.named_children if child.type == "function_item"] if body else []
+            impl_type = impl_node.child_by_field_name("type")
+            impl_name = self._get_node_text(impl_type, code) if impl_type else "unknown"
+
+            by_name: Dict[str, List[Node]] = {}
+            for method in methods:
+                by_name.setdefault(self._get_function_name(method, code), []).append(method)
+            for name, definitions in by_name.items():
+                for duplicate in definitions[1:]:
+                    self._add_issue(definitions[0], duplicate, code,
+                                    f"Method '{name}' is defined more than once in impl '{impl_name}'",
+                                    "The second definition fails to compile, and removing the wrong one "
+                                    "silently changes what callers get.", results)
+
+            if not self._get_item_flags(impl_node, code).external_callable:
+                continue
+            by_selector: Dict[str, List[Node]] = {}
+            signatures: Dict[str, str] = {}
+            for definitions in by_name.values():
+                method = definitions[0]
+                if not self._is_public_method(method, code):
+                    continue
+                signature = self._get_abi_signature(method, code)
+                if signature is None:
+                    continue
+                selector = compute_selector(signature)
+                if selector in signatures and signatures[selector] != signature:
+                    # Different signatures sharing a selector are a hash collision, not a duplicate
+                    continue
+                signatures[selector] = signature
+                by_selector.setdefault(selector, []).append(method)
+            for selector, definitions in by_selector.items():
+                first_name = self._get_function_name(definitions[0], code)
+                for duplicate in definitions[1:]:
+                    self._add_issue(definitions[0], duplicate, code,
+                                    f"Methods '{first_name}' and '{self._get_function_name(duplicate, code)}' of "
+                                    f"impl '{impl_name}' are both exported as '{signatures[selector]}' "
+                                    f"(selector {selector})",
+                                    "ABI dispatch can only route the selector to one of them.", results)
+
+    def _add_issue(self, first: Node, second: Node, code: str, summary: str, impact: str, results) -> None:
+        """Add a duplicate definition issue spanning both definitions"""
+        first_start, first_end = self._get_line_for_node(first)
+        second_start, second_end = self._get_line_for_node(second)
+        results.add_issue(
+            issue_type="duplicate_function_definition",
+            severity="High",
+            description=f"{summary}: lines {first_start}-{first_end} and lines {second_start}-{second_end}. {impact}",
+            line_start=first_start,
+            line_end=second_end,
+            column=self._get_column_for_node(first, code),
+            code_snippet=f"{self._get_signature_text(first, code)}\n...\n{self._get_signature_text(second, code)}",
+            recommendation="Remove or rename one of the definitions, or give it a distinct `#[selector(name = \"...\")]`."
+        )
+
+    def _get_signature_text(self, function_node: Node, code: str) -> str:
+        """Get the source of a function without its body"""
+        body = function_node.child_by_field_name("body")
+        end = body.start_byte if body else function_node.end_byte
+        return self._get_source_text(code, function_node.start_byte, end).strip()
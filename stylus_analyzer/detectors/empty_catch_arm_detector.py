@@ -0,0 +1,107 @@
+"""
+Detector for `match` expressions whose `Err` arm swallows the error in Stylus Rust contracts
+"""
+import re
+from typing import List, Optional
+from tree_sitter import Node
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+RESULT_ARM_PATTERN = re.compile(r"^(Ok|Err)\s*\(")
+
+COMMENT_TYPES = ("line_comment", "block_comment")
+
+
+class EmptyCatchArmDetector(BaseDetector):
+    """
+    Detector for `match` expressions on a `Result` whose `Err` arm does
+    nothing, such as `Err(_) => {}`.
+
+    The error vanishes and the function carries on as if the operation had
+    succeeded. An arm that logs, returns or reverts handles the error and
+    is not reported, and a `_` arm counts as the `Err` arm of a match
+    that only names `Ok`.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.HIGH
+    cwe = 390
+    node_types = ("match_expression",)
+
+    documentation = DetectorDoc(
+        catches=(
+            "`match` expressions on a `Result` whose `Err` arm is an empty block, `()` or a block holding only "
+            "comments."
+        ),
+        rationale=(
+            "The error vanishes, so the function carries on and commits its other changes as if the operation had "
+            "succeeded."
+        ),
+        vulnerable_example="""
+            match token.transfer(&mut *self, to, amount) {
+                Ok(_) => {}
+                Err(_) => {} // Ignore any errors
+            }
+        """,
+        fixed_example="""
+            token.transfer(&mut *self, to, amount)?;
+        """,
+        references=["https://doc.rust-lang.org/book/ch09-02-recoverable-errors-with-result.html"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="empty_catch_arm",
+            description="Detects match expressions whose Err arm silently swallows the error"
+        )
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if a match on a Result has an empty Err arm"""
+        arm = self._find_empty_error_arm(node, code)
+        if arm is None:
+            return
+        function_node = self._find_parent_function(node)
+        function_name = self._get_function_name(function_node, code) if function_node else "unknown"
+        value = node.child_by_field_name("value")
+        scrutinee = self._get_node_text(value, code) if value else "the result"
+        pattern = self._get_node_text(arm.child_by_field_name("pattern"), code)
+        line_start, line_end = self._get_line_for_node(node)
+        results.add_issue(
+            issue_type="empty_catch_arm",
+            severity="Medium",
+            description=f"The `{pattern}` arm of the match on `{scrutinee}` in function '{function_name}' "
+                        "is empty, so the error is silently swallowed.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=self._get_node_text(node, code),
+            recommendation="Propagate the error with `?`, or handle it explicitly in the `Err` arm, "
+                           "e.g. by returning an error or logging it."
+        )
+
+    def _find_empty_error_arm(self, match: Node, code: str) -> Optional[Node]:
+        """Get the arm catching the errors of a match on a Result if it does nothing"""
+        arms = self._get_arms(match)
+        patterns = [self._get_node_text(arm.child_by_field_name("pattern"), code).strip() for arm in arms]
+        if not any(RESULT_ARM_PATTERN.match(pattern) for pattern in patterns):
+            return None
+        error_arms = [arm for arm, pattern in zip(arms, patterns) if pattern.startswith("Err")]
+        if not error_arms:
+            # `Ok(..) => ..., _ => {}` catches the errors with the wildcard
+            error_arms = [arm for arm, pattern in zip(arms, patterns) if pattern == "_"]
+        return next((arm for arm in error_arms if self._is_empty(arm.child_by_field_name("value"), code)), None)
+
+    def _get_arms(self, match: Node) -> List[Node]:
+        """Get the arms of a match expression that have a pattern"""
+        body = match.child_by_field_name("body")
+        return [arm for arm in (body.named_children if body else [])
+                if arm.type == "match_arm" and arm.child_by_field_name("pattern") is not None]
+
+    def _is_empty(self, value: Optional[Node], code: str) -> bool:
+        """Check if an arm's value is `{}`, `()` or a block holding only comments"""
+        if value is None:
+            return False
+        if value.type == "block":
+            return all(child.type in COMMENT_TYPES for child in value.named_children)
+        return value.type == "unit_expression" or re.sub(r"\s", "", self._get_node_text(value, code)) == "()"
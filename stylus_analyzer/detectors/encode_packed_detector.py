@@ -3,7 +3,8 @@ Detector for unsafe encode_packed operations in Stylus Rust contracts
 """
 from tree_sitter import Node, Tree
 
-from stylus_analyzer.detectors.detector_base import BaseDetector
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
 
 
 class EncodePackedDetector(BaseDetector):
@@ -15,20 +16,42 @@ class EncodePackedDetector(BaseDetector):
     This is especially dangerous when hashing the result for signatures, authentication, etc.
     """
     
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.MEDIUM
+    cwe = 294
+    node_types = ("call_expression", "array_expression")
+
+    documentation = DetectorDoc(
+        catches=(
+            "`encode_packed` used on several dynamic values, such as strings or byte arrays, without delimiters."
+        ),
+        rationale=(
+            "Packed encoding concatenates dynamic values without their lengths, so different inputs can produce "
+            "the same bytes, e.g. `(\"a\", \"bc\")` and `(\"ab\", \"c\")`. Hashes of those bytes collide, which breaks "
+            "signatures and unique identifiers built on them."
+        ),
+        vulnerable_example="""
+            let id = keccak(<(String, String) as SolType>::abi_encode_packed(&(name, symbol)));
+        """,
+        fixed_example="""
+            let id = keccak(<(String, String) as SolType>::abi_encode(&(name, symbol)));
+        """,
+        references=["https://swcregistry.io/docs/SWC-133"],
+    )
+
     def __init__(self):
         super().__init__(
             name="unsafe_encode_packed",
             description="Detects potentially unsafe encode_packed operations with dynamic types"
         )
         
-    def detect(self, tree: Tree, code: str, results) -> None:
-        """Detect unsafe encode_packed calls in the contract"""
+    def start(self, tree: Tree, code: str) -> None:
+        """Forget the unsafe calls found in the previous file"""
         # Track found unsafe calls by location to avoid duplicates
         self.found_locations = set()
-        self._find_encode_packed_calls(tree.root_node, code, results)
-    
-    def _find_encode_packed_calls(self, node: Node, code: str, results) -> None:
-        """Recursively search for unsafe encode_packed calls in the contract"""
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if a call or an array expression packs dynamic values without delimiters"""
         # Look for method call expressions
         if node.type == "call_expression":
             # Get method name
@@ -55,6 +78,7 @@ class EncodePackedDetector(BaseDetector):
                             description=f"Potentially unsafe use of encode_packed in function '{function_name}'. When used with dynamic types like strings, it may lead to hash collisions.",
                             line_start=line_start,
                             line_end=line_end,
+                            column=self._get_column_for_node(node, code),
                             code_snippet=method_text,
                             recommendation="Use regular abi_encode instead, or ensure you're only using fixed-size types with encode_packed, or add delimiters between dynamic values."
                         )
@@ -78,6 +102,7 @@ class EncodePackedDetector(BaseDetector):
                             description=f"Potentially unsafe manual byte concatenation in function '{function_name}'. Concatenating dynamic types without delimiters can lead to collisions.",
                             line_start=line_start,
                             line_end=line_end,
+                            column=self._get_column_for_node(node, code),
                             code_snippet=method_text,
                             recommendation="Add a delimiter (e.g., a zero byte) between concatenated dynamic values."
                         )
@@ -104,13 +129,10 @@ class EncodePackedDetector(BaseDetector):
                         description=f"Manual byte packing without delimiters in function '{function_name}'. This can cause hash collisions with different inputs.",
                         line_start=line_start,
                         line_end=line_end,
+                        column=self._get_column_for_node(node, code),
                         code_snippet=parent_text,
                         recommendation="Add a delimiter (e.g., a zero byte) between concatenated string/byte values."
                     )
-        
-        # Process all children
-        for child in node.children:
-            self._find_encode_packed_calls(child, code, results)
     
     def _is_potentially_unsafe_usage(self, node: Node, code: str) -> bool:
         """Check if the encode_packed usage is potentially unsafe (using with dynamic types)"""
@@ -172,9 +194,3 @@ class EncodePackedDetector(BaseDetector):
     def _get_line_for_node(self, node: Node) -> tuple:
         """Get the line number for a node (1-indexed)"""
         return node.start_point[0] + 1, node.end_point[0] + 1
-    
-    def _get_node_text(self, node: Node, code: str) -> str:
-        """Get the text of a node from the code"""
-        if node.start_byte < 0 or node.end_byte > len(code) or node.start_byte >= node.end_byte:
-            return ""
-        return code[node.start_byte:node.end_byte] 
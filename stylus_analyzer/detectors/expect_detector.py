@@ -0,0 +1,107 @@
+"""
+Detector for potentially unsafe expect() calls in Stylus Rust contracts
+"""
+import re
+from typing import Optional
+from tree_sitter import Node
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+TEST_ATTRIBUTE_PATTERN = re.compile(r"^#\[\s*(test|cfg\s*\(\s*test\s*\))\s*\]$")
+
+
+class ExpectDetector(BaseDetector):
+    """
+    Detector for .expect() calls in public Stylus methods.
+
+    `.expect("msg")` panics exactly like `.unwrap()` when the value is
+    None/Err; the message does not turn the panic into a recoverable error.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.HIGH
+    cwe = 248
+    node_types = ("call_expression",)
+
+    documentation = DetectorDoc(
+        catches="`.expect(\"...\")` calls in public methods.",
+        rationale=(
+            "Like `.unwrap()`, `.expect()` panics on `None` or `Err`. The message doesn't make the failure "
+            "recoverable and doesn't reach the caller as a revert reason."
+        ),
+        vulnerable_example="""
+            pub fn claim(&mut self, id: U256) -> Result<(), Vec<u8>> {
+                let amount = self.rewards.get(id).checked_sub(self.claimed.get(id)).expect("overflow");
+                Ok(())
+            }
+        """,
+        fixed_example="""
+            pub fn claim(&mut self, id: U256) -> Result<(), Vec<u8>> {
+                let amount = self.rewards.get(id).checked_sub(self.claimed.get(id)).ok_or(b"overflow".to_vec())?;
+                Ok(())
+            }
+        """,
+        references=["https://doc.rust-lang.org/book/ch09-02-recoverable-errors-with-result.html"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="unsafe_expect",
+            description="Detects .expect() calls in public methods that can panic at runtime"
+        )
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if a call is an expect call in a public method"""
+        if self._get_method_name(node, code) != "expect":
+            return
+        function_node = self._find_parent_function(node)
+        if function_node is None or not self._is_public_method(function_node, code) or \
+                self._is_test_code(function_node, code):
+            return
+        if self._is_inside_unwrap_chain(node, code):
+            # The enclosing .unwrap() already reports this chain
+            return
+        arguments = node.child_by_field_name("arguments")
+        message = self._get_node_text(arguments.named_children[0], code) if arguments and \
+            arguments.named_children else '""'
+
+        function_name = self._get_function_name(function_node, code)
+        line_start, line_end = self._get_line_for_node(node)
+        results.add_issue(
+            issue_type="unsafe_expect",
+            severity="Medium",
+            description=f"Potentially unsafe call to .expect({message}) in function '{function_name}'. This panics like unwrap() if the value is None/Err.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=self._get_node_text(node, code),
+            recommendation="Return an error with the message instead, e.g. `.ok_or(...)?` or `.map_err(...)?`, rather than expect()."
+        )
+
+    def _is_inside_unwrap_chain(self, node: Node, code: str) -> bool:
+        """Check if a call is the receiver of a later .unwrap() in the same method chain"""
+        current = node
+        while current.parent and current.parent.type in ("field_expression", "call_expression", "try_expression"):
+            current = current.parent
+            if current.type == "call_expression" and self._get_method_name(current, code) == "unwrap":
+                return True
+        return False
+
+    def _is_test_code(self, node: Node, code: str) -> bool:
+        """Check if a node is inside a `#[test]` function, a `#[cfg(test)]` item or a `tests` module"""
+        current: Optional[Node] = node
+        while current:
+            if current.type == "mod_item":
+                name = current.child_by_field_name("name")
+                if name and self._get_node_text(name, code) == "tests":
+                    return True
+            if current.type in ("function_item", "mod_item", "impl_item"):
+                sibling = current.prev_sibling
+                while sibling and sibling.type in ("attribute_item", "line_comment", "block_comment"):
+                    if sibling.type == "attribute_item" and \
+                            TEST_ATTRIBUTE_PATTERN.match(self._get_node_text(sibling, code)):
+                        return True
+                    sibling = sibling.prev_sibling
+            current = current.parent
+        return False
@@ -0,0 +1,148 @@
+"""
+Detector for fallback functions that do more than accept the call in Stylus Rust contracts
+"""
+import re
+from typing import Optional
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+from stylus_analyzer.sol_parser import SolFunction
+
+# Statements a fallback may have before it is reported
+DEFAULT_MAX_STATEMENTS = 0
+
+# Tail expressions that only return an empty result, e.g. `Ok(Vec::new())`
+TRIVIAL_RETURN_PATTERN = re.compile(
+    r"^(?:\(\)|Ok\(\s*(?:\(\)|Vec::new\(\)|vec!\[\s*\]|Default::default\(\)|Bytes::new\(\))?\s*\))$"
+)
+
+
+class FallbackWithLogicDetector(BaseDetector):
+    """
+    Detector for `fallback` functions with logic in their body.
+
+    The fallback runs for every call whose selector matches no method, so
+    logic in it is reachable by calls the author never meant to accept.
+    `#[fallback]` entrypoints and `fallback` methods are reported when they
+    have more statements than the threshold, not counting a tail that only
+    returns `Ok(())` or empty bytes. In `sol!` contracts, `fallback()` is
+    reported the same way, so `fallback() external payable {}` is fine.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.MEDIUM
+
+    documentation = DetectorDoc(
+        catches=(
+            "`#[fallback]` entrypoints and `sol!` `fallback()` functions whose body does more than return, such as "
+            "writing storage, dispatching calls or emitting events."
+        ),
+        rationale=(
+            "Every call with an unknown selector, including typos and selectors clashing with a proxy's, ends up "
+            "in the fallback. Logic there runs on inputs nobody designed it for and is easy to trigger by accident."
+        ),
+        vulnerable_example="""
+            #[fallback]
+            #[payable]
+            pub fn fallback(&mut self, calldata: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+                let deposited = self.deposits.get(msg::sender());
+                self.deposits.setter(msg::sender()).set(deposited + msg::value());
+                Ok(Vec::new())
+            }
+        """,
+        fixed_example="""
+            #[payable]
+            pub fn deposit(&mut self) {
+                let deposited = self.deposits.get(msg::sender());
+                self.deposits.setter(msg::sender()).set(deposited + msg::value());
+            }
+
+            #[fallback]
+            #[payable]
+            pub fn fallback(&mut self, _calldata: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+                Ok(Vec::new())
+            }
+        """,
+        references=["https://docs.soliditylang.org/en/latest/contracts.html#fallback-function"],
+    )
+
+    options = {"max_statements": int}
+
+    def __init__(self, max_statements: int = DEFAULT_MAX_STATEMENTS):
+        """
+        Args:
+            max_statements: Statements a fallback may have before it is reported
+        """
+        super().__init__(
+            name="fallback_with_logic",
+            description="Detects fallback functions that contain logic beyond accepting the call"
+        )
+        self.max_statements = max_statements
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect Rust and sol! fallbacks with more statements than the threshold"""
+        for function_node in self._iter_nodes(tree.root_node):
+            if function_node.type == "function_item" and self._is_fallback(function_node, code):
+                count = self._count_statements(function_node, code)
+                if count > self.max_statements:
+                    line_start, line_end = self._get_line_for_node(function_node)
+                    body = function_node.child_by_field_name("body")
+                    snippet = self._get_source_text(code, function_node.start_byte, body.start_byte).strip() \
+                        if body is not None else self._get_node_text(function_node, code)
+                    self._add_issue(self._get_function_name(function_node, code), count, line_start, line_end,
+                                    self._get_column_for_node(function_node, code), snippet, results)
+
+        for function in self._parse_sol_functions(tree.root_node, code):
+            if function.name == "fallback" and len(function.statements) > self.max_statements:
+                self._check_sol_fallback(function, code, results)
+
+    def _is_fallback(self, function_node: Node, code: str) -> bool:
+        """Check if a function is a `#[fallback]` entrypoint or a `fallback` method"""
+        if self._get_item_flags(function_node, code).fallback:
+            return True
+        return self._get_parent_impl(function_node) is not None and \
+            self._get_function_name(function_node, code) == "fallback"
+
+    def _count_statements(self, function_node: Node, code: str) -> int:
+        """Count the statements of a function body, leaving out a tail that returns an empty result"""
+        body = function_node.child_by_field_name("body")
+        if body is None:
+            return 0
+        statements = [child for child in body.named_children if child.type not in ("line_comment", "block_comment")]
+        if statements and self._is_trivial_return(statements[-1], code):
+            statements = statements[:-1]
+        return len(statements)
+
+    def _is_trivial_return(self, node: Node, code: str) -> bool:
+        """Check if a statement only returns `()`, `Ok(())` or empty bytes"""
+        while node.type in ("expression_statement", "return_expression") and node.named_children:
+            node = node.named_children[0]
+        if node.type == "return_expression":
+            return True
+        return bool(TRIVIAL_RETURN_PATTERN.match(re.sub(r"\s+", "", self._get_node_text(node, code))))
+
+    def _check_sol_fallback(self, function: SolFunction, code: str, results) -> None:
+        """Report a sol! fallback with statements in its body"""
+        line_start, _ = self._get_line_for_node(function.node)
+        _, line_end = self._get_line_for_node(function.body)
+        snippet = self._get_source_text(code, function.node.start_byte, function.body.start_byte).strip()
+        self._add_issue(function.name, len(function.statements), line_start, line_end,
+                        self._get_column_for_node(function.node, code), snippet, results)
+
+    def _add_issue(self, name: str, count: int, line_start: int, line_end: int, column: int, snippet: Optional[str],
+                   results) -> None:
+        """Add an issue spanning the fallback"""
+        statements = "statement" if count == 1 else "statements"
+        results.add_issue(
+            issue_type="fallback_with_logic",
+            severity="Low",
+            description=f"Fallback '{name}' runs {count} {statements} for every call with an unknown selector. "
+                        "Logic in the fallback is reachable by mistyped calls and selector clashes.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation="Move the logic into a named method and keep the fallback empty, or make it revert "
+                           "calls it doesn't expect."
+        )
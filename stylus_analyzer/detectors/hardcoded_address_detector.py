@@ -0,0 +1,266 @@
+"""
+Detector for hardcoded address literals in Stylus Rust contracts
+"""
+import re
+from typing import Iterable, List, Optional, Set, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc, GUARD_MACROS, LOW_LEVEL_CALLS
+from stylus_analyzer.findings import Confidence, Severity
+
+HEX_ADDRESS_PATTERN = re.compile(r"^(0x)?([0-9a-fA-F]{40})$")
+
+# Ethereum precompiles 0x01-0x0a and the Arbitrum precompiles (ArbSys, ArbGasInfo, ..., NodeInterface, ArbDebug)
+KNOWN_SAFE_ADDRESSES = tuple(f"0x{value:040x}" for value in
+                             list(range(0x01, 0x0b)) + list(range(0x64, 0x73)) + [0xc8, 0xff])
+
+# Constructors and parsers turning a literal into an Address
+ADDRESS_CONSTRUCTORS = ("from", "new", "from_str", "from_slice", "parse_checksummed")
+
+# Calls whose address argument receives value or control
+TARGET_CALLS = LOW_LEVEL_CALLS + ("transfer", "transfer_from", "safe_transfer", "safe_transfer_from", "send")
+
+# sol! tokens marking a statement that moves value to its address
+SOL_TARGET_CALLS = ("transfer", "send", "call", "delegatecall", "staticcall")
+
+# Nodes where the search for an enclosing use of an address stops
+USE_BOUNDARY_TYPES = ("block", "let_declaration", "expression_statement", "function_item", "closure_expression")
+
+
+class HardcodedAddressDetector(BaseDetector):
+    """
+    Detector for literal 20-byte addresses, written as `address!("0x...")`,
+    `Address::from([...])` or a parsed hex string, that are compared against
+    in control flow or used as a transfer or call target.
+
+    Such literals are often leftovers from testing, or privileged accounts that
+    can't be rotated. The zero address, `Address::ZERO`, the canonical
+    Ethereum and Arbitrum precompiles and any allowlisted address are ignored.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.HIGH
+    cwe = 547
+    node_types = ("call_expression", "macro_invocation")
+
+    documentation = DetectorDoc(
+        catches=(
+            "Literal addresses compared against, used in a condition or passed as the target of a transfer or "
+            "call."
+        ),
+        rationale=(
+            "Hardcoded accounts are often test leftovers, or privileged addresses that can't be rotated once the "
+            "contract is deployed."
+        ),
+        vulnerable_example="""
+            if msg::sender() != address!("0x1234567890123456789012345678901234567890") {
+                return Err(b"not the admin".to_vec());
+            }
+        """,
+        fixed_example="""
+            if msg::sender() != self.admin.get() {
+                return Err(b"not the admin".to_vec());
+            }
+        """,
+    )
+
+    options = {"allowlist": list}
+
+    def __init__(self, allowlist: Optional[Iterable[str]] = None):
+        super().__init__(
+            name="hardcoded_address",
+            description="Detects hardcoded address literals used in control flow or as call targets"
+        )
+        self.allowlist = {address.lower() for address in KNOWN_SAFE_ADDRESSES + tuple(allowlist or ())}
+
+    def start(self, tree: Tree, code: str) -> None:
+        """Remember the root of the file, where the uses of addresses bound at the top level are searched"""
+        self.root = tree.root_node
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if an address literal drives control flow or a transfer"""
+        address = self._get_address_literal(node, code)
+        if address is None or self._is_ignored(address):
+            return
+        use = self._find_use(node, code)
+        if use is None:
+            name, scope = self._get_bound_name(node, code)
+            if name:
+                use = self._find_name_use(scope or self.root, name, code)
+        if use is None:
+            return
+        usage, use_node = use
+        function_node = self._find_parent_function(use_node)
+        location = f"in function '{self._get_function_name(function_node, code)}'" if function_node else ""
+        self._add_issue(node, code, address, f"{usage} {location}".strip(), results)
+
+    def finish(self, tree: Tree, code: str, results) -> None:
+        """Check the statements of sol! function bodies for hardcoded addresses"""
+        for function in self._get_sol_functions(tree.root_node, code):
+            for statement in self._split_sol_statements(function["body"]):
+                self._check_sol_statement(statement, function["name"], code, results)
+
+    def _get_address_literal(self, node: Node, code: str) -> Optional[str]:
+        """Get the normalized hex of an address literal expression, if the node is one"""
+        if node.type == "macro_invocation" and self._get_macro_name(node, code) == "address":
+            return self._find_hex_string(node, code)
+        if node.type != "call_expression":
+            return None
+        function = node.child_by_field_name("function")
+        arguments = node.child_by_field_name("arguments")
+        if not function or not arguments:
+            return None
+        if function.type == "scoped_identifier":
+            path = function.child_by_field_name("path")
+            name = function.child_by_field_name("name")
+            if not path or not name or self._get_node_text(path, code).split("::")[-1] != "Address" or \
+                    self._get_node_text(name, code) not in ADDRESS_CONSTRUCTORS:
+                return None
+            return self._find_hex_string(arguments, code) or self._find_byte_array(arguments, code)
+        if function.type == "generic_function":
+            # "0x...".parse::<Address>()
+            inner = function.child_by_field_name("function")
+            type_arguments = function.child_by_field_name("type_arguments")
+            receiver = inner.child_by_field_name("value") if inner and inner.type == "field_expression" else None
+            if receiver is not None and receiver.type == "string_literal" and type_arguments and \
+                    self._get_node_text(type_arguments, code).strip("<> ").split("::")[-1] == "Address":
+                return self._normalize(self._get_node_text(receiver, code).strip('"'))
+        return None
+
+    def _find_hex_string(self, node: Node, code: str) -> Optional[str]:
+        """Find a 40-digit hex string literal below a node"""
+        for child in self._iter_nodes(node):
+            if child.type == "string_literal":
+                address = self._normalize(self._get_node_text(child, code).strip('"'))
+                if address:
+                    return address
+        return None
+
+    def _find_byte_array(self, node: Node, code: str) -> Optional[str]:
+        """Find a 20-byte array literal, `[0x12, ...]` or `[0x12; 20]`, below a node"""
+        for child in self._iter_nodes(node):
+            if child.type != "array_expression":
+                continue
+            length = child.child_by_field_name("length")
+            values = [element for element in child.named_children
+                      if length is None or element.start_byte != length.start_byte]
+            if length is not None:
+                if self._parse_integer(self._get_node_text(length, code)) != 20 or len(values) != 1:
+                    continue
+                values = values * 20
+            if len(values) != 20:
+                continue
+            parsed = [self._parse_integer(self._get_node_text(value, code)) for value in values]
+            if all(value is not None and 0 <= value < 256 for value in parsed):
+                return "0x" + "".join(f"{value:02x}" for value in parsed)
+        return None
+
+    def _parse_integer(self, text: str) -> Optional[int]:
+        """Parse a Rust integer literal such as `0x1fu8` or `20`"""
+        text = re.sub(r"(u|i)(8|16|32|64|128|size)$", "", text.replace("_", ""))
+        try:
+            return int(text, 0)
+        except ValueError:
+            return None
+
+    def _normalize(self, text: str) -> Optional[str]:
+        """Normalize a hex address to lowercase with a 0x prefix"""
+        match = HEX_ADDRESS_PATTERN.match(text.strip())
+        return f"0x{match.group(2).lower()}" if match else None
+
+    def _is_ignored(self, address: str) -> bool:
+        """Check if an address is the zero address or known to be safe"""
+        return int(address, 16) == 0 or address in self.allowlist
+
+    def _find_use(self, node: Node, code: str) -> Optional[Tuple[str, Node]]:
+        """Find an enclosing comparison, condition or call target use of an expression"""
+        child, current = node, node.parent
+        while current is not None and current.type not in USE_BOUNDARY_TYPES:
+            if current.type == "binary_expression":
+                operator = current.child_by_field_name("operator")
+                if operator and operator.type in ("==", "!="):
+                    return "compared against", current
+            elif current.type in ("if_expression", "while_expression"):
+                condition = current.child_by_field_name("condition")
+                if condition and condition.start_byte == child.start_byte:
+                    return "used in a condition", current
+            elif current.type == "match_expression":
+                value = current.child_by_field_name("value")
+                if value and value.start_byte == child.start_byte:
+                    return "matched on", current
+            elif current.type == "arguments" and current.parent and current.parent.type == "call_expression":
+                if self._get_call_name(current.parent, code) in TARGET_CALLS:
+                    return "used as a call or transfer target", current.parent
+                return None
+            child, current = current, current.parent
+        return None
+
+    def _get_call_name(self, node: Node, code: str) -> str:
+        """Get the method or function name of a call expression"""
+        function = node.child_by_field_name("function")
+        if function is None:
+            return ""
+        if function.type == "field_expression":
+            field = function.child_by_field_name("field")
+            return self._get_node_text(field, code) if field else ""
+        return self._get_node_text(function, code).split("::")[-1]
+
+    def _get_bound_name(self, node: Node, code: str) -> Tuple[Optional[str], Optional[Node]]:
+        """Get the constant or local an address literal is bound to, and the scope it is visible in"""
+        parent = node.parent
+        if parent is None:
+            return None, None
+        value = parent.child_by_field_name("value")
+        if value is None or value.start_byte != node.start_byte:
+            return None, None
+        name = parent.child_by_field_name("name") if parent.type in ("const_item", "static_item") else \
+            parent.child_by_field_name("pattern") if parent.type == "let_declaration" else None
+        if name is None or name.type != "identifier":
+            return None, None
+        return self._get_node_text(name, code), self._find_parent_function(parent)
+
+    def _find_name_use(self, scope: Node, name: str, code: str) -> Optional[Tuple[str, Node]]:
+        """Find the first use of a bound address in a comparison, condition or call target"""
+        for node in self._iter_nodes(scope):
+            if node.type == "identifier" and self._get_node_text(node, code) == name:
+                use = self._find_use(node, code)
+                if use:
+                    return use
+            elif node.type == "macro_invocation" and self._get_macro_name(node, code) in GUARD_MACROS:
+                if any(token.type == "identifier" and self._get_node_text(token, code) == name
+                       for token in self._iter_nodes(node)):
+                    return "checked in a guard", node
+        return None
+
+    def _check_sol_statement(self, statement: List[Node], function_name: str, code: str, results) -> None:
+        """Check a sol! statement for address literals in conditions or value transfers"""
+        tokens: List[Node] = []
+        for token in statement:
+            tokens.extend(self._iter_nodes(token))
+        names: Set[str] = {self._get_node_text(token, code) for token in tokens if token.type == "identifier"}
+        if self._get_node_text(statement[0], code) in ("require", "if", "while"):
+            usage = "used in a condition"
+        elif names & set(SOL_TARGET_CALLS):
+            usage = "used as a call or transfer target"
+        else:
+            return
+        for token in tokens:
+            if token.type != "integer_literal":
+                continue
+            address = self._normalize(self._get_node_text(token, code))
+            if address and not self._is_ignored(address):
+                self._add_issue(token, code, address, f"{usage} in sol! function '{function_name}'", results)
+
+    def _add_issue(self, node: Node, code: str, address: str, usage: str, results) -> None:
+        """Add a hardcoded address issue at the literal"""
+        line_start, line_end = self._get_line_for_node(node)
+        results.add_issue(
+            issue_type="hardcoded_address",
+            severity="Low",
+            description=f"Hardcoded address {address} is {usage}. Literal addresses are often test leftovers or privileged accounts that can't be changed after deployment.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=self._get_node_text(node, code),
+            recommendation="Store the address in a storage field set at deployment and updatable by governance, or list it in the `allowlist` of `[options.hardcoded_address]` in .stylus-analyzer.toml if it is a known system contract."
+        )
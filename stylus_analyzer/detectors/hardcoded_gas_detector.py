@@ -0,0 +1,193 @@
+"""
+Detector for external calls with a hardcoded gas limit or value in Stylus Rust contracts
+"""
+import re
+from typing import Dict
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.detectors.outbound_transfer_gating_detector import SOL_TRANSFER_METHODS, ZERO_VALUE_PATTERN
+from stylus_analyzer.findings import Confidence, Severity
+
+# Call builders of the SDK, e.g. `Call::new_in(self)` or `RawCall::new_with_value(v)`
+CALL_BUILDER_PATTERN = re.compile(r"\b(?:Raw)?Call\s*::\s*new\w*\s*\(")
+
+# Builder methods setting the gas limit or the value of a call
+GAS_METHODS = ("gas",)
+VALUE_METHODS = ("value",)
+VALUE_CONSTRUCTORS = ("new_with_value",)
+
+# Integer literals, optionally wrapped into a `U256`, e.g. `2300`, `5_000u64` or `U256::from(1)`
+LITERAL_PATTERN = re.compile(
+    r"^(?:(?:U\d+|u\d+)\s*::\s*from\s*\(\s*)?(?:0x[0-9a-fA-F_]+|\d[\d_]*)(?:_?[ui]\d+|_?U\d+)?(?:\s*\))?$|"
+    r"^uint!\s*\(\s*\d[\d_]*_U\d+\s*\)$"
+)
+
+# Gas and value options of sol! calls, e.g. `to.call{gas: 2300, value: 1 ether}(...)`
+SOL_CALL_METHODS = ("call", "delegatecall", "staticcall")
+SOL_OPTION_PATTERN = re.compile(r"\b(gas|value)\s*:\s*(\d[\d_]*(?:\s*(?:wei|gwei|ether))?)\s*(?=[,}])")
+
+# Gas `transfer` and `send` forward to the recipient
+TRANSFER_STIPEND = 2300
+
+
+class HardcodedGasOrValueInCallDetector(BaseDetector):
+    """
+    Detector for call builders given a fixed gas limit or Ether value.
+
+    `.gas(...)` and `.value(...)` on `Call` and `RawCall` builders, and
+    `RawCall::new_with_value(...)`, are reported when their argument is an
+    integer literal or a constant set to one. In `sol!` functions, literal
+    `gas` and `value` options of `.call{...}` are reported, along with
+    `.transfer(amount)` and `.send(amount)`, which forward a fixed stipend
+    of 2300 gas. Gas costs change between chains and with repricing of
+    opcodes, so a limit that works today can make the call fail later.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.MEDIUM
+    cwe = 655
+    node_types = ("call_expression",)
+
+    documentation = DetectorDoc(
+        catches=(
+            "External calls built with a literal gas limit or value, such as `Call::new_in(self).gas(2300)`, and "
+            "`sol!` calls with literal `gas`/`value` options or through `.transfer()`/`.send()`."
+        ),
+        rationale=(
+            "The 2300 gas stipend of Solidity's `transfer` and `send` is not enough for recipients that are "
+            "contracts, and gas costs change with opcode repricing and between chains, so a fixed limit can make "
+            "a call fail after the fact. A hardcoded value is easy to get wrong when token decimals or prices change."
+        ),
+        vulnerable_example="""
+            pub fn pay(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+                call(Call::new_in(self).gas(2300).value(amount), to, &[])?;
+                Ok(())
+            }
+        """,
+        fixed_example="""
+            pub fn pay(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+                // Forwards all available gas; guard against reentrancy instead of limiting gas
+                call(Call::new_in(self).value(amount), to, &[])?;
+                Ok(())
+            }
+        """,
+        references=["https://swcregistry.io/docs/SWC-134",
+                    "https://consensys.io/diligence/blog/2019/09/stop-using-soliditys-transfer-now/"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="hardcoded_gas_or_value_in_call",
+            description="Detects external calls with a hardcoded gas limit or value"
+        )
+
+    def start(self, tree: Tree, code: str) -> None:
+        """Collect the constants of the file bound to a literal"""
+        self.constants = self._collect_literal_constants(tree.root_node, code)
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check a call builder for a literal gas limit or value"""
+        self._check_builder_call(node, code, self.constants, results)
+
+    def finish(self, tree: Tree, code: str, results) -> None:
+        """Check the calls in sol! function bodies"""
+        for function in self._get_sol_functions(tree.root_node, code):
+            self._check_sol_function(function, code, results)
+
+    def _collect_literal_constants(self, root: Node, code: str) -> Dict[str, str]:
+        """Map `const` and `static` items set to an integer literal to their value"""
+        constants = {}
+        for node in self._iter_nodes(root):
+            if node.type in ("const_item", "static_item"):
+                name = node.child_by_field_name("name")
+                value = node.child_by_field_name("value")
+                if name is not None and value is not None and \
+                        LITERAL_PATTERN.match(self._get_node_text(value, code).strip()):
+                    constants[self._get_node_text(name, code)] = self._get_node_text(value, code).strip()
+        return constants
+
+    def _check_builder_call(self, node: Node, code: str, constants: Dict[str, str], results) -> None:
+        """Check a `.gas(...)`/`.value(...)` builder call or `RawCall::new_with_value(...)` for a literal"""
+        function = node.child_by_field_name("function")
+        arguments = node.child_by_field_name("arguments")
+        if function is None or arguments is None or len(arguments.named_children) != 1:
+            return
+        if function.type == "field_expression":
+            method = function.child_by_field_name("field")
+            receiver = function.child_by_field_name("value")
+            if method is None or receiver is None or \
+                    not CALL_BUILDER_PATTERN.search(self._get_node_text(receiver, code)):
+                return
+            name = self._get_node_text(method, code)
+        elif function.type == "scoped_identifier" and \
+                CALL_BUILDER_PATTERN.search(f"{self._get_node_text(function, code)}("):
+            name = self._get_node_text(function, code).split("::")[-1].strip()
+        else:
+            return
+        if name not in GAS_METHODS + VALUE_METHODS + VALUE_CONSTRUCTORS:
+            return
+
+        argument = self._get_node_text(arguments.named_children[0], code).strip()
+        literal = argument if LITERAL_PATTERN.match(argument) else constants.get(argument)
+        if literal is None:
+            return
+        described = f"`{literal}`" if literal == argument else f"`{literal}` through `{argument}`"
+        if name in GAS_METHODS:
+            self._add_gas_issue(node, f"a fixed gas limit of {described}", self._get_node_text(node, code), code,
+                                results)
+        elif not ZERO_VALUE_PATTERN.match(literal):
+            self._add_value_issue(node, described, self._get_node_text(node, code), code, results)
+
+    def _check_sol_function(self, function: Dict, code: str, results) -> None:
+        """Check a sol! function for literal call options and `.transfer()`/`.send()` of Ether"""
+        for token in self._iter_nodes(function["body"]):
+            if token.type != "identifier" or token.prev_sibling is None or token.prev_sibling.type != ".":
+                continue
+            method = self._get_node_text(token, code)
+            following = token.next_sibling
+            if method in SOL_CALL_METHODS and following is not None and self._is_token_tree(following, "{"):
+                for option, literal in SOL_OPTION_PATTERN.findall(self._get_node_text(following, code)):
+                    snippet = self._get_source_text(code, token.start_byte, following.end_byte)
+                    if option == "gas":
+                        self._add_gas_issue(token, f"a fixed gas limit of `{literal}`", snippet, code, results)
+                    elif not re.match(r"^0+(\s|$)", literal):
+                        self._add_value_issue(token, f"`{literal}`", snippet, code, results)
+            elif method in SOL_TRANSFER_METHODS and following is not None and self._is_token_tree(following, "("):
+                # Token transfers such as `token.transfer(to, amount)` take the recipient as well
+                arguments = self._get_node_text(following, code)[1:-1]
+                if not arguments.strip() or "," in arguments:
+                    continue
+                self._add_gas_issue(token, f"the fixed {TRANSFER_STIPEND} gas stipend of `.{method}()`",
+                                    self._get_source_text(code, token.start_byte, following.end_byte), code, results)
+
+    def _add_gas_issue(self, node: Node, limit: str, snippet: str, code: str, results) -> None:
+        """Report a call made with a fixed gas limit"""
+        line_start, line_end = self._get_line_for_node(node)
+        results.add_issue(
+            issue_type="hardcoded_gas_or_value_in_call",
+            severity="Low",
+            description=f"External call with {limit}. Gas costs differ between chains and change when opcodes are "
+                        "repriced, so the call can run out of gas, especially when the recipient is a contract.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=snippet,
+            recommendation="Forward all available gas and protect against reentrancy with a guard or the "
+                           "checks-effects-interactions pattern instead of limiting the gas."
+        )
+
+    def _add_value_issue(self, node: Node, value: str, snippet: str, code: str, results) -> None:
+        """Report a call sending a hardcoded amount of Ether"""
+        line_start, line_end = self._get_line_for_node(node)
+        results.add_issue(
+            issue_type="hardcoded_gas_or_value_in_call",
+            severity="Low",
+            description=f"External call sending a hardcoded value of {value}. A fixed amount doesn't follow "
+                        "changes in prices or configuration and can't be corrected without a redeployment.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=snippet,
+            recommendation="Pass the value as a parameter or read it from a storage field the owner can update."
+        )
@@ -0,0 +1,165 @@
+"""
+Detector for Result and Option values of calls that are dropped in Stylus Rust contracts
+"""
+import re
+from typing import Dict, Optional, Set
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.detectors.unchecked_low_level_call_detector import RAW_CALLS
+from stylus_analyzer.findings import Confidence, Severity
+
+# Return types whose value carries a failure, possibly written with their path
+FALLIBLE_TYPE_PATTERN = re.compile(r"^(?:\w+\s*::\s*)*(Result|Option)\b")
+
+# SDK functions returning a Result; raw calls are left to the low-level call detector
+SDK_RESULT_FUNCTIONS = ("transfer_eth",)
+
+# Methods of the standard library and alloy types returning a Result or an Option
+RESULT_METHODS = ("try_into", "try_from", "parse")
+OPTION_METHODS = ("checked_add", "checked_sub", "checked_mul", "checked_div", "checked_rem", "checked_pow")
+
+
+class IgnoredResultDetector(BaseDetector):
+    """
+    Detector for calls returning a `Result` or `Option` whose value is
+    dropped in statement position.
+
+    Return types come from the functions and methods of the file, the
+    `sol_interface!` declarations, whose calls all return a `Result`, and
+    a seed of common SDK and standard library methods. `let _ = ...` is
+    an explicit choice and is reported as informational, and matches that
+    swallow the error are left to the empty catch arm detector.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.MEDIUM
+    cwe = 252
+    node_types = ("call_expression",)
+
+    documentation = DetectorDoc(
+        catches="Calls returning a `Result` or `Option` whose value is dropped in statement position.",
+        rationale=(
+            "The error is silently swallowed, so the function carries on and commits its other changes as if the "
+            "call had succeeded."
+        ),
+        vulnerable_example="""
+            pub fn deposit(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+                self.credit(msg::sender(), amount);
+                Ok(())
+            }
+        """,
+        fixed_example="""
+            pub fn deposit(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+                self.credit(msg::sender(), amount)?;
+                Ok(())
+            }
+        """,
+        references=["https://doc.rust-lang.org/std/result/index.html#results-must-be-used"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="ignored_result",
+            description="Detects Result and Option values of calls that are dropped in statement position"
+        )
+
+    def start(self, tree: Tree, code: str) -> None:
+        """Collect the methods, free functions and interfaces of the file that calls are resolved against"""
+        root = tree.root_node
+        self.impls = self._collect_impl_methods(root, code)
+        self.free_functions = {self._get_function_name(node, code): node for node in root.named_children
+                               if node.type == "function_item"}
+        self.interfaces = self._collect_interface_names(root, code)
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if a call in a function drops a Result or an Option"""
+        function_node = self._find_parent_function(node)
+        if function_node is None:
+            return
+        impl_node = self._get_parent_impl(function_node)
+        impl_type = impl_node.child_by_field_name("type") if impl_node else None
+        own_methods = self.impls.get(self._get_node_text(impl_type, code).split("<")[0].strip(), {}) \
+            if impl_type else {}
+        handles = self._get_function_fact(f"{self.id}.handles", function_node, code,
+                                          lambda: self._collect_interface_handles(function_node, code,
+                                                                                  self.interfaces))
+        kind = self._get_return_kind(node, code, self.impls, own_methods, self.free_functions, self.interfaces,
+                                     handles)
+        if kind is not None:
+            self._check_use(node, kind, self._get_function_name(function_node, code), code, results)
+
+    def _get_return_kind(self, call: Node, code: str, impls: Dict[str, Dict[str, Node]],
+                         own_methods: Dict[str, Node], free_functions: Dict[str, Node],
+                         interfaces: Set[str], handles: Set[str]) -> Optional[str]:
+        """Infer if a call returns a `Result` or an `Option`, or None if neither or unknown"""
+        function = call.child_by_field_name("function")
+        if function is None:
+            return None
+        if function.type == "field_expression":
+            receiver = function.child_by_field_name("value")
+            method_node = function.child_by_field_name("field")
+            if receiver is None or method_node is None:
+                return None
+            method = self._get_node_text(method_node, code)
+            receiver_text = self._get_node_text(receiver, code)
+            if receiver.type == "self":
+                return self._get_declared_kind(own_methods.get(method), code)
+            if receiver.type == "identifier" and receiver_text in handles:
+                return "Result"
+            if method in RESULT_METHODS:
+                return "Result"
+            if method in OPTION_METHODS:
+                return "Option"
+            return None
+        if function.type == "identifier":
+            name = self._get_node_text(function, code)
+            if name in SDK_RESULT_FUNCTIONS:
+                return "Result"
+            return None if name in RAW_CALLS else self._get_declared_kind(free_functions.get(name), code)
+        if function.type == "scoped_identifier":
+            path = self._get_node_text(function, code)
+            type_name, _, name = path.rpartition("::")
+            type_name = type_name.strip().split("::")[-1]
+            if name in RAW_CALLS:
+                return None
+            if name in SDK_RESULT_FUNCTIONS or type_name in interfaces:
+                return "Result"
+            methods = own_methods if type_name == "Self" else impls.get(type_name, {})
+            return self._get_declared_kind(methods.get(name), code)
+        return None
+
+    def _get_declared_kind(self, function_node: Optional[Node], code: str) -> Optional[str]:
+        """Get `Result` or `Option` if a function of the file is declared to return one"""
+        if function_node is None:
+            return None
+        return_type = function_node.child_by_field_name("return_type")
+        match = FALLIBLE_TYPE_PATTERN.match(self._get_node_text(return_type, code)) if return_type else None
+        return match.group(1) if match else None
+
+    def _check_use(self, call: Node, kind: str, function_name: str, code: str, results) -> None:
+        """Report a call whose value is dropped or discarded with `let _`"""
+        parent = call.parent
+        callee = self._get_node_text(call.child_by_field_name("function"), code)
+        consequence = "its error is silently ignored" if kind == "Result" else "a missing value goes unnoticed"
+        if parent.type == "expression_statement":
+            severity, how = "Medium", f"is dropped, so {consequence}"
+            recommendation = f"Propagate the {kind} with `?` or handle it explicitly."
+        elif parent.type == "let_declaration" and parent.child_by_field_name("pattern") is not None and \
+                parent.child_by_field_name("pattern").type == "_":
+            severity, how = "Info", "is explicitly discarded with `let _ =`"
+            recommendation = f"Make sure ignoring the {kind} is intended, or propagate it with `?`."
+        else:
+            return
+
+        line_start, line_end = self._get_line_for_node(parent)
+        results.add_issue(
+            issue_type="ignored_result",
+            severity=severity,
+            description=f"The {kind} returned by `{callee}` in function '{function_name}' {how}.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(parent, code),
+            code_snippet=self._get_node_text(parent, code),
+            recommendation=recommendation
+        )
@@ -0,0 +1,242 @@
+"""
+Detector for arithmetic mixing decimals-scaled and raw token amounts in Stylus Rust contracts
+"""
+import re
+from typing import Dict, List, Optional, Set, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+from stylus_analyzer.sol_parser import SolFunction
+
+# `10 ** decimals` in sol!, possibly with a cast such as `10 ** uint256(_decimals)`
+SOL_SCALE_PATTERN = re.compile(r"\b10\s*\*\*\s*(?:\w+\s*\(\s*)?\w*decimals", re.IGNORECASE)
+
+# The base of a Rust `.pow(decimals)` call: `10`, `10u128`, `10_u64` or `U256::from(10)`
+BASE_TEN_PATTERN = re.compile(r"^(?:(?:\w+\s*::\s*)*from\s*\(\s*)?10(?:_?[ui]\d+)?\s*\)?$")
+
+POW_METHODS = ("pow", "checked_pow", "saturating_pow", "wrapping_pow")
+
+# Methods adding or subtracting their argument to their receiver
+ADDITIVE_METHODS = ("checked_add", "checked_sub", "saturating_add", "saturating_sub",
+                    "wrapping_add", "wrapping_sub", "overflowing_add", "overflowing_sub")
+
+ADDITIVE_OPERATORS = ("+", "-", "+=", "-=")
+
+# The variable an indexed or member sol! target is rooted at, e.g. `balanceOf` for `balanceOf[to]`
+SOL_ROOT_PATTERN = re.compile(r"^\s*(\w+)")
+
+
+class InconsistentDecimalsMathDetector(BaseDetector):
+    """
+    Detector for storage values scaled by `10 ** decimals` that functions
+    later add or subtract unscaled parameters to.
+
+    A field is considered scaled when some write gives it a value built with
+    the `10 ** decimals` idiom, `U256::from(10).pow(...decimals...)` in Rust.
+    Adding a parameter that the function never scales to such a field mixes
+    whole tokens with base units, as in a constructor taking the supply in
+    whole tokens and a `mint` taking base units. Which unit a parameter is in
+    can't be known for sure, so findings carry Low confidence and are only
+    reported when the scaling idiom appears in the file.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.LOW
+    cwe = 682
+
+    documentation = DetectorDoc(
+        catches=(
+            "Storage values scaled by `10 ** decimals` that are later added to or subtracted from with a "
+            "parameter that is never scaled."
+        ),
+        rationale=(
+            "Mixing whole-token amounts with base units inflates or deflates balances and the supply by a factor "
+            "of `10 ** decimals`, a common accounting bug in token contracts."
+        ),
+        vulnerable_example="""
+            constructor(uint256 _initialSupply, uint8 _decimals) {
+                totalSupply = _initialSupply * 10 ** uint256(_decimals);
+            }
+
+            function mint(address to, uint256 value) public {
+                totalSupply += value;
+            }
+        """,
+        fixed_example="""
+            constructor(uint256 _initialSupply, uint8 _decimals) {
+                totalSupply = _initialSupply * 10 ** uint256(_decimals);
+            }
+
+            function mint(address to, uint256 value) public {
+                totalSupply += value * 10 ** uint256(decimals);
+            }
+        """,
+        references=["https://eips.ethereum.org/EIPS/eip-20#decimals"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="inconsistent_decimals_math",
+            description="Detects arithmetic mixing decimals-scaled storage values with raw amounts"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect raw parameters added to or subtracted from decimals-scaled values"""
+        self._check_rust(tree.root_node, code, results)
+        self._check_sol(self._parse_sol_functions(tree.root_node, code), code, results)
+
+    def _check_rust(self, root: Node, code: str, results) -> None:
+        """Check the Rust functions of the file"""
+        functions = [node for node in self._iter_nodes(root) if node.type == "function_item"]
+        scale_locals = {function.start_byte: self._collect_scale_locals(function, code) for function in functions}
+
+        # Fields written with a scaled value, with the line and function of the first such write
+        scaled: Dict[str, Tuple[int, str]] = {}
+        for function in functions:
+            setter_aliases = self._collect_setter_aliases(function, code)
+            for node in self._iter_nodes(function):
+                field = self._get_storage_write_field(node, code, setter_aliases)
+                if field and field not in scaled and self._scales(node, code, scale_locals[function.start_byte]):
+                    scaled[field] = (self._get_line_for_node(node)[0], self._get_function_name(function, code))
+        if not scaled:
+            return
+
+        for function in functions:
+            raw_parameters = self._collect_parameter_names(function, code) - \
+                self._collect_scaled_names(function, code, scale_locals[function.start_byte])
+            function_name = self._get_function_name(function, code)
+            for node in self._iter_nodes(function):
+                operands = self._get_additive_operands(node, code)
+                if operands is None:
+                    continue
+                for scaled_side, raw_side in (operands, operands[::-1]):
+                    field = self._get_self_field(scaled_side, code)
+                    if field in scaled and raw_side.type == "identifier" and \
+                            self._get_node_text(raw_side, code) in raw_parameters:
+                        line_start, line_end = self._get_line_for_node(node)
+                        self._add_issue(line_start, line_end, self._get_column_for_node(node, code),
+                                        self._get_node_text(node, code), field,
+                                        scaled[field], self._get_node_text(raw_side, code),
+                                        f"function '{function_name}'", results)
+                        break
+
+    def _is_scale_call(self, node: Node, code: str) -> bool:
+        """Check if a node is a `10.pow(decimals)`-style call"""
+        if node.type != "call_expression":
+            return False
+        function = node.child_by_field_name("function")
+        arguments = node.child_by_field_name("arguments")
+        if function is None or function.type != "field_expression" or arguments is None:
+            return False
+        method = function.child_by_field_name("field")
+        receiver = function.child_by_field_name("value")
+        return method is not None and receiver is not None and \
+            self._get_node_text(method, code) in POW_METHODS and \
+            bool(BASE_TEN_PATTERN.match(self._get_node_text(receiver, code))) and \
+            "decimals" in self._get_node_text(arguments, code).lower()
+
+    def _scales(self, node: Node, code: str, scale_locals: Set[str]) -> bool:
+        """Check if an expression is built with a scale factor, directly or through a local holding one"""
+        return any(self._is_scale_call(child, code) or
+                   (child.type == "identifier" and self._get_node_text(child, code) in scale_locals)
+                   for child in self._iter_nodes(node))
+
+    def _collect_scale_locals(self, function_node: Node, code: str) -> Set[str]:
+        """Collect the locals bound to a scale factor or a scaled value, e.g. `let unit = U256::from(10).pow(...)`"""
+        names: Set[str] = set()
+        for node in self._iter_nodes(function_node):
+            if node.type != "let_declaration":
+                continue
+            pattern = node.child_by_field_name("pattern")
+            value = node.child_by_field_name("value")
+            if pattern is not None and pattern.type == "mut_pattern" and pattern.named_children:
+                pattern = pattern.named_children[-1]
+            if pattern is not None and pattern.type == "identifier" and value is not None and \
+                    self._scales(value, code, names):
+                names.add(self._get_node_text(pattern, code))
+        return names
+
+    def _collect_scaled_names(self, function_node: Node, code: str, scale_locals: Set[str]) -> Set[str]:
+        """Collect the identifiers used in a statement of the function that also scales a value"""
+        names: Set[str] = set()
+        body = function_node.child_by_field_name("body")
+        for node in self._iter_nodes(body) if body else []:
+            if node.type in ("let_declaration", "expression_statement") and self._scales(node, code, scale_locals):
+                names.update(self._get_node_text(child, code) for child in self._iter_nodes(node)
+                             if child.type == "identifier")
+        return names
+
+    def _get_additive_operands(self, node: Node, code: str) -> Optional[Tuple[Node, Node]]:
+        """Get both operands of an addition or subtraction, written with an operator or a `checked_*` method"""
+        if node.type in ("binary_expression", "compound_assignment_expr"):
+            operator = node.child_by_field_name("operator")
+            left = node.child_by_field_name("left")
+            right = node.child_by_field_name("right")
+            if operator is not None and left is not None and right is not None and \
+                    self._get_node_text(operator, code) in ADDITIVE_OPERATORS:
+                return left, right
+        elif node.type == "call_expression":
+            function = node.child_by_field_name("function")
+            arguments = node.child_by_field_name("arguments")
+            if function is not None and function.type == "field_expression" and arguments is not None and \
+                    len(arguments.named_children) == 1:
+                method = function.child_by_field_name("field")
+                receiver = function.child_by_field_name("value")
+                if method is not None and receiver is not None and \
+                        self._get_node_text(method, code) in ADDITIVE_METHODS:
+                    return receiver, arguments.named_children[0]
+        return None
+
+    def _check_sol(self, functions: List[SolFunction], code: str, results) -> None:
+        """Check the functions of `sol!` contracts"""
+        scaled: Dict[str, Tuple[int, str]] = {}
+        for function in functions:
+            for statement in function.statements:
+                if statement.operator == "=" and statement.kind == "assignment" and \
+                        SOL_SCALE_PATTERN.search(statement.value):
+                    root = SOL_ROOT_PATTERN.match(statement.target)
+                    if root and root.group(1) not in scaled:
+                        scaled[root.group(1)] = (statement.line, function.name)
+        if not scaled:
+            return
+
+        for function in functions:
+            scaled_parameters = {name for statement in function.statements
+                                 if SOL_SCALE_PATTERN.search(statement.text)
+                                 for name in re.findall(r"\w+", statement.text)}
+            raw_parameters = set(function.parameters) - scaled_parameters
+            for statement in function.statements:
+                if statement.kind != "assignment" or not statement.target:
+                    continue
+                root = SOL_ROOT_PATTERN.match(statement.target)
+                if not root or root.group(1) not in scaled:
+                    continue
+                value = statement.value.strip()
+                if statement.operator in ("+=", "-="):
+                    raw = value
+                else:
+                    # `totalSupply = totalSupply + value`
+                    match = re.match(rf"^{re.escape(statement.target.strip())}\s*[+-]\s*(\w+)$", value)
+                    raw = match.group(1) if match and statement.operator == "=" else None
+                if raw in raw_parameters:
+                    self._add_issue(statement.line, statement.tokens[-1].end_point[0] + 1,
+                                    self._get_column_for_node(statement.tokens[0], code), statement.text, root.group(1), scaled[root.group(1)], raw, f"sol! function '{function.name}'", results)
+
+    def _add_issue(self, line_start: int, line_end: int, column: int, snippet: str, field: str,
+                   scaling: Tuple[int, str], parameter: str, location: str, results) -> None:
+        """Add an inconsistent decimals issue"""
+        scaled_line, scaled_in = scaling
+        results.add_issue(
+            issue_type="inconsistent_decimals_math",
+            severity="Low",
+            description=f"'{field}' is scaled by `10 ** decimals` on line {scaled_line} in '{scaled_in}', but the "
+                        f"{location} adds or subtracts the unscaled parameter '{parameter}'. Whole-token amounts "
+                        "and base units may be mixed.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation=f"Make sure '{parameter}' is in the same unit as '{field}', scaling it by "
+                           "`10 ** decimals` or documenting that it is given in base units."
+        )
@@ -0,0 +1,221 @@
+"""
+Detector for subtractions from unsigned integers without a preceding bounds check in Stylus Rust contracts
+"""
+import re
+from typing import Dict, List, Optional, Set
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import DetectorDoc
+from stylus_analyzer.detectors.unchecked_arithmetic_detector import UncheckedArithmeticDetector
+from stylus_analyzer.findings import Confidence, Severity
+from stylus_analyzer.sol_parser import SolFunction
+
+# Unsigned integer types, including Stylus storage wrappers and Solidity types
+UNSIGNED_TYPE_PATTERN = re.compile(
+    r"\b(U\d+|Uint|u8|u16|u32|u64|u128|usize|StorageU\d+|StorageUint|uint\d*)\b"
+)
+
+# Operators ordering two values
+ORDERING_OPERATORS = (">=", "<=", ">", "<")
+
+# Checks of a value against zero, which guard subtracting a literal such as `count - 1`
+ZERO_CHECK_PATTERN = re.compile(r">=|<=|[<>]|==|!=|\bis_zero\s*\(")
+
+# Names declared with an unsigned type in Solidity, e.g. `uint256 public totalSupply`,
+# `mapping(address => uint256) public balanceOf` or the parameter `uint256 value`
+SOL_UNSIGNED_DECLARATION = re.compile(
+    r"\buint\d*\s*\)*\s+(?:(?:public|private|internal|memory|calldata|storage|constant|immutable)\s+)*(\w+)"
+)
+
+# A Solidity subtraction such as `balanceOf[from] - value`, leaving out `-=` and `->`
+SOL_SUBTRACTION = re.compile(r"([\w.]+(?:\s*\[[^\[\]]+\])*)\s*-(?![-=>])\s*([\w.]+(?:\s*\[[^\[\]]+\])*)")
+
+
+class IntegerUnderflowOnSubtractionDetector(UncheckedArithmeticDetector):
+    """
+    Narrow variant of the unchecked arithmetic detector for subtractions
+    that can underflow.
+
+    `x - y` and `x -= y` are reported when `x` is an unsigned integer, such
+    as a `U256` local or parameter or a `StorageU256` field, and no `if`
+    condition or guard macro earlier in the function compares `x` with `y`.
+    Either order counts, so `if balance < amount { return Err(...) }` guards
+    `balance - amount` as much as `assert!(balance >= amount)`. Locals are
+    matched with the expression they were bound to, and a literal subtrahend
+    such as `count - 1` is guarded by any comparison of the minuend. In
+    `sol!` functions, subtractions from names declared as `uint` need an
+    earlier `require` or `if` comparing them to the subtrahend.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.HIGH
+    cwe = 191
+
+    integer_type_pattern = UNSIGNED_TYPE_PATTERN
+    supersedes = ("unchecked_arithmetic",)
+
+    documentation = DetectorDoc(
+        catches=(
+            "`-` and `-=` on `U256` and other unsigned integers whose minuend is never compared with the "
+            "subtrahend earlier in the function, such as `allowance[from][msg.sender] -= value`."
+        ),
+        rationale=(
+            "Unsigned values wrap around below zero, so spending more than a balance or allowance leaves a huge "
+            "value behind instead of reverting."
+        ),
+        vulnerable_example="""
+            let allowance = self.allowances.get(from).get(msg::sender());
+            self.allowances.setter(from).setter(msg::sender()).set(allowance - value);
+        """,
+        fixed_example="""
+            let allowance = self.allowances.get(from).get(msg::sender());
+            if allowance < value {
+                return Err(b"insufficient allowance".to_vec());
+            }
+            self.allowances.setter(from).setter(msg::sender()).set(allowance - value);
+        """,
+        references=["https://swcregistry.io/docs/SWC-101"],
+    )
+
+    def __init__(self):
+        super().__init__()
+        self.name = "integer_underflow_on_subtraction"
+        self.description = "Detects unsigned subtractions that are not preceded by a bounds check"
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if a subtraction of a Rust function follows a comparison of its operands"""
+        operator = node.child_by_field_name("operator")
+        left = node.child_by_field_name("left")
+        right = node.child_by_field_name("right")
+        if operator is None or operator.type not in ("-", "-=") or left is None or right is None:
+            return
+        function_node = self._find_parent_function(node)
+        if function_node is None or self._is_in_constant(node) or self._is_safe_annotated(node):
+            return
+        local_types = self._get_local_types(function_node, code)
+        if self._infer_operand(left, code, local_types, self.storage_fields) != "int":
+            return
+        bindings = self._get_function_fact(f"{self.id}.bindings", function_node, code,
+                                           lambda: self._collect_bindings(function_node, code))
+        guards = self._get_function_fact(f"{self.id}.guards", function_node, code,
+                                         lambda: [(guard.span[0], _compact(guard.condition))
+                                                  for guard in self._get_guards(function_node, code)])
+        minuend = _compact(self._get_node_text(left, code))
+        subtrahend = None if self._infer_operand(right, code, local_types, self.storage_fields) == "literal" \
+            else _compact(self._get_node_text(right, code))
+        earlier = [guard for start, guard in guards if start < node.start_byte]
+        if not self._is_guarded(earlier, _aliases(minuend, bindings),
+                                _aliases(subtrahend, bindings) if subtrahend else None):
+            line_start, line_end = self._get_line_for_node(node)
+            self._add_underflow_issue(minuend, subtrahend or self._get_node_text(right, code),
+                                      f"function '{self._get_function_name(function_node, code)}'", line_start,
+                                      line_end, self._get_column_for_node(node, code), self._get_node_text(node, code),
+                                      results)
+
+    def finish(self, tree: Tree, code: str, results) -> None:
+        """Check the subtractions of sol! functions from names declared as `uint`"""
+        unsigned = self._collect_sol_unsigned_names(tree.root_node, code)
+        for function in self._parse_sol_functions(tree.root_node, code):
+            self._check_sol_subtractions(function, unsigned, code, results)
+
+    def _collect_bindings(self, function_node: Node, code: str) -> Dict[str, str]:
+        """Map the locals of a function to the compact text of the expression they are bound to"""
+        bindings = {}
+        for node in self._iter_nodes(function_node):
+            if node.type != "let_declaration":
+                continue
+            pattern = node.child_by_field_name("pattern")
+            value = node.child_by_field_name("value")
+            if pattern is not None and value is not None and pattern.type in ("identifier", "mut_pattern"):
+                bindings[self._get_binding_name(pattern, code)] = _compact(self._get_node_text(value, code))
+        return bindings
+
+    def _is_guarded(self, guards: List[str], minuends: Set[str], subtrahends: Optional[Set[str]]) -> bool:
+        """
+        Check if a guard compares the minuend with the subtrahend, in either order
+
+        Args:
+            subtrahends: The subtrahend and its aliases, or None for a literal,
+                which any comparison of the minuend guards
+        """
+        for guard in guards:
+            for minuend in minuends:
+                mentioned = re.search(rf"(?<![\w.]){re.escape(minuend)}(?![\w(])", guard)
+                if mentioned is None:
+                    continue
+                if subtrahends is None:
+                    if ZERO_CHECK_PATTERN.search(guard):
+                        return True
+                    continue
+                for subtrahend in subtrahends:
+                    for first, second in ((minuend, subtrahend), (subtrahend, minuend)):
+                        operators = "|".join(re.escape(operator) for operator in ORDERING_OPERATORS)
+                        if re.search(rf"(?<![\w.]){re.escape(first)}(?:{operators}){re.escape(second)}(?![\w(])",
+                                     guard):
+                            return True
+        return False
+
+    def _collect_sol_unsigned_names(self, root: Node, code: str) -> Set[str]:
+        """Get the state variables, mappings, parameters and locals declared as `uint` in sol! macros"""
+        names = set()
+        for node in self._iter_nodes(root):
+            if node.type == "macro_invocation" and self._get_macro_name(node, code) in ("sol", "sol_storage"):
+                names.update(SOL_UNSIGNED_DECLARATION.findall(self._get_node_text(node, code)))
+        return names
+
+    def _check_sol_subtractions(self, function: SolFunction, unsigned: Set[str], code: str, results) -> None:
+        """Check the subtractions of a sol! function for an earlier `require` or `if` comparing their operands"""
+        guards = self._get_guards(function, code)
+        for statement in function.statements:
+            if statement.kind in ("require", "control"):
+                continue
+            earlier = [_compact(guard.condition) for guard in guards if guard.span[0] < statement.tokens[0].start_byte]
+            if statement.operator == "-=" and statement.target is not None and statement.value is not None:
+                subtractions = [(statement.target, statement.value)]
+            else:
+                subtractions = SOL_SUBTRACTION.findall(statement.value or statement.text)
+            for minuend, subtrahend in subtractions:
+                minuend, subtrahend = _compact(minuend), _compact(subtrahend)
+                name = re.match(r"\w+", minuend)
+                if name is None or name.group(0) not in unsigned:
+                    continue
+                literal = subtrahend.isdigit()
+                if self._is_guarded(earlier, {minuend}, None if literal else {subtrahend}) or \
+                        self._is_safe_annotated(statement.tokens[0]):
+                    continue
+                _, line_end = self._get_line_for_node(statement.tokens[-1])
+                snippet = self._get_source_text(code, statement.tokens[0].start_byte, statement.tokens[-1].end_byte)
+                self._add_underflow_issue(minuend, subtrahend, f"sol! function '{function.name}'", statement.line,
+                                          line_end, self._get_column_for_node(statement.tokens[0], code), snippet,
+                                          results)
+
+    def _add_underflow_issue(self, minuend: str, subtrahend: str, location: str, line_start: int, line_end: int,
+                             column: int, snippet: str, results) -> None:
+        """Add an issue at a subtraction without a bounds check"""
+        results.add_issue(
+            issue_type="integer_underflow_on_subtraction",
+            severity="Medium",
+            description=f"'{subtrahend}' is subtracted from '{minuend}' in {location} without first checking that "
+                        f"'{minuend}' is at least '{subtrahend}'. The unsigned result wraps around to a huge value "
+                        "instead of reverting.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation=f"Compare the values first, e.g. `if {minuend} < {subtrahend} {{ return Err(...) }}` or "
+                           f"`require({minuend} >= {subtrahend}, \"...\")`, or use `checked_sub`."
+        )
+
+
+def _compact(text: str) -> str:
+    """Remove the whitespace of a snippet so spacing differences don't matter"""
+    return "".join(text.split())
+
+
+def _aliases(text: str, bindings: Dict[str, str]) -> Set[str]:
+    """Get an expression along with the local it was bound to, or the expression a local was bound to"""
+    aliases = {text}
+    if text in bindings:
+        aliases.add(bindings[text])
+    aliases.update(name for name, value in bindings.items() if value == text)
+    return aliases
@@ -0,0 +1,176 @@
+"""
+Detector for ignored `bool` results of `sol_interface!` calls in Stylus Rust contracts
+"""
+from typing import Dict, Optional, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.abi_utils import to_camel_case
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+from stylus_analyzer.sol_parser import SolInterface, SolInterfaceFunction
+
+# Methods of the call's Result that only look at whether the call reverted
+SUCCESS_ONLY_METHODS = ("is_ok", "is_err", "ok")
+
+
+class MissingReturnBoolCheckOnInterfaceDetector(BaseDetector):
+    """
+    Detector for calls to `sol_interface!` methods declared to return `bool`
+    whose value is never looked at.
+
+    Each call site is correlated with the parsed signature of the interface
+    its handle is declared with, so only methods declared `returns (bool)`
+    are checked, under their Solidity or snake_case name. The value is
+    ignored when the call is a statement, when it is bound to `_` or to a
+    local that is never read, when it is matched with `Ok(_)` and when only
+    `.is_ok()` is checked. Propagating with `?` handles the revert but not a
+    `false` return.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.HIGH
+    cwe = 252
+    node_types = ("call_expression",)
+
+    documentation = DetectorDoc(
+        catches=(
+            "Calls to `sol_interface!` methods declared to return `bool` whose result is discarded, bound to `_`, "
+            "matched with `Ok(_)` or never read."
+        ),
+        rationale=(
+            "Tokens and other contracts often report failure by returning `false` without reverting. `?` only "
+            "propagates reverts, so the contract carries on as if the call had succeeded."
+        ),
+        vulnerable_example="""
+            pub fn pay(&mut self, token: IERC20, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+                token.transfer(&mut *self, to, amount)?;
+                Ok(())
+            }
+        """,
+        fixed_example="""
+            pub fn pay(&mut self, token: IERC20, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+                if !token.transfer(&mut *self, to, amount)? {
+                    return Err(b"transfer failed".to_vec());
+                }
+                Ok(())
+            }
+        """,
+        references=["https://swcregistry.io/docs/SWC-104"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="unchecked_interface_bool",
+            description="Detects ignored bool results of sol_interface! calls"
+        )
+
+    def start(self, tree: Tree, code: str) -> None:
+        """Collect the interfaces of the file declaring a method that returns `bool`"""
+        self.interfaces = [interface for interface in self._get_interfaces(tree.root_node, code)
+                           if any(function.returns_bool for function in interface.functions)]
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if a call to an interface method returning `bool` ignores the result"""
+        if not self.interfaces:
+            return
+        function_node = self._find_parent_function(node)
+        if function_node is None:
+            return
+        handles = self._get_function_fact(f"{self.id}.handles", function_node, code,
+                                          lambda: self._collect_handles(function_node, code))
+        declared = self._get_bool_method(node, code, handles)
+        if declared is None:
+            return
+        found = self._find_ignored_use(node, function_node, code)
+        if found is not None:
+            use_node, how = found
+            self._add_issue(use_node, declared, how, self._get_function_name(function_node, code), code, results)
+
+    def _collect_handles(self, function_node: Node, code: str) -> Dict[str, SolInterface]:
+        """Collect the interface handles of a function, by variable name"""
+        handles: Dict[str, SolInterface] = {}
+        for interface in self.interfaces:
+            for handle in self._collect_interface_handles(function_node, code, {interface.name}):
+                handles[handle] = interface
+        return handles
+
+    def _get_bool_method(self, node: Node, code: str,
+                         handles: Dict[str, SolInterface]) -> Optional[SolInterfaceFunction]:
+        """Get the declared interface method a call goes to, if it returns `bool`"""
+        if node.type != "call_expression":
+            return None
+        function = node.child_by_field_name("function")
+        if function is None or function.type != "field_expression":
+            return None
+        receiver = function.child_by_field_name("value")
+        method = function.child_by_field_name("field")
+        if receiver is None or method is None:
+            return None
+        interface = handles.get(self._get_node_text(receiver, code))
+        if interface is None:
+            return None
+        declared = interface.get_function(to_camel_case(self._get_node_text(method, code))) or \
+            interface.get_function(self._get_node_text(method, code))
+        return declared if declared is not None and declared.returns_bool else None
+
+    def _find_ignored_use(self, call: Node, function_node: Node, code: str) -> Optional[Tuple[Node, str]]:
+        """Find how a call's value is ignored, as the node to report and a description, or None if it is used"""
+        value = call.parent if call.parent is not None and call.parent.type == "try_expression" else call
+        parent = value.parent
+        if parent is None:
+            return None
+        if parent.type == "expression_statement":
+            return parent, "is discarded"
+        if parent.type == "let_declaration":
+            pattern = parent.child_by_field_name("pattern")
+            if pattern is None:
+                return None
+            name = self._get_node_text(pattern, code)
+            if pattern.type == "_" or name.startswith("_"):
+                return parent, f"is bound to `{name}`"
+            if pattern.type == "identifier" and not self._is_read_after(name, parent, function_node, code):
+                return parent, f"is bound to '{name}' but never read"
+            return None
+        if value is call and parent.type in ("match_expression", "let_condition"):
+            body = parent.child_by_field_name("body")
+            patterns = [arm.child_by_field_name("pattern") for arm in body.named_children if arm.type == "match_arm"] \
+                if parent.type == "match_expression" and body is not None else [parent.child_by_field_name("pattern")]
+            for pattern in patterns:
+                if pattern is not None and self._is_ok_wildcard(pattern, code):
+                    return parent, "is matched with `Ok(_)`"
+            return None
+        if value is call and parent.type == "field_expression":
+            field = parent.child_by_field_name("field")
+            method = self._get_node_text(field, code) if field is not None else ""
+            if method in SUCCESS_ONLY_METHODS:
+                return parent.parent if parent.parent is not None else parent, \
+                    f"is only checked for a revert with `.{method}()`"
+        return None
+
+    def _is_ok_wildcard(self, pattern: Node, code: str) -> bool:
+        """Check if a pattern is `Ok(_)` or `Ok(..)`, possibly inside a match arm pattern"""
+        node = pattern.named_children[0] if pattern.type == "match_pattern" and pattern.named_children else pattern
+        text = "".join(self._get_node_text(node, code).split())
+        return text in ("Ok(_)", "Ok(..)")
+
+    def _is_read_after(self, name: str, declaration: Node, function_node: Node, code: str) -> bool:
+        """Check if a local is used anywhere in the function after its declaration"""
+        return any(node.type == "identifier" and node.start_byte >= declaration.end_byte and
+                   self._get_node_text(node, code) == name for node in self._iter_nodes(function_node))
+
+    def _add_issue(self, node: Node, declared: SolInterfaceFunction, how: str, function_name: str, code: str,
+                   results) -> None:
+        """Add an ignored interface bool issue"""
+        line_start, line_end = self._get_line_for_node(node)
+        results.add_issue(
+            issue_type="unchecked_interface_bool",
+            severity="Medium",
+            description=f"The `bool` returned by `{declared.signature}` in function '{function_name}' {how}. "
+                        "A `false` return reports a failure without reverting and goes unnoticed.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=self._get_node_text(node, code),
+            recommendation=f"Check the returned `bool` and fail when it is `false`, e.g. "
+                           f"`if !{declared.name}(...)? {{ return Err(...); }}`."
+        )
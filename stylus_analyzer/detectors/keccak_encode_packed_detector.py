@@ -0,0 +1,247 @@
+"""
+Detector for Keccak256 hashes of packed dynamically-sized values in Stylus Rust contracts
+"""
+import re
+from typing import Dict, Optional
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# Types whose values have no fixed size, e.g. `String`, `SOLBytes` or `Vec<u8>`
+DYNAMIC_TYPE_PATTERN = re.compile(r"(?:\b|SOL)(?:String|Bytes|str)\b|\bVec\s*<")
+
+# Functions hashing their argument with Keccak256
+HASH_FUNCTIONS = ("keccak", "keccak256", "native_keccak256")
+
+# Constructors of Keccak256 hashers fed through `update`
+HASHER_PATTERN = re.compile(r"\bKeccak(?:256)?::(?:new|v256)\b")
+
+# Methods feeding bytes to a hasher
+HASHER_UPDATE_METHODS = ("update", "chain_update")
+
+# Constructors of dynamically-sized values
+DYNAMIC_CONSTRUCTOR_PATTERN = re.compile(r"\b(?:String|Vec|Bytes)::(?:from|new|with_capacity)$")
+
+# Methods returning a view of, or a copy of, their receiver's bytes
+BYTE_VIEW_METHODS = ("as_slice", "as_ref", "as_str", "to_vec", "to_owned", "clone", "into", "into_bytes")
+
+# Lets followed when resolving a local to the expression it was bound to
+MAX_BINDING_DEPTH = 3
+
+
+class KeccakOnEncodePackedDynamicDetector(BaseDetector):
+    """
+    Detector for Keccak256 hashes of the packed encoding of two or more
+    dynamically-sized values.
+
+    Packed encoding drops the lengths of strings, bytes and vectors, so
+    `("a", "bc")` and `("ab", "c")` pack to the same bytes and hash to the
+    same id. Both `SolType::abi_encode_packed` of a tuple type and manual
+    `[a.as_bytes(), b.as_bytes()].concat()` are followed into `keccak`,
+    `keccak256`, `Keccak256::digest` or a `Keccak256` hasher's `update`,
+    directly or through locals. A fixed-size value, such as a delimiter
+    byte, between every two dynamic values of a concatenation makes it safe.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.MEDIUM
+    cwe = 294
+    node_types = ("call_expression",)
+
+    documentation = DetectorDoc(
+        catches=(
+            "Keccak256 hashes of `abi_encode_packed` or `[..].concat()` over two or more strings, byte arrays or "
+            "vectors without a delimiter between them."
+        ),
+        rationale=(
+            "The packed bytes of adjacent dynamic values don't record where one ends and the next starts, so "
+            "distinct inputs hash to the same value. Ids, signatures and commitments built on such hashes can be "
+            "forged or made to collide."
+        ),
+        vulnerable_example="""
+            let id = keccak(<(SOLString, SOLString) as SolType>::abi_encode_packed(&(name, symbol)));
+        """,
+        fixed_example="""
+            let id = keccak(<(SOLString, SOLString) as SolType>::abi_encode(&(name, symbol)));
+        """,
+        references=["https://swcregistry.io/docs/SWC-133"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="keccak_encode_packed_dynamic",
+            description="Detects Keccak256 hashes of packed encodings of several dynamically-sized values"
+        )
+
+    def start(self, tree: Tree, code: str) -> None:
+        """Collect the type aliases of the file"""
+        self.type_aliases = {}
+        for node in self._iter_nodes(tree.root_node):
+            name = node.child_by_field_name("name") if node.type == "type_item" else None
+            if name is not None and node.child_by_field_name("type") is not None:
+                self.type_aliases[self._get_node_text(name, code)] = node.child_by_field_name("type")
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if a call in a function hashes two or more packed dynamic values"""
+        function_node = self._find_parent_function(node)
+        if function_node is None:
+            return
+        bindings = self._get_function_fact(f"{self.id}.bindings", function_node, code,
+                                           lambda: self._collect_bindings(function_node, code))
+        hashed = self._get_hashed_argument(node, code, bindings)
+        packed = self._resolve(hashed, code, bindings) if hashed is not None else None
+        if packed is None:
+            return
+        local_types = self._get_local_type_texts(function_node, code)
+        dynamic_count = self._count_packed_dynamic_values(packed, code, local_types, bindings, self.type_aliases)
+        if dynamic_count >= 2:
+            self._add_issue(node, packed, dynamic_count, function_node, code, results)
+
+    def _collect_bindings(self, function_node: Node, code: str) -> Dict[str, Node]:
+        """Map the locals of a function to the expression they are bound to"""
+        bindings = {}
+        for node in self._iter_nodes(function_node):
+            if node.type != "let_declaration":
+                continue
+            pattern = node.child_by_field_name("pattern")
+            value = node.child_by_field_name("value")
+            if pattern is not None and pattern.type == "mut_pattern" and pattern.named_children:
+                pattern = pattern.named_children[-1]
+            if pattern is not None and pattern.type == "identifier" and value is not None:
+                bindings[self._get_node_text(pattern, code)] = value
+        return bindings
+
+    def _get_hashed_argument(self, call: Node, code: str, bindings: Dict[str, Node]) -> Optional[Node]:
+        """Get the bytes a call hashes with Keccak256, or None if it isn't a hashing call"""
+        function = call.child_by_field_name("function")
+        arguments = call.child_by_field_name("arguments")
+        if function is None or arguments is None or not arguments.named_children:
+            return None
+        if function.type in ("identifier", "scoped_identifier"):
+            name = function.child_by_field_name("name") if function.type == "scoped_identifier" else function
+            path = function.child_by_field_name("path") if function.type == "scoped_identifier" else None
+            name_text = self._get_node_text(name, code)
+            if name_text in HASH_FUNCTIONS or (name_text == "digest" and path is not None and
+                                                self._get_node_text(path, code).endswith("Keccak256")):
+                return arguments.named_children[0]
+        elif function.type == "field_expression":
+            method = function.child_by_field_name("field")
+            receiver = function.child_by_field_name("value")
+            if method is None or receiver is None or self._get_node_text(method, code) not in HASHER_UPDATE_METHODS:
+                return None
+            if receiver.type == "identifier":
+                receiver = bindings.get(self._get_node_text(receiver, code), receiver)
+            if HASHER_PATTERN.search(self._get_node_text(receiver, code)):
+                return arguments.named_children[0]
+        return None
+
+    def _resolve(self, node: Node, code: str, bindings: Dict[str, Node]) -> Node:
+        """Follow references, byte views and locals back to the expression producing the bytes"""
+        depth = 0
+        while True:
+            if node.type in ("reference_expression", "parenthesized_expression") and node.named_children:
+                node = node.named_children[-1]
+            elif node.type == "call_expression" and self._get_method_name(node, code) in BYTE_VIEW_METHODS:
+                node = node.child_by_field_name("function").child_by_field_name("value")
+            elif node.type == "identifier" and depth < MAX_BINDING_DEPTH and \
+                    self._get_node_text(node, code) in bindings:
+                node = bindings[self._get_node_text(node, code)]
+                depth += 1
+            else:
+                return node
+
+    def _count_packed_dynamic_values(self, node: Node, code: str, local_types: Dict[str, str],
+                                     bindings: Dict[str, Node], type_aliases: Dict[str, Node]) -> int:
+        """Count the dynamic values an `abi_encode_packed` or `.concat()` packs next to each other"""
+        if node.type != "call_expression":
+            return 0
+        function = node.child_by_field_name("function")
+        method = self._get_method_name(node, code)
+        if method == "concat":
+            array = self._resolve(function.child_by_field_name("value"), code, bindings)
+            if array.type != "array_expression":
+                return 0
+            # A fixed-size value between two dynamic ones delimits them
+            longest, run = 0, 0
+            for element in array.named_children:
+                run = run + 1 if self._is_dynamic(element, code, local_types, bindings) else 0
+                longest = max(longest, run)
+            return longest
+        if method == "abi_encode_packed":
+            values = self._resolve(function.child_by_field_name("value"), code, bindings)
+            return self._count_dynamic_elements(values, code, local_types, bindings)
+        if function.type != "scoped_identifier" or \
+                self._get_node_text(function.child_by_field_name("name"), code) != "abi_encode_packed":
+            return 0
+        tuple_type = self._get_encoded_type(function.child_by_field_name("path"), code, type_aliases)
+        if tuple_type is not None and tuple_type.type == "tuple_type":
+            return sum(1 for element in tuple_type.named_children
+                       if DYNAMIC_TYPE_PATTERN.search(self._get_node_text(element, code)))
+        arguments = node.child_by_field_name("arguments")
+        if arguments is None or not arguments.named_children:
+            return 0
+        values = self._resolve(arguments.named_children[0], code, bindings)
+        return self._count_dynamic_elements(values, code, local_types, bindings)
+
+    def _get_encoded_type(self, path: Optional[Node], code: str, type_aliases: Dict[str, Node]) -> Optional[Node]:
+        """Get the type of `<(A, B) as SolType>::` or of a `type Alias = (A, B)` path"""
+        if path is None:
+            return None
+        if path.type == "bracketed_type" and path.named_children:
+            inner = path.named_children[0]
+            return inner.child_by_field_name("type") if inner.type == "qualified_type" else inner
+        return type_aliases.get(self._get_node_text(path, code))
+
+    def _count_dynamic_elements(self, values: Node, code: str, local_types: Dict[str, str],
+                                bindings: Dict[str, Node]) -> int:
+        """Count the dynamic elements of a tuple of values"""
+        if values.type != "tuple_expression":
+            return 0
+        return sum(1 for element in values.named_children if self._is_dynamic(element, code, local_types, bindings))
+
+    def _is_dynamic(self, node: Node, code: str, local_types: Dict[str, str], bindings: Dict[str, Node],
+                    depth: int = 0) -> bool:
+        """Check if an expression is a dynamically-sized value, or a view of its bytes"""
+        while node.type in ("reference_expression", "parenthesized_expression") and node.named_children:
+            node = node.named_children[-1]
+        if node.type == "identifier":
+            name = self._get_node_text(node, code)
+            if name in local_types:
+                return bool(DYNAMIC_TYPE_PATTERN.search(local_types[name]))
+            if name in bindings and depth < MAX_BINDING_DEPTH:
+                return self._is_dynamic(bindings[name], code, local_types, bindings, depth + 1)
+            return False
+        if node.type == "macro_invocation":
+            return self._get_macro_name(node, code) in ("format", "vec")
+        if node.type != "call_expression":
+            return False
+        method = self._get_method_name(node, code)
+        if method in ("as_bytes", "to_string"):
+            return True
+        if method in BYTE_VIEW_METHODS:
+            receiver = node.child_by_field_name("function").child_by_field_name("value")
+            return self._is_dynamic(receiver, code, local_types, bindings, depth)
+        function = node.child_by_field_name("function")
+        return function is not None and bool(DYNAMIC_CONSTRUCTOR_PATTERN.search(self._get_node_text(function, code)))
+
+    def _add_issue(self, hash_call: Node, packed: Node, dynamic_count: int, function_node: Node, code: str,
+                   results) -> None:
+        """Add an issue spanning the hashing call"""
+        packed_line, _ = self._get_line_for_node(packed)
+        line_start, line_end = self._get_line_for_node(hash_call)
+        function_name = self._get_function_name(function_node, code)
+        results.add_issue(
+            issue_type="keccak_encode_packed_dynamic",
+            severity="Medium",
+            description=f"Function '{function_name}' hashes the packed encoding of {dynamic_count} "
+                        f"dynamically-sized values from line {packed_line} with Keccak256. Packing drops their "
+                        "lengths, so different inputs such as (\"a\", \"bc\") and (\"ab\", \"c\") hash to the "
+                        "same value.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(hash_call, code),
+            code_snippet=self._get_node_text(hash_call, code),
+            recommendation="Hash the standard `abi_encode` of the values, which length-prefixes each of them, or "
+                           "put a fixed-size delimiter between every two dynamic values."
+        )
@@ -0,0 +1,147 @@
+"""
+Detector for collection lengths re-read in loop conditions in Stylus Rust contracts
+"""
+from typing import Optional
+from tree_sitter import Node
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Fix, Severity
+
+# Methods that change the length of a Vec or storage vector
+RESIZING_METHODS = ("push", "pop", "insert", "remove", "swap_remove", "truncate", "clear",
+                    "retain", "drain", "extend", "append", "resize", "erase", "grow", "shrink")
+
+
+class ArrayLengthInLoopConditionDetector(BaseDetector):
+    """
+    Detector for `.len()` calls evaluated on every iteration of a loop.
+
+    A `while` condition is re-evaluated each time around, and the length of a
+    storage vector is a storage read. `for` ranges are evaluated once, so
+    `for i in 0..self.items.len()` is not reported.
+    """
+
+    default_severity = Severity.INFO
+    default_confidence = Confidence.HIGH
+    node_types = ("while_expression",)
+
+    documentation = DetectorDoc(
+        catches="`.len()` calls in `while` conditions, which are evaluated again on every iteration.",
+        rationale=(
+            "On a storage vector each evaluation is a storage read. The length only needs to be read once when "
+            "the loop doesn't resize the collection."
+        ),
+        vulnerable_example="""
+            let mut i = 0;
+            while i < self.items.len() {
+                total += self.items.get(i).unwrap_or_default();
+                i += 1;
+            }
+        """,
+        fixed_example="""
+            let mut i = 0;
+            let items_len = self.items.len();
+            while i < items_len {
+                total += self.items.get(i).unwrap_or_default();
+                i += 1;
+            }
+        """,
+        references=["https://github.com/crytic/slither/wiki/Detector-Documentation"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="array_length_in_loop_condition",
+            description="Detects .len() calls re-evaluated in loop conditions"
+        )
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if the condition of a while loop calls .len() on a collection the loop doesn't resize"""
+        condition = node.child_by_field_name("condition")
+        body = node.child_by_field_name("body")
+        if not condition or not body:
+            return
+        for call in self._iter_nodes(condition):
+            receiver = self._get_len_receiver(call, code)
+            if receiver is None or self._is_resized_in(body, receiver, code):
+                continue
+            self._report(node, call, receiver, code, results)
+
+    def _get_len_receiver(self, node: Node, code: str) -> Optional[Node]:
+        """Get the receiver of a `.len()` call"""
+        if node.type != "call_expression":
+            return None
+        function = node.child_by_field_name("function")
+        if not function or function.type != "field_expression":
+            return None
+        method = function.child_by_field_name("field")
+        if not method or self._get_node_text(method, code) != "len":
+            return None
+        return function.child_by_field_name("value")
+
+    def _is_resized_in(self, body: Node, receiver: Node, code: str) -> bool:
+        """Check if the collection's length can change inside the loop body"""
+        receiver_text = self._get_node_text(receiver, code)
+        field = self._get_self_field(receiver, code)
+        for node in self._iter_nodes(body):
+            if field and self._get_storage_write_field(node, code) == field:
+                return True
+            if node.type == "call_expression":
+                function = node.child_by_field_name("function")
+                if function and function.type == "field_expression":
+                    method = function.child_by_field_name("field")
+                    value = function.child_by_field_name("value")
+                    if method and value and self._get_node_text(method, code) in RESIZING_METHODS and \
+                            self._get_node_text(value, code) == receiver_text:
+                        return True
+            elif node.type == "assignment_expression":
+                left = node.child_by_field_name("left")
+                if left and self._get_node_text(left, code) == receiver_text:
+                    return True
+        return False
+
+    def _report(self, loop: Node, call: Node, receiver: Node, code: str, results) -> None:
+        """Report a length call in a loop condition"""
+        function_name = self._get_function_name(self._find_parent_function(loop), code)
+        receiver_text = self._get_node_text(receiver, code)
+        if self._get_self_field(receiver, code):
+            cost = "reads the storage vector's length from storage on every iteration"
+        else:
+            cost = "is recomputed on every iteration"
+        line_start, line_end = self._get_line_for_node(call)
+        results.add_issue(
+            issue_type="array_length_in_loop_condition",
+            severity="Info",
+            description=f"Loop condition in function '{function_name}' calls '{receiver_text}.len()', which {cost}.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(call, code),
+            code_snippet=self._get_node_text(loop.child_by_field_name("condition"), code),
+            recommendation=f"Bind the length to a local before the loop, e.g. `let len = {receiver_text}.len();`, and compare against it.",
+            fix=self._get_fix(loop, call, receiver, code)
+        )
+
+    def _get_fix(self, loop: Node, call: Node, receiver: Node, code: str) -> Optional[Fix]:
+        """
+        Get a fix binding the length to a local before the loop, e.g. `let items_len = self.items.len();`
+
+        The local is named after the collection, and no fix is offered if the
+        function already uses that name or the loop isn't a statement of a block.
+        """
+        statement = loop.parent if loop.parent and loop.parent.type == "expression_statement" else loop
+        if not statement.parent or statement.parent.type != "block":
+            return None
+        names = [self._get_node_text(node, code) for node in self._iter_nodes(receiver)
+                 if node.type in ("identifier", "field_identifier") and self._get_node_text(node, code) != "self"]
+        if not names:
+            return None
+        local = f"{names[-1]}_len"
+        function_node = self._find_parent_function(loop) or statement.parent
+        if any(node.type == "identifier" and self._get_node_text(node, code) == local
+               for node in self._iter_nodes(function_node)):
+            return None
+        receiver_text = self._get_node_text(receiver, code)
+        return Fix(description=f"Bind '{receiver_text}.len()' to a local before the loop",
+                   edits=[self._insert_statement_before(code, statement, f"let {local} = {receiver_text}.len();"),
+                          self._text_edit(code, call.start_point, call.end_point, local)],
+                   safe=True)
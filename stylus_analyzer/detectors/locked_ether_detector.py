@@ -4,7 +4,11 @@ Detector for locked Ether vulnerabilities in Stylus Rust contracts
 from tree_sitter import Node, Tree
 from typing import Set, List, Optional
 
-from stylus_analyzer.detectors.detector_base import BaseDetector
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# sol! calls that send Ether out of the contract
+SOL_OUTBOUND_CALLS = ("transfer", "send", "selfdestruct")
 
 
 class LockedEtherDetector(BaseDetector):
@@ -15,6 +19,41 @@ class LockedEtherDetector(BaseDetector):
     potentially causing funds to become permanently inaccessible.
     """
     
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.MEDIUM
+
+    documentation = DetectorDoc(
+        catches="Contracts with `#[payable]` functions but no function that sends Ether out.",
+        rationale="Ether sent to the contract can never be withdrawn, so it is locked forever.",
+        vulnerable_example="""
+            #[public]
+            impl Vault {
+                #[payable]
+                pub fn deposit(&mut self) {
+                    let balance = self.balances.get(msg::sender());
+                    self.balances.setter(msg::sender()).set(balance + msg::value());
+                }
+            }
+        """,
+        fixed_example="""
+            #[public]
+            impl Vault {
+                #[payable]
+                pub fn deposit(&mut self) {
+                    let balance = self.balances.get(msg::sender());
+                    self.balances.setter(msg::sender()).set(balance + msg::value());
+                }
+
+                pub fn withdraw(&mut self) -> Result<(), Vec<u8>> {
+                    let balance = self.balances.get(msg::sender());
+                    self.balances.setter(msg::sender()).set(U256::ZERO);
+                    transfer_eth(msg::sender(), balance)
+                }
+            }
+        """,
+        references=["https://github.com/crytic/slither/wiki/Detector-Documentation"],
+    )
+
     def __init__(self):
         super().__init__(
             name="locked_ether",
@@ -51,6 +90,7 @@ class LockedEtherDetector(BaseDetector):
                     'name': function_name,
                     'line_start': line_start,
                     'line_end': line_end,
+                    'column': self._get_column_for_node(node, code),
                     'text': function_text[:100] + "..." if len(function_text) > 100 else function_text
                 })
         
@@ -91,6 +131,11 @@ class LockedEtherDetector(BaseDetector):
         """Check if a function can receive Ether"""
         function_name = self._get_function_name(node, code)
         
+        # receive/fallback entrypoints are covered by the receive/fallback variant of this detector,
+        # and deploy-time Ether by the payable constructor variant
+        if self._is_receive_or_fallback(node, code) or self._is_deploy_entrypoint(node, code):
+            return False
+        
         # Check for explicit payable attributes
         if "#[payable]" in function_text:
             return True
@@ -99,8 +144,8 @@ class LockedEtherDetector(BaseDetector):
         if "evm::msg_value()" in function_text or "msg_value()" in function_text:
             return True
             
-        # Check for receive/fallback function patterns
-        if function_name in ["receive", "fallback", "default"]:
+        # Check for default function patterns
+        if function_name == "default":
             return True
         
         # Check for value parameter in function signature
@@ -200,6 +245,21 @@ class LockedEtherDetector(BaseDetector):
         
         return False
     
+    def _has_sol_outbound_transfer(self, root: Node, code: str) -> bool:
+        """Check if any sol! function transfers Ether, e.g. `.transfer()` or `.call{value: ...}()`"""
+        for function in self._get_sol_functions(root, code):
+            for token in self._iter_nodes(function["body"]):
+                if token.type != "identifier":
+                    continue
+                name = self._get_node_text(token, code)
+                if name in SOL_OUTBOUND_CALLS:
+                    return True
+                following = token.next_sibling
+                if name == "call" and following is not None and self._is_token_tree(following, "{") and \
+                        "value" in self._get_node_text(following, code):
+                    return True
+        return False
+
     def _extract_sol_macro_sections(self, code: str) -> List[dict]:
         """Extract sections of code within sol! macros"""
         sections = []
@@ -236,11 +296,13 @@ class LockedEtherDetector(BaseDetector):
         if self.payable_functions:
             line_start = self.payable_functions[0]['line_start']
             line_end = self.payable_functions[0]['line_end']
+            column = self.payable_functions[0]['column']
             code_snippet = self.payable_functions[0]['text']
         else:
             # Fallback to start of file if no specific payable function found
             line_start = 1
             line_end = 1
+            column = 1
             code_snippet = "Contract can receive Ether but lacks withdrawal methods"
         
         results.add_issue(
@@ -249,6 +311,7 @@ class LockedEtherDetector(BaseDetector):
             description=f"Contract can receive Ether but lacks withdrawal methods. {payable_details}This may cause funds to become permanently locked.",
             line_start=line_start,
             line_end=line_end,
+            column=column,
             code_snippet=code_snippet,
             recommendation="Add withdrawal functions, access controls for fund management, or remove the ability to receive Ether if not needed. Consider implementing functions like 'withdraw()', 'emergency_withdraw()', or 'transfer_funds()'."
         )
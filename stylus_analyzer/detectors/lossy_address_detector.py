@@ -0,0 +1,261 @@
+"""
+Detector for addresses built from truncated integers or byte slices in Stylus Rust contracts
+"""
+import re
+from typing import Dict, Optional, Tuple
+from tree_sitter import Node
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.detectors.unsafe_cast_detector import PRIMITIVE_WIDTHS
+from stylus_analyzer.findings import Confidence, Severity
+
+# Bytes and bits of an address
+ADDRESS_BYTES = 20
+ADDRESS_BITS = 160
+
+# Constructors of `Address` taking integers or bytes
+ADDRESS_CONSTRUCTORS = ("from", "try_from", "from_slice", "left_padding_from", "right_padding_from", "from_word")
+
+# Integer types converted into an address on the way, e.g. `Address::from(U160::from(value))`
+INTERMEDIATE_TYPE_PATTERN = re.compile(r"^(?:U160|U256|B160|B256|Uint<[^>]*>|FixedBytes<[^>]*>)$")
+
+# Conversions that silently drop the bits that don't fit
+TRUNCATING_CONVERSIONS = ("wrapping_to", "wrapping_from", "saturating_to", "saturating_from")
+
+# Expressions holding a 32-byte word: `U256` bytes, hashes, and `B256` or `[u8; 32]` values
+WORD_EXPRESSION_PATTERN = re.compile(r"\bto_be_bytes\s*(?:::\s*<\s*32\s*>)?\s*\(\s*\)|\bkeccak256\s*\(")
+WORD_TYPE_PATTERN = re.compile(r"^&?\s*(?:B256|FixedBytes\s*<\s*32\s*>|\[\s*u8\s*;\s*32\s*\])$")
+
+# Fixed-size byte arrays of a known length, e.g. `[u8; 32]`
+BYTE_ARRAY_PATTERN = re.compile(r"^&?\s*\[\s*u8\s*;\s*(\d+)\s*\]$")
+
+# Locals followed back to the expression they were bound to
+MAX_BINDING_DEPTH = 8
+
+
+class LossyU256ToAddressDetector(BaseDetector):
+    """
+    Detector for `Address` values built from data that can't hold an
+    address.
+
+    An address is 20 bytes, so `Address::from`, `from_slice`, the padding
+    constructors and `from_word` are reported when they get an integer
+    narrowed below 160 bits, by an `as` cast, a `u64`-style parameter or
+    local, or a `wrapping_*`/`saturating_*` conversion, or a slice with
+    literal bounds that isn't 20 bytes long. Slicing the first 20 bytes of
+    a 32-byte word, such as `value.to_be_bytes::<32>()[..20]` or a hash, is
+    reported too, since words hold an address in their last 20 bytes.
+    General narrowing casts are left to the unsafe cast detector.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.MEDIUM
+    cwe = 704
+    node_types = ("call_expression",)
+
+    documentation = DetectorDoc(
+        catches=(
+            "`Address` values built from integers narrowed below 160 bits, from slices that aren't 20 bytes "
+            "long, or from the first 20 bytes of a 32-byte word."
+        ),
+        rationale=(
+            "The conversion succeeds and yields a valid-looking but different address, so tokens and permissions "
+            "go to an account nobody controls."
+        ),
+        vulnerable_example="""
+            pub fn set_recipient(&mut self, word: U256) {
+                let recipient = Address::from_slice(&word.to_be_bytes::<32>()[..20]);
+                self.recipient.set(recipient);
+            }
+        """,
+        fixed_example="""
+            pub fn set_recipient(&mut self, word: U256) {
+                let recipient = Address::from_word(word.into());
+                self.recipient.set(recipient);
+            }
+        """,
+        references=["https://docs.rs/alloy-primitives/latest/alloy_primitives/struct.Address.html"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="lossy_u256_to_address",
+            description="Detects addresses built from truncated integers or byte slices of the wrong length"
+        )
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if an `Address` construction in a Rust function narrows or slices its argument wrongly"""
+        argument = self._get_address_argument(node, code)
+        if argument is None:
+            return
+        function_node = self._find_parent_function(node)
+        if function_node is None:
+            return
+        local_types = self._get_local_type_texts(function_node, code)
+        problem = self._get_narrowing(argument, function_node, code, local_types) or \
+            self._get_bad_slice(argument, code, local_types)
+        if problem:
+            self._add_issue(node, problem, self._get_function_name(function_node, code), code, results)
+
+    def _get_address_argument(self, node: Node, code: str) -> Optional[Node]:
+        """Get the argument of a call such as `Address::from(...)` or `Address::from_slice(...)`"""
+        if node.type != "call_expression":
+            return None
+        function = node.child_by_field_name("function")
+        arguments = node.child_by_field_name("arguments")
+        if function is None or function.type != "scoped_identifier" or arguments is None or \
+                not arguments.named_children:
+            return None
+        path = function.child_by_field_name("path")
+        name = function.child_by_field_name("name")
+        if path is None or name is None or self._get_node_text(path, code).split("::")[-1] != "Address" or \
+                self._get_node_text(name, code) not in ADDRESS_CONSTRUCTORS:
+            return None
+        return arguments.named_children[0]
+
+    def _get_narrowing(self, node: Node, function_node: Node, code: str, local_types: Dict[str, str],
+                       depth: int = 0) -> Optional[str]:
+        """Describe how an integer turned into an address was narrowed below 160 bits, if it was"""
+        node = _strip_references(node)
+        if node.type == "type_cast_expression":
+            cast_type = node.child_by_field_name("type")
+            if cast_type is not None and self._get_node_text(cast_type, code) in PRIMITIVE_WIDTHS:
+                return f"`{self._get_node_text(node, code)}`, which is cast to a {self._get_node_text(cast_type, code)}"
+        elif node.type == "identifier" and depth <= MAX_BINDING_DEPTH:
+            name = self._get_node_text(node, code)
+            type_text = local_types.get(name, "").lstrip("&").strip()
+            if PRIMITIVE_WIDTHS.get(type_text, ADDRESS_BITS) < ADDRESS_BITS:
+                return f"'{name}', which is a {type_text}"
+            binding = self._find_binding(function_node, name, code)
+            if binding is not None:
+                return self._get_narrowing(binding, function_node, code, local_types, depth + 1)
+        elif node.type == "call_expression":
+            function = node.child_by_field_name("function")
+            arguments = node.child_by_field_name("arguments")
+            conversion = self._get_conversion(function, code)
+            if conversion is None:
+                return None
+            type_name, method = conversion
+            if method in TRUNCATING_CONVERSIONS:
+                return f"`{self._get_node_text(node, code)}`, which drops the bits that don't fit"
+            # `U160::from(value)` narrows when its argument does
+            if method == "from" and type_name is not None and INTERMEDIATE_TYPE_PATTERN.match(type_name) and \
+                    arguments is not None and arguments.named_children:
+                return self._get_narrowing(arguments.named_children[0], function_node, code, local_types, depth)
+        return None
+
+    def _get_conversion(self, function: Optional[Node], code: str) -> Optional[Tuple[Optional[str], str]]:
+        """Get the type and method of a conversion call, e.g. `("U160", "from")` or `(None, "wrapping_to")`"""
+        if function is None:
+            return None
+        if function.type == "generic_function":
+            function = function.child_by_field_name("function")
+        if function is None:
+            return None
+        if function.type == "scoped_identifier":
+            path = function.child_by_field_name("path")
+            name = function.child_by_field_name("name")
+            if path is None or name is None:
+                return None
+            return self._get_node_text(path, code).split("::")[-1].strip(), self._get_node_text(name, code)
+        if function.type == "field_expression":
+            method = function.child_by_field_name("field")
+            return (None, self._get_node_text(method, code)) if method is not None else None
+        return None
+
+    def _get_bad_slice(self, node: Node, code: str, local_types: Dict[str, str]) -> Optional[str]:
+        """Describe a slice with literal bounds that doesn't hold exactly the 20 bytes of an address, if it is one"""
+        node = _strip_references(node)
+        if node.type != "index_expression" or len(node.named_children) != 2:
+            return None
+        source, index = node.named_children
+        if index.type != "range_expression":
+            return None
+        bounds = self._get_range_bounds(index, code)
+        if bounds is None:
+            return None
+        start, end, inclusive = bounds
+        source_length = self._get_byte_length(source, code, local_types)
+        if end is None:
+            end = source_length
+        elif inclusive:
+            end += 1
+        if end is None:
+            return None
+        source_text = self._get_node_text(source, code)
+        if end - start != ADDRESS_BYTES:
+            return f"{max(end - start, 0)} bytes of `{source_text}`, while an address is {ADDRESS_BYTES} bytes"
+        if start == 0 and source_length == 32:
+            return f"the first {ADDRESS_BYTES} bytes of the 32-byte word `{source_text}`, which holds an address " \
+                   f"in its last {ADDRESS_BYTES} bytes"
+        return None
+
+    def _get_range_bounds(self, range_node: Node, code: str) -> Optional[Tuple[int, Optional[int], bool]]:
+        """
+        Get the literal bounds of a range such as `12..32`, `..20` or `4..=23`
+
+        Returns:
+            The start, the end or None when open, and whether the end is
+            inclusive, or None if a bound isn't an integer literal
+        """
+        operator = next((child for child in range_node.children if child.type in ("..", "..=")), None)
+        if operator is None:
+            return None
+        start, end = 0, None
+        for child in range_node.named_children:
+            if child.type != "integer_literal":
+                return None
+            value = int(re.sub(r"[^\d]", "", self._get_node_text(child, code)) or 0)
+            if child.start_byte < operator.start_byte:
+                start = value
+            else:
+                end = value
+        return start, end, operator.type == "..="
+
+    def _get_byte_length(self, node: Node, code: str, local_types: Dict[str, str]) -> Optional[int]:
+        """Get the length of a byte array or 32-byte word, if it is known"""
+        text = self._get_node_text(node, code)
+        if node.type == "identifier":
+            type_text = local_types.get(text, "").strip()
+            array = BYTE_ARRAY_PATTERN.match(type_text)
+            if array:
+                return int(array.group(1))
+            return 32 if WORD_TYPE_PATTERN.match(type_text) else None
+        return 32 if WORD_EXPRESSION_PATTERN.search(text) else None
+
+    def _find_binding(self, function_node: Node, name: str, code: str) -> Optional[Node]:
+        """Find the value of the `let` binding of a local"""
+        for node in self._iter_nodes(function_node):
+            if node.type != "let_declaration":
+                continue
+            pattern = node.child_by_field_name("pattern")
+            value = node.child_by_field_name("value")
+            if pattern is not None and pattern.type == "mut_pattern" and pattern.named_children:
+                pattern = pattern.named_children[-1]
+            if pattern is not None and value is not None and pattern.type == "identifier" and \
+                    self._get_node_text(pattern, code) == name:
+                return value
+        return None
+
+    def _add_issue(self, node: Node, problem: str, function_name: str, code: str, results) -> None:
+        """Add a lossy address issue at the `Address` constructor call"""
+        line_start, line_end = self._get_line_for_node(node)
+        results.add_issue(
+            issue_type="lossy_u256_to_address",
+            severity="Medium",
+            description=f"Function '{function_name}' builds an Address from {problem}. The result is a valid "
+                        "but different address.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=self._get_node_text(node, code),
+            recommendation="Build addresses from the last 20 bytes of a word, e.g. `Address::from_word(word)` or "
+                           "`&word[12..]`, and check that integers fit in 160 bits instead of casting them."
+        )
+
+
+def _strip_references(node: Node) -> Node:
+    """Get the expression inside `&`, `&mut` and parentheses"""
+    while node.type in ("reference_expression", "parenthesized_expression") and node.named_children:
+        node = node.named_children[-1]
+    return node
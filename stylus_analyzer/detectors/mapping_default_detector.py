@@ -0,0 +1,201 @@
+"""
+Detector for subtractions from mapping entries that may never have been set in Stylus Rust contracts
+"""
+import re
+from typing import Dict, List, Optional, Set, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# Storage types that map keys to values, defaulting missing keys to zero
+MAPPING_TYPE_PATTERN = re.compile(r"\bStorageMap\b|\bmapping\s*\(")
+
+# Methods of a storage map chain leading to the read entry
+ENTRY_METHODS = ("get", "getter", "setter")
+
+# Operators comparing an entry against the amount taken from it, or checking it for zero
+COMPARISON_PATTERN = re.compile(r">=|<=|[<>]|==|!=|\bis_zero\s*\(")
+
+# A Solidity mapping entry such as `allowance[from][msg.sender]` followed by a subtraction
+SOL_ENTRY_SUBTRACTION = re.compile(r"\b(\w+(?:\s*\[[^\[\]]+\])+)\s*-(?![->])")
+
+# A mapping entry read in Rust: the read node, the map field and the compact text of the read
+EntryRead = Tuple[Node, str, str]
+
+
+class MappingDefaultAssumptionDetector(BaseDetector):
+    """
+    Detector for mapping entries read and subtracted from without checking
+    them first.
+
+    Storage maps return zero for keys that were never written, so an entry
+    that has never been set looks like a real zero balance or allowance.
+    The check follows the value read from a map, directly or through a local
+    it is bound to, into the left side of a `-` or `-=`. A guard before the
+    subtraction comparing the entry or its local, e.g.
+    `if allowance < value { return Err(...) }`, counts as an existence
+    check. In `sol!` functions, subtractions from `map[key]` entries are
+    checked against earlier `require`/`if` statements on the same entry.
+    """
+
+    default_severity = Severity.INFO
+    default_confidence = Confidence.LOW
+    cwe = 191
+
+    documentation = DetectorDoc(
+        catches=(
+            "Values read from a storage map that are subtracted from without a preceding comparison of the "
+            "same entry, such as `allowance[from][msg.sender] -= value` for a spender that was never approved."
+        ),
+        rationale=(
+            "A key that was never set reads as zero. Subtracting from it silently treats \"never set\" as a real "
+            "value, which wraps an unchecked `U256` or reverts without a meaningful error."
+        ),
+        vulnerable_example="""
+            let allowance = self.allowances.get(from).get(msg::sender());
+            self.allowances.setter(from).setter(msg::sender()).set(allowance - value);
+        """,
+        fixed_example="""
+            let allowance = self.allowances.get(from).get(msg::sender());
+            if allowance < value {
+                return Err(b"insufficient allowance".to_vec());
+            }
+            self.allowances.setter(from).setter(msg::sender()).set(allowance - value);
+        """,
+        references=["https://docs.soliditylang.org/en/latest/types.html#mapping-types"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="mapping_default_assumption",
+            description="Detects subtractions from mapping entries that assume the entry was set"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect unguarded subtractions from mapping entries in Rust and sol! functions"""
+        maps = {name for name, type_text in self._collect_storage_fields(tree.root_node, code).items()
+                if MAPPING_TYPE_PATTERN.search(type_text)}
+        if maps:
+            for function_node in self._iter_nodes(tree.root_node):
+                if function_node.type == "function_item":
+                    self._check_function(function_node, maps, code, results)
+
+        for function in self._get_sol_functions(tree.root_node, code):
+            self._check_sol_function(function, code, results)
+
+    def _check_function(self, function_node: Node, maps: Set[str], code: str, results) -> None:
+        """Check the subtractions of a Rust function for entries read without a guard"""
+        body = function_node.child_by_field_name("body")
+        if body is None:
+            return
+        function_name = self._get_function_name(function_node, code)
+        guards: List[Tuple[int, str]] = []
+        # Locals bound to an entry read, by name
+        locals_read: Dict[str, EntryRead] = {}
+        for node in self._iter_nodes(body):
+            guard = self._get_guard_text(node, code)
+            if guard is not None and COMPARISON_PATTERN.search(guard):
+                guards.append((node.start_byte, "".join(guard.split())))
+            elif node.type == "let_declaration":
+                pattern = node.child_by_field_name("pattern")
+                value = node.child_by_field_name("value")
+                if pattern is not None and pattern.type == "mut_pattern" and pattern.named_children:
+                    pattern = pattern.named_children[-1]
+                read = self._get_entry_read(value, maps, code) if value is not None else None
+                if pattern is not None and pattern.type == "identifier" and read is not None:
+                    locals_read[self._get_node_text(pattern, code)] = read
+            elif node.type in ("binary_expression", "compound_assignment_expr"):
+                operator = node.child_by_field_name("operator")
+                left = node.child_by_field_name("left")
+                if operator is None or operator.type not in ("-", "-=") or left is None:
+                    continue
+                name = self._get_node_text(left, code) if left.type == "identifier" else None
+                read = locals_read.get(name) if name else self._get_entry_read(left, maps, code)
+                if read is None:
+                    continue
+                mentions = [name] if name else []
+                if not self._is_guarded(guards, node.start_byte, mentions, read[2]):
+                    self._add_issue(node, read, name, f"function '{function_name}'", code, results)
+
+    def _get_entry_read(self, node: Node, maps: Set[str], code: str) -> Optional[EntryRead]:
+        """Get the read of a storage map entry, such as `self.allowances.get(from).get(spender)`"""
+        if node.type != "call_expression" or self._get_method_name(node, code) != "get":
+            return None
+        current: Optional[Node] = node
+        while current is not None and current.type == "call_expression":
+            function = current.child_by_field_name("function")
+            if self._get_method_name(current, code) not in ENTRY_METHODS:
+                return None
+            current = function.child_by_field_name("value")
+        if current is None or current.type != "field_expression":
+            return None
+        receiver = current.child_by_field_name("value")
+        field = current.child_by_field_name("field")
+        if receiver is None or receiver.type != "self" or field is None:
+            return None
+        name = self._get_node_text(field, code)
+        if name not in maps:
+            return None
+        return node, name, "".join(self._get_node_text(node, code).split())
+
+    def _is_guarded(self, guards: List[Tuple[int, str]], position: int, names: List[str], entry: str) -> bool:
+        """Check if a comparison before a position mentions the entry or a local holding it"""
+        for start, guard in guards:
+            if start >= position:
+                continue
+            if entry in guard or any(re.search(rf"\b{re.escape(name)}\b", guard) for name in names):
+                return True
+        return False
+
+    def _check_sol_function(self, function: Dict, code: str, results) -> None:
+        """Check a sol! function for subtractions from mapping entries without an earlier guard"""
+        guards: List[Tuple[int, str]] = []
+        for statement in self._split_sol_statements(function["body"]):
+            text = self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte)
+            if self._get_node_text(statement[0], code) in ("require", "if"):
+                if COMPARISON_PATTERN.search(text):
+                    guards.append((statement[0].start_byte, "".join(text.split())))
+                continue
+            match = SOL_ENTRY_SUBTRACTION.search(text)
+            if match is None:
+                continue
+            entry = "".join(match.group(1).split())
+            if self._is_guarded(guards, statement[0].start_byte, [], entry):
+                continue
+            line_start, _ = self._get_line_for_node(statement[0])
+            _, line_end = self._get_line_for_node(statement[-1])
+            results.add_issue(
+                issue_type="mapping_default_assumption",
+                severity="Info",
+                description=f"In sol! function '{function['name']}', '{entry}' is subtracted from without checking "
+                            "it first. A key that was never set reads as zero, so the subtraction treats a missing "
+                            "entry as a real value.",
+                line_start=line_start,
+                line_end=line_end,
+                column=self._get_column_for_node(statement[0], code),
+                code_snippet=text,
+                recommendation=f"Check the entry first, e.g. `require({entry} >= amount, \"...\")`, so missing "
+                               "entries fail with a clear error."
+            )
+
+    def _add_issue(self, node: Node, read: EntryRead, local: Optional[str], location: str, code: str,
+                   results) -> None:
+        """Add a mapping default issue at a Rust subtraction"""
+        read_node, field, _ = read
+        read_line, _ = self._get_line_for_node(read_node)
+        subject = f"'{local}', read from '{field}' on line {read_line}," if local else \
+            f"The entry of '{field}' read here"
+        line_start, line_end = self._get_line_for_node(node)
+        results.add_issue(
+            issue_type="mapping_default_assumption",
+            severity="Info",
+            description=f"{subject} is subtracted from in {location} without checking it first. A key that was "
+                        "never set reads as zero, so the subtraction treats a missing entry as a real value.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=self._get_node_text(node, code),
+            recommendation="Compare the entry with the amount before subtracting and return an error when it is "
+                           "too small, or use `checked_sub`."
+        )
@@ -0,0 +1,243 @@
+"""
+Detector for state-mutating public methods without a caller check in Stylus Rust contracts
+"""
+import re
+from typing import Iterable, List, Optional, Set
+from tree_sitter import Node, Tree
+
+# SENDER_PATTERN is re-exported for detectors importing it from here
+from stylus_analyzer.detectors.detector_base import SENDER_PATTERN, BaseDetector, DetectorDoc, GUARD_MACROS
+from stylus_analyzer.findings import Confidence, Severity
+from stylus_analyzer.sol_parser import SolFunction
+
+# Functions anyone is expected to be able to call
+DEFAULT_ALLOWLIST = ("deposit", "receive", "fallback")
+
+# Storage fields and helpers that hold or enforce a privileged role
+ROLE_PATTERN = re.compile(r"owner|admin|role|governance|operator|minter|auth", re.IGNORECASE)
+GUARD_HELPER_PATTERN = re.compile(
+    r"^only_?|^(require|check|ensure|assert|is)_?\w*(owner|admin|role|governance|operator|minter|auth)",
+    re.IGNORECASE
+)
+
+# Solidity types starting a local variable declaration
+SOL_TYPE_PATTERN = re.compile(r"^(u?int\d*|address|bool|bytes\d*|string|mapping)$")
+
+SOL_ASSIGNMENT_OPERATORS = ("=", "+=", "-=", "*=", "/=", "%=", "|=", "&=", "^=")
+
+
+class MissingAccessControlDetector(BaseDetector):
+    """
+    Detector for public methods that mutate storage without checking the caller.
+
+    Without a comparison against `msg::sender()` or an owner/role field, any
+    account can call privileged functions such as `mint` or `burn`.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.MEDIUM
+    cwe = 284
+
+    documentation = DetectorDoc(
+        catches=(
+            "Public methods taking `&mut self` that write storage without first checking `msg::sender()` against "
+            "an owner or role, and public `sol!` functions without a `require` or modifier doing so."
+        ),
+        rationale=(
+            "Without a caller check anyone can call privileged functions, minting tokens, changing parameters or "
+            "taking over ownership."
+        ),
+        vulnerable_example="""
+            #[public]
+            impl Token {
+                pub fn mint(&mut self, to: Address, amount: U256) {
+                    let balance = self.balances.get(to);
+                    self.balances.setter(to).set(balance + amount);
+                }
+            }
+        """,
+        fixed_example="""
+            #[public]
+            impl Token {
+                pub fn mint(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+                    if msg::sender() != self.owner.get() {
+                        return Err(b"not the owner".to_vec());
+                    }
+                    let balance = self.balances.get(to);
+                    self.balances.setter(to).set(balance + amount);
+                    Ok(())
+                }
+            }
+        """,
+        references=["https://swcregistry.io/docs/SWC-105", "https://swcregistry.io/docs/SWC-106"],
+    )
+
+    options = {"allowlist": list}
+
+    def __init__(self, allowlist: Optional[Iterable[str]] = None):
+        super().__init__(
+            name="missing_access_control",
+            description="Detects state-mutating public methods with no caller check"
+        )
+        self.allowlist = set(DEFAULT_ALLOWLIST if allowlist is None else allowlist)
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect unprotected state mutations in public methods and sol! functions"""
+        for function_node in self._get_public_methods(tree.root_node, code):
+            self._check_function(function_node, code, results)
+
+        for function in self._parse_sol_functions(tree.root_node, code):
+            self._check_sol_function(function, code, results)
+
+    def _check_function(self, function_node: Node, code: str, results) -> None:
+        """Check a public Rust method for a caller check before its first storage write"""
+        function_name = self._get_function_name(function_node, code)
+        if function_name in self.allowlist or self._get_item_flags(function_node, code).constructor:
+            return
+        if not self._takes_mut_self(function_node, code):
+            return
+        body = function_node.child_by_field_name("body")
+        if not body:
+            return
+
+        setter_aliases = self._collect_setter_aliases(function_node, code)
+        sender_aliases = self._collect_sender_aliases(function_node, code)
+
+        first_write = None
+        for node in self._iter_nodes(body):
+            field = self._get_storage_write_field(node, code, setter_aliases)
+            if field:
+                first_write = (node, field)
+                break
+        if first_write is None:
+            return
+
+        write_node, field = first_write
+        if any(guard.span[0] < write_node.start_byte and self._is_caller_condition(guard.condition, sender_aliases)
+               for guard in self._get_guards(function_node, code)):
+            return
+        for node in self._iter_nodes(body):
+            if self._is_caller_keyed_access(node, code, sender_aliases):
+                # Callers can only touch their own balances or allowances
+                return
+            if node.start_byte < write_node.start_byte and self._is_guard_helper_call(node, code):
+                return
+
+        line_start, line_end = self._get_line_for_node(write_node)
+        self._add_issue(line_start, line_end, self._get_column_for_node(write_node, code),
+                        self._get_node_text(write_node, code), f"function '{function_name}'", field, results)
+
+    def _check_sol_function(self, function: SolFunction, code: str, results) -> None:
+        """Check a public sol! function for a caller check before its first state write"""
+        name = function.name
+        modifiers = function.modifiers
+        if name in self.allowlist or name == "constructor":
+            return
+        if not any(modifier in ("public", "external") for modifier in modifiers):
+            return
+        if any(modifier in ("view", "pure") for modifier in modifiers):
+            return
+        if any(GUARD_HELPER_PATTERN.search(modifier) for modifier in modifiers):
+            # Modifiers like onlyOwner or onlyRole(MINTER)
+            return
+
+        statements = self._split_sol_statements(function.body)
+        guards = self._get_guards(function, code)
+        first_write = None
+        for statement in statements:
+            target = self._get_sol_target(statement, code)
+            if "[" in target and SENDER_PATTERN.search(target):
+                # Callers can only touch their own balances or allowances
+                return
+            if first_write is None and self._is_sol_state_write(statement, code):
+                first_write = statement
+            guard = next((guard for guard in guards if guard.node == statement[0]), None)
+            if first_write is None and guard is not None and self._is_caller_condition(guard.condition, set()):
+                return
+        if first_write is None:
+            return
+
+        line_start, _ = self._get_line_for_node(first_write[0])
+        _, line_end = self._get_line_for_node(first_write[-1])
+        target = self._get_sol_target(first_write, code)
+        field = re.split(r"[\[.\s]", target, maxsplit=1)[0]
+        self._add_issue(line_start, line_end, self._get_column_for_node(first_write[0], code),
+                        self._get_source_text(code, first_write[0].start_byte, first_write[-1].end_byte),
+                        f"sol! function '{name}'", field, results)
+
+    def _add_issue(self, line_start: int, line_end: int, column: int, snippet: str, location: str, field: str,
+                   results) -> None:
+        """Add a missing access control issue"""
+        results.add_issue(
+            issue_type="missing_access_control",
+            severity="Medium",
+            description=f"Public {location} writes storage field '{field}' without checking the caller. Any account can call it.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation="Compare msg::sender() against an owner or role before mutating storage, or list the function in the `allowlist` of `[options.missing_access_control]` in .stylus-analyzer.toml if it is meant to be permissionless."
+        )
+
+    def _mentions_sender(self, text: str, sender_aliases: Set[str]) -> bool:
+        """Check if source text refers to the caller"""
+        if SENDER_PATTERN.search(text):
+            return True
+        return any(re.search(rf"\b{re.escape(alias)}\b", text) for alias in sender_aliases)
+
+    def _is_caller_check(self, node: Node, code: str, sender_aliases: Set[str]) -> bool:
+        """Check if a node compares the caller or calls a guard helper"""
+        condition = None
+        if node.type in ("if_expression", "while_expression"):
+            condition = node.child_by_field_name("condition")
+        elif node.type == "macro_invocation" and self._get_macro_name(node, code) in GUARD_MACROS:
+            condition = node
+        if condition is not None:
+            return self._is_caller_condition(self._get_node_text(condition, code), sender_aliases)
+        return self._is_guard_helper_call(node, code)
+
+    def _is_caller_condition(self, condition: str, sender_aliases: Set[str]) -> bool:
+        """Check if the condition of a guard compares the caller or a privileged role"""
+        return self._mentions_sender(condition, sender_aliases) or bool(ROLE_PATTERN.search(condition))
+
+    def _is_guard_helper_call(self, node: Node, code: str) -> bool:
+        """Check if a node calls a guard helper such as `self.only_owner()`"""
+        if node.type == "call_expression":
+            function = node.child_by_field_name("function")
+            if function and function.type == "field_expression":
+                receiver = function.child_by_field_name("value")
+                method = function.child_by_field_name("field")
+                if receiver and receiver.type == "self" and method and \
+                        GUARD_HELPER_PATTERN.search(self._get_node_text(method, code)):
+                    return True
+        return False
+
+    def _is_caller_keyed_access(self, node: Node, code: str, sender_aliases: Set[str]) -> bool:
+        """Check if a node accesses a storage map entry keyed by the caller"""
+        if node.type != "call_expression":
+            return False
+        function = node.child_by_field_name("function")
+        arguments = node.child_by_field_name("arguments")
+        if not function or not arguments or function.type != "field_expression":
+            return False
+        method = function.child_by_field_name("field")
+        receiver = function.child_by_field_name("value")
+        if not method or not receiver or self._get_node_text(method, code) not in ("get", "setter", "get_mut", "insert"):
+            return False
+        if not self._get_self_field(receiver, code):
+            return False
+        return self._mentions_sender(self._get_node_text(arguments, code), sender_aliases)
+
+    def _get_sol_target(self, statement: List[Node], code: str) -> str:
+        """Get the assignment target of a sol! statement, or an empty string"""
+        for index, token in enumerate(statement):
+            if token.type in SOL_ASSIGNMENT_OPERATORS:
+                return self._get_source_text(code, statement[0].start_byte, statement[index - 1].end_byte) if index else ""
+        return ""
+
+    def _is_sol_state_write(self, statement: List[Node], code: str) -> bool:
+        """Check if a sol! statement assigns to a state variable rather than declaring a local"""
+        if not self._get_sol_target(statement, code):
+            return False
+        first = self._get_node_text(statement[0], code)
+        return not SOL_TYPE_PATTERN.match(first) and first not in ("require", "if", "emit", "return", "for", "while")
@@ -0,0 +1,158 @@
+"""
+Detector for state changes that emit no event in Stylus Rust contracts
+"""
+from typing import Dict, Iterable, Optional, Set
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.detectors.reentrancy_detector import GUARD_FIELD_PATTERN
+from stylus_analyzer.findings import Confidence, Severity
+
+# Functions emitting an event log
+LOG_FUNCTIONS = ("log", "raw_log", "emit")
+
+# Names used for initializers before #[constructor] existed
+CONSTRUCTOR_NAMES = ("constructor", "init", "initialize")
+
+
+class MissingEventOnStateChangeDetector(BaseDetector):
+    """
+    Detector for public functions that write storage but emit no event.
+
+    Off-chain indexers and wallets rely on events to track state, so changes
+    without a log are invisible to them.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.MEDIUM
+    cwe = 778
+
+    documentation = DetectorDoc(
+        catches=(
+            "Public functions that write storage without calling `evm::log(...)`, and public `sol!` functions "
+            "without an `emit`."
+        ),
+        rationale=(
+            "Off-chain indexers, wallets and monitoring rely on events, so state changes without one are "
+            "invisible to them."
+        ),
+        vulnerable_example="""
+            pub fn set_fee(&mut self, fee: U256) {
+                self.fee.set(fee);
+            }
+        """,
+        fixed_example="""
+            pub fn set_fee(&mut self, fee: U256) {
+                self.fee.set(fee);
+                evm::log(FeeUpdated { fee });
+            }
+        """,
+        references=["https://github.com/crytic/slither/wiki/Detector-Documentation"],
+    )
+
+    def __init__(self, required_fields: Optional[Iterable[str]] = None):
+        """
+        Args:
+            required_fields: Fields whose changes must always emit an event. By
+                default a write to any storage field is reported.
+        """
+        super().__init__(
+            name="missing_event_on_state_change",
+            description="Detects public functions that change storage without emitting an event"
+        )
+        self.required_fields = set(required_fields) if required_fields is not None else None
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect public state changes without an event"""
+        storage_fields = self._collect_storage_fields(tree.root_node, code)
+        for function_node in self._get_public_methods(tree.root_node, code):
+            self._check_function(function_node, code, storage_fields, results)
+
+        for function in self._get_sol_functions(tree.root_node, code):
+            self._check_sol_function(function, code, results)
+
+    def _check_function(self, function_node: Node, code: str, storage_fields: Dict[str, str], results) -> None:
+        """Check a public Rust method for storage writes without a log"""
+        function_name = self._get_function_name(function_node, code)
+        if function_name in CONSTRUCTOR_NAMES or self._get_item_flags(function_node, code).constructor:
+            return
+        body = function_node.child_by_field_name("body")
+        if not body:
+            return
+
+        setter_aliases = self._collect_setter_aliases(function_node, code)
+        written: Set[str] = set()
+        for node in self._iter_nodes(body):
+            if self._is_log_call(node, code):
+                return
+            field = self._get_storage_write_field(node, code, setter_aliases)
+            if field:
+                written.add(field)
+
+        written = {field for field in written if not self._is_lock_field(field, storage_fields)}
+        self._report(function_node, function_name, written, code, results)
+
+    def _check_sol_function(self, function: Dict, code: str, results) -> None:
+        """Check a public sol! function for state writes without an emit"""
+        name = function["name"]
+        if name == "constructor" or not any(modifier in ("public", "external") for modifier in function["modifiers"]):
+            return
+        written: Set[str] = set()
+        for statement in self._split_sol_statements(function["body"]):
+            first = self._get_node_text(statement[0], code)
+            if first == "emit":
+                return
+            if first in ("require", "if", "return", "for", "while") or statement[0].type != "identifier":
+                continue
+            if len(statement) > 1 and any(token.type in ("=", "+=", "-=", "*=", "/=") for token in statement[1:]):
+                if statement[1].type in ("identifier", "primitive_type"):
+                    # A local variable declaration such as `uint256 x = ...`
+                    continue
+                written.add(first)
+        self._report(function["node"], name, written, code, results, end_node=function["body"])
+
+    def _report(self, node: Node, function_name: str, written: Set[str], code: str, results,
+                end_node: Optional[Node] = None) -> None:
+        """Report the written fields of a function that emits nothing"""
+        if self.required_fields is not None:
+            written &= self.required_fields
+        if not written:
+            return
+        line_start, line_end = self._get_line_for_node(node)
+        if end_node is not None:
+            _, line_end = self._get_line_for_node(end_node)
+        fields = ", ".join(f"'{field}'" for field in sorted(written))
+        results.add_issue(
+            issue_type="missing_event_on_state_change",
+            severity="Low",
+            description=f"Public function '{function_name}' changes {fields} without emitting an event. Off-chain indexers can't observe the change.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=self._get_node_text(node, code).split("\n")[0],
+            recommendation="Define an event for the state change and emit it with evm::log() after updating storage."
+        )
+
+    def _is_log_call(self, node: Node, code: str) -> bool:
+        """Check if a node emits an event log"""
+        if node.type == "macro_invocation":
+            return self._get_macro_name(node, code) == "emit"
+        if node.type != "call_expression":
+            return False
+        function = node.child_by_field_name("function")
+        if not function:
+            return False
+        if function.type == "field_expression":
+            name = function.child_by_field_name("field")
+        elif function.type == "scoped_identifier":
+            name = function.child_by_field_name("name")
+        elif function.type == "identifier":
+            name = function
+        else:
+            return False
+        return bool(name) and self._get_node_text(name, code) in LOG_FUNCTIONS
+
+    def _is_lock_field(self, field: str, storage_fields: Dict[str, str]) -> bool:
+        """Check if a field is a boolean reentrancy lock"""
+        field_type = storage_fields.get(field, "")
+        return bool(GUARD_FIELD_PATTERN.search(field)) and ("Bool" in field_type or "bool" in field_type)
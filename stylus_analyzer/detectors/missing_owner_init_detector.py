@@ -0,0 +1,227 @@
+"""
+Detector for owner fields the constructor never assigns in Stylus Rust contracts
+"""
+from typing import Dict, Iterable, List, Optional, Set
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import SENDER_PATTERN, BaseDetector, DetectorDoc, STORAGE_WRITE_METHODS
+from stylus_analyzer.findings import Confidence, Severity
+
+# Fields holding the privileged account of the contract
+DEFAULT_PRIVILEGED_FIELDS = ("owner", "admin", "governance")
+
+
+class MissingOwnerInitializationDetector(BaseDetector):
+    """
+    Detector for `owner`-style storage fields that no constructor assigns.
+
+    An owner left at the zero address makes every owner-gated function
+    unreachable, or reachable by whoever initializes it first. The constructor
+    is expected to assign `msg::sender()` or one of its parameters.
+    """
+
+    default_severity = Severity.HIGH
+    default_confidence = Confidence.MEDIUM
+    cwe = 665
+
+    documentation = DetectorDoc(
+        catches=(
+            "`owner`, `admin` and `governance` fields that no constructor assigns the deployer or a constructor "
+            "parameter to."
+        ),
+        rationale=(
+            "An owner left at the zero address makes owner-gated functions unusable, or lets the first caller of "
+            "an initializer claim the contract."
+        ),
+        vulnerable_example="""
+            #[constructor]
+            pub fn constructor(&mut self, fee: U256) {
+                self.fee.set(fee);
+            }
+        """,
+        fixed_example="""
+            #[constructor]
+            pub fn constructor(&mut self, fee: U256) {
+                self.owner.set(msg::sender());
+                self.fee.set(fee);
+            }
+        """,
+        references=["https://swcregistry.io/docs/SWC-105"],
+    )
+
+    def __init__(self, privileged_fields: Optional[Iterable[str]] = None):
+        super().__init__(
+            name="missing_owner_initialization",
+            description="Detects owner fields the constructor never assigns to the sender or a parameter"
+        )
+        self.privileged_fields = set(DEFAULT_PRIVILEGED_FIELDS if privileged_fields is None else privileged_fields)
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect privileged fields left unassigned at deployment"""
+        methods = self._collect_impl_methods(tree.root_node, code)
+        for struct in self._collect_storage_structs(tree.root_node, code):
+            fields = [field for field in struct["fields"] if field in self.privileged_fields]
+            if not fields:
+                continue
+            struct_methods = methods.get(struct["name"], {})
+            assigned: Set[str] = set()
+            for method in struct_methods.values():
+                if self._is_constructor(method, code):
+                    parameters = set(self._get_ordered_parameters(method, code)) - {""}
+                    assigned |= self._collect_owner_assignments(method, code, struct_methods, set(), parameters)
+            for field in fields:
+                if field not in assigned:
+                    self._add_issue(struct["node"], struct["body"], code, struct["name"], field, results)
+
+        for contract in self._collect_sol_contracts(tree.root_node, code):
+            self._check_sol_contract(contract, code, results)
+
+    def _collect_owner_assignments(self, function_node: Node, code: str, methods: Dict[str, Node],
+                                   visited: Set[str], trusted_parameters: Set[str]) -> Set[str]:
+        """Collect the fields assigned the sender or a constructor parameter, following `self` helpers"""
+        visited.add(self._get_function_name(function_node, code))
+        body = function_node.child_by_field_name("body")
+        if not body:
+            return set()
+        trusted = self._collect_sender_aliases(body, code) | trusted_parameters
+
+        fields: Set[str] = set()
+        for node in self._iter_nodes(body):
+            if node.type == "call_expression":
+                function = node.child_by_field_name("function")
+                arguments = node.child_by_field_name("arguments")
+                if not function or function.type != "field_expression" or not arguments:
+                    continue
+                method = self._get_node_text(function.child_by_field_name("field"), code)
+                receiver = function.child_by_field_name("value")
+                if receiver is None:
+                    continue
+                if receiver.type == "self" and method in methods and method not in visited:
+                    # Helper parameters are trusted when the constructor passes a trusted value
+                    helper_parameters = {
+                        parameter for parameter, argument in
+                        zip(self._get_ordered_parameters(methods[method], code), arguments.named_children)
+                        if self._is_trusted(argument, code, trusted)
+                    }
+                    fields |= self._collect_owner_assignments(methods[method], code, methods, visited,
+                                                              helper_parameters)
+                elif method in STORAGE_WRITE_METHODS + ("initialize",) and arguments.named_children and \
+                        self._is_trusted(arguments.named_children[-1], code, trusted):
+                    field = self._get_receiver_field(receiver, code)
+                    if field:
+                        fields.add(field)
+            elif node.type == "assignment_expression":
+                left = node.child_by_field_name("left")
+                right = node.child_by_field_name("right")
+                field = self._get_receiver_field(left, code) if left else None
+                if field and right and self._is_trusted(right, code, trusted):
+                    fields.add(field)
+            elif node.type == "field_initializer":
+                name = node.child_by_field_name("name")
+                value = node.child_by_field_name("value")
+                if name and value and self._is_trusted(value, code, trusted):
+                    fields.add(self._get_node_text(name, code))
+            elif node.type == "shorthand_field_initializer":
+                if self._get_node_text(node, code) in trusted:
+                    fields.add(self._get_node_text(node, code))
+        return fields
+
+    def _get_ordered_parameters(self, function_node: Node, code: str) -> List[str]:
+        """Get the parameter names of a function in declaration order, excluding `self`"""
+        names = []
+        parameters = function_node.child_by_field_name("parameters")
+        for parameter in parameters.named_children if parameters else []:
+            if parameter.type != "parameter":
+                continue
+            pattern = parameter.child_by_field_name("pattern")
+            if pattern and pattern.type == "mut_pattern" and pattern.named_children:
+                pattern = pattern.named_children[-1]
+            # Keep destructuring patterns as placeholders so positions line up with arguments
+            names.append(self._get_node_text(pattern, code) if pattern and pattern.type == "identifier" else "")
+        return names
+
+    def _is_trusted(self, node: Node, code: str, trusted: Set[str]) -> bool:
+        """Check if a value is the sender, a sender alias or a constructor parameter"""
+        while node.type in ("reference_expression", "parenthesized_expression") and node.named_children:
+            node = node.named_children[-1]
+        text = self._get_node_text(node, code)
+        return bool(SENDER_PATTERN.search(text)) or text in trusted
+
+    def _get_receiver_field(self, node: Node, code: str) -> Optional[str]:
+        """Get `field` from `self.field` or `instance.field`"""
+        if node.type != "field_expression":
+            return None
+        value = node.child_by_field_name("value")
+        field = node.child_by_field_name("field")
+        if value is None or field is None or value.type not in ("self", "identifier"):
+            return None
+        return self._get_node_text(field, code)
+
+    def _collect_sol_contracts(self, root: Node, code: str) -> List[Dict]:
+        """Collect `contract <Name> { ... }` definitions inside `sol!` macros along with their functions"""
+        functions = self._get_sol_functions(root, code)
+        contracts = []
+        for node in self._iter_nodes(root):
+            if node.type != "macro_invocation" or self._get_macro_name(node, code) != "sol":
+                continue
+            for tree in self._iter_nodes(node):
+                if tree.type != "token_tree":
+                    continue
+                tokens = tree.children
+                for index, token in enumerate(tokens[:-2]):
+                    if self._get_node_text(token, code) == "contract" and tokens[index + 1].type == "identifier" and \
+                            self._is_token_tree(tokens[index + 2], "{"):
+                        body = tokens[index + 2]
+                        contracts.append({
+                            "name": self._get_node_text(tokens[index + 1], code),
+                            "node": token,
+                            "body": body,
+                            "functions": [function for function in functions
+                                          if body.start_byte < function["node"].start_byte < body.end_byte],
+                        })
+        return contracts
+
+    def _check_sol_contract(self, contract: Dict, code: str, results) -> None:
+        """Check a sol! contract for an `address owner` state variable the constructor never assigns"""
+        fields = []
+        statement: List[Node] = []
+        for token in contract["body"].children[1:-1]:
+            if token.type == ";" or self._is_token_tree(token, "{"):
+                names = [self._get_node_text(part, code) for part in statement if part.type == "identifier"]
+                if len(names) > 1 and names[0] == "address" and "=" not in [part.type for part in statement]:
+                    fields.extend(name for name in names[1:] if name in self.privileged_fields)
+                statement = []
+            else:
+                statement.append(token)
+        if not fields:
+            return
+
+        assigned = set()
+        for function in contract["functions"]:
+            if function["name"] != "constructor":
+                continue
+            parameters = self._get_sol_parameter_names(function["params"], code)
+            for statement in self._split_sol_statements(function["body"]):
+                if len(statement) < 3 or statement[1].type != "=":
+                    continue
+                value = self._get_source_text(code, statement[2].start_byte, statement[-1].end_byte).strip()
+                if SENDER_PATTERN.search(value) or value in parameters:
+                    assigned.add(self._get_node_text(statement[0], code))
+        for field in fields:
+            if field not in assigned:
+                self._add_issue(contract["node"], contract["body"], code, contract["name"], field, results)
+
+    def _add_issue(self, start: Node, end: Node, code: str, struct_name: str, field: str, results) -> None:
+        """Add a missing owner initialization issue spanning the struct or contract"""
+        line_start, _ = self._get_line_for_node(start)
+        _, line_end = self._get_line_for_node(end)
+        results.add_issue(
+            issue_type="missing_owner_initialization",
+            severity="High",
+            description=f"Field '{field}' of '{struct_name}' is never assigned msg::sender() or a constructor parameter in the constructor. It stays the zero address, so owner-gated functions are unusable or can be claimed by anyone who initializes it first.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(start, code),
+            code_snippet=self._get_source_text(code, start.start_byte, end.start_byte).strip(),
+            recommendation=f"Assign the deployer in the constructor, e.g. `self.{field}.set(msg::sender())`."
+        )
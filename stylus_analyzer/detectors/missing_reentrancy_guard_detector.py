@@ -0,0 +1,176 @@
+"""
+Detector for payable and value-handling methods calling out without a reentrancy guard in Stylus Rust contracts
+"""
+import re
+from typing import Dict, List, Optional, Set, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import DetectorDoc
+from stylus_analyzer.detectors.msg_value_non_payable_detector import MSG_VALUE_PATTERN
+from stylus_analyzer.detectors.outbound_transfer_gating_detector import VALUE_CONFIG_PATTERN, ZERO_VALUE_PATTERN
+from stylus_analyzer.detectors.unreleased_guard_detector import (
+    LOCK_VALUE_PATTERN, UNLOCKED_VALUE_PATTERN, GuardWrite, UnreleasedReentrancyGuardDetector
+)
+from stylus_analyzer.findings import Confidence, Severity
+
+# Storage types a guard flag can have, e.g. `StorageBool` or `StorageU256`
+GUARD_TYPE_PATTERN = re.compile(r"\b(?:Storage)?(?:Bool|bool|U\d+|u\d+|Uint)\b")
+
+# Helpers followed from a method when looking for external calls
+MAX_HELPER_DEPTH = 8
+
+
+class PublicMutWithoutReentrancyGuardDetector(UnreleasedReentrancyGuardDetector):
+    """
+    Structural variant of the reentrancy detectors for methods that handle
+    Ether.
+
+    A public `&mut self` method that is `#[payable]`, reads `msg::value()`
+    or sends value along with a call is reported when it makes an external
+    call, directly or through `self` helpers, and no guard protects it. A
+    guard is a `bool` or integer field with a guard-like name (`locked`,
+    `status`, ...) that the method, or a helper such as `self.enter()`, sets
+    to a locked value before the first external call and resets later on.
+    The ordering of the method's writes doesn't matter, so this reports
+    methods the reentrancy detector is fine with. Whether the guard is
+    released on every exit is left to the unreleased guard detector.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.LOW
+    cwe = 841
+
+    documentation = DetectorDoc(
+        catches=(
+            "Public `&mut self` methods that are `#[payable]` or move Ether and make an external call without "
+            "setting a reentrancy guard field first."
+        ),
+        rationale=(
+            "Methods that move Ether are what reentrancy attacks go after. Checks-effects-interactions ordering "
+            "is easy to break in a later change or through a helper, while a guard blocks reentry structurally."
+        ),
+        vulnerable_example="""
+            #[payable]
+            pub fn deposit_and_notify(&mut self, hook: IHook) -> Result<(), Vec<u8>> {
+                self.deposits.setter(msg::sender()).set(msg::value());
+                hook.on_deposit(Call::new_in(self), msg::sender())?;
+                Ok(())
+            }
+        """,
+        fixed_example="""
+            #[payable]
+            pub fn deposit_and_notify(&mut self, hook: IHook) -> Result<(), Vec<u8>> {
+                if self.locked.get() {
+                    return Err(b"reentrant call".to_vec());
+                }
+                self.locked.set(true);
+                self.deposits.setter(msg::sender()).set(msg::value());
+                hook.on_deposit(Call::new_in(self), msg::sender())?;
+                self.locked.set(false);
+                Ok(())
+            }
+        """,
+        references=["https://docs.openzeppelin.com/contracts/5.x/api/utils#ReentrancyGuard"],
+    )
+
+    def __init__(self):
+        super().__init__()
+        self.name = "public_mut_without_reentrancy_guard"
+        self.description = "Detects payable or value-handling methods that call out without a reentrancy guard"
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect unguarded external calls of public methods handling Ether"""
+        root = tree.root_node
+        interfaces = self._collect_interface_names(root, code)
+        storage_fields = self._collect_storage_fields(root, code)
+        methods = {self._get_function_name(node, code): node
+                   for node in self._iter_nodes(root) if node.type == "function_item"}
+        helper_writes = {name: self._collect_direct_writes(node, code) for name, node in methods.items()}
+        for function_node in self._get_public_methods(root, code):
+            if not self._takes_mut_self(function_node, code) or self._is_constructor(function_node, code):
+                continue
+            calls = self._collect_external_calls(function_node, code, interfaces, methods,
+                                                 {self._get_function_name(function_node, code)})
+            if not calls:
+                continue
+            how = self._get_value_handling(function_node, code, calls)
+            site = calls[0][0]
+            if how is None or self._is_guarded(function_node, code, helper_writes, storage_fields, site):
+                continue
+            self._add_guard_issue(function_node, how, site, code, results)
+
+    def _collect_external_calls(self, function_node: Node, code: str, interfaces: Set[str], methods: Dict[str, Node],
+                                visited: Set[str], depth: int = 0) -> List[Tuple[Node, Node]]:
+        """
+        Collect the external calls of a method in source order, each with where
+        the method makes it: the call itself or the call of the `self` helper
+        making it
+        """
+        handles = self._collect_interface_handles(function_node, code, interfaces)
+        calls = []
+        for node in self._iter_nodes(function_node):
+            if self._is_external_call(node, code, interfaces, handles):
+                calls.append((node, node))
+                continue
+            helper = self._get_self_call(node, code)
+            if helper in methods and helper not in visited and depth < MAX_HELPER_DEPTH:
+                visited.add(helper)
+                calls.extend((node, call) for _, call in
+                             self._collect_external_calls(methods[helper], code, interfaces, methods, visited,
+                                                          depth + 1))
+        return calls
+
+    def _get_value_handling(self, function_node: Node, code: str, calls: List[Tuple[Node, Node]]) -> Optional[str]:
+        """Describe how a method handles Ether, or None if it doesn't"""
+        if self._get_item_flags(function_node, code).payable:
+            return "is `#[payable]`"
+        for node in self._iter_nodes(function_node):
+            function = node.child_by_field_name("function") if node.type == "call_expression" else None
+            if function is not None and MSG_VALUE_PATTERN.match(self._get_node_text(function, code)):
+                return "reads `msg::value()`"
+        for _, call in calls:
+            text = self._get_node_text(call, code)
+            function = call.child_by_field_name("function")
+            if function is not None and self._get_node_text(function, code).split("::")[-1].endswith("transfer_eth"):
+                return "sends Ether with `transfer_eth`"
+            match = VALUE_CONFIG_PATTERN.search(text)
+            value = (match.group(1) or match.group(2) or "").strip() if match else ""
+            if match and not ZERO_VALUE_PATTERN.match(value):
+                return "sends Ether along with a call"
+        return None
+
+    def _is_guarded(self, function_node: Node, code: str, helper_writes: Dict[str, List[GuardWrite]],
+                    storage_fields: Dict[str, str], first_call: Node) -> bool:
+        """Check if a guard field is locked before the first external call and reset afterwards"""
+        writes = self._collect_guard_writes(function_node, code, helper_writes)
+        for index, (lock, field, value) in enumerate(writes):
+            if lock.start_byte >= first_call.start_byte:
+                break
+            if not LOCK_VALUE_PATTERN.match(value) or UNLOCKED_VALUE_PATTERN.match(value):
+                continue
+            if field in storage_fields and not GUARD_TYPE_PATTERN.search(storage_fields[field]):
+                continue
+            if any(written == field and released != value for _, written, released in writes[index + 1:]):
+                return True
+        return False
+
+    def _add_guard_issue(self, function_node: Node, how: str, call: Node, code: str, results) -> None:
+        """Add an issue spanning a method that calls out without a reentrancy guard"""
+        function_name = self._get_function_name(function_node, code)
+        line_start, line_end = self._get_line_for_node(function_node)
+        call_line, _ = self._get_line_for_node(call)
+        body = function_node.child_by_field_name("body")
+        results.add_issue(
+            issue_type="public_mut_without_reentrancy_guard",
+            severity="Medium",
+            description=f"Function '{function_name}' {how} and makes an external call at line {call_line}, but no "
+                        "reentrancy guard is set before the call. The callee can re-enter the contract while it "
+                        "is handling Ether.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(function_node, code),
+            code_snippet=self._get_source_text(code, function_node.start_byte, body.start_byte).strip()
+            if body is not None else self._get_node_text(function_node, code),
+            recommendation="Set a guard field such as `locked` before the first external call, revert when it "
+                           "is already set, and reset it before returning."
+        )
@@ -0,0 +1,215 @@
+"""
+Detector for caller checks and storage writes in read-only methods of Stylus Rust contracts
+"""
+import re
+from typing import Dict, Optional, Set
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.dead_code_after_revert_detector import DIVERGING_MACROS, REVERT_PATTERN
+from stylus_analyzer.detectors.detector_base import DetectorDoc, GUARD_MACROS
+from stylus_analyzer.detectors.missing_access_control_detector import (
+    MissingAccessControlDetector, GUARD_HELPER_PATTERN, SENDER_PATTERN
+)
+from stylus_analyzer.findings import Confidence, Severity
+
+# `self.<helper>(` calls, checked against the guard helper names
+SELF_CALL_PATTERN = re.compile(r"\bself\s*\.\s*(\w+)\s*\(")
+
+# Returned values rejecting the caller, as opposed to a `return true`
+ERROR_VALUE_PATTERN = re.compile(r"^(?:\w+\s*::\s*)*Err\s*\(")
+
+
+class MsgSenderInViewDetector(MissingAccessControlDetector):
+    """
+    Detector for public methods taking `&self` that restrict their caller
+    with a `msg::sender()` check.
+
+    A read-only method can't protect anything: storage is public on-chain
+    and anyone can read it without calling the method. The check usually
+    means the author meant a state-changing method gated to the owner.
+    Methods taking `&self` that write storage are reported as well, since
+    the compiler's borrow error doesn't point at that mistake.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.MEDIUM
+    # The inherited allowlist doesn't apply to view methods
+    options = {}
+
+    documentation = DetectorDoc(
+        catches=(
+            "Public methods taking `&self`, and `view` functions in `sol!`, that check `msg::sender()` before "
+            "returning, and methods taking `&self` that write storage."
+        ),
+        rationale=(
+            "A read-only method can't restrict who reads the contract's storage, which anyone can read directly "
+            "from the chain. A caller check there usually means `&mut self` and a state change were intended."
+        ),
+        vulnerable_example="""
+            pub fn secret(&self) -> Result<U256, Vec<u8>> {
+                if msg::sender() != self.owner.get() {
+                    return Err(b"not the owner".to_vec());
+                }
+                Ok(self.secret.get())
+            }
+        """,
+        fixed_example="""
+            pub fn set_secret(&mut self, secret: U256) -> Result<(), Vec<u8>> {
+                if msg::sender() != self.owner.get() {
+                    return Err(b"not the owner".to_vec());
+                }
+                self.secret.set(secret);
+                Ok(())
+            }
+        """,
+        references=["https://docs.arbitrum.io/stylus/reference/rust-sdk-guide#storage"],
+    )
+
+    def __init__(self):
+        super().__init__()
+        self.name = "msg_sender_in_view"
+        self.description = "Detects caller checks and storage writes in read-only methods"
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect caller checks in `&self` public methods and storage writes through `&self`"""
+        public_starts = {node.start_byte for node in self._get_public_methods(tree.root_node, code)}
+        for function_node in self._iter_nodes(tree.root_node):
+            if function_node.type != "function_item" or not self._takes_shared_self(function_node, code):
+                continue
+            function_name = self._get_function_name(function_node, code)
+            if function_node.start_byte in public_starts and not GUARD_HELPER_PATTERN.search(function_name):
+                guard = self._find_sender_guard(function_node, code)
+                if guard is not None:
+                    self._add_guard_issue(guard, f"function '{function_name}'", code, results)
+            self._check_writes(function_node, function_name, code, results)
+
+        for function in self._get_sol_functions(tree.root_node, code):
+            self._check_sol_view(function, code, results)
+
+    def _takes_shared_self(self, function_node: Node, code: str) -> bool:
+        """Check if a method takes `&self` rather than `&mut self` or `self`"""
+        parameters = function_node.child_by_field_name("parameters")
+        for parameter in parameters.named_children if parameters else []:
+            if parameter.type == "self_parameter":
+                text = self._get_node_text(parameter, code)
+                return text.startswith("&") and "mut" not in text
+        return False
+
+    def _find_sender_guard(self, function_node: Node, code: str) -> Optional[Node]:
+        """Find the first guard restricting the caller of a method"""
+        body = function_node.child_by_field_name("body")
+        if not body:
+            return None
+        sender_aliases = self._collect_sender_aliases(function_node, code)
+        for node in self._iter_nodes(body):
+            if node.type == "macro_invocation" and self._get_macro_name(node, code) in GUARD_MACROS:
+                if self._checks_caller(self._get_node_text(node, code), sender_aliases):
+                    return node
+            elif node.type == "if_expression":
+                condition = node.child_by_field_name("condition")
+                consequence = node.child_by_field_name("consequence")
+                if condition and consequence and self._rejects(consequence, code) and \
+                        self._checks_caller(self._get_node_text(condition, code), sender_aliases):
+                    return node
+            elif node.type in ("try_expression", "expression_statement"):
+                # `self.only_owner()?;`
+                call = node.named_children[0] if node.named_children else None
+                if call is not None and call.type == "call_expression" and \
+                        self._is_caller_check(call, code, sender_aliases):
+                    return node
+        return None
+
+    def _checks_caller(self, text: str, sender_aliases: Set[str]) -> bool:
+        """Check if a condition compares the caller or calls a guard helper"""
+        if self._mentions_sender(text, sender_aliases):
+            return True
+        return any(GUARD_HELPER_PATTERN.search(helper) for helper in SELF_CALL_PATTERN.findall(text))
+
+    def _rejects(self, block: Node, code: str) -> bool:
+        """Check if a block returns an error, reverts or panics somewhere"""
+        for node in self._iter_nodes(block):
+            if node.type == "return_expression":
+                value = node.named_children[0] if node.named_children else None
+                if value is not None and ERROR_VALUE_PATTERN.match(self._get_node_text(value, code)):
+                    return True
+            if node.type == "macro_invocation" and self._get_macro_name(node, code) in DIVERGING_MACROS:
+                return True
+            if node.type == "call_expression":
+                function = node.child_by_field_name("function")
+                if function is not None and REVERT_PATTERN.match(self._get_node_text(function, code)):
+                    return True
+        return False
+
+    def _check_writes(self, function_node: Node, function_name: str, code: str, results) -> None:
+        """Report the first storage write of a method taking `&self`"""
+        setter_aliases = self._collect_setter_aliases(function_node, code)
+        for node in self._iter_nodes(function_node):
+            field = self._get_storage_write_field(node, code, setter_aliases)
+            if field:
+                line_start, line_end = self._get_line_for_node(node)
+                self._add_write_issue(line_start, line_end, self._get_column_for_node(node, code),
+                                      self._get_node_text(node, code),
+                                      f"function '{function_name}'", "takes `&self`", field, results)
+                return
+
+    def _check_sol_view(self, function: Dict, code: str, results) -> None:
+        """Check a `view` or `pure` sol! function for a caller check or a state write"""
+        modifiers = function["modifiers"]
+        mutability = next((modifier for modifier in modifiers if modifier in ("view", "pure")), None)
+        if mutability is None:
+            return
+        location = f"sol! function '{function['name']}'"
+        guarded = written = False
+        for statement in self._split_sol_statements(function["body"]):
+            text = self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte)
+            line_start, _ = self._get_line_for_node(statement[0])
+            _, line_end = self._get_line_for_node(statement[-1])
+            keyword = self._get_node_text(statement[0], code)
+            rejects = keyword == "require" or (keyword == "if" and re.search(r"\brevert\b", text))
+            if not guarded and rejects and SENDER_PATTERN.search(text):
+                guarded = True
+                self._add_caller_check_issue(line_start, line_end, self._get_column_for_node(statement[0], code), text,
+                                             f"{mutability} {location}", results)
+            elif not written and self._is_sol_state_write(statement, code):
+                written = True
+                self._add_write_issue(line_start, line_end, self._get_column_for_node(statement[0], code), text,
+                                      location, f"is declared `{mutability}`",
+                                      re.split(r"[\[.\s]", self._get_sol_target(statement, code), maxsplit=1)[0],
+                                      results)
+
+    def _add_guard_issue(self, guard: Node, location: str, code: str, results) -> None:
+        """Add a caller check issue for a Rust method"""
+        line_start, line_end = self._get_line_for_node(guard)
+        self._add_caller_check_issue(line_start, line_end, self._get_column_for_node(guard, code),
+                                     self._get_node_text(guard, code),
+                                     f"read-only {location}", results)
+
+    def _add_caller_check_issue(self, line_start: int, line_end: int, column: int, snippet: str, location: str,
+                                results) -> None:
+        """Add a caller check issue for a read-only function"""
+        results.add_issue(
+            issue_type="msg_sender_in_view",
+            severity="Low",
+            description=f"The {location} restricts its caller, but reading state can't be restricted on-chain. "
+                        "This usually means a state-changing, owner-only function was intended.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation="Take `&mut self` if the function is meant to change state for the owner only, "
+                           "or drop the caller check from the getter."
+        )
+
+    def _add_write_issue(self, line_start: int, line_end: int, column: int, snippet: str, location: str,
+                         read_only: str, field: str, results) -> None:
+        """Add a storage write issue for a read-only function"""
+        results.add_issue(
+            issue_type="msg_sender_in_view",
+            severity="Low",
+            description=f"The {location} {read_only} but writes storage field '{field}', which does not compile.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation="Take `&mut self` to change state, or move the write out of the read-only function."
+        )
@@ -0,0 +1,110 @@
+"""
+Detector for msg_value reads in non-payable functions in Stylus Rust contracts
+"""
+import re
+from typing import Dict, Optional
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# Value accessors of the Stylus SDK and Solidity
+MSG_VALUE_PATTERN = re.compile(r"^((evm|msg)\s*::\s*)?msg_value$|^msg\s*::\s*value$")
+SOL_MSG_VALUE_PATTERN = re.compile(r"\bmsg\s*\.\s*value\b")
+
+
+class MsgValueWithoutPayableDetector(BaseDetector):
+    """
+    Detector for public methods reading `msg::value()` without `#[payable]`.
+
+    Stylus rejects calls carrying ETH to methods that are not `#[payable]`,
+    so the value is always zero there and any check on it is dead code.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.MEDIUM
+
+    documentation = DetectorDoc(
+        catches=(
+            "Public methods reading `msg::value()` without `#[payable]`, and `sol!` functions reading `msg.value` "
+            "without the `payable` modifier."
+        ),
+        rationale=(
+            "Calls sending Ether to non-payable methods are rejected, so the value is always zero and any logic "
+            "depending on it is dead or misleading."
+        ),
+        vulnerable_example="""
+            pub fn deposit(&mut self) {
+                let balance = self.balances.get(msg::sender());
+                self.balances.setter(msg::sender()).set(balance + msg::value());
+            }
+        """,
+        fixed_example="""
+            #[payable]
+            pub fn deposit(&mut self) {
+                let balance = self.balances.get(msg::sender());
+                self.balances.setter(msg::sender()).set(balance + msg::value());
+            }
+        """,
+        references=["https://docs.arbitrum.io/stylus/reference/rust-sdk-guide"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="msg_value_without_payable",
+            description="Detects msg_value reads in functions that are not payable"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect value reads in non-payable functions"""
+        for function_node in self._get_public_methods(tree.root_node, code):
+            if self._get_item_flags(function_node, code).payable:
+                continue
+            call = self._find_msg_value_call(function_node, code)
+            if call is None:
+                continue
+            function_start, function_end = self._get_line_for_node(function_node)
+            line_start, line_end = self._get_line_for_node(call)
+            self._add_issue(line_start, line_end, self._get_column_for_node(call, code), self._get_node_text(call, code),
+                            f"function '{self._get_function_name(function_node, code)}' (lines {function_start}-{function_end})",
+                            results)
+
+        for function in self._get_sol_functions(tree.root_node, code):
+            self._check_sol_function(function, code, results)
+
+    def _find_msg_value_call(self, function_node: Node, code: str) -> Optional[Node]:
+        """Find the first `evm::msg_value()` or `msg::value()` call in a function"""
+        body = function_node.child_by_field_name("body")
+        for node in self._iter_nodes(body) if body else []:
+            if node.type != "call_expression":
+                continue
+            function = node.child_by_field_name("function")
+            if function and MSG_VALUE_PATTERN.match(self._get_node_text(function, code)):
+                return node
+        return None
+
+    def _check_sol_function(self, function: Dict, code: str, results) -> None:
+        """Check a sol! function reading `msg.value` without the `payable` modifier"""
+        if "payable" in function["modifiers"]:
+            return
+        for statement in self._split_sol_statements(function["body"]):
+            text = self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte)
+            if SOL_MSG_VALUE_PATTERN.search(text):
+                line_start, _ = self._get_line_for_node(statement[0])
+                _, line_end = self._get_line_for_node(statement[-1])
+                self._add_issue(line_start, line_end, self._get_column_for_node(statement[0], code), text,
+                                f"sol! function '{function['name']}'", results)
+                return
+
+    def _add_issue(self, line_start: int, line_end: int, column: int, snippet: str, location: str, results) -> None:
+        """Add a msg_value without payable issue"""
+        results.add_issue(
+            issue_type="msg_value_without_payable",
+            severity="Low",
+            description=f"Non-payable {location} reads msg_value. Calls sending ETH to it are rejected, so the value is always zero and the check is dead or misleading.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation="Mark the method #[payable] if it should accept ETH, or remove the msg_value logic."
+        )
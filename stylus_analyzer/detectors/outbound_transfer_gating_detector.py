@@ -0,0 +1,245 @@
+"""
+Detector for Ether transfers reachable from public methods without a caller check
+"""
+import re
+from typing import Dict, Iterable, List, Optional, Set, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import DetectorDoc
+from stylus_analyzer.detectors.missing_access_control_detector import (
+    MissingAccessControlDetector, GUARD_HELPER_PATTERN, ROLE_PATTERN, SENDER_PATTERN
+)
+from stylus_analyzer.findings import Confidence, Severity
+
+# Call values that send no Ether
+ZERO_VALUE_PATTERN = re.compile(r"^(U256\s*::\s*(ZERO|from\s*\(\s*0\s*\))|0(u\d+)?)$")
+
+VALUE_CONFIG_PATTERN = re.compile(r"\.value\s*\((.*)\)|new_with_value\s*\((.*)\)", re.DOTALL)
+
+# Rust recipients read from a storage field rather than chosen by the caller
+STORED_RECIPIENT_PATTERN = re.compile(r"^self\s*\.\s*\w+(\s*\.\s*get\s*\(\s*\))?$")
+
+# sol! calls that send Ether out of the contract
+SOL_TRANSFER_METHODS = ("transfer", "send")
+
+
+class OutboundTransferGatingDetector(MissingAccessControlDetector):
+    """
+    Detector for `transfer_eth` calls and calls with a non-zero value that a
+    public method reaches, directly or through `self` helpers, without
+    checking the caller.
+
+    Anyone can call such a method to move the contract's Ether. Sending the
+    caller their own recorded balance, as in a `withdraw` that reads a map
+    keyed by `msg::sender()`, is the expected pattern and is reported as Low
+    so the accounting can be reviewed.
+    """
+
+    default_severity = Severity.HIGH
+    default_confidence = Confidence.MEDIUM
+
+    documentation = DetectorDoc(
+        catches=(
+            "Ether transfers that a public function reaches before any caller check, directly or through `self` "
+            "helpers."
+        ),
+        rationale=(
+            "When the recipient or the timing of the payout is up to the caller, anyone can drain the contract."
+        ),
+        vulnerable_example="""
+            pub fn sweep(&mut self, to: Address) -> Result<(), Vec<u8>> {
+                transfer_eth(to, contract::balance())
+            }
+        """,
+        fixed_example="""
+            pub fn sweep(&mut self, to: Address) -> Result<(), Vec<u8>> {
+                if msg::sender() != self.owner.get() {
+                    return Err(b"not the owner".to_vec());
+                }
+                transfer_eth(to, contract::balance())
+            }
+        """,
+        references=["https://swcregistry.io/docs/SWC-105"],
+    )
+
+    def __init__(self, allowlist: Optional[Iterable[str]] = None):
+        super().__init__(allowlist)
+        self.name = "unguarded_eth_transfer"
+        self.description = "Detects Ether transfers reachable from public methods without a caller check"
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect unguarded outbound transfers in public methods and sol! functions"""
+        impls = self._collect_impl_methods(tree.root_node, code)
+        for function_node in self._get_public_methods(tree.root_node, code):
+            function_name = self._get_function_name(function_node, code)
+            if function_name in self.allowlist:
+                continue
+            impl_node = self._get_parent_impl(function_node)
+            impl_type = impl_node.child_by_field_name("type") if impl_node else None
+            methods = impls.get(self._get_node_text(impl_type, code).split("<")[0].strip(), {}) if impl_type else {}
+            sender_aliases = self._collect_sender_aliases(function_node, code)
+            own_balance = any(self._is_caller_keyed_access(node, code, sender_aliases)
+                              for node in self._iter_nodes(function_node))
+            for transfer, target, via in self._collect_unguarded_transfers(function_node, code, methods, set()):
+                self._add_transfer_issue(transfer, target, via, function_name, code, own_balance,
+                                         sender_aliases, results)
+
+        for function in self._get_sol_functions(tree.root_node, code):
+            self._check_sol_transfers(function, code, results)
+
+    def _collect_unguarded_transfers(self, function_node: Node, code: str, methods: Dict[str, Node],
+                                     visited: Set[str]) -> List[Tuple[Node, Node, Optional[str]]]:
+        """Collect the transfers a method makes before any caller check, following `self` helpers"""
+        visited.add(self._get_function_name(function_node, code))
+        body = function_node.child_by_field_name("body")
+        if not body:
+            return []
+        sender_aliases = self._collect_sender_aliases(function_node, code)
+        transfers = []
+        for node in self._iter_nodes(body):
+            if self._is_caller_check(node, code, sender_aliases):
+                # Everything after a caller check is gated
+                break
+            target = self._get_transfer_target(node, code)
+            if target is not None:
+                transfers.append((node, target, None))
+                continue
+            helper = self._get_self_call(node, code)
+            if helper in methods and helper not in visited:
+                transfers.extend((transfer, helper_target, helper)
+                                 for transfer, helper_target, _ in
+                                 self._collect_unguarded_transfers(methods[helper], code, methods, visited))
+        return transfers
+
+    def _get_transfer_target(self, node: Node, code: str) -> Optional[Node]:
+        """Get the recipient of `transfer_eth(to, amount)` or of a call sending a non-zero value"""
+        if node.type != "call_expression":
+            return None
+        function = node.child_by_field_name("function")
+        arguments = node.child_by_field_name("arguments")
+        if not function or not arguments:
+            return None
+        args = arguments.named_children
+        if function.type in ("identifier", "scoped_identifier"):
+            name = self._get_node_text(function, code).split("::")[-1]
+            if name == "transfer_eth" and args:
+                return args[0]
+            if name == "call" and len(args) >= 2 and self._sends_value(args[0], code):
+                return args[1]
+        elif function.type == "field_expression":
+            # RawCall::new_with_value(amount).call(to, data)
+            method = function.child_by_field_name("field")
+            receiver = function.child_by_field_name("value")
+            if method and receiver and self._get_node_text(method, code) == "call" and args and \
+                    self._sends_value(receiver, code):
+                return args[0]
+        return None
+
+    def _sends_value(self, node: Node, code: str) -> bool:
+        """Check if a call configuration attaches a non-zero value"""
+        match = VALUE_CONFIG_PATTERN.search(self._get_node_text(node, code))
+        if not match:
+            return False
+        value = (match.group(1) or match.group(2) or "").strip()
+        return not ZERO_VALUE_PATTERN.match(value)
+
+    def _get_self_call(self, node: Node, code: str) -> Optional[str]:
+        """Get the method name of a `self.method(...)` call"""
+        if node.type != "call_expression":
+            return None
+        function = node.child_by_field_name("function")
+        if not function or function.type != "field_expression":
+            return None
+        receiver = function.child_by_field_name("value")
+        method = function.child_by_field_name("field")
+        if not receiver or receiver.type != "self" or not method:
+            return None
+        return self._get_node_text(method, code)
+
+    def _add_transfer_issue(self, transfer: Node, target: Node, via: Optional[str], function_name: str,
+                            code: str, own_balance: bool, sender_aliases: Set[str], results) -> None:
+        """Add an unguarded transfer issue for a Rust method"""
+        target_text = self._get_node_text(target, code)
+        route = f" through helper '{via}'" if via else ""
+        line_start, line_end = self._get_line_for_node(transfer)
+        refunds_caller = self._mentions_sender(target_text, sender_aliases) and own_balance
+        self._add_issue_for_target(line_start, line_end, self._get_column_for_node(transfer, code),
+                                   self._get_node_text(transfer, code),
+                                   f"function '{function_name}'", target_text, route, refunds_caller,
+                                   bool(STORED_RECIPIENT_PATTERN.search(target_text)), results)
+
+    def _add_issue_for_target(self, line_start: int, line_end: int, column: int, snippet: str, location: str,
+                              target_text: str, route: str, refunds_caller: bool, stored_recipient: bool, results) -> None:
+        """Add an unguarded transfer issue, with a severity depending on who receives the Ether"""
+        if refunds_caller:
+            severity = "Low"
+            impact = "The recipient is the caller and the method reads their recorded balance, so make sure the balance is debited before the transfer."
+        elif stored_recipient:
+            # A recipient read from storage can't be chosen by the caller, only the timing of the payout
+            severity = "Medium"
+            impact = "The recipient comes from storage, but any account can trigger the payout at any time."
+        else:
+            severity = "High"
+            impact = "Any account can call it to drain the contract's Ether."
+        results.add_issue(
+            issue_type="unguarded_eth_transfer",
+            severity=severity,
+            description=f"Public {location} sends Ether to '{target_text}'{route} without checking the caller. {impact}",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation="Restrict the transfer to an owner or role, or only pay out the caller's own recorded balance after debiting it."
+        )
+
+    def _check_sol_transfers(self, function: Dict, code: str, results) -> None:
+        """Check a public sol! function for `.transfer()`, `.send()` or `.call{value: ...}()` before a caller check"""
+        name = function["name"]
+        modifiers = function["modifiers"]
+        if name in self.allowlist or name == "constructor":
+            return
+        if not any(modifier in ("public", "external") for modifier in modifiers):
+            return
+        if any(GUARD_HELPER_PATTERN.search(modifier) for modifier in modifiers):
+            return
+
+        parameters = self._get_sol_parameter_names(function["params"], code)
+        body_text = self._get_node_text(function["body"], code)
+        own_balance = bool(re.search(r"\w+\s*\[\s*msg\s*\.\s*sender\s*\]", body_text))
+        guard_start = None
+        for statement in self._split_sol_statements(function["body"]):
+            text = self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte)
+            if self._get_node_text(statement[0], code) in ("require", "if") and \
+                    (SENDER_PATTERN.search(text) or ROLE_PATTERN.search(text)):
+                guard_start = statement[0].start_byte
+                break
+
+        # Statements are split at `{...}`, so `.call{value: ...}(...)` is matched on the tokens
+        for token in self._iter_nodes(function["body"]):
+            if guard_start is not None and token.start_byte > guard_start:
+                break
+            if token.type != "identifier" or token.prev_sibling is None or token.prev_sibling.type != ".":
+                continue
+            method = self._get_node_text(token, code)
+            following = token.next_sibling
+            if method == "call":
+                if following is None or not self._is_token_tree(following, "{") or \
+                        "value" not in self._get_node_text(following, code):
+                    continue
+            elif method not in SOL_TRANSFER_METHODS or following is None or not self._is_token_tree(following, "("):
+                continue
+            target = token.prev_sibling.prev_sibling
+            if target is None:
+                continue
+            target_start = target.prev_sibling if target.type == "token_tree" and target.prev_sibling is not None and \
+                target.prev_sibling.type == "identifier" else target
+            target_text = self._get_source_text(code, target_start.start_byte, target.end_byte)
+            line_start, line_end = self._get_line_for_node(token)
+            # State variables, unlike parameters, can't be chosen by the caller
+            recipient = re.sub(r"^payable\s*\(\s*|\s*\)$", "", target_text)
+            stored_recipient = bool(re.match(r"^[A-Za-z_]\w*$", recipient)) and recipient not in parameters
+            self._add_issue_for_target(line_start, line_end, self._get_column_for_node(token, code),
+                                       self._get_source_text(code, target_start.start_byte, following.end_byte),
+                                       f"sol! function '{name}'", target_text, "",
+                                       bool(SENDER_PATTERN.search(target_text)) and own_balance, stored_recipient,
+                                       results)
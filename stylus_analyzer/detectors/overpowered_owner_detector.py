@@ -0,0 +1,177 @@
+"""
+Detector for owner-only functions that can mint, burn or move any account's tokens in Stylus Rust contracts
+"""
+import re
+from typing import Dict, Iterable, List, Optional, Set
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.call_graph import CallGraph
+from stylus_analyzer.detectors.detector_base import DetectorDoc
+from stylus_analyzer.detectors.missing_access_control_detector import (
+    MissingAccessControlDetector, GUARD_HELPER_PATTERN, ROLE_PATTERN, SENDER_PATTERN
+)
+from stylus_analyzer.findings import Confidence, Severity
+from stylus_analyzer.sol_parser import SolFunction
+
+# Storage fields holding the token supply and balances, in Rust and Solidity spelling
+DEFAULT_POWERFUL_FIELDS = ("total_supply", "totalSupply", "balances", "balance_of", "balanceOf")
+
+
+class OverpoweredOwnerDetector(MissingAccessControlDetector):
+    """
+    Detector for functions only the owner can call that write the token
+    supply or balances.
+
+    A function counts as owner-only when one of its guards compares the
+    caller against an owner or role, or it calls a guard helper such as
+    `self.only_owner()`; in `sol!`, a modifier such as `onlyOwner` counts
+    too. Writes of the `self` helpers it calls count as its own, while
+    writes to the balance of the caller, keyed by `msg::sender()`, don't.
+    The findings are a centralization disclosure for audit reports rather
+    than bugs: whoever holds the owner key can mint unlimited supply, burn
+    any balance or move other accounts' funds. Which fields count is set
+    by `powerful_fields`.
+    """
+
+    default_severity = Severity.INFO
+    default_confidence = Confidence.MEDIUM
+    cwe = 269
+
+    documentation = DetectorDoc(
+        catches=(
+            "Owner- or role-gated functions that write the total supply or the balances of arbitrary accounts, "
+            "directly or through `self` helpers."
+        ),
+        rationale=(
+            "Users have to trust whoever holds the owner key not to mint unlimited supply, burn their balance or "
+            "move their funds, and a leaked key lets an attacker do the same. Audit reports disclose these powers "
+            "so users can judge the centralization risk."
+        ),
+        vulnerable_example="""
+            pub fn burn_from(&mut self, account: Address, amount: U256) -> Result<(), Vec<u8>> {
+                self.only_owner()?;
+                self.balances.setter(account).set(self.balances.get(account) - amount);
+                self.total_supply.set(self.total_supply.get() - amount);
+                Ok(())
+            }
+        """,
+        fixed_example="""
+            // Holders burn their own tokens; the owner has no power over balances
+            pub fn burn(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+                let sender = msg::sender();
+                self.balances.setter(sender).set(self.balances.get(sender) - amount);
+                self.total_supply.set(self.total_supply.get() - amount);
+                Ok(())
+            }
+        """,
+        references=["https://cwe.mitre.org/data/definitions/269.html"],
+    )
+
+    options = {"powerful_fields": list}
+
+    def __init__(self, powerful_fields: Optional[Iterable[str]] = None):
+        """
+        Args:
+            powerful_fields: Storage fields whose writes by the owner are reported,
+                by default the total supply and balance fields of common token layouts
+        """
+        super().__init__()
+        self.name = "overpowered_owner"
+        self.description = "Detects owner-only functions that can mint, burn or move any account's tokens"
+        self.powerful_fields = set(DEFAULT_POWERFUL_FIELDS if powerful_fields is None else powerful_fields)
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect owner-gated Rust methods and sol! functions writing the supply or balances"""
+        call_graphs = self._get_call_graphs(tree.root_node, code)
+        for function_node in self._get_public_methods(tree.root_node, code):
+            if self._get_item_flags(function_node, code).constructor or self._is_constructor(function_node, code):
+                continue
+            if not self._takes_mut_self(function_node, code) or not self._is_owner_gated(function_node, code):
+                continue
+            fields = self._collect_powerful_writes(function_node, code)
+            graph = self._get_call_graph(function_node, code, call_graphs)
+            name = self._get_function_name(function_node, code)
+            for helper in sorted(graph.reachable_from([name]) - {name}) if graph else []:
+                if helper in graph.methods:
+                    fields.extend(field for field in self._collect_powerful_writes(graph.methods[helper], code)
+                                  if field not in fields)
+            if fields:
+                body = function_node.child_by_field_name("body")
+                signature = self._get_source_text(code, function_node.start_byte, body.start_byte).strip()
+                self._add_issue(function_node, signature, f"Function '{name}'", fields, code, results)
+
+        for function in self._parse_sol_functions(tree.root_node, code):
+            self._check_sol_function(function, code, results)
+
+    def _get_call_graph(self, function_node: Node, code: str, call_graphs: Dict[str, CallGraph]) -> Optional[CallGraph]:
+        """Get the call graph of the type a method is implemented for"""
+        impl_node = self._get_parent_impl(function_node)
+        impl_type = impl_node.child_by_field_name("type") if impl_node else None
+        return call_graphs.get(self._get_node_text(impl_type, code).split("<")[0].strip()) if impl_type else None
+
+    def _is_owner_gated(self, function_node: Node, code: str) -> bool:
+        """Check if a guard of a method compares the caller against an owner or role, or it calls a guard helper"""
+        sender_aliases = self._collect_sender_aliases(function_node, code)
+        if any(self._is_owner_condition(guard.condition, sender_aliases)
+               for guard in self._get_guards(function_node, code)):
+            return True
+        return any(self._is_guard_helper_call(node, code) for node in self._iter_nodes(function_node))
+
+    def _is_owner_condition(self, condition: str, sender_aliases: Set[str]) -> bool:
+        """Check if a guard condition compares the caller against an owner or role"""
+        return self._mentions_sender(condition, sender_aliases) and bool(ROLE_PATTERN.search(condition))
+
+    def _collect_powerful_writes(self, function_node: Node, code: str) -> List[str]:
+        """Collect the powerful fields a method writes, other than at keys of the caller, in order"""
+        body = function_node.child_by_field_name("body")
+        setter_aliases = self._collect_setter_aliases(function_node, code)
+        sender_aliases = self._collect_sender_aliases(function_node, code)
+        fields: List[str] = []
+        for node in self._iter_nodes(body) if body else []:
+            field = self._get_storage_write_field(node, code, setter_aliases)
+            if field not in self.powerful_fields or field in fields:
+                continue
+            if any(self._is_caller_keyed_access(inner, code, sender_aliases) for inner in self._iter_nodes(node)):
+                # The caller moving their own tokens
+                continue
+            fields.append(field)
+        return fields
+
+    def _check_sol_function(self, function: SolFunction, code: str, results) -> None:
+        """Check a public sol! function gated to the owner for writes to the supply or balances"""
+        modifiers = function.modifiers
+        if function.name == "constructor" or not any(modifier in ("public", "external") for modifier in modifiers):
+            return
+        gated = any(GUARD_HELPER_PATTERN.search(modifier) for modifier in modifiers) or \
+            any(self._is_owner_condition(guard.condition, set()) for guard in self._get_guards(function, code))
+        if not gated:
+            return
+        fields: List[str] = []
+        for statement in function.statements:
+            if statement.kind != "assignment" or not statement.target or SENDER_PATTERN.search(statement.target):
+                continue
+            field = re.split(r"[\[.\s]", statement.target, maxsplit=1)[0]
+            if field in self.powerful_fields and field not in fields:
+                fields.append(field)
+        if fields:
+            body = function.body
+            signature = self._get_source_text(code, function.node.start_byte, body.start_byte).strip()
+            self._add_issue(function.node, signature, f"sol! function '{function.name}'", fields, code,
+                            results)
+
+    def _add_issue(self, node: Node, signature: str, location: str, fields: List[str], code: str, results) -> None:
+        """Report an owner-only function writing the supply or balances"""
+        line_start, _ = self._get_line_for_node(node)
+        written = ", ".join(f"'{field}'" for field in fields)
+        results.add_issue(
+            issue_type="overpowered_owner",
+            severity="Info",
+            description=f"{location} can only be called by the owner and writes {written}, so whoever holds the "
+                        "owner key can mint, burn or move any account's tokens without limit.",
+            line_start=line_start,
+            line_end=line_start + signature.count("\n"),
+            column=self._get_column_for_node(node, code),
+            code_snippet=signature,
+            recommendation="Disclose the owner's powers to users, and limit them with a supply cap, a timelock or "
+                           "a multisig owner, or renounce the role once the token is set up."
+        )
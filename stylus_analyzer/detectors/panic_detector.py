@@ -3,7 +3,8 @@ Detector for panic!() macro calls in Stylus Rust contracts
 """
 from tree_sitter import Node, Tree
 
-from stylus_analyzer.detectors.detector_base import BaseDetector
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
 
 
 class PanicDetector(BaseDetector):
@@ -14,51 +15,79 @@ class PanicDetector(BaseDetector):
     which is dangerous in blockchain contexts where transactions must be handled gracefully.
     """
     
+    default_severity = Severity.HIGH
+    default_confidence = Confidence.HIGH
+    cwe = 617
+    node_types = ("macro_invocation",)
+
+    documentation = DetectorDoc(
+        catches="Uses of the `panic!()` macro.",
+        rationale=(
+            "A panic aborts execution and reverts the transaction with no way for the contract or its callers to "
+            "handle the error. Errors should be returned so the revert carries a reason."
+        ),
+        vulnerable_example="""
+            pub fn withdraw(&mut self, amount: U256) {
+                if self.balances.get(msg::sender()) < amount {
+                    panic!("insufficient balance");
+                }
+            }
+        """,
+        fixed_example="""
+            pub fn withdraw(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+                if self.balances.get(msg::sender()) < amount {
+                    return Err(b"insufficient balance".to_vec());
+                }
+                Ok(())
+            }
+        """,
+        references=[
+            "https://doc.rust-lang.org/book/ch09-03-to-panic-or-not-to-panic.html",
+            "https://doc.rust-lang.org/book/ch09-02-recoverable-errors-with-result.html",
+        ],
+    )
+
     def __init__(self):
         super().__init__(
             name="unsafe_panic",
             description="Detects panic!() macro calls that cause immediate termination"
         )
         
-    def detect(self, tree: Tree, code: str, results) -> None:
-        """Detect panic!() macro calls in the contract"""
+    def start(self, tree: Tree, code: str) -> None:
+        """Forget the panic calls found in the previous file"""
         # Track found panic calls by location to avoid duplicates
         self.found_locations = set()
-        self._find_panic_calls(tree.root_node, code, results)
-    
-    def _find_panic_calls(self, node: Node, code: str, results) -> None:
-        """Recursively search for panic!() macro calls in the contract"""
-        # Check for macro invocations
-        if node.type == "macro_invocation":
-            node_text = self._get_node_text(node, code)
-            
-            # Check if this is a panic! macro call
-            if node_text.startswith("panic!"):
-                # Get the location to avoid duplicates
-                location = (node.start_point[0], node.end_point[0])
-                
-                # Check if we've already reported this panic call
-                if location not in self.found_locations:
-                    self.found_locations.add(location)
-                    
-                    line_start, line_end = self._get_line_for_node(node)
-                    function_node = self._find_parent_function(node)
-                    function_name = self._get_function_name(function_node, code) if function_node else "unknown"
-                    
-                    results.add_issue(
-                        issue_type="unsafe_panic",
-                        severity="High",
-                        description=f"Unsafe call to panic!() macro in function '{function_name}'. This causes immediate termination and cannot be caught.",
-                        line_start=line_start,
-                        line_end=line_end,
-                        code_snippet=node_text,
-                        recommendation="Use Result/Option types with explicit error handling or the ? operator instead of panic!()."
-                    )
-        
-        # Process all children
-        for child in node.children:
-            self._find_panic_calls(child, code, results)
-            
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if a macro invocation is a panic!() call"""
+        node_text = self._get_node_text(node, code)
+
+        # Check if this is a panic! macro call
+        if not node_text.startswith("panic!"):
+            return
+        # Get the location to avoid duplicates
+        location = (node.start_point[0], node.end_point[0])
+
+        # Check if we've already reported this panic call
+        if location in self.found_locations:
+            return
+        self.found_locations.add(location)
+
+        line_start, line_end = self._get_line_for_node(node)
+        function_node = self._find_parent_function(node)
+        function_name = self._get_function_name(function_node, code) if function_node else "unknown"
+
+        results.add_issue(
+            issue_type="unsafe_panic",
+            severity="High",
+            description=f"Unsafe call to panic!() macro in function '{function_name}'. This causes immediate termination and cannot be caught.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=node_text,
+            recommendation="Use Result/Option types with explicit error handling or the ? operator instead of panic!()."
+        )
+
     def _find_parent_function(self, node: Node) -> Node:
         """Find the parent function containing this node"""
         parent = node.parent
@@ -83,9 +112,3 @@ class PanicDetector(BaseDetector):
     def _get_line_for_node(self, node: Node) -> tuple:
         """Get the line number for a node (1-indexed)"""
         return node.start_point[0] + 1, node.end_point[0] + 1
-    
-    def _get_node_text(self, node: Node, code: str) -> str:
-        """Get the text of a node from the code"""
-        if node.start_byte < 0 or node.end_byte > len(code) or node.start_byte >= node.end_byte:
-            return ""
-        return code[node.start_byte:node.end_byte] 
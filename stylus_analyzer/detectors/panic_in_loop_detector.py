@@ -0,0 +1,100 @@
+"""
+Detector for aborting macros in loops and public methods in Stylus Rust contracts
+"""
+from typing import Optional
+from tree_sitter import Node
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# Macros that abort the transaction; panic!() itself is reported by the panic detector
+ABORTING_MACROS = ("unreachable", "todo", "unimplemented", "assert", "assert_eq", "assert_ne")
+
+LOOP_TYPES = ("for_expression", "while_expression", "loop_expression")
+
+
+class PanicInLoopDetector(BaseDetector):
+    """
+    Detector for `assert!`-family, `unreachable!`, `todo!` and `unimplemented!`
+    macros inside loop bodies or `#[public]` methods.
+
+    These compile to aborts in Stylus that revert the whole transaction without
+    a decodable error, and inside a loop a late failure wastes the gas of every
+    earlier iteration.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.HIGH
+    cwe = 617
+    node_types = ("macro_invocation",)
+
+    documentation = DetectorDoc(
+        catches=(
+            "`assert!`, `assert_eq!`, `assert_ne!`, `unreachable!`, `todo!` and `unimplemented!` in loop bodies "
+            "or public methods."
+        ),
+        rationale=(
+            "They abort and revert the transaction without a decodable error, and a failure late in a loop wastes "
+            "the gas of every earlier iteration."
+        ),
+        vulnerable_example="""
+            for amount in amounts {
+                assert!(amount > U256::ZERO);
+                total += amount;
+            }
+        """,
+        fixed_example="""
+            for amount in amounts {
+                if amount == U256::ZERO {
+                    return Err(b"zero amount".to_vec());
+                }
+                total += amount;
+            }
+        """,
+        references=["https://swcregistry.io/docs/SWC-110"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="panic_in_loop",
+            description="Detects aborting macros inside loops and public methods"
+        )
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if an aborting macro is used in a loop or a public method"""
+        macro = self._get_macro_name(node, code)
+        if macro not in ABORTING_MACROS:
+            return
+        function_node = self._find_parent_function(node)
+        if function_node is None:
+            return
+        function_name = self._get_function_name(function_node, code)
+        loop = self._find_enclosing_loop(node)
+        if loop is not None:
+            loop_line, _ = self._get_line_for_node(loop)
+            location = f"inside the loop at line {loop_line} in function '{function_name}'"
+        elif self._is_public_method(function_node, code):
+            location = f"in public function '{function_name}'"
+        else:
+            return
+
+        line_start, line_end = self._get_line_for_node(node)
+        results.add_issue(
+            issue_type="panic_in_loop",
+            severity="Medium",
+            description=f"{macro}!() used {location}. It aborts the transaction without a recoverable error and consumes the gas spent so far.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=self._get_node_text(node, code),
+            recommendation=f"Replace {macro}!() with a check that returns an Err, so callers receive a proper revert reason."
+        )
+
+    def _find_enclosing_loop(self, node: Node) -> Optional[Node]:
+        """Find the innermost loop around a node within its function"""
+        current = node.parent
+        while current is not None and current.type not in ("function_item", "closure_expression"):
+            if current.type in LOOP_TYPES:
+                return current
+            current = current.parent
+        return None
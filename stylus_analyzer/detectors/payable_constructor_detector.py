@@ -0,0 +1,140 @@
+"""
+Detector for payable constructors whose deploy-time Ether is locked in Stylus Rust contracts
+"""
+from typing import Dict, List, Optional, Set
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import DetectorDoc
+from stylus_analyzer.detectors.locked_ether_detector import LockedEtherDetector
+from stylus_analyzer.detectors.msg_value_non_payable_detector import MSG_VALUE_PATTERN, SOL_MSG_VALUE_PATTERN
+from stylus_analyzer.findings import Confidence, Severity
+
+
+class PayableConstructorMismatchDetector(LockedEtherDetector):
+    """
+    Variant of the locked Ether detector for Ether sent along with the
+    deployment.
+
+    A constructor, `#[constructor]` or `new`/`init`-style, or a `deploy`
+    method that is `#[payable]` or reads `msg::value()` accepts Ether at
+    deployment. When it doesn't record the value in storage and no function
+    of the contract sends Ether out, that Ether is locked from the start. The
+    runtime locked Ether detector leaves constructors to this one, so its
+    findings are about the contract's other payable functions.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.MEDIUM
+
+    documentation = DetectorDoc(
+        catches=(
+            "Payable constructors and `deploy` methods, or ones reading `msg::value()`, that don't record the "
+            "value in storage in contracts with no function sending Ether out."
+        ),
+        rationale=(
+            "Ether sent with the deployment transaction stays in the contract forever, and nothing in the "
+            "contract's state says it was ever received."
+        ),
+        vulnerable_example="""
+            #[constructor]
+            #[payable]
+            pub fn constructor(&mut self, owner: Address) {
+                self.owner.set(owner);
+            }
+        """,
+        fixed_example="""
+            #[constructor]
+            pub fn constructor(&mut self, owner: Address) {
+                self.owner.set(owner);
+            }
+        """,
+        references=["https://docs.arbitrum.io/stylus/reference/rust-sdk-guide#constructor"],
+    )
+
+    def __init__(self):
+        super().__init__()
+        self.name = "payable_constructor_mismatch"
+        self.description = "Detects payable constructors whose deploy-time Ether can't be accounted for or withdrawn"
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect payable constructors of contracts that never send Ether out"""
+        self.has_withdrawal_method = False
+        self.withdrawal_functions = []
+        self._find_ether_withdrawal_functions(tree.root_node, code)
+        if self.has_withdrawal_method or self._has_sol_outbound_transfer(tree.root_node, code):
+            return
+
+        for node in self._iter_nodes(tree.root_node):
+            if node.type != "function_item" or not self._is_deploy_entrypoint(node, code):
+                continue
+            payable = self._get_item_flags(node, code).payable
+            value_read = self._find_value_read(node, code)
+            if not payable and value_read is None:
+                continue
+            if self._records_value(node, code):
+                continue
+            how = "is `#[payable]`" if payable else f"reads msg_value on line {self._get_line_for_node(value_read)[0]}"
+            body = node.child_by_field_name("body")
+            self._add_issue(node, body, f"'{self._get_function_name(node, code)}'", how, code, results)
+
+        for function in self._get_sol_functions(tree.root_node, code):
+            if function["name"] != "constructor" or "payable" not in function["modifiers"]:
+                continue
+            statements = self._split_sol_statements(function["body"])
+            if any(self._is_sol_value_record(statement, code) for statement in statements):
+                continue
+            self._add_issue(function["node"], function["body"], "sol! constructor", "is `payable`", code, results)
+
+    def _find_value_read(self, function_node: Node, code: str) -> Optional[Node]:
+        """Find the first `msg::value()` call of a function"""
+        for node in self._iter_nodes(function_node):
+            if node.type == "call_expression":
+                function = node.child_by_field_name("function")
+                if function is not None and MSG_VALUE_PATTERN.match(self._get_node_text(function, code)):
+                    return node
+        return None
+
+    def _records_value(self, function_node: Node, code: str) -> bool:
+        """Check if a function writes `msg::value()`, directly or through a local holding it, to storage"""
+        value_locals: Set[str] = set()
+        setter_aliases: Dict[str, str] = self._collect_setter_aliases(function_node, code)
+        for node in self._iter_nodes(function_node):
+            if node.type == "let_declaration":
+                pattern = node.child_by_field_name("pattern")
+                value = node.child_by_field_name("value")
+                if pattern is not None and value is not None and self._mentions_value(value, code, value_locals):
+                    value_locals.add(self._get_node_text(pattern, code).replace("mut ", "").strip())
+            elif self._get_storage_write_field(node, code, setter_aliases) and \
+                    self._mentions_value(node, code, value_locals):
+                return True
+        return False
+
+    def _mentions_value(self, node: Node, code: str, value_locals: Set[str]) -> bool:
+        """Check if an expression contains a `msg::value()` call or a local holding one"""
+        return self._find_value_read(node, code) is not None or \
+            any(child.type == "identifier" and self._get_node_text(child, code) in value_locals
+                for child in self._iter_nodes(node))
+
+    def _is_sol_value_record(self, statement: List[Node], code: str) -> bool:
+        """Check if a sol! statement assigns a value computed from `msg.value`"""
+        text = self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte)
+        return "=" in text.replace("==", "") and bool(SOL_MSG_VALUE_PATTERN.search(text))
+
+    def _add_issue(self, node: Node, body: Optional[Node], location: str, how: str, code: str, results) -> None:
+        """Add a payable constructor issue spanning a constructor's signature"""
+        line_start, line_end = self._get_line_for_node(node)
+        if body is not None:
+            _, line_end = self._get_line_for_node(body)
+        results.add_issue(
+            issue_type="payable_constructor_mismatch",
+            severity="Low",
+            description=f"The constructor {location} {how}, but it doesn't record the value and no function sends "
+                        "Ether out of the contract. Ether sent with the deployment is locked forever.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=self._get_source_text(code, node.start_byte, body.start_byte).strip() if body is not None
+            else self._get_node_text(node, code),
+            recommendation="Remove `#[payable]` if the deployment shouldn't carry Ether, or record the value in "
+                           "storage and add an access-controlled withdrawal function."
+        )
@@ -0,0 +1,151 @@
+"""
+Detector for public methods that are never called internally in Stylus Rust contracts
+"""
+import re
+from typing import Set
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc, SELECTOR_ATTRIBUTE_PATTERN
+from stylus_analyzer.abi_utils import to_camel_case
+from stylus_analyzer.findings import Confidence, Severity
+
+# Attributes routing calls to other types' methods through the ABI router
+ROUTING_ATTRIBUTES = ("inherit", "implements")
+
+
+class PublicFunctionCouldBeExternalDetector(BaseDetector):
+    """
+    Detector for methods of `#[public]` impls that no other method of the
+    contract calls through `self.method(...)` or `Self::method(...)`.
+
+    Methods only reached from outside the contract can be declared
+    `#[external]` to make the intent explicit and keep the internal API
+    small. The call graph only covers the analyzed file, so findings are
+    flagged with Low confidence. Constructors, receive/fallback, methods with
+    a `#[selector]` override, methods also defined by a trait and methods
+    declared in a `sol_interface!` are exported on purpose and skipped.
+    """
+
+    default_severity = Severity.INFO
+    default_confidence = Confidence.LOW
+
+    documentation = DetectorDoc(
+        catches="Methods of `#[public]` impls that no other method of the same type calls.",
+        rationale=(
+            "These methods are only reached through the ABI. Declaring them `#[external]` makes that explicit to "
+            "readers and reviewers."
+        ),
+        vulnerable_example="""
+            #[public]
+            impl Token {
+                pub fn total_supply(&self) -> U256 {
+                    self.total_supply.get()
+                }
+            }
+        """,
+        fixed_example="""
+            #[external]
+            impl Token {
+                pub fn total_supply(&self) -> U256 {
+                    self.total_supply.get()
+                }
+            }
+        """,
+        references=["https://swcregistry.io/docs/SWC-100"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="public_could_be_external",
+            description="Detects #[public] methods never called from inside the contract"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect public methods without internal callers"""
+        root = tree.root_node
+        call_graphs = self._get_call_graphs(root, code)
+        trait_methods = self._collect_trait_method_names(root, code)
+        interface_functions = self._collect_interface_function_names(root, code)
+        routed_types = self._collect_routed_types(root, code)
+
+        for node in self._iter_nodes(root):
+            if node.type != "impl_item" or node.child_by_field_name("trait") is not None:
+                continue
+            if "public" not in self._get_attribute_names(node, code):
+                continue
+            impl_type = node.child_by_field_name("type")
+            type_name = self._get_node_text(impl_type, code).split("<")[0].strip() if impl_type else ""
+            if type_name in routed_types:
+                continue
+            call_graph = call_graphs.get(type_name)
+            body = node.child_by_field_name("body")
+            for method in body.named_children if body else []:
+                if method.type != "function_item" or not self._is_public_method(method, code):
+                    continue
+                name = self._get_function_name(method, code)
+                if (call_graph and call_graph.callers(name)) or name in trait_methods:
+                    continue
+                if self._is_constructor(method, code) or self._is_receive_or_fallback(method, code):
+                    continue
+                if self._has_selector_override(method, code) or to_camel_case(name) in interface_functions:
+                    continue
+
+                line_start, line_end = self._get_line_for_node(method)
+                method_body = method.child_by_field_name("body")
+                results.add_issue(
+                    issue_type="public_could_be_external",
+                    severity="Info",
+                    description=f"Public method '{name}' of '{type_name}' is never called from inside the contract, only through the ABI.",
+                    line_start=line_start,
+                    line_end=line_end,
+                    column=self._get_column_for_node(method, code),
+                    code_snippet=self._get_source_text(code, method.start_byte, method_body.start_byte).strip() if method_body
+                    else self._get_node_text(method, code),
+                    recommendation="Declare the method `#[external]` if it is only meant to be called from outside the contract."
+                )
+
+    def _collect_trait_method_names(self, root: Node, code: str) -> Set[str]:
+        """Collect the names of methods declared by traits or defined in trait impls"""
+        names = set()
+        for node in self._iter_nodes(root):
+            if node.type not in ("function_item", "function_signature_item"):
+                continue
+            container = node.parent.parent if node.parent is not None else None
+            if container is None:
+                continue
+            if container.type == "trait_item" or \
+                    (container.type == "impl_item" and container.child_by_field_name("trait") is not None):
+                names.add(self._get_function_name(node, code))
+        return names
+
+    def _collect_interface_function_names(self, root: Node, code: str) -> Set[str]:
+        """Collect the function names declared in `sol_interface!` macros"""
+        names = set()
+        for node in self._iter_nodes(root):
+            if node.type == "macro_invocation" and self._get_macro_name(node, code) == "sol_interface":
+                names.update(re.findall(r"\bfunction\s+(\w+)", self._get_node_text(node, code)))
+        return names
+
+    def _collect_routed_types(self, root: Node, code: str) -> Set[str]:
+        """Collect the types listed in `#[inherit(...)]` or `#[implements(...)]` attributes"""
+        types = set()
+        for node in self._iter_nodes(root):
+            if node.type != "attribute_item":
+                continue
+            for attribute in node.named_children:
+                if attribute.type != "attribute" or not attribute.children:
+                    continue
+                path = self._get_node_text(attribute.children[0], code).split("::")[-1]
+                if path in ROUTING_ATTRIBUTES:
+                    arguments = self._get_node_text(attribute, code)[len(self._get_node_text(attribute.children[0], code)):]
+                    types.update(re.findall(r"\b[A-Z]\w*", arguments))
+        return types
+
+    def _has_selector_override(self, function_node: Node, code: str) -> bool:
+        """Check if a method is exported under a `#[selector(name = "...")]` override"""
+        sibling = function_node.prev_sibling
+        while sibling and sibling.type in ("attribute_item", "line_comment", "block_comment"):
+            if sibling.type == "attribute_item" and SELECTOR_ATTRIBUTE_PATTERN.search(self._get_node_text(sibling, code)):
+                return True
+            sibling = sibling.prev_sibling
+        return False
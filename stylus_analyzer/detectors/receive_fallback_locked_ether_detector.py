@@ -0,0 +1,129 @@
+"""
+Detector for Ether locked in contracts that only receive it through receive/fallback
+"""
+from typing import Dict, List
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import DetectorDoc, ETHER_ENTRYPOINTS
+from stylus_analyzer.detectors.locked_ether_detector import LockedEtherDetector
+from stylus_analyzer.findings import Confidence, Severity
+
+
+class ReceiveFallbackPayableNoWithdrawDetector(LockedEtherDetector):
+    """
+    Variant of the locked Ether detector for contracts whose only Ether
+    ingress is a `receive` or `fallback` entrypoint.
+
+    Plain transfers land in `receive`/`fallback` without calling any payable
+    method, so such contracts accumulate Ether even when none of their
+    functions is `#[payable]`. Contracts that also have payable functions are
+    left to the locked Ether detector, so a contract is reported only once.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.MEDIUM
+
+    documentation = DetectorDoc(
+        catches=(
+            "Contracts whose only Ether ingress is a `#[receive]` or payable `#[fallback]` entrypoint and that "
+            "have no function sending Ether out."
+        ),
+        rationale=(
+            "Plain transfers land in these entrypoints, so the contract accumulates Ether that can never be "
+            "withdrawn."
+        ),
+        vulnerable_example="""
+            #[public]
+            impl Wallet {
+                #[receive]
+                pub fn receive(&mut self) -> Result<(), Vec<u8>> {
+                    Ok(())
+                }
+            }
+        """,
+        fixed_example="""
+            #[public]
+            impl Wallet {
+                #[receive]
+                pub fn receive(&mut self) -> Result<(), Vec<u8>> {
+                    Ok(())
+                }
+
+                pub fn withdraw(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+                    if msg::sender() != self.owner.get() {
+                        return Err(b"not the owner".to_vec());
+                    }
+                    transfer_eth(msg::sender(), amount)
+                }
+            }
+        """,
+        references=["https://github.com/crytic/slither/wiki/Detector-Documentation"],
+    )
+
+    def __init__(self):
+        super().__init__()
+        self.name = "receive_fallback_locked_ether"
+        self.description = "Detects contracts that receive Ether through receive/fallback but can't send it out"
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect receive/fallback-only contracts without an outbound transfer"""
+        self.can_receive_ether = False
+        self.has_withdrawal_method = False
+        self.payable_functions = []
+        self.withdrawal_functions = []
+
+        entrypoints = self._collect_entrypoints(tree.root_node, code)
+        if not entrypoints:
+            return
+        self._find_ether_receiving_functions(tree.root_node, code)
+        if self.can_receive_ether:
+            return
+        self._find_ether_withdrawal_functions(tree.root_node, code)
+        if self.has_withdrawal_method or self._has_sol_outbound_transfer(tree.root_node, code):
+            return
+
+        names = ", ".join(f"'{entrypoint['name']}' (line {entrypoint['line_start']})" for entrypoint in entrypoints)
+        first = entrypoints[0]
+        results.add_issue(
+            issue_type="receive_fallback_locked_ether",
+            severity="Medium",
+            description=f"Contract accepts Ether through {names} but has no function that transfers Ether out. Funds sent to the contract become permanently locked.",
+            line_start=first["line_start"],
+            line_end=first["line_end"],
+            column=first["column"],
+            code_snippet=first["text"],
+            recommendation="Add an access-controlled withdrawal function, or make receive/fallback non-payable (or revert) if the contract should not hold Ether."
+        )
+
+    def _collect_entrypoints(self, root: Node, code: str) -> List[Dict]:
+        """Collect the Rust and sol! receive/fallback entrypoints that accept Ether"""
+        entrypoints = []
+        for node in self._iter_nodes(root):
+            if node.type != "function_item" or not self._is_receive_or_fallback(node, code):
+                continue
+            flags = self._get_item_flags(node, code)
+            # receive is always payable, fallback only accepts Ether when marked #[payable]
+            if flags.receive or self._get_function_name(node, code) == "receive" or flags.payable:
+                body = node.child_by_field_name("body")
+                line_start, line_end = self._get_line_for_node(node)
+                entrypoints.append({
+                    "name": self._get_function_name(node, code),
+                    "line_start": line_start,
+                    "line_end": line_end,
+                    "column": self._get_column_for_node(node, code),
+                    "text": self._get_source_text(code, node.start_byte, body.start_byte).strip() if body else self._get_node_text(node, code),
+                })
+        for function in self._get_sol_functions(root, code):
+            if function["name"] not in ETHER_ENTRYPOINTS or \
+                    "payable" not in function["modifiers"]:
+                continue
+            line_start, _ = self._get_line_for_node(function["node"])
+            _, line_end = self._get_line_for_node(function["body"])
+            entrypoints.append({
+                "name": function["name"],
+                "line_start": line_start,
+                "line_end": line_end,
+                "column": self._get_column_for_node(function["node"], code),
+                "text": self._get_source_text(code, function["node"].start_byte, function["body"].start_byte).strip(),
+            })
+        return entrypoints
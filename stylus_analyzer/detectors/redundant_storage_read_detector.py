@@ -0,0 +1,169 @@
+"""
+Detector for repeated reads of the same storage slot in Stylus Rust contracts
+"""
+from typing import Dict, List, Optional, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+
+class RedundantStorageReadDetector(BaseDetector):
+    """
+    Detector for storage fields read more than once without an intervening write.
+
+    Every `.get()` on a storage field is an SLOAD, so reading the value once
+    into a local is cheaper. Reads separated by a write to the field or by a
+    call that could modify storage are not considered redundant.
+    """
+
+    default_severity = Severity.INFO
+    default_confidence = Confidence.MEDIUM
+
+    documentation = DetectorDoc(
+        catches="A storage field read with `.get()` more than once in a function without a write in between.",
+        rationale=(
+            "Every read is an SLOAD. Reading the value once into a local is cheaper and gives the same result."
+        ),
+        vulnerable_example="""
+            pub fn fee(&self, amount: U256) -> U256 {
+                if self.rate.get() == U256::ZERO {
+                    return U256::ZERO;
+                }
+                amount * self.rate.get() / U256::from(10_000)
+            }
+        """,
+        fixed_example="""
+            pub fn fee(&self, amount: U256) -> U256 {
+                let rate = self.rate.get();
+                if rate == U256::ZERO {
+                    return U256::ZERO;
+                }
+                amount * rate / U256::from(10_000)
+            }
+        """,
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="redundant_storage_read",
+            description="Detects storage fields read multiple times without an intervening write"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect redundant storage reads in every function"""
+        interfaces = self._collect_interface_names(tree.root_node, code)
+        for function_node in self._iter_nodes(tree.root_node):
+            if function_node.type == "function_item":
+                self._check_function(function_node, code, interfaces, results)
+
+    def _check_function(self, function_node: Node, code: str, interfaces, results) -> None:
+        """Check a single function for repeated reads"""
+        body = function_node.child_by_field_name("body")
+        if not body:
+            return
+        handles = self._collect_interface_handles(function_node, code, interfaces)
+        setter_aliases = self._collect_setter_aliases(function_node, code)
+
+        # Reads take effect where they start, writes and calls once their arguments are evaluated
+        events: List[Tuple[int, str, Node, Optional[Tuple[str, str]]]] = []
+        for node in self._iter_nodes(body):
+            read = self._get_storage_read(node, code)
+            if read:
+                events.append((node.start_byte, "read", node, read))
+                continue
+            field = self._get_storage_write_field(node, code, setter_aliases)
+            if field:
+                events.append((node.end_byte, "write", node, (field, "")))
+            elif self._may_modify_storage(node, code, interfaces, handles):
+                events.append((node.end_byte, "call", node, None))
+        events.sort(key=lambda event: event[0])
+
+        first_reads: Dict[Tuple[str, str], Node] = {}
+        redundant: Dict[str, List[Node]] = {}
+        for _, kind, node, access in events:
+            if kind == "call":
+                first_reads.clear()
+            elif kind == "write":
+                first_reads = {key: read for key, read in first_reads.items() if key[0] != access[0]}
+            elif access in first_reads and not self._are_exclusive(first_reads[access], node):
+                reads = redundant.setdefault(access[0], [])
+                if not any(read.start_byte == first_reads[access].start_byte for read in reads):
+                    reads.append(first_reads[access])
+                reads.append(node)
+            else:
+                first_reads[access] = node
+
+        function_name = self._get_function_name(function_node, code)
+        for field, reads in redundant.items():
+            reads.sort(key=lambda read: read.start_byte)
+            lines = [self._get_line_for_node(read)[0] for read in reads]
+            results.add_issue(
+                issue_type="redundant_storage_read",
+                severity="Info",
+                description=f"Storage field '{field}' is read {len(reads)} times in function '{function_name}' (lines {', '.join(str(line) for line in lines)}) without an intervening write.",
+                line_start=lines[0],
+                line_end=self._get_line_for_node(reads[-1])[1],
+                column=self._get_column_for_node(reads[0], code),
+                code_snippet="\n".join(self._get_node_text(read, code) for read in reads),
+                recommendation="Read the value once into a local variable and reuse it."
+            )
+
+    def _get_storage_read(self, node: Node, code: str) -> Optional[Tuple[str, str]]:
+        """Get the field and key text of a `self.<field>.get(...)` read"""
+        if node.type != "call_expression":
+            return None
+        function = node.child_by_field_name("function")
+        if not function or function.type != "field_expression":
+            return None
+        method = function.child_by_field_name("field")
+        receiver = function.child_by_field_name("value")
+        if not method or not receiver or self._get_node_text(method, code) != "get":
+            return None
+        if receiver.type != "field_expression" or not receiver.child_by_field_name("value") or \
+                receiver.child_by_field_name("value").type != "self":
+            return None
+        field = self._get_self_field(receiver, code)
+        arguments = node.child_by_field_name("arguments")
+        key = self._get_node_text(arguments, code) if arguments else "()"
+        return (field, "".join(key.split())) if field else None
+
+    def _may_modify_storage(self, node: Node, code: str, interfaces, handles) -> bool:
+        """Check if a call could change storage behind the function's back"""
+        if node.type != "call_expression":
+            return False
+        if self._is_external_call(node, code, interfaces, handles):
+            return True
+        function = node.child_by_field_name("function")
+        if function and function.type == "field_expression":
+            receiver = function.child_by_field_name("value")
+            if receiver and receiver.type == "self":
+                # Internal methods can write any field
+                return True
+        arguments = node.child_by_field_name("arguments")
+        return bool(arguments) and any(argument.type == "self" or self._get_node_text(argument, code) in ("&mut self", "self")
+                                       for argument in arguments.named_children)
+
+    def _branch_path(self, node: Node) -> Dict[int, int]:
+        """Map each enclosing `if`/`match` to the branch the node is in"""
+        path = {}
+        child = node
+        parent = node.parent
+        while parent and parent.type != "function_item":
+            if parent.type == "if_expression":
+                alternative = parent.child_by_field_name("alternative")
+                in_alternative = alternative is not None and child.start_byte >= alternative.start_byte
+                condition = parent.child_by_field_name("condition")
+                if not (condition and condition.start_byte <= child.start_byte < condition.end_byte):
+                    path[parent.start_byte] = 1 if in_alternative else 0
+            elif parent.type == "match_arm" and parent.parent:
+                path[parent.parent.start_byte] = parent.start_byte
+            child = parent
+            parent = parent.parent
+        return path
+
+    def _are_exclusive(self, first: Node, second: Node) -> bool:
+        """Check if two nodes are in different branches of the same if or match"""
+        first_path = self._branch_path(first)
+        second_path = self._branch_path(second)
+        return any(key in second_path and second_path[key] != branch for key, branch in first_path.items())
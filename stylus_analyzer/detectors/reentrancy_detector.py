@@ -0,0 +1,110 @@
+"""
+Detector for reentrancy-prone state updates in Stylus Rust contracts
+"""
+import re
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# Field names that indicate a manual reentrancy guard
+GUARD_FIELD_PATTERN = re.compile(r"lock|guard|entered|reentran|mutex|status", re.IGNORECASE)
+
+
+class ReentrancyDetector(BaseDetector):
+    """
+    Detector for storage writes that happen after an external call.
+
+    Violating the checks-effects-interactions pattern lets the callee re-enter
+    the contract while its storage still reflects the pre-call state.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.MEDIUM
+    cwe = 841
+
+    documentation = DetectorDoc(
+        catches=(
+            "Public methods that write storage after an external call, through a `sol_interface!` handle or a "
+            "low-level call."
+        ),
+        rationale=(
+            "The callee can call back into the contract while its storage still holds the state from before the "
+            "call, e.g. to withdraw the same balance twice."
+        ),
+        vulnerable_example="""
+            pub fn withdraw(&mut self) -> Result<(), Vec<u8>> {
+                let balance = self.balances.get(msg::sender());
+                call(Call::new_in(self).value(balance), msg::sender(), &[])?;
+                self.balances.setter(msg::sender()).set(U256::ZERO);
+                Ok(())
+            }
+        """,
+        fixed_example="""
+            pub fn withdraw(&mut self) -> Result<(), Vec<u8>> {
+                let balance = self.balances.get(msg::sender());
+                self.balances.setter(msg::sender()).set(U256::ZERO);
+                call(Call::new_in(self).value(balance), msg::sender(), &[])?;
+                Ok(())
+            }
+        """,
+        references=[
+            "https://swcregistry.io/docs/SWC-107",
+            "https://docs.soliditylang.org/en/latest/security-considerations.html",
+        ],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="reentrancy",
+            description="Detects storage writes that follow an external call in public methods"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect state writes after external calls in the contract"""
+        interfaces = self._collect_interface_names(tree.root_node, code)
+        for function_node in self._get_public_methods(tree.root_node, code):
+            self._check_function(function_node, code, interfaces, results)
+
+    def _check_function(self, function_node: Node, code: str, interfaces, results) -> None:
+        """Check a single public method for writes after an external call"""
+        body = function_node.child_by_field_name("body")
+        if not body:
+            return
+
+        handles = self._collect_interface_handles(function_node, code, interfaces)
+        setter_aliases = self._collect_setter_aliases(function_node, code)
+        function_name = self._get_function_name(function_node, code)
+
+        first_call = None
+        reported_fields = set()
+        for node in self._iter_nodes(body):
+            if first_call is None:
+                field = self._get_storage_write_field(node, code, setter_aliases)
+                if field and GUARD_FIELD_PATTERN.search(field):
+                    # A reentrancy guard is toggled before any external call
+                    return
+                if self._is_external_call(node, code, interfaces, handles):
+                    first_call = node
+                continue
+
+            if node.start_byte < first_call.end_byte:
+                continue
+
+            field = self._get_storage_write_field(node, code, setter_aliases)
+            if not field or field in reported_fields:
+                continue
+            reported_fields.add(field)
+
+            call_line, _ = self._get_line_for_node(first_call)
+            write_line, write_end = self._get_line_for_node(node)
+            results.add_issue(
+                issue_type="reentrancy",
+                severity="Medium",
+                description=f"Storage field '{field}' is written at line {write_line} after an external call at line {call_line} in function '{function_name}'. The callee can re-enter the contract before the state is updated.",
+                line_start=call_line,
+                line_end=write_end,
+                column=self._get_column_for_node(first_call, code),
+                code_snippet=f"{self._get_node_text(first_call, code)}\n...\n{self._get_node_text(node, code)}",
+                recommendation="Follow the checks-effects-interactions pattern: update storage before making external calls, or protect the function with a reentrancy guard."
+            )
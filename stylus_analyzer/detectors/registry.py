@@ -0,0 +1,149 @@
+"""
+Registry of the detectors available to the static analyzer
+"""
+from typing import Any, Dict, Iterable, Iterator, List, Optional, Set
+
+from stylus_analyzer.detectors import get_available_detectors
+from stylus_analyzer.detectors.detector_base import BaseDetector
+from stylus_analyzer.findings import Severity
+
+
+class DetectorRegistry:
+    """
+    Collection of detector instances keyed by their id.
+
+    Detectors keep their registration order, which is the order they run
+    and are listed in. Every detector starts out enabled.
+    """
+
+    def __init__(self, detectors: Iterable[BaseDetector] = ()):
+        self._detectors: Dict[str, BaseDetector] = {}
+        self._disabled: Set[str] = set()
+        self._severity_overrides: Dict[str, Severity] = {}
+        self._options: Dict[str, Dict[str, Any]] = {}
+        for detector in detectors:
+            self.register(detector)
+
+    @classmethod
+    def with_builtin_detectors(cls) -> "DetectorRegistry":
+        """Create a registry holding an instance of every available detector"""
+        return cls(detector_class() for detector_class in get_available_detectors())
+
+    def register(self, detector: BaseDetector) -> None:
+        """
+        Register a detector instance
+
+        Raises:
+            ValueError: If a detector with the same id is already registered
+        """
+        if detector.id in self._detectors:
+            raise ValueError(f"Detector '{detector.id}' is already registered")
+        self._detectors[detector.id] = detector
+
+    def get(self, detector_id: str) -> BaseDetector:
+        """
+        Get a registered detector by id
+
+        Raises:
+            ValueError: If no detector has this id
+        """
+        if detector_id not in self._detectors:
+            raise ValueError(f"Unknown detector '{detector_id}'")
+        return self._detectors[detector_id]
+
+    def find(self, name: str) -> Optional[BaseDetector]:
+        """
+        Look up a registered detector by id or by class name
+
+        Names are compared ignoring case, `_` and `-` separators and a
+        trailing `Detector`, so `MissingAccessControl` finds
+        `missing_access_control`.
+        """
+        if name in self._detectors:
+            return self._detectors[name]
+        key = _lookup_key(name)
+        for detector in self._detectors.values():
+            if key in (_lookup_key(detector.id), _lookup_key(type(detector).__name__)):
+                return detector
+        return None
+
+    def enable(self, detector_id: str) -> None:
+        """Enable a registered detector"""
+        self.get(detector_id)
+        self._disabled.discard(detector_id)
+
+    def disable(self, detector_id: str) -> None:
+        """Disable a registered detector so it no longer runs"""
+        self.get(detector_id)
+        self._disabled.add(detector_id)
+
+    def is_enabled(self, detector_id: str) -> bool:
+        """Check if a registered detector is enabled"""
+        self.get(detector_id)
+        return detector_id not in self._disabled
+
+    def override_severity(self, detector_id: str, severity: Severity) -> None:
+        """Report every finding of a registered detector at the given severity"""
+        self.get(detector_id).default_severity = severity
+        self._severity_overrides[detector_id] = severity
+
+    def severity_override(self, detector_id: str) -> Optional[Severity]:
+        """Get the severity a detector's findings are reported at, if overridden"""
+        return self._severity_overrides.get(detector_id)
+
+    def configure(self, detector_id: str, options: Dict[str, Any]) -> None:
+        """
+        Rebuild a registered detector with options passed to its constructor
+
+        The detector keeps its place in the registry and its severity override.
+
+        Raises:
+            ValueError: If the detector has no such option or a value has the wrong type
+        """
+        detector = self.get(detector_id)
+        for name, value in options.items():
+            if name not in detector.options:
+                valid = ", ".join(sorted(detector.options)) or "none"
+                raise ValueError(f"Unknown option '{name}' for detector '{detector_id}'. Valid options: {valid}.")
+            expected = detector.options[name]
+            if expected is list:
+                valid_value = isinstance(value, list) and all(isinstance(item, str) for item in value)
+            else:
+                # bool is a subclass of int, but `max_statements = true` is a mistake
+                valid_value = isinstance(value, expected) and (expected is bool or not isinstance(value, bool))
+            if not valid_value:
+                kind = "a list of strings" if expected is list else f"of type {expected.__name__}"
+                raise ValueError(f"Option '{name}' of detector '{detector_id}' must be {kind}")
+        configured = type(detector)(**options)
+        if detector_id in self._severity_overrides:
+            configured.default_severity = self._severity_overrides[detector_id]
+        self._detectors[detector_id] = configured
+        self._options[detector_id] = dict(options)
+
+    def detector_options(self, detector_id: str) -> Dict[str, Any]:
+        """Get the options a detector was configured with, empty if it runs with its defaults"""
+        return self._options.get(detector_id, {})
+
+    def ids(self) -> List[str]:
+        """Get the ids of all registered detectors"""
+        return list(self._detectors)
+
+    def enabled_ids(self) -> List[str]:
+        """Get the ids of the enabled detectors"""
+        return [detector_id for detector_id in self._detectors if detector_id not in self._disabled]
+
+    def enabled_detectors(self) -> List[BaseDetector]:
+        """Get the enabled detector instances"""
+        return [self._detectors[detector_id] for detector_id in self.enabled_ids()]
+
+    def __iter__(self) -> Iterator[BaseDetector]:
+        return iter(self._detectors.values())
+
+    def __len__(self) -> int:
+        return len(self._detectors)
+
+
+def _lookup_key(name: str) -> str:
+    """Normalize a detector id or class name for `DetectorRegistry.find`"""
+    key = name.lower().replace("_", "").replace("-", "")
+    return key[:-len("detector")] if key.endswith("detector") and key != "detector" else key
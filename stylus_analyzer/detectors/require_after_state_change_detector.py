@@ -0,0 +1,188 @@
+"""
+Detector for input validation performed after storage writes in Stylus Rust contracts
+"""
+from typing import Dict, Iterator, List, Optional, Set, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.dead_code_after_revert_detector import DIVERGING_MACROS, REVERT_PATTERN
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc, GUARD_MACROS
+from stylus_analyzer.detectors.require_without_message_detector import ERR_PATTERN
+from stylus_analyzer.findings import Confidence, Severity
+
+# A storage write seen before a check: the node writing, the field and the parameters it depends on
+StorageWrite = Tuple[Node, str, Set[str]]
+
+
+class RequireAfterStateChangeDetector(BaseDetector):
+    """
+    Detector for checks of a function's parameters that come after a storage
+    write depending on the same parameters.
+
+    The statements of the function body are followed in order. A check is a
+    guard macro such as `assert!` or an `if` whose block reverts with
+    `return Err(...)`, `evm::revert(...)` or `panic!()`. A write depends on a
+    parameter when it mentions it, directly or through a local computed from
+    it, like `balance` in `let balance = self.balances.get(from)`. The revert
+    rolls the write back, so this is about ordering rather than an exploit,
+    but the check belongs before the state it protects.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.MEDIUM
+    cwe = 696
+
+    documentation = DetectorDoc(
+        catches=(
+            "`assert!`/`require`-style guards and `if ... { return Err(...) }` checks of a parameter that come "
+            "after a storage write depending on that parameter."
+        ),
+        rationale=(
+            "Validating after the state change works only because the revert rolls it back. It makes the function "
+            "harder to audit, spends gas on writes that are undone, and behaves surprisingly for callers that "
+            "catch the revert."
+        ),
+        vulnerable_example="""
+            pub fn burn(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+                let balance = self.balances.get(msg::sender());
+                self.balances.setter(msg::sender()).set(balance - amount);
+                if balance < amount {
+                    return Err(b"insufficient balance".to_vec());
+                }
+                Ok(())
+            }
+        """,
+        fixed_example="""
+            pub fn burn(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+                let balance = self.balances.get(msg::sender());
+                if balance < amount {
+                    return Err(b"insufficient balance".to_vec());
+                }
+                self.balances.setter(msg::sender()).set(balance - amount);
+                Ok(())
+            }
+        """,
+        references=["https://docs.soliditylang.org/en/latest/security-considerations.html#use-the-checks-effects-interactions-pattern"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="require_after_state_change",
+            description="Detects input validation performed after a storage write depending on the same inputs"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect parameter checks that follow storage writes using the same parameters"""
+        for function_node in self._iter_nodes(tree.root_node):
+            if function_node.type != "function_item":
+                continue
+            parameters = self._collect_parameter_names(function_node, code)
+            body = function_node.child_by_field_name("body")
+            if not parameters or body is None:
+                continue
+            self._check_function(function_node, body, parameters, code, results)
+
+    def _check_function(self, function_node: Node, body: Node, parameters: Set[str], code: str, results) -> None:
+        """Follow a function body in order, reporting the first check after each conflicting write"""
+        setter_aliases = self._collect_setter_aliases(function_node, code)
+        # The parameters each local is computed from
+        depends: Dict[str, Set[str]] = {name: {name} for name in parameters}
+        writes: List[StorageWrite] = []
+        for statement in body.named_children:
+            guard = self._get_check(statement, code)
+            if guard is not None:
+                checked = self._get_dependencies(guard, code, depends)
+                conflicting = [write for write in writes if write[2] & checked]
+                if conflicting:
+                    self._add_issue(statement, conflicting[0], checked, function_node, code, results)
+                    writes = [write for write in writes if write not in conflicting]
+                continue
+            if statement.type == "let_declaration":
+                self._record_local(statement, code, depends)
+            for node in self._iter_executed_nodes(statement):
+                field = self._get_storage_write_field(node, code, setter_aliases)
+                if field:
+                    writes.append((node, field, self._get_dependencies(node, code, depends)))
+
+    def _iter_executed_nodes(self, node: Node) -> Iterator[Node]:
+        """Iterate over a statement's nodes in source order, skipping closures and nested functions that may never run"""
+        stack = [node]
+        while stack:
+            current = stack.pop()
+            if current is not node and current.type in ("closure_expression", "function_item"):
+                continue
+            yield current
+            stack.extend(reversed(current.children))
+
+    def _record_local(self, declaration: Node, code: str, depends: Dict[str, Set[str]]) -> None:
+        """Record the parameters a `let` binding is computed from"""
+        pattern = declaration.child_by_field_name("pattern")
+        value = declaration.child_by_field_name("value")
+        if pattern is None or value is None:
+            return
+        if pattern.type == "mut_pattern" and pattern.named_children:
+            pattern = pattern.named_children[-1]
+        if pattern.type == "identifier":
+            depends[self._get_node_text(pattern, code)] = self._get_dependencies(value, code, depends)
+
+    def _get_dependencies(self, node: Node, code: str, depends: Dict[str, Set[str]]) -> Set[str]:
+        """Get the parameters an expression mentions, directly or through locals computed from them"""
+        found: Set[str] = set()
+        for child in self._iter_nodes(node):
+            if child.type == "identifier":
+                found |= depends.get(self._get_node_text(child, code), set())
+        return found
+
+    def _get_check(self, statement: Node, code: str) -> Optional[Node]:
+        """Get the condition or guard macro of a statement that reverts when its inputs are invalid"""
+        expression = statement.named_children[0] if statement.type == "expression_statement" and \
+            statement.named_children else statement
+        if expression.type == "macro_invocation" and self._get_macro_name(expression, code) in GUARD_MACROS:
+            return expression
+        if expression.type != "if_expression" or expression.child_by_field_name("alternative") is not None:
+            return None
+        condition = expression.child_by_field_name("condition")
+        consequence = expression.child_by_field_name("consequence")
+        if condition is None or condition.type == "let_condition" or consequence is None:
+            return None
+        return condition if self._reverts(consequence, code) else None
+
+    def _reverts(self, block: Node, code: str) -> bool:
+        """Check if a block returns an error, reverts or panics at its top level"""
+        for statement in block.named_children:
+            expression = statement.named_children[0] if statement.type == "expression_statement" and \
+                statement.named_children else statement
+            if expression.type == "return_expression":
+                value = expression.named_children[0] if expression.named_children else None
+                function = value.child_by_field_name("function") if value is not None and \
+                    value.type == "call_expression" else None
+                if function is not None and ERR_PATTERN.match(self._get_node_text(function, code)):
+                    return True
+            elif expression.type == "macro_invocation":
+                if self._get_macro_name(expression, code) in DIVERGING_MACROS:
+                    return True
+            elif expression.type == "call_expression":
+                function = expression.child_by_field_name("function")
+                if function is not None and REVERT_PATTERN.match(self._get_node_text(function, code)):
+                    return True
+        return False
+
+    def _add_issue(self, check: Node, write: StorageWrite, checked: Set[str], function_node: Node, code: str,
+                   results) -> None:
+        """Add a late validation issue at the check"""
+        write_node, field, write_depends = write
+        names = ", ".join(f"'{name}'" for name in sorted(write_depends & checked))
+        write_line, _ = self._get_line_for_node(write_node)
+        line_start, line_end = self._get_line_for_node(check)
+        function_name = self._get_function_name(function_node, code)
+        results.add_issue(
+            issue_type="require_after_state_change",
+            severity="Low",
+            description=f"Function '{function_name}' validates {names} only after writing storage field '{field}' "
+                        f"on line {write_line} from it. The revert undoes the write, but the check runs too late.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(check, code),
+            code_snippet=self._get_node_text(check, code),
+            recommendation="Move the check before the first storage write it protects, following the "
+                           "checks-effects-interactions pattern."
+        )
@@ -0,0 +1,113 @@
+"""
+Detector for reverts without a reason in Stylus Rust contracts
+"""
+import re
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.dead_code_after_revert_detector import REVERT_PATTERN
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# Revert payloads without any bytes, optionally converted with `.into()`
+EMPTY_PAYLOAD_PATTERN = re.compile(
+    r'^(?:Vec\s*(?:::\s*<\s*u8\s*>\s*)?::\s*new\s*\(\s*\)|vec!\s*[\[(]\s*[\])]|b""(?:\s*\.\s*to_vec\s*\(\s*\))?|'
+    r'&?\s*\[\s*\]|Default\s*::\s*default\s*\(\s*\)|\(\s*\))(?:\s*\.\s*into\s*\(\s*\))?$'
+)
+
+# `Err(...)`, possibly written with its path
+ERR_PATTERN = re.compile(r"^(?:(?:std\s*::\s*)?(?:result\s*::\s*)?Result\s*::\s*)?Err$")
+
+
+class RequireWithoutMessageDetector(BaseDetector):
+    """
+    Detector for reverts that give no reason.
+
+    In Rust these are `Err(...)` values and `evm::revert(...)` calls with an
+    empty payload, such as `Err(Vec::new())` or `Err(vec![])`. In `sol!`
+    functions they are `require(cond)` without a message and a bare
+    `revert()`. Typed errors carry their own reason and are not reported.
+    Teams that leave reasons out to save bytecode can disable the detector.
+    """
+
+    default_severity = Severity.INFO
+    default_confidence = Confidence.HIGH
+    cwe = 755
+    node_types = ("call_expression",)
+
+    documentation = DetectorDoc(
+        catches=(
+            "`Err(...)` values and `evm::revert(...)` calls with an empty payload, and `require(cond)` without a "
+            "message or a bare `revert()` in `sol!` functions."
+        ),
+        rationale=(
+            "A revert without a reason gives callers, wallets and explorers nothing to show, which makes failed "
+            "transactions hard to debug."
+        ),
+        vulnerable_example="""
+            if amount > self.balances.get(msg::sender()) {
+                return Err(Vec::new());
+            }
+        """,
+        fixed_example="""
+            if amount > self.balances.get(msg::sender()) {
+                return Err(b"insufficient balance".to_vec());
+            }
+        """,
+        references=["https://docs.soliditylang.org/en/latest/control-structures.html#revert"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="require_without_message",
+            description="Detects reverts that provide no human-readable reason"
+        )
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if a call returns an `Err` or reverts with an empty payload"""
+        function = node.child_by_field_name("function")
+        arguments = node.child_by_field_name("arguments")
+        if function is None or arguments is None:
+            return
+        callee = self._get_node_text(function, code)
+        if not (ERR_PATTERN.match(callee) or REVERT_PATTERN.match(callee)):
+            return
+        payload = arguments.named_children
+        if len(payload) == 1 and EMPTY_PAYLOAD_PATTERN.match(self._get_node_text(payload[0], code)):
+            parent = self._find_parent_function(node)
+            location = f" in function '{self._get_function_name(parent, code)}'" if parent else ""
+            self._add_issue(node, node, f"`{callee}(...)`{location} has an empty payload", code, results)
+
+    def finish(self, tree: Tree, code: str, results) -> None:
+        """Check the require and revert statements of sol! functions for a message"""
+        for function in self._get_sol_functions(tree.root_node, code):
+            for statement in self._split_sol_statements(function["body"]):
+                keyword = self._get_node_text(statement[0], code)
+                arguments = statement[1] if len(statement) > 1 and self._is_token_tree(statement[1], "(") else None
+                if arguments is None or len(statement) > 2:
+                    continue
+                if keyword == "require" and not self._has_top_level_comma(arguments):
+                    self._add_issue(statement[0], statement[-1], f"`require` in sol! function "
+                                    f"'{function['name']}' has no message", code, results)
+                elif keyword == "revert" and len(arguments.children) == 2:
+                    self._add_issue(statement[0], statement[-1], f"`revert()` in sol! function "
+                                    f"'{function['name']}' has no reason", code, results)
+
+    def _has_top_level_comma(self, arguments: Node) -> bool:
+        """Check if a `( ... )` token tree holds more than one argument"""
+        return any(child.type == "," for child in arguments.children[1:-1])
+
+    def _add_issue(self, start: Node, end: Node, what: str, code: str, results) -> None:
+        """Add a revert without message issue spanning from one node to another"""
+        line_start, _ = self._get_line_for_node(start)
+        _, line_end = self._get_line_for_node(end)
+        results.add_issue(
+            issue_type="require_without_message",
+            severity="Info",
+            description=f"The {what}, so the failure reaches callers without a human-readable reason.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(start, code),
+            code_snippet=self._get_source_text(code, start.start_byte, end.end_byte),
+            recommendation="Add a descriptive message, e.g. `Err(b\"insufficient balance\".to_vec())` or "
+                           "`require(cond, \"insufficient balance\")`, or use a typed error."
+        )
@@ -0,0 +1,92 @@
+"""
+Detector for 4-byte selector collisions between Stylus contract methods
+"""
+from typing import Dict, List, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.abi_utils import compute_selector
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+
+class SelectorCollisionDetector(BaseDetector):
+    """
+    Detector for externally callable methods whose different signatures hash
+    to the same 4-byte selector, such as `burn(uint256)` and
+    `collate_propagate_storage(bytes16)`.
+
+    Methods of every `#[public]`/`#[external]` impl block in the file are
+    compared, so a clash between a contract and an inherited or routed
+    implementation is found too. The EVM dispatch routes both signatures to a
+    single implementation.
+    """
+
+    default_severity = Severity.HIGH
+    default_confidence = Confidence.HIGH
+    cwe = 694
+
+    documentation = DetectorDoc(
+        catches=(
+            "Externally callable methods whose different Solidity signatures hash to the same 4-byte selector."
+        ),
+        rationale=(
+            "The EVM dispatch routes both signatures to one implementation, so calls meant for one method run the "
+            "other."
+        ),
+        vulnerable_example="""
+            #[public]
+            impl Token {
+                pub fn burn(&mut self, amount: U256) { /* burn(uint256): 0x42966c68 */ }
+                pub fn collate_propagate_storage(&mut self, data: FixedBytes<16>) { /* also 0x42966c68 */ }
+            }
+        """,
+        fixed_example="""
+            #[public]
+            impl Token {
+                pub fn burn(&mut self, amount: U256) { /* 0x42966c68 */ }
+                #[selector(name = "propagateStorage")]
+                pub fn collate_propagate_storage(&mut self, data: FixedBytes<16>) { /* new selector */ }
+            }
+        """,
+        references=["https://docs.soliditylang.org/en/latest/abi-spec.html#function-selector"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="selector_collision",
+            description="Detects different method signatures sharing a 4-byte ABI selector"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect selector collisions across the public methods of a file"""
+        by_selector: Dict[str, List[Tuple[str, Node]]] = {}
+        for function_node in self._get_public_methods(tree.root_node, code):
+            signature = self._get_abi_signature(function_node, code)
+            if signature is None:
+                continue
+            entries = by_selector.setdefault(compute_selector(signature), [])
+            # Identical signatures are duplicates, reported by the duplicate definition detector
+            if all(existing != signature for existing, _ in entries):
+                entries.append((signature, function_node))
+
+        for selector, entries in by_selector.items():
+            first_signature, first = entries[0]
+            for signature, function_node in entries[1:]:
+                first_start, first_end = self._get_line_for_node(first)
+                line_start, line_end = self._get_line_for_node(function_node)
+                results.add_issue(
+                    issue_type="selector_collision",
+                    severity="High",
+                    description=f"'{first_signature}' in function '{self._get_function_name(first, code)}' (lines {first_start}-{first_end}) and '{signature}' in function '{self._get_function_name(function_node, code)}' (lines {line_start}-{line_end}) share the selector {selector}. Calls to either signature are dispatched to one implementation.",
+                    line_start=first_start,
+                    line_end=line_end,
+                    column=self._get_column_for_node(first, code),
+                    code_snippet=f"{self._get_header_text(first, code)}\n...\n{self._get_header_text(function_node, code)}",
+                    recommendation="Rename one of the methods, or export it under a different name with `#[selector(name = \"...\")]`."
+                )
+
+    def _get_header_text(self, function_node: Node, code: str) -> str:
+        """Get the source of a function without its body"""
+        body = function_node.child_by_field_name("body")
+        end = body.start_byte if body else function_node.end_byte
+        return self._get_source_text(code, function_node.start_byte, end).strip()
@@ -0,0 +1,197 @@
+"""
+Detector for transfers that break when the sender and recipient are the same in Stylus Rust contracts
+"""
+import re
+from typing import Dict, List, Optional, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# Solidity mapping updates such as `balanceOf[from] -= value`
+SOL_MAPPING_UPDATE = re.compile(r"^(\w+)\s*\[\s*([^\]]+?)\s*\]$")
+
+
+class SelfTransferNoOpDetector(BaseDetector):
+    """
+    Detector for two-sided balance updates without a `from != to` guard.
+
+    When the same mapping is debited for one key and credited for another,
+    passing the same address twice makes both updates hit one slot. With the
+    balances read up front, the credit overwrites the debit and inflates the
+    balance.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.MEDIUM
+    cwe = 682
+
+    documentation = DetectorDoc(
+        catches=(
+            "Functions debiting one entry of a mapping and crediting another entry of the same mapping without a "
+            "`from != to` check."
+        ),
+        rationale=(
+            "When both balances are read before the updates, a transfer to oneself makes the credit overwrite the "
+            "debit, inflating the balance."
+        ),
+        vulnerable_example="""
+            let from_balance = self.balances.get(from);
+            let to_balance = self.balances.get(to);
+            self.balances.setter(from).set(from_balance - value);
+            self.balances.setter(to).set(to_balance + value);
+        """,
+        fixed_example="""
+            if from == to {
+                return Err(b"self transfer".to_vec());
+            }
+            let from_balance = self.balances.get(from);
+            let to_balance = self.balances.get(to);
+            self.balances.setter(from).set(from_balance - value);
+            self.balances.setter(to).set(to_balance + value);
+        """,
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="self_transfer",
+            description="Detects transfers that don't guard against the sender and recipient being the same"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect unguarded two-sided mapping updates"""
+        for function_node in self._iter_nodes(tree.root_node):
+            if function_node.type == "function_item":
+                self._check_function(function_node, code, results)
+
+        for function in self._get_sol_functions(tree.root_node, code):
+            self._check_sol_function(function, code, results)
+
+    def _check_function(self, function_node: Node, code: str, results) -> None:
+        """Check a Rust function for a debit and credit on the same storage map"""
+        body = function_node.child_by_field_name("body")
+        if not body:
+            return
+        debits: List[Tuple[str, str, Node]] = []
+        credits: List[Tuple[str, str, Node]] = []
+        guards: List[str] = []
+        for node in self._iter_nodes(body):
+            guard = self._get_guard_text(node, code)
+            if guard is not None:
+                guards.append(guard)
+                continue
+            update = self._get_map_update(node, code)
+            if update:
+                field, key, direction = update
+                (debits if direction == "-" else credits).append((field, key, node))
+
+        pair = self._find_unguarded_pair(debits, credits, guards)
+        if pair:
+            (field, debit_key, debit), (_, credit_key, credit) = pair
+            function_name = self._get_function_name(function_node, code)
+            self._add_issue(self._get_line_for_node(debit)[0], self._get_line_for_node(credit)[1],
+                            self._get_column_for_node(debit, code),
+                            f"{self._get_node_text(debit, code)}\n{self._get_node_text(credit, code)}",
+                            field, debit_key, credit_key, f"function '{function_name}'", results)
+
+    def _check_sol_function(self, function: Dict, code: str, results) -> None:
+        """Check a sol! function for `-=` and `+=` on the same mapping"""
+        debits: List[Tuple[str, str, List[Node]]] = []
+        credits: List[Tuple[str, str, List[Node]]] = []
+        guards: List[str] = []
+        for statement in self._split_sol_statements(function["body"]):
+            text = self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte)
+            if self._get_node_text(statement[0], code) in ("require", "if"):
+                guards.append(text)
+                continue
+            for index, token in enumerate(statement):
+                if token.type not in ("-=", "+=") or index == 0:
+                    continue
+                target = self._get_source_text(code, statement[0].start_byte, statement[index - 1].end_byte)
+                match = SOL_MAPPING_UPDATE.match(target)
+                if match:
+                    entry = (match.group(1), match.group(2), statement)
+                    (debits if token.type == "-=" else credits).append(entry)
+                break
+
+        pair = self._find_unguarded_pair(debits, credits, guards)
+        if pair:
+            (field, debit_key, debit), (_, credit_key, credit) = pair
+            self._add_issue(debit[0].start_point[0] + 1, credit[-1].end_point[0] + 1,
+                            self._get_column_for_node(debit[0], code),
+                            f"{self._get_source_text(code, debit[0].start_byte, debit[-1].end_byte)}\n"
+                            f"{self._get_source_text(code, credit[0].start_byte, credit[-1].end_byte)}",
+                            field, debit_key, credit_key, f"sol! function '{function['name']}'", results)
+
+    def _find_unguarded_pair(self, debits: List[Tuple], credits: List[Tuple], guards: List[str]) -> Optional[Tuple]:
+        """Find a debit and credit of the same map under different keys with no guard comparing the keys"""
+        for debit in debits:
+            for credit in credits:
+                if debit[0] != credit[0] or debit[1] == credit[1]:
+                    continue
+                if not any(self._compares(guard, debit[1], credit[1]) for guard in guards):
+                    return debit, credit
+        return None
+
+    def _compares(self, guard: str, first: str, second: str) -> bool:
+        """Check if a guard compares two key expressions for (in)equality"""
+        compact = "".join(guard.split())
+        first, second = "".join(first.split()), "".join(second.split())
+        return any(f"{a}{operator}{b}" in compact
+                   for a, b in ((first, second), (second, first)) for operator in ("!=", "=="))
+
+    def _get_map_update(self, node: Node, code: str) -> Optional[Tuple[str, str, str]]:
+        """
+        Get the field, key and direction of a `self.<map>.setter(key).set(...)` or
+        `self.<map>.insert(key, ...)` update that adds to or subtracts from the entry.
+        """
+        if node.type != "call_expression":
+            return None
+        function = node.child_by_field_name("function")
+        arguments = node.child_by_field_name("arguments")
+        if not function or not arguments or function.type != "field_expression" or not arguments.named_children:
+            return None
+        method = self._get_node_text(function.child_by_field_name("field"), code)
+        receiver = function.child_by_field_name("value")
+        if method == "set" and receiver is not None and receiver.type == "call_expression":
+            key_call = receiver
+            value = arguments.named_children[0]
+        elif method == "insert" and len(arguments.named_children) > 1:
+            key_call = node
+            value = arguments.named_children[1]
+        else:
+            return None
+
+        key_function = key_call.child_by_field_name("function")
+        key_arguments = key_call.child_by_field_name("arguments")
+        if not key_function or key_function.type != "field_expression" or not key_arguments or \
+                not key_arguments.named_children:
+            return None
+        if key_call is receiver and self._get_node_text(key_function.child_by_field_name("field"), code) != "setter":
+            return None
+        field = self._get_self_field(key_function.child_by_field_name("value"), code)
+        if not field:
+            return None
+
+        value_text = self._get_node_text(value, code)
+        if re.search(r"\s-\s|checked_sub|saturating_sub|wrapping_sub", value_text):
+            direction = "-"
+        elif re.search(r"\s\+\s|checked_add|saturating_add|wrapping_add", value_text):
+            direction = "+"
+        else:
+            return None
+        return field, self._get_node_text(key_arguments.named_children[0], code), direction
+
+    def _add_issue(self, line_start: int, line_end: int, column: int, snippet: str, field: str, debit_key: str,
+                   credit_key: str, location: str, results) -> None:
+        """Add a self transfer issue"""
+        results.add_issue(
+            issue_type="self_transfer",
+            severity="Medium",
+            description=f"In {location}, '{field}' is debited for '{debit_key}' and credited for '{credit_key}' without checking that they differ. A transfer to oneself updates the same entry twice.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation=f"Require '{debit_key}' != '{credit_key}' before updating the balances, or re-read the entry between the debit and the credit."
+        )
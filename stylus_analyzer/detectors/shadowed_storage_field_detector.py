@@ -0,0 +1,182 @@
+"""
+Detector for local variables shadowing storage fields in Stylus Rust contracts
+"""
+import re
+from typing import Dict, Optional
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.abi_utils import rust_type_to_abi
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc, STORAGE_WRITE_METHODS
+from stylus_analyzer.findings import Confidence, Severity
+
+# Value types of calls whose result type is fixed
+KNOWN_CALL_TYPES = {
+    "msg::sender": "address",
+    "msg::value": "uint256",
+    "tx::origin": "address",
+    "contract::address": "address",
+    "contract::balance": "uint256",
+    "block::timestamp": "uint64",
+    "block::number": "uint64",
+}
+
+# Associated items whose type is the type they are called on, e.g. `U256::from(1)`
+TYPED_CONSTRUCTOR_PATTERN = re.compile(r"^([A-Z]\w*)\s*::\s*(from|new|ZERO|MAX|MIN|default|from_limbs)\b")
+
+
+class ShadowedStorageFieldDetector(BaseDetector):
+    """
+    Detector for `let` bindings that reuse the name of a storage field of `self`.
+
+    A local `balance` next to `self.balance` makes it easy to update the copy
+    and forget the write back to storage. To keep noise down, only locals of
+    the field's value type are reported, and only when the field itself is
+    read or written later in the same scope. Writing the local back with
+    `self.<field>.set(...)` is the expected pattern and doesn't count.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.HIGH
+    cwe = 710
+
+    documentation = DetectorDoc(
+        catches="`let` bindings in the methods of a storage struct that reuse the name of one of its fields.",
+        rationale="Updating the local is easily mistaken for updating storage, so the change is silently lost.",
+        vulnerable_example="""
+            let mut total = self.total.get();
+            total += amount;
+            evm::log(Deposited { total: self.total.get() });
+        """,
+        fixed_example="""
+            let mut new_total = self.total.get();
+            new_total += amount;
+            self.total.set(new_total);
+            evm::log(Deposited { total: self.total.get() });
+        """,
+        references=["https://swcregistry.io/docs/SWC-119"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="shadowed_storage_field",
+            description="Detects local variables that shadow a storage field of self"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect locals shadowing storage fields in the methods of storage structs"""
+        impls = self._collect_impl_methods(tree.root_node, code)
+        for struct in self._collect_storage_structs(tree.root_node, code):
+            for method in impls.get(struct["name"], {}).values():
+                self._check_method(method, struct, code, results)
+
+    def _check_method(self, method: Node, struct: Dict, code: str, results) -> None:
+        """Check the let bindings of a method against the fields of its storage struct"""
+        local_types = self._collect_local_type_texts(method, code)
+        for node in self._iter_nodes(method):
+            if node.type != "let_declaration":
+                continue
+            pattern = node.child_by_field_name("pattern")
+            if pattern and pattern.type == "mut_pattern" and pattern.named_children:
+                pattern = pattern.named_children[-1]
+            if not pattern or pattern.type != "identifier":
+                continue
+            name = self._get_node_text(pattern, code)
+            if name not in struct["fields"]:
+                continue
+            if not self._is_compatible(node, name, struct["fields"][name], code, local_types):
+                continue
+            later_use = self._find_later_field_use(node, name, code)
+            if later_use is None:
+                continue
+
+            function_name = self._get_function_name(method, code)
+            line_start, line_end = self._get_line_for_node(node)
+            field_line = self._find_field_line(struct, name, code)
+            use_line, _ = self._get_line_for_node(later_use)
+            results.add_issue(
+                issue_type="shadowed_storage_field",
+                severity="Low",
+                description=f"Local '{name}' (lines {line_start}-{line_end}) in function '{function_name}' shadows storage field '{name}' of '{struct['name']}' (line {field_line}), which is still used at line {use_line}. Updates to the local are easily mistaken for writes to storage.",
+                line_start=line_start,
+                line_end=line_end,
+                column=self._get_column_for_node(node, code),
+                code_snippet=self._get_node_text(node, code),
+                recommendation=f"Rename the local, e.g. `current_{name}`, and write the result back with `self.{name}.set(...)` explicitly."
+            )
+
+    def _is_compatible(self, let_node: Node, name: str, field_type: str, code: str,
+                       local_types: Dict[str, str]) -> bool:
+        """Check if a local holds a value of the storage field's type"""
+        value = let_node.child_by_field_name("value")
+        if value is not None and re.search(rf"\bself\s*\.\s*{re.escape(name)}\b", self._get_node_text(value, code)):
+            return True
+        field_abi = self._get_value_type(field_type)
+        declared = let_node.child_by_field_name("type")
+        if declared is not None:
+            local_abi = self._get_value_type(self._get_node_text(declared, code))
+        else:
+            local_abi = self._infer_value_type(value, code, local_types) if value is not None else None
+        return field_abi is not None and field_abi == local_abi
+
+    def _get_value_type(self, type_text: str) -> Optional[str]:
+        """Get the ABI type of a value or storage type, e.g. `uint256` for `StorageU256` or `U256`"""
+        text = type_text.strip()
+        if text.startswith("Storage"):
+            text = text[len("Storage"):]
+        if text in ("Bool", "bool"):
+            return "bool"
+        # sol_storage! fields are already written as Solidity types
+        if re.match(r"^(address|bool|string|bytes\d*|u?int\d*)$", text):
+            return {"uint": "uint256", "int": "int256"}.get(text, text)
+        return rust_type_to_abi(text)
+
+    def _infer_value_type(self, value: Node, code: str, local_types: Dict[str, str]) -> Optional[str]:
+        """Infer the ABI type of an initializer from calls, typed constructors and typed locals"""
+        while value.type in ("try_expression", "reference_expression", "parenthesized_expression") and \
+                value.named_children:
+            value = value.named_children[0]
+        text = self._get_node_text(value, code)
+        if value.type == "boolean_literal":
+            return "bool"
+        if value.type == "identifier" and text in local_types:
+            return self._get_value_type(local_types[text])
+        if value.type == "call_expression":
+            function = value.child_by_field_name("function")
+            call_name = re.sub(r"\s+", "", self._get_node_text(function, code)) if function else ""
+            if call_name in KNOWN_CALL_TYPES:
+                return KNOWN_CALL_TYPES[call_name]
+        match = TYPED_CONSTRUCTOR_PATTERN.match(text)
+        return self._get_value_type(match.group(1)) if match else None
+
+    def _find_later_field_use(self, let_node: Node, name: str, code: str) -> Optional[Node]:
+        """Find a `self.<name>` access after a let binding within its enclosing block"""
+        scope = let_node.parent
+        if scope is None:
+            return None
+        for node in self._iter_nodes(scope):
+            if node.start_byte < let_node.end_byte or node.type != "field_expression":
+                continue
+            value = node.child_by_field_name("value")
+            field = node.child_by_field_name("field")
+            if value and field and value.type == "self" and self._get_node_text(field, code) == name and \
+                    not self._is_write_back(node, name, code):
+                return node
+        return None
+
+    def _is_write_back(self, field_node: Node, name: str, code: str) -> bool:
+        """Check if a field access is `self.<name>.set(...)` storing a value computed from the local"""
+        method = field_node.parent
+        call = method.parent if method is not None else None
+        if method is None or method.type != "field_expression" or call is None or call.type != "call_expression":
+            return False
+        method_name = method.child_by_field_name("field")
+        arguments = call.child_by_field_name("arguments")
+        return bool(method_name and arguments and self._get_node_text(method_name, code) in STORAGE_WRITE_METHODS and
+                    re.search(rf"\b{re.escape(name)}\b", self._get_node_text(arguments, code)))
+
+    def _find_field_line(self, struct: Dict, name: str, code: str) -> int:
+        """Find the line a field is declared at in a storage struct"""
+        for node in self._iter_nodes(struct["body"]):
+            if node.type in ("field_identifier", "identifier") and self._get_node_text(node, code) == name:
+                return self._get_line_for_node(node)[0]
+        return self._get_line_for_node(struct["node"])[0]
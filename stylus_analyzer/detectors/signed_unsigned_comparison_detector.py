@@ -0,0 +1,356 @@
+"""
+Detector for expressions mixing signed and unsigned integers in Stylus Rust contracts
+"""
+import re
+from typing import Dict, List, Optional, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.detectors.unsafe_cast_detector import PRIMITIVE_WIDTHS
+from stylus_analyzer.findings import Confidence, Severity
+
+# Signedness and width in bits of an integer type, width None when unknown
+IntegerType = Tuple[bool, Optional[int]]
+
+SIGNED_TYPE_PATTERN = re.compile(r"^(?:Storage)?(?:I(\d+)|i(\d+|size)|Signed|int(\d*))$")
+UNSIGNED_TYPE_PATTERN = re.compile(r"^(?:Storage)?(?:U(\d+)|u(\d+|size)|Uint|uint(\d*))$")
+
+COMPARISON_OPERATORS = ("==", "!=", "<", ">", "<=", ">=")
+ARITHMETIC_OPERATORS = ("+", "-", "*", "/", "%")
+
+# Constructors reinterpreting the bits of an integer as the other signedness, e.g. `I256::from_raw(amount)`
+REINTERPRETING_CONSTRUCTORS = ("from_raw", "unchecked_from")
+
+# Methods returning an unsigned integer whatever their receiver, `into_raw` reinterpreting the bits of an `I256`
+UNSIGNED_METHODS = ("len", "into_raw", "unsigned_abs", "count_ones", "leading_zeros", "trailing_zeros", "bit_len",
+                    "byte_len")
+REINTERPRETING_METHODS = ("into_raw",)
+
+# Methods returning a value of the same type as their receiver
+PRESERVING_METHOD_PATTERN = re.compile(
+    r"^(?:(?:checked|saturating|wrapping|overflowing)_\w+|unwrap\w*|expect|map_err|ok_or\w*|clone|abs|pow|min|max|"
+    r"get)$"
+)
+
+# Methods testing the sign of a value, which make a following conversion safe
+SIGN_CHECK_METHODS = ("is_negative", "is_positive", "signum")
+
+# Solidity integer types, e.g. `int256` or `uint8`
+SOL_INTEGER_PATTERN = re.compile(r"^(u?)int(\d*)$")
+
+
+class SignedUnsignedComparisonDetector(BaseDetector):
+    """
+    Detector for comparisons, arithmetic and conversions mixing signed and unsigned integers.
+
+    Operand types come from parameters, annotated and inferred locals,
+    storage fields, literal suffixes, casts and constructors such as
+    `I256::from(...)`. Reported are comparisons and arithmetic between a
+    signed and an unsigned operand, `as` casts between signed and unsigned
+    types that don't widen an unsigned value into a larger signed type, and
+    reinterpretations through `I256::from_raw(...)` or `.into_raw()`, unless
+    an `if` condition or guard macro of the function checks the sign or
+    bounds of the converted variable. `sol!` functions are checked for the
+    same mix between their `int` and `uint` parameters.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.MEDIUM
+    cwe = 195
+    node_types = ("binary_expression", "compound_assignment_expr", "type_cast_expression", "call_expression")
+
+    documentation = DetectorDoc(
+        catches=(
+            "Comparisons and arithmetic between `I256`/`iN` and `U256`/`uN` values, and casts or "
+            "`from_raw`/`into_raw` conversions between signed and unsigned integers with no sign check."
+        ),
+        rationale=(
+            "Reinterpreting a negative `I256` as a `U256` turns it into a huge amount, and a `U256` above "
+            "`I256::MAX` becomes negative, so a balance delta compared against an unsigned threshold can pass or "
+            "fail the check for the wrong values."
+        ),
+        vulnerable_example="""
+            pub fn exceeds(&self, account: Address) -> bool {
+                let delta = self.deltas.get(account);
+                delta.into_raw() > self.threshold.get()
+            }
+        """,
+        fixed_example="""
+            pub fn exceeds(&self, account: Address) -> bool {
+                let delta = self.deltas.get(account);
+                !delta.is_negative() && delta.into_raw() > self.threshold.get()
+            }
+        """,
+        references=["https://cwe.mitre.org/data/definitions/195.html",
+                    "https://docs.rs/alloy-primitives/latest/alloy_primitives/struct.Signed.html"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="signed_unsigned_comparison",
+            description="Detects comparisons, arithmetic and conversions mixing signed and unsigned integers"
+        )
+
+    def start(self, tree: Tree, code: str) -> None:
+        """Collect the storage fields of the file the operands are typed against"""
+        self.storage_fields = self._collect_storage_fields(tree.root_node, code)
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check a comparison, arithmetic expression or conversion in the body of a function"""
+        function_node = self._find_parent_function(node)
+        body = function_node.child_by_field_name("body") if function_node is not None else None
+        if body is None or node.start_byte < body.start_byte:
+            return
+        storage_fields = self.storage_fields
+        function_name = self._get_function_name(function_node, code)
+        local_types = self._get_function_fact(f"{self.id}.local_types", function_node, code,
+                                              lambda: self._collect_local_types(function_node, code, storage_fields))
+        if node.type in ("binary_expression", "compound_assignment_expr"):
+            operator = node.child_by_field_name("operator")
+            operator = self._get_node_text(operator, code) if operator is not None else ""
+            if node.type == "compound_assignment_expr":
+                # `total += delta` mixes the operands as `total + delta` does
+                operator = operator[:-1]
+            if operator not in COMPARISON_OPERATORS + ARITHMETIC_OPERATORS:
+                return
+            left = self._infer_type(node.child_by_field_name("left"), code, local_types, storage_fields)
+            right = self._infer_type(node.child_by_field_name("right"), code, local_types, storage_fields)
+            if left is not None and right is not None and left[0] != right[0]:
+                kind = "Comparison" if operator in COMPARISON_OPERATORS else "Arithmetic"
+                self._add_issue(node, code, results,
+                                f"{kind} mixing a signed and an unsigned integer with `{operator}` in function "
+                                f"'{function_name}'. A negative signed value, or an unsigned one above the signed "
+                                "maximum, gives the wrong result once one side is converted to the other.")
+            return
+        guards = self._get_function_fact(f"{self.id}.guards", function_node, code,
+                                         lambda: self._collect_guard_texts(function_node, code))
+        if node.type == "type_cast_expression":
+            self._check_cast(node, code, local_types, storage_fields, guards, function_name, results)
+        else:
+            self._check_reinterpretation(node, code, guards, function_name, results)
+
+    def finish(self, tree: Tree, code: str, results) -> None:
+        """Check the expressions of sol! function bodies"""
+        for function in self._get_sol_functions(tree.root_node, code):
+            self._check_sol_function(function, code, results)
+
+    def _collect_guard_texts(self, function_node: Node, code: str) -> List[str]:
+        """Collect the conditions of the guards in a function"""
+        guards = [self._get_guard_text(node, code) for node in self._iter_nodes(function_node)]
+        return [guard for guard in guards if guard is not None]
+
+    def _check_cast(self, node: Node, code: str, local_types: Dict[str, IntegerType], storage_fields: Dict[str, str],
+                    guards: List[str], function_name: str, results) -> None:
+        """Report an `as` cast between signed and unsigned types that can change the value"""
+        value = node.child_by_field_name("value")
+        cast_type = node.child_by_field_name("type")
+        if value is None or cast_type is None:
+            return
+        source = self._infer_type(value, code, local_types, storage_fields)
+        target = self._get_type(self._get_node_text(cast_type, code))
+        if source is None or target is None or source[0] == target[0]:
+            return
+        # Every unsigned value fits in a wider signed type
+        if not source[0] and source[1] is not None and target[1] is not None and target[1] > source[1]:
+            return
+        if self._is_sign_checked(value, code, guards):
+            return
+        direction = "a signed integer to an unsigned" if source[0] else "an unsigned integer to a signed"
+        effect = "negative values wrap to huge ones" if source[0] else "large values wrap to negative ones"
+        self._add_issue(node, code, results,
+                        f"Cast from {direction} type in function '{function_name}' changes the value domain: {effect}.")
+
+    def _check_reinterpretation(self, node: Node, code: str, guards: List[str], function_name: str,
+                                results) -> None:
+        """Report `I256::from_raw(...)` and `.into_raw()`, which reinterpret the bits of a value"""
+        function = node.child_by_field_name("function")
+        arguments = node.child_by_field_name("arguments")
+        if function is None or arguments is None:
+            return
+        if function.type == "scoped_identifier":
+            path = function.child_by_field_name("path")
+            name = function.child_by_field_name("name")
+            if path is None or name is None or self._get_node_text(name, code) not in REINTERPRETING_CONSTRUCTORS:
+                return
+            target = self._get_type(self._get_node_text(path, code))
+            if target is None or len(arguments.named_children) != 1 or \
+                    self._is_sign_checked(arguments.named_children[0], code, guards):
+                return
+            conversion = f"`{self._get_node_text(function, code)}`"
+            signed = target[0]
+        elif function.type == "field_expression":
+            method = function.child_by_field_name("field")
+            receiver = function.child_by_field_name("value")
+            if method is None or receiver is None or self._get_node_text(method, code) not in REINTERPRETING_METHODS \
+                    or self._is_sign_checked(receiver, code, guards):
+                return
+            conversion = f"`.{self._get_node_text(method, code)}()`"
+            signed = False
+        else:
+            return
+        effect = "an unsigned value above the signed maximum becomes negative" if signed else \
+            "a negative value becomes a huge unsigned one"
+        self._add_issue(node, code, results,
+                        f"Conversion with {conversion} in function '{function_name}' reinterprets the bits of the "
+                        f"value without checking its sign: {effect}.")
+
+    def _is_sign_checked(self, value: Node, code: str, guards: List[str]) -> bool:
+        """Check if a guard of the function tests the sign or bounds of a converted variable"""
+        if value.type == "parenthesized_expression" and value.named_children:
+            value = value.named_children[0]
+        if value.type != "identifier":
+            return False
+        name = re.escape(self._get_node_text(value, code))
+        check = re.compile(rf"\b{name}\s*(?:<=?|>=?)|(?:<=?|>=?)\s*{name}\b|"
+                           rf"\b{name}\s*\.\s*(?:{'|'.join(SIGN_CHECK_METHODS)})\s*\(")
+        return any(check.search(guard) for guard in guards)
+
+    def _collect_local_types(self, function_node: Node, code: str,
+                             storage_fields: Dict[str, str]) -> Dict[str, IntegerType]:
+        """Map the parameters and locals of a function to their integer type, inferring unannotated locals"""
+        local_types = {}
+        for name, type_text in self._collect_local_type_texts(function_node, code).items():
+            integer_type = self._get_type(type_text)
+            if integer_type is not None:
+                local_types[name] = integer_type
+        for node in self._iter_nodes(function_node):
+            if node.type != "let_declaration" or node.child_by_field_name("type") is not None:
+                continue
+            pattern = node.child_by_field_name("pattern")
+            if pattern is not None and pattern.type == "mut_pattern" and pattern.named_children:
+                pattern = pattern.named_children[-1]
+            if pattern is None or pattern.type != "identifier":
+                continue
+            inferred = self._infer_type(node.child_by_field_name("value"), code, local_types, storage_fields)
+            if inferred is not None:
+                local_types[self._get_node_text(pattern, code)] = inferred
+        return local_types
+
+    def _infer_type(self, node: Optional[Node], code: str, local_types: Dict[str, IntegerType],
+                    storage_fields: Dict[str, str]) -> Optional[IntegerType]:
+        """Heuristically infer the signedness and width of an integer expression, or None if unknown"""
+        if node is None:
+            return None
+        if node.type == "identifier":
+            return local_types.get(self._get_node_text(node, code))
+        if node.type == "integer_literal":
+            suffix = re.search(r"[iu](?:\d+|size)$", self._get_node_text(node, code))
+            return self._get_type(suffix.group(0)) if suffix else None
+        if node.type in ("parenthesized_expression", "try_expression", "unary_expression", "reference_expression"):
+            children = [child for child in node.named_children if child.type != "mutable_specifier"]
+            return self._infer_type(children[0] if children else None, code, local_types, storage_fields)
+        if node.type == "type_cast_expression":
+            cast_type = node.child_by_field_name("type")
+            return self._get_type(self._get_node_text(cast_type, code)) if cast_type else None
+        if node.type == "binary_expression":
+            operator = node.child_by_field_name("operator")
+            if operator is None or self._get_node_text(operator, code) not in ARITHMETIC_OPERATORS:
+                return None
+            return self._infer_type(node.child_by_field_name("left"), code, local_types, storage_fields) or \
+                self._infer_type(node.child_by_field_name("right"), code, local_types, storage_fields)
+        if node.type == "scoped_identifier":
+            path = node.child_by_field_name("path")
+            return self._get_type(self._get_node_text(path, code)) if path else None
+        if node.type == "field_expression":
+            field = self._get_self_field(node, code)
+            return self._get_type(storage_fields[field]) if field in storage_fields else None
+        if node.type == "call_expression":
+            return self._infer_call_type(node, code, local_types, storage_fields)
+        return None
+
+    def _infer_call_type(self, node: Node, code: str, local_types: Dict[str, IntegerType],
+                         storage_fields: Dict[str, str]) -> Optional[IntegerType]:
+        """Infer the integer type a call returns, from its constructor's type or its method and receiver"""
+        function = node.child_by_field_name("function")
+        if function is None:
+            return None
+        if function.type == "scoped_identifier":
+            path = function.child_by_field_name("path")
+            return self._get_type(self._get_node_text(path, code)) if path else None
+        if function.type == "generic_function":
+            # `value.to::<u64>()`
+            type_arguments = function.child_by_field_name("type_arguments")
+            return self._get_type(self._get_node_text(type_arguments, code).strip("<>").strip()) \
+                if type_arguments else None
+        if function.type != "field_expression":
+            return None
+        method = function.child_by_field_name("field")
+        receiver = function.child_by_field_name("value")
+        if method is None or receiver is None:
+            return None
+        name = self._get_node_text(method, code)
+        if name in UNSIGNED_METHODS:
+            return False, None
+        if not PRESERVING_METHOD_PATTERN.match(name):
+            return None
+        if name == "get":
+            # `self.balances.get(key)` reads the value type of a storage field
+            field = self._get_self_field(receiver, code)
+            return self._get_type(storage_fields[field]) if field in storage_fields else None
+        return self._infer_type(receiver, code, local_types, storage_fields)
+
+    def _get_type(self, type_text: str) -> Optional[IntegerType]:
+        """Get the last integer type mentioned in a type, e.g. `I256` for `StorageMap<Address, StorageI256>`"""
+        for token in reversed(re.findall(r"[A-Za-z_]\w*", type_text)):
+            for signed, pattern in ((True, SIGNED_TYPE_PATTERN), (False, UNSIGNED_TYPE_PATTERN)):
+                match = pattern.match(token)
+                if not match:
+                    continue
+                if token in PRIMITIVE_WIDTHS:
+                    return signed, PRIMITIVE_WIDTHS[token]
+                bits = next((group for group in match.groups() if group), None)
+                # Solidity's bare `int` and `uint` are 256 bits, `Signed<...>` and `Uint<...>` take it as an argument
+                return signed, int(bits) if bits else (256 if token in ("int", "uint") else None)
+        return None
+
+    def _check_sol_function(self, function: Dict, code: str, results) -> None:
+        """Check a sol! function for casts and expressions mixing its `int` and `uint` parameters"""
+        parameters = {}
+        for parameter in self._get_node_text(function["params"], code)[1:-1].split(","):
+            words = parameter.split()
+            match = SOL_INTEGER_PATTERN.match(words[0]) if len(words) >= 2 else None
+            if match:
+                parameters[words[-1]] = not match.group(1)
+        if not parameters:
+            return
+
+        mix = re.compile(rf"\b({'|'.join(map(re.escape, parameters))})\s*(==|!=|<=|>=|<|>|\+|-|\*|/|%)(?![=>])\s*"
+                         rf"({'|'.join(map(re.escape, parameters))})\b")
+        for statement in self._split_sol_statements(function["body"]):
+            text = self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte)
+            for left, operator, right in mix.findall(text):
+                if parameters[left] != parameters[right]:
+                    self._add_issue(statement[0], code, results,
+                                    f"Expression `{left} {operator} {right}` in sol! function '{function['name']}' "
+                                    "mixes a signed and an unsigned integer.",
+                                    end_node=statement[-1], snippet=text)
+            for token in statement:
+                cast = SOL_INTEGER_PATTERN.match(self._get_node_text(token, code))
+                following = token.next_sibling
+                if cast is None or following is None or not self._is_token_tree(following, "("):
+                    continue
+                argument = self._get_node_text(following, code)[1:-1].strip()
+                if argument in parameters and parameters[argument] == bool(cast.group(1)):
+                    self._add_issue(token, code, results,
+                                    f"Conversion `{self._get_node_text(token, code)}({argument})` in sol! function "
+                                    f"'{function['name']}' reinterprets a "
+                                    f"{'signed' if parameters[argument] else 'unsigned'} value without checking it.",
+                                    snippet=self._get_source_text(code, token.start_byte, following.end_byte))
+
+    def _add_issue(self, node: Node, code: str, results, description: str, end_node: Optional[Node] = None,
+                   snippet: Optional[str] = None) -> None:
+        """Report an expression mixing signed and unsigned integers"""
+        line_start, line_end = self._get_line_for_node(node)
+        if end_node is not None:
+            _, line_end = self._get_line_for_node(end_node)
+        results.add_issue(
+            issue_type="signed_unsigned_comparison",
+            severity="Low",
+            description=description,
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=snippet if snippet is not None else self._get_node_text(node, code),
+            recommendation="Check the sign before converting, e.g. with `is_negative()`, and convert with "
+                           "`try_from`/`try_into` so out-of-range values are rejected instead of reinterpreted."
+        )
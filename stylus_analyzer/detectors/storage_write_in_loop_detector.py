@@ -0,0 +1,172 @@
+"""
+Detector for repeated storage writes inside loops in Stylus Rust contracts
+"""
+import re
+from typing import Dict, Optional, Set, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+LOOP_TYPES = ("for_expression", "while_expression", "loop_expression")
+
+# Writes that touch a new slot on every call
+APPENDING_METHODS = ("push", "pop")
+
+
+class StorageWriteInLoopDetector(BaseDetector):
+    """
+    Detector for storage writes to the same slot on every loop iteration.
+
+    Each write is a separate SSTORE, so accumulating in a local variable and
+    writing once after the loop is considerably cheaper.
+    """
+
+    default_severity = Severity.INFO
+    default_confidence = Confidence.HIGH
+
+    documentation = DetectorDoc(
+        catches="Storage writes inside loops that hit the same slot on every iteration.",
+        rationale=(
+            "Every write is a separate SSTORE, so the loop pays for storage once per iteration instead of once in "
+            "total."
+        ),
+        vulnerable_example="""
+            for amount in amounts {
+                self.total.set(self.total.get() + amount);
+            }
+        """,
+        fixed_example="""
+            let mut total = self.total.get();
+            for amount in amounts {
+                total += amount;
+            }
+            self.total.set(total);
+        """,
+        references=["https://github.com/crytic/slither/wiki/Detector-Documentation"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="storage_write_in_loop",
+            description="Detects storage writes to the same field inside loops"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect storage writes repeated across loop iterations"""
+        for function_node in self._iter_nodes(tree.root_node):
+            if function_node.type == "function_item":
+                self._check_function(function_node, code, results)
+
+    def _check_function(self, function_node: Node, code: str, results) -> None:
+        """Check the loops of a single function"""
+        body = function_node.child_by_field_name("body")
+        if not body:
+            return
+        setter_aliases = self._collect_setter_aliases(function_node, code)
+        alias_values = self._collect_alias_values(function_node, code)
+        function_name = self._get_function_name(function_node, code)
+
+        reported: Set[Tuple[int, str]] = set()
+        for node in self._iter_nodes(body):
+            field = self._get_storage_write_field(node, code, setter_aliases)
+            if not field or self._is_appending_write(node, code):
+                continue
+            loop = self._find_enclosing_loop(node, body)
+            if loop is None or (loop.start_byte, field) in reported:
+                continue
+            if self._has_loop_varying_key(node, code, field, loop, alias_values):
+                continue
+            reported.add((loop.start_byte, field))
+
+            loop_line, _ = self._get_line_for_node(loop)
+            line_start, line_end = self._get_line_for_node(node)
+            results.add_issue(
+                issue_type="storage_write_in_loop",
+                severity="Info",
+                description=f"Storage field '{field}' is written on every iteration of the loop at line {loop_line} in function '{function_name}'. Each write costs a separate SSTORE.",
+                line_start=line_start,
+                line_end=line_end,
+                column=self._get_column_for_node(node, code),
+                code_snippet=self._get_node_text(node, code),
+                recommendation="Accumulate the value in a local variable inside the loop and write it to storage once after the loop."
+            )
+
+    def _find_enclosing_loop(self, node: Node, body: Node) -> Optional[Node]:
+        """Find the innermost loop containing a node within the function body"""
+        parent = node.parent
+        while parent and parent.start_byte >= body.start_byte:
+            if parent.type in LOOP_TYPES:
+                return parent
+            if parent.type == "closure_expression":
+                return None
+            parent = parent.parent
+        return None
+
+    def _is_appending_write(self, node: Node, code: str) -> bool:
+        """Check if a write pushes to or pops from a storage vector"""
+        if node.type != "call_expression":
+            return False
+        function = node.child_by_field_name("function")
+        if not function or function.type != "field_expression":
+            return False
+        method = function.child_by_field_name("field")
+        return bool(method) and self._get_node_text(method, code) in APPENDING_METHODS
+
+    def _collect_alias_values(self, function_node: Node, code: str) -> Dict[str, str]:
+        """Map locals to the text of the expression they are bound to"""
+        values = {}
+        for node in self._iter_nodes(function_node):
+            if node.type != "let_declaration":
+                continue
+            pattern = node.child_by_field_name("pattern")
+            value = node.child_by_field_name("value")
+            if not pattern or not value:
+                continue
+            if pattern.type == "mut_pattern" and pattern.named_children:
+                pattern = pattern.named_children[-1]
+            values[self._get_node_text(pattern, code)] = self._get_node_text(value, code)
+        return values
+
+    def _collect_loop_locals(self, loop: Node, code: str) -> Set[str]:
+        """Collect the names bound by a loop pattern or declared inside the loop"""
+        names = set()
+        pattern = loop.child_by_field_name("pattern")
+        if pattern:
+            names.update(self._get_node_text(node, code) for node in self._iter_nodes(pattern)
+                         if node.type == "identifier")
+        body = loop.child_by_field_name("body")
+        if body:
+            for node in self._iter_nodes(body):
+                if node.type == "let_declaration":
+                    let_pattern = node.child_by_field_name("pattern")
+                    if let_pattern:
+                        names.update(self._get_node_text(child, code) for child in self._iter_nodes(let_pattern)
+                                     if child.type == "identifier")
+        return names
+
+    def _get_write_target(self, node: Node, code: str, alias_values: Dict[str, str]) -> str:
+        """Get the text selecting the slot a write goes to, including map keys"""
+        if node.type == "call_expression":
+            function = node.child_by_field_name("function")
+            receiver = function.child_by_field_name("value") if function else None
+            method = function.child_by_field_name("field") if function else None
+            if not receiver:
+                return ""
+            target = self._get_node_text(receiver, code)
+            if receiver.type == "identifier":
+                target = alias_values.get(target, target)
+            if method and self._get_node_text(method, code) == "insert":
+                arguments = node.child_by_field_name("arguments")
+                if arguments and arguments.named_children:
+                    target += f"[{self._get_node_text(arguments.named_children[0], code)}]"
+            return target
+        left = node.child_by_field_name("left")
+        return self._get_node_text(left, code) if left else ""
+
+    def _has_loop_varying_key(self, node: Node, code: str, field: str, loop: Node,
+                              alias_values: Dict[str, str]) -> bool:
+        """Check if the slot written depends on a value that changes across iterations"""
+        target = self._get_write_target(node, code, alias_values)
+        keys = target.replace(f"self.{field}", "", 1)
+        return any(re.search(rf"\b{re.escape(name)}\b", keys) for name in self._collect_loop_locals(loop, code))
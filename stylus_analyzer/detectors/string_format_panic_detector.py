@@ -0,0 +1,126 @@
+"""
+Detector for unwrapped parsing of formatted strings in Stylus Rust contracts
+"""
+from typing import Dict, Optional, Tuple
+from tree_sitter import Node
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# Methods that panic when parsing failed
+PANICKING_METHODS = ("unwrap", "expect")
+
+# Macros and methods producing a freshly formatted string
+FORMAT_MACROS = ("format",)
+STRINGIFYING_METHODS = ("to_string",)
+
+
+class StringFormatPanicDetector(BaseDetector):
+    """
+    Detector for `format!(...).parse().unwrap()` and similar chains that
+    render a value to a string only to parse it back.
+
+    The round trip panics whenever the formatted text isn't valid for the
+    target type, e.g. `format!("0x{:x}", value)` parsed as a number, and the
+    value could have been converted directly. `.parse::<T>()?` returns the
+    error instead and is not reported.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.HIGH
+    cwe = 248
+    node_types = ("call_expression",)
+
+    documentation = DetectorDoc(
+        catches=(
+            "Values rendered to a string and parsed back with a panicking `unwrap()` or `expect()`, such as "
+            "`format!(\"{}\", value).parse().unwrap()`."
+        ),
+        rationale=(
+            "The round trip panics whenever the text isn't valid for the target type, and is usually a roundabout "
+            "conversion."
+        ),
+        vulnerable_example="""
+            let amount: U256 = format!("{}", raw).parse().unwrap();
+        """,
+        fixed_example="""
+            let amount = U256::from(raw);
+        """,
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="string_format_panic",
+            description="Detects formatted strings parsed back with a panicking unwrap() or expect()"
+        )
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if a call unwraps the parse of a formatted string"""
+        panicking = self._get_method(node, code)
+        if panicking is None or panicking[0] not in PANICKING_METHODS:
+            return
+        parse = panicking[1]
+        parse_method = self._get_method(parse, code)
+        if parse_method is None or parse_method[0] != "parse":
+            return
+        function_node = self._find_parent_function(node)
+        if function_node is None:
+            return
+        formatted = self._get_function_fact(f"{self.id}.formatted_locals", function_node, code,
+                                            lambda: self._collect_formatted_locals(function_node, code))
+        source = parse_method[1]
+        if not self._is_formatted(source, code, formatted):
+            return
+
+        function_name = self._get_function_name(function_node, code)
+        line_start, line_end = self._get_line_for_node(node)
+        results.add_issue(
+            issue_type="string_format_panic",
+            severity="Low",
+            description=f"Formatted string '{self._get_node_text(source, code)}' is parsed back with .parse().{panicking[0]}() in function '{function_name}'. This panics if the text isn't valid for the target type and round-trips a value that could be converted directly.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=self._get_node_text(node, code),
+            recommendation="Construct the target type directly from the value (e.g. via From/TryFrom), or use `.parse::<T>()` with `?` or map_err to return an error."
+        )
+
+    def _get_method(self, node: Node, code: str) -> Optional[Tuple[str, Node]]:
+        """Get the method name and receiver of a method call, looking through turbofish generics"""
+        if node is None or node.type != "call_expression":
+            return None
+        function = node.child_by_field_name("function")
+        if function is not None and function.type == "generic_function":
+            function = function.child_by_field_name("function")
+        if function is None or function.type != "field_expression":
+            return None
+        method = function.child_by_field_name("field")
+        receiver = function.child_by_field_name("value")
+        if method is None or receiver is None:
+            return None
+        return self._get_node_text(method, code), receiver
+
+    def _is_formatted(self, node: Node, code: str, formatted: Dict[str, Node]) -> bool:
+        """Check if an expression is a `format!` result, a `.to_string()` or a local bound to one"""
+        while node.type in ("reference_expression", "parenthesized_expression") and node.named_children:
+            node = node.named_children[-1]
+        if node.type == "macro_invocation":
+            return self._get_macro_name(node, code) in FORMAT_MACROS
+        if node.type == "identifier":
+            return self._get_node_text(node, code) in formatted
+        method = self._get_method(node, code)
+        return method is not None and method[0] in STRINGIFYING_METHODS
+
+    def _collect_formatted_locals(self, function_node: Node, code: str) -> Dict[str, Node]:
+        """Map locals bound to a formatted string to their initializer"""
+        formatted: Dict[str, Node] = {}
+        for node in self._iter_nodes(function_node):
+            if node.type != "let_declaration":
+                continue
+            pattern = node.child_by_field_name("pattern")
+            value = node.child_by_field_name("value")
+            if pattern and pattern.type == "mut_pattern" and pattern.named_children:
+                pattern = pattern.named_children[-1]
+            if pattern and value and pattern.type == "identifier" and self._is_formatted(value, code, formatted):
+                formatted[self._get_node_text(pattern, code)] = value
+        return formatted
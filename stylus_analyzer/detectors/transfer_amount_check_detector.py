@@ -0,0 +1,257 @@
+"""
+Detector for transfer functions that never validate the amount in Stylus Rust contracts
+"""
+import re
+from typing import Dict, List, Optional, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.detectors.missing_event_detector import LOG_FUNCTIONS
+from stylus_analyzer.findings import Confidence, Severity
+
+# Event whose amount is checked, and the fields carrying the amount
+TRANSFER_EVENT = "Transfer"
+AMOUNT_FIELDS = ("value", "amount")
+
+# Zero written as a literal or a `U256` constant
+ZERO_EXPRESSION = r"(?:U256\s*::\s*(?:ZERO|from\s*\(\s*0\s*\))|0(?:_?u\d+)?\b)"
+
+COMPARISON = r"(?:==|!=|>=|<=|<|>)"
+
+# Functions of sol! bodies that only run when called from the contract itself
+SOL_INTERNAL_MODIFIERS = ("internal", "private")
+
+
+class MissingRequireOnTransferAmountDetector(BaseDetector):
+    """
+    Detector for functions emitting `Transfer` with an amount nobody checked.
+
+    The amount is the `value` or `amount` field of the event, when it is a
+    parameter of the emitting function. It counts as checked when an `if`
+    condition or guard macro of the function, or of a function calling it
+    with its own parameter, compares it to zero. With `flag_zero_amount`
+    off, only amounts that are never compared to anything at all or passed
+    to a `checked_*` method are reported, for protocols that allow
+    zero-amount transfers.
+    """
+
+    default_severity = Severity.INFO
+    default_confidence = Confidence.LOW
+    cwe = 20
+
+    documentation = DetectorDoc(
+        catches=(
+            "Functions emitting a `Transfer` event whose `value` or `amount` parameter is never compared to zero, "
+            "or, with `flag_zero_amount` off, never compared to anything."
+        ),
+        rationale=(
+            "Zero-amount transfers waste gas and emit events that indexers and wallets show as noise, and amounts "
+            "that are never bounded by a balance or limit check leave the arithmetic as the only safeguard."
+        ),
+        vulnerable_example="""
+            pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+                let sender = msg::sender();
+                self.move_balance(sender, to, value)?;
+                evm::log(Transfer { from: sender, to, value });
+                Ok(true)
+            }
+        """,
+        fixed_example="""
+            pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+                if value == U256::ZERO {
+                    return Err(b"zero amount".to_vec());
+                }
+                let sender = msg::sender();
+                self.move_balance(sender, to, value)?;
+                evm::log(Transfer { from: sender, to, value });
+                Ok(true)
+            }
+        """,
+        references=["https://eips.ethereum.org/EIPS/eip-20"],
+    )
+
+    options = {"flag_zero_amount": bool}
+
+    def __init__(self, flag_zero_amount: bool = True):
+        """
+        Args:
+            flag_zero_amount: Report amounts that are bounded but never compared
+                to zero. Turn off for protocols that allow zero-amount transfers.
+        """
+        super().__init__(
+            name="missing_require_on_transfer_amount",
+            description="Detects transfer functions that never check the amount they emit in a Transfer event"
+        )
+        self.flag_zero_amount = flag_zero_amount
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect Rust and sol! functions emitting a Transfer amount that is never checked"""
+        root = tree.root_node
+        functions = [node for node in self._iter_nodes(root) if node.type == "function_item"]
+        for function_node in functions:
+            for log, amount in self._find_transfer_amounts(function_node, code):
+                checks = self._collect_checks(function_node, code, amount)
+                for caller, argument in self._find_callers(function_node, code, amount, functions):
+                    caller_checks = self._collect_checks(caller, code, argument)
+                    checks = (checks[0] or caller_checks[0], checks[1] or caller_checks[1])
+                self._check_amount(log, amount, checks, f"function '{self._get_function_name(function_node, code)}'",
+                                   code, results)
+
+        for function in self._get_sol_functions(root, code):
+            if any(modifier in SOL_INTERNAL_MODIFIERS for modifier in function["modifiers"]):
+                continue
+            self._check_sol_function(function, code, results)
+
+    def _find_transfer_amounts(self, function_node: Node, code: str) -> List[Tuple[Node, str]]:
+        """Find the `Transfer` logs of a function whose amount is one of its parameters"""
+        body = function_node.child_by_field_name("body")
+        if body is None:
+            return []
+        parameters = self._collect_parameter_names(function_node, code)
+        amounts = []
+        for node in self._iter_nodes(body):
+            if not self._is_log_call(node, code):
+                continue
+            for event in self._iter_nodes(node):
+                if event.type != "struct_expression":
+                    continue
+                name = event.child_by_field_name("name")
+                if name is None or self._get_node_text(name, code).split("::")[-1].strip() != TRANSFER_EVENT:
+                    continue
+                amount = self._get_amount_field(event, code)
+                if amount in parameters:
+                    amounts.append((node, amount))
+        return amounts
+
+    def _is_log_call(self, node: Node, code: str) -> bool:
+        """Check if a node is a call such as `evm::log(...)` or `log(self.vm(), ...)`"""
+        function = node.child_by_field_name("function") if node.type == "call_expression" else None
+        return function is not None and self._get_node_text(function, code).split("::")[-1].split(".")[-1].strip() \
+            in LOG_FUNCTIONS
+
+    def _get_amount_field(self, event: Node, code: str) -> Optional[str]:
+        """Get the identifier an event's `value` or `amount` field is set to"""
+        body = event.child_by_field_name("body")
+        for field in body.named_children if body else []:
+            if field.type == "shorthand_field_initializer" and field.named_children:
+                name = self._get_node_text(field.named_children[0], code)
+                if name in AMOUNT_FIELDS:
+                    return name
+            elif field.type == "field_initializer":
+                name = field.child_by_field_name("field")
+                value = field.child_by_field_name("value")
+                if name is not None and value is not None and value.type == "identifier" and \
+                        self._get_node_text(name, code) in AMOUNT_FIELDS:
+                    return self._get_node_text(value, code)
+        return None
+
+    def _find_callers(self, function_node: Node, code: str, amount: str,
+                      functions: List[Node]) -> List[Tuple[Node, str]]:
+        """Find the functions calling a function as `self.name(...)`, with the parameter they pass as the amount"""
+        name = self._get_function_name(function_node, code)
+        position = self._get_parameter_position(function_node, code, amount)
+        callers = []
+        for caller in functions:
+            if caller == function_node or position is None:
+                continue
+            parameters = self._collect_parameter_names(caller, code)
+            for node in self._iter_nodes(caller):
+                function = node.child_by_field_name("function") if node.type == "call_expression" else None
+                if function is None or function.type not in ("field_expression", "scoped_identifier"):
+                    continue
+                field = function.child_by_field_name("field" if function.type == "field_expression" else "name")
+                receiver = function.child_by_field_name("value" if function.type == "field_expression" else "path")
+                if field is None or receiver is None or self._get_node_text(field, code) != name or \
+                        self._get_node_text(receiver, code) not in ("self", "Self"):
+                    continue
+                arguments = node.child_by_field_name("arguments")
+                values = arguments.named_children if arguments else []
+                # `Self::name(self, ...)` passes the receiver as its first argument
+                index = position + (function.type == "scoped_identifier")
+                if index < len(values) and self._get_node_text(values[index], code) in parameters:
+                    callers.append((caller, self._get_node_text(values[index], code)))
+        return callers
+
+    def _get_parameter_position(self, function_node: Node, code: str, name: str) -> Optional[int]:
+        """Get the index of a named parameter among the parameters other than `self`"""
+        parameters = function_node.child_by_field_name("parameters")
+        named = [parameter for parameter in parameters.named_children if parameter.type == "parameter"] \
+            if parameters else []
+        for index, parameter in enumerate(named):
+            pattern = parameter.child_by_field_name("pattern")
+            if pattern is not None and self._get_node_text(pattern, code).split()[-1] == name:
+                return index
+        return None
+
+    def _collect_checks(self, function_node: Node, code: str, amount: str) -> Tuple[bool, bool]:
+        """Check if a function compares an amount to zero, and if it compares or bounds it at all"""
+        zero_check = self._zero_check_pattern(amount)
+        bound_check = self._bound_check_pattern(amount)
+        checked_call = re.compile(rf"\b{re.escape(amount)}\s*\.\s*checked_\w+\s*\(|\bchecked_\w+\s*\(\s*"
+                                  rf"&?{re.escape(amount)}\b")
+        compares_zero = bounded = False
+        for node in self._iter_nodes(function_node):
+            guard = self._get_guard_text(node, code)
+            if guard is not None:
+                compares_zero = compares_zero or bool(zero_check.search(guard))
+                bounded = bounded or bool(bound_check.search(guard))
+            elif node.type == "call_expression" and checked_call.search(self._get_node_text(node, code)):
+                bounded = True
+        return compares_zero, bounded or compares_zero
+
+    def _zero_check_pattern(self, amount: str) -> re.Pattern:
+        """Match comparisons of an amount to zero, such as `value == U256::ZERO` or `value.is_zero()`"""
+        name = re.escape(amount)
+        return re.compile(rf"\b{name}\s*{COMPARISON}\s*{ZERO_EXPRESSION}|{ZERO_EXPRESSION}\s*{COMPARISON}\s*{name}\b|"
+                          rf"\b{name}\s*\.\s*is_zero\s*\(")
+
+    def _bound_check_pattern(self, amount: str) -> re.Pattern:
+        """Match any comparison involving an amount"""
+        name = re.escape(amount)
+        return re.compile(rf"\b{name}\s*{COMPARISON}|{COMPARISON}\s*{name}\b|\b{name}\s*\.\s*is_zero\s*\(")
+
+    def _check_sol_function(self, function: Dict, code: str, results) -> None:
+        """Check a sol! function for `emit Transfer(..., value)` without a zero check in `require` or `if`"""
+        parameters = self._get_sol_parameter_names(function["params"], code)
+        guards: List[str] = []
+        for statement in self._split_sol_statements(function["body"]):
+            first = self._get_node_text(statement[0], code)
+            if first in ("require", "if"):
+                guards.append(self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte))
+                continue
+            if first != "emit" or len(statement) < 3 or self._get_node_text(statement[1], code) != TRANSFER_EVENT \
+                    or not self._is_token_tree(statement[2], "("):
+                continue
+            arguments = self._get_node_text(statement[2], code)[1:-1].split(",")
+            amount = arguments[-1].strip()
+            if amount not in parameters:
+                continue
+            checks = (any(self._zero_check_pattern(amount).search(guard) for guard in guards),
+                      any(self._bound_check_pattern(amount).search(guard) for guard in guards))
+            self._check_amount(statement[0], amount, checks, f"sol! function '{function['name']}'", code, results,
+                               end_node=statement[-1])
+
+    def _check_amount(self, node: Node, amount: str, checks: Tuple[bool, bool], function: str, code: str, results,
+                      end_node: Optional[Node] = None) -> None:
+        """Report an emitted amount that isn't compared to zero, or isn't checked at all"""
+        compares_zero, bounded = checks
+        if bounded and (compares_zero or not self.flag_zero_amount):
+            return
+        if bounded:
+            problem = "never compared to zero, so zero-amount transfers go through and emit noise events"
+        else:
+            problem = "never compared to zero or bounded by a balance or limit check"
+        line_start, line_end = self._get_line_for_node(node)
+        if end_node is not None:
+            _, line_end = self._get_line_for_node(end_node)
+        results.add_issue(
+            issue_type="missing_require_on_transfer_amount",
+            severity="Info",
+            description=f"The {function} emits a Transfer event with `{amount}`, which is {problem}.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=self._get_source_text(code, node.start_byte, (end_node or node).end_byte),
+            recommendation=f"Reject a zero `{amount}` with an early `Err` before moving balances, or document "
+                           "that zero-amount transfers are allowed."
+        )
@@ -0,0 +1,99 @@
+"""
+Single traversal of the AST shared by the detectors that look at one node at a time
+"""
+import logging
+import time
+from dataclasses import dataclass, field
+from typing import Dict, List, Optional
+
+from stylus_analyzer.detectors.detector_base import AnalysisContext, BaseDetector
+from stylus_analyzer.findings import Finding
+
+logger = logging.getLogger(__name__)
+
+
+@dataclass
+class DetectorOutcome:
+    """What one detector reported during a shared traversal, or the error it raised, and the seconds it took"""
+    detector: BaseDetector
+    findings: List[Finding] = field(default_factory=list)
+    error: Optional[Exception] = None
+    elapsed: float = 0.0
+
+    def result(self) -> List[Finding]:
+        """Get the findings, raising the detector's error as running it on its own would have"""
+        if self.error is not None:
+            raise self.error
+        return self.findings
+
+
+def run_node_detectors(detectors: List[BaseDetector], ctx: AnalysisContext) -> Dict[str, DetectorOutcome]:
+    """
+    Run detectors declaring `node_types` over a single traversal of a context's AST
+
+    Each node is dispatched to the detectors interested in its type, in the
+    order they are given. Every detector reports into its own results, and a
+    detector raising an error stops being dispatched to and loses its
+    findings while the others carry on, the same as when each runs on its
+    own.
+
+    Args:
+        detectors: The detectors to run, all declaring `node_types`
+        ctx: The parsed contract to analyze
+
+    Returns:
+        The outcome of each detector, by detector id
+    """
+    # Imported here since the analyzer itself imports the detectors
+    from stylus_analyzer.static_analyzer import StaticAnalysisResult
+    outcomes = {detector.id: DetectorOutcome(detector) for detector in detectors}
+    results = {detector.id: StaticAnalysisResult() for detector in detectors}
+
+    def fail(detector: BaseDetector, error: Exception) -> None:
+        logger.debug(f"Detector {detector.id} failed during the shared traversal: {error}")
+        outcomes[detector.id].error = error
+
+    active = []
+    for detector in detectors:
+        detector.context = ctx
+        started = time.perf_counter()
+        try:
+            detector.start(ctx.tree, ctx.code)
+            active.append(detector)
+        except Exception as e:
+            fail(detector, e)
+        outcomes[detector.id].elapsed += time.perf_counter() - started
+
+    dispatch: Dict[str, List[BaseDetector]] = {}
+    for detector in active:
+        for node_type in detector.node_types:
+            dispatch.setdefault(node_type, []).append(detector)
+
+    stack = [ctx.tree.root_node]
+    while stack:
+        node = stack.pop()
+        for detector in dispatch.get(node.type, ()):
+            if outcomes[detector.id].error is not None:
+                continue
+            started = time.perf_counter()
+            try:
+                detector.visit_node(node, ctx.code, results[detector.id])
+            except Exception as e:
+                fail(detector, e)
+            outcomes[detector.id].elapsed += time.perf_counter() - started
+        stack.extend(reversed(node.children))
+
+    for detector in active:
+        outcome = outcomes[detector.id]
+        if outcome.error is not None:
+            continue
+        started = time.perf_counter()
+        try:
+            detector.finish(ctx.tree, ctx.code, results[detector.id])
+            outcome.findings = detector.locate_findings(results[detector.id].findings, ctx)
+        except Exception as e:
+            fail(detector, e)
+        outcome.elapsed += time.perf_counter() - started
+    for detector in detectors:
+        detector.context = None
+    return outcomes
@@ -0,0 +1,132 @@
+"""
+Detector for authentication based on the transaction origin in Stylus Rust contracts
+"""
+import re
+from typing import Optional
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc, GUARD_MACROS
+from stylus_analyzer.findings import Confidence, Severity
+
+# Transaction origin accessors of the Stylus SDK and Solidity
+TX_ORIGIN_PATTERN = re.compile(r"\btx\s*::\s*origin\s*\(|\btx_origin\s*\(")
+SOL_TX_ORIGIN_PATTERN = re.compile(r"\btx\s*\.\s*origin\b")
+
+
+class TxOriginAuthDetector(BaseDetector):
+    """
+    Detector for guards that compare `tx::origin()` instead of `msg::sender()`.
+
+    The transaction origin is the externally owned account that started the
+    transaction, so a malicious contract called by the owner passes the check.
+    """
+
+    default_severity = Severity.HIGH
+    default_confidence = Confidence.HIGH
+    cwe = 477
+    node_types = ("call_expression", "macro_invocation")
+
+    documentation = DetectorDoc(
+        catches="`tx::origin()` or `tx.origin` used in a condition or guard to authorize the caller.",
+        rationale=(
+            "The transaction origin is the account that started the transaction, not the direct caller. A "
+            "malicious contract the owner interacts with passes the check on the owner's behalf."
+        ),
+        vulnerable_example="""
+            pub fn set_owner(&mut self, owner: Address) {
+                assert!(tx::origin() == self.owner.get());
+                self.owner.set(owner);
+            }
+        """,
+        fixed_example="""
+            pub fn set_owner(&mut self, owner: Address) {
+                assert!(msg::sender() == self.owner.get());
+                self.owner.set(owner);
+            }
+        """,
+        references=["https://swcregistry.io/docs/SWC-115"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="tx_origin_auth",
+            description="Detects authentication checks using tx::origin() instead of msg::sender()"
+        )
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if a call reads tx::origin() in a condition, or a guard macro compares it"""
+        if node.type == "call_expression" and self._is_tx_origin_call(node, code):
+            condition = self._get_enclosing_condition(node)
+            if condition:
+                self._report(self._get_comparison(node, condition), code, results)
+        elif node.type == "macro_invocation" and self._get_macro_name(node, code) in GUARD_MACROS:
+            if TX_ORIGIN_PATTERN.search(self._get_node_text(node, code)):
+                self._report(node, code, results)
+
+    def finish(self, tree: Tree, code: str, results) -> None:
+        """Check the require and if statements of sol! functions for tx.origin"""
+        for function in self._get_sol_functions(tree.root_node, code):
+            for statement in self._split_sol_statements(function["body"]):
+                if self._get_node_text(statement[0], code) not in ("require", "if"):
+                    continue
+                text = self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte)
+                if SOL_TX_ORIGIN_PATTERN.search(text):
+                    line_start, _ = self._get_line_for_node(statement[0])
+                    _, line_end = self._get_line_for_node(statement[-1])
+                    self._add_issue(line_start, line_end, self._get_column_for_node(statement[0], code), text,
+                                    f"sol! function '{function['name']}'", results)
+
+    def _is_tx_origin_call(self, node: Node, code: str) -> bool:
+        """Check if a call expression reads the transaction origin"""
+        function = node.child_by_field_name("function")
+        if not function:
+            return False
+        if function.type == "field_expression":
+            method = function.child_by_field_name("field")
+            return bool(method) and self._get_node_text(method, code) == "tx_origin"
+        return self._get_node_text(function, code).replace(" ", "").endswith("tx::origin")
+
+    def _get_enclosing_condition(self, node: Node) -> Optional[Node]:
+        """Get the `if`/`while` condition containing a node, if any"""
+        current = node
+        while current.parent and current.parent.type not in ("block", "function_item"):
+            parent = current.parent
+            if parent.type in ("if_expression", "while_expression"):
+                condition = parent.child_by_field_name("condition")
+                if condition and condition.start_byte == current.start_byte and \
+                        condition.end_byte == current.end_byte:
+                    return current
+                return None
+            current = parent
+        return None
+
+    def _get_comparison(self, node: Node, condition: Node) -> Node:
+        """Get the innermost equality comparison around the call, or the whole condition"""
+        current = node.parent
+        while current and current.start_byte >= condition.start_byte and current.end_byte <= condition.end_byte:
+            if current.type == "binary_expression":
+                operator = current.child_by_field_name("operator")
+                if operator and operator.type in ("==", "!="):
+                    return current
+            current = current.parent
+        return condition
+
+    def _report(self, node: Node, code: str, results) -> None:
+        """Report a guard in a Rust function"""
+        line_start, line_end = self._get_line_for_node(node)
+        function_name = self._get_function_name(self._find_parent_function(node), code)
+        self._add_issue(line_start, line_end, self._get_column_for_node(node, code), self._get_node_text(node, code),
+                        f"function '{function_name}'", results)
+
+    def _add_issue(self, line_start: int, line_end: int, column: int, snippet: str, location: str, results) -> None:
+        """Add a tx origin authentication issue"""
+        results.add_issue(
+            issue_type="tx_origin_auth",
+            severity="High",
+            description=f"Authorization check in {location} uses the transaction origin. Any contract the owner interacts with can pass this check on their behalf.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation="Use msg::sender() to authenticate the immediate caller instead of tx::origin()."
+        )
@@ -0,0 +1,157 @@
+"""
+Detector for loops bounded by caller-supplied input in Stylus Rust contracts
+"""
+import re
+from typing import Dict, Optional, Set
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc, GUARD_MACROS
+from stylus_analyzer.findings import Confidence, Severity
+
+COMPARISON_PATTERN = re.compile(r"<=?|>=?")
+
+
+class UnboundedLoopOverCallerInputDetector(BaseDetector):
+    """
+    Detector for loops whose iteration count comes from a function parameter.
+
+    When such a loop reads or writes storage or calls out, a caller can pass
+    enough input to exhaust the block gas limit, blocking the function for
+    everyone relying on it.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.MEDIUM
+    cwe = 400
+
+    documentation = DetectorDoc(
+        catches=(
+            "Loops in public methods whose iteration count comes from a parameter and whose body accesses storage "
+            "or makes an external call."
+        ),
+        rationale=(
+            "A caller can pass enough input to exceed the block gas limit, so the call can never succeed, or "
+            "another user's transaction fails."
+        ),
+        vulnerable_example="""
+            pub fn airdrop(&mut self, recipients: Vec<Address>, amount: U256) {
+                for recipient in recipients.iter() {
+                    self.balances.setter(*recipient).set(amount);
+                }
+            }
+        """,
+        fixed_example="""
+            pub fn airdrop(&mut self, recipients: Vec<Address>, amount: U256) -> Result<(), Vec<u8>> {
+                if recipients.len() > MAX_BATCH {
+                    return Err(b"batch too large".to_vec());
+                }
+                for recipient in recipients.iter() {
+                    self.balances.setter(*recipient).set(amount);
+                }
+                Ok(())
+            }
+        """,
+        references=["https://swcregistry.io/docs/SWC-128"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="unbounded_loop_over_caller_input",
+            description="Detects loops bounded by caller input that touch storage or make external calls"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect unbounded loops in public methods"""
+        interfaces = self._collect_interface_names(tree.root_node, code)
+        for function_node in self._get_public_methods(tree.root_node, code):
+            self._check_function(function_node, code, interfaces, results)
+
+    def _check_function(self, function_node: Node, code: str, interfaces, results) -> None:
+        """Check the loops of a single public method"""
+        parameters = self._collect_parameters(function_node, code)
+        body = function_node.child_by_field_name("body")
+        if not parameters or not body:
+            return
+        handles = self._collect_interface_handles(function_node, code, interfaces)
+        function_name = self._get_function_name(function_node, code)
+
+        for loop in self._iter_nodes(body):
+            if loop.type == "for_expression":
+                bound = loop.child_by_field_name("value")
+            elif loop.type == "while_expression":
+                bound = loop.child_by_field_name("condition")
+            else:
+                continue
+            if bound is None:
+                continue
+            parameter = self._find_parameter(bound, code, parameters)
+            if parameter is None or self._is_bounded_before(body, loop, parameter, code):
+                continue
+            loop_body = loop.child_by_field_name("body")
+            operation = self._find_costly_operation(loop_body, code, interfaces, handles) if loop_body else None
+            if operation is None:
+                continue
+
+            loop_line, _ = self._get_line_for_node(loop)
+            parameter_line = parameters[parameter]
+            results.add_issue(
+                issue_type="unbounded_loop_over_caller_input",
+                severity="Medium",
+                description=f"Loop at line {loop_line} in function '{function_name}' iterates over caller-supplied parameter '{parameter}' (line {parameter_line}) and {operation} on every iteration. A large input can exhaust the gas limit.",
+                line_start=loop_line,
+                line_end=self._get_line_for_node(bound)[1],
+                column=self._get_column_for_node(loop, code),
+                code_snippet=self._get_source_text(code, loop.start_byte, bound.end_byte),
+                recommendation=f"Cap the size of '{parameter}' with an explicit maximum before the loop, or process the input in bounded batches."
+            )
+
+    def _collect_parameters(self, function_node: Node, code: str) -> Dict[str, int]:
+        """Map the parameter names of a function to their line"""
+        parameters = {}
+        parameter_list = function_node.child_by_field_name("parameters")
+        if not parameter_list:
+            return parameters
+        for parameter in parameter_list.named_children:
+            if parameter.type != "parameter":
+                continue
+            pattern = parameter.child_by_field_name("pattern")
+            if pattern and pattern.type == "mut_pattern" and pattern.named_children:
+                pattern = pattern.named_children[-1]
+            if pattern and pattern.type == "identifier":
+                parameters[self._get_node_text(pattern, code)] = self._get_line_for_node(parameter)[0]
+        return parameters
+
+    def _find_parameter(self, node: Node, code: str, parameters: Dict[str, int]) -> Optional[str]:
+        """Find the first parameter referenced by an expression"""
+        for child in self._iter_nodes(node):
+            if child.type == "identifier" and self._get_node_text(child, code) in parameters:
+                return self._get_node_text(child, code)
+        return None
+
+    def _is_bounded_before(self, body: Node, loop: Node, parameter: str, code: str) -> bool:
+        """Check if the parameter is compared against a limit before the loop"""
+        reference = re.compile(rf"\b{re.escape(parameter)}\b")
+        for node in self._iter_nodes(body):
+            if node.start_byte >= loop.start_byte:
+                break
+            if node.type == "if_expression":
+                condition = node.child_by_field_name("condition")
+                text = self._get_node_text(condition, code) if condition else ""
+            elif node.type == "macro_invocation" and self._get_macro_name(node, code) in GUARD_MACROS:
+                text = self._get_node_text(node, code)
+            else:
+                continue
+            if reference.search(text) and COMPARISON_PATTERN.search(text.replace("->", "").replace("=>", "")):
+                return True
+        return False
+
+    def _find_costly_operation(self, loop_body: Node, code: str, interfaces, handles: Set[str]) -> Optional[str]:
+        """Describe the first storage access or external call in a loop body"""
+        for node in self._iter_nodes(loop_body):
+            if self._is_external_call(node, code, interfaces, handles):
+                return "makes an external call"
+            if node.type == "field_expression":
+                value = node.child_by_field_name("value")
+                if value and value.type == "self":
+                    return "accesses storage"
+        return None
@@ -0,0 +1,273 @@
+"""
+Detector for unchecked integer arithmetic in Stylus Rust contracts
+"""
+import re
+from typing import Dict, List, Optional
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# Arithmetic operators that can overflow or underflow
+ARITHMETIC_OPERATORS = ("+", "-", "*", "**")
+COMPOUND_OPERATORS = ("+=", "-=", "*=")
+
+# Fixed-width integer types, including Stylus storage wrappers and Solidity types
+INTEGER_TYPE_PATTERN = re.compile(
+    r"\b(U\d+|I\d+|Uint|Signed|u8|u16|u32|u64|u128|i8|i16|i32|i64|i128|"
+    r"StorageU\d+|StorageI\d+|StorageUint|StorageSigned|uint\d*|int\d*)\b"
+)
+
+# Comment marking a statement or block as reviewed for overflow
+SAFE_ANNOTATION = re.compile(r"//\s*SAFE\b")
+
+# Identifiers following the constant naming convention
+CONSTANT_NAME = re.compile(r"^[A-Z][A-Z0-9_]*$")
+
+
+class UncheckedArithmeticDetector(BaseDetector):
+    """
+    Detector for arithmetic on fixed-width integers without checked operations.
+
+    `U256` and the primitive integer types wrap silently in release builds, so
+    balances and supplies computed with `+`, `-` or `*` can overflow or underflow
+    instead of reverting.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.LOW
+    cwe = 190
+    node_types = ("binary_expression", "compound_assignment_expr")
+
+    # Types whose arithmetic is reported, narrowed by subclasses
+    integer_type_pattern = INTEGER_TYPE_PATTERN
+
+    documentation = DetectorDoc(
+        catches=(
+            "`+`, `-` and `*` on `U256`, `I256` and fixed-width integers that don't use `checked_*` or "
+            "`saturating_*` operations."
+        ),
+        rationale=(
+            "These types wrap around silently, so a balance can underflow to a huge value instead of the "
+            "transaction reverting."
+        ),
+        vulnerable_example="""
+            let balance = self.balances.get(from);
+            self.balances.setter(from).set(balance - amount);
+        """,
+        fixed_example="""
+            let balance = self.balances.get(from);
+            let remaining = balance.checked_sub(amount).ok_or(b"insufficient balance".to_vec())?;
+            self.balances.setter(from).set(remaining);
+        """,
+        references=["https://swcregistry.io/docs/SWC-101"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="unchecked_arithmetic",
+            description="Detects integer arithmetic that doesn't use checked or saturating operations"
+        )
+
+    def start(self, tree: Tree, code: str) -> None:
+        """Collect the lines and storage fields of the file the expressions are checked against"""
+        self.lines = code.split("\n")
+        self.storage_fields = self._collect_storage_fields(tree.root_node, code)
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if an arithmetic expression of a Rust function works on integers without a check"""
+        if node.type == "binary_expression":
+            operator = node.child_by_field_name("operator")
+            if not operator or operator.type not in ARITHMETIC_OPERATORS:
+                return
+            # Only report the outermost expression of a chain like `a + b + c`
+            if node.parent and node.parent.type == "binary_expression" and self._is_arithmetic(node.parent):
+                return
+        else:
+            operator = node.child_by_field_name("operator")
+            if not operator or operator.type not in COMPOUND_OPERATORS:
+                return
+
+        function_node = self._find_parent_function(node)
+        if function_node is None or self._is_in_constant(node):
+            return
+        local_types = self._get_local_types(function_node, code)
+        left_kind = self._infer_operand(node.child_by_field_name("left"), code, local_types, self.storage_fields)
+        right_kind = self._infer_operand(node.child_by_field_name("right"), code, local_types, self.storage_fields)
+        if "int" not in (left_kind, right_kind):
+            return
+
+        self._report(node, code, operator.type, f"function '{self._get_function_name(function_node, code)}'",
+                     results)
+
+    def finish(self, tree: Tree, code: str, results) -> None:
+        """Check the arithmetic statements of sol! function bodies"""
+        for function in self._get_sol_functions(tree.root_node, code):
+            self._check_sol_function(function, code, results)
+
+    def _get_local_types(self, function_node: Node, code: str) -> Dict[str, bool]:
+        """Get whether each parameter and local of a function holds an integer, collected once per function"""
+        return self._get_function_fact(f"{self.id}.local_types", function_node, code,
+                                       lambda: self._collect_local_types(function_node, code, self.storage_fields))
+
+    def _check_sol_function(self, function: Dict, code: str, results) -> None:
+        """Check the arithmetic statements inside a sol! function body"""
+        for statement in self._split_sol_statements(function["body"]):
+            texts = [self._get_node_text(token, code) for token in statement]
+            if "constant" in texts:
+                continue
+            operators = [token for token in statement
+                         if token.type in ARITHMETIC_OPERATORS + COMPOUND_OPERATORS]
+            if not operators:
+                continue
+            operator = operators[0]
+            self._report_tokens(statement, operator, code, f"sol! function '{function['name']}'", results)
+
+    def _report(self, node: Node, code: str, operator: str, location: str, results) -> None:
+        """Report one arithmetic expression of a Rust function"""
+        line_start, line_end = self._get_line_for_node(node)
+        self._add_issue(line_start, line_end, self._get_column_for_node(node, code), self._get_node_text(node, code),
+                        operator, location, self._is_safe_annotated(node), results)
+
+    def _report_tokens(self, statement: List[Node], operator: Node, code: str, location: str, results) -> None:
+        """Report one arithmetic statement of a sol! function"""
+        line_start, _ = self._get_line_for_node(operator)
+        _, line_end = self._get_line_for_node(statement[-1])
+        snippet = self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte)
+        self._add_issue(line_start, line_end, self._get_column_for_node(operator, code), snippet, operator.type,
+                        location, self._is_safe_annotated(statement[0]), results)
+
+    def _add_issue(self, line_start: int, line_end: int, column: int, snippet: str, operator: str,
+                   location: str, safe: bool, results) -> None:
+        """Add an unchecked arithmetic issue, downgraded if marked as reviewed"""
+        if safe:
+            severity = "Info"
+            description = f"Unchecked '{operator}' in {location} is marked as SAFE. Make sure the operands are bounded."
+        else:
+            severity = "Medium"
+            description = f"Unchecked '{operator}' in {location} can silently overflow or underflow."
+        results.add_issue(
+            issue_type="unchecked_arithmetic",
+            severity=severity,
+            description=description,
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation="Use checked_add/checked_sub/checked_mul and handle the None case, or saturating_* when clamping is the intended behavior."
+        )
+
+    def _is_arithmetic(self, node: Node) -> bool:
+        """Check if a binary expression uses an arithmetic operator"""
+        operator = node.child_by_field_name("operator")
+        return bool(operator) and operator.type in ARITHMETIC_OPERATORS
+
+    def _is_in_constant(self, node: Node) -> bool:
+        """Check if a node is part of a const or static item"""
+        parent = node.parent
+        while parent:
+            if parent.type in ("const_item", "static_item"):
+                return True
+            parent = parent.parent
+        return False
+
+    def _is_safe_annotated(self, node: Node) -> bool:
+        """
+        Check if a `// SAFE` comment covers the node, either on its own line,
+        on the line above it, or right before one of its enclosing blocks.
+        """
+        if self._has_safe_comment(node.start_point[0]):
+            return True
+        parent = node.parent
+        while parent and parent.type != "function_item":
+            if parent.type == "block" or (parent.type == "token_tree" and parent.children
+                                          and parent.children[0].type == "{"):
+                if self._has_safe_comment(parent.start_point[0]):
+                    return True
+            parent = parent.parent
+        return False
+
+    def _has_safe_comment(self, row: int) -> bool:
+        """Check the given line and the one above it for a SAFE annotation"""
+        for index in (row, row - 1):
+            if 0 <= index < len(self.lines) and SAFE_ANNOTATION.search(self.lines[index]):
+                return True
+        return False
+
+    def _collect_local_types(self, function_node: Node, code: str,
+                             storage_fields: Dict[str, str]) -> Dict[str, bool]:
+        """Map parameters and locals of a function to whether they hold an integer"""
+        local_types = {}
+        for node in self._iter_nodes(function_node):
+            if node.type == "parameter":
+                pattern = node.child_by_field_name("pattern")
+                param_type = node.child_by_field_name("type")
+                if pattern and param_type:
+                    local_types[self._get_binding_name(pattern, code)] = \
+                        bool(self.integer_type_pattern.search(self._get_node_text(param_type, code)))
+            elif node.type == "let_declaration":
+                pattern = node.child_by_field_name("pattern")
+                if not pattern:
+                    continue
+                let_type = node.child_by_field_name("type")
+                value = node.child_by_field_name("value")
+                if let_type:
+                    is_integer = bool(self.integer_type_pattern.search(self._get_node_text(let_type, code)))
+                else:
+                    is_integer = self._infer_operand(value, code, local_types, storage_fields) == "int"
+                local_types[self._get_binding_name(pattern, code)] = is_integer
+        return local_types
+
+    def _get_binding_name(self, pattern: Node, code: str) -> str:
+        """Get the name bound by a simple `x` or `mut x` pattern"""
+        if pattern.type == "mut_pattern" and pattern.named_children:
+            pattern = pattern.named_children[-1]
+        return self._get_node_text(pattern, code)
+
+    def _infer_operand(self, node: Optional[Node], code: str, local_types: Dict[str, bool],
+                       storage_fields: Dict[str, str]) -> Optional[str]:
+        """
+        Heuristically classify an operand.
+
+        Returns:
+            "int" for expressions known to be fixed-width integers, "literal" for
+            constants, or None when the type is unknown
+        """
+        if node is None:
+            return None
+        if node.type == "integer_literal":
+            return "literal"
+        if node.type == "identifier":
+            name = self._get_node_text(node, code)
+            if CONSTANT_NAME.match(name):
+                return "literal"
+            return "int" if local_types.get(name) else None
+        if node.type == "scoped_identifier":
+            # e.g. U256::ZERO or U256::MAX
+            return "literal"
+        if node.type in ("parenthesized_expression", "unary_expression", "try_expression"):
+            inner = node.named_children[-1] if node.named_children else None
+            return self._infer_operand(inner, code, local_types, storage_fields)
+        if node.type == "type_cast_expression":
+            cast_type = node.child_by_field_name("type")
+            if cast_type and self.integer_type_pattern.search(self._get_node_text(cast_type, code)):
+                return "int"
+            return None
+        if node.type == "binary_expression" and self._is_arithmetic(node):
+            kinds = (self._infer_operand(node.child_by_field_name("left"), code, local_types, storage_fields),
+                     self._infer_operand(node.child_by_field_name("right"), code, local_types, storage_fields))
+            if "int" in kinds:
+                return "int"
+            return "literal" if kinds == ("literal", "literal") else None
+        if node.type == "call_expression":
+            function = node.child_by_field_name("function")
+            if function and function.type == "scoped_identifier":
+                # Constructors such as U256::from(x)
+                path = function.child_by_field_name("path")
+                if path and self.integer_type_pattern.search(self._get_node_text(path, code)):
+                    return "int"
+                return None
+        field = self._get_self_field(node, code)
+        if field and self.integer_type_pattern.search(storage_fields.get(field, "")):
+            return "int"
+        return None
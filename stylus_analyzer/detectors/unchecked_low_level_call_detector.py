@@ -0,0 +1,231 @@
+"""
+Detector for low-level calls whose result is discarded in Stylus Rust contracts
+"""
+import re
+from typing import Dict, List, Optional
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# Raw call entry points of the Stylus SDK and Solidity
+RAW_CALLS = ("call", "delegate_call", "static_call")
+SOL_RAW_CALLS = ("call", "delegatecall", "staticcall")
+
+# Adapters that turn a call `Result` into something that is easily dropped
+DISCARDING_ADAPTERS = ("ok", "unwrap_or_default")
+
+
+class UncheckedLowLevelCallDetector(BaseDetector):
+    """
+    Detector for raw `call`/`delegate_call`/`static_call` results that are ignored.
+
+    A low-level call does not revert the caller when the callee fails, so a
+    dropped result or success flag lets the function continue as if the call
+    had succeeded. Calls through `sol_interface!` handles are left to the
+    unchecked transfer detector.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.HIGH
+    cwe = 252
+    node_types = ("call_expression",)
+
+    documentation = DetectorDoc(
+        catches=(
+            "`call`, `delegate_call` and `static_call` results that are discarded, and `sol!` low-level calls "
+            "whose success flag is never checked."
+        ),
+        rationale=(
+            "A failed low-level call doesn't revert the caller, so the contract continues as if the call had "
+            "succeeded."
+        ),
+        vulnerable_example="""
+            let _ = call(Call::new_in(self).value(amount), recipient, &[]);
+        """,
+        fixed_example="""
+            call(Call::new_in(self).value(amount), recipient, &[])?;
+        """,
+        references=[
+            "https://swcregistry.io/docs/SWC-104",
+            "https://github.com/crytic/slither/wiki/Detector-Documentation",
+        ],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="unchecked_low_level_call",
+            description="Detects low-level calls whose result or success flag is discarded"
+        )
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if the result of a low-level call is discarded"""
+        if self._is_raw_call(node, code):
+            self._check_call(node, code, results)
+
+    def finish(self, tree: Tree, code: str, results) -> None:
+        """Check the low-level calls in sol! function bodies"""
+        for function in self._get_sol_functions(tree.root_node, code):
+            self._check_sol_function(function, code, results)
+
+    def _is_raw_call(self, node: Node, code: str) -> bool:
+        """Check if a call expression is a raw low-level call"""
+        function = node.child_by_field_name("function")
+        if not function:
+            return False
+        if function.type == "field_expression":
+            method = function.child_by_field_name("field")
+            return bool(method) and self._get_node_text(method, code) in RAW_CALLS
+        if function.type in ("identifier", "scoped_identifier"):
+            return self._get_node_text(function, code).split("::")[-1] in RAW_CALLS
+        return False
+
+    def _check_call(self, node: Node, code: str, results) -> None:
+        """Check how the result of a raw call is used"""
+        result = node
+        while True:
+            if self._is_discarding_adapter(result.parent, result, code):
+                result = result.parent.parent
+            elif self._is_unsafe_block_value(result):
+                result = result.parent.parent
+            else:
+                break
+        parent = result.parent
+        if parent is None:
+            return
+
+        reason = None
+        if parent.type == "expression_statement":
+            reason = "has its result discarded"
+        elif parent.type == "let_declaration":
+            pattern = parent.child_by_field_name("pattern")
+            if pattern is not None and pattern.type == "_":
+                reason = "has its result bound to `_`"
+            elif pattern is not None and pattern.type == "tuple_pattern":
+                elements = [child for child in pattern.children if child.type not in ("(", ",", ")")]
+                flag = elements[0] if elements else None
+                if flag is None or flag.type != "identifier":
+                    reason = "ignores the success flag"
+                else:
+                    name = self._get_node_text(flag, code)
+                    function_node = self._find_parent_function(node)
+                    scope = function_node.child_by_field_name("body") if function_node else None
+                    if scope is not None and not self._is_flag_checked(scope, parent.end_byte, name, code):
+                        reason = f"binds the success flag '{name}' but never checks it"
+        if reason is None:
+            return
+
+        function_node = self._find_parent_function(node)
+        location = f"function '{self._get_function_name(function_node, code)}'" if function_node else "the contract"
+        line_start, line_end = self._get_line_for_node(parent)
+        self._add_issue(line_start, line_end, self._get_column_for_node(parent, code), self._get_node_text(parent, code),
+                        reason, location, results)
+
+    def _is_discarding_adapter(self, node: Optional[Node], receiver: Node, code: str) -> bool:
+        """Check if a node is `.ok()` or a similar adapter called on the receiver"""
+        if node is None or node.type != "field_expression" or node.parent is None:
+            return False
+        if node.parent.type != "call_expression":
+            return False
+        value = node.child_by_field_name("value")
+        if value is None or (value.start_byte, value.end_byte) != (receiver.start_byte, receiver.end_byte):
+            return False
+        method = node.child_by_field_name("field")
+        return bool(method) and self._get_node_text(method, code) in DISCARDING_ADAPTERS
+
+    def _is_unsafe_block_value(self, node: Node) -> bool:
+        """Check if a node is the value of an `unsafe { ... }` block, as raw calls usually are"""
+        block = node.parent
+        if block is None or block.type != "block" or block.parent is None or block.parent.type != "unsafe_block":
+            return False
+        last = block.named_children[-1]
+        return (last.start_byte, last.end_byte) == (node.start_byte, node.end_byte)
+
+    def _is_flag_checked(self, scope: Node, after: int, name: str, code: str) -> bool:
+        """Check if a success flag is used in a guard, match or return after its binding"""
+        reference = re.compile(rf"\b{re.escape(name)}\b")
+        for node in self._iter_nodes(scope):
+            if node.start_byte < after:
+                continue
+            if node.type == "match_expression":
+                value = node.child_by_field_name("value")
+                text = self._get_node_text(value, code) if value else ""
+            elif node.type == "return_expression":
+                text = self._get_node_text(node, code)
+            else:
+                text = self._get_guard_text(node, code) or ""
+            if reference.search(text):
+                return True
+        return False
+
+    def _check_sol_function(self, function: Dict, code: str, results) -> None:
+        """Check a sol! function for raw calls in statement position or with an unchecked flag"""
+        statements = self._split_sol_statements(function["body"])
+        for position, statement in enumerate(statements):
+            call_index = self._find_sol_call(statement, code)
+            if call_index is None:
+                continue
+            reason = None
+            assignment = next((index for index, token in enumerate(statement[:call_index]) if token.type == "="), None)
+            if assignment is None:
+                reason = "has its result discarded"
+            elif assignment == 1 and self._is_token_tree(statement[0], "("):
+                name = self._get_sol_flag_name(statement[0], code)
+                if name is None:
+                    reason = "ignores the success flag"
+                elif not self._is_sol_flag_checked(statements[position + 1:], name, code):
+                    reason = f"binds the success flag '{name}' but never checks it"
+            if reason is None:
+                continue
+            line_start, _ = self._get_line_for_node(statement[0])
+            _, line_end = self._get_line_for_node(statement[-1])
+            self._add_issue(line_start, line_end, self._get_column_for_node(statement[0], code),
+                            self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte), reason,
+                            f"sol! function '{function['name']}'", results)
+
+    def _find_sol_call(self, statement: List[Node], code: str) -> Optional[int]:
+        """Find the index of a `.call`, `.delegatecall` or `.staticcall` in a sol! statement"""
+        for index, token in enumerate(statement[1:], start=1):
+            if token.type != "identifier" or self._get_node_text(token, code) not in SOL_RAW_CALLS:
+                continue
+            if statement[index - 1].type != ".":
+                continue
+            # `addr.call{value: v}(...)` splits the statement at the options block
+            if index + 1 == len(statement) or self._is_token_tree(statement[index + 1], "("):
+                return index
+        return None
+
+    def _get_sol_flag_name(self, target: Node, code: str) -> Optional[str]:
+        """Get the name bound to the first element of a `(bool ok, ...)` tuple"""
+        first = []
+        for token in target.children[1:-1]:
+            if token.type == ",":
+                break
+            first.append(token)
+        if not first or first[-1].type != "identifier":
+            return None
+        return self._get_node_text(first[-1], code)
+
+    def _is_sol_flag_checked(self, statements: List[List[Node]], name: str, code: str) -> bool:
+        """Check if a success flag is used in a later require, if or return"""
+        reference = re.compile(rf"\b{re.escape(name)}\b")
+        for statement in statements:
+            if self._get_node_text(statement[0], code) not in ("require", "assert", "if", "return"):
+                continue
+            if reference.search(self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte)):
+                return True
+        return False
+
+    def _add_issue(self, line_start: int, line_end: int, column: int, snippet: str, reason: str, location: str,
+                   results) -> None:
+        """Add an unchecked low-level call issue"""
+        results.add_issue(
+            issue_type="unchecked_low_level_call",
+            severity="Medium",
+            description=f"Low-level call in {location} {reason}. A failed call does not revert the caller, so execution continues as if it had succeeded.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation="Propagate the call result with `?` or check the success flag, e.g. `let (success, data) = ...; require(success, ...)`."
+        )
@@ -4,20 +4,55 @@ Detector for unchecked transfer calls in Stylus Rust contracts
 from tree_sitter import Node, Tree
 from typing import Optional
 
-from stylus_analyzer.detectors.detector_base import BaseDetector
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
 
 
 class UncheckedTransferDetector(BaseDetector):
     """Detector for unchecked transfer calls in Stylus contracts"""
     
+    default_severity = Severity.HIGH
+    default_confidence = Confidence.HIGH
+    cwe = 252
+
+    documentation = DetectorDoc(
+        catches="Calls to `transfer` and `transfer_from` on token interfaces whose returned result is ignored.",
+        rationale=(
+            "Many tokens report failure through their return value instead of reverting. When it is dropped, the "
+            "contract carries on as if the tokens had moved, for example crediting a deposit that never arrived."
+        ),
+        vulnerable_example="""
+            pub fn deposit(&mut self, token: IERC20, amount: U256) {
+                token.transfer_from(&mut *self, msg::sender(), contract::address(), amount);
+                self.balances.setter(msg::sender()).set(amount);
+            }
+        """,
+        fixed_example="""
+            pub fn deposit(&mut self, token: IERC20, amount: U256) -> Result<(), Vec<u8>> {
+                let ok = token.transfer_from(&mut *self, msg::sender(), contract::address(), amount)?;
+                if !ok {
+                    return Err(b"transfer failed".to_vec());
+                }
+                self.balances.setter(msg::sender()).set(amount);
+                Ok(())
+            }
+        """,
+        references=[
+            "https://swcregistry.io/docs/SWC-104",
+            "https://github.com/crytic/slither/wiki/Detector-Documentation",
+        ],
+    )
+
     def __init__(self):
         super().__init__(
             name="unchecked_transfer",
             description="Detects unchecked transfer calls where the return value is not checked"
         )
+        self._interfaces = []
         
     def detect(self, tree: Tree, code: str, results) -> None:
         """Detect unchecked transfers in the contract"""
+        self._interfaces = self._get_interfaces(tree.root_node, code)
         self._find_unchecked_transfers(tree.root_node, code, results)
         self._check_solidity_unchecked_transfers(code, results)
     
@@ -37,6 +72,7 @@ class UncheckedTransferDetector(BaseDetector):
                         description="ERC20 transfer call with unchecked return value. This can lead to silent failures.",
                         line_start=line_start,
                         line_end=line_end,
+                        column=self._get_column_for_node(node, code),
                         code_snippet=call_text,
                         recommendation="Check the boolean return value of transfer calls (e.g., `if !success { revert }`)."
                     )
@@ -51,6 +87,7 @@ class UncheckedTransferDetector(BaseDetector):
                         description="Low-level call to transfer function without checking return value.",
                         line_start=line_start,
                         line_end=line_end,
+                        column=self._get_column_for_node(node, code),
                         code_snippet=call_text,
                         recommendation="Check the return value using `(bool success, bytes memory returnData) = ...` and verify success."
                     )
@@ -66,6 +103,7 @@ class UncheckedTransferDetector(BaseDetector):
                     description="Transfer errors are explicitly caught and ignored. This can lead to silent failures.",
                     line_start=line_start,
                     line_end=line_end,
+                    column=self._get_column_for_node(node, code),
                     code_snippet=match_text,
                     recommendation="Handle errors appropriately by propagating them or providing fallback behavior."
                 )
@@ -155,10 +193,12 @@ class UncheckedTransferDetector(BaseDetector):
                         # Find the line with the transfer call
                         transfer_line = None
                         transfer_code = None
+                        transfer_column = 1
                         for j, body_line in enumerate(func_body.split('\n')):
                             if ".call(" in body_line and any(x in body_line for x in ["transfer", "transferFrom"]):
                                 transfer_line = line_num + j
                                 transfer_code = body_line.strip()
+                                transfer_column = len(body_line) - len(body_line.lstrip()) + 1
                                 break
                         
                         if transfer_line:
@@ -168,6 +208,7 @@ class UncheckedTransferDetector(BaseDetector):
                                 description=f"Solidity function '{function_name}' contains unchecked transfer call. This can lead to silent failures.",
                                 line_start=transfer_line,
                                 line_end=transfer_line,
+                                column=transfer_column,
                                 code_snippet=transfer_code,
                                 recommendation="Check the return value using `(bool success, bytes memory returnData) = ...` and verify success with require."
                             )
@@ -183,9 +224,27 @@ class UncheckedTransferDetector(BaseDetector):
                 function_text = self._get_node_text(function_node, code)
                 if "IERC20" in function_text:
                     return True
-            if "IERC20" in code[:node.start_byte]:
+            if "IERC20" in self._get_source_text(code, 0, node.start_byte):
                 return True
-        return False
+        return self._is_bool_interface_call(node, code)
+
+    def _is_bool_interface_call(self, node: Node, code: str) -> bool:
+        """Check if a call goes through an interface handle to a sol_interface! method returning bool"""
+        function = node.child_by_field_name("function")
+        function_node = self._find_parent_function(node)
+        if not function or function.type != "field_expression" or not function_node:
+            return False
+        method = function.child_by_field_name("field")
+        receiver = function.child_by_field_name("value")
+        if not method or not receiver:
+            return False
+        handles = self._collect_interface_handles(function_node, code,
+                                                  {interface.name for interface in self._interfaces})
+        if self._get_node_text(receiver, code) not in handles:
+            return False
+        method_name = self._get_node_text(method, code)
+        return any(declared.returns_bool for interface in self._interfaces
+                   for declared in interface.functions if declared.name == method_name)
     
     def _is_return_value_checked(self, node: Node, code: str) -> bool:
         """Check if the return value of a token transfer call is properly checked"""
@@ -331,9 +390,3 @@ class UncheckedTransferDetector(BaseDetector):
     def _get_line_for_node(self, node: Node) -> tuple:
         """Get the line number for a node (1-indexed)"""
         return node.start_point[0] + 1, node.end_point[0] + 1
-    
-    def _get_node_text(self, node: Node, code: str) -> str:
-        """Get the text of a node from the code"""
-        if node.start_byte < 0 or node.end_byte > len(code) or node.start_byte >= node.end_byte:
-            return ""
-        return code[node.start_byte:node.end_byte]
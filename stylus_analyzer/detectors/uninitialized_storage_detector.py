@@ -0,0 +1,170 @@
+"""
+Detector for storage fields the constructor never initializes in Stylus Rust contracts
+"""
+import re
+from typing import Dict, Iterable, Optional, Set
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc, STORAGE_WRITE_METHODS
+from stylus_analyzer.findings import Confidence, Severity
+
+# Collections start out empty, which is the expected initial state
+COLLECTION_TYPE_PATTERN = re.compile(r"Map|Vec|Array|mapping|\[")
+
+# Numeric and boolean fields whose zero default is usually fine
+DEFAULTABLE_TYPE_PATTERN = re.compile(
+    r"\b(Storage)?(U\d+|I\d+|Uint|Signed|Bool|bool|u8|u16|u32|u64|u128|i8|i16|i32|i64|i128|uint\d*|int\d*)\b"
+)
+
+# Configuration fields that are rarely meant to stay zero
+IMPORTANT_FIELD_PATTERN = re.compile(
+    r"owner|admin|governance|fee|rate|price|cap|limit|decimals|threshold|treasury|oracle|delay|duration",
+    re.IGNORECASE
+)
+
+# Placeholder values that allocate a field without giving it a value
+PLACEHOLDER_PATTERN = re.compile(r"^(\w+::)*(new|default)\s*\(\s*\)$|^Default::default\s*\(\s*\)$")
+
+INITIALIZING_METHODS = STORAGE_WRITE_METHODS + ("initialize",)
+
+
+class UninitializedStorageFieldDetector(BaseDetector):
+    """
+    Detector for storage struct fields that the constructor leaves unset.
+
+    Collections and plain counters may start at their default, but fields such
+    as `owner` or `fee` that stay zero after deployment usually indicate a
+    forgotten initialization. Fields can be opted out by name.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.MEDIUM
+    cwe = 909
+
+    documentation = DetectorDoc(
+        catches=(
+            "Fields of storage structs that the constructor never initializes, when their name suggests "
+            "configuration such as `owner`, `fee` or `decimals`."
+        ),
+        rationale=(
+            "Storage starts out zeroed, so a forgotten field silently holds zero, the zero address or `false` "
+            "after deployment."
+        ),
+        vulnerable_example="""
+            #[public]
+            impl Token {
+                #[constructor]
+                pub fn constructor(&mut self, name: String) {
+                    self.name.set_str(name);
+                }
+            }
+        """,
+        fixed_example="""
+            #[public]
+            impl Token {
+                #[constructor]
+                pub fn constructor(&mut self, name: String, decimals: u8) {
+                    self.name.set_str(name);
+                    self.decimals.set(U8::from(decimals));
+                }
+            }
+        """,
+        references=["https://swcregistry.io/docs/SWC-109"],
+    )
+
+    options = {"allowlist": list}
+
+    def __init__(self, allowlist: Optional[Iterable[str]] = None):
+        super().__init__(
+            name="uninitialized_storage_field",
+            description="Detects storage fields never initialized in the constructor"
+        )
+        self.allowlist = set(allowlist or ())
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect fields left uninitialized by the constructor of each storage struct"""
+        impls = self._collect_impl_methods(tree.root_node, code)
+        # The constructor is reported rather than the struct, so structs from other files of the crate count
+        for struct in self._collect_storage_structs(tree.root_node, code, include_imported=True):
+            methods = impls.get(struct["name"], {})
+            constructors = [method for method in methods.values() if self._is_constructor(method, code)]
+            if not constructors:
+                continue
+            # An explicit #[constructor] takes precedence over new/init-style methods
+            constructor = next((method for method in constructors
+                                if self._get_item_flags(method, code).constructor), constructors[0])
+            initialized = self._collect_initialized_fields(constructor, code, methods, set())
+            missing = [field for field, field_type in struct["fields"].items()
+                       if field not in initialized and field not in self.allowlist and
+                       self._needs_initialization(field, field_type)]
+            if not missing:
+                continue
+
+            constructor_name = self._get_function_name(constructor, code)
+            line_start, line_end = self._get_line_for_node(constructor)
+            body = constructor.child_by_field_name("body")
+            snippet = self._get_source_text(code, constructor.start_byte, body.start_byte).strip() if body else \
+                self._get_node_text(constructor, code)
+            results.add_issue(
+                issue_type="uninitialized_storage_field",
+                severity="Low",
+                description=f"Constructor '{constructor_name}' of storage struct '{struct['name']}' never initializes: {', '.join(missing)}. These fields keep their zero default after deployment.",
+                line_start=line_start,
+                line_end=line_end,
+                column=self._get_column_for_node(constructor, code),
+                code_snippet=snippet,
+                recommendation="Set these fields in the constructor, or list them in the `allowlist` of `[options.uninitialized_storage_field]` in .stylus-analyzer.toml if the zero default is intended."
+            )
+
+    def _needs_initialization(self, field: str, field_type: str) -> bool:
+        """Check if a field's default value is likely unintended"""
+        if COLLECTION_TYPE_PATTERN.search(field_type):
+            return False
+        if DEFAULTABLE_TYPE_PATTERN.search(field_type):
+            return bool(IMPORTANT_FIELD_PATTERN.search(field))
+        return True
+
+    def _collect_initialized_fields(self, function_node: Node, code: str, methods: Dict[str, Node],
+                                    visited: Set[str]) -> Set[str]:
+        """Collect the fields a method initializes, following calls to `self` helpers once"""
+        visited.add(self._get_function_name(function_node, code))
+        fields: Set[str] = set()
+        body = function_node.child_by_field_name("body")
+        for node in self._iter_nodes(body) if body else []:
+            if node.type == "call_expression":
+                function = node.child_by_field_name("function")
+                if not function or function.type != "field_expression":
+                    continue
+                method = self._get_node_text(function.child_by_field_name("field"), code)
+                receiver = function.child_by_field_name("value")
+                if receiver is None:
+                    continue
+                if receiver.type == "self" and method in methods and method not in visited:
+                    fields |= self._collect_initialized_fields(methods[method], code, methods, visited)
+                elif method in INITIALIZING_METHODS:
+                    field = self._get_receiver_field(receiver, code)
+                    if field:
+                        fields.add(field)
+            elif node.type == "assignment_expression":
+                left = node.child_by_field_name("left")
+                field = self._get_receiver_field(left, code) if left else None
+                if field:
+                    fields.add(field)
+            elif node.type == "field_initializer":
+                name = node.child_by_field_name("name")
+                value = node.child_by_field_name("value")
+                if name and value and not PLACEHOLDER_PATTERN.match(self._get_node_text(value, code)):
+                    fields.add(self._get_node_text(name, code))
+            elif node.type == "shorthand_field_initializer":
+                fields.add(self._get_node_text(node, code))
+        return fields
+
+    def _get_receiver_field(self, node: Node, code: str) -> Optional[str]:
+        """Get `field` from `self.field` or `instance.field`"""
+        if node.type != "field_expression":
+            return None
+        value = node.child_by_field_name("value")
+        field = node.child_by_field_name("field")
+        if value is None or field is None or value.type not in ("self", "identifier"):
+            return None
+        return self._get_node_text(field, code)
@@ -0,0 +1,255 @@
+"""
+Detector for manual reentrancy guards left locked on some exit paths of Stylus Rust contracts
+"""
+import re
+from typing import Dict, List, Optional, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, CONSTRUCTOR_NAMES, DetectorDoc
+from stylus_analyzer.detectors.reentrancy_detector import GUARD_FIELD_PATTERN
+from stylus_analyzer.findings import Confidence, Severity
+
+# Values a guard holds when it is released, e.g. `false` or `U256::ZERO`
+UNLOCKED_VALUE_PATTERN = re.compile(r"^(?:false|0(?:_?[ui]\d+)?|(?:\w+::)*ZERO|(?:\w+::)*from\(0\)|Default::default\(\))$")
+
+# Values that can mark a guard as locked: `true`, a literal or a constant, possibly wrapped in `from(...)`
+LOCK_VALUE_PATTERN = re.compile(r"^(?:true|\d[\w]*|(?:\w+::)*[A-Z][A-Z0-9_]*|(?:\w+::)*from\((?:\d\w*|[A-Z][A-Z0-9_]*)\))$")
+
+# Nodes a statement only runs in when a branch is taken, bounding where a write dominates
+SCOPE_TYPES = ("block", "match_arm", "closure_expression")
+
+# A write of a guard field: the node writing it, the field and the normalized value
+GuardWrite = Tuple[Node, str, str]
+
+# A point where a function returns: its byte offset, its 1-based line and how it exits
+Exit = Tuple[int, int, str]
+
+
+class UnreleasedReentrancyGuardDetector(BaseDetector):
+    """
+    Detector for public methods that set a manual reentrancy guard and can
+    return without resetting it.
+
+    The lock is the first write of a guard-like field (`locked`, `status`,
+    ...) to `true`, a literal or a constant. Every exit that follows it, each
+    `return`, each `?` and each way the body can end, must come after a
+    write of another value to the same field that runs whenever the exit is
+    reached, either directly or through a helper method such as
+    `self.exit()`. The guard is checked by structure, so a release in one
+    branch does not cover an exit after the branch. An exit that skips the
+    release leaves the guard set, and every later call of the method fails
+    the guard check.
+    """
+
+    default_severity = Severity.HIGH
+    default_confidence = Confidence.MEDIUM
+    cwe = 667
+
+    documentation = DetectorDoc(
+        catches=(
+            "Public methods that set a manual reentrancy guard field and have a `return`, a `?` or an end of the "
+            "body that is reached without resetting it."
+        ),
+        rationale=(
+            "A guard left locked makes every later call fail the reentrancy check, permanently bricking the "
+            "method and anything that shares the guard."
+        ),
+        vulnerable_example="""
+            pub fn withdraw(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+                self.locked.set(true);
+                if amount > self.balances.get(msg::sender()) {
+                    return Ok(());
+                }
+                // ...
+                self.locked.set(false);
+                Ok(())
+            }
+        """,
+        fixed_example="""
+            pub fn withdraw(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+                self.locked.set(true);
+                if amount > self.balances.get(msg::sender()) {
+                    self.locked.set(false);
+                    return Ok(());
+                }
+                // ...
+                self.locked.set(false);
+                Ok(())
+            }
+        """,
+        references=["https://docs.openzeppelin.com/contracts/5.x/api/utils#ReentrancyGuard"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="unreleased_reentrancy_guard",
+            description="Detects reentrancy guards that some exit paths leave locked"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect exits after a guard is locked that don't release it"""
+        helper_writes = {self._get_function_name(node, code): self._collect_direct_writes(node, code)
+                         for node in self._iter_nodes(tree.root_node) if node.type == "function_item"}
+        for function_node in self._get_public_methods(tree.root_node, code):
+            function_name = self._get_function_name(function_node, code)
+            body = function_node.child_by_field_name("body")
+            if body is None or function_name in CONSTRUCTOR_NAMES:
+                continue
+            writes = self._collect_guard_writes(function_node, code, helper_writes)
+            if not writes:
+                continue
+            lock, field, value = writes[0]
+            if not LOCK_VALUE_PATTERN.match(value) or UNLOCKED_VALUE_PATTERN.match(value):
+                continue
+            releases = [node for node, written, released in writes[1:] if written == field and released != value]
+            unreleased = [exit_point for exit_point in self._collect_exits(lock, function_node, body, code)
+                          if not any(self._dominates(release, exit_point[0]) for release in releases)]
+            if unreleased:
+                self._add_issue(lock, field, unreleased, function_name, code, results)
+
+    def _collect_direct_writes(self, function_node: Node, code: str) -> List[GuardWrite]:
+        """Collect the writes of guard-like fields in a function, with the value they write"""
+        writes = []
+        for node in self._iter_nodes(function_node):
+            field = self._get_storage_write_field(node, code)
+            if not field or not GUARD_FIELD_PATTERN.search(field):
+                continue
+            value = self._get_written_value(node)
+            if value is not None:
+                writes.append((node, field, "".join(self._get_node_text(value, code).split())))
+        return writes
+
+    def _collect_guard_writes(self, function_node: Node, code: str,
+                              helper_writes: Dict[str, List[GuardWrite]]) -> List[GuardWrite]:
+        """Collect the guard writes of a method in source order, counting helper calls as their helper's last write"""
+        function_name = self._get_function_name(function_node, code)
+        writes = self._collect_direct_writes(function_node, code)
+        for node in self._iter_nodes(function_node):
+            helper = self._get_self_call(node, code)
+            if helper is None or helper == function_name:
+                continue
+            last_writes: Dict[str, str] = {}
+            for _, field, value in helper_writes.get(helper, []):
+                last_writes[field] = value
+            writes.extend((node, field, value) for field, value in last_writes.items())
+        return sorted(writes, key=lambda write: write[0].start_byte)
+
+    def _get_written_value(self, node: Node) -> Optional[Node]:
+        """Get the value a `.set(value)` call or an assignment writes"""
+        if node.type == "call_expression":
+            arguments = node.child_by_field_name("arguments")
+            return arguments.named_children[-1] if arguments is not None and arguments.named_children else None
+        return node.child_by_field_name("right")
+
+    def _get_self_call(self, node: Node, code: str) -> Optional[str]:
+        """Get the method a `self.method(...)` call goes to"""
+        if node.type != "call_expression":
+            return None
+        function = node.child_by_field_name("function")
+        if function is None or function.type != "field_expression":
+            return None
+        receiver = function.child_by_field_name("value")
+        method = function.child_by_field_name("field")
+        if receiver is None or receiver.type != "self" or method is None:
+            return None
+        return self._get_node_text(method, code)
+
+    def _collect_exits(self, lock: Node, function_node: Node, body: Node, code: str) -> List[Exit]:
+        """Collect the exits of a method that the lock runs before"""
+        scope = self._get_scope(lock)
+        exits = []
+        for node in self._iter_nodes(scope):
+            if node.start_byte < lock.end_byte or not self._belongs_to(node, function_node):
+                continue
+            if node.type == "return_expression":
+                exits.append((node.start_byte, self._get_line_for_node(node)[0], "returns"))
+            elif node.type == "try_expression":
+                exits.append((node.end_byte - 1, self._get_line_for_node(node)[1], "propagates an error with `?`"))
+        if scope is body:
+            exits.extend(exit_point for exit_point in self._collect_tail_exits(body) if exit_point[0] >= lock.end_byte)
+        return exits
+
+    def _collect_tail_exits(self, block: Node) -> List[Exit]:
+        """Collect the points where a block ends the function, following `if` and `match` in tail position"""
+        statements = [child for child in block.named_children if "comment" not in child.type]
+        last = statements[-1] if statements else None
+        if last is not None and last.type == "expression_statement" and \
+                not any(child.type == ";" for child in last.children) and last.named_children:
+            last = last.named_children[0]
+        closing = (block.end_byte - 1, block.end_point[0] + 1, "reaches the end of the function")
+        if last is None or last.type in ("expression_statement", "let_declaration", "empty_statement"):
+            is_return = last is not None and last.named_children and last.named_children[0].type == "return_expression"
+            return [] if is_return else [closing]
+        if last.type == "return_expression":
+            return []
+        if last.type == "block":
+            return self._collect_tail_exits(last)
+        if last.type == "if_expression":
+            exits = []
+            branch: Optional[Node] = last
+            while branch is not None and branch.type == "if_expression":
+                consequence = branch.child_by_field_name("consequence")
+                exits.extend(self._collect_tail_exits(consequence) if consequence is not None else [])
+                alternative = branch.child_by_field_name("alternative")
+                branch = alternative.named_children[-1] if alternative is not None and alternative.named_children \
+                    else None
+                if branch is None:
+                    exits.append(closing)
+            if branch is not None and branch.type == "block":
+                exits.extend(self._collect_tail_exits(branch))
+            return exits
+        if last.type == "match_expression":
+            exits = []
+            arms = last.child_by_field_name("body")
+            for arm in arms.named_children if arms is not None else []:
+                value = arm.child_by_field_name("value")
+                if value is None or value.type == "return_expression":
+                    continue
+                exits.extend(self._collect_tail_exits(value) if value.type == "block" else
+                             [(value.start_byte, value.start_point[0] + 1, "returns")])
+            return exits
+        return [(last.start_byte, last.start_point[0] + 1, "returns")]
+
+    def _get_scope(self, node: Node) -> Node:
+        """Get the innermost block or match arm a node always runs in once it is entered"""
+        current = node.parent
+        while current is not None and current.type not in SCOPE_TYPES:
+            current = current.parent
+        return current if current is not None else node
+
+    def _dominates(self, write: Node, position: int) -> bool:
+        """Check if a write runs on every path that reaches a byte offset after it"""
+        if write.end_byte > position:
+            return False
+        scope = self._get_scope(write)
+        return scope.start_byte <= position < scope.end_byte
+
+    def _belongs_to(self, node: Node, function_node: Node) -> bool:
+        """Check if a node returns from the function itself rather than from a closure or a nested function"""
+        current = node.parent
+        while current is not None and current != function_node:
+            if current.type in ("closure_expression", "function_item"):
+                return False
+            current = current.parent
+        return True
+
+    def _add_issue(self, lock: Node, field: str, exits: List[Exit], function_name: str, code: str,
+                   results) -> None:
+        """Add an unreleased guard issue for a method"""
+        lock_line, _ = self._get_line_for_node(lock)
+        _, first_line, how = exits[0]
+        others = sorted({line for _, line, _ in exits[1:]} - {first_line})
+        also = f" (also on line{'s' if len(others) > 1 else ''} {', '.join(map(str, others))})" if others else ""
+        results.add_issue(
+            issue_type="unreleased_reentrancy_guard",
+            severity="High",
+            description=f"Function '{function_name}' locks reentrancy guard '{field}' on line {lock_line}, but "
+                        f"{how} on line {first_line}{also} without releasing it. Once left locked, every later "
+                        "call fails the guard check.",
+            line_start=lock_line,
+            line_end=first_line,
+            column=self._get_column_for_node(lock, code),
+            code_snippet=self._get_node_text(lock, code),
+            recommendation=f"Reset '{field}' before every return and `?` after the lock, or move the guarded "
+                           "logic into a helper so the guard is released in one place."
+        )
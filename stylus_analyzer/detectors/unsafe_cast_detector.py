@@ -0,0 +1,202 @@
+"""
+Detector for narrowing integer conversions in Stylus Rust contracts
+"""
+import re
+from typing import Dict, Optional
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# Integer types and their widths in bits (usize is 32 bits on wasm32)
+PRIMITIVE_WIDTHS = {
+    "u8": 8, "u16": 16, "u32": 32, "u64": 64, "u128": 128, "usize": 32,
+    "i8": 8, "i16": 16, "i32": 32, "i64": 64, "i128": 128, "isize": 32,
+}
+WIDE_TYPE_PATTERN = re.compile(
+    r"\b(?:Storage)?(?:U|I|Uint<|Signed<|uint|int)(\d+)|\b(u8|u16|u32|u64|u128|usize|i8|i16|i32|i64|i128|isize)\b"
+)
+
+# Conversions that return a Result/Option and are commonly unwrapped
+FALLIBLE_CONVERSION = re.compile(r"^try_(as_\w+|into|from)$")
+
+
+class UnsafeCastTruncationDetector(BaseDetector):
+    """
+    Detector for conversions from wide integers to narrower Rust types.
+
+    `as` casts silently drop the high bits, while `.to::<T>()` and unwrapped
+    `try_*` conversions panic when the value doesn't fit.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.MEDIUM
+    cwe = 197
+    node_types = ("type_cast_expression", "call_expression")
+
+    documentation = DetectorDoc(
+        catches=(
+            "`as` casts from wide integers to narrower types, and `.to::<T>()` or unwrapped `try_into()` "
+            "conversions that can fail."
+        ),
+        rationale=(
+            "`as` silently drops the high bits, so a large `U256` amount can become a small one, and the "
+            "panicking conversions revert without a decodable error."
+        ),
+        vulnerable_example="""
+            pub fn set_limit(&mut self, limit: U256) {
+                let limit = limit.to::<u64>();
+                self.limit.set(U64::from(limit));
+            }
+        """,
+        fixed_example="""
+            pub fn set_limit(&mut self, limit: U256) -> Result<(), Vec<u8>> {
+                let limit: u64 = limit.try_into().map_err(|_| b"limit too large".to_vec())?;
+                self.limit.set(U64::from(limit));
+                Ok(())
+            }
+        """,
+        references=[
+            "https://doc.rust-lang.org/reference/expressions/operator-expr.html#type-cast-expressions",
+            "https://doc.rust-lang.org/std/convert/trait.TryFrom.html",
+        ],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="unsafe_cast_truncation",
+            description="Detects narrowing integer casts and panicking conversions"
+        )
+
+    def start(self, tree: Tree, code: str) -> None:
+        """Collect the storage fields of the file, whose types tell the width of `self.field` reads"""
+        self.storage_fields = self._collect_storage_fields(tree.root_node, code)
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if a cast narrows an integer or a conversion panics when the value doesn't fit"""
+        function_node = self._find_parent_function(node)
+        if function_node is None:
+            return
+        local_types = self._get_local_type_texts(function_node, code)
+        function_name = self._get_function_name(function_node, code)
+        if node.type == "type_cast_expression":
+            self._check_cast(node, code, local_types, self.storage_fields, function_name, results)
+        else:
+            self._check_conversion(node, code, local_types, self.storage_fields, function_name, results)
+
+    def _check_cast(self, node: Node, code: str, local_types: Dict[str, str],
+                    storage_fields: Dict[str, str], function_name: str, results) -> None:
+        """Report `as` casts to a narrower integer type"""
+        cast_type = node.child_by_field_name("type")
+        value = node.child_by_field_name("value")
+        if not cast_type or not value:
+            return
+        target = self._get_node_text(cast_type, code)
+        target_width = PRIMITIVE_WIDTHS.get(target)
+        source_width = self._infer_width(value, code, local_types, storage_fields)
+        if target_width is None or source_width is None or source_width <= target_width:
+            return
+        self._add_issue(node, code, results,
+                        f"Cast from a {source_width}-bit integer to '{target}' in function '{function_name}' silently truncates the high bits.")
+
+    def _check_conversion(self, node: Node, code: str, local_types: Dict[str, str],
+                          storage_fields: Dict[str, str], function_name: str, results) -> None:
+        """Report `.to::<T>()` and unwrapped `try_*` conversions"""
+        function = node.child_by_field_name("function")
+        if not function:
+            return
+
+        if function.type == "generic_function":
+            inner = function.child_by_field_name("function")
+            type_arguments = function.child_by_field_name("type_arguments")
+            if not inner or inner.type != "field_expression" or not type_arguments:
+                return
+            method = inner.child_by_field_name("field")
+            if not method or self._get_node_text(method, code) != "to":
+                return
+            target = self._get_node_text(type_arguments, code).strip("<>").strip()
+            target_width = PRIMITIVE_WIDTHS.get(target)
+            if target_width is None:
+                return
+            source_width = self._infer_width(inner.child_by_field_name("value"), code, local_types, storage_fields)
+            if source_width is not None and source_width <= target_width:
+                return
+            self._add_issue(node, code, results,
+                            f"Conversion to '{target}' with .to::<{target}>() in function '{function_name}' panics if the value doesn't fit.")
+            return
+
+        if function.type != "field_expression":
+            return
+        method = function.child_by_field_name("field")
+        receiver = function.child_by_field_name("value")
+        if not method or not receiver or self._get_node_text(method, code) not in ("unwrap", "expect"):
+            return
+        if receiver.type != "call_expression" or not self._is_fallible_conversion(receiver, code):
+            return
+        self._add_issue(node, code, results,
+                        f"Unwrapped integer conversion in function '{function_name}' panics if the value doesn't fit in the target type.")
+
+    def _is_fallible_conversion(self, node: Node, code: str) -> bool:
+        """Check if a call is a `try_as_usize()`, `try_into()` or `T::try_from()` style conversion"""
+        function = node.child_by_field_name("function")
+        if not function:
+            return False
+        if function.type == "field_expression":
+            name_node = function.child_by_field_name("field")
+        elif function.type == "scoped_identifier":
+            name_node = function.child_by_field_name("name")
+        else:
+            return False
+        return bool(name_node) and bool(FALLIBLE_CONVERSION.match(self._get_node_text(name_node, code)))
+
+    def _add_issue(self, node: Node, code: str, results, description: str) -> None:
+        """Add an unsafe cast issue"""
+        line_start, line_end = self._get_line_for_node(node)
+        truncating = node.type == "type_cast_expression"
+        results.add_issue(
+            issue_type="unsafe_cast_truncation",
+            severity="Medium",
+            description=description,
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=self._get_node_text(node, code),
+            recommendation="Use a checked conversion such as try_into() and handle the error instead of truncating."
+            if truncating else
+            "Handle the conversion error and return it to the caller instead of panicking."
+        )
+
+    def _get_type_width(self, type_text: str) -> Optional[int]:
+        """Get the width of the last integer type mentioned in a type"""
+        matches = WIDE_TYPE_PATTERN.findall(type_text)
+        if not matches:
+            return None
+        bits, primitive = matches[-1]
+        return int(bits) if bits else PRIMITIVE_WIDTHS[primitive]
+
+    def _infer_width(self, node: Optional[Node], code: str, local_types: Dict[str, str],
+                     storage_fields: Dict[str, str]) -> Optional[int]:
+        """Heuristically infer the width of an integer expression"""
+        if node is None:
+            return None
+        if node.type == "identifier":
+            type_text = local_types.get(self._get_node_text(node, code))
+            return self._get_type_width(type_text) if type_text else None
+        if node.type == "integer_literal":
+            suffix = re.search(r"(u8|u16|u32|u64|u128|usize|i8|i16|i32|i64|i128|isize)$", self._get_node_text(node, code))
+            return PRIMITIVE_WIDTHS[suffix.group(1)] if suffix else None
+        if node.type == "parenthesized_expression":
+            return self._infer_width(node.named_children[0] if node.named_children else None,
+                                     code, local_types, storage_fields)
+        if node.type == "type_cast_expression":
+            cast_type = node.child_by_field_name("type")
+            return self._get_type_width(self._get_node_text(cast_type, code)) if cast_type else None
+        if node.type == "call_expression":
+            function = node.child_by_field_name("function")
+            if function and function.type == "scoped_identifier":
+                path = function.child_by_field_name("path")
+                return self._get_type_width(self._get_node_text(path, code)) if path else None
+        field = self._get_self_field(node, code)
+        if field and field in storage_fields:
+            return self._get_type_width(storage_fields[field])
+        return None
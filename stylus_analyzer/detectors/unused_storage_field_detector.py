@@ -0,0 +1,135 @@
+"""
+Detector for storage fields that no method reads or writes in Stylus Rust contracts
+"""
+import re
+from typing import Dict, List, Set
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.constant_suggestion_detector import ConstantImmutableSuggestionDetector, MUTABLE_ACCESSORS
+from stylus_analyzer.detectors.detector_base import DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+from stylus_analyzer.storage_layout import StorageField, StorageStruct
+
+# `#[borrow]` on a field, whose storage the inherited type accesses
+BORROW_PATTERN = re.compile(r"#\s*\[\s*borrow\b")
+
+
+class UnusedStorageFieldDetector(ConstantImmutableSuggestionDetector):
+    """
+    Detector for fields of a storage struct that the methods of its impl
+    blocks never reference, never write or never read.
+
+    All `self.<field>` accesses of every impl block of the struct are
+    collected at once, reusing the write and read counting of the constant
+    suggestion detector. A field that is read but never written always holds
+    its zero default and is likely a bug, while a field that is only written
+    may just be exposed to off-chain readers. Structs without an impl block in
+    the file and `#[borrow]` fields, which inherited types access, are skipped.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.MEDIUM
+    cwe = 1164
+
+    documentation = DetectorDoc(
+        catches=(
+            "Fields of storage structs that no method of the struct references, that are read but never "
+            "written, or that are written but never read."
+        ),
+        rationale=(
+            "An unreferenced field wastes a storage slot and usually marks leftover or unfinished code. A field "
+            "that is never written always reads as zero, the zero address or `false`."
+        ),
+        vulnerable_example="""
+            #[storage]
+            pub struct Vault {
+                balances: StorageMap<Address, StorageU256>,
+                paused: StorageBool,
+            }
+        """,
+        fixed_example="""
+            #[storage]
+            pub struct Vault {
+                balances: StorageMap<Address, StorageU256>,
+            }
+        """,
+        references=["https://docs.arbitrum.io/stylus/reference/rust-sdk-guide#storage"],
+    )
+
+    def __init__(self):
+        super().__init__()
+        self.name = "unused_storage_field"
+        self.description = "Detects storage fields that are never referenced, never written or never read"
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect unused fields of each storage struct"""
+        impls: Dict[str, List[Node]] = {}
+        for node in self._iter_nodes(tree.root_node):
+            impl_type = node.child_by_field_name("type") if node.type == "impl_item" else None
+            if impl_type is not None:
+                impls.setdefault(self._get_node_text(impl_type, code).split("<")[0].strip(), []).append(node)
+
+        for struct in self._get_storage_layout(tree.root_node, code).structs:
+            impl_nodes = impls.get(struct.name)
+            if not impl_nodes:
+                continue
+            writes: Set[str] = set()
+            reads: Set[str] = set()
+            for impl_node in impl_nodes:
+                writes.update(self._count_writes(impl_node, code))
+                reads.update(self._count_reads(impl_node, code))
+                reads.update(self._count_mutable_borrows(impl_node, code))
+
+            fields = [storage_field for storage_field in struct.fields if not self._is_borrowed(storage_field, code)]
+            unreferenced = [f.name for f in fields if f.name not in writes and f.name not in reads]
+            never_written = [f.name for f in fields if f.name in reads and f.name not in writes]
+            never_read = [f.name for f in fields if f.name in writes and f.name not in reads]
+
+            if unreferenced:
+                self._add_issue(struct, unreferenced, "are never read or written by any of its methods",
+                                "Remove these fields, or finish the code that was meant to use them.", code, results)
+            if never_written:
+                self._add_issue(struct, never_written,
+                                "are read but never written, so they always hold their zero default, "
+                                "which is likely a bug",
+                                "Initialize these fields in the constructor or a setter, or replace them with "
+                                "constants.", code, results)
+            if never_read:
+                self._add_issue(struct, never_read,
+                                "are written but never read by the contract. This may be fine if the values "
+                                "are only meant for off-chain readers",
+                                "Make sure these fields are meant to be write-only, or add a getter or remove them.",
+                                code, results)
+
+    def _count_mutable_borrows(self, node: Node, code: str) -> Set[str]:
+        """Collect the fields handed out through `setter`/`get_mut`, which may be read through the borrow"""
+        return {self._get_node_text(child.child_by_field_name("field"), code)
+                for child in self._iter_nodes(node) if self._get_accessed_method(child, code) in MUTABLE_ACCESSORS}
+
+    def _is_borrowed(self, storage_field: StorageField, code: str) -> bool:
+        """Check if a field is marked `#[borrow]`, as a Rust attribute or inside `sol_storage!`"""
+        if BORROW_PATTERN.search(storage_field.type):
+            return True
+        sibling = storage_field.node.prev_named_sibling
+        while sibling is not None and sibling.type == "attribute_item":
+            if BORROW_PATTERN.search(self._get_node_text(sibling, code)):
+                return True
+            sibling = sibling.prev_named_sibling
+        return False
+
+    def _add_issue(self, struct: StorageStruct, field_names: List[str], problem: str, recommendation: str,
+                   code: str, results) -> None:
+        """Add an unused storage field issue listing the fields of a struct"""
+        line_start, _ = self._get_line_for_node(struct.node)
+        _, line_end = self._get_line_for_node(struct.body)
+        listing = ", ".join(f"'{name}'" for name in field_names)
+        results.add_issue(
+            issue_type="unused_storage_field",
+            severity="Low",
+            description=f"Storage struct '{struct.name}' has fields that {problem}: {listing}.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(struct.node, code),
+            code_snippet=self._get_source_text(code, struct.node.start_byte, struct.body.start_byte).strip(),
+            recommendation=recommendation
+        )
@@ -0,0 +1,193 @@
+"""
+Detector for ABI decoding of untrusted bytes without a length check in Stylus Rust contracts
+"""
+import re
+from typing import List, Optional, Set
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+from stylus_analyzer.sol_parser import SolFunction
+from stylus_analyzer.taint import EXTERNAL_DATA_SOURCE_PATTERN, TaintAnalysis
+
+# Decoding entry points of alloy's `SolType` and `SolValue`
+DECODE_FUNCTIONS = ("abi_decode", "abi_decode_params", "abi_decode_sequence")
+
+# Parameter types holding raw calldata bytes, e.g. `Bytes`, `Vec<u8>` or `&[u8]`
+BYTES_TYPE_PATTERN = re.compile(r"\bBytes\b|\bVec\s*<\s*u8\s*>|\[\s*u8\s*\]")
+
+# Methods checking how many bytes there are
+LENGTH_METHODS = ("len", "is_empty")
+
+# Solidity bytes bound by a parameter list or a low-level call, e.g. `bytes memory returnData`
+SOL_BYTES_BINDING = re.compile(r"\bbytes\s+(?:memory|calldata)\s+(\w+)")
+
+# Solidity low-level calls returning bytes
+SOL_CALL_PATTERN = re.compile(r"\.\s*(?:call|staticcall|delegatecall)\b")
+
+# A Solidity decode of a local, e.g. `abi.decode(returnData, (bool))`
+SOL_DECODE_PATTERN = re.compile(r"\babi\s*\.\s*decode\s*\(\s*(\w+)")
+
+
+class UnvalidatedExternalDataDecodeDetector(BaseDetector):
+    """
+    Detector for `abi_decode` of call return data or calldata that is not
+    checked for its length first.
+
+    Untrusted bytes come from the taint framework: the results of `call`,
+    `static_call` and `delegate_call`, calldata read with `contract::args`
+    or `msg::data`, and the byte parameters of public methods, followed
+    through the locals they flow into. A `.len()` or `.is_empty()` check of
+    the decoded local anywhere before the decode, including the left side
+    of `||`/`&&`, counts as a guard. In `sol!` functions, `abi.decode` of
+    call return data or `bytes` parameters needs an earlier `.length` check,
+    as in `returnData.length == 0 || abi.decode(returnData, (bool))`.
+    """
+
+    default_severity = Severity.LOW
+    default_confidence = Confidence.MEDIUM
+    cwe = 20
+
+    documentation = DetectorDoc(
+        catches=(
+            "`abi_decode` of bytes returned by a low-level call, or received as calldata, without checking "
+            "their length or emptiness first."
+        ),
+        rationale=(
+            "Tokens that return nothing and callers that send short payloads hand the decoder empty or truncated "
+            "bytes. The decode then fails and reverts the whole transaction, or the contract ends up treating a "
+            "silent success as an error."
+        ),
+        vulnerable_example="""
+            let return_data = static_call(Call::new(), token, &calldata)?;
+            let ok = <SOLBool as SolType>::abi_decode(&return_data, true)?;
+        """,
+        fixed_example="""
+            let return_data = static_call(Call::new(), token, &calldata)?;
+            if return_data.is_empty() {
+                return Err(b"empty return data".to_vec());
+            }
+            let ok = <SOLBool as SolType>::abi_decode(&return_data, true)?;
+        """,
+        references=["https://github.com/OpenZeppelin/openzeppelin-contracts/blob/master/contracts/token/ERC20/utils/SafeERC20.sol"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="unvalidated_external_data_decode",
+            description="Detects ABI decoding of call return data or calldata without a length check"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect decodes of untrusted bytes in Rust functions and sol! functions"""
+        for function_node in self._iter_nodes(tree.root_node):
+            if function_node.type == "function_item":
+                self._check_function(function_node, code, results)
+
+        for function in self._parse_sol_functions(tree.root_node, code):
+            self._check_sol_function(function, code, results)
+
+    def _check_function(self, function_node: Node, code: str, results) -> None:
+        """Check the decodes of a Rust function for untrusted bytes without an earlier length check"""
+        decodes = [node for node in self._iter_nodes(function_node) if self._get_decoded_bytes(node, code)]
+        if not decodes:
+            return
+        sources = self._collect_calldata_parameters(function_node, code)
+        taint = TaintAnalysis(function_node, code, sources=sources, source_pattern=EXTERNAL_DATA_SOURCE_PATTERN)
+        checks = [node for node in self._iter_nodes(function_node) if self._get_length_checked(node, code)]
+        for decode in decodes:
+            data = self._get_decoded_bytes(decode, code)
+            if not taint.is_tainted(data):
+                continue
+            names = {self._get_node_text(node, code) for node in self._iter_nodes(data)
+                     if node.type == "identifier" and taint.is_tainted(self._get_node_text(node, code))}
+            if any(check.start_byte < decode.start_byte and self._get_length_checked(check, code) in names
+                   for check in checks):
+                continue
+            self._add_issue(decode, data, names, function_node, code, results)
+
+    def _collect_calldata_parameters(self, function_node: Node, code: str) -> Set[str]:
+        """Get the byte parameters of a public method, which carry calldata chosen by the caller"""
+        if not self._is_public_method(function_node, code):
+            return set()
+        return {name for name, type_text in self._collect_local_type_texts(function_node, code).items()
+                if name in self._collect_parameter_names(function_node, code) and BYTES_TYPE_PATTERN.search(type_text)}
+
+    def _get_decoded_bytes(self, node: Node, code: str) -> Optional[Node]:
+        """Get the bytes argument of a decode call such as `<T as SolType>::abi_decode(&data, true)`"""
+        if node.type != "call_expression":
+            return None
+        function = node.child_by_field_name("function")
+        arguments = node.child_by_field_name("arguments")
+        if function is None or arguments is None:
+            return None
+        name = function.child_by_field_name("name") if function.type == "scoped_identifier" else None
+        if name is None or self._get_node_text(name, code) not in DECODE_FUNCTIONS or not arguments.named_children:
+            return None
+        return arguments.named_children[0]
+
+    def _get_length_checked(self, node: Node, code: str) -> Optional[str]:
+        """Get the local a `.len()` or `.is_empty()` call checks, e.g. `data` for `data.len()`"""
+        if node.type != "call_expression":
+            return None
+        function = node.child_by_field_name("function")
+        if function is None or function.type != "field_expression":
+            return None
+        field = function.child_by_field_name("field")
+        receiver = function.child_by_field_name("value")
+        if field is None or receiver is None or self._get_node_text(field, code) not in LENGTH_METHODS:
+            return None
+        return self._get_node_text(receiver, code) if receiver.type == "identifier" else None
+
+    def _check_sol_function(self, function: SolFunction, code: str, results) -> None:
+        """Check a sol! function for decodes of call return data or bytes parameters without a length check"""
+        untrusted = set(SOL_BYTES_BINDING.findall(self._get_node_text(function.params, code)))
+        checked: Set[str] = set()
+        for statement in function.statements:
+            if statement.target is not None and statement.value is not None and \
+                    SOL_CALL_PATTERN.search(statement.value):
+                untrusted |= set(SOL_BYTES_BINDING.findall(statement.target))
+            for match in SOL_DECODE_PATTERN.finditer(statement.text):
+                name = match.group(1)
+                before = statement.text[:match.start()]
+                if name in untrusted and name not in checked and \
+                        not re.search(rf"\b{re.escape(name)}\s*\.\s*length\b", before):
+                    self._add_sol_issue(statement.tokens, name, function.name, code, results)
+            checked |= {name for name in untrusted
+                        if re.search(rf"\b{re.escape(name)}\s*\.\s*length\b", statement.text)}
+
+    def _add_issue(self, decode: Node, data: Node, names: Set[str], function_node: Node, code: str,
+                   results) -> None:
+        """Add an issue at a Rust decode of unchecked bytes"""
+        subject = ", ".join(f"'{name}'" for name in sorted(names)) or f"'{self._get_node_text(data, code)}'"
+        line_start, line_end = self._get_line_for_node(decode)
+        function_name = self._get_function_name(function_node, code)
+        results.add_issue(
+            issue_type="unvalidated_external_data_decode",
+            severity="Low",
+            description=f"Function '{function_name}' decodes {subject}, which holds call return data or calldata, "
+                        "without checking its length first. Empty or truncated bytes make the decode fail.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(decode, code),
+            code_snippet=self._get_node_text(decode, code),
+            recommendation="Check `.is_empty()` or `.len()` of the bytes before decoding them, and handle empty "
+                           "return data from tokens that don't return a value."
+        )
+
+    def _add_sol_issue(self, tokens: List[Node], name: str, function_name: str, code: str, results) -> None:
+        """Add an issue at a sol! statement decoding unchecked bytes"""
+        line_start, _ = self._get_line_for_node(tokens[0])
+        _, line_end = self._get_line_for_node(tokens[-1])
+        results.add_issue(
+            issue_type="unvalidated_external_data_decode",
+            severity="Low",
+            description=f"In sol! function '{function_name}', '{name}' is decoded with abi.decode without checking "
+                        "its length first. Empty or truncated bytes make the decode revert.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(tokens[0], code),
+            code_snippet=self._get_source_text(code, tokens[0].start_byte, tokens[-1].end_byte),
+            recommendation=f"Check `{name}.length` before decoding, e.g. "
+                           f"`{name}.length == 0 || abi.decode({name}, (bool))`."
+        )
@@ -1,9 +1,19 @@
 """
 Detector for potentially unsafe unwrap() calls in Stylus Rust contracts
 """
+from typing import Optional
 from tree_sitter import Node, Tree
 
-from stylus_analyzer.detectors.detector_base import BaseDetector
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Fix, Severity
+
+# Return types a `?` can propagate an error or a missing value out of
+PROPAGATING_RETURN_TYPES = ("Result", "Option")
+
+# Methods known to return an Option, which `?` can't propagate out of a function returning a Result
+OPTION_METHODS = ("first", "last", "first_mut", "last_mut", "get", "get_mut", "pop", "next", "find", "position",
+                  "max", "min", "checked_add", "checked_sub", "checked_mul", "checked_div", "checked_rem",
+                  "checked_pow", "checked_neg", "checked_shl", "checked_shr")
 
 
 class UnwrapDetector(BaseDetector):
@@ -15,60 +25,103 @@ class UnwrapDetector(BaseDetector):
     after a panic.
     """
     
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.HIGH
+    cwe = 248
+    node_types = ("call_expression",)
+
+    documentation = DetectorDoc(
+        catches="Calls to `.unwrap()` on `Option` and `Result` values.",
+        rationale=(
+            "An unwrap on `None` or `Err` panics, which reverts the whole transaction without a decodable error. "
+            "Callers can't tell what went wrong, and a panic in a commonly used path can make the contract "
+            "unusable."
+        ),
+        vulnerable_example="""
+            pub fn price(&self, id: U256) -> Result<U256, Vec<u8>> {
+                let price = self.prices.get(id).checked_mul(self.rate.get()).unwrap();
+                Ok(price)
+            }
+        """,
+        fixed_example="""
+            pub fn price(&self, id: U256) -> Result<U256, Vec<u8>> {
+                let price = self.prices.get(id).checked_mul(self.rate.get()).ok_or(b"overflow".to_vec())?;
+                Ok(price)
+            }
+        """,
+        references=["https://doc.rust-lang.org/book/ch09-02-recoverable-errors-with-result.html"],
+    )
+
     def __init__(self):
         super().__init__(
             name="unsafe_unwrap",
             description="Detects potentially unsafe .unwrap() calls that can panic at runtime"
         )
         
-    def detect(self, tree: Tree, code: str, results) -> None:
-        """Detect unwrap calls in the contract"""
+    def start(self, tree: Tree, code: str) -> None:
+        """Forget the unwrap calls found in the previous file"""
         # Track found unwrap calls by location to avoid duplicates
         self.found_locations = set()
-        self._find_unwrap_calls(tree.root_node, code, results)
-    
-    def _find_unwrap_calls(self, node: Node, code: str, results) -> None:
-        """Recursively search for unwrap calls in the contract"""
-        # Check for call expressions (method calls)
-        if node.type == "call_expression":
-            # Check if the method being called is unwrap
-            method_name = self._get_method_name(node, code)
-            if method_name == "unwrap":
-                # Get the location to avoid duplicates
-                location = (node.start_point[0], node.end_point[0])
-                
-                # Check if we've already reported this unwrap call
-                if location not in self.found_locations:
-                    self.found_locations.add(location)
-                    
-                    line_start, line_end = self._get_line_for_node(node)
-                    function_node = self._find_parent_function(node)
-                    function_name = self._get_function_name(function_node, code) if function_node else "unknown"
-                    
-                    results.add_issue(
-                        issue_type="unsafe_unwrap",
-                        severity="Medium",
-                        description=f"Potentially unsafe call to .unwrap() in function '{function_name}'. This can cause runtime panics if the value is None/Err.",
-                        line_start=line_start,
-                        line_end=line_end,
-                        code_snippet=self._get_node_text(node, code),
-                        recommendation="Use pattern matching, if let, or explicit error handling (like ? operator) instead of unwrap()."
-                    )
-        
-        # Process all children
-        for child in node.children:
-            self._find_unwrap_calls(child, code, results)
-    
-    def _get_method_name(self, node: Node, code: str) -> str:
-        """Extract the method name from a call expression"""
-        for child in node.children:
-            # Field expressions like obj.method
-            if child.type == "field_expression":
-                for field_child in child.children:
-                    if field_child.type == "field_identifier":
-                        return self._get_node_text(field_child, code)
-        return ""
-    
+
+    def visit_node(self, node: Node, code: str, results) -> None:
+        """Check if a call expression is an unwrap call"""
+        # Check if the method being called is unwrap
+        method_name = self._get_method_name(node, code)
+        if method_name != "unwrap":
+            return
+        # Get the location to avoid duplicates
+        location = (node.start_point, node.end_point)
+
+        # Check if we've already reported this unwrap call
+        if location in self.found_locations:
+            return
+        self.found_locations.add(location)
+
+        line_start, line_end = self._get_line_for_node(node)
+        function_node = self._find_parent_function(node)
+        function_name = self._get_function_name(function_node, code) if function_node else "unknown"
+
+        results.add_issue(
+            issue_type="unsafe_unwrap",
+            severity="Medium",
+            description=f"Potentially unsafe call to .unwrap() in function '{function_name}'. This can cause runtime panics if the value is None/Err.",
+            line_start=line_start,
+            line_end=line_end,
+            column=self._get_column_for_node(node, code),
+            code_snippet=self._get_node_text(node, code),
+            recommendation="Use pattern matching, if let, or explicit error handling (like ? operator) instead of unwrap().",
+            fix=self._get_fix(node, function_node, code)
+        )
+
+    def _get_fix(self, node: Node, function_node: Node, code: str) -> Optional[Fix]:
+        """
+        Get a fix replacing `.unwrap()` with `?`, when the enclosing function returns a Result or an Option
+
+        Unwraps inside closures are left alone, since `?` would return from
+        the closure, as are unwraps of known Option methods such as `first()`
+        in functions returning a Result.
+        """
+        if not function_node or function_node.type != "function_item":
+            return None
+        return_type = function_node.child_by_field_name("return_type")
+        return_kind = self._get_node_text(return_type, code).split("<")[0].strip() if return_type else None
+        if return_kind not in PROPAGATING_RETURN_TYPES:
+            return None
+        parent = node.parent
+        while parent and parent.start_byte != function_node.start_byte:
+            if parent.type == "closure_expression":
+                return None
+            parent = parent.parent
+        function = node.child_by_field_name("function")
+        receiver = function.child_by_field_name("value") if function else None
+        if not receiver:
+            return None
+        if return_kind == "Result" and receiver.type == "call_expression" and \
+                self._get_method_name(receiver, code) in OPTION_METHODS:
+            return None
+        return Fix(description="Replace `.unwrap()` with `?`",
+                   edits=[self._text_edit(code, receiver.end_point, node.end_point, "?")], safe=True)
+
     def _find_parent_function(self, node: Node) -> Node:
         """Find the parent function containing this node"""
         parent = node.parent
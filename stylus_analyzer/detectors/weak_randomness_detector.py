@@ -0,0 +1,194 @@
+"""
+Detector for randomness derived from predictable chain data in Stylus Rust contracts
+"""
+import re
+from typing import Dict, List, Optional, Set
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc
+from stylus_analyzer.findings import Confidence, Severity
+
+# On-chain values known to (or chosen by) the caller and the sequencer
+SOURCE_PATTERN = re.compile(
+    r"\bblock\s*::\s*(timestamp|number|coinbase|basefee|gas_limit)\s*\(|"
+    r"\bblock_(timestamp|number|coinbase|basefee)\s*\(|\bmsg\s*::\s*sender\s*\(|\bmsg_sender\s*\("
+)
+SOL_SOURCE_PATTERN = re.compile(r"\bblock\s*\.\s*(timestamp|number|difficulty|prevrandao|coinbase)\b|\bmsg\s*\.\s*sender\b|\bnow\b")
+
+# Operations turning a source into something that looks random
+MIXING_PATTERN = re.compile(r"keccak|%")
+
+# Storage accessors whose key selects an entry
+SELECTING_METHODS = ("get", "get_mut", "setter", "insert")
+
+# Solidity types starting a local variable declaration
+SOL_TYPE_PATTERN = re.compile(r"^(u?int\d*|bytes\d*|address|bool)$")
+
+
+class WeakRandomnessDetector(BaseDetector):
+    """
+    Detector for pseudo-random values computed from block data or the caller.
+
+    Values like the timestamp, block number or `msg::sender()` are known before
+    the transaction executes, so hashing them or reducing them modulo `n` does
+    not make the outcome unpredictable.
+    """
+
+    default_severity = Severity.HIGH
+    default_confidence = Confidence.MEDIUM
+    cwe = 330
+
+    documentation = DetectorDoc(
+        catches=(
+            "Values derived from `block::timestamp()`, `block::number()`, `msg::sender()` and similar inputs, "
+            "hashed or reduced with `%`, then used to branch or pick an entry."
+        ),
+        rationale=(
+            "These inputs are known before the transaction executes, so anyone can predict the outcome, and a "
+            "sequencer or a contract calling in can choose when to act."
+        ),
+        vulnerable_example="""
+            let seed = keccak(block::timestamp().to_be_bytes());
+            let index = U256::from_be_bytes(seed.0) % U256::from(self.players.len());
+            let winner = self.players.get(index).unwrap_or_default();
+        """,
+        fixed_example="""
+            // Reveal a seed committed to before the draw, or use an external VRF
+            let seed = self.reveal_seed(secret)?;
+            let index = U256::from_be_bytes(seed.0) % U256::from(self.players.len());
+            let winner = self.players.get(index).unwrap_or_default();
+        """,
+        references=["https://swcregistry.io/docs/SWC-120"],
+    )
+
+    def __init__(self):
+        super().__init__(
+            name="weak_randomness",
+            description="Detects randomness derived from predictable block or caller data"
+        )
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect decisions made on predictable pseudo-random values"""
+        for function_node in self._iter_nodes(tree.root_node):
+            if function_node.type == "function_item":
+                self._check_function(function_node, code, results)
+
+        for function in self._get_sol_functions(tree.root_node, code):
+            self._check_sol_function(function, code, results)
+
+    def _check_function(self, function_node: Node, code: str, results) -> None:
+        """Track predictable values through a function into its decision points"""
+        body = function_node.child_by_field_name("body")
+        if not body:
+            return
+        function_name = self._get_function_name(function_node, code)
+
+        tainted: Set[str] = set()
+        random: Set[str] = set()
+        for node in self._iter_nodes(body):
+            if node.type == "let_declaration":
+                self._track_binding(node, code, tainted, random)
+                continue
+            decision = self._get_decision_expression(node, code)
+            if decision is None:
+                continue
+            if self._is_random(self._get_node_text(decision, code), tainted, random, SOURCE_PATTERN):
+                line_start, line_end = self._get_line_for_node(decision)
+                self._add_issue(line_start, line_end, self._get_column_for_node(decision, code),
+                                self._get_node_text(decision, code),
+                                f"function '{function_name}'", results)
+
+    def _track_binding(self, node: Node, code: str, tainted: Set[str], random: Set[str]) -> None:
+        """Propagate taint from a let binding's value to the names it binds"""
+        pattern = node.child_by_field_name("pattern")
+        value = node.child_by_field_name("value")
+        if not pattern or not value:
+            return
+        if pattern.type == "mut_pattern" and pattern.named_children:
+            pattern = pattern.named_children[-1]
+        if pattern.type != "identifier":
+            return
+        name = self._get_node_text(pattern, code)
+        self._bind(name, self._get_node_text(value, code), tainted, random, SOURCE_PATTERN)
+
+    def _bind(self, name: str, value: str, tainted: Set[str], random: Set[str], source: re.Pattern) -> None:
+        """Record whether a local holds a predictable or pseudo-random value"""
+        if self._is_random(value, tainted, random, source):
+            random.add(name)
+        if source.search(value) or self._mentions_any(value, tainted | random):
+            tainted.add(name)
+
+    def _mentions_any(self, text: str, names: Set[str]) -> bool:
+        """Check if source text refers to any of the names"""
+        return any(re.search(rf"\b{re.escape(name)}\b", text) for name in names)
+
+    def _is_random(self, text: str, tainted: Set[str], random: Set[str], source: re.Pattern) -> bool:
+        """Check if an expression is a pseudo-random value derived from a predictable source"""
+        if self._mentions_any(text, random):
+            return True
+        return bool(MIXING_PATTERN.search(text)) and (bool(source.search(text)) or self._mentions_any(text, tainted))
+
+    def _get_decision_expression(self, node: Node, code: str) -> Optional[Node]:
+        """Get the expression a node branches on or selects an entry with"""
+        if node.type in ("if_expression", "while_expression"):
+            return node.child_by_field_name("condition")
+        if node.type == "match_expression":
+            return node.child_by_field_name("value")
+        if node.type == "macro_invocation" and self._get_guard_text(node, code) is not None:
+            return node
+        if node.type == "index_expression" and len(node.named_children) > 1:
+            return node.named_children[1]
+        if node.type == "call_expression":
+            function = node.child_by_field_name("function")
+            arguments = node.child_by_field_name("arguments")
+            if function and arguments and function.type == "field_expression":
+                method = function.child_by_field_name("field")
+                if method and self._get_node_text(method, code) in SELECTING_METHODS:
+                    return arguments
+        return None
+
+    def _check_sol_function(self, function: Dict, code: str, results) -> None:
+        """Track predictable values through the statements of a sol! function"""
+        tainted: Set[str] = set()
+        random: Set[str] = set()
+        for statement in self._split_sol_statements(function["body"]):
+            text = self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte)
+            first = self._get_node_text(statement[0], code)
+            name = self._get_sol_declared_name(statement, code)
+            if name:
+                self._bind(name, text.split("=", 1)[1], tainted, random, SOL_SOURCE_PATTERN)
+                continue
+            decisions = [token for token in statement if self._is_token_tree(token, "[")]
+            if first in ("require", "if"):
+                decisions.append(None)
+            for decision in decisions:
+                decision_text = text if decision is None else self._get_node_text(decision, code)
+                if self._is_random(decision_text, tainted, random, SOL_SOURCE_PATTERN):
+                    line_start, _ = self._get_line_for_node(statement[0])
+                    _, line_end = self._get_line_for_node(statement[-1])
+                    self._add_issue(line_start, line_end, self._get_column_for_node(statement[0], code), text,
+                                    f"sol! function '{function['name']}'", results)
+                    break
+
+    def _get_sol_declared_name(self, statement: List[Node], code: str) -> Optional[str]:
+        """Get the local declared by a sol! statement like `uint256 x = ...`"""
+        if len(statement) < 3 or statement[0].type not in ("identifier", "primitive_type"):
+            return None
+        if not SOL_TYPE_PATTERN.match(self._get_node_text(statement[0], code)):
+            return None
+        if statement[1].type == "identifier" and statement[2].type == "=":
+            return self._get_node_text(statement[1], code)
+        return None
+
+    def _add_issue(self, line_start: int, line_end: int, column: int, snippet: str, location: str, results) -> None:
+        """Add a weak randomness issue"""
+        results.add_issue(
+            issue_type="weak_randomness",
+            severity="High",
+            description=f"Decision in {location} is based on a pseudo-random value derived from block data or the caller. The outcome can be predicted or influenced before the transaction executes.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation="Use a commit-reveal scheme or an external verifiable randomness source (VRF) instead of on-chain values."
+        )
@@ -0,0 +1,236 @@
+"""
+Detector for address parameters used without a zero-address check in Stylus Rust contracts
+"""
+import re
+from typing import Dict, Iterable, List, Optional, Set, Tuple
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.detectors.detector_base import BaseDetector, DetectorDoc, STORAGE_WRITE_METHODS
+from stylus_analyzer.findings import Confidence, Fix, Severity
+from stylus_analyzer.sol_parser import SolFunction
+
+# Comparisons against the zero address in Rust and Solidity
+ZERO_ADDRESS_PATTERN = re.compile(r"\bZERO\b|is_zero\s*\(|Address::default\s*\(|\baddress\s*\(\s*0\s*\)")
+
+# `Result<T, Vec<u8>>`, the return type a revert message can be returned through
+VEC_U8_RESULT_PATTERN = re.compile(r"^Result\s*<.*,\s*Vec\s*<\s*u8\s*>\s*>$", re.DOTALL)
+
+# Calls whose arguments include a recipient of value
+TRANSFER_FUNCTIONS = ("transfer_eth", "call", "transfer", "transfer_from", "transferFrom", "safe_transfer", "send")
+
+
+class ZeroAddressCheckDetector(BaseDetector):
+    """
+    Detector for `Address` parameters that are stored or receive value
+    without being checked against `Address::ZERO`.
+
+    Setting an owner to the zero address bricks the contract, and transfers or
+    credits to it burn the funds.
+    """
+
+    default_severity = Severity.MEDIUM
+    default_confidence = Confidence.MEDIUM
+    cwe = 20
+
+    documentation = DetectorDoc(
+        catches=(
+            "`Address` parameters of public methods that are stored, written into a mapping or used as a transfer "
+            "recipient without being compared to `Address::ZERO`."
+        ),
+        rationale=(
+            "Storing the zero address as an owner bricks the contract, and sending or crediting funds to it burns "
+            "them."
+        ),
+        vulnerable_example="""
+            pub fn set_owner(&mut self, owner: Address) -> Result<(), Vec<u8>> {
+                self.owner.set(owner);
+                Ok(())
+            }
+        """,
+        fixed_example="""
+            pub fn set_owner(&mut self, owner: Address) -> Result<(), Vec<u8>> {
+                if owner == Address::ZERO {
+                    return Err(b"zero address".to_vec());
+                }
+                self.owner.set(owner);
+                Ok(())
+            }
+        """,
+        references=["https://github.com/crytic/slither/wiki/Detector-Documentation"],
+    )
+
+    def __init__(self, ignored_parameters: Optional[Iterable[str]] = None):
+        """
+        Args:
+            ignored_parameters: Parameter names that are never reported
+        """
+        super().__init__(
+            name="zero_address_check",
+            description="Detects address parameters that are stored or used as recipients without a zero-address check"
+        )
+        self.ignored_parameters = set(ignored_parameters or ())
+
+    def detect(self, tree: Tree, code: str, results) -> None:
+        """Detect unvalidated address parameters"""
+        for function_node in self._get_public_methods(tree.root_node, code):
+            self._check_function(function_node, code, results)
+
+        for function in self._parse_sol_functions(tree.root_node, code):
+            self._check_sol_function(function, code, results)
+
+    def _check_function(self, function_node: Node, code: str, results) -> None:
+        """Check the address parameters of a public Rust method"""
+        addresses = self._collect_address_parameters(function_node, code)
+        body = function_node.child_by_field_name("body")
+        if not addresses or not body:
+            return
+        function_name = self._get_function_name(function_node, code)
+        setter_aliases = self._collect_setter_aliases(function_node, code)
+        return_type = function_node.child_by_field_name("return_type")
+        can_return_error = bool(return_type) and \
+            VEC_U8_RESULT_PATTERN.match(self._get_node_text(return_type, code)) is not None
+
+        guards = self._get_guards(function_node, code)
+        validated: Set[str] = set()
+        reported: Set[str] = set()
+        for node in self._iter_nodes(body):
+            guard = next((guard for guard in guards if guard.node == node), None)
+            if guard is not None and ZERO_ADDRESS_PATTERN.search(guard.condition):
+                validated.update(name for name in addresses if self._mentions(guard.condition, name))
+                continue
+            for name, usage, severity in self._get_sinks(node, code, addresses, setter_aliases):
+                if name in validated or name in reported:
+                    continue
+                reported.add(name)
+                line_start, line_end = self._get_line_for_node(node)
+                fix = None
+                if can_return_error and body.named_children:
+                    check = f'if {name} == Address::ZERO {{\n    return Err(b"zero address".to_vec());\n}}'
+                    fix = Fix(description=f"Return an error if '{name}' is the zero address",
+                              edits=[self._insert_statement_before(code, body.named_children[0], check)])
+                self._add_issue(line_start, line_end, self._get_column_for_node(node, code),
+                                self._get_node_text(node, code), name, usage, severity, f"function '{function_name}'", results, fix)
+
+    def _check_sol_function(self, function: SolFunction, code: str, results) -> None:
+        """Check the address parameters of a sol! function"""
+        if not any(modifier in ("public", "external") for modifier in function.modifiers):
+            return
+        params = function.params.children[1:-1]
+        addresses = [self._get_node_text(param, code) for index, param in enumerate(params)
+                     if index > 0 and param.type == "identifier" and self._get_node_text(params[index - 1], code) == "address"]
+        addresses = [name for name in addresses if name not in self.ignored_parameters]
+        if not addresses:
+            return
+
+        guards = self._get_guards(function, code)
+        validated: Set[str] = set()
+        reported: Set[str] = set()
+        for statement in self._split_sol_statements(function.body):
+            text = self._get_source_text(code, statement[0].start_byte, statement[-1].end_byte)
+            guard = next((guard for guard in guards if guard.node == statement[0]), None)
+            if guard is not None:
+                if ZERO_ADDRESS_PATTERN.search(guard.condition):
+                    validated.update(name for name in addresses if self._mentions(guard.condition, name))
+                continue
+            target, operator = self._split_sol_assignment(statement, code)
+            for name in addresses:
+                if name in validated or name in reported:
+                    continue
+                if operator in ("=", "+=") and re.search(rf"\[\s*{re.escape(name)}\s*\]", target):
+                    usage, severity = "written into a mapping", "Low"
+                elif operator == "=" and self._mentions(text.split("=", 1)[1], name):
+                    usage, severity = "stored", "Medium"
+                else:
+                    continue
+                reported.add(name)
+                line_start, _ = self._get_line_for_node(statement[0])
+                _, line_end = self._get_line_for_node(statement[-1])
+                first_token = function.body.children[1]
+                fix = Fix(description=f"Require '{name}' not to be the zero address",
+                          edits=[self._insert_statement_before(code, first_token, f"require({name} != address(0));")])
+                self._add_issue(line_start, line_end, self._get_column_for_node(statement[0], code), text, name,
+                                usage, severity, f"sol! function '{function.name}'", results, fix)
+
+    def _add_issue(self, line_start: int, line_end: int, column: int, snippet: str, name: str, usage: str,
+                   severity: str, location: str, results, fix: Optional[Fix] = None) -> None:
+        """Add a missing zero-address check issue"""
+        results.add_issue(
+            issue_type="zero_address_check",
+            severity=severity,
+            description=f"Address parameter '{name}' in {location} is {usage} without being checked against the zero address.",
+            line_start=line_start,
+            line_end=line_end,
+            column=column,
+            code_snippet=snippet,
+            recommendation=f"Reject the zero address before using it, e.g. `if {name} == Address::ZERO {{ return Err(...); }}`.",
+            fix=fix
+        )
+
+    def _mentions(self, text: str, name: str) -> bool:
+        """Check if source text refers to a name"""
+        return bool(re.search(rf"\b{re.escape(name)}\b", text))
+
+    def _collect_address_parameters(self, function_node: Node, code: str) -> List[str]:
+        """Collect the names of the `Address` parameters of a function"""
+        names = []
+        parameters = function_node.child_by_field_name("parameters")
+        if not parameters:
+            return names
+        for parameter in parameters.named_children:
+            if parameter.type != "parameter":
+                continue
+            pattern = parameter.child_by_field_name("pattern")
+            param_type = parameter.child_by_field_name("type")
+            if not pattern or not param_type or pattern.type != "identifier":
+                continue
+            name = self._get_node_text(pattern, code)
+            if self._get_node_text(param_type, code).lstrip("&").strip() == "Address" and \
+                    name not in self.ignored_parameters:
+                names.append(name)
+        return names
+
+    def _get_sinks(self, node: Node, code: str, addresses: List[str],
+                   setter_aliases: Dict[str, str]) -> List[Tuple[str, str, str]]:
+        """Get the address parameters a node stores or sends value to, with the usage and severity"""
+        if node.type != "call_expression":
+            return []
+        function = node.child_by_field_name("function")
+        arguments = node.child_by_field_name("arguments")
+        if not function or not arguments:
+            return []
+        arguments_text = self._get_node_text(arguments, code)
+
+        if function.type == "field_expression":
+            method = function.child_by_field_name("field")
+            receiver = function.child_by_field_name("value")
+            method_name = self._get_node_text(method, code) if method else ""
+            if receiver and method_name in STORAGE_WRITE_METHODS and \
+                    self._get_storage_write_field(node, code, setter_aliases):
+                receiver_text = self._get_node_text(receiver, code)
+                if receiver.type == "identifier":
+                    receiver_text = ""
+                sinks = []
+                for name in addresses:
+                    if self._mentions(receiver_text, name) or (method_name == "insert" and arguments.named_children and
+                                                               self._get_node_text(arguments.named_children[0], code) == name):
+                        sinks.append((name, "written into a mapping", "Low"))
+                    elif self._mentions(arguments_text, name):
+                        sinks.append((name, "stored", "Medium"))
+                return sinks
+            name_node = method
+        elif function.type == "scoped_identifier":
+            name_node = function.child_by_field_name("name")
+        else:
+            name_node = function
+        if name_node and self._get_node_text(name_node, code) in TRANSFER_FUNCTIONS:
+            return [(name, "used as a transfer recipient", "Medium")
+                    for name in addresses if self._mentions(arguments_text, name)]
+        return []
+
+    def _split_sol_assignment(self, statement: List[Node], code: str) -> Tuple[str, str]:
+        """Split a sol! statement into its assignment target and operator"""
+        for index, token in enumerate(statement):
+            if token.type in ("=", "+=", "-="):
+                target = self._get_source_text(code, statement[0].start_byte, statement[index - 1].end_byte) if index else ""
+                return target, token.type
+        return "", ""
@@ -0,0 +1,242 @@
+"""
+Selection of the files to analyze by .gitignore rules and --include/--exclude globs
+"""
+import logging
+import os
+import re
+from dataclasses import dataclass, field
+from typing import Dict, Iterable, Iterator, List, Optional, Pattern
+
+GITIGNORE_NAME = ".gitignore"
+
+logger = logging.getLogger(__name__)
+
+
+def glob_to_regex(pattern: str) -> Pattern:
+    """
+    Translate a glob to a regex matching `/`-separated relative paths
+
+    `*` and `?` don't match `/`, `**` matches any number of directories and
+    `[...]` is a character class, negated by a leading `!`. A pattern
+    without a `/` other than a trailing one matches a name at any depth, and
+    a leading `/` only anchors the pattern, like in .gitignore.
+    """
+    anchored = "/" in pattern.rstrip("/")
+    pattern = pattern.strip("/")
+    regex = "" if anchored else "(?:.*/)?"
+    index = 0
+    while index < len(pattern):
+        char = pattern[index]
+        if pattern.startswith("**/", index):
+            regex += "(?:.*/)?"
+            index += 3
+            continue
+        if pattern.startswith("**", index):
+            regex += ".*"
+            index += 2
+            continue
+        if char == "*":
+            regex += "[^/]*"
+        elif char == "?":
+            regex += "[^/]"
+        elif char == "[" and "]" in pattern[index + 2:]:
+            end = pattern.index("]", index + 2)
+            chars = pattern[index + 1:end]
+            if chars.startswith("!"):
+                chars = "^" + chars[1:]
+            regex += "[" + chars.replace("\\", "\\\\") + "]"
+            index = end
+        elif char == "\\" and index + 1 < len(pattern):
+            index += 1
+            regex += re.escape(pattern[index])
+        else:
+            regex += re.escape(char)
+        index += 1
+    return re.compile(regex)
+
+
+@dataclass
+class GlobPattern:
+    """
+    A glob as written in .gitignore or passed to --include/--exclude.
+
+    A trailing `/` restricts the pattern to directories and a leading `!`
+    re-includes what earlier .gitignore rules ignored.
+    """
+    pattern: str
+    regex: Pattern
+    directory_only: bool = False
+    negated: bool = False
+
+    @classmethod
+    def parse(cls, pattern: str, negatable: bool = False) -> "GlobPattern":
+        """Compile a glob, reading a leading `!` as negation if `negatable`"""
+        text = pattern
+        negated = negatable and text.startswith("!")
+        if negated:
+            text = text[1:]
+        return cls(pattern=pattern, regex=glob_to_regex(text), directory_only=text.endswith("/"), negated=negated)
+
+    def matches(self, path: str, is_directory: bool = False) -> bool:
+        """Check if a `/`-separated relative path matches"""
+        if self.directory_only and not is_directory:
+            return False
+        return self.regex.fullmatch(path) is not None
+
+    def matches_path_or_parent(self, path: str, is_directory: bool = False) -> bool:
+        """Check if a path or one of the directories it is in matches, e.g. `vendor/` for `vendor/lib.rs`"""
+        parts = path.split("/")
+        return any(self.matches("/".join(parts[:index]), is_directory or index < len(parts))
+                   for index in range(1, len(parts) + 1))
+
+
+def find_repository_root(path: str) -> Optional[str]:
+    """Get the nearest directory containing a path that has a `.git`, if any"""
+    directory = os.path.abspath(path if os.path.isdir(path) else os.path.dirname(path))
+    while True:
+        if os.path.exists(os.path.join(directory, ".git")):
+            return directory
+        parent = os.path.dirname(directory)
+        if parent == directory:
+            return None
+        directory = parent
+
+
+class GitignoreRules:
+    """
+    The .gitignore files below a directory, read as they are needed.
+
+    Rules apply to the paths below the .gitignore they are written in, and
+    the last matching rule wins, with deeper files read after shallower
+    ones. A file in an ignored directory is ignored even if a rule
+    re-includes it, as git does.
+    """
+
+    def __init__(self, root: str):
+        self.root = os.path.abspath(root)
+        self._rules: Dict[str, List[GlobPattern]] = {}
+
+    def is_ignored(self, path: str, is_directory: bool = False) -> bool:
+        """Check if a file or directory is ignored; paths outside the root never are"""
+        relative = os.path.relpath(os.path.abspath(path), self.root).replace(os.sep, "/")
+        if relative == "." or relative.startswith("../"):
+            return False
+        parts = relative.split("/")
+        return any(self._is_ignored_entry(parts[:index], is_directory or index < len(parts))
+                   for index in range(1, len(parts) + 1))
+
+    def _is_ignored_entry(self, parts: List[str], is_directory: bool) -> bool:
+        """Check if the rules ignore one entry, without looking at its parent directories"""
+        ignored = False
+        for depth in range(len(parts)):
+            path = "/".join(parts[depth:])
+            for rule in self._load(os.path.join(self.root, *parts[:depth])):
+                if rule.matches(path, is_directory):
+                    ignored = not rule.negated
+        return ignored
+
+    def _load(self, directory: str) -> List[GlobPattern]:
+        """Read the rules of a directory's .gitignore, empty if it has none"""
+        if directory not in self._rules:
+            rules = []
+            try:
+                with open(os.path.join(directory, GITIGNORE_NAME), encoding='utf-8') as f:
+                    lines = f.read().splitlines()
+            except (OSError, UnicodeDecodeError):
+                lines = []
+            for line in lines:
+                # Trailing spaces are ignored unless escaped
+                line = line.rstrip() if not line.endswith("\\ ") else line[:-2] + " "
+                if line and not line.startswith("#"):
+                    rules.append(GlobPattern.parse(line, negatable=True))
+            self._rules[directory] = rules
+        return self._rules[directory]
+
+
+@dataclass
+class FileFilter:
+    """
+    Which of the discovered contract files to analyze.
+
+    When `include` globs are given, only files matching one of them are
+    kept. Files matching an `exclude` glob, and with `gitignore` set, files
+    ignored by the .gitignore files of their repository are dropped. Globs
+    are matched against paths relative to the current directory and to the
+    analyzed directory, so `test_contracts/**` and `**/token.rs` both work
+    wherever the analysis starts.
+    """
+    include: List[str] = field(default_factory=list)
+    exclude: List[str] = field(default_factory=list)
+    gitignore: bool = True
+
+    def __post_init__(self):
+        self._include = [GlobPattern.parse(pattern) for pattern in self.include]
+        self._exclude = [GlobPattern.parse(pattern) for pattern in self.exclude]
+        self._gitignore_rules: Dict[str, GitignoreRules] = {}
+        self._repository_roots: Dict[str, Optional[str]] = {}
+
+    def accepts(self, path: str, root: Optional[str] = None) -> bool:
+        """Check if a contract file found below `root` should be analyzed"""
+        candidates = _relative_paths(path, root)
+        if self._include and not any(pattern.matches_path_or_parent(candidate)
+                                     for pattern in self._include for candidate in candidates):
+            logger.debug(f"Skipping {path}: it matches no --include glob")
+            return False
+        return not self._is_excluded(path, candidates, root, is_directory=False)
+
+    def accepts_directory(self, directory: str, root: Optional[str] = None) -> bool:
+        """Check if a directory may hold files to analyze, so walks can skip excluded and ignored ones"""
+        return not self._is_excluded(directory, _relative_paths(directory, root), root, is_directory=True)
+
+    def apply(self, paths: Iterable[str], root: Optional[str] = None) -> List[str]:
+        """Keep the accepted paths, in order"""
+        return [path for path in paths if self.accepts(path, root)]
+
+    def walk(self, directory: str, extension: str = ".rs") -> Iterator[str]:
+        """
+        Yield the accepted files with an extension below a directory
+
+        Excluded and ignored directories aren't descended into, and hidden
+        files and directories are skipped, as `**` globs skip them.
+        """
+        for current, directories, files in os.walk(directory):
+            directories[:] = sorted(name for name in directories if not name.startswith(".") and
+                                    self.accepts_directory(os.path.join(current, name), directory))
+            for name in sorted(files):
+                path = os.path.join(current, name)
+                if name.endswith(extension) and not name.startswith(".") and self.accepts(path, directory):
+                    yield path
+
+    def _is_excluded(self, path: str, candidates: List[str], root: Optional[str], is_directory: bool) -> bool:
+        """Check if a path matches an exclude glob or is ignored by git"""
+        if any(pattern.matches_path_or_parent(candidate, is_directory)
+               for pattern in self._exclude for candidate in candidates):
+            logger.debug(f"Skipping {path}: it matches an --exclude glob")
+            return True
+        if not self.gitignore:
+            return False
+        rules = self._rules_for(path, root)
+        if rules is not None and rules.is_ignored(path, is_directory):
+            logger.debug(f"Skipping {path}: it is ignored by .gitignore")
+            return True
+        return False
+
+    def _rules_for(self, path: str, root: Optional[str]) -> Optional[GitignoreRules]:
+        """Get the .gitignore rules of the repository a path is in, or of `root` outside of one"""
+        directory = os.path.dirname(os.path.abspath(path))
+        if directory not in self._repository_roots:
+            self._repository_roots[directory] = find_repository_root(directory)
+        root = self._repository_roots[directory] or (root if root is not None and os.path.isdir(root) else None)
+        if root is None:
+            return None
+        if root not in self._gitignore_rules:
+            self._gitignore_rules[root] = GitignoreRules(root)
+        return self._gitignore_rules[root]
+
+
+def _relative_paths(path: str, root: Optional[str]) -> List[str]:
+    """Get a path relative to the current directory and to `root`, `/`-separated, to match globs against"""
+    paths = [os.path.relpath(path)]
+    if root is not None and os.path.isdir(root):
+        paths.append(os.path.relpath(path, root))
+    return [candidate.replace(os.sep, "/") for candidate in dict.fromkeys(paths)]
@@ -0,0 +1,255 @@
+"""
+Finding and severity types shared by the detectors and reporters
+"""
+from dataclasses import dataclass, field
+from enum import IntEnum
+from typing import Any, Dict, Iterable, List, Optional, Tuple, Union
+
+
+class Severity(IntEnum):
+    """Severity of a finding, ordered from least to most severe"""
+    INFO = 0
+    LOW = 1
+    MEDIUM = 2
+    HIGH = 3
+    CRITICAL = 4
+
+    @classmethod
+    def parse(cls, value: Union[str, "Severity"]) -> "Severity":
+        """
+        Parse a severity name such as "High", ignoring case
+
+        Raises:
+            ValueError: If the name is not a known severity
+        """
+        if isinstance(value, Severity):
+            return value
+        try:
+            return cls[value.strip().upper()]
+        except KeyError:
+            raise ValueError(f"Unknown severity '{value}'") from None
+
+    def __str__(self) -> str:
+        return self.name.capitalize()
+
+
+class Confidence(IntEnum):
+    """How likely a finding is to be a real issue rather than a false positive, from least to most likely"""
+    LOW = 0
+    MEDIUM = 1
+    HIGH = 2
+
+    @classmethod
+    def parse(cls, value: Union[str, "Confidence"]) -> "Confidence":
+        """
+        Parse a confidence name such as "High", ignoring case
+
+        Raises:
+            ValueError: If the name is not a known confidence
+        """
+        if isinstance(value, Confidence):
+            return value
+        try:
+            return cls[value.strip().upper()]
+        except KeyError:
+            raise ValueError(f"Unknown confidence '{value}'") from None
+
+    def __str__(self) -> str:
+        return self.name.capitalize()
+
+
+@dataclass
+class TextEdit:
+    """
+    A replacement of a span of a file's text.
+
+    Lines and columns are 1-based and count characters, and the end is
+    exclusive, so an edit whose end equals its start inserts text.
+    """
+    line: int
+    column: int
+    line_end: int
+    column_end: int
+    replacement: str
+
+    def to_dict(self) -> Dict[str, Any]:
+        """Convert to the dictionary used in reports"""
+        return {"line": self.line, "column": self.column, "end_line": self.line_end,
+                "end_column": self.column_end, "replacement": self.replacement}
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "TextEdit":
+        """Build an edit from the dictionary written by `to_dict`"""
+        return cls(line=data["line"], column=data["column"], line_end=data["end_line"],
+                   column_end=data["end_column"], replacement=data["replacement"])
+
+
+@dataclass
+class Fix:
+    """
+    A concrete fix for a finding: edits to its file that are applied together.
+
+    `safe` fixes are mechanical rewrites that `--fix` applies without review.
+    Others, such as inserting a new revert path, are only offered, e.g. as
+    editor quick fixes.
+    """
+    description: str
+    edits: List[TextEdit] = field(default_factory=list)
+    safe: bool = False
+
+    def to_dict(self) -> Dict[str, Any]:
+        """Convert to the dictionary used in reports"""
+        return {"description": self.description, "edits": [edit.to_dict() for edit in self.edits], "safe": self.safe}
+
+    @classmethod
+    def from_dict(cls, data: Dict[str, Any]) -> "Fix":
+        """Build a fix from the dictionary written by `to_dict`"""
+        return cls(description=data["description"], edits=[TextEdit.from_dict(edit) for edit in data["edits"]],
+                   safe=data.get("safe", False))
+
+
+@dataclass
+class Finding:
+    """
+    A single issue reported by a detector.
+
+    Lines and columns are 1-based and count characters, a column of 0 means
+    it is not known. Findings of detectors always have a confidence, the
+    detector's default unless it set one for the finding.
+    Findings that can be fixed mechanically carry the edits in `fix`, on
+    top of the prose `suggestion`.
+    """
+    detector_id: str
+    severity: Severity
+    message: str
+    line: int
+    column: int = 0
+    file: Optional[str] = None
+    suggestion: Optional[str] = None
+    line_end: Optional[int] = None
+    code_snippet: str = ""
+    confidence: Optional[Confidence] = None
+    fix: Optional[Fix] = None
+
+    def sort_key(self) -> Tuple:
+        """
+        Key ordering findings by file, line, column and detector id
+
+        The end line, severity and message break the remaining ties, so findings
+        sort the same however they were collected.
+        """
+        return (self.file or "", self.line, self.column, self.detector_id,
+                self.line_end if self.line_end is not None else self.line, -self.severity, self.message)
+
+    def to_dict(self) -> Dict[str, Any]:
+        """Convert to the issue dictionary used in reports"""
+        issue = {
+            "type": self.detector_id,
+            "severity": str(self.severity),
+            "description": self.message,
+            "line_start": self.line,
+            "line_end": self.line_end if self.line_end is not None else self.line,
+            "column": self.column,
+            "code_snippet": self.code_snippet,
+            "recommendation": self.suggestion or ""
+        }
+        if self.file is not None:
+            issue["file"] = self.file
+        if self.confidence is not None:
+            issue["confidence"] = str(self.confidence)
+        if self.fix is not None:
+            issue["fix"] = self.fix.to_dict()
+        return issue
+
+
+@dataclass
+class ParseError:
+    """
+    The first syntax error of a file that could not be parsed.
+
+    Lines and columns are 1-based and count characters. The file is reported
+    with this error instead of being analyzed, since detectors would misread
+    the partial tree, and the other files of the run are still analyzed.
+    """
+    message: str
+    line: int
+    column: int
+    file: Optional[str] = None
+
+    def describe(self) -> str:
+        """Describe the error with its location, e.g. for a warning"""
+        location = f"{self.file}:{self.line}:{self.column}" if self.file else f"line {self.line}, column {self.column}"
+        return f"{location}: could not parse file: {self.message}"
+
+    def to_dict(self) -> Dict[str, Any]:
+        """Convert to the dictionary used in reports"""
+        return {"file": self.file, "line": self.line, "column": self.column, "message": self.message}
+
+
+def sort_findings(findings: Iterable[Finding]) -> List[Finding]:
+    """Sort findings by location and detector id, in the order every report lists them"""
+    return sorted(findings, key=Finding.sort_key)
+
+
+def filter_findings(findings: Iterable[Finding], threshold: Optional[Severity],
+                    min_confidence: Optional[Confidence] = None) -> List[Finding]:
+    """
+    Keep the findings at or above a severity threshold and a minimum confidence, or all of them without either
+
+    Findings without a confidence are kept.
+    """
+    return [finding for finding in findings
+            if (threshold is None or finding.severity >= threshold) and
+            (min_confidence is None or finding.confidence is None or finding.confidence >= min_confidence)]
+
+
+def exit_code_for(findings: Iterable[Finding], fail_on: Optional[Severity]) -> int:
+    """Get the process exit code: 1 if any finding meets or exceeds `fail_on`, 0 otherwise"""
+    if fail_on is None:
+        return 0
+    return 1 if any(finding.severity >= fail_on for finding in findings) else 0
+
+
+@dataclass
+class FindingSummary:
+    """
+    Counts of the reported findings per severity and per detector, with the
+    number of files scanned, how many of them could not be parsed and how
+    long the analysis took.
+    """
+    total: int = 0
+    by_severity: Dict[Severity, int] = field(default_factory=dict)
+    by_detector: Dict[str, int] = field(default_factory=dict)
+    files_scanned: int = 0
+    duration: float = 0.0
+    parse_errors: int = 0
+
+    @classmethod
+    def of(cls, findings: Iterable[Finding], files_scanned: int, duration: float,
+           parse_errors: int = 0) -> "FindingSummary":
+        """
+        Tally findings after all filtering, so the counts match what is reported
+
+        Every severity is present, from the most severe, and detectors are
+        ordered by their count, then by id.
+        """
+        findings = list(findings)
+        by_severity = {severity: 0 for severity in sorted(Severity, reverse=True)}
+        by_detector: Dict[str, int] = {}
+        for finding in findings:
+            by_severity[finding.severity] += 1
+            by_detector[finding.detector_id] = by_detector.get(finding.detector_id, 0) + 1
+        by_detector = dict(sorted(by_detector.items(), key=lambda item: (-item[1], item[0])))
+        return cls(total=len(findings), by_severity=by_severity, by_detector=by_detector,
+                   files_scanned=files_scanned, duration=duration, parse_errors=parse_errors)
+
+    def to_dict(self) -> Dict[str, Any]:
+        """Convert to the dictionary used in reports"""
+        return {
+            "total": self.total,
+            "by_severity": {str(severity): count for severity, count in self.by_severity.items()},
+            "by_detector": dict(self.by_detector),
+            "files_scanned": self.files_scanned,
+            "parse_errors": self.parse_errors,
+            "duration_seconds": round(self.duration, 3),
+        }
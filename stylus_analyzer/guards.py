@@ -0,0 +1,156 @@
+"""
+Guards of a function: the checks the code after them depends on, in Rust and sol! bodies
+"""
+from dataclasses import dataclass
+from typing import List, Tuple, Union
+
+from tree_sitter import Node
+
+from stylus_analyzer.ast_utils import get_macro_name, iter_nodes, node_text, text_between
+from stylus_analyzer.sol_parser import SolFunction, SolStatement, is_token_tree
+
+# Macros reverting when their condition is false
+ASSERT_MACROS = ("assert", "assert_eq", "assert_ne")
+REQUIRE_MACROS = ("require", "ensure")
+
+# Comparison the two arguments of `assert_eq!` and `assert_ne!` stand for
+ASSERT_COMPARISONS = {"assert_eq": "==", "assert_ne": "!="}
+
+# Macros and functions a Rust block reverts with, besides returning an `Err`
+REVERTING_MACROS = ("panic", "unreachable")
+REVERTING_FUNCTIONS = ("revert",)
+
+
+@dataclass
+class Guard:
+    """
+    A check in a function body that the code after it depends on.
+
+    `condition` is the checked expression as written, without the message:
+    `amount > 0` for `require(amount > 0, "zero amount")` or
+    `assert!(amount > 0)`, and `a == b` for `assert_eq!(a, b)`. `kind` is
+    "require" for sol! `require` and the `require!`/`ensure!` macros,
+    "assert" for sol! `assert` and the `assert!` macros, "revert" for an
+    `if` whose block returns an `Err` or reverts, so that its condition is
+    the failing case, and "condition" for other `if` and `while` conditions.
+    `span` holds the byte offsets of the whole construct and `node` the node
+    or token it starts at.
+    """
+    condition: str
+    kind: str
+    span: Tuple[int, int]
+    node: Node
+
+    @property
+    def line(self) -> int:
+        """1-based line the guard starts on"""
+        return self.node.start_point[0] + 1
+
+    @property
+    def reverts(self) -> bool:
+        """Check if the guard reverts when its check fails, rather than only skipping code"""
+        return self.kind != "condition"
+
+
+def extract_guards(function: Union[Node, SolFunction], code: str) -> List[Guard]:
+    """Collect the guards of a Rust `function_item` or a sol! function in source order"""
+    if isinstance(function, SolFunction):
+        return _extract_sol_guards(function, code)
+    return _extract_rust_guards(function, code)
+
+
+def _extract_rust_guards(function_node: Node, code: str) -> List[Guard]:
+    """Collect the `if` and `while` conditions and guard macros of a Rust function"""
+    body = function_node.child_by_field_name("body")
+    guards = []
+    for node in iter_nodes(body) if body is not None else []:
+        if node.type in ("if_expression", "while_expression"):
+            condition = node.child_by_field_name("condition")
+            if condition is None:
+                continue
+            consequence = node.child_by_field_name("consequence") if node.type == "if_expression" else None
+            reverts = consequence is not None and _is_reverting_block(consequence, code)
+            guards.append(Guard(condition=node_text(condition, code), kind="revert" if reverts else "condition",
+                                span=(node.start_byte, node.end_byte), node=node))
+        elif node.type == "macro_invocation":
+            macro = get_macro_name(node, code)
+            if macro not in ASSERT_MACROS + REQUIRE_MACROS:
+                continue
+            tree = next((child for child in node.children if child.type == "token_tree"), None)
+            arguments = _split_arguments(tree, code) if tree is not None else []
+            if not arguments:
+                continue
+            condition = f" {ASSERT_COMPARISONS[macro]} ".join(arguments[:2]) if macro in ASSERT_COMPARISONS \
+                else arguments[0]
+            guards.append(Guard(condition=condition, kind="assert" if macro in ASSERT_MACROS else "require",
+                                span=(node.start_byte, node.end_byte), node=node))
+    return guards
+
+
+def _is_reverting_block(block: Node, code: str) -> bool:
+    """Check if a block returns an `Err`, reverts or panics among its statements"""
+    for statement in block.named_children:
+        node = statement.named_children[0] if statement.type == "expression_statement" and \
+            statement.named_children else statement
+        if node.type == "return_expression":
+            value = node.named_children[0] if node.named_children else None
+            if value is not None and node_text(value, code).startswith("Err"):
+                return True
+        elif node.type == "macro_invocation" and get_macro_name(node, code) in REVERTING_MACROS:
+            return True
+        elif node.type == "call_expression":
+            function = node.child_by_field_name("function")
+            if function is not None and node_text(function, code).split("::")[-1] in REVERTING_FUNCTIONS:
+                return True
+    return False
+
+
+def _extract_sol_guards(function: SolFunction, code: str) -> List[Guard]:
+    """Collect the `require`/`assert` calls and the `if` and `while` conditions of a sol! function"""
+    guards = []
+    for statement in function.statements:
+        tokens = statement.tokens
+        first = node_text(tokens[0], code)
+        if statement.kind == "require" and len(tokens) > 1 and is_token_tree(tokens[1], "("):
+            arguments = _split_arguments(tokens[1], code)
+            if arguments:
+                guards.append(Guard(condition=arguments[0], kind=first, span=_statement_span(statement),
+                                    node=tokens[0]))
+            continue
+        # `else if (x)` checks its condition like an `if`
+        index = 1 if first == "else" and len(tokens) > 1 and node_text(tokens[1], code) == "if" else 0
+        keyword = node_text(tokens[index], code)
+        if keyword not in ("if", "while") or len(tokens) <= index + 1 or not is_token_tree(tokens[index + 1], "("):
+            continue
+        condition = tokens[index + 1]
+        reverts = keyword == "if" and _is_sol_reverting(condition, tokens[index + 2:], code)
+        guards.append(Guard(condition=text_between(code, condition.start_byte + 1, condition.end_byte - 1).strip(),
+                            kind="revert" if reverts else "condition", span=_statement_span(statement),
+                            node=tokens[0]))
+    return guards
+
+
+def _is_sol_reverting(condition: Node, rest: List[Node], code: str) -> bool:
+    """Check if the statement or block following an `if (...)` header reverts"""
+    block = condition.next_sibling if not rest else None
+    tokens = block.children[1:-1] if block is not None and is_token_tree(block, "{") else rest
+    return any(token.type == "identifier" and node_text(token, code) == "revert" for token in tokens)
+
+
+def _split_arguments(tree: Node, code: str) -> List[str]:
+    """Split a `( ... )` token tree, such as the arguments of `assert_eq!(a, b, "message")`, on top-level commas"""
+    arguments = []
+    current: List[Node] = []
+    for token in tree.children[1:-1] + [None]:
+        if token is None or token.type == ",":
+            if current:
+                arguments.append(text_between(code, current[0].start_byte, current[-1].end_byte))
+            current = []
+        else:
+            current.append(token)
+    return arguments
+
+
+def _statement_span(statement: SolStatement) -> Tuple[int, int]:
+    """Get the byte offsets of a sol! statement"""
+    return statement.tokens[0].start_byte, statement.tokens[-1].end_byte
@@ -0,0 +1,98 @@
+"""
+Logging setup of the command line, with the level picked by --quiet/--verbose and STYLUS_ANALYZER_LOG
+"""
+import logging
+import os
+import sys
+from typing import Dict, Mapping, Optional, Set, Tuple
+
+# Environment variable refining the log levels, e.g. `info,stylus_analyzer.cache=debug`
+LOG_FILTER_ENV = "STYLUS_ANALYZER_LOG"
+
+LOG_FORMAT = '%(asctime)s - %(name)s - %(levelname)s - %(message)s'
+
+# Logger all of the analyzer's modules log below
+ROOT_LOGGER = "stylus_analyzer"
+
+# Levels of --quiet, the default and --verbose
+QUIET = logging.ERROR
+DEFAULT = logging.INFO
+VERBOSE = logging.DEBUG
+
+LEVEL_NAMES = {"error": logging.ERROR, "warn": logging.WARNING, "warning": logging.WARNING, "info": logging.INFO,
+               "debug": logging.DEBUG, "off": logging.CRITICAL + 1}
+
+# Loggers given a level of their own by the last filter, reset when logging is configured again
+_filtered_loggers: Set[str] = set()
+
+
+class _StderrHandler(logging.StreamHandler):
+    """Handler writing to whatever `sys.stderr` is when a record is logged, e.g. when click captures it"""
+
+    @property
+    def stream(self):
+        return sys.stderr
+
+    @stream.setter
+    def stream(self, value):
+        pass
+
+
+def parse_log_filter(spec: str) -> Tuple[Optional[int], Dict[str, int]]:
+    """
+    Parse a log filter such as `warning,cache=debug`
+
+    Returns:
+        The level of a bare directive, if any, and the level of each named
+        logger, with names relative to `stylus_analyzer` expanded
+
+    Raises:
+        ValueError: If a directive names an unknown level
+    """
+    default = None
+    levels = {}
+    for directive in spec.split(","):
+        directive = directive.strip()
+        if not directive:
+            continue
+        name, _, level_name = directive.rpartition("=")
+        level = LEVEL_NAMES.get(level_name.strip().lower())
+        if level is None:
+            raise ValueError(f"Unknown log level '{level_name.strip()}' in {LOG_FILTER_ENV}, expected one of "
+                             f"{', '.join(LEVEL_NAMES)}")
+        name = name.strip()
+        if not name:
+            default = level
+        else:
+            levels[name if name.split(".")[0] == ROOT_LOGGER else f"{ROOT_LOGGER}.{name}"] = level
+    return default, levels
+
+
+def configure_logging(level: int = DEFAULT, env: Optional[Mapping[str, str]] = None) -> None:
+    """
+    Send the analyzer's logs to stderr at a level, refined by the STYLUS_ANALYZER_LOG filter
+
+    Can be called again, e.g. once a command knows its verbosity, and
+    replaces the earlier setup.
+
+    Raises:
+        ValueError: If the filter in the environment can't be parsed
+    """
+    env = os.environ if env is None else env
+    default, levels = parse_log_filter(env.get(LOG_FILTER_ENV, ""))
+
+    root = logging.getLogger(ROOT_LOGGER)
+    if not any(isinstance(handler, _StderrHandler) for handler in root.handlers):
+        handler = _StderrHandler()
+        handler.setFormatter(logging.Formatter(LOG_FORMAT))
+        root.addHandler(handler)
+    # The analyzer's records are printed once, even if the root logger has a handler too
+    root.propagate = False
+    root.setLevel(default if default is not None else level)
+
+    for name in _filtered_loggers:
+        logging.getLogger(name).setLevel(logging.NOTSET)
+    _filtered_loggers.clear()
+    for name, logger_level in levels.items():
+        logging.getLogger(name).setLevel(logger_level)
+        _filtered_loggers.add(name)
@@ -0,0 +1,334 @@
+"""
+Language Server Protocol server publishing findings as editor diagnostics
+
+Speaks JSON-RPC over a pair of byte streams, normally stdin and stdout. Open
+documents are kept in memory with full text sync, and each open, change or
+save re-analyzes only the document it is about, from the editor's text.
+Findings carrying a fix are offered as quick-fix code actions.
+"""
+import json
+import logging
+import os
+from typing import Any, BinaryIO, Callable, Dict, List, Optional
+from urllib.parse import unquote, urlparse
+from urllib.request import pathname2url, url2pathname
+
+from stylus_analyzer import __version__
+from stylus_analyzer.findings import Finding, ParseError, Severity, TextEdit, sort_findings
+
+logger = logging.getLogger(__name__)
+
+SERVER_NAME = "stylus-analyzer"
+
+# TextDocumentSyncKind.Full: clients send the whole document on every change
+TEXT_DOCUMENT_SYNC_FULL = 1
+
+# CodeActionKind of the fixes offered for findings
+QUICK_FIX_KIND = "quickfix"
+
+# DiagnosticSeverity values
+DIAGNOSTIC_SEVERITIES = {
+    Severity.CRITICAL: 1,
+    Severity.HIGH: 1,
+    Severity.MEDIUM: 2,
+    Severity.LOW: 3,
+    Severity.INFO: 4,
+}
+
+# JSON-RPC error codes
+PARSE_ERROR = -32700
+INVALID_REQUEST = -32600
+METHOD_NOT_FOUND = -32601
+INTERNAL_ERROR = -32603
+SERVER_NOT_INITIALIZED = -32002
+
+
+def read_message(stream: BinaryIO) -> Optional[Dict[str, Any]]:
+    """
+    Read one `Content-Length` framed JSON-RPC message
+
+    Returns:
+        The decoded message, or None at the end of the stream
+
+    Raises:
+        ValueError: If the headers or the body are malformed
+    """
+    length = None
+    while True:
+        line = stream.readline()
+        if not line:
+            return None
+        line = line.strip()
+        if not line:
+            break
+        name, _, value = line.decode('ascii').partition(":")
+        if name.strip().lower() == "content-length":
+            length = int(value)
+    if length is None:
+        raise ValueError("Message without a Content-Length header")
+    return json.loads(stream.read(length).decode('utf-8'))
+
+
+def write_message(stream: BinaryIO, message: Dict[str, Any]) -> None:
+    """Write a JSON-RPC message with its `Content-Length` header"""
+    body = json.dumps(message).encode('utf-8')
+    stream.write(f"Content-Length: {len(body)}\r\n\r\n".encode('ascii') + body)
+    stream.flush()
+
+
+def uri_to_path(uri: str) -> str:
+    """Convert a `file://` URI to a local path; other URIs are returned as they are"""
+    parsed = urlparse(uri)
+    if parsed.scheme != "file":
+        return uri
+    return url2pathname(unquote(parsed.path))
+
+
+def path_to_uri(path: str) -> str:
+    """Convert a local path to a `file://` URI"""
+    return "file://" + pathname2url(os.path.abspath(path))
+
+
+def utf16_character(line: str, character: int) -> int:
+    """Convert a 0-based character offset on a line to the UTF-16 code units LSP positions count"""
+    return len(line[:character].encode("utf-16-le")) // 2 + max(character - len(line), 0)
+
+
+def finding_to_diagnostic(finding: Finding, lines: List[str]) -> Dict[str, Any]:
+    """
+    Convert a finding to an LSP diagnostic
+
+    LSP positions are 0-based and count UTF-16 code units. A finding
+    without a column starts at the first non-blank character of its line,
+    and the range ends at the end of the finding's last line.
+    """
+    line_count = max(len(lines), 1)
+    start_line = min(max(finding.line, 1), line_count) - 1
+    end_line = min(max(finding.line_end if finding.line_end is not None else finding.line, finding.line, 1),
+                   line_count) - 1
+    start_text = lines[start_line] if start_line < len(lines) else ""
+    if finding.column:
+        start_character = finding.column - 1
+    else:
+        start_character = len(start_text) - len(start_text.lstrip())
+    end_text = lines[end_line] if end_line < len(lines) else ""
+    end_character = max(len(end_text.rstrip()), start_character if end_line == start_line else 0)
+
+    message = finding.message
+    if finding.suggestion:
+        message += f"\n{finding.suggestion}"
+    return {
+        "range": {
+            "start": {"line": start_line, "character": utf16_character(start_text, start_character)},
+            "end": {"line": end_line, "character": utf16_character(end_text, end_character)},
+        },
+        "severity": DIAGNOSTIC_SEVERITIES[finding.severity],
+        "code": finding.detector_id,
+        "source": SERVER_NAME,
+        "message": message,
+    }
+
+
+def parse_error_to_diagnostic(parse_error: ParseError, lines: List[str]) -> Dict[str, Any]:
+    """Convert the syntax error of a document that could not be parsed to an error diagnostic up to the end of its line"""
+    line = min(max(parse_error.line, 1), max(len(lines), 1)) - 1
+    text = lines[line] if line < len(lines) else ""
+    return {
+        "range": {
+            "start": {"line": line, "character": utf16_character(text, parse_error.column - 1)},
+            "end": {"line": line, "character": utf16_character(text, len(text))},
+        },
+        "severity": DIAGNOSTIC_SEVERITIES[Severity.HIGH],
+        "code": "parse_error",
+        "source": SERVER_NAME,
+        "message": f"Could not parse file: {parse_error.message}. The file was not analyzed.",
+    }
+
+
+def text_edit_to_lsp(edit: TextEdit, lines: List[str]) -> Dict[str, Any]:
+    """Convert a finding's edit to an LSP TextEdit, whose positions are 0-based and count UTF-16 code units"""
+    def position(line: int, column: int) -> Dict[str, int]:
+        text = lines[line - 1] if 0 < line <= len(lines) else ""
+        return {"line": line - 1, "character": utf16_character(text, column - 1)}
+
+    return {
+        "range": {
+            "start": position(edit.line, edit.column),
+            "end": position(edit.line_end, edit.column_end),
+        },
+        "newText": edit.replacement,
+    }
+
+
+class LanguageServer:
+    """
+    A diagnostics-only language server.
+
+    Requests before `initialize` are rejected, and `serve` returns once the
+    client sends `exit`, with the exit code the protocol asks for: 0 after a
+    `shutdown` request and 1 otherwise.
+    """
+
+    def __init__(self, analyzer, reader: BinaryIO, writer: BinaryIO):
+        """
+        Args:
+            analyzer: The StaticAnalyzer running the enabled detectors
+            reader: Stream the client's messages are read from
+            writer: Stream responses and notifications are written to
+        """
+        self.analyzer = analyzer
+        self.reader = reader
+        self.writer = writer
+        # Text of the open documents by URI
+        self.documents: Dict[str, str] = {}
+        # Findings of the last analysis of each open document, for code actions
+        self.findings: Dict[str, List[Finding]] = {}
+        self.initialized = False
+        self.shutdown_requested = False
+        self._handlers: Dict[str, Callable[[Dict[str, Any]], Any]] = {
+            "initialize": self._initialize,
+            "initialized": lambda params: None,
+            "shutdown": self._shutdown,
+            "textDocument/didOpen": self._did_open,
+            "textDocument/didChange": self._did_change,
+            "textDocument/didSave": self._did_save,
+            "textDocument/didClose": self._did_close,
+            "textDocument/codeAction": self._code_action,
+        }
+
+    def serve(self) -> int:
+        """Handle messages until `exit` or the end of the input, returning the exit code"""
+        while True:
+            try:
+                message = read_message(self.reader)
+            except ValueError as e:
+                logger.error(f"Invalid message: {e}")
+                self._send({"jsonrpc": "2.0", "id": None, "error": {"code": PARSE_ERROR, "message": str(e)}})
+                continue
+            if message is None:
+                return 0 if self.shutdown_requested else 1
+            if message.get("method") == "exit":
+                return 0 if self.shutdown_requested else 1
+            self.handle(message)
+
+    def handle(self, message: Dict[str, Any]) -> None:
+        """Dispatch a request or notification, answering requests with their result or an error"""
+        method = message.get("method")
+        is_request = "id" in message
+        if not isinstance(method, str):
+            if is_request:
+                self._respond_error(message["id"], INVALID_REQUEST, "Message without a method")
+            return
+        if not self.initialized and method != "initialize":
+            if is_request:
+                self._respond_error(message["id"], SERVER_NOT_INITIALIZED, "Server not initialized")
+            return
+        handler = self._handlers.get(method)
+        if handler is None:
+            # Unknown notifications, such as `$/setTrace`, are ignored
+            if is_request:
+                self._respond_error(message["id"], METHOD_NOT_FOUND, f"Unknown method '{method}'")
+            return
+        try:
+            result = handler(message.get("params") or {})
+        except Exception as e:
+            # A malformed message, such as a didOpen without a text, must not stop the server
+            logger.exception(f"Error handling '{method}'")
+            if is_request:
+                self._respond_error(message["id"], INTERNAL_ERROR, f"Error handling '{method}': {e}")
+            return
+        if is_request:
+            self._send({"jsonrpc": "2.0", "id": message["id"], "result": result})
+
+    def diagnostics(self, uri: str) -> List[Dict[str, Any]]:
+        """Analyze an open document and convert its findings to diagnostics"""
+        text = self.documents[uri]
+        # Analyzed without a file path, so the editor's text is used instead of expanding the saved file
+        result = self.analyzer.analyze(text)
+        for error in result.errors:
+            logger.warning(f"{uri}: {error['detector']}: {error['message']}")
+        lines = text.splitlines()
+        path = uri_to_path(uri)
+        findings = sort_findings(result.findings)
+        for finding in findings:
+            finding.file = path
+        self.findings[uri] = findings
+        if result.parse_error:
+            return [parse_error_to_diagnostic(result.parse_error, lines)]
+        return [finding_to_diagnostic(finding, lines) for finding in findings]
+
+    def code_actions(self, uri: str, first_line: int, last_line: int) -> List[Dict[str, Any]]:
+        """Get the quick fixes of the findings of a document overlapping a range of 0-based lines"""
+        if uri not in self.documents:
+            return []
+        lines = self.documents[uri].splitlines()
+        actions = []
+        for finding in self.findings.get(uri, []):
+            if finding.fix is None:
+                continue
+            diagnostic = finding_to_diagnostic(finding, lines)
+            if diagnostic["range"]["end"]["line"] < first_line or diagnostic["range"]["start"]["line"] > last_line:
+                continue
+            actions.append({
+                "title": finding.fix.description,
+                "kind": QUICK_FIX_KIND,
+                "diagnostics": [diagnostic],
+                "edit": {"changes": {uri: [text_edit_to_lsp(edit, lines) for edit in finding.fix.edits]}},
+            })
+        return actions
+
+    def publish(self, uri: str) -> None:
+        """Send the diagnostics of a document, or clear them once it is closed"""
+        diagnostics = self.diagnostics(uri) if uri in self.documents else []
+        self._send({"jsonrpc": "2.0", "method": "textDocument/publishDiagnostics",
+                    "params": {"uri": uri, "diagnostics": diagnostics}})
+
+    def _initialize(self, params: Dict[str, Any]) -> Dict[str, Any]:
+        self.initialized = True
+        return {
+            "capabilities": {
+                "textDocumentSync": {"openClose": True, "change": TEXT_DOCUMENT_SYNC_FULL, "save": True},
+                "codeActionProvider": {"codeActionKinds": [QUICK_FIX_KIND]},
+            },
+            "serverInfo": {"name": SERVER_NAME, "version": __version__},
+        }
+
+    def _shutdown(self, params: Dict[str, Any]) -> None:
+        self.shutdown_requested = True
+
+    def _did_open(self, params: Dict[str, Any]) -> None:
+        document = params["textDocument"]
+        self.documents[document["uri"]] = document["text"]
+        self.publish(document["uri"])
+
+    def _did_change(self, params: Dict[str, Any]) -> None:
+        uri = params["textDocument"]["uri"]
+        changes = params.get("contentChanges") or []
+        if not changes or uri not in self.documents:
+            return
+        # With full sync the last change holds the whole document
+        self.documents[uri] = changes[-1]["text"]
+        self.publish(uri)
+
+    def _did_save(self, params: Dict[str, Any]) -> None:
+        uri = params["textDocument"]["uri"]
+        if "text" in params:
+            self.documents[uri] = params["text"]
+        if uri in self.documents:
+            self.publish(uri)
+
+    def _did_close(self, params: Dict[str, Any]) -> None:
+        uri = params["textDocument"]["uri"]
+        self.documents.pop(uri, None)
+        self.findings.pop(uri, None)
+        self.publish(uri)
+
+    def _code_action(self, params: Dict[str, Any]) -> List[Dict[str, Any]]:
+        selection = params["range"]
+        return self.code_actions(params["textDocument"]["uri"], selection["start"]["line"], selection["end"]["line"])
+
+    def _respond_error(self, request_id: Any, code: int, message: str) -> None:
+        self._send({"jsonrpc": "2.0", "id": request_id, "error": {"code": code, "message": message}})
+
+    def _send(self, message: Dict[str, Any]) -> None:
+        write_message(self.writer, message)
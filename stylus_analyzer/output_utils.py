@@ -3,15 +3,140 @@ Output formatting utilities for Stylus Analyzer
 """
 import click
 import os
+import sys
+import json
 import datetime
+import re
+import textwrap
+import xml.etree.ElementTree as ET
+from typing import Dict, Iterable, List, Optional
 from reportlab.lib.pagesizes import letter
 from reportlab.lib import colors
 from reportlab.lib.styles import getSampleStyleSheet, ParagraphStyle
 from reportlab.platypus import SimpleDocTemplate, Paragraph, Spacer, Table, TableStyle, PageBreak
 from reportlab.lib.units import inch
 
+from stylus_analyzer import __version__
+from stylus_analyzer.baseline import FindingDiff, fingerprint
+from stylus_analyzer.findings import Confidence, Finding, FindingSummary, Fix, ParseError, Severity, sort_findings
 
-def format_analysis_results(file_path: str, analysis_result, verbose: bool) -> None:
+# Version of the JSON report schema, bumped on incompatible changes
+JSON_SCHEMA_VERSION = 1
+
+SARIF_SCHEMA = "https://json.schemastore.org/sarif-2.1.0.json"
+
+# JSON schema of the security report, for aggregators validating what they ingest
+SECURITY_REPORT_SCHEMA = {
+    "$schema": "https://json-schema.org/draft/2020-12/schema",
+    "title": "stylus-analyzer security report",
+    "type": "object",
+    "required": ["schema_version", "tool", "findings"],
+    "properties": {
+        "schema_version": {"type": "integer"},
+        "tool": {
+            "type": "object",
+            "required": ["name", "version"],
+            "properties": {"name": {"type": "string"}, "version": {"type": "string"}},
+        },
+        "findings": {
+            "type": "array",
+            "items": {
+                "type": "object",
+                "required": ["id", "title", "severity", "location", "cwe", "description"],
+                "properties": {
+                    "id": {"type": "string"},
+                    "title": {"type": "string"},
+                    "severity": {"type": "string", "enum": ["critical", "high", "medium", "low", "info"]},
+                    "location": {
+                        "type": "object",
+                        "required": ["file", "start_line", "end_line", "column"],
+                        "properties": {
+                            "file": {"type": ["string", "null"]},
+                            "start_line": {"type": "integer"},
+                            "end_line": {"type": "integer"},
+                            "column": {"type": "integer"},
+                        },
+                    },
+                    "cwe": {"type": ["string", "null"], "pattern": "^CWE-[0-9]+$"},
+                    "description": {"type": "string"},
+                    "detector_id": {"type": "string"},
+                    "confidence": {"type": ["string", "null"]},
+                    "recommendation": {"type": ["string", "null"]},
+                },
+            },
+        },
+        "parse_errors": {"type": "array"},
+    },
+}
+
+# SARIF result levels for each severity
+SARIF_LEVELS = {
+    Severity.CRITICAL: "error",
+    Severity.HIGH: "error",
+    Severity.MEDIUM: "warning",
+    Severity.LOW: "note",
+    Severity.INFO: "note",
+}
+
+# GitHub Actions workflow commands for each severity
+GITHUB_ANNOTATION_LEVELS = {
+    Severity.CRITICAL: "error",
+    Severity.HIGH: "error",
+    Severity.MEDIUM: "error",
+    Severity.LOW: "warning",
+    Severity.INFO: "notice",
+}
+
+# JUnit elements findings of each severity are reported as, errors failing a CI run harder than failures
+JUNIT_ELEMENTS = {
+    Severity.CRITICAL: "error",
+    Severity.HIGH: "error",
+    Severity.MEDIUM: "failure",
+    Severity.LOW: "failure",
+    Severity.INFO: "failure",
+}
+
+# Characters XML 1.0 documents can't contain, even escaped
+XML_INVALID_CHARACTERS = re.compile(r"[\x00-\x08\x0b\x0c\x0e-\x1f\ufffe\uffff]")
+
+# Terminal colors of the severity labels in text output
+SEVERITY_COLORS = {
+    Severity.CRITICAL: "bright_red",
+    Severity.HIGH: "red",
+    Severity.MEDIUM: "yellow",
+    Severity.LOW: "blue",
+    Severity.INFO: "cyan",
+}
+
+# Source lines shown before and after a finding's span in text output
+SNIPPET_CONTEXT_LINES = 2
+
+# Lines of a multi-line span shown before eliding the rest
+SNIPPET_MAX_SPAN_LINES = 5
+
+
+def use_color(choice: str) -> bool:
+    """
+    Decide whether text output is colored for a `--color` choice
+
+    `auto` colors only when stdout is a terminal and the `NO_COLOR`
+    environment variable is unset or empty.
+    """
+    if choice == "always":
+        return True
+    if choice == "never":
+        return False
+    return sys.stdout.isatty() and not os.environ.get("NO_COLOR")
+
+
+def format_file_header(file_path: str, color: bool = False) -> str:
+    """Format the header printed before a file's results, with the path in bold when colored"""
+    path = click.style(file_path, bold=True) if color else file_path
+    return f"\n===== Static Analysis for {path} ====="
+
+
+def format_analysis_results(file_path: str, analysis_result, verbose: bool, snippet: bool = True,
+                            color: bool = False) -> None:
     """
     Format and print analysis results
     
@@ -19,37 +144,723 @@ def format_analysis_results(file_path: str, analysis_result, verbose: bool) -> N
         file_path: Path to the analyzed file
         analysis_result: The analysis result object
         verbose: Whether to show detailed output
+        snippet: Whether to show the source lines of each finding with its span underlined
+        color: Whether to color severities and carets with ANSI escape codes
     """
-    if analysis_result.has_issues():
-        click.echo(f"\nFound {len(analysis_result.issues)} issues:")
-        
-        # Group issues by type and severity
-        issues_by_severity = {}
-        for issue in analysis_result.issues:
-            severity = issue['severity']
-            if severity not in issues_by_severity:
-                issues_by_severity[severity] = []
-            issues_by_severity[severity].append(issue)
-        
-        # Print issues by severity (High to Low)
-        severities = ['Critical', 'High', 'Medium', 'Low']
-        for severity in severities:
-            if severity in issues_by_severity:
-                click.echo(f"\n{severity} severity issues:")
-                for i, issue in enumerate(issues_by_severity[severity], 1):
-                    click.echo(f"  [{i}] {issue['type']}")
-                    click.echo(f"      Lines {issue['line_start']}-{issue['line_end']}")
-                    if verbose:
-                        click.echo(f"      Description: {issue['description']}")
-                        click.echo(f"      Code: {issue['code_snippet']}")
-                    click.echo(f"      Recommendation: {issue['recommendation']}")
+    def echo(text: str = "") -> None:
+        # Passing color=True keeps the escape codes when stdout isn't a terminal
+        click.echo(text, color=color or None)
+
+    def style(text: str, severity: Severity, **styles) -> str:
+        return click.style(text, fg=SEVERITY_COLORS[severity], **styles) if color else text
+
+    sources: Dict[str, Optional[List[str]]] = {}
+    parse_error = analysis_result.parse_error
+    if parse_error:
+        echo(f"\nCould not parse the file at line {parse_error.line}, column {parse_error.column}: "
+             f"{parse_error.message}")
+        echo("The file was not analyzed.")
+    elif analysis_result.has_issues():
+        echo(f"\nFound {len(analysis_result.findings)} issues:")
+        
+        # Group findings by severity, keeping them sorted by location
+        findings_by_severity = {}
+        for finding in sort_findings(analysis_result.findings):
+            findings_by_severity.setdefault(finding.severity, []).append(finding)
+        
+        # Print findings from the most to the least severe
+        for severity in sorted(findings_by_severity, reverse=True):
+            findings = findings_by_severity[severity]
+            echo(f"\n{style(f'{severity} severity issues:', severity, bold=True)}")
+            for i, finding in enumerate(findings, 1):
+                echo(f"  [{i}] {style(finding.detector_id, severity)}")
+                echo(f"      Lines {finding.line}-{finding.line_end}")
+                if snippet and finding.file:
+                    if finding.file not in sources:
+                        sources[finding.file] = _read_source_lines(finding.file)
+                    if sources[finding.file]:
+                        for line in format_snippet(sources[finding.file], finding):
+                            if color and line.lstrip(" |").startswith("^"):
+                                carets = line.lstrip(" |")
+                                line = line[:-len(carets)] + style(carets, severity, bold=True)
+                            echo(f"      {line}")
+                if finding.confidence is not None:
+                    echo(f"      Confidence: {finding.confidence}")
+                if verbose:
+                    echo(f"      Description: {finding.message}")
+                    echo(f"      Code: {finding.code_snippet}")
+                echo(f"      Recommendation: {finding.suggestion}")
     else:
-        click.echo("No issues found.")
+        echo("No issues found.")
     
     if analysis_result.has_errors():
-        click.echo(f"\nAnalysis encountered {len(analysis_result.errors)} errors:")
+        echo(f"\nAnalysis encountered {len(analysis_result.errors)} errors:")
         for error in analysis_result.errors:
-            click.echo(f"  Error in {error['detector']}: {error['message']}") 
+            echo(f"  Error in {error['detector']}: {error['message']}") 
+
+
+def format_summary(summary: FindingSummary, color: bool = False) -> str:
+    """
+    Format the summary printed at the end of text output
+
+    Only the severities and detectors with findings are listed.
+    """
+    def style(text: str, severity: Severity) -> str:
+        return click.style(text, fg=SEVERITY_COLORS[severity], bold=True) if color else text
+
+    files = "file" if summary.files_scanned == 1 else "files"
+    lines = [
+        "\n===== Analysis Summary =====",
+        f"Analyzed {summary.files_scanned} {files} in {summary.duration:.2f} seconds",
+        f"Found {summary.total} total issues",
+    ]
+    if summary.parse_errors:
+        lines.insert(2, f"Could not parse {summary.parse_errors} {'file' if summary.parse_errors == 1 else 'files'}")
+    for severity, count in summary.by_severity.items():
+        if count:
+            lines.append(f"  {style(str(severity), severity)}: {count}")
+    if summary.by_detector:
+        id_width = max(len(detector_id) for detector_id in summary.by_detector)
+        lines.append("By detector:")
+        lines.extend(f"  {detector_id:<{id_width}}  {count}" for detector_id, count in summary.by_detector.items())
+    return "\n".join(lines)
+
+
+def format_profile(profile) -> str:
+    """
+    Format the time each detector took across all files for `--profile`, slowest first
+
+    Args:
+        profile: The `DetectorProfile` of the run
+    """
+    entries = profile.entries()
+    id_width = max([len("DETECTOR")] + [len(detector_id) for detector_id, _, _ in entries])
+    lines = ["\n===== Detector Profile =====",
+             f"{'DETECTOR':<{id_width}}  {'TIME (MS)':>10}  {'FILES':>5}  {'SHARE':>6}"]
+    for detector_id, elapsed, runs in entries:
+        share = elapsed / profile.total * 100 if profile.total else 0.0
+        lines.append(f"{detector_id:<{id_width}}  {elapsed * 1000:>10.2f}  {runs:>5}  {share:>5.1f}%")
+    lines.append(f"{'Total':<{id_width}}  {profile.total * 1000:>10.2f}")
+    return "\n".join(lines)
+
+
+def format_snippet(lines: List[str], finding: Finding, context_lines: int = SNIPPET_CONTEXT_LINES) -> List[str]:
+    """
+    Render a finding's source lines like a rustc diagnostic, with carets under its span
+
+    The carets start at the finding's column and cover the first line of its
+    code snippet, or the rest of the line when the snippet isn't found there.
+
+    Args:
+        lines: The lines of the analyzed file
+        finding: The finding to show
+        context_lines: Lines to show before and after the span
+
+    Returns:
+        The rendered lines, or none if the finding's line isn't in the file
+    """
+    if not 0 < finding.line <= len(lines):
+        return []
+    line_end = min(max(finding.line_end or finding.line, finding.line), len(lines))
+    first = max(1, finding.line - context_lines)
+    last = min(len(lines), line_end + context_lines)
+    width = len(str(last))
+    gutter = " " * width
+
+    source_line = lines[finding.line - 1]
+    start = finding.column - 1 if finding.column else len(source_line) - len(source_line.lstrip())
+    snippet_line = finding.code_snippet.strip().split("\n")[0].strip()
+    if snippet_line and source_line.startswith(snippet_line, start):
+        end = start + len(snippet_line)
+    else:
+        end = len(source_line.rstrip())
+    # Tabs are shown as four spaces, so the carets are placed on the expanded text
+    caret_start = len(source_line[:start].expandtabs(4))
+    caret_length = max(1, len(source_line[start:end].expandtabs(4)))
+
+    rendered = [f"{gutter}--> {finding.file or ''}:{finding.line}:{start + 1}", f"{gutter} |"]
+    for number in range(first, last + 1):
+        if finding.line + SNIPPET_MAX_SPAN_LINES <= number <= line_end - 1:
+            if number == finding.line + SNIPPET_MAX_SPAN_LINES:
+                rendered.append(f"{gutter} | ...")
+            continue
+        rendered.append(f"{number:>{width}} | {lines[number - 1].expandtabs(4)}".rstrip())
+        if number == finding.line:
+            rendered.append(f"{gutter} | {' ' * caret_start}{'^' * caret_length}")
+    rendered.append(f"{gutter} |")
+    return rendered
+
+
+def _read_source_lines(file_path: str) -> Optional[List[str]]:
+    """Read the lines of an analyzed file for snippets, or None if it can't be read"""
+    try:
+        with open(file_path, 'r', encoding='utf-8') as f:
+            return f.read().splitlines()
+    except (OSError, UnicodeDecodeError):
+        return None
+
+
+def generate_json_report(findings: Iterable[Finding], summary: Optional[FindingSummary] = None,
+                         parse_errors: Optional[Iterable[ParseError]] = None) -> str:
+    """
+    Serialize findings to a versioned JSON report
+
+    Args:
+        findings: The findings to report, in any order
+        summary: Counts of the findings to include as a `summary` object
+        parse_errors: The files that could not be parsed, included as a `parse_errors` array
+
+    Returns:
+        The JSON report with a `schema_version` and the findings sorted by location and detector id
+    """
+    report = {
+        "schema_version": JSON_SCHEMA_VERSION,
+        "findings": [_finding_to_json(finding) for finding in sort_findings(findings)]
+    }
+    if parse_errors is not None:
+        report["parse_errors"] = [parse_error.to_dict() for parse_error in parse_errors]
+    if summary is not None:
+        report["summary"] = summary.to_dict()
+    return json.dumps(report, indent=2)
+
+
+def _finding_to_json(finding: Finding) -> Dict:
+    """Convert a finding to its entry in JSON reports"""
+    return {
+        "detector_id": finding.detector_id,
+        "severity": str(finding.severity),
+        "file": finding.file,
+        "line": finding.line,
+        "end_line": finding.line_end if finding.line_end is not None else finding.line,
+        "column": finding.column,
+        "message": finding.message,
+        "suggestion": finding.suggestion,
+        "confidence": str(finding.confidence) if finding.confidence is not None else None,
+        "code_snippet": finding.code_snippet,
+        "fix": finding.fix.to_dict() if finding.fix else None,
+    }
+
+
+def load_json_report(text: str) -> List[Finding]:
+    """
+    Parse findings back from a JSON report
+
+    Raises:
+        ValueError: If the report was written with an unsupported schema version
+    """
+    report = json.loads(text)
+    if report.get("schema_version") != JSON_SCHEMA_VERSION:
+        raise ValueError(f"Unsupported JSON report schema version: {report.get('schema_version')}")
+    return [
+        Finding(
+            detector_id=entry["detector_id"],
+            severity=Severity.parse(entry["severity"]),
+            message=entry["message"],
+            line=entry["line"],
+            column=entry["column"],
+            file=entry["file"],
+            suggestion=entry["suggestion"],
+            line_end=entry.get("end_line"),
+            confidence=Confidence.parse(entry["confidence"]) if entry.get("confidence") else None,
+            code_snippet=entry.get("code_snippet") or "",
+            fix=Fix.from_dict(entry["fix"]) if entry.get("fix") else None,
+        )
+        for entry in report["findings"]
+    ]
+
+
+def generate_sarif_report(findings: Iterable[Finding], detectors: Iterable,
+                          tool_version: Optional[str] = None,
+                          parse_errors: Optional[Iterable[ParseError]] = None) -> str:
+    """
+    Serialize findings to a SARIF 2.1.0 log, e.g. for GitHub code scanning
+
+    Args:
+        findings: The findings to report, in any order
+        detectors: The detectors that ran, each becoming a rule of the tool
+        tool_version: Version reported for the tool, defaults to the package version
+        parse_errors: The files that could not be parsed, reported as warning notifications of the invocation
+
+    Returns:
+        The SARIF log with one run holding a result per finding
+    """
+    rules: List[Dict] = []
+    rule_indexes: Dict[str, int] = {}
+
+    def add_rule(rule_id: str, name: str, description: str, severity: Severity) -> None:
+        rule_indexes[rule_id] = len(rules)
+        rules.append({
+            "id": rule_id,
+            "name": name,
+            "shortDescription": {"text": description},
+            "defaultConfiguration": {"level": SARIF_LEVELS[severity]},
+        })
+
+    for detector in detectors:
+        add_rule(detector.id, type(detector).__name__, detector.description, detector.default_severity)
+
+    results = []
+    for finding in sort_findings(findings):
+        if finding.detector_id not in rule_indexes:
+            # Issues added outside the detectors, such as the reentrancy feature check
+            add_rule(finding.detector_id, finding.detector_id, finding.message, finding.severity)
+        result = {
+            "ruleId": finding.detector_id,
+            "ruleIndex": rule_indexes[finding.detector_id],
+            "level": SARIF_LEVELS[finding.severity],
+            "message": {"text": finding.message},
+        }
+        if finding.file is not None:
+            # SARIF lines start at 1, project-level issues are reported on line 0
+            region = {"startLine": max(finding.line, 1)}
+            if finding.column:
+                region["startColumn"] = finding.column
+            region["endLine"] = max(finding.line_end if finding.line_end is not None else finding.line, 1)
+            result["locations"] = [{
+                "physicalLocation": {
+                    "artifactLocation": {"uri": finding.file.replace(os.sep, "/")},
+                    "region": region,
+                }
+            }]
+        results.append(result)
+
+    log = {
+        "$schema": SARIF_SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "stylus-analyzer",
+                    "version": tool_version if tool_version is not None else __version__,
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    }
+    notifications = [{
+        "level": "warning",
+        "message": {"text": f"Could not parse file: {parse_error.message}"},
+        "locations": [{
+            "physicalLocation": {
+                "artifactLocation": {"uri": (parse_error.file or "").replace(os.sep, "/")},
+                "region": {"startLine": parse_error.line, "startColumn": parse_error.column},
+            }
+        }],
+    } for parse_error in parse_errors or []]
+    if notifications:
+        log["runs"][0]["invocations"] = [{"executionSuccessful": True, "toolExecutionNotifications": notifications}]
+    return json.dumps(log, indent=2)
+
+
+def generate_security_report(findings: Iterable[Finding], detectors: Iterable,
+                             tool_version: Optional[str] = None,
+                             parse_errors: Optional[Iterable[ParseError]] = None) -> str:
+    """
+    Serialize findings to the normalized shape of security finding aggregators
+
+    Every finding has a stable `id`, a `title`, a lowercase `severity`, a
+    `location`, the `cwe` of its detector and a `description`, as described
+    by `SECURITY_REPORT_SCHEMA`.
+
+    Args:
+        findings: The findings to report, in any order
+        detectors: The detectors that ran, providing the title and CWE of their findings
+        tool_version: Version reported for the tool, defaults to the package version
+        parse_errors: The files that could not be parsed, included as a `parse_errors` array
+
+    Returns:
+        The security report with the findings sorted by location and detector id
+    """
+    cwes = {detector.id: detector.cwe for detector in detectors}
+    report = {
+        "schema_version": JSON_SCHEMA_VERSION,
+        "tool": {
+            "name": "stylus-analyzer",
+            "version": tool_version if tool_version is not None else __version__,
+        },
+        "findings": [],
+    }
+    for finding in sort_findings(findings):
+        cwe = cwes.get(finding.detector_id)
+        report["findings"].append({
+            "id": fingerprint(finding),
+            "title": finding.detector_id.replace("_", " ").capitalize(),
+            "severity": str(finding.severity).lower(),
+            "location": {
+                "file": finding.file.replace(os.sep, "/") if finding.file is not None else None,
+                "start_line": finding.line,
+                "end_line": finding.line_end if finding.line_end is not None else finding.line,
+                "column": finding.column,
+            },
+            "cwe": f"CWE-{cwe}" if cwe is not None else None,
+            "description": finding.message,
+            "detector_id": finding.detector_id,
+            "confidence": str(finding.confidence) if finding.confidence is not None else None,
+            "recommendation": finding.suggestion,
+        })
+    if parse_errors is not None:
+        report["parse_errors"] = [parse_error.to_dict() for parse_error in parse_errors]
+    return json.dumps(report, indent=2)
+
+
+def generate_markdown_report(findings: Iterable[Finding], summary: Optional[FindingSummary] = None,
+                             parse_errors: Optional[Iterable[ParseError]] = None,
+                             base_dir: Optional[str] = None) -> str:
+    """
+    Serialize findings to a Markdown document, e.g. for a pull request comment
+
+    Findings are grouped under a header per file, each with a table of its
+    findings followed by a collapsible `<details>` section per finding
+    holding the message, the code snippet and the suggestion.
+
+    Args:
+        findings: The findings to report, in any order
+        summary: Counts of the findings to open the document with
+        parse_errors: The files that could not be parsed, listed before the findings
+        base_dir: Directory the file paths are shown relative to
+
+    Returns:
+        The Markdown document
+    """
+    def display_path(path: Optional[str]) -> str:
+        if path is None:
+            return "Project"
+        return (os.path.relpath(path, base_dir) if base_dir else path).replace(os.sep, "/")
+
+    lines = ["# Stylus Analyzer Report", ""]
+    if summary is not None:
+        counts = ", ".join(f"{count} {severity}" for severity, count in summary.by_severity.items() if count)
+        files = "file" if summary.files_scanned == 1 else "files"
+        lines.append(f"Found {summary.total} {'issue' if summary.total == 1 else 'issues'} in "
+                     f"{summary.files_scanned} {files}{f' ({counts})' if counts else ''}.")
+        lines.append("")
+
+    parse_errors = list(parse_errors or [])
+    if parse_errors:
+        lines.extend(["## Files that could not be parsed", ""])
+        lines.extend(f"- `{display_path(parse_error.file)}:{parse_error.line}:{parse_error.column}`: "
+                     f"{_markdown_inline(parse_error.message)}" for parse_error in parse_errors)
+        lines.append("")
+
+    by_file: Dict[Optional[str], List[Finding]] = {}
+    for finding in sort_findings(findings):
+        by_file.setdefault(finding.file, []).append(finding)
+    if not by_file:
+        lines.extend(["No issues found.", ""])
+
+    for file_path in sorted(by_file, key=lambda path: display_path(path)):
+        file_findings = by_file[file_path]
+        lines.extend([f"## `{display_path(file_path)}`", "",
+                      "| Severity | Detector | Location | Message |",
+                      "| --- | --- | --- | --- |"])
+        for finding in file_findings:
+            lines.append(f"| {finding.severity} | `{finding.detector_id}` | {_markdown_location(finding)} | "
+                         f"{_markdown_inline(finding.message)} |")
+        lines.append("")
+        for finding in file_findings:
+            lines.extend(["<details>",
+                          f"<summary>{finding.severity}: <code>{finding.detector_id}</code> at "
+                          f"{_markdown_location(finding)}</summary>", "",
+                          finding.message, ""])
+            if finding.code_snippet:
+                snippet = _dedent_snippet(finding.code_snippet)
+                fence = "`" * max(3, _longest_backtick_run(snippet) + 1)
+                lines.extend([f"{fence}rust", snippet, fence, ""])
+            if finding.suggestion:
+                lines.extend([f"**Suggestion:** {finding.suggestion}", ""])
+            lines.extend(["</details>", ""])
+    return "\n".join(lines)
+
+
+def _markdown_location(finding: Finding) -> str:
+    """Format the lines of a finding, e.g. `line 4` or `lines 4-7`"""
+    line_end = finding.line_end if finding.line_end is not None else finding.line
+    return f"line {finding.line}" if line_end == finding.line else f"lines {finding.line}-{line_end}"
+
+
+def _dedent_snippet(snippet: str) -> str:
+    """
+    Remove the indentation the lines after the first carry from the source
+
+    A snippet starts at its node, so its first line has no indentation
+    while the following lines keep theirs, e.g. the arms and closing brace of
+    a `match`.
+    """
+    first, *rest = snippet.strip("\n").split("\n")
+    return "\n".join([first.strip(), *textwrap.dedent("\n".join(rest)).split("\n")]) if rest else first.strip()
+
+
+def _markdown_inline(text: str) -> str:
+    """Make text safe for a single Markdown table cell or list item"""
+    return " ".join(text.split()).replace("|", "\\|")
+
+
+def _longest_backtick_run(text: str) -> int:
+    """Get the length of the longest run of backticks, so a code fence can be made longer"""
+    longest = current = 0
+    for char in text:
+        current = current + 1 if char == "`" else 0
+        longest = max(longest, current)
+    return longest
+
+
+def generate_github_annotations(findings: Iterable[Finding], parse_errors: Optional[Iterable[ParseError]] = None,
+                                base_dir: Optional[str] = None) -> str:
+    """
+    Serialize findings to GitHub Actions workflow commands, shown as inline annotations of a pull request
+
+    Each finding becomes one `::error`, `::warning` or `::notice` line with
+    its file, lines and column, titled with the detector id. Files that could
+    not be parsed are reported as errors.
+
+    Args:
+        findings: The findings to report, in any order
+        parse_errors: The files that could not be parsed
+        base_dir: Directory the file paths are made relative to, usually the repository checkout
+
+    Returns:
+        The workflow command lines
+    """
+    def command(level: str, file_path: Optional[str], line: int, line_end: Optional[int], column: int,
+                title: str, message: str) -> str:
+        properties = []
+        if file_path is not None:
+            path = os.path.relpath(file_path, base_dir) if base_dir else file_path
+            properties.append(f"file={_escape_annotation_property(path.replace(os.sep, '/'))}")
+            # Project-level issues are reported on line 0 and annotate the whole file
+            if line > 0:
+                properties.append(f"line={line}")
+                if line_end is not None and line_end > line:
+                    properties.append(f"endLine={line_end}")
+                if column:
+                    properties.append(f"col={column}")
+        properties.append(f"title={_escape_annotation_property(title)}")
+        return f"::{level} {','.join(properties)}::{_escape_annotation_data(message)}"
+
+    lines = [command("error", parse_error.file, parse_error.line, None, parse_error.column, "parse_error",
+                     f"Could not parse file: {parse_error.message}") for parse_error in parse_errors or []]
+    for finding in sort_findings(findings):
+        message = f"{finding.message} {finding.suggestion}" if finding.suggestion else finding.message
+        lines.append(command(GITHUB_ANNOTATION_LEVELS[finding.severity], finding.file, finding.line,
+                             finding.line_end, finding.column, finding.detector_id, message))
+    return "\n".join(lines)
+
+
+def _escape_annotation_data(text: str) -> str:
+    """Escape the message of a workflow command"""
+    return text.replace("%", "%25").replace("\r", "%0D").replace("\n", "%0A")
+
+
+def _escape_annotation_property(text: str) -> str:
+    """Escape a property value of a workflow command, which also can't hold `:` or `,`"""
+    return _escape_annotation_data(text).replace(":", "%3A").replace(",", "%2C")
+
+
+def generate_junit_report(findings: Iterable[Finding], parse_errors: Optional[Iterable[ParseError]] = None,
+                          base_dir: Optional[str] = None) -> str:
+    """
+    Serialize findings to JUnit XML, which most CI systems render as test results
+
+    Each file with findings is a `testsuite` and each finding a `testcase`
+    holding an `<error>` for High and Critical findings and a `<failure>`
+    otherwise, with the message, location, snippet and suggestion. Files
+    that could not be parsed get a `parse_error` test case with an error.
+
+    Args:
+        findings: The findings to report, in any order
+        parse_errors: The files that could not be parsed
+        base_dir: Directory the file paths are shown relative to
+
+    Returns:
+        The JUnit XML document
+    """
+    def display_path(path: Optional[str]) -> str:
+        if path is None:
+            return "Project"
+        return (os.path.relpath(path, base_dir) if base_dir else path).replace(os.sep, "/")
+
+    cases: Dict[str, List[ET.Element]] = {}
+    for parse_error in parse_errors or []:
+        path = display_path(parse_error.file)
+        case = _junit_case(path, "parse_error", parse_error.line)
+        _junit_result(case, "error", f"Could not parse file: {parse_error.message}", "parse_error",
+                      [f"Location: {path}:{parse_error.line}:{parse_error.column}"])
+        cases.setdefault(path, []).append(case)
+    for finding in sort_findings(findings):
+        path = display_path(finding.file)
+        case = _junit_case(path, f"{finding.detector_id} at {_markdown_location(finding)}", finding.line)
+        details = [f"Severity: {finding.severity}"]
+        if finding.confidence is not None:
+            details.append(f"Confidence: {finding.confidence}")
+        details.append(f"Location: {path}:{finding.line}:{finding.column}")
+        if finding.code_snippet:
+            details.extend(["", _dedent_snippet(finding.code_snippet)])
+        if finding.suggestion:
+            details.extend(["", f"Suggestion: {finding.suggestion}"])
+        _junit_result(case, JUNIT_ELEMENTS[finding.severity], finding.message, finding.detector_id, details)
+        cases.setdefault(path, []).append(case)
+
+    root = ET.Element("testsuites", name="stylus-analyzer")
+    for path in sorted(cases):
+        suite = ET.SubElement(root, "testsuite", name=_xml_text(path))
+        suite.extend(cases[path])
+        _junit_counts(suite, cases[path])
+    _junit_counts(root, [case for path in cases for case in cases[path]])
+    ET.indent(root, space="  ")
+    return '<?xml version="1.0" encoding="UTF-8"?>\n' + ET.tostring(root, encoding="unicode") + "\n"
+
+
+def _junit_case(path: str, name: str, line: int) -> ET.Element:
+    """Create the test case of a finding or parse error, located in its file"""
+    case = ET.Element("testcase", name=_xml_text(name), classname=_xml_text(path))
+    if path != "Project":
+        case.set("file", _xml_text(path))
+        case.set("line", str(line))
+    return case
+
+
+def _junit_result(case: ET.Element, tag: str, message: str, result_type: str, details: List[str]) -> None:
+    """Add the `<failure>` or `<error>` of a test case, with the details as its text"""
+    result = ET.SubElement(case, tag, message=_xml_text(message), type=_xml_text(result_type))
+    result.text = _xml_text("\n".join([message, ""] + details))
+
+
+def _junit_counts(element: ET.Element, cases: List[ET.Element]) -> None:
+    """Set the test, failure and error counts of a test suite"""
+    element.set("tests", str(len(cases)))
+    element.set("failures", str(sum(case.find("failure") is not None for case in cases)))
+    element.set("errors", str(sum(case.find("error") is not None for case in cases)))
+
+
+def _xml_text(text: str) -> str:
+    """Drop the characters XML can't represent; ElementTree escapes the rest"""
+    return XML_INVALID_CHARACTERS.sub("", text)
+
+
+def format_finding_diff(diff: FindingDiff) -> str:
+    """
+    Format the added, removed and unchanged findings of two runs
+
+    Unchanged findings whose line moved show the line they had before.
+    """
+    def location(finding: Finding) -> str:
+        return f"{finding.file}:{finding.line}" if finding.file else f"line {finding.line}"
+
+    def entry(finding: Finding) -> str:
+        return f"  [{finding.severity}] {finding.detector_id} at {location(finding)}: {finding.message}"
+
+    lines = [f"Added ({len(diff.added)}):"]
+    lines.extend(entry(finding) for finding in diff.added)
+    lines.append(f"Removed ({len(diff.removed)}):")
+    lines.extend(entry(finding) for finding in diff.removed)
+    lines.append(f"Unchanged ({len(diff.unchanged)}):")
+    for old, new in diff.unchanged:
+        moved = f" (was line {old.line})" if old.line != new.line else ""
+        lines.append(f"  [{new.severity}] {new.detector_id} at {location(new)}{moved}")
+    lines.append(f"\n{len(diff.added)} added, {len(diff.removed)} removed, {len(diff.unchanged)} unchanged")
+    return "\n".join(lines)
+
+
+def generate_finding_diff_json(diff: FindingDiff) -> str:
+    """
+    Serialize the added, removed and unchanged findings of two runs to JSON
+
+    Entries are JSON report findings with their fingerprint. Unchanged
+    entries are the new finding with the line it had in the old run.
+    """
+    def entry(finding: Finding) -> Dict:
+        return {**_finding_to_json(finding), "fingerprint": fingerprint(finding)}
+
+    return json.dumps({
+        "schema_version": JSON_SCHEMA_VERSION,
+        "added": [entry(finding) for finding in diff.added],
+        "removed": [entry(finding) for finding in diff.removed],
+        "unchanged": [{**entry(new), "previous_line": old.line} for old, new in diff.unchanged],
+        "summary": {
+            "added": len(diff.added),
+            "removed": len(diff.removed),
+            "unchanged": len(diff.unchanged),
+        },
+    }, indent=2)
+
+
+def format_detector_list(detectors: Iterable) -> str:
+    """
+    Format detectors as a table of ids, default severities and descriptions
+
+    Args:
+        detectors: The detectors to list, in the order they run
+    """
+    detectors = list(detectors)
+    id_width = max((len(detector.id) for detector in detectors), default=0)
+    lines = [f"{'ID':<{id_width}}  {'SEVERITY':<8}  DESCRIPTION"]
+    for detector in detectors:
+        lines.append(f"{detector.id:<{id_width}}  {str(detector.default_severity):<8}  {detector.description}")
+    return "\n".join(lines)
+
+
+def generate_detector_list_json(detectors: Iterable) -> str:
+    """Serialize detector ids, default severities and descriptions to JSON"""
+    return json.dumps({
+        "schema_version": JSON_SCHEMA_VERSION,
+        "detectors": [
+            {
+                "id": detector.id,
+                "default_severity": str(detector.default_severity),
+                "default_confidence": str(detector.default_confidence),
+                "cwe": detector.cwe,
+                "description": detector.description,
+            }
+            for detector in detectors
+        ]
+    }, indent=2)
+
+
+def format_detector_explanation(detector) -> str:
+    """
+    Format the long-form documentation of a detector for `explain`
+
+    Detectors without documentation, such as most custom ones, are shown
+    with their one-line description only.
+    """
+    lines = [f"{detector.id} ({detector.default_severity}, {detector.default_confidence} confidence)",
+             detector.description]
+    doc = detector.documentation
+    if doc is None:
+        lines.extend(["", "No further documentation is available for this detector."])
+        return "\n".join(lines)
+    sections = [
+        ("What it catches", textwrap.fill(doc.catches, width=96)),
+        ("Why it matters", textwrap.fill(doc.rationale, width=96)),
+        ("Vulnerable", doc.vulnerable_example),
+        ("Fixed", doc.fixed_example),
+    ]
+    if doc.references:
+        sections.append(("References", "\n".join(f"- {reference}" for reference in doc.references)))
+    for title, body in sections:
+        lines.extend(["", f"{title}:", textwrap.indent(body, "    ")])
+    return "\n".join(lines)
+
+
+def generate_detector_explanation_json(detector) -> str:
+    """Serialize the long-form documentation of a detector to JSON"""
+    doc = detector.documentation
+    return json.dumps({
+        "schema_version": JSON_SCHEMA_VERSION,
+        "id": detector.id,
+        "default_severity": str(detector.default_severity),
+        "default_confidence": str(detector.default_confidence),
+        "cwe": detector.cwe,
+        "description": detector.description,
+        "documentation": None if doc is None else {
+            "catches": doc.catches,
+            "rationale": doc.rationale,
+            "vulnerable_example": doc.vulnerable_example,
+            "fixed_example": doc.fixed_example,
+            "references": doc.references,
+        },
+    }, indent=2)
 
 
 def generate_pdf_report(results, output_file: str) -> None:
@@ -109,7 +920,7 @@ def generate_pdf_report(results, output_file: str) -> None:
         ]
         
         # Group issues by severity across all files
-        severity_counts = {'Critical': 0, 'High': 0, 'Medium': 0, 'Low': 0}
+        severity_counts = {'Critical': 0, 'High': 0, 'Medium': 0, 'Low': 0, 'Info': 0}
         for file_result in results_dict.values():
             if isinstance(file_result, dict) and 'issues' in file_result:
                 for issue in file_result.get('issues', []):
@@ -143,7 +954,7 @@ def generate_pdf_report(results, output_file: str) -> None:
         summary_text = [f"Found {total_issues} total issues"]
         
         # Group issues by severity
-        severity_counts = {'Critical': 0, 'High': 0, 'Medium': 0, 'Low': 0}
+        severity_counts = {'Critical': 0, 'High': 0, 'Medium': 0, 'Low': 0, 'Info': 0}
         for issue in results_dict.get('issues', []):
             severity = issue.get('severity', 'Unknown')
             if severity in severity_counts:
@@ -171,7 +982,15 @@ def generate_pdf_report(results, output_file: str) -> None:
 def _add_file_analysis_to_pdf(elements, result, normal_style, code_style):
     """Helper function to add a single file's analysis to the PDF"""
     issues = result.get('issues', [])
-    
+    parse_error = result.get('parse_error')
+    if parse_error:
+        elements.append(Paragraph(
+            f"Could not parse the file at line {parse_error['line']}, column {parse_error['column']}: "
+            f"{parse_error['message']}. The file was not analyzed.",
+            normal_style
+        ))
+        return
+
     if not issues:
         elements.append(Paragraph("No issues found in this file.", normal_style))
         return
@@ -185,7 +1004,7 @@ def _add_file_analysis_to_pdf(elements, result, normal_style, code_style):
         issues_by_severity[severity].append(issue)
     
     # Add issues by severity (Critical to Low)
-    severities = ['Critical', 'High', 'Medium', 'Low']
+    severities = ['Critical', 'High', 'Medium', 'Low', 'Info']
     for severity in severities:
         if severity in issues_by_severity:
             elements.append(Paragraph(f"{severity} Severity Issues:", normal_style))
@@ -0,0 +1,332 @@
+"""
+Parser for the Solidity-like contract bodies of `sol!` and `sol_interface!` macros
+
+tree-sitter sees a macro body as an opaque token tree, so functions and their
+statements are recognized from token sequences such as
+`function <name>(...) ... { ... }`.
+"""
+from dataclasses import dataclass, field
+from typing import List, Optional, Tuple
+
+from tree_sitter import Node
+
+from stylus_analyzer.ast_utils import get_macro_name, iter_nodes, node_text, text_between
+
+# Macros whose bodies are written in Solidity syntax
+SOL_MACROS = ("sol", "sol_interface")
+
+# Keywords defining a function without the `function` keyword
+SPECIAL_FUNCTIONS = ("constructor", "receive", "fallback")
+
+# Data locations, which aren't part of a parameter's type
+DATA_LOCATIONS = ("memory", "calldata", "storage")
+
+STATE_MUTABILITIES = ("view", "pure", "payable")
+
+ASSIGNMENT_OPERATORS = ("=", "+=", "-=", "*=", "/=", "%=", "&=", "|=", "^=", "<<=", ">>=")
+
+# Statements classified by their first token
+STATEMENT_KINDS = {
+    "require": "require",
+    "assert": "require",
+    "revert": "revert",
+    "emit": "emit",
+    "return": "return",
+    "if": "control",
+    "else": "control",
+    "for": "control",
+    "while": "control",
+    "do": "control",
+}
+
+
+@dataclass
+class SolStatement:
+    """
+    A statement of a sol! function body.
+
+    `kind` is "require" (also for `assert`), "revert", "emit", "return",
+    "control" for block headers such as `if (x)`, "declaration" for
+    `uint256 x = ...`, "assignment" for `balanceOf[to] += value` and
+    "expression" otherwise. Assignments and declarations with an initializer
+    have their `target`, `operator` and `value` text set.
+    """
+    tokens: List[Node]
+    text: str
+    line: int
+    kind: str
+    target: Optional[str] = None
+    operator: Optional[str] = None
+    value: Optional[str] = None
+
+    def contains_token(self, token_type: str) -> bool:
+        """Check if the statement has a token of this type, e.g. "-", including inside parentheses"""
+        return any(node.type == token_type for token in self.tokens for node in iter_nodes(token))
+
+    @property
+    def is_subtraction(self) -> bool:
+        """Check if the statement subtracts, by `-=` or a binary or unary `-`"""
+        return self.operator == "-=" or self.contains_token("-")
+
+
+@dataclass
+class SolFunction:
+    """
+    A function defined in a sol! or sol_interface! macro.
+
+    Constructors, `receive` and `fallback` are named after their keyword.
+    `node` is the token the definition starts at and `modifiers` holds the
+    text of the tokens between the parameters and the body, e.g.
+    `["public", "returns", "(bool)"]`.
+    """
+    name: str
+    macro: str
+    node: Node
+    params: Node
+    body: Node
+    modifiers: List[str] = field(default_factory=list)
+    parameters: List[str] = field(default_factory=list)
+    statements: List[SolStatement] = field(default_factory=list)
+
+    @property
+    def line(self) -> int:
+        """1-based line the definition starts on"""
+        return self.node.start_point[0] + 1
+
+
+@dataclass
+class SolInterfaceFunction:
+    """
+    A function declared in a `sol_interface!` interface.
+
+    `parameter_types` and `return_types` hold the Solidity types such as
+    `address` or `uint256[]`, without data locations. `state_mutability` is
+    `view`, `pure` or `payable` when declared.
+    """
+    name: str
+    node: Node
+    parameter_types: List[str] = field(default_factory=list)
+    parameter_names: List[str] = field(default_factory=list)
+    return_types: List[str] = field(default_factory=list)
+    state_mutability: Optional[str] = None
+
+    @property
+    def signature(self) -> str:
+        """The Solidity signature, e.g. `transfer(address,uint256)`"""
+        return f"{self.name}({','.join(self.parameter_types)})"
+
+    @property
+    def returns_bool(self) -> bool:
+        """Check if the function returns a single `bool`, like ERC-20 `transfer`"""
+        return self.return_types == ["bool"]
+
+
+@dataclass
+class SolInterface:
+    """An interface declared in a `sol_interface!` macro"""
+    name: str
+    node: Node
+    functions: List[SolInterfaceFunction] = field(default_factory=list)
+
+    def get_function(self, name: str) -> Optional[SolInterfaceFunction]:
+        """Get the first function with this name, overloads being declared in source order"""
+        return next((function for function in self.functions if function.name == name), None)
+
+
+def parse_sol_functions(root: Node, code: str, macros: Tuple[str, ...] = ("sol",)) -> List[SolFunction]:
+    """
+    Parse the functions with a body defined in the given macros below a node
+
+    Functions without a body, such as interface declarations, are skipped.
+    """
+    functions = []
+    for node in iter_nodes(root):
+        if node.type != "macro_invocation":
+            continue
+        macro = get_macro_name(node, code)
+        if macro not in macros:
+            continue
+        for tree in iter_nodes(node):
+            if tree.type == "token_tree":
+                functions.extend(_parse_token_tree_functions(tree, code, macro))
+    return functions
+
+
+def parse_sol_interfaces(root: Node, code: str) -> List[SolInterface]:
+    """Parse the interfaces declared in the `sol_interface!` macros below a node"""
+    interfaces = []
+    for node in iter_nodes(root):
+        if node.type != "macro_invocation" or get_macro_name(node, code) != "sol_interface":
+            continue
+        for tree in iter_nodes(node):
+            if tree.type != "token_tree":
+                continue
+            tokens = tree.children
+            for index, token in enumerate(tokens[:-2]):
+                if token.type == "identifier" and node_text(token, code) == "interface" and \
+                        tokens[index + 1].type == "identifier" and is_token_tree(tokens[index + 2], "{"):
+                    interfaces.append(SolInterface(
+                        name=node_text(tokens[index + 1], code),
+                        node=token,
+                        functions=_parse_interface_functions(tokens[index + 2], code)
+                    ))
+    return interfaces
+
+
+def split_sol_statements(body: Node) -> List[List[Node]]:
+    """
+    Split a `{ ... }` token tree into statements, each a list of tokens.
+
+    Nested blocks (e.g. the body of an `if`) are split recursively, so the
+    statements are returned in source order with the block header, such as
+    `if (x)`, as a statement of its own.
+    """
+    statements = []
+    current = []
+    for token in body.children[1:-1]:
+        if token.type == ";":
+            if current:
+                statements.append(current)
+            current = []
+        elif is_token_tree(token, "{"):
+            if current:
+                statements.append(current)
+            current = []
+            statements.extend(split_sol_statements(token))
+        else:
+            current.append(token)
+    if current:
+        statements.append(current)
+    return statements
+
+
+def parse_sol_statement(tokens: List[Node], code: str) -> SolStatement:
+    """Classify a statement's tokens, splitting assignments into target, operator and value"""
+    text = text_between(code, tokens[0].start_byte, tokens[-1].end_byte)
+    statement = SolStatement(tokens=tokens, text=text, line=tokens[0].start_point[0] + 1,
+                             kind=STATEMENT_KINDS.get(node_text(tokens[0], code), "expression"))
+    if statement.kind != "expression":
+        return statement
+
+    index = next((index for index, token in enumerate(tokens) if token.type in ASSIGNMENT_OPERATORS), None)
+    if index is None or index == 0 or index == len(tokens) - 1:
+        return statement
+    statement.target = text_between(code, tokens[0].start_byte, tokens[index - 1].end_byte)
+    statement.operator = tokens[index].type
+    statement.value = text_between(code, tokens[index + 1].start_byte, tokens[-1].end_byte)
+    # `uint256 x = ...` and `uint256[] memory values = ...` end their left side with a type and a name
+    is_declaration = statement.operator == "=" and index >= 2 and tokens[index - 1].type == "identifier" and \
+        (tokens[index - 2].type == "identifier" or is_token_tree(tokens[index - 2], "["))
+    statement.kind = "declaration" if is_declaration else "assignment"
+    return statement
+
+
+def sol_parameter_names(params: Node, code: str) -> List[str]:
+    """Get the names of a parameter list such as `(address target, bytes calldata data)`, in order"""
+    names = []
+    previous = None
+    for token in params.children[1:-1] + [None]:
+        if (token is None or token.type == ",") and previous is not None and previous.type == "identifier":
+            names.append(node_text(previous, code))
+        previous = token
+    return names
+
+
+def is_token_tree(node: Node, opening: str) -> bool:
+    """Check if a node is a token tree opened by the given delimiter"""
+    return node.type == "token_tree" and bool(node.children) and node.children[0].type == opening
+
+
+def _parse_token_tree_functions(tree: Node, code: str, macro: str) -> List[SolFunction]:
+    """Parse the functions defined directly in a token tree"""
+    functions = []
+    tokens = tree.children
+    for index, token in enumerate(tokens):
+        if token.type != "identifier":
+            continue
+        keyword = node_text(token, code)
+        position = index + 1
+        if keyword == "function":
+            if position >= len(tokens) or tokens[position].type != "identifier":
+                continue
+            name = node_text(tokens[position], code)
+            position += 1
+        elif keyword in SPECIAL_FUNCTIONS:
+            if index > 0 and node_text(tokens[index - 1], code) == "function":
+                continue
+            name = keyword
+        else:
+            continue
+        if position >= len(tokens) or not is_token_tree(tokens[position], "("):
+            continue
+        params = tokens[position]
+        modifiers = []
+        body = None
+        for following in tokens[position + 1:]:
+            if is_token_tree(following, "{"):
+                body = following
+                break
+            if following.type == ";":
+                break
+            modifiers.append(node_text(following, code))
+        if body is None:
+            continue
+        functions.append(SolFunction(
+            name=name,
+            macro=macro,
+            node=token,
+            params=params,
+            body=body,
+            modifiers=modifiers,
+            parameters=sol_parameter_names(params, code),
+            statements=[parse_sol_statement(statement, code) for statement in split_sol_statements(body)]
+        ))
+    return functions
+
+
+def _parse_interface_functions(body: Node, code: str) -> List[SolInterfaceFunction]:
+    """Parse the function declarations of an interface body"""
+    functions = []
+    for statement in split_sol_statements(body):
+        if len(statement) < 3 or node_text(statement[0], code) != "function" or \
+                statement[1].type != "identifier" or not is_token_tree(statement[2], "("):
+            continue
+        parameter_types, parameter_names = _parse_parameter_list(statement[2], code)
+        function = SolInterfaceFunction(name=node_text(statement[1], code), node=statement[0],
+                                        parameter_types=parameter_types, parameter_names=parameter_names)
+        modifiers = statement[3:]
+        for index, token in enumerate(modifiers):
+            text = node_text(token, code)
+            if text in STATE_MUTABILITIES:
+                function.state_mutability = text
+            elif text == "returns" and index + 1 < len(modifiers) and is_token_tree(modifiers[index + 1], "("):
+                function.return_types = _parse_parameter_list(modifiers[index + 1], code)[0]
+        functions.append(function)
+    return functions
+
+
+def _parse_parameter_list(params: Node, code: str) -> Tuple[List[str], List[str]]:
+    """Split a parameter list such as `(address to, uint256[] calldata ids)` into types and names"""
+    types = []
+    names = []
+    parameters = [[]]
+    for token in params.children[1:-1]:
+        if token.type == ",":
+            parameters.append([])
+        else:
+            parameters[-1].append(token)
+    for parameter in parameters:
+        parameter = [token for token in parameter if node_text(token, code) not in DATA_LOCATIONS]
+        if not parameter:
+            continue
+        name = ""
+        if len(parameter) > 1 and parameter[-1].type == "identifier" and \
+                node_text(parameter[-1], code) != "payable":
+            name = node_text(parameter.pop(), code)
+        type_text = ""
+        for token in parameter:
+            text = node_text(token, code)
+            type_text += text if not type_text or is_token_tree(token, "[") else f" {text}"
+        types.append(type_text)
+        names.append(name)
+    return types, names
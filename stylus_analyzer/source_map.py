@@ -0,0 +1,92 @@
+"""
+Mapping of tree-sitter byte offsets to the text, lines and columns of a file
+"""
+import re
+from bisect import bisect_right
+from dataclasses import dataclass
+from functools import lru_cache
+from typing import List, Tuple
+
+from tree_sitter import Node
+
+
+@dataclass(frozen=True)
+class SourceSpan:
+    """
+    The location of a node in its file.
+
+    Lines and columns are 1-based and count characters, like the positions
+    of findings and edits, and the end is exclusive. The byte offsets are the
+    ones tree-sitter reports.
+    """
+    line: int
+    column: int
+    line_end: int
+    column_end: int
+    start_byte: int
+    end_byte: int
+
+
+class SourceMap:
+    """
+    Maps the byte offsets of a parsed file back to its text and positions.
+
+    tree-sitter parses the UTF-8 encoding of the code, so its offsets and the
+    columns of its points count bytes. Slicing the decoded text with them, or
+    using them as columns, goes wrong as soon as a multibyte character, such
+    as an accented letter in a comment or an emoji in a string, comes before
+    the node on its line or in the file.
+    """
+
+    def __init__(self, code: str):
+        self.code = code
+        self.data = code.encode("utf-8")
+        # Byte offset of the start of each line
+        self.line_starts: List[int] = [0] + [match.end() for match in re.finditer(b"\n", self.data)]
+
+    @classmethod
+    def of(cls, code: str) -> "SourceMap":
+        """Get the source map of a file's text, reusing the one built for the same text"""
+        return _source_map(code)
+
+    def text(self, start_byte: int, end_byte: int) -> str:
+        """Get the text between two byte offsets, or an empty string when they don't span any"""
+        start, end = max(start_byte, 0), min(end_byte, len(self.data))
+        if start >= end:
+            return ""
+        return self.data[start:end].decode("utf-8", errors="replace")
+
+    def node_text(self, node: Node) -> str:
+        """Get the source text of a node"""
+        return self.text(node.start_byte, node.end_byte)
+
+    def position(self, byte_offset: int) -> Tuple[int, int]:
+        """Get the 1-based line and character column of a byte offset"""
+        offset = min(max(byte_offset, 0), len(self.data))
+        row = bisect_right(self.line_starts, offset) - 1
+        return row + 1, self._column(row, offset - self.line_starts[row])
+
+    def point_position(self, point: Tuple[int, int]) -> Tuple[int, int]:
+        """Get the 1-based line and character column of a tree-sitter point, whose column counts bytes"""
+        row, byte_column = point
+        if row >= len(self.line_starts):
+            return row + 1, byte_column + 1
+        return row + 1, self._column(row, byte_column)
+
+    def span(self, node: Node) -> SourceSpan:
+        """Get the location of a node"""
+        line, column = self.point_position(node.start_point)
+        line_end, column_end = self.point_position(node.end_point)
+        return SourceSpan(line=line, column=column, line_end=line_end, column_end=column_end,
+                          start_byte=node.start_byte, end_byte=node.end_byte)
+
+    def _column(self, row: int, byte_column: int) -> int:
+        """Convert a byte column on a line to a 1-based character column"""
+        start = self.line_starts[row]
+        return len(self.data[start:start + byte_column].decode("utf-8", errors="ignore")) + 1
+
+
+@lru_cache(maxsize=16)
+def _source_map(code: str) -> SourceMap:
+    """Build the source map of a file's text, once per text while it is being analyzed"""
+    return SourceMap(code)
@@ -2,18 +2,23 @@
 Static analyzer for Stylus Rust contracts
 """
 import logging
-from typing import Dict, List, Optional, Any, Set, Tuple
+from concurrent.futures import ProcessPoolExecutor
+from dataclasses import dataclass, field
+from typing import Dict, List, Optional, Any, Set, Tuple, Union
 import time
 import subprocess
 import os
 
 
+from stylus_analyzer.cache import AnalysisCache
 from stylus_analyzer.file_utils import generate_rust_ast, read_file_content
-from stylus_analyzer.detectors import AVAILABLE_DETECTORS
+from stylus_analyzer.detectors.detector_base import AnalysisContext
+from stylus_analyzer.detectors.registry import DetectorRegistry
+from stylus_analyzer.detectors.traversal import run_node_detectors
+from stylus_analyzer.findings import Confidence, Finding, Fix, ParseError, Severity, sort_findings
+from stylus_analyzer.source_map import SourceMap
+from stylus_analyzer.suppressions import Suppression, apply_suppressions, collect_suppressions
 
-# Configure logging
-logging.basicConfig(level=logging.INFO,
-                    format='%(asctime)s - %(name)s - %(levelname)s - %(message)s')
 logger = logging.getLogger(__name__)
 
 
@@ -21,28 +26,44 @@ class StaticAnalysisResult:
     """Class to represent static analysis results"""
 
     def __init__(self):
-        self.issues: List[Dict[str, Any]] = []
+        self.findings: List[Finding] = []
         self.errors: List[Dict[str, str]] = []
+        self.unused_suppressions: List[Suppression] = []
+        # Set instead of any findings when the file fails to parse
+        self.parse_error: Optional[ParseError] = None
         self.analysis_time: float = 0
+        # Seconds each detector took, by detector id; empty for results read from the cache
+        self.detector_times: Dict[str, float] = {}
+
+    @property
+    def issues(self) -> List[Dict[str, Any]]:
+        """The findings as issue dictionaries, in the order they were reported"""
+        return [finding.to_dict() for finding in self.findings]
 
     def add_issue(self,
                   issue_type: str,
-                  severity: str,
+                  severity: Union[str, Severity],
                   description: str,
                   line_start: int,
                   line_end: int,
                   code_snippet: str,
-                  recommendation: str):
-        """Add an issue to the results"""
-        self.issues.append({
-            "type": issue_type,
-            "severity": severity,
-            "description": description,
-            "line_start": line_start,
-            "line_end": line_end,
-            "code_snippet": code_snippet,
-            "recommendation": recommendation
-        })
+                  recommendation: str,
+                  confidence: Optional[Union[str, Confidence]] = None,
+                  column: int = 0,
+                  fix: Optional[Fix] = None):
+        """Add an issue to the results, optionally flagging how certain the finding is and how to fix it"""
+        self.findings.append(Finding(
+            detector_id=issue_type,
+            severity=Severity.parse(severity),
+            message=description,
+            line=line_start,
+            column=column,
+            suggestion=recommendation,
+            line_end=line_end,
+            code_snippet=code_snippet,
+            confidence=Confidence.parse(confidence) if confidence is not None else None,
+            fix=fix
+        ))
 
     def add_error(self, detector_name: str, error_message: str):
         """Add an error that occurred during analysis"""
@@ -53,7 +74,7 @@ class StaticAnalysisResult:
 
     def has_issues(self) -> bool:
         """Check if there are any issues"""
-        return len(self.issues) > 0
+        return len(self.findings) > 0
 
     def has_errors(self) -> bool:
         """Check if there were any errors during analysis"""
@@ -62,21 +83,56 @@ class StaticAnalysisResult:
     def to_dict(self) -> Dict[str, Any]:
         """Convert to dictionary for serialization"""
         return {
-            "issues": self.issues,
-            "total_issues": len(self.issues),
+            "issues": [finding.to_dict() for finding in sort_findings(self.findings)],
+            "total_issues": len(self.findings),
             "errors": self.errors,
+            "parse_error": self.parse_error.to_dict() if self.parse_error else None,
             "analysis_time_seconds": self.analysis_time
         }
 
 
+@dataclass
+class DetectorProfile:
+    """
+    Wall-clock time each detector spent across the analyzed files, by detector id.
+
+    Results read from the cache carry no timings and add nothing, so profile
+    runs should re-analyze every file.
+    """
+    times: Dict[str, float] = field(default_factory=dict)
+    # Files each detector ran on
+    runs: Dict[str, int] = field(default_factory=dict)
+
+    def add(self, result: StaticAnalysisResult) -> None:
+        """Add the detector timings of a file's analysis"""
+        for detector_id, elapsed in result.detector_times.items():
+            self.times[detector_id] = self.times.get(detector_id, 0.0) + elapsed
+            self.runs[detector_id] = self.runs.get(detector_id, 0) + 1
+
+    def entries(self) -> List[Tuple[str, float, int]]:
+        """The detector ids with their total time and number of files, slowest first"""
+        return [(detector_id, self.times[detector_id], self.runs[detector_id])
+                for detector_id in sorted(self.times, key=lambda detector_id: (-self.times[detector_id], detector_id))]
+
+    @property
+    def total(self) -> float:
+        """Seconds spent in all detectors together"""
+        return sum(self.times.values())
+
+
 class StaticAnalyzer:
     """Main static analyzer that manages detectors and runs analysis"""
 
     def __init__(self):
-        self.detectors = []
-
         # Register built-in detectors
-        self._register_default_detectors()
+        self.registry = DetectorRegistry.with_builtin_detectors()
+        # Reuses results of unchanged files across runs when set
+        self.cache: Optional[AnalysisCache] = None
+
+    @property
+    def detectors(self) -> List:
+        """The enabled detectors, in the order they run"""
+        return self.registry.enabled_detectors()
 
     def _preprocess_with_cargo_expand(self, file_path: str) -> Optional[str]:
         """Preprocess the Rust code with cargo expand to handle macros"""
@@ -93,14 +149,9 @@ class StaticAnalyzer:
                 f"Failed to expand macros: {e}. Falling back to original code.")
             return read_file_content(file_path)
 
-    def _register_default_detectors(self):
-        """Register the default set of detectors"""
-        for detector_class in AVAILABLE_DETECTORS:
-            self.detectors.append(detector_class())
-
     def register_detector(self, detector):
         """Register a new detector"""
-        self.detectors.append(detector)
+        self.registry.register(detector)
 
     def analyze(self, code: str, file_path: Optional[str] = None) -> StaticAnalysisResult:
         """
@@ -133,31 +184,97 @@ class StaticAnalyzer:
             results.analysis_time = time.time() - start_time
             return results
 
-        # Run all detectors
+        results.parse_error = find_parse_error(tree, code, file_path)
+        if results.parse_error:
+            logger.warning(results.parse_error.describe())
+            results.analysis_time = time.time() - start_time
+            return results
+
+        # Run all enabled detectors, those looking at one node at a time over a single shared traversal
+        context = AnalysisContext(tree=tree, code=code, file_path=file_path)
+        shared = run_node_detectors([detector for detector in self.detectors if detector.node_types], context)
         for detector in self.detectors:
+            started = time.perf_counter()
             try:
-                detector.detect(tree, code, results)
+                findings = shared[detector.id].result() if detector.id in shared else detector.run(context)
+                override = self.registry.severity_override(detector.id)
+                if override is not None:
+                    for finding in findings:
+                        finding.severity = override
+                results.findings.extend(findings)
             except Exception as e:
                 error_msg = f"Error in detector {detector.name}: {str(e)}"
                 logger.error(error_msg)
                 results.add_error(detector.name, str(e))
+            results.detector_times[detector.id] = shared[detector.id].elapsed if detector.id in shared \
+                else time.perf_counter() - started
+        results.findings = drop_superseded_findings(results.findings, self.detectors)
 
         # Record analysis time
         results.analysis_time = time.time() - start_time
+        if logger.isEnabledFor(logging.DEBUG):
+            logger.debug(f"Analyzed {file_path or 'code'} in {results.analysis_time * 1000:.1f} ms")
+            for detector_id, elapsed in sorted(results.detector_times.items(), key=lambda item: -item[1]):
+                logger.debug(f"  {detector_id}: {elapsed * 1000:.2f} ms")
 
         # Check for reentrancy feature
-        if self.check_reentrancy_feature(os.path.dirname(file_path)):
+        if file_path and self.check_reentrancy_feature(os.path.dirname(file_path)):
             results.add_issue(
                 "reentrancy_feature",
-                "Warning",
+                "High",
                 "Reentrancy feature is disabled for stylus-sdk.",
                 0,
                 0,
                 "",
-                "Consider removing the reentrant feature with caution."
+                "Consider removing the reentrant feature with caution.",
+                confidence=Confidence.HIGH
             )
+
+        # Drop findings silenced by stylus-analyzer-disable comments
+        suppressions = collect_suppressions(tree, code)
+        results.findings = apply_suppressions(results.findings, suppressions)
+        results.unused_suppressions = [suppression for suppression in suppressions if not suppression.used]
         return results
 
+    def analyze_file(self, file_path: str) -> Optional[StaticAnalysisResult]:
+        """Read and analyze a file, or return None if it can't be read"""
+        code = read_file_content(file_path)
+        if not code:
+            return None
+        if self.cache is None:
+            return self.analyze(code, file_path)
+
+        key = self.cache.key(code, file_path, self)
+        result = self.cache.get(key, file_path)
+        if result is not None:
+            logger.debug(f"Using cached analysis of {file_path}")
+            return result
+        result = self.analyze(code, file_path)
+        self.cache.put(key, result)
+        return result
+
+    def analyze_files(self, file_paths: List[str], jobs: Optional[int] = None) -> Dict[str, Optional[StaticAnalysisResult]]:
+        """
+        Analyze several files, in parallel worker processes when there is more than one job
+
+        Args:
+            file_paths: The files to analyze
+            jobs: Number of worker processes, defaults to the number of CPUs
+
+        Returns:
+            The result of each file, None for files that couldn't be read, in the order
+            of `file_paths` regardless of which worker finished first
+        """
+        jobs = jobs or os.cpu_count() or 1
+        if jobs == 1 or len(file_paths) <= 1:
+            return {file_path: self.analyze_file(file_path) for file_path in file_paths}
+        workers = min(jobs, len(file_paths))
+        logger.debug(f"Analyzing {len(file_paths)} files with {workers} worker processes")
+        # Each worker gets its own copy of the analyzer, so detectors keeping state can't interfere
+        with ProcessPoolExecutor(max_workers=workers) as executor:
+            results = executor.map(_analyze_file_in_worker, [self] * len(file_paths), file_paths)
+            return dict(zip(file_paths, results))
+
     def check_reentrancy_feature(self, directory: str) -> bool:
         """Check if the stylus-sdk dependency with reentrant feature is present in Cargo.toml."""
         cargo_toml_path = os.path.join(directory, 'Cargo.toml')
@@ -171,3 +288,51 @@ class StaticAnalyzer:
                     if 'reentrant' in line:
                         return True  # Found stylus-sdk with reentrant feature
         return False  # Not found
+
+
+def drop_superseded_findings(findings: List[Finding], detectors: List) -> List[Finding]:
+    """Drop the findings of broader detectors that a detector superseding them reported at the same line and snippet"""
+    superseded = set()
+    for detector in detectors:
+        if detector.supersedes:
+            superseded.update((detector_id, finding.line, finding.code_snippet)
+                              for finding in findings if finding.detector_id == detector.id
+                              for detector_id in detector.supersedes)
+    return [finding for finding in findings
+            if (finding.detector_id, finding.line, finding.code_snippet) not in superseded]
+
+
+def find_parse_error(tree, code: str, file_path: Optional[str] = None) -> Optional[ParseError]:
+    """
+    Locate the first syntax error of a parsed file, or return None if it parsed cleanly
+
+    tree-sitter recovers from syntax errors, wrapping the text it could not
+    parse in ERROR nodes and inserting the tokens it expected as missing nodes.
+    """
+    if not tree.root_node.has_error:
+        return None
+    stack = [tree.root_node]
+    while stack:
+        node = stack.pop()
+        if node.type == "ERROR" or node.is_missing:
+            break
+        stack.extend(reversed([child for child in node.children if child.has_error or child.is_missing]))
+    else:
+        node = tree.root_node
+    source_map = SourceMap.of(code)
+    line, column = source_map.point_position(node.start_point)
+    if node.is_missing:
+        message = f"expected `{node.type}`"
+    else:
+        # The first token tree-sitter could not fit
+        token = node
+        while token.children:
+            token = token.children[0]
+        text = source_map.node_text(token).strip().split("\n")[0]
+        message = f"unexpected `{text[:40]}{'...' if len(text) > 40 else ''}`" if text else "unexpected end of file"
+    return ParseError(message=message, line=line, column=column, file=file_path)
+
+
+def _analyze_file_in_worker(analyzer: StaticAnalyzer, file_path: str) -> Optional[StaticAnalysisResult]:
+    """Analyze a file in a worker process of `StaticAnalyzer.analyze_files`"""
+    return analyzer.analyze_file(file_path)
@@ -0,0 +1,145 @@
+"""
+Storage layout of a contract: its storage structs and their fields
+"""
+import re
+from dataclasses import dataclass, field
+from typing import Dict, List, Optional
+
+from tree_sitter import Node
+
+from stylus_analyzer.ast_utils import get_macro_name, iter_nodes, node_text
+from stylus_analyzer.sol_parser import is_token_tree, split_sol_statements
+from stylus_analyzer.stylus_attributes import get_item_flags
+
+# `Storage<U256>`, the generic wrapper form of a storage field type
+STORAGE_WRAPPER_PATTERN = re.compile(r"^Storage\s*<\s*(.+)\s*>$", re.DOTALL)
+
+
+@dataclass
+class StorageField:
+    """A field of a storage struct, with the text of its declared type"""
+    name: str
+    type: str
+    struct: str
+    node: Node
+
+    @property
+    def line(self) -> int:
+        """1-based line the field is declared on"""
+        return self.node.start_point[0] + 1
+
+    @property
+    def value_type(self) -> str:
+        """The stored type, unwrapping the `Storage<T>` form, e.g. `U256` for `Storage<U256>`"""
+        match = STORAGE_WRAPPER_PATTERN.match(self.type)
+        return match.group(1).strip() if match else self.type
+
+
+@dataclass
+class StorageStruct:
+    """
+    A storage struct.
+
+    `node` is where the declaration starts, the struct item for Rust structs
+    and the `struct` keyword inside `sol_storage!`, and `body` is its field list.
+    Structs imported from another file of the crate have that `file` set, and
+    their nodes belong to its tree rather than the analyzed file's.
+    """
+    name: str
+    node: Node
+    body: Node
+    fields: List[StorageField] = field(default_factory=list)
+    file: Optional[str] = None
+
+
+@dataclass
+class StorageLayout:
+    """
+    The storage structs of a contract in source order, with their fields looked up by name.
+
+    `imported` holds the storage structs declared in other files of the crate
+    that the file's impl blocks implement, see `resolve_imported_structs`.
+    """
+    structs: List[StorageStruct] = field(default_factory=list)
+    imported: List[StorageStruct] = field(default_factory=list)
+
+    @property
+    def fields(self) -> Dict[str, StorageField]:
+        """
+        Map field names to fields; a name declared in several structs maps to the
+        last one, with the file's own structs coming after the imported ones
+        """
+        return {storage_field.name: storage_field for struct in self.imported + self.structs
+                for storage_field in struct.fields}
+
+    def get_field(self, name: str) -> Optional[StorageField]:
+        """Get a storage field by name"""
+        return self.fields.get(name)
+
+    def get_struct(self, name: str) -> Optional[StorageStruct]:
+        """Get a storage struct by name"""
+        return next((struct for struct in self.structs if struct.name == name), None)
+
+    def __contains__(self, name: str) -> bool:
+        return name in self.fields
+
+
+def collect_storage_layout(root: Node, code: str) -> StorageLayout:
+    """
+    Collect the storage structs below a node.
+
+    Covers Rust structs annotated with `#[storage]`, `#[entrypoint]` or
+    `#[public]`, whose fields are either SDK types such as `StorageU256` or
+    wrapped as `Storage<U256>`, and Solidity-style declarations inside
+    `sol_storage!`.
+    """
+    layout = StorageLayout()
+    for node in iter_nodes(root):
+        if node.type == "struct_item":
+            struct = _collect_rust_struct(node, code)
+            if struct:
+                layout.structs.append(struct)
+        elif node.type == "macro_invocation" and get_macro_name(node, code) == "sol_storage":
+            layout.structs.extend(_collect_sol_storage_structs(node, code))
+    return layout
+
+
+def _collect_rust_struct(node: Node, code: str) -> Optional[StorageStruct]:
+    """Collect a Rust struct item if it is annotated as storage"""
+    if not get_item_flags(node, code).storage:
+        return None
+    name = node.child_by_field_name("name")
+    body = node.child_by_field_name("body")
+    if not name or not body:
+        return None
+    struct = StorageStruct(name=node_text(name, code), node=node, body=body)
+    for declaration in body.named_children:
+        if declaration.type != "field_declaration":
+            continue
+        field_name = declaration.child_by_field_name("name")
+        field_type = declaration.child_by_field_name("type")
+        if field_name and field_type:
+            struct.fields.append(StorageField(name=node_text(field_name, code), type=node_text(field_type, code),
+                                              struct=struct.name, node=declaration))
+    return struct
+
+
+def _collect_sol_storage_structs(macro: Node, code: str) -> List[StorageStruct]:
+    """Collect the structs declared in a `sol_storage!` macro, e.g. `struct Token { uint256 total; }`"""
+    structs = []
+    for tree in iter_nodes(macro):
+        if not is_token_tree(tree, "{") or tree.parent.type == "macro_invocation":
+            continue
+        name = tree.prev_sibling
+        keyword = name.prev_sibling if name else None
+        if not (name and keyword and name.type == "identifier" and node_text(keyword, code) == "struct"):
+            continue
+        struct = StorageStruct(name=node_text(name, code), node=keyword, body=tree)
+        for statement in split_sol_statements(tree):
+            if len(statement) < 2 or statement[-1].type != "identifier":
+                continue
+            type_text = " ".join(node_text(token, code) for token in statement[:-1])
+            struct.fields.append(StorageField(name=node_text(statement[-1], code), type=type_text,
+                                              struct=struct.name, node=statement[0]))
+        structs.append(struct)
+    return structs
@@ -0,0 +1,177 @@
+"""
+Recognition of the Stylus SDK attributes on structs, impls and methods, across SDK versions
+"""
+import re
+from dataclasses import dataclass
+from functools import lru_cache
+from typing import Dict, FrozenSet, List, Optional, Tuple
+
+from tree_sitter import Node
+
+from stylus_analyzer.ast_utils import get_parent_impl, node_text
+
+# Attributes that expose a method or impl block to external callers
+PUBLIC_ATTRIBUTES = ("public", "external")
+
+# Attributes marking a Rust struct as contract storage
+STORAGE_ATTRIBUTES = ("storage", "entrypoint") + PUBLIC_ATTRIBUTES
+
+# Crates the attribute macros are exported from, directly or through `stylus_sdk::prelude`
+STYLUS_CRATES = ("stylus_sdk", "stylus_proc")
+
+# Spellings of older SDK versions, by the name they are recognized under
+LEGACY_ATTRIBUTES = {"solidity_storage": "storage"}
+
+# Attribute macros of the SDK, which `use` aliases and re-exports are resolved to
+STYLUS_ATTRIBUTES = ("storage", "entrypoint", "public", "external", "payable", "constructor", "receive", "fallback",
+                     "selector", "inherit", "implements") + tuple(LEGACY_ATTRIBUTES)
+
+# `use stylus_sdk::prelude::{public as export, storage}`, `use stylus_sdk::prelude::public as export`
+# and `use stylus_sdk::prelude as sdk`
+USE_PATTERN = re.compile(
+    r"\buse\s+(?:::)?((?:" + "|".join(STYLUS_CRATES) + r")(?:\s*::\s*\w+)*)\s*(?:::\s*\{([^{}]*)\}|as\s+(\w+))"
+)
+USE_AS_PATTERN = re.compile(r"^(\w+)\s+as\s+(\w+)$")
+
+
+@dataclass(frozen=True)
+class ItemFlags:
+    """
+    What the Stylus attributes of an item make of it.
+
+    `attributes` are the normalized attribute names, see
+    `get_attribute_names`. `external_callable` is set for `#[public]` and
+    `#[external]` impls, and for their `pub` methods or methods with such
+    an attribute of their own. `storage` is set for structs laid out as
+    contract storage. The other flags follow the attribute of the same name.
+    """
+    attributes: Tuple[str, ...] = ()
+    storage: bool = False
+    entrypoint: bool = False
+    external_callable: bool = False
+    payable: bool = False
+    constructor: bool = False
+    receive: bool = False
+    fallback: bool = False
+
+
+@dataclass(frozen=True)
+class AttributeAliases:
+    """Names a file imports SDK attributes and modules under, e.g. `export` for `use ...::public as export`"""
+    attributes: Dict[str, str]
+    modules: FrozenSet[str]
+
+
+def get_attribute_names(node: Node, code: str) -> List[str]:
+    """
+    Get the names of the outer attributes (e.g. `#[public]`) attached to an item.
+
+    tree-sitter places attributes as siblings preceding the item, so walk
+    backwards over attribute items and comments. Names are normalized so
+    the SDK versions look alike: paths through the SDK such as
+    `#[stylus_sdk::prelude::public]` become their last segment, attributes
+    imported under another name become the SDK's, the attributes of
+    `#[cfg_attr(condition, ...)]` are included, and legacy spellings such
+    as `#[solidity_storage]` become the current ones. Paths into other
+    crates are kept whole, so `#[serde::storage]` isn't taken for storage.
+    """
+    aliases = collect_attribute_aliases(code)
+    names = []
+    sibling = node.prev_sibling
+    while sibling and sibling.type in ("attribute_item", "line_comment", "block_comment"):
+        if sibling.type == "attribute_item":
+            for child in sibling.children:
+                if child.type == "attribute" and child.children:
+                    path = node_text(child.children[0], code)
+                    if path.split("::")[-1].strip() == "cfg_attr" and len(child.children) > 1:
+                        arguments = _split_arguments(node_text(child.children[-1], code).strip()[1:-1])
+                        names.extend(normalize_attribute(re.split(r"[\s(=\[]", argument.strip(), 1)[0], aliases)
+                                     for argument in arguments[1:] if argument.strip())
+                    else:
+                        names.append(normalize_attribute(path, aliases))
+        sibling = sibling.prev_sibling
+    return names
+
+
+def normalize_attribute(path: str, aliases: Optional[AttributeAliases] = None) -> str:
+    """Get the name an attribute path is recognized under, e.g. `public` for `stylus_sdk::prelude::public`"""
+    segments = [segment.strip() for segment in path.split("::") if segment.strip()]
+    if not segments:
+        return ""
+    if len(segments) == 1:
+        name = aliases.attributes.get(segments[0], segments[0]) if aliases is not None else segments[0]
+    elif segments[0] in STYLUS_CRATES or segments[0] == "crate" or \
+            (aliases is not None and segments[0] in aliases.modules):
+        name = segments[-1]
+    else:
+        return "::".join(segments)
+    return LEGACY_ATTRIBUTES.get(name, name)
+
+
+@lru_cache(maxsize=16)
+def collect_attribute_aliases(code: str) -> AttributeAliases:
+    """Find the names a file's `use` declarations give SDK attributes and modules"""
+    attributes: Dict[str, str] = {}
+    modules = set()
+    for match in USE_PATTERN.finditer(code):
+        path = [segment.strip() for segment in match.group(1).split("::")]
+        if match.group(3):
+            if path[-1] in STYLUS_ATTRIBUTES:
+                attributes[match.group(3)] = path[-1]
+            else:
+                modules.add(match.group(3))
+            continue
+        for item in match.group(2).split(","):
+            renamed = USE_AS_PATTERN.match(item.strip())
+            if renamed is None:
+                continue
+            if renamed.group(1) in STYLUS_ATTRIBUTES:
+                attributes[renamed.group(2)] = renamed.group(1)
+            else:
+                modules.add(renamed.group(2))
+    return AttributeAliases(attributes=attributes, modules=frozenset(modules))
+
+
+def get_item_flags(node: Node, code: str) -> ItemFlags:
+    """Get the flags the Stylus attributes of a struct, impl or function item set"""
+    names = get_attribute_names(node, code)
+    external_callable = any(name in PUBLIC_ATTRIBUTES for name in names)
+    if node.type == "function_item" and not external_callable:
+        external_callable = is_public_method(node, code)
+    return ItemFlags(
+        attributes=tuple(names),
+        storage=node.type == "struct_item" and any(name in STORAGE_ATTRIBUTES for name in names),
+        entrypoint="entrypoint" in names,
+        external_callable=external_callable,
+        payable="payable" in names,
+        constructor="constructor" in names,
+        receive="receive" in names,
+        fallback="fallback" in names,
+    )
+
+
+def is_public_method(node: Node, code: str) -> bool:
+    """Check if a function item is an externally callable contract method"""
+    if any(attr in PUBLIC_ATTRIBUTES for attr in get_attribute_names(node, code)):
+        return True
+    impl_node = get_parent_impl(node)
+    if not impl_node:
+        return False
+    if not any(attr in PUBLIC_ATTRIBUTES for attr in get_attribute_names(impl_node, code)):
+        return False
+    return any(child.type == "visibility_modifier" for child in node.children)
+
+
+def _split_arguments(text: str) -> List[str]:
+    """Split attribute arguments such as `not(test), public, selector(name = "x")` at their top-level commas"""
+    arguments, depth, start = [], 0, 0
+    for index, char in enumerate(text):
+        if char in "([{":
+            depth += 1
+        elif char in ")]}":
+            depth -= 1
+        elif char == "," and depth == 0:
+            arguments.append(text[start:index])
+            start = index + 1
+    arguments.append(text[start:])
+    return arguments
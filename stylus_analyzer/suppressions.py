@@ -0,0 +1,81 @@
+"""
+Inline suppression comments for Stylus Analyzer findings
+"""
+import re
+from dataclasses import dataclass, field
+from typing import Iterable, Iterator, List
+
+from tree_sitter import Node, Tree
+
+from stylus_analyzer.ast_utils import text_between
+from stylus_analyzer.findings import Finding
+
+# `// stylus-analyzer-disable-next-line unsafe_unwrap, unsafe_panic -- reason`
+SUPPRESSION_PATTERN = re.compile(
+    r"stylus-analyzer-disable-(next-line|line|file)\b([^\n]*?)(?:\s--.*)?(?:\*/)?\s*$"
+)
+
+COMMENT_TYPES = ("line_comment", "block_comment")
+
+
+@dataclass
+class Suppression:
+    """
+    A suppression comment.
+
+    `scope` is "line" for the comment's own line, "next-line" for the line
+    after it, or "file". Without detector ids, every detector is suppressed.
+    """
+    scope: str
+    line: int
+    detector_ids: List[str] = field(default_factory=list)
+    used: bool = False
+
+    def matches(self, finding: Finding) -> bool:
+        """Check if the suppression covers a finding"""
+        if self.detector_ids and finding.detector_id not in self.detector_ids:
+            return False
+        if self.scope == "file":
+            return True
+        target_line = self.line + 1 if self.scope == "next-line" else self.line
+        return finding.line == target_line
+
+    def describe(self) -> str:
+        """Describe the suppression comment, e.g. for an unused suppression warning"""
+        ids = ", ".join(self.detector_ids) if self.detector_ids else "all detectors"
+        return f"stylus-analyzer-disable-{self.scope} ({ids}) on line {self.line}"
+
+
+def collect_suppressions(tree: Tree, code: str) -> List[Suppression]:
+    """Collect the suppression comments of a parsed file"""
+    suppressions = []
+    for node in _iter_comments(tree.root_node):
+        text = text_between(code, node.start_byte, node.end_byte)
+        match = SUPPRESSION_PATTERN.search(text)
+        if not match:
+            continue
+        detector_ids = [detector_id for detector_id in re.split(r"[\s,]+", match.group(2)) if detector_id]
+        suppressions.append(Suppression(scope=match.group(1), line=node.start_point[0] + 1,
+                                        detector_ids=detector_ids))
+    return suppressions
+
+
+def apply_suppressions(findings: Iterable[Finding], suppressions: List[Suppression]) -> List[Finding]:
+    """Drop the suppressed findings, marking each suppression that matched as used"""
+    kept = []
+    for finding in findings:
+        matching = [suppression for suppression in suppressions if suppression.matches(finding)]
+        for suppression in matching:
+            suppression.used = True
+        if not matching:
+            kept.append(finding)
+    return kept
+
+
+def _iter_comments(node: Node) -> Iterator[Node]:
+    """Iterate over the comment nodes below a node"""
+    if node.type in COMMENT_TYPES:
+        yield node
+        return
+    for child in node.children:
+        yield from _iter_comments(child)
@@ -0,0 +1,126 @@
+"""
+Lightweight intra-procedural taint tracking for detectors
+"""
+import re
+from typing import Iterable, Optional, Pattern, Set, Union
+
+from tree_sitter import Node
+
+from stylus_analyzer.ast_utils import iter_nodes, node_text
+
+# Values chosen by the caller of the transaction
+CALLER_SOURCE_PATTERN = re.compile(
+    r"\bmsg\s*::\s*(sender|value)\s*\(|\bmsg_(sender|value)\s*\(|\btx\s*::\s*origin\s*\(|\btx_origin\s*\("
+)
+
+# Block fields known before the transaction executes, and influenced by the sequencer
+BLOCK_SOURCE_PATTERN = re.compile(
+    r"\bblock\s*::\s*(timestamp|number|coinbase|basefee|gas_limit|chainid)\s*\(|"
+    r"\bblock_(timestamp|number|coinbase|basefee|gas_limit)\s*\("
+)
+
+# Bytes returned by low-level calls or read from the transaction's calldata
+EXTERNAL_DATA_SOURCE_PATTERN = re.compile(
+    r"\b(call|delegate_call|static_call)\s*\(|\bcontract\s*::\s*args\s*\(|\bmsg\s*::\s*data\s*\("
+)
+
+# Nodes binding names from a value: `let x = v`, `if let Some(x) = v` and `for x in v`
+BINDING_TYPES = ("let_declaration", "let_condition", "for_expression")
+
+ASSIGNMENT_TYPES = ("assignment_expression", "compound_assignment_expr")
+
+
+class TaintAnalysis:
+    """
+    Tracks which locals of a function hold values derived from a source.
+
+    Sources are parameter names and expressions matching `source_pattern`,
+    such as `msg::sender()` or `block::timestamp()`. Taint flows through
+    `let` bindings, `if let`/`for` patterns and assignments to locals: a
+    binding is tainted when any name or source in its value is, so
+    arithmetic, method calls on tainted receivers and calls with tainted
+    arguments all propagate it.
+
+    The analysis is conservative. It stays within one function, ignores
+    control flow so a name tainted anywhere is tainted everywhere, and
+    repeats until nothing changes so values flowing around loops are caught.
+    """
+
+    def __init__(self, function_node: Node, code: str, sources: Iterable[str] = (),
+                 source_pattern: Optional[Pattern] = None, taint_parameters: bool = False):
+        """
+        Args:
+            function_node: The function item to analyze
+            code: The source code
+            sources: Names of locals or parameters holding untrusted values
+            source_pattern: Pattern matching the text of source expressions
+            taint_parameters: Treat every parameter of the function as a source
+        """
+        self.code = code
+        self.source_pattern = source_pattern
+        self.tainted: Set[str] = set(sources)
+        if taint_parameters:
+            self.tainted |= parameter_names(function_node, code)
+        body = function_node.child_by_field_name("body")
+        if body is not None:
+            self._propagate(body)
+
+    def is_tainted(self, expr: Union[Node, str]) -> bool:
+        """Check if an expression node, or a local by name, holds a value derived from a source"""
+        if isinstance(expr, str):
+            return expr in self.tainted
+        if self.source_pattern is not None and self.source_pattern.search(node_text(expr, self.code)):
+            return True
+        return any(node.type == "identifier" and node_text(node, self.code) in self.tainted
+                   for node in iter_nodes(expr))
+
+    def _propagate(self, body: Node) -> None:
+        """Taint the names bound or assigned from tainted values until a fixed point is reached"""
+        changed = True
+        while changed:
+            changed = False
+            for node in iter_nodes(body):
+                for name in self._tainted_targets(node):
+                    if name not in self.tainted:
+                        self.tainted.add(name)
+                        changed = True
+
+    def _tainted_targets(self, node: Node) -> Set[str]:
+        """Get the names a binding or assignment taints, if its value is tainted"""
+        if node.type in BINDING_TYPES:
+            target = node.child_by_field_name("pattern")
+            value = node.child_by_field_name("value")
+        elif node.type in ASSIGNMENT_TYPES:
+            target = node.child_by_field_name("left")
+            value = node.child_by_field_name("right")
+            # Only plain locals; `self.field = v` writes storage
+            if target is not None and target.type != "identifier":
+                return set()
+        else:
+            return set()
+        if target is None or value is None or not self.is_tainted(value):
+            return set()
+        return bound_names(target, self.code)
+
+
+def parameter_names(function_node: Node, code: str) -> Set[str]:
+    """Get the names of a function's parameters, without `self`"""
+    names = set()
+    parameters = function_node.child_by_field_name("parameters")
+    for parameter in parameters.named_children if parameters else []:
+        pattern = parameter.child_by_field_name("pattern") if parameter.type == "parameter" else None
+        if pattern is not None:
+            names |= bound_names(pattern, code)
+    return names
+
+
+def bound_names(pattern: Node, code: str) -> Set[str]:
+    """Get the names a pattern binds, e.g. `x` for `Some(x)` and `y` for `Point { y, .. }`"""
+    names = set()
+    for node in iter_nodes(pattern):
+        if node.type == "shorthand_field_identifier":
+            names.add(node_text(node, code))
+        elif node.type == "identifier" and not (node.parent is not None and
+                                                 node.parent.child_by_field_name("type") == node):
+            names.add(node_text(node, code))
+    return names
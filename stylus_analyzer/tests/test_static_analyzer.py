@@ -2,11 +2,75 @@
 Tests for the static analyzer
 """
 import os
+import sys
+import json
+import logging
+import random
+import subprocess
+import inspect
+import io
+import shutil
+import tempfile
+import time
 import unittest
+import xml.etree.ElementTree as ET
+from unittest import mock
 from pathlib import Path
+import click
 import tree_sitter
+from click.testing import CliRunner
 
-from stylus_analyzer.static_analyzer import StaticAnalyzer
+from stylus_analyzer.abi_utils import to_camel_case
+from stylus_analyzer.cli import cli
+from stylus_analyzer.static_analyzer import DetectorProfile, StaticAnalyzer, StaticAnalysisResult
+from stylus_analyzer.file_utils import generate_rust_ast
+from stylus_analyzer.guards import extract_guards
+from stylus_analyzer.detectors.missing_access_control_detector import MissingAccessControlDetector
+from stylus_analyzer.detectors.missing_event_detector import MissingEventOnStateChangeDetector
+from stylus_analyzer.detectors.zero_address_check_detector import ZeroAddressCheckDetector
+from stylus_analyzer.detectors.uninitialized_storage_detector import UninitializedStorageFieldDetector
+from stylus_analyzer.detectors.missing_owner_init_detector import MissingOwnerInitializationDetector
+from stylus_analyzer.detectors.hardcoded_address_detector import HardcodedAddressDetector
+from stylus_analyzer.detectors.fallback_logic_detector import FallbackWithLogicDetector
+from stylus_analyzer.detectors.transfer_amount_check_detector import MissingRequireOnTransferAmountDetector
+from stylus_analyzer.detectors.overpowered_owner_detector import OverpoweredOwnerDetector
+from stylus_analyzer.detectors import detector_base
+from stylus_analyzer.detectors.detector_base import AnalysisContext
+from stylus_analyzer.detectors.registry import DetectorRegistry
+from stylus_analyzer.detectors.traversal import run_node_detectors
+from stylus_analyzer.autofix import apply_fixes
+from stylus_analyzer.baseline import Baseline, fingerprint
+from stylus_analyzer.cache import AnalysisCache
+from stylus_analyzer.file_filter import FileFilter
+from stylus_analyzer import logging_utils
+from stylus_analyzer.logging_utils import configure_logging
+from stylus_analyzer.lsp import LanguageServer, path_to_uri, read_message, write_message
+from stylus_analyzer.config import CONFIG_FILE_NAME, Config, ConfigError, find_config_file, load_config
+from stylus_analyzer.findings import (
+    Confidence, Finding, FindingSummary, Fix, ParseError, Severity, TextEdit, exit_code_for, filter_findings, sort_findings
+)
+from stylus_analyzer.source_map import SourceMap
+from stylus_analyzer.stylus_attributes import get_item_flags
+from stylus_analyzer.taint import BLOCK_SOURCE_PATTERN, TaintAnalysis
+from stylus_analyzer.workspace import WorkspaceError, collect_contract_files, find_workspace_contracts
+from stylus_analyzer.output_utils import (
+    JSON_SCHEMA_VERSION, SECURITY_REPORT_SCHEMA, format_detector_list, format_snippet, generate_detector_list_json, generate_json_report,
+    generate_github_annotations, generate_junit_report, generate_markdown_report, generate_sarif_report,
+    load_json_report
+)
+
+
+class WorkerPidDetector(detector_base.BaseDetector):
+    """Detector reporting the id of the process it ran in, defined at module level so workers can unpickle it"""
+
+    def __init__(self):
+        super().__init__(name="worker_pid", description="Reports the id of the process analyzing the file")
+
+    def detect(self, tree, code, results) -> None:
+        # Long enough that the other workers pick up files meanwhile
+        time.sleep(0.05)
+        results.add_issue(issue_type="worker_pid", severity="Info", description=str(os.getpid()), line_start=1,
+                          line_end=1, code_snippet="", recommendation="")
 
 
 class TestStaticAnalyzer(unittest.TestCase):
@@ -81,7 +145,2880 @@ class TestStaticAnalyzer(unittest.TestCase):
         
         # There should be no issues
         self.assertFalse(results.has_issues(), "Should not detect issues in safe code")
-        
+
+    def test_reentrancy_detection(self):
+        """Test that storage writes after external calls are detected"""
+        contract_path = self.test_dir / "reentrancy_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "reentrancy"]
+        descriptions = " ".join(issue["description"] for issue in issues)
+
+        # Both vulnerable withdrawals should be flagged, including the write inside the if branch
+        self.assertEqual(len(issues), 2, "Should detect both writes after external calls")
+        self.assertIn("'withdraw'", descriptions)
+        self.assertIn("'withdraw_all'", descriptions)
+
+        # CEI ordering and a guard toggled before the call are safe
+        self.assertNotIn("safe_withdraw", descriptions)
+        self.assertNotIn("guarded_withdraw", descriptions)
+
+    def test_unchecked_arithmetic_detection(self):
+        """Test that arithmetic without checked operations is detected"""
+        contract_path = self.test_dir / "unchecked_arithmetic_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        # Without the narrower underflow detector, whose findings replace these at the same subtraction
+        analyzer = StaticAnalyzer()
+        analyzer.registry.disable("integer_underflow_on_subtraction")
+        results = analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "unchecked_arithmetic"]
+        medium = [issue for issue in issues if issue["severity"] == "Medium"]
+        info = [issue for issue in issues if issue["severity"] == "Info"]
+
+        self.assertEqual(len(medium), 2, "Should detect the unchecked addition and subtraction")
+        self.assertEqual([issue["line_start"] for issue in medium], [22, 28])
+
+        # The SAFE annotated block is downgraded, constants and checked calls are skipped
+        self.assertEqual(len(info), 1)
+        self.assertIn("'fee'", info[0]["description"])
+        self.assertFalse(any("checked_add" in issue["description"] for issue in issues))
+
+    def test_unchecked_arithmetic_in_sol_macro(self):
+        """Test that unchecked arithmetic is reported at the right lines inside sol! bodies"""
+        contract_path = self.test_dir / "token.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        analyzer = StaticAnalyzer()
+        analyzer.registry.disable("integer_underflow_on_subtraction")
+        results = analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "unchecked_arithmetic"]
+        lines = code.split("\n")
+
+        decrement_line = next(i for i, line in enumerate(lines, 1) if "balanceOf[from] -= value;" in line)
+        self.assertIn(decrement_line, [issue["line_start"] for issue in issues])
+        self.assertTrue(any("'transferFrom'" in issue["description"] for issue in issues))
+
+    def test_subtraction_reported_once_per_detector_family(self):
+        """Test that the underflow finding of a subtraction replaces the unchecked arithmetic one"""
+        with open(self.test_dir / "token.rs", 'r') as f:
+            code = f.read()
+        line = next(i for i, text in enumerate(code.split("\n"), 1) if "allowance[from][msg.sender] -= value;" in text)
+        issues = self.analyzer.analyze(code).issues
+        at_line = [issue["type"] for issue in issues if issue["line_start"] == line]
+
+        # The arithmetic detectors report it once, at the higher confidence, and the mapping default detector once
+        arithmetic = [issue_type for issue_type in at_line
+                      if issue_type in ("unchecked_arithmetic", "integer_underflow_on_subtraction")]
+        self.assertEqual(arithmetic, ["integer_underflow_on_subtraction"])
+        self.assertEqual(at_line.count("mapping_default_assumption"), 1)
+        # Additions, which the underflow detector doesn't look at, are still reported
+        self.assertIn(line + 1, [issue["line_start"] for issue in issues if issue["type"] == "unchecked_arithmetic"])
+
+    def test_tx_origin_auth_detection(self):
+        """Test that guards comparing tx::origin() are detected"""
+        contract_path = self.test_dir / "tx_origin_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "tx_origin_auth"]
+
+        self.assertEqual(len(issues), 2, "Should detect the if guard and the assertion")
+        self.assertTrue(all(issue["severity"] == "High" for issue in issues))
+        self.assertEqual(issues[0]["code_snippet"], "tx::origin() != self.owner.get()")
+        self.assertIn("msg::sender()", issues[0]["recommendation"])
+
+        # msg::sender() guards and plain reads of the origin are fine
+        descriptions = " ".join(issue["description"] for issue in issues)
+        self.assertNotIn("'unpause'", descriptions)
+        self.assertNotIn("'origin'", descriptions)
+
+    def test_missing_access_control_detection(self):
+        """Test that state-mutating public methods without a caller check are detected"""
+        contract_path = self.test_dir / "access_control_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "missing_access_control"]
+
+        # Only set_fee lacks a check; guarded, caller-scoped, allowlisted and view methods are skipped
+        self.assertEqual(len(issues), 1)
+        self.assertIn("'set_fee'", issues[0]["description"])
+
+    def test_missing_access_control_in_sol_macro(self):
+        """Test that mint and burn in the token contract are reported"""
+        contract_path = self.test_dir / "token.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "missing_access_control"]
+        descriptions = " ".join(issue["description"] for issue in issues)
+
+        self.assertEqual(len(issues), 2)
+        self.assertIn("'mint'", descriptions)
+        self.assertIn("'burn'", descriptions)
+
+    def test_missing_access_control_allowlist(self):
+        """Test that allowlisted functions are not reported"""
+        detector = MissingAccessControlDetector(allowlist=["set_fee"])
+        contract_path = self.test_dir / "access_control_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        tree = generate_rust_ast(code)
+        results = StaticAnalysisResult()
+        detector.detect(tree, code, results)
+        descriptions = " ".join(issue["description"] for issue in results.issues)
+
+        # deposit is reported once it's no longer on the allowlist
+        self.assertNotIn("'set_fee'", descriptions)
+        self.assertIn("'deposit'", descriptions)
+
+        # The allowlist can be set in the config file
+        self.assertEqual(self._configured_issues(code, "missing_access_control", 'allowlist = ["set_fee"]'),
+                         [issue["line_start"] for issue in results.issues])
+        # Every option a detector declares, including inherited ones, is a keyword of its constructor
+        for detector in DetectorRegistry.with_builtin_detectors():
+            parameters = inspect.signature(type(detector).__init__).parameters
+            self.assertFalse([name for name in detector.options if name not in parameters], detector.id)
+
+    def test_unsafe_cast_truncation_detection(self):
+        """Test that narrowing casts and panicking conversions are detected"""
+        contract_path = self.test_dir / "unsafe_cast_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "unsafe_cast_truncation"]
+        descriptions = [issue["description"] for issue in issues]
+
+        self.assertEqual(len(issues), 4)
+        self.assertIn("truncates", descriptions[0])
+        self.assertTrue(all("panics" in description for description in descriptions[1:]))
+
+        # Widening casts and conversions to the same width are fine
+        self.assertFalse(any("'widen'" in description or "'count'" in description for description in descriptions))
+
+        # The try_as_usize().unwrap() pattern from the unwrap example is reported too
+        with open(self.test_dir / "unwrap_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        self.assertTrue(any(issue["type"] == "unsafe_cast_truncation" for issue in results.issues))
+
+    def test_storage_write_in_loop_detection(self):
+        """Test that storage writes repeated across loop iterations are detected"""
+        contract_path = self.test_dir / "storage_loop_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "storage_write_in_loop"]
+        descriptions = " ".join(issue["description"] for issue in issues)
+
+        self.assertEqual(len(issues), 2)
+        self.assertTrue(all(issue["severity"] == "Info" for issue in issues))
+        self.assertIn("'total'", descriptions)
+        self.assertIn("'count'", descriptions)
+
+        # Per-iteration keys, pushes and a single write after the loop are fine
+        self.assertNotIn("'airdrop'", descriptions)
+        self.assertNotIn("'sum_cached'", descriptions)
+
+    def test_array_length_in_loop_condition_detection(self):
+        """Test that .len() calls in loop conditions are detected"""
+        contract_path = self.test_dir / "loop_length_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "array_length_in_loop_condition"]
+        descriptions = " ".join(issue["description"] for issue in issues)
+
+        self.assertEqual(len(issues), 2)
+        self.assertIn("'sum'", descriptions)
+        self.assertIn("'count_nonzero'", descriptions)
+
+        # Collections resized in the loop and for ranges are not reported
+        self.assertNotIn("'drain'", descriptions)
+        self.assertNotIn("'sum_for'", descriptions)
+
+    def test_redundant_storage_read_detection(self):
+        """Test that repeated reads of the same storage slot are grouped per field"""
+        contract_path = self.test_dir / "redundant_read_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "redundant_storage_read"]
+        descriptions = " ".join(issue["description"] for issue in issues)
+
+        self.assertEqual(len(issues), 2)
+        self.assertIn("'reserve' is read 3 times in function 'quote'", descriptions)
+        self.assertIn("'balances' is read 2 times in function 'can_withdraw'", descriptions)
+
+        # Different keys, intervening writes or calls and exclusive branches are fine
+        for function_name in ("bump", "sync", "pick"):
+            self.assertNotIn(f"'{function_name}'", descriptions)
+
+    def test_missing_event_on_state_change_detection(self):
+        """Test that public state changes without an event are detected"""
+        contract_path = self.test_dir / "missing_event_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "missing_event_on_state_change"]
+
+        # Constructors, logged changes and lock toggles are skipped
+        self.assertEqual(len(issues), 1)
+        self.assertIn("'set_price'", issues[0]["description"])
+        self.assertEqual(issues[0]["severity"], "Low")
+
+        # Every state change in the token contract emits an event
+        with open(self.test_dir / "token.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        self.assertFalse(any(issue["type"] == "missing_event_on_state_change" for issue in results.issues))
+
+    def test_missing_event_required_fields(self):
+        """Test that only the configured fields are reported"""
+        contract_path = self.test_dir / "missing_event_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        tree = generate_rust_ast(code)
+        results = StaticAnalysisResult()
+        MissingEventOnStateChangeDetector(required_fields=["owner"]).detect(tree, code, results)
+        self.assertEqual(len(results.issues), 0)
+
+    def test_division_before_multiplication_detection(self):
+        """Test that multiplying a division result is detected"""
+        contract_path = self.test_dir / "division_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "division_before_multiplication"]
+        descriptions = " ".join(issue["description"] for issue in issues)
+
+        self.assertEqual(len(issues), 3)
+        self.assertIn("'vested'", descriptions)
+        self.assertIn("'amount / parts'", descriptions)
+        self.assertIn("sol! function 'reward'", descriptions)
+
+        # The correct order and floating point code are fine
+        self.assertNotIn("'vested_precise'", descriptions)
+        self.assertNotIn("'ratio'", descriptions)
+
+    def test_unbounded_loop_over_caller_input_detection(self):
+        """Test that loops bounded by caller input with costly bodies are detected"""
+        contract_path = self.test_dir / "unbounded_loop_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "unbounded_loop_over_caller_input"]
+        descriptions = " ".join(issue["description"] for issue in issues)
+
+        self.assertEqual(len(issues), 2)
+        self.assertIn("'airdrop' iterates over caller-supplied parameter 'recipients'", descriptions)
+        self.assertIn("'payout' iterates over caller-supplied parameter 'n'", descriptions)
+
+        # Capped inputs and loops that don't touch storage are fine
+        self.assertNotIn("'capped_airdrop'", descriptions)
+        self.assertNotIn("'sum'", descriptions)
+
+    def test_zero_address_check_detection(self):
+        """Test that unvalidated address parameters are detected"""
+        contract_path = self.test_dir / "zero_address_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        tree = generate_rust_ast(code)
+        results = StaticAnalysisResult()
+        ZeroAddressCheckDetector(ignored_parameters=["delegate"]).detect(tree, code, results)
+        descriptions = " ".join(issue["description"] for issue in results.issues)
+
+        self.assertEqual(len(results.issues), 3)
+        self.assertIn("'new_owner'", descriptions)
+        self.assertIn("'account'", descriptions)
+        self.assertIn("'recipient'", descriptions)
+
+        # Validated and opted-out parameters are not reported
+        self.assertNotIn("'treasury'", descriptions)
+        self.assertNotIn("'delegate'", descriptions)
+
+    def test_zero_address_check_in_sol_macro(self):
+        """Test that mint is reported while the checked transfer is clean"""
+        contract_path = self.test_dir / "token.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "zero_address_check"]
+        descriptions = " ".join(issue["description"] for issue in issues)
+
+        self.assertIn("'to' in sol! function 'mint'", descriptions)
+        self.assertNotIn("sol! function 'transfer'", descriptions)
+
+    def test_block_timestamp_dependence_detection(self):
+        """Test that conditions on the block timestamp are detected"""
+        contract_path = self.test_dir / "timestamp_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "block_timestamp_dependence"]
+        descriptions = " ".join(issue["description"] for issue in issues)
+
+        self.assertEqual(len(issues), 2)
+        self.assertTrue(all(issue["severity"] == "Low" for issue in issues))
+        self.assertIn("'claim'", descriptions)
+        self.assertIn("'is_open'", descriptions)
+
+        # Logging, storing and returning the timestamp is harmless
+        self.assertNotIn("'touch'", descriptions)
+        self.assertNotIn("'now'", descriptions)
+
+    def test_weak_randomness_detection(self):
+        """Test that decisions on predictable pseudo-random values are detected"""
+        contract_path = self.test_dir / "weak_randomness_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "weak_randomness"]
+        descriptions = " ".join(issue["description"] for issue in issues)
+
+        # The taint flows from the timestamp through three lets into the index
+        self.assertEqual(len(issues), 2)
+        self.assertTrue(all(issue["severity"] == "High" for issue in issues))
+        self.assertIn("'draw'", descriptions)
+        self.assertIn("'flip'", descriptions)
+
+        # Deadline and caller checks aren't randomness
+        self.assertNotIn("'is_open'", descriptions)
+        self.assertNotIn("'is_winner'", descriptions)
+
+    def test_self_transfer_detection(self):
+        """Test that two-sided balance updates without a from != to guard are detected"""
+        contract_path = self.test_dir / "self_transfer_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "self_transfer"]
+
+        # Guarded transfers and one-sided updates are fine
+        self.assertEqual(len(issues), 1)
+        self.assertIn("'move_funds'", issues[0]["description"])
+
+        with open(self.test_dir / "token.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "self_transfer"]
+        self.assertTrue(any("'transferFrom'" in issue["description"] for issue in issues))
+
+    def test_approve_race_detection(self):
+        """Test that approve functions overwriting the allowance are detected"""
+        contract_path = self.test_dir / "approve_race_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "approve_race_condition"]
+
+        # The approve requiring a zero allowance is fine
+        self.assertEqual(len(issues), 1)
+        self.assertIn("'approve'", issues[0]["description"])
+        self.assertEqual(issues[0]["severity"], "Low")
+
+        with open(self.test_dir / "token.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "approve_race_condition"]
+        self.assertEqual(len(issues), 1)
+        self.assertIn("sol! function 'approve'", issues[0]["description"])
+
+    def test_unchecked_low_level_call_detection(self):
+        """Test that low-level calls with a discarded result are detected"""
+        contract_path = self.test_dir / "unchecked_call_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "unchecked_low_level_call"]
+
+        # Propagated results and required success flags are fine
+        self.assertEqual(len(issues), 6)
+        descriptions = " ".join(issue["description"] for issue in issues)
+        for function_name in ("'ping'", "'ping_ignored'", "'ping_ok'", "'forward'", "'forwardUnchecked'"):
+            self.assertIn(function_name, descriptions)
+        self.assertNotIn("'ping_checked'", descriptions)
+        self.assertNotIn("'forwardChecked'", descriptions)
+
+    def test_delegatecall_untrusted_target_detection(self):
+        """Test that delegatecalls to caller-controlled targets are detected"""
+        contract_path = self.test_dir / "delegatecall_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "delegatecall_untrusted_target"]
+
+        # Constant targets and owner-gated storage are fine
+        self.assertEqual(len(issues), 4)
+        descriptions = " ".join(issue["description"] for issue in issues)
+        self.assertIn("function 'exec' targets 'target', which comes from parameter 'target'", descriptions)
+        self.assertIn("'set_plugin'", descriptions)
+        self.assertIn("sol! function 'forward'", descriptions)
+        self.assertIn("sol! function 'execute'", descriptions)
+        self.assertNotIn("'run_module'", descriptions)
+        self.assertNotIn("'run_library'", descriptions)
+        self.assertTrue(all(issue["severity"] == "High" for issue in issues))
+
+    def test_msg_value_without_payable_detection(self):
+        """Test that msg_value reads in non-payable methods are detected"""
+        contract_path = self.test_dir / "locked_ether_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "msg_value_without_payable"]
+
+        # The #[payable] deposit is fine
+        self.assertEqual(len(issues), 1)
+        self.assertIn("'receive_payment'", issues[0]["description"])
+        self.assertIn("evm::msg_value()", issues[0]["code_snippet"])
+
+    def test_panic_in_loop_detection(self):
+        """Test that aborting macros in loops and public methods are detected"""
+        contract_path = self.test_dir / "panic_loop_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "panic_in_loop"]
+
+        # Asserts in internal helpers outside loops are fine
+        self.assertEqual(len(issues), 4)
+        descriptions = [issue["description"] for issue in issues]
+        self.assertTrue(descriptions[0].startswith("assert_ne!() used inside the loop"))
+        self.assertIn("public function 'set_total'", descriptions[1])
+        self.assertIn("todo!()", descriptions[2])
+        self.assertIn("unreachable!() used inside the loop", descriptions[3])
+
+        # panic!() is left to the panic detector
+        with open(self.test_dir / "panic_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        self.assertFalse(any(issue["type"] == "panic_in_loop" for issue in results.issues))
+
+    def test_expect_detection(self):
+        """Test that .expect() calls in public methods are detected"""
+        contract_path = self.test_dir / "expect_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "unsafe_expect"]
+
+        # Internal helpers, test modules and expect().unwrap() chains are not reported
+        self.assertEqual(len(issues), 2)
+        self.assertIn('.expect("index too large")', issues[0]["description"])
+        self.assertIn('.expect("no entry at index")', issues[1]["description"])
+        self.assertTrue(all(issue["severity"] == "Medium" for issue in issues))
+
+        unwraps = [issue for issue in results.issues if issue["type"] == "unsafe_unwrap"]
+        self.assertEqual(len([issue for issue in unwraps if "'first_fee'" in issue["description"]]), 1)
+
+    def test_constructor_integer_overflow_detection(self):
+        """Test that overflowing computations on constructor parameters are detected"""
+        contract_path = self.test_dir / "constructor_overflow_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "constructor_integer_overflow"]
+        descriptions = " ".join(issue["description"] for issue in issues)
+
+        # Bounded exponents, checked variants and non-constructors are fine
+        self.assertEqual(len(issues), 3)
+        self.assertIn("exponentiation in constructor 'constructor' depends on parameter 'decimals'", descriptions)
+        self.assertIn("multiplication in constructor 'init'", descriptions)
+        self.assertNotIn("'initialize'", descriptions)
+        self.assertNotIn("'scale'", descriptions)
+        self.assertNotIn("'new'", descriptions)
+
+        with open(self.test_dir / "token.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "constructor_integer_overflow"]
+        self.assertEqual(len(issues), 1)
+        self.assertIn("sol! constructor 'constructor' depends on parameter '_decimals'", issues[0]["description"])
+
+    def test_uninitialized_storage_field_detection(self):
+        """Test that storage fields never set by the constructor are detected"""
+        contract_path = self.test_dir / "uninitialized_storage_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "uninitialized_storage_field"]
+
+        # Counters, flags, collections and fields set through helpers are fine
+        self.assertEqual(len(issues), 2)
+        self.assertIn("'Vault' never initializes: owner, fee_bps.", issues[0]["description"])
+        self.assertIn("'Registry' never initializes: label.", issues[1]["description"])
+
+        # Fields can be allowlisted
+        detector = UninitializedStorageFieldDetector(allowlist=["fee_bps", "label"])
+        results = StaticAnalysisResult()
+        detector.detect(generate_rust_ast(code), code, results)
+        self.assertEqual(len(results.issues), 1)
+        self.assertIn("never initializes: owner.", results.issues[0]["description"])
+        configured = self._configured_issues(code, "uninitialized_storage_field", 'allowlist = ["fee_bps", "label"]')
+        self.assertEqual(configured, [results.issues[0]["line_start"]])
+
+    def test_missing_owner_initialization_detection(self):
+        """Test that owner fields never assigned in the constructor are detected"""
+        contract_path = self.test_dir / "owner_init_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "missing_owner_initialization"]
+
+        # Owners set from the sender or a parameter, directly or through a helper, are fine
+        self.assertEqual(len(issues), 2)
+        self.assertIn("Field 'admin' of 'Pool'", issues[0]["description"])
+        self.assertIn("Field 'owner' of 'Ownable'", issues[1]["description"])
+        self.assertTrue(all(issue["severity"] == "High" for issue in issues))
+        self.assertEqual((issues[0]["line_start"], issues[0]["line_end"]), (31, 35))
+
+        # A token whose owner field is commented out has no owner to initialize
+        with open(self.test_dir / "token.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        self.assertFalse([issue for issue in results.issues if issue["type"] == "missing_owner_initialization"])
+
+        # The set of privileged fields is configurable
+        detector = MissingOwnerInitializationDetector(privileged_fields=["governance"])
+        results = StaticAnalysisResult()
+        detector.detect(generate_rust_ast(code), code, results)
+        self.assertEqual(len(results.issues), 0)
+
+    def test_duplicate_function_definition_detection(self):
+        """Test that methods sharing a name or an exported selector are detected"""
+        with open(self.test_dir / "locked_ether_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "duplicate_function_definition"]
+
+        # Both definitions of withdraw are reported in one finding
+        self.assertEqual(len(issues), 1)
+        self.assertIn("Method 'withdraw'", issues[0]["description"])
+        self.assertIn("lines 60-64 and lines 67-75", issues[0]["description"])
+        self.assertEqual(issues[0]["severity"], "High")
+
+        with open(self.test_dir / "duplicate_function_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "duplicate_function_definition"]
+
+        # balance_of/balanceOf and a #[selector] rename clash; transfer_batch does not
+        self.assertEqual(len(issues), 2)
+        self.assertIn("'balanceOf(address)' (selector 0x70a08231)", issues[0]["description"])
+        self.assertIn("'transfer(address,uint256)' (selector 0xa9059cbb)", issues[1]["description"])
+        self.assertEqual((issues[1]["line_start"], issues[1]["line_end"]), (26, 39))
+
+    def test_selector_collision_detection(self):
+        """Test that different signatures sharing a 4-byte selector are detected"""
+        contract_path = self.test_dir / "selector_collision_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "selector_collision"]
+
+        # burn(uint256) and collate_propagate_storage(bytes16) collide across impl blocks
+        self.assertEqual(len(issues), 1)
+        self.assertIn("'burn(uint256)'", issues[0]["description"])
+        self.assertIn("'collate_propagate_storage(bytes16)'", issues[0]["description"])
+        self.assertIn("0x42966c68", issues[0]["description"])
+        self.assertEqual(issues[0]["severity"], "High")
+        self.assertEqual((issues[0]["line_start"], issues[0]["line_end"]), (19, 37))
+
+        # Identical signatures are left to the duplicate definition detector
+        with open(self.test_dir / "duplicate_function_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        self.assertFalse(any(issue["type"] == "selector_collision" for issue in results.issues))
+
+    def test_camel_case_matches_stylus_renaming(self):
+        """Test that method names are camel-cased the way heck, used by the Stylus SDK, splits words"""
+        self.assertEqual(to_camel_case("balance_of"), "balanceOf")
+        self.assertEqual(to_camel_case("erc20_transfer"), "erc20Transfer")
+        self.assertEqual(to_camel_case("max_u256"), "maxU256")
+        self.assertEqual(to_camel_case("get_XMLHttp"), "getXmlHttp")
+
+        # A digit is no word boundary, so the letter after it keeps its case
+        self.assertEqual(to_camel_case("foo2bar"), "foo2bar")
+        self.assertEqual(to_camel_case("foo2Bar"), "foo2Bar")
+        self.assertEqual(to_camel_case("set_2fa_key"), "set2faKey")
+
+    def test_hardcoded_address_detection(self):
+        """Test that literal addresses in control flow or as transfer targets are detected"""
+        contract_path = self.test_dir / "hardcoded_address_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "hardcoded_address"]
+
+        # The zero address, precompiles and returned literals are not reported
+        self.assertEqual(len(issues), 4)
+        self.assertEqual([issue["line_start"] for issue in issues], [9, 40, 47, 17])
+        self.assertIn("compared against in function 'set_fee'", issues[0]["description"])
+        self.assertIn("0x1111111111111111111111111111111111111111 is used as a call or transfer target",
+                      issues[1]["description"])
+        self.assertIn("sol! function 'sweep'", issues[3]["description"])
+        self.assertTrue(all(issue["severity"] == "Low" for issue in issues))
+
+        # Known-safe addresses can be allowlisted
+        detector = HardcodedAddressDetector(allowlist=["0x5B38Da6a701c568545dCfcB03FcB875f56beddC4"])
+        results = StaticAnalysisResult()
+        detector.detect(generate_rust_ast(code), code, results)
+        self.assertEqual(len(results.issues), 3)
+        configured = self._configured_issues(code, "hardcoded_address",
+                                             'allowlist = ["0x5B38Da6a701c568545dCfcB03FcB875f56beddC4"]')
+        self.assertEqual(configured, [issue["line_start"] for issue in results.issues])
+
+    def test_shadowed_storage_field_detection(self):
+        """Test that locals shadowing a storage field that is used again are detected"""
+        contract_path = self.test_dir / "shadowed_field_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "shadowed_storage_field"]
+
+        # Write-backs, locals of another type and unused fields are not reported
+        self.assertEqual(len(issues), 2)
+        self.assertIn("Local 'total' (lines 21-21) in function 'deposit'", issues[0]["description"])
+        self.assertIn("field 'total' of 'Vault' (line 12), which is still used at line 25", issues[0]["description"])
+        self.assertIn("function 'transfer_ownership'", issues[1]["description"])
+        self.assertTrue(all(issue["severity"] == "Low" for issue in issues))
+
+    def test_clone_in_loop_detection(self):
+        """Test that loop-invariant heap clones inside loops are detected"""
+        contract_path = self.test_dir / "clone_loop_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "clone_in_loop"]
+
+        # Loop variables, closure parameters and values mutated in the loop are not reported
+        self.assertEqual(len(issues), 3)
+        self.assertIn("'label.clone()' inside the loop at line 19", issues[0]["description"])
+        self.assertIn("'data.to_vec()'", issues[1]["description"])
+        self.assertIn("'\"member\".to_string()'", issues[2]["description"])
+        self.assertTrue(all(issue["severity"] == "Info" for issue in issues))
+
+    def test_string_format_panic_detection(self):
+        """Test that formatted strings parsed back with unwrap/expect are detected"""
+        with open(self.test_dir / "unwrap_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "string_format_panic"]
+
+        self.assertEqual(len(issues), 1)
+        self.assertIn("function 'to_hex_string'", issues[0]["description"])
+        self.assertEqual(issues[0]["line_start"], 100)
+
+        with open(self.test_dir / "format_parse_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "string_format_panic"]
+
+        # Propagated parse errors and non-formatted inputs are not reported
+        self.assertEqual(len(issues), 3)
+        self.assertIn("'label' is parsed back with .parse().expect()", issues[1]["description"])
+        self.assertIn("'value.to_string()'", issues[2]["description"])
+        self.assertTrue(all(issue["severity"] == "Low" for issue in issues))
+
+    def test_receive_fallback_locked_ether_detection(self):
+        """Test that Ether received only through receive/fallback without a withdrawal is detected"""
+        with open(self.test_dir / "receive_locked_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "receive_fallback_locked_ether"]
+
+        self.assertEqual(len(issues), 1)
+        self.assertIn("'receive' (line 19), 'fallback' (line 28)", issues[0]["description"])
+        self.assertEqual(issues[0]["severity"], "Medium")
+        # The entrypoints are not reported again as payable functions
+        self.assertFalse(any(issue["type"] == "locked_ether" for issue in results.issues))
+
+        with open(self.test_dir / "receive_sol_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "receive_fallback_locked_ether"]
+        self.assertEqual(len(issues), 1)
+        self.assertEqual(issues[0]["line_start"], 22)
+
+        # Contracts with payable functions are left to the locked Ether detector
+        with open(self.test_dir / "locked_ether_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        self.assertFalse(any(issue["type"] == "receive_fallback_locked_ether" for issue in results.issues))
+
+    def test_unguarded_eth_transfer_detection(self):
+        """Test that Ether transfers reachable without a caller check are detected"""
+        contract_path = self.test_dir / "eth_transfer_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "unguarded_eth_transfer"]
+
+        # Guarded transfers and calls without value are not reported
+        self.assertEqual(len(issues), 6)
+        by_location = {(issue["line_start"], issue["severity"]) for issue in issues}
+        self.assertEqual(by_location, {(43, "High"), (52, "Low"), (85, "High"), (72, "High"),
+                                       (15, "High"), (22, "Low")})
+        descriptions = " ".join(issue["description"] for issue in issues)
+        self.assertIn("'pay' sends Ether to 'to' through helper 'send_out'", descriptions)
+        self.assertNotIn("'admin_withdraw'", descriptions)
+        self.assertNotIn("'ping'", descriptions)
+        self.assertNotIn("sol! function 'sweep'", descriptions)
+
+        # Recipients read from storage can't be chosen by the caller
+        with open(self.test_dir / "timestamp_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "unguarded_eth_transfer"]
+        self.assertEqual(len(issues), 1)
+        self.assertEqual(issues[0]["severity"], "Medium")
+
+    def test_storage_field_could_be_constant_detection(self):
+        """Test that fields only written once in the constructor are suggested as constants"""
+        contract_path = self.test_dir / "constant_candidate_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "storage_field_could_be_constant"]
+
+        # Fields written elsewhere, never read, or collections are not suggested
+        self.assertEqual(len(issues), 1)
+        self.assertIn("'fee_bps' (2 reads), 'treasury' (1 read).", issues[0]["description"])
+        self.assertNotIn("'owner'", issues[0]["description"])
+        self.assertNotIn("'decimals'", issues[0]["description"])
+        self.assertEqual(issues[0]["severity"], "Info")
+        self.assertEqual((issues[0]["line_start"], issues[0]["line_end"]), (10, 17))
+
+    def test_public_could_be_external_detection(self):
+        """Test that public methods without internal callers are suggested as external"""
+        contract_path = self.test_dir / "public_external_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "public_could_be_external"]
+
+        # Internally called, interface, selector and trait methods are skipped
+        self.assertEqual(len(issues), 1)
+        self.assertIn("'deposit'", issues[0]["description"])
+        self.assertEqual(issues[0]["severity"], "Info")
+        self.assertEqual(issues[0]["confidence"], "Low")
+        self.assertEqual(issues[0]["line_start"], 26)
+
+    def test_detector_registry(self):
+        """Test that registered detectors have unique ids and can be enabled and disabled"""
+        registry = DetectorRegistry.with_builtin_detectors()
+        ids = registry.ids()
+
+        self.assertEqual(len(ids), len(set(ids)))
+        self.assertEqual(len(ids), len(registry))
+        for detector in registry:
+            self.assertIsInstance(detector.default_severity, Severity)
+
+        # Disabling and re-enabling restores the original order
+        registry.disable("unsafe_unwrap")
+        registry.disable("reentrancy")
+        self.assertNotIn("unsafe_unwrap", registry.enabled_ids())
+        self.assertFalse(registry.is_enabled("reentrancy"))
+        self.assertEqual(len(registry.enabled_detectors()), len(ids) - 2)
+        registry.enable("unsafe_unwrap")
+        registry.enable("reentrancy")
+        self.assertEqual(registry.enabled_ids(), ids)
+
+        with self.assertRaises(ValueError):
+            registry.disable("no_such_detector")
+        with self.assertRaises(ValueError):
+            registry.register(registry.get("reentrancy"))
+
+        # Disabled detectors don't run
+        self.analyzer.registry.disable("unsafe_unwrap")
+        with open(self.test_dir / "unwrap_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        self.assertFalse(any(issue["type"] == "unsafe_unwrap" for issue in results.issues))
+
+    def test_finding_sorting(self):
+        """Test that findings sort by file, line, column and detector id"""
+        findings = [
+            Finding("unsafe_unwrap", Severity.MEDIUM, "unwrap", line=12, file="b.rs"),
+            Finding("reentrancy", Severity.HIGH, "reentrancy", line=40, file="b.rs"),
+            Finding("clone_in_loop", Severity.INFO, "clone", line=3, file="a.rs"),
+            Finding("unsafe_panic", Severity.HIGH, "panic", line=7, file="b.rs"),
+            Finding("unsafe_expect", Severity.MEDIUM, "expect", line=30, file="a.rs"),
+        ]
+
+        ordered = [(finding.severity, finding.file, finding.line) for finding in sort_findings(findings)]
+        self.assertEqual(ordered, [
+            (Severity.INFO, "a.rs", 3),
+            (Severity.MEDIUM, "a.rs", 30),
+            (Severity.HIGH, "b.rs", 7),
+            (Severity.MEDIUM, "b.rs", 12),
+            (Severity.HIGH, "b.rs", 40),
+        ])
+
+        # The column and detector id break ties on a line, then the severity and message
+        same_line = [
+            Finding("unsafe_unwrap", Severity.LOW, "second", line=5, column=9, file="a.rs"),
+            Finding("unsafe_unwrap", Severity.HIGH, "first", line=5, column=9, file="a.rs"),
+            Finding("clone_in_loop", Severity.INFO, "clone", line=5, column=9, file="a.rs"),
+            Finding("unsafe_panic", Severity.HIGH, "panic", line=5, column=3, file="a.rs"),
+        ]
+        self.assertEqual([finding.message for finding in sort_findings(same_line)],
+                         ["panic", "clone", "first", "second"])
+        self.assertEqual(Severity.parse("high"), Severity.HIGH)
+        self.assertEqual(str(Severity.LOW), "Low")
+        with self.assertRaises(ValueError):
+            Severity.parse("Warning")
+
+        # Analyzer findings carry the file and a resolved column
+        code = "fn main() {\n    let value = maybe.unwrap();\n}\n"
+        results = self.analyzer.analyze(code)
+        finding = next(finding for finding in results.findings if finding.detector_id == "unsafe_unwrap")
+        self.assertEqual(finding.line, 2)
+        self.assertEqual(finding.column, 17)
+        self.assertEqual(results.issues[0]["column"], finding.column)
+
+    def test_json_report_round_trip(self):
+        """Test that findings survive serialization to the versioned JSON report"""
+        findings = [
+            Finding("unsafe_unwrap", Severity.MEDIUM, "Unwrap may panic", line=12, column=9, file="src/lib.rs",
+                    suggestion="Handle the error", line_end=12),
+            Finding("public_could_be_external", Severity.INFO, "Only called externally", line=3, column=5,
+                    file="src/lib.rs", suggestion="Use #[external]", line_end=6, confidence=Confidence.LOW),
+        ]
+
+        report = generate_json_report(findings)
+        data = json.loads(report)
+        self.assertEqual(data["schema_version"], JSON_SCHEMA_VERSION)
+        for key in ("detector_id", "severity", "file", "line", "column", "message", "suggestion"):
+            self.assertIn(key, data["findings"][0])
+        self.assertEqual(data["findings"][0]["severity"], "Info")
+
+        # Findings come back sorted, but otherwise unchanged
+        self.assertEqual(load_json_report(report), sort_findings(findings))
+
+        data["schema_version"] = JSON_SCHEMA_VERSION + 1
+        with self.assertRaises(ValueError):
+            load_json_report(json.dumps(data))
+
+    def test_sarif_report_matches_golden_file(self):
+        """Test that the SARIF log for a small fixture matches the checked-in expected document"""
+        contract_path = self.test_dir / "sarif_example.rs"
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        registry = DetectorRegistry.with_builtin_detectors()
+        detectors = [registry.get("unsafe_unwrap"), registry.get("unsafe_panic")]
+        context = AnalysisContext(tree=generate_rust_ast(code), code=code, file_path="test_contracts/sarif_example.rs")
+        findings = [finding for detector in detectors for finding in detector.run(context)]
+
+        sarif = json.loads(generate_sarif_report(findings, detectors, tool_version="0.0.0"))
+        with open(self.test_dir / "sarif_example.sarif", 'r') as f:
+            expected = json.load(f)
+        self.assertEqual(sarif, expected)
+
+    def test_severity_threshold_and_exit_codes(self):
+        """Test threshold filtering and the --fail-on exit code for every severity pair"""
+        severities = [Severity.INFO, Severity.LOW, Severity.MEDIUM, Severity.HIGH]
+        for threshold in severities:
+            findings = [Finding(f"detector_{severity}", severity, "message", line=1) for severity in severities]
+            reported = filter_findings(findings, threshold)
+            self.assertEqual([finding.severity for finding in reported],
+                             [severity for severity in severities if severity >= threshold])
+
+        # A single finding fails the run exactly when it meets or exceeds --fail-on
+        for finding_severity in severities:
+            for fail_on in severities:
+                with self.subTest(finding=finding_severity, fail_on=fail_on):
+                    findings = [Finding("detector", finding_severity, "message", line=1)]
+                    self.assertEqual(exit_code_for(findings, fail_on), 1 if finding_severity >= fail_on else 0)
+
+        # Without --fail-on, or once the finding is filtered out, the run passes
+        high = [Finding("detector", Severity.HIGH, "message", line=1)]
+        low = [Finding("detector", Severity.LOW, "message", line=1)]
+        self.assertEqual(exit_code_for(high, None), 0)
+        self.assertEqual(exit_code_for(filter_findings(low, Severity.MEDIUM), Severity.LOW), 0)
+        self.assertEqual(filter_findings(low, None), low)
+
+    def test_list_detectors(self):
+        """Test that the detector listing covers the built-in ids with non-empty descriptions"""
+        registry = DetectorRegistry.with_builtin_detectors()
+        detectors = list(registry)
+        listing = json.loads(generate_detector_list_json(detectors))["detectors"]
+        ids = [entry["id"] for entry in listing]
+
+        for detector_id in ("unchecked_transfer", "unsafe_unwrap", "unsafe_panic", "reentrancy",
+                            "missing_access_control", "selector_collision", "public_could_be_external"):
+            self.assertIn(detector_id, ids)
+        for entry in listing:
+            self.assertTrue(entry["description"].strip())
+            self.assertEqual(Severity.parse(entry["default_severity"]), registry.get(entry["id"]).default_severity)
+
+        # The table has a header and one row per detector
+        table = format_detector_list(detectors).splitlines()
+        self.assertEqual(len(table), len(detectors) + 1)
+        self.assertTrue(table[2].startswith("unsafe_unwrap "))
+        self.assertIn("Medium", table[2])
+
+    def test_config_merge_precedence(self):
+        """Test that CLI options take precedence over the config file"""
+        file_config = Config.from_dict({
+            "detectors": {"disabled": ["unsafe_panic", "unsafe_unwrap"]},
+            "severity": {"missing_event_on_state_change": "High", "unsafe_expect": "Low"},
+        })
+        cli_config = Config(enabled=["unsafe_unwrap"], disabled=["reentrancy"],
+                            severity_overrides={"unsafe_expect": Severity.HIGH})
+        merged = file_config.merge(cli_config)
+
+        registry = DetectorRegistry.with_builtin_detectors()
+        merged.apply(registry)
+        self.assertFalse(registry.is_enabled("unsafe_panic"))
+        self.assertTrue(registry.is_enabled("unsafe_unwrap"))
+        self.assertFalse(registry.is_enabled("reentrancy"))
+        self.assertEqual(registry.severity_override("missing_event_on_state_change"), Severity.HIGH)
+        self.assertEqual(registry.severity_override("unsafe_expect"), Severity.HIGH)
+        self.assertEqual(registry.get("missing_event_on_state_change").default_severity, Severity.HIGH)
+
+        # An allowlist in the file is extended by detectors enabled on the command line
+        allowlist = Config.from_dict({"detectors": {"enabled": ["unsafe_panic"]}})
+        merged = allowlist.merge(Config(enabled=["unsafe_unwrap"]))
+        registry = DetectorRegistry.with_builtin_detectors()
+        merged.apply(registry)
+        self.assertEqual(registry.enabled_ids(), ["unsafe_unwrap", "unsafe_panic"])
+
+        # Overridden severities apply to the findings themselves
+        analyzer = StaticAnalyzer()
+        Config(severity_overrides={"unsafe_unwrap": Severity.LOW}).apply(analyzer.registry)
+        with open(self.test_dir / "unwrap_example.rs", 'r') as f:
+            results = analyzer.analyze(f.read())
+        unwraps = [issue for issue in results.issues if issue["type"] == "unsafe_unwrap"]
+        self.assertTrue(unwraps)
+        self.assertTrue(all(issue["severity"] == "Low" for issue in unwraps))
+
+    def test_config_file_loading_and_errors(self):
+        """Test that the config file is found upward and unknown detector ids are rejected"""
+        with tempfile.TemporaryDirectory() as root:
+            nested = Path(root) / "contracts" / "src"
+            nested.mkdir(parents=True)
+            contract = nested / "lib.rs"
+            contract.write_text("fn main() {}\n")
+            config_path = Path(root) / CONFIG_FILE_NAME
+            config_path.write_text('[detectors]\ndisabled = ["unsafe_panic", "not_a_detector"]\n')
+
+            self.assertEqual(find_config_file(str(contract)), str(config_path))
+            config = load_config(find_config_file(str(nested)))
+            with self.assertRaises(ConfigError) as error:
+                config.apply(DetectorRegistry.with_builtin_detectors())
+            self.assertIn("not_a_detector", str(error.exception))
+            self.assertIn(str(config_path), str(error.exception))
+
+            config_path.write_text('[severity]\nunsafe_unwrap = "Severe"\n')
+            with self.assertRaises(ConfigError):
+                load_config(str(config_path))
+            config_path.write_text('[detectors\n')
+            with self.assertRaises(ConfigError):
+                load_config(str(config_path))
+
+            # Options must be tables of options the detector has, of the right type
+            config_path.write_text('[options]\nfallback_with_logic = 2\n')
+            with self.assertRaises(ConfigError):
+                load_config(str(config_path))
+            for options, message in (("limit = 2", "Unknown option 'limit'"),
+                                     ("max_statements = true", "must be of type int")):
+                config_path.write_text(f'[options.fallback_with_logic]\n{options}\n')
+                with self.assertRaises(ConfigError) as error:
+                    load_config(str(config_path)).apply(DetectorRegistry.with_builtin_detectors())
+                self.assertIn(message, str(error.exception))
+                self.assertIn(str(config_path), str(error.exception))
+
+    def test_inline_suppressions(self):
+        """Test the line, next-line and file suppression scopes with and without detector ids"""
+        contract_path = self.test_dir / "suppression_example.rs"
+
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        located = {(issue["type"], issue["line_start"]) for issue in results.issues}
+
+        # next-line with an id, line without ids, and file scope for unsafe_expect
+        self.assertNotIn(("unsafe_unwrap", 11), located)
+        self.assertNotIn(("unsafe_unwrap", 12), located)
+        self.assertIn(("unsafe_unwrap", 13), located)
+        self.assertFalse(any(issue["type"] == "unsafe_expect" for issue in results.issues))
+
+        # A suppression for another detector doesn't hide the finding and is reported as unused
+        self.assertIn(("unsafe_panic", 21), located)
+        self.assertEqual([(suppression.scope, suppression.line) for suppression in results.unused_suppressions],
+                         [("next-line", 20)])
+
+    def test_baseline_hides_known_findings(self):
+        """Test that baselined findings stay hidden when lines shift while new ones are reported"""
+        with open(self.test_dir / "sarif_example.rs", 'r') as f:
+            code = f.read()
+        original = self.analyzer.analyze(code).findings
+        self.assertTrue(original)
+
+        with tempfile.TemporaryDirectory() as root:
+            path = os.path.join(root, "baseline.json")
+            Baseline.from_findings(original).save(path)
+            baseline = Baseline.load(path)
+
+        # Shift every line down and re-indent, then add a new unwrap
+        shifted = "// New header\n\n" + code.replace(
+            "        panic!(\"reset is not supported\");",
+            "            panic!(\"reset is not supported\");\n        let limit = config.unwrap();")
+        findings = self.analyzer.analyze(shifted).findings
+        new_findings = baseline.filter(findings)
+
+        # The new line follows the panic!(), so it is also new dead code
+        self.assertEqual([(finding.detector_id, finding.line) for finding in new_findings],
+                         [("unsafe_unwrap", 15), ("dead_code_after_revert", 15)])
+        moved = next(finding for finding in findings if finding.detector_id == "unsafe_panic")
+        self.assertEqual(moved.line, 14)
+        self.assertIn(fingerprint(moved), baseline.entries)
+
+        # A second occurrence of a baselined snippet is new
+        duplicate = [original[0], Finding(original[0].detector_id, original[0].severity, original[0].message,
+                                          line=99, code_snippet=original[0].code_snippet)]
+        self.assertEqual(len(Baseline.from_findings(original).filter(duplicate)), 1)
+
+    def test_parallel_analysis_matches_sequential(self):
+        """Test that analyzing many files in worker processes gives the same, stably ordered results"""
+        with tempfile.TemporaryDirectory() as root:
+            paths = []
+            for index in range(24):
+                path = os.path.join(root, f"contract_{index:02d}.rs")
+                with open(path, 'w') as f:
+                    f.write(f"pub fn run_{index}(value: Option<u64>) -> u64 {{\n"
+                            f"    let result = value.unwrap();\n"
+                            f"    if result > {index} {{ panic!(\"too large\"); }}\n"
+                            f"    result\n"
+                            f"}}\n")
+                paths.append(path)
+            paths.append(os.path.join(root, "missing.rs"))
+
+            sequential = self.analyzer.analyze_files(paths, jobs=1)
+            parallel = self.analyzer.analyze_files(paths, jobs=4)
+
+        self.assertEqual(list(parallel), paths)
+        self.assertIsNone(parallel[paths[-1]])
+        for path in paths[:-1]:
+            self.assertEqual(parallel[path].findings, sequential[path].findings)
+            self.assertEqual({finding.detector_id for finding in parallel[path].findings},
+                             {"unsafe_unwrap", "unsafe_panic"})
+
+        # Reports list the findings by file, line and detector however the workers returned them
+        findings = [finding for result in parallel.values() if result for finding in result.findings]
+        random.Random(0).shuffle(findings)
+        self.assertEqual([(finding.file, finding.line, finding.detector_id) for finding in sort_findings(findings)],
+                         [(path, line, detector_id) for path in sorted(paths[:-1])
+                          for line, detector_id in ((2, "unsafe_unwrap"), (3, "unsafe_panic"))])
+
+    def test_parallel_analysis_distributes_files(self):
+        """Test that analyze_files spreads the files over several worker processes"""
+        analyzer = StaticAnalyzer()
+        analyzer.registry = DetectorRegistry([WorkerPidDetector()])
+        with tempfile.TemporaryDirectory() as root:
+            paths = []
+            for index in range(16):
+                path = os.path.join(root, f"contract_{index:02d}.rs")
+                with open(path, 'w') as f:
+                    f.write(f"pub fn run_{index}() {{}}\n")
+                paths.append(path)
+
+            sequential = analyzer.analyze_files(paths, jobs=1)
+            parallel = analyzer.analyze_files(paths, jobs=4)
+
+        self.assertEqual({result.findings[0].message for result in sequential.values()}, {str(os.getpid())})
+        pids = {result.findings[0].message for result in parallel.values()}
+        self.assertGreater(len(pids), 1)
+        self.assertNotIn(str(os.getpid()), pids)
+
+    def test_cache_reuses_results_until_detectors_change(self):
+        """Test that unchanged files hit the cache and that changing the detector set invalidates it"""
+        with tempfile.TemporaryDirectory() as root:
+            path = os.path.join(root, "contract.rs")
+            with open(self.test_dir / "sarif_example.rs", 'r') as source, open(path, 'w') as f:
+                f.write(source.read())
+            self.analyzer.cache = AnalysisCache(os.path.join(root, "cache"))
+            first = self.analyzer.analyze_file(path)
+            self.assertTrue(first.findings)
+
+            analyzed = []
+            original_analyze = self.analyzer.analyze
+            self.analyzer.analyze = lambda code, file_path=None: analyzed.append(file_path) or \
+                original_analyze(code, file_path)
+
+            # Unchanged content and detectors reuse the cached findings
+            cached = self.analyzer.analyze_file(path)
+            self.assertEqual(analyzed, [])
+            self.assertEqual(cached.findings, first.findings)
+
+            # Disabling a detector misses the cache
+            self.analyzer.registry.disable("unsafe_panic")
+            reanalyzed = self.analyzer.analyze_file(path)
+            self.assertEqual(analyzed, [path])
+            self.assertNotIn("unsafe_panic", {finding.detector_id for finding in reanalyzed.findings})
+
+            # So does a different severity for an enabled detector
+            self.analyzer.registry.override_severity("unsafe_unwrap", Severity.LOW)
+            self.analyzer.analyze_file(path)
+            self.assertEqual(len(analyzed), 2)
+
+            # Or different detector options
+            self.analyzer.registry.configure("fallback_with_logic", {"max_statements": 2})
+            self.analyzer.analyze_file(path)
+            self.assertEqual(len(analyzed), 3)
+
+            # And editing the file
+            with open(path, 'a') as f:
+                f.write("\n// edited\n")
+            self.analyzer.analyze_file(path)
+            self.assertEqual(len(analyzed), 4)
+
+            self.assertTrue(self.analyzer.cache.clear())
+            self.assertFalse(os.path.exists(os.path.join(root, "cache")))
+
+    def test_sol_parser_transfer_from(self):
+        """Test that sol! function bodies are parsed into statements detectors can inspect"""
+        with open(self.test_dir / "token.rs", 'r') as f:
+            code = f.read()
+        context = AnalysisContext(tree=generate_rust_ast(code), code=code)
+
+        transfer_from = next(function for function in context.sol_functions if function.name == "transferFrom")
+        self.assertEqual(transfer_from.line, 50)
+        self.assertEqual(transfer_from.parameters, ["from", "to", "value"])
+        self.assertIn("public", transfer_from.modifiers)
+        self.assertEqual(transfer_from.statements[0].kind, "require")
+
+        subtractions = [statement for statement in transfer_from.statements if statement.is_subtraction]
+        self.assertEqual([(statement.line, statement.target, statement.operator, statement.value)
+                          for statement in subtractions],
+                         [(52, "balanceOf[from]", "-=", "value"), (53, "allowance[from][msg.sender]", "-=", "value")])
+        self.assertTrue(all(statement.kind == "assignment" for statement in subtractions))
+
+        # Rust impl methods and sol! functions are iterated alike
+        functions = list(context.iter_functions())
+        self.assertIn(("sol", "transferFrom"), [(function.language, function.name) for function in functions])
+        self.assertTrue(all(function.body is not None for function in functions))
+
+    def test_storage_layout_of_both_field_styles(self):
+        """Test that #[storage] structs with SDK field types and #[public] structs with Storage<T> fields are collected"""
+        def layout_of(name):
+            with open(self.test_dir / name, 'r') as f:
+                code = f.read()
+            return AnalysisContext(tree=generate_rust_ast(code), code=code).storage_layout()
+
+        wrapped = layout_of("unwrap_example.rs")
+        self.assertEqual([struct.name for struct in wrapped.structs], ["UnwrapExamples"])
+        self.assertEqual([(field.name, field.type, field.value_type) for field in wrapped.structs[0].fields],
+                         [("owner", "Storage<Address>", "Address"), ("balance", "Storage<U256>", "U256"),
+                          ("last_caller", "Storage<Address>", "Address")])
+
+        sdk_types = layout_of("uninitialized_storage_example.rs")
+        self.assertEqual([struct.name for struct in sdk_types.structs], ["Vault", "Registry", "Config"])
+        self.assertEqual([(field.name, field.type) for field in sdk_types.get_struct("Config").fields],
+                         [("owner", "StorageAddress"), ("oracle", "StorageAddress")])
+        fee = sdk_types.get_field("fee_bps")
+        self.assertEqual((fee.struct, fee.type, fee.value_type, fee.line), ("Vault", "StorageU256", "StorageU256", 13))
+        self.assertIn("balances", sdk_types)
+        self.assertNotIn("missing", sdk_types)
+
+    def test_sol_interface_signatures(self):
+        """Test that sol_interface! declarations are parsed with their signatures and return types"""
+        with open(self.test_dir / "unsafe_transfer_example.rs", 'r') as f:
+            code = f.read()
+        interfaces = AnalysisContext(tree=generate_rust_ast(code), code=code).interfaces()
+
+        self.assertEqual(list(interfaces), ["IERC20"])
+        transfer = interfaces["IERC20"].get_function("transfer")
+        self.assertEqual(transfer.signature, "transfer(address,uint256)")
+        self.assertEqual(transfer.parameter_names, ["to", "value"])
+        self.assertTrue(transfer.returns_bool)
+        balance_of = interfaces["IERC20"].get_function("balanceOf")
+        self.assertEqual((balance_of.return_types, balance_of.state_mutability), (["uint256"], "view"))
+        self.assertFalse(balance_of.returns_bool)
+
+        # The unchecked transfer detector uses the declared return type for interfaces of any name
+        vault_code = '''
+sol_interface! {
+    interface IVault {
+        function transfer(address to, uint256[] calldata ids) external returns (bool);
+    }
+}
+
+#[public]
+impl Router {
+    pub fn send(&mut self, vault: IVault, to: Address, ids: Vec<U256>) -> Result<(), Vec<u8>> {
+        let _ = vault.transfer(self, to, ids);
+        Ok(())
+    }
+}
+'''
+        vault = AnalysisContext(tree=generate_rust_ast(vault_code), code=vault_code).interfaces()["IVault"]
+        self.assertEqual(vault.functions[0].signature, "transfer(address,uint256[])")
+        findings = self.analyzer.analyze(vault_code).findings
+        self.assertEqual([finding.line for finding in findings if finding.detector_id == "unchecked_transfer"], [11])
+
+    def test_call_graph_chain_and_recursion(self):
+        """Test callers, callees and reachability over a chain of internal calls and a recursive method"""
+        code = '''
+#[public]
+impl Vault {
+    pub fn deposit(&mut self, amount: U256) {
+        self.credit(amount);
+    }
+
+    fn credit(&mut self, amount: U256) {
+        Self::record(self, amount);
+    }
+
+    fn record(&mut self, amount: U256) {
+        self.total.set(amount);
+    }
+
+    fn walk(&self, depth: u32) -> u32 {
+        if depth == 0 { return 0; }
+        self.bounce(depth - 1)
+    }
+
+    fn bounce(&self, depth: u32) -> u32 {
+        self.walk(depth)
+    }
+
+    fn unused(&self) {}
+}
+'''
+        graph = AnalysisContext(tree=generate_rust_ast(code), code=code).call_graphs()["Vault"]
+
+        self.assertEqual(graph.public_methods, {"deposit"})
+        self.assertEqual(graph.callees("deposit"), {"credit"})
+        self.assertEqual(graph.callees("credit"), {"record"})
+        self.assertEqual(graph.callers("record"), {"credit"})
+        self.assertEqual(graph.callers("deposit"), set())
+        self.assertEqual(graph.reachable_from(["deposit"]), {"deposit", "credit", "record"})
+        self.assertTrue(graph.is_reachable_from_public("record"))
+
+        # The walk/bounce cycle terminates and isn't reachable from the public method
+        self.assertEqual(graph.reachable_from(["walk"]), {"walk", "bounce"})
+        self.assertTrue(graph.is_recursive("walk"))
+        self.assertFalse(graph.is_recursive("credit"))
+        self.assertFalse(graph.is_reachable_from_public("bounce"))
+        self.assertFalse(graph.is_reachable_from_public("unused"))
+
+    def test_taint_analysis_propagation(self):
+        """Test that taint flows from parameters and sources through let, arithmetic and method calls"""
+        code = '''
+fn play(&mut self, guess: U256, salt: U256) -> bool {
+    let doubled = guess * U256::from(2);
+    let mixed = doubled.wrapping_add(self.nonce.get());
+    let hashed = keccak256(mixed.to_be_bytes::<32>());
+    let now = block::timestamp();
+    let (seed, _) = (now, 0);
+    let fixed = U256::from(7);
+    let mut total = fixed;
+    if let Some(winner) = self.winners.get(seed) { total = winner; }
+    for item in salt.iter() { let copy = item; }
+    let early = late;
+    let late = fixed + guess;
+    hashed == total
+}
+'''
+        tree = generate_rust_ast(code)
+        function_node = tree.root_node.named_children[0]
+
+        user_input = TaintAnalysis(function_node, code, sources=["guess"])
+        for name in ("guess", "doubled", "mixed", "hashed", "late"):
+            self.assertTrue(user_input.is_tainted(name), name)
+        for name in ("salt", "now", "seed", "fixed", "total", "item", "copy"):
+            self.assertFalse(user_input.is_tainted(name), name)
+        # A name is tainted everywhere once tainted, even before its binding
+        self.assertTrue(user_input.is_tainted("early"))
+
+        predictable = TaintAnalysis(function_node, code, source_pattern=BLOCK_SOURCE_PATTERN, taint_parameters=True)
+        for name in ("now", "seed", "winner", "total", "salt", "item", "copy"):
+            self.assertTrue(predictable.is_tainted(name), name)
+        self.assertFalse(predictable.is_tainted("fixed"))
+        self.assertFalse(predictable.is_tainted("Some"))
+
+        # Expressions are tainted if they mention a tainted local or a source
+        return_expr = function_node.child_by_field_name("body").named_children[-1]
+        self.assertTrue(user_input.is_tainted(return_expr))
+
+    def test_snippet_caret_aligns_with_column(self):
+        """Test that the caret underline of a text snippet starts at the finding's column and covers its span"""
+        with open(self.test_dir / "sarif_example.rs", 'r') as f:
+            code = f.read()
+        lines = code.splitlines()
+        findings = self.analyzer.analyze(code, None).findings
+        unwrap = next(finding for finding in findings if finding.detector_id == "unsafe_unwrap")
+
+        rendered = format_snippet(lines, unwrap)
+        self.assertEqual(rendered[0], f"  --> :{unwrap.line}:{unwrap.column}")
+        source_index = next(index for index, line in enumerate(rendered) if line.startswith(f"{unwrap.line:>2} | "))
+        source_line, caret_line = rendered[source_index], rendered[source_index + 1]
+        caret_start = caret_line.index("^")
+        self.assertEqual(caret_start - len(f"{unwrap.line:>2} | "), unwrap.column - 1)
+        self.assertEqual(source_line[caret_start:caret_start + caret_line.count("^")], unwrap.code_snippet.strip())
+        # Two lines of context on each side
+        self.assertEqual([line.split(" |")[0].strip() for line in rendered[2:-1] if "^" not in line],
+                         [str(number) for number in range(unwrap.line - 2, unwrap.line + 3)])
+
+        # Tabs are expanded before placing the carets, and unknown lines render nothing
+        tabbed = Finding("unsafe_unwrap", Severity.MEDIUM, "", line=1, column=2, code_snippet="x.unwrap()")
+        self.assertEqual(format_snippet(["\tx.unwrap();"], tabbed, context_lines=0)[3], "  |     ^^^^^^^^^^")
+        self.assertEqual(format_snippet(lines, Finding("reentrancy_feature", Severity.HIGH, "", line=0)), [])
+
+    def test_color_never_is_plain_output(self):
+        """Test that --color never prints the same bytes as --color always without the escape codes"""
+        runner = CliRunner()
+        contract_path = str(self.test_dir / "sarif_example.rs")
+
+        def run(color):
+            result = runner.invoke(cli, ["static-analyze", contract_path, "--no-cache", "--color", color])
+            self.assertEqual(result.exit_code, 0, result.output)
+            # The timing lines differ between runs
+            return "\n".join(line for line in result.stdout.splitlines()
+                             if not line.startswith(("Analysis completed", "Analyzed ")))
+
+        plain = run("never")
+        colored = run("always")
+        self.assertNotIn("\x1b[", plain)
+        self.assertIn("\x1b[31m", colored)
+        self.assertEqual(click.unstyle(colored), plain)
+        self.assertIn("High severity issues:", plain)
+
+    def test_workspace_members_are_analyzed(self):
+        """Test that a workspace resolves to the sources of its member crates, skipping excluded ones"""
+        workspace = self.test_dir / "workspace"
+        expected = [str(workspace / "crates" / "token" / "src" / "lib.rs"),
+                    str(workspace / "crates" / "vault" / "src" / "lib.rs"),
+                    str(workspace / "crates" / "vault" / "src" / "logic" / "mod.rs")]
+        self.assertEqual(find_workspace_contracts(str(workspace / "Cargo.toml")), expected)
+        self.assertEqual(collect_contract_files(str(workspace)), expected)
+
+        result = CliRunner().invoke(cli, ["static-analyze", str(workspace / "Cargo.toml"), "--format", "json",
+                                          "--no-cache"])
+        self.assertEqual(result.exit_code, 0, result.output)
+        findings = load_json_report(result.stdout)
+        self.assertEqual({(os.path.relpath(finding.file, workspace), finding.detector_id) for finding in findings
+                          if finding.detector_id != "public_could_be_external"},
+                         {(os.path.join("crates", "token", "src", "lib.rs"), "unsafe_unwrap"),
+                          (os.path.join("crates", "token", "src", "lib.rs"), "unused_storage_field"),
+                          (os.path.join("crates", "vault", "src", "lib.rs"), "unsafe_panic"),
+                          (os.path.join("crates", "vault", "src", "lib.rs"), "unused_storage_field"),
+                          (os.path.join("crates", "vault", "src", "logic", "mod.rs"), "unsafe_unwrap")})
+
+        with tempfile.TemporaryDirectory() as root:
+            manifest = os.path.join(root, "Cargo.toml")
+            with open(manifest, 'w') as f:
+                f.write('[workspace]\nmembers = ["missing"]\n')
+            with self.assertRaises(WorkspaceError):
+                collect_contract_files(root)
+
+    def test_gitignore_and_include_exclude_globs(self):
+        """Test that gitignored and excluded files are skipped and --include narrows the analyzed files"""
+        with tempfile.TemporaryDirectory() as root:
+            for name in ("src/token.rs", "src/vault.rs", "generated/bindings.rs", "vendor/lib.rs", "debug.rs"):
+                path = os.path.join(root, name)
+                os.makedirs(os.path.dirname(path), exist_ok=True)
+                with open(path, 'w') as f:
+                    f.write("fn main() {}\n")
+            with open(os.path.join(root, ".gitignore"), 'w') as f:
+                f.write("# build output\ngenerated/\n*.rs\n!src/*.rs\n!vendor/lib.rs\n")
+
+            def collect(**options):
+                paths = collect_contract_files(root, FileFilter(**options))
+                return [os.path.relpath(path, root).replace(os.sep, "/") for path in paths]
+
+            self.assertEqual(collect(), ["src/token.rs", "src/vault.rs", "vendor/lib.rs"])
+            self.assertEqual(collect(gitignore=False),
+                             ["debug.rs", "generated/bindings.rs", "src/token.rs", "src/vault.rs", "vendor/lib.rs"])
+            self.assertEqual(collect(include=["**/token.rs"]), ["src/token.rs"])
+            self.assertEqual(collect(exclude=["vendor/**"]), ["src/token.rs", "src/vault.rs"])
+            self.assertEqual(collect(include=["src"], exclude=["vault.rs"]), ["src/token.rs"])
+
+            result = CliRunner().invoke(cli, ["static-analyze", root, "--no-cache", "--color", "never",
+                                              "--include", "**/token.rs"])
+            self.assertEqual(result.exit_code, 0, result.output)
+            self.assertIn("token.rs", result.output)
+            self.assertNotIn("vault.rs", result.output)
+
+    def test_cargo_subcommand_invocation(self):
+        """Test that the cargo entry point drops the subcommand name cargo passes before the arguments"""
+        contract = str(self.test_dir / "unwrap_example.rs")
+        script = "from stylus_analyzer.cli import cargo_main; cargo_main()"
+        for argv in (["stylus-analyzer", "static-analyze"], ["static-analyze"]):
+            result = subprocess.run([sys.executable, "-c", script] + argv + [contract, "--format", "json", "--no-cache"],
+                                    capture_output=True, text=True)
+            self.assertEqual(result.returncode, 0, result.stderr)
+            findings = load_json_report(result.stdout)
+            self.assertIn("unsafe_unwrap", {finding.detector_id for finding in findings})
+
+        result = subprocess.run([sys.executable, "-c", script, "stylus-analyzer", "--help"],
+                                capture_output=True, text=True)
+        self.assertIn("Usage: cargo stylus-analyzer", result.stdout)
+
+    def test_lsp_publishes_diagnostics_on_open(self):
+        """Test that the language server answers a didOpen of token.rs with diagnostics for its findings"""
+        contract = self.test_dir / "token.rs"
+        uri = path_to_uri(str(contract))
+        requests = io.BytesIO()
+        for message in (
+            {"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {"capabilities": {}}},
+            {"jsonrpc": "2.0", "method": "initialized", "params": {}},
+            {"jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {"textDocument": {
+                "uri": uri, "languageId": "rust", "version": 1, "text": contract.read_text()}}},
+            {"jsonrpc": "2.0", "method": "textDocument/didChange", "params": {
+                "textDocument": {"uri": uri, "version": 2}, "contentChanges": [{"text": "fn main() {}\n"}]}},
+            {"jsonrpc": "2.0", "id": 2, "method": "shutdown"},
+            {"jsonrpc": "2.0", "method": "exit"},
+        ):
+            write_message(requests, message)
+        requests.seek(0)
+        responses = io.BytesIO()
+        self.assertEqual(LanguageServer(StaticAnalyzer(), requests, responses).serve(), 0)
+
+        responses.seek(0)
+        messages = []
+        while True:
+            message = read_message(responses)
+            if message is None:
+                break
+            messages.append(message)
+        self.assertEqual(messages[0]["result"]["capabilities"]["textDocumentSync"]["change"], 1)
+        published = [message["params"] for message in messages
+                     if message.get("method") == "textDocument/publishDiagnostics"]
+        self.assertEqual([params["uri"] for params in published], [uri, uri])
+
+        diagnostics = published[0]["diagnostics"]
+        approve = next(diagnostic for diagnostic in diagnostics if diagnostic["code"] == "approve_race_condition")
+        self.assertEqual(approve["severity"], 3)
+        self.assertEqual(approve["source"], "stylus-analyzer")
+        self.assertEqual(approve["range"]["start"], {"line": 44, "character": 12})
+        arithmetic = next(diagnostic for diagnostic in diagnostics if diagnostic["code"] == "unchecked_arithmetic")
+        self.assertEqual(arithmetic["severity"], 2)
+        # Only the changed document is re-analyzed, from the editor's text
+        self.assertEqual(published[1]["diagnostics"], [])
+        self.assertEqual(messages[-1], {"jsonrpc": "2.0", "id": 2, "result": None})
+
+    def test_lsp_code_action_replaces_unwrap(self):
+        """Test that an unwrap finding in a Result function yields a quick fix replacing the call with `?`"""
+        contract = self.test_dir / "unwrap_example.rs"
+        uri = path_to_uri(str(contract))
+        text = contract.read_text()
+        server = LanguageServer(StaticAnalyzer(), io.BytesIO(), io.BytesIO())
+        server.handle({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}})
+        server.handle({"jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {"textDocument": {
+            "uri": uri, "languageId": "rust", "version": 1, "text": text}}})
+
+        line = next(index for index, source in enumerate(text.splitlines()) if "try_as_usize().unwrap()" in source)
+        actions = server.code_actions(uri, line, line)
+        self.assertEqual([action["title"] for action in actions], ["Replace `.unwrap()` with `?`"])
+        self.assertEqual(actions[0]["kind"], "quickfix")
+        self.assertEqual(actions[0]["diagnostics"][0]["code"], "unsafe_unwrap")
+        edit, = actions[0]["edit"]["changes"][uri]
+        source = text.splitlines()[line]
+        start, end = edit["range"]["start"], edit["range"]["end"]
+        self.assertEqual((start["line"], end["line"]), (line, line))
+        self.assertEqual(source[start["character"]:end["character"]], ".unwrap()")
+        self.assertEqual(source[:start["character"]] + edit["newText"] + source[end["character"]:],
+                         "        let idx = index.try_as_usize()?; // This can panic if index doesn't fit in usize")
+
+        # Unwraps in functions that can't propagate with `?` get no fix
+        result = StaticAnalyzer().analyze("impl A {\n    pub fn f(&self) -> U256 {\n        self.x.get(0).unwrap()\n    }\n}\n")
+        self.assertEqual([(finding.detector_id, finding.fix) for finding in result.findings
+                          if finding.detector_id == "unsafe_unwrap"], [("unsafe_unwrap", None)])
+
+    def test_lsp_survives_malformed_messages(self):
+        """Test that a handler failing on a malformed message is logged and answered without stopping the server"""
+        uri = path_to_uri(str(self.test_dir / "unwrap_example.rs"))
+        selection = {"start": {"line": 0, "character": 0}, "end": {"line": 0, "character": 0}}
+        requests = io.BytesIO()
+        for message in (
+            {"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {"capabilities": {}}},
+            # Neither has the text document, so their handlers raise
+            {"jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {}},
+            {"jsonrpc": "2.0", "id": 2, "method": "textDocument/codeAction", "params": {"range": selection}},
+            {"jsonrpc": "2.0", "id": 3, "method": "textDocument/codeAction",
+             "params": {"textDocument": {"uri": uri}, "range": selection}},
+            {"jsonrpc": "2.0", "id": 4, "method": "shutdown"},
+            {"jsonrpc": "2.0", "method": "exit"},
+        ):
+            write_message(requests, message)
+        requests.seek(0)
+        responses = io.BytesIO()
+        with self.assertLogs("stylus_analyzer.lsp", level="ERROR") as logs:
+            self.assertEqual(LanguageServer(StaticAnalyzer(), requests, responses).serve(), 0)
+        self.assertEqual(len(logs.records), 2)
+        self.assertIn("textDocument/didOpen", logs.output[0])
+
+        responses.seek(0)
+        messages = []
+        while True:
+            message = read_message(responses)
+            if message is None:
+                break
+            messages.append(message)
+        # The failed notification gets no answer, the failed request an internal error
+        self.assertEqual([message.get("id") for message in messages], [1, 2, 3, 4])
+        self.assertEqual(messages[1]["error"]["code"], -32603)
+        self.assertIn("textDocument/codeAction", messages[1]["error"]["message"])
+        self.assertEqual(messages[2], {"jsonrpc": "2.0", "id": 3, "result": []})
+        self.assertEqual(messages[3], {"jsonrpc": "2.0", "id": 4, "result": None})
+
+    def test_fix_is_idempotent(self):
+        """Test that --fix rewrites unwraps, divisions and loop lengths, and that a second run changes nothing"""
+        names = ("unwrap_example.rs", "division_example.rs", "loop_length_example.rs")
+        with tempfile.TemporaryDirectory() as root:
+            for name in names:
+                Path(root, name).write_text((self.test_dir / name).read_text())
+
+            first = CliRunner().invoke(cli, ["static-analyze", root, "--fix", "--no-cache", "--format", "json"])
+            self.assertEqual(first.exit_code, 0, first.output)
+            fixed = {name: Path(root, name).read_text() for name in names}
+            self.assertIn("let idx = index.try_as_usize()?;", fixed["unwrap_example.rs"])
+            # `?` can't turn the Option of `first()` into the function's Result
+            self.assertIn("addresses.first().unwrap()", fixed["unwrap_example.rs"])
+            self.assertIn("self.total.get() * elapsed / self.duration.get()", fixed["division_example.rs"])
+            self.assertIn("weight * amount / parts", fixed["division_example.rs"])
+            self.assertIn("let items_len = self.items.len();\n        while i < items_len {",
+                          fixed["loop_length_example.rs"])
+            # The fixed findings are not reported, leaving the sol! division, which has no fix
+            remaining = load_json_report(first.stdout)
+            self.assertEqual([finding.line for finding in remaining
+                              if finding.detector_id == "division_before_multiplication"], [11])
+            self.assertNotIn("array_length_in_loop_condition", {finding.detector_id for finding in remaining})
+
+            second = CliRunner().invoke(cli, ["static-analyze", root, "--fix", "--no-cache", "--format", "json"])
+            self.assertEqual(second.exit_code, 0, second.output)
+            self.assertNotIn("Fixed", second.stderr)
+            self.assertEqual({name: Path(root, name).read_text() for name in names}, fixed)
+
+        def finding(line: int, column: int, column_end: int, replacement: str) -> Finding:
+            return Finding(detector_id="test", severity=Severity.LOW, message="", line=line, column=column,
+                           fix=Fix("test", [TextEdit(line, column, line, column_end, replacement)], safe=True))
+
+        overlapping = [finding(1, 5, 10, "X"), finding(1, 8, 12, "Y"), finding(2, 1, 1, "Z")]
+        result = apply_fixes("let a = b + c;\nd;\n", overlapping)
+        self.assertEqual(result.code, "let X + c;\nZd;\n")
+        self.assertEqual(result.applied, [overlapping[0], overlapping[2]])
+        self.assertEqual(result.skipped, [overlapping[1]])
+
+    def test_explain_detector(self):
+        """Test that explain resolves class names and documents every built-in detector"""
+        runner = CliRunner()
+        result = runner.invoke(cli, ["explain", "MissingAccessControl", "--format", "json"])
+        self.assertEqual(result.exit_code, 0, result.output)
+        explanation = json.loads(result.stdout)
+        self.assertEqual(explanation["id"], "missing_access_control")
+        self.assertTrue(explanation["documentation"]["rationale"].strip())
+        self.assertIn("fn mint", explanation["documentation"]["vulnerable_example"])
+        self.assertIn("msg::sender()", explanation["documentation"]["fixed_example"])
+
+        text = runner.invoke(cli, ["explain", "missing_access_control"])
+        self.assertEqual(text.exit_code, 0, text.output)
+        self.assertIn("Why it matters:", text.stdout)
+        self.assertNotEqual(runner.invoke(cli, ["explain", "no_such_detector"]).exit_code, 0)
+
+        for detector in DetectorRegistry.with_builtin_detectors():
+            self.assertIsNotNone(detector.documentation, detector.id)
+            self.assertTrue(detector.documentation.vulnerable_example.strip(), detector.id)
+            self.assertTrue(detector.documentation.fixed_example.strip(), detector.id)
+
+    def test_summary_matches_reported_findings(self):
+        """Test that the summary tallies exactly the findings reported after filtering"""
+        runner = CliRunner()
+        target = str(self.test_dir)
+        for threshold in (None, "medium", "high"):
+            args = ["static-analyze", target, "--no-cache", "--format", "json"]
+            if threshold:
+                args += ["--severity-threshold", threshold]
+            result = runner.invoke(cli, args)
+            self.assertEqual(result.exit_code, 0, result.output)
+            report = json.loads(result.stdout)
+            findings, summary = report["findings"], report["summary"]
+
+            self.assertEqual(summary["total"], len(findings))
+            for severity in Severity:
+                self.assertEqual(summary["by_severity"][str(severity)],
+                                 sum(1 for finding in findings if finding["severity"] == str(severity)))
+            detector_ids = [finding["detector_id"] for finding in findings]
+            self.assertEqual(summary["by_detector"],
+                             {detector_id: detector_ids.count(detector_id) for detector_id in set(detector_ids)})
+            self.assertEqual(summary["files_scanned"], len(collect_contract_files(target)))
+            if threshold == "high":
+                self.assertEqual(summary["by_severity"]["Medium"], 0)
+
+        text = runner.invoke(cli, ["static-analyze", str(self.test_dir / "sarif_example.rs"), "--no-cache",
+                                   "--severity-threshold", "medium"])
+        self.assertEqual(text.exit_code, 0, text.output)
+        summary = text.stdout.split("===== Analysis Summary =====")[1]
+        self.assertIn("Found 2 total issues", summary)
+        self.assertIn("High: 1", summary)
+        self.assertIn("Medium: 1", summary)
+        self.assertNotIn("Info", summary)
+
+    def test_dead_code_after_revert_detection(self):
+        """Test that statements after an unconditional return, revert or panic are detected"""
+        with open(self.test_dir / "dead_code_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "dead_code_after_revert"]
+
+        # Conditional returns and an if/else with one returning arm are fine
+        self.assertEqual([issue["line_start"] for issue in issues], [24, 35, 42, 70])
+        self.assertEqual(issues[0]["code_snippet"], "self.total.set(total);")
+        self.assertIn("the return on line 23", issues[0]["description"])
+        self.assertIn("if/else whose branches all exit", issues[1]["description"])
+        self.assertIn("the revert", issues[2]["description"])
+        self.assertIn("panic!()", issues[3]["description"])
+        self.assertTrue(all(issue["severity"] == "Low" for issue in issues))
+
+    def test_ignored_result_detection(self):
+        """Test that dropped Result and Option values are detected, with `let _ =` downgraded to Info"""
+        with open(self.test_dir / "ignored_result_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "ignored_result"]
+
+        # Propagated, bound and checked results in withdraw are fine, and the match in sync is
+        # left to the empty catch arm detector
+        self.assertEqual([(issue["line_start"], issue["severity"]) for issue in issues],
+                         [(27, "Medium"), (34, "Medium"), (41, "Medium"), (47, "Info")])
+        self.assertIn("`self.credit`", issues[0]["description"])
+        self.assertIn("`transfer_eth`", issues[1]["description"])
+        self.assertIn("`oracle.update`", issues[2]["description"])
+        self.assertIn("explicitly discarded", issues[3]["description"])
+
+    def test_empty_catch_arm_detection(self):
+        """Test that matches on a Result whose Err arm does nothing are detected"""
+        with open(self.test_dir / "empty_catch_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "empty_catch_arm"]
+
+        # Logging and returning arms handle the error, and `None => {}` on an Option is fine
+        self.assertEqual([(issue["line_start"], issue["line_end"]) for issue in issues], [(28, 31), (38, 43), (49, 52)])
+        self.assertIn("The `Err(_)` arm", issues[0]["description"])
+        self.assertIn("The `Err(e)` arm", issues[1]["description"])
+        self.assertIn("The `_` arm", issues[2]["description"])
+        self.assertTrue(issues[0]["code_snippet"].startswith("match token.transfer("))
+        self.assertIn("`?`", issues[0]["recommendation"])
+
+        with open(self.test_dir / "unsafe_transfer_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "empty_catch_arm"]
+        self.assertEqual(len(issues), 1)
+        self.assertIn("function 'transfer_with_ignored_error'", issues[0]["description"])
+        self.assertFalse(any(issue["type"] == "ignored_result" and issue["severity"] == "Medium"
+                             for issue in results.issues))
+
+    def test_msg_sender_in_view_detection(self):
+        """Test that caller checks and storage writes in read-only functions are detected"""
+        with open(self.test_dir / "msg_sender_in_view_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "msg_sender_in_view"]
+
+        # Caller-keyed reads, plain comparisons, gated setters and the guard helper itself are fine
+        self.assertEqual([(issue["line_start"], issue["severity"]) for issue in issues],
+                         [(34, "Low"), (42, "Low"), (48, "Low"), (15, "Low")])
+        self.assertIn("read-only function 'secret' restricts its caller", issues[0]["description"])
+        self.assertIn("read-only function 'audit_secret'", issues[1]["description"])
+        self.assertIn("'read_and_count' takes `&self` but writes storage field 'reads'", issues[2]["description"])
+        self.assertIn("view sol! function 'getFee'", issues[3]["description"])
+        self.assertIn("`&mut self`", issues[0]["recommendation"])
+
+        # Returning a boolean from a comparison with the caller doesn't restrict it
+        with open(self.test_dir / "weak_randomness_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        self.assertFalse(any(issue["type"] == "msg_sender_in_view" for issue in results.issues))
+
+    def test_source_map_multibyte_positions(self):
+        """Test that byte offsets are mapped to exact text and character columns across multibyte characters"""
+        code = '// Prüfung 🦀\nfn f(x: Option<u8>) {\n    let s = "🦀é"; let v = x.unwrap();\n}\n'
+        tree = generate_rust_ast(code)
+        call, stack = None, [tree.root_node]
+        while call is None:
+            node = stack.pop()
+            call = node if node.type == "call_expression" else None
+            stack.extend(node.children)
+
+        source_map = SourceMap.of(code)
+        self.assertIs(SourceMap.of(code), source_map)
+        self.assertEqual(source_map.node_text(call), "x.unwrap()")
+        # The emoji takes 4 bytes and é 2, but each is a single character
+        column = code.splitlines()[2].index("x.unwrap()") + 1
+        span = source_map.span(call)
+        self.assertEqual((span.line, span.column, span.line_end, span.column_end), (3, column, 3, column + 10))
+        self.assertEqual(source_map.position(call.start_byte), (3, column))
+        self.assertEqual(source_map.text(span.start_byte, span.end_byte), "x.unwrap()")
+        self.assertEqual(source_map.position(len(code.encode("utf-8"))), (5, 1))
+
+    def test_repeated_token_columns(self):
+        """Test that findings of a token repeated on its line each carry the column of their own occurrence"""
+        code = ('impl Vault {\n    pub fn get(&self, x: Option<U256>) -> U256 {\n'
+                '        let total = x.unwrap() + x.unwrap(); x.unwrap()\n    }\n}\n')
+        findings = [finding for finding in StaticAnalyzer().analyze(code).findings
+                    if finding.detector_id == "unsafe_unwrap"]
+        line = code.splitlines()[2]
+        columns = [index + 1 for index in range(len(line)) if line.startswith("x.unwrap()", index)]
+        self.assertEqual(len(columns), 3)
+        self.assertEqual(sorted((finding.line, finding.column) for finding in findings),
+                         [(3, column) for column in columns])
+
+    def test_multibyte_text_before_finding(self):
+        """Test that findings after multibyte characters are reported, located and fixed exactly"""
+        code = ('// Überweisung 🦀\nimpl Vault {\n    pub fn get(&self, x: Result<U256, Vec<u8>>) -> Result<U256, Vec<u8>> {\n'
+                '        let label = "🦀🦀"; let value = x.unwrap();\n        Ok(value)\n    }\n}\n')
+        result = StaticAnalyzer().analyze(code)
+        finding, = [finding for finding in result.findings if finding.detector_id == "unsafe_unwrap"]
+        line = code.splitlines()[3]
+        self.assertEqual((finding.line, finding.column), (4, line.index("x.unwrap()") + 1))
+        self.assertEqual(finding.code_snippet, "x.unwrap()")
+        self.assertEqual(apply_fixes(code, [finding]).code.splitlines()[3],
+                         '        let label = "🦀🦀"; let value = x?;')
+
+        # LSP positions count UTF-16 code units, two per emoji
+        uri = path_to_uri(os.path.join(tempfile.gettempdir(), "vault.rs"))
+        server = LanguageServer(StaticAnalyzer(), io.BytesIO(), io.BytesIO())
+        server.handle({"jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {}})
+        server.handle({"jsonrpc": "2.0", "method": "textDocument/didOpen", "params": {"textDocument": {
+            "uri": uri, "languageId": "rust", "version": 1, "text": code}}})
+        diagnostic = next(diagnostic for diagnostic in server.diagnostics(uri) if diagnostic["code"] == "unsafe_unwrap")
+        self.assertEqual(diagnostic["range"]["start"], {"line": 3, "character": line.index("x.unwrap()") + 2})
+        edit, = server.code_actions(uri, 3, 3)[0]["edit"]["changes"][uri]
+        self.assertEqual(edit["range"]["start"]["character"], line.index(".unwrap()") + 2)
+
+    def test_parse_error_does_not_abort_run(self):
+        """Test that a file failing to parse is reported with its error location and the other files are analyzed"""
+        with tempfile.TemporaryDirectory() as root:
+            (Path(root) / "valid.rs").write_text((self.test_dir / "unsafe_transfer_example.rs").read_text())
+            (Path(root) / "broken.rs").write_text("#[public]\nimpl Broken {\n    pub fn f(&self) -> U256 { let x = ; x }\n}\n")
+
+            result = StaticAnalyzer().analyze_file(str(Path(root) / "broken.rs"))
+            self.assertEqual(result.findings, [])
+            self.assertEqual(result.parse_error.file, str(Path(root) / "broken.rs"))
+            self.assertEqual(result.parse_error.line, 3)
+            self.assertGreater(result.parse_error.column, 0)
+            self.assertIsNone(StaticAnalyzer().analyze_file(str(Path(root) / "valid.rs")).parse_error)
+
+            runner = CliRunner()
+            report = json.loads(runner.invoke(cli, ["static-analyze", root, "--no-cache", "--format", "json"]).stdout)
+            self.assertTrue(any(finding["file"].endswith("valid.rs") for finding in report["findings"]))
+            parse_error, = report["parse_errors"]
+            self.assertTrue(parse_error["file"].endswith("broken.rs"))
+            self.assertEqual(parse_error["line"], 3)
+            self.assertEqual((report["summary"]["files_scanned"], report["summary"]["parse_errors"]), (2, 1))
+
+            sarif = json.loads(runner.invoke(cli, ["static-analyze", root, "--no-cache", "--format", "sarif"]).stdout)
+            notification, = sarif["runs"][0]["invocations"][0]["toolExecutionNotifications"]
+            self.assertEqual(notification["level"], "warning")
+            self.assertEqual(notification["locations"][0]["physicalLocation"]["region"]["startLine"], 3)
+            self.assertTrue(sarif["runs"][0]["results"])
+
+            text = runner.invoke(cli, ["static-analyze", root, "--no-cache"])
+            self.assertEqual(text.exit_code, 0, text.output)
+            self.assertIn("Could not parse the file at line 3", text.stdout)
+            self.assertIn("Could not parse 1 file", text.stdout.split("===== Analysis Summary =====")[1])
+
+            # Cached results keep the parse error
+            analyzer = StaticAnalyzer()
+            analyzer.cache = AnalysisCache(str(Path(root) / "cache"))
+            analyzer.analyze_file(str(Path(root) / "broken.rs"))
+            self.assertEqual(analyzer.analyze_file(str(Path(root) / "broken.rs")).parse_error, result.parse_error)
+
+    def test_unused_storage_field_detection(self):
+        """Test that unreferenced, never written and never read storage fields are reported per struct"""
+        with open(self.test_dir / "unused_storage_field_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "unused_storage_field"]
+
+        # Fields read through a setter borrow count as read and written
+        self.assertEqual([(issue["line_start"], issue["severity"]) for issue in issues],
+                         [(10, "Low"), (10, "Low"), (10, "Low")])
+        self.assertIn("never read or written by any of its methods: 'legacy_rate'.", issues[0]["description"])
+        self.assertIn("read but never written, so they always hold their zero default", issues[1]["description"])
+        self.assertIn(": 'fee'.", issues[1]["description"])
+        self.assertIn("written but never read", issues[2]["description"])
+        self.assertIn(": 'last_depositor'.", issues[2]["description"])
+
+        # Borrowed fields are accessed by the inherited type
+        code = ("#[storage]\n#[entrypoint]\npub struct Token {\n    #[borrow]\n    erc20: Erc20,\n"
+                "    owner: StorageAddress,\n}\n\n#[public]\n#[inherit(Erc20)]\nimpl Token {\n"
+                "    pub fn owner(&self) -> Address {\n        self.owner.get()\n    }\n}\n")
+        issues = [issue for issue in self.analyzer.analyze(code).issues if issue["type"] == "unused_storage_field"]
+        self.assertEqual(len(issues), 1)
+        self.assertIn(": 'owner'.", issues[0]["description"])
+
+    def test_inconsistent_decimals_math_detection(self):
+        """Test that raw parameters added to decimals-scaled values are reported with low confidence"""
+        with open(self.test_dir / "decimals_math_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "inconsistent_decimals_math"]
+
+        # Amounts scaled in the function and comparisons are fine
+        self.assertEqual([(issue["line_start"], issue["severity"], issue["confidence"]) for issue in issues],
+                         [(30, "Low", "Low"), (38, "Low", "Low")])
+        self.assertIn("'total_supply' is scaled by `10 ** decimals` on line 23 in 'constructor'",
+                      issues[0]["description"])
+        self.assertIn("function 'mint' adds or subtracts the unscaled parameter 'amount'", issues[0]["description"])
+
+        # The sol! token scales its initial supply but mints and burns raw values
+        with open(self.test_dir / "token.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "inconsistent_decimals_math"]
+        self.assertEqual([issue["line_start"] for issue in issues], [60, 67])
+        self.assertIn("sol! function 'mint'", issues[0]["description"])
+
+        # Without the scaling idiom nothing is reported
+        with open(self.test_dir / "constructor_overflow_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        self.assertFalse(any(issue["type"] == "inconsistent_decimals_math" for issue in results.issues))
+
+    def test_require_without_message_detection(self):
+        """Test that reverts without a reason are reported and can be turned off in the config"""
+        with open(self.test_dir / "revert_message_example.rs", 'r') as f:
+            code = f.read()
+        issues = [issue for issue in self.analyzer.analyze(code).issues if issue["type"] == "require_without_message"]
+
+        # Messages, reasons and typed errors are fine
+        self.assertEqual([(issue["line_start"], issue["severity"]) for issue in issues],
+                         [(49, "Info"), (58, "Info"), (77, "Info"), (17, "Info"), (24, "Info")])
+        self.assertIn("`Err(...)` in function 'withdraw' has an empty payload", issues[0]["description"])
+        self.assertEqual(issues[0]["code_snippet"], "Err(Vec::new())")
+        self.assertIn("`require` in sol! function 'release' has no message", issues[3]["description"])
+        self.assertEqual(issues[3]["code_snippet"], "require(msg.sender == owner)")
+        self.assertIn("descriptive message", issues[0]["recommendation"])
+
+        # Teams minimizing revert strings disable it in their config
+        analyzer = StaticAnalyzer()
+        Config.from_dict({"detectors": {"disabled": ["require_without_message"]}}).apply(analyzer.registry)
+        self.assertFalse(any(issue["type"] == "require_without_message" for issue in analyzer.analyze(code).issues))
+
+    def test_unchecked_interface_bool_detection(self):
+        """Test that ignored bool results of sol_interface! calls are matched against the parsed signatures"""
+        with open(self.test_dir / "unsafe_transfer_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "unchecked_interface_bool"]
+
+        # safe_transfer_via_interface checks `if !success` on line 92 but drops the second transfer on line 93
+        self.assertEqual([(issue["line_start"], issue["severity"]) for issue in issues],
+                         [(77, "Medium"), (79, "Medium"), (85, "Medium"), (93, "Medium"), (104, "Medium")])
+        self.assertIn("`transfer(address,uint256)` in function 'unsafe_transfer_via_interface' is bound to `_`",
+                      issues[0]["description"])
+        self.assertIn("is bound to 'success' but never read", issues[1]["description"])
+        self.assertIn("`transferFrom(address,address,uint256)`", issues[2]["description"])
+        self.assertIn("in function 'safe_transfer_via_interface' is discarded", issues[3]["description"])
+        self.assertIn("'transfer_with_ignored_error' is matched with `Ok(_)`", issues[4]["description"])
+
+        # Only methods declared to return bool are checked, under their snake_case name too
+        code = """
+        sol_interface! {
+            interface IToken {
+                function transferFrom(address from, address to, uint256 value) external returns (bool);
+                function burn(uint256 value) external;
+            }
+        }
+
+        impl Vault {
+            pub fn pull(&mut self, token: IToken, from: Address, amount: U256) -> Result<(), Vec<u8>> {
+                token.burn(&mut *self, amount)?;
+                token.transfer_from(&mut *self, from, contract::address(), amount)?;
+                if token.transfer_from(&mut *self, from, contract::address(), amount).is_ok() {
+                    self.total.set(amount);
+                }
+                Ok(())
+            }
+        }
+        """
+        issues = [issue for issue in self.analyzer.analyze(code).issues if issue["type"] == "unchecked_interface_bool"]
+        self.assertEqual([issue["line_start"] for issue in issues], [12, 13])
+        self.assertIn("is discarded", issues[0]["description"])
+        self.assertIn("is only checked for a revert with `.is_ok()`", issues[1]["description"])
+
+    def test_markdown_report_matches_golden_file(self):
+        """Test that the Markdown report for a small fixture matches the checked-in expected document"""
+        with open(self.test_dir / "sarif_example.rs", 'r') as f:
+            code = f.read()
+
+        registry = DetectorRegistry.with_builtin_detectors()
+        detectors = [registry.get("unsafe_unwrap"), registry.get("unsafe_panic")]
+        context = AnalysisContext(tree=generate_rust_ast(code), code=code, file_path="test_contracts/sarif_example.rs")
+        findings = [finding for detector in detectors for finding in detector.run(context)]
+
+        markdown = generate_markdown_report(findings, FindingSummary.of(findings, 1, 0.0))
+        with open(self.test_dir / "sarif_example.md", 'r') as f:
+            self.assertEqual(markdown, f.read())
+
+        # Table cells stay on one line, and a snippet with a code fence gets a longer one
+        finding = Finding("detector", Severity.LOW, "a | b\nc", line=3, line_end=5, file="src/lib.rs",
+                          code_snippet="let s = \"```\";\n        x")
+        markdown = generate_markdown_report([finding], base_dir=None)
+        self.assertIn("| Low | `detector` | lines 3-5 | a \\| b c |", markdown)
+        self.assertIn("````rust\nlet s = \"```\";\nx\n````", markdown)
+
+        result = CliRunner().invoke(cli, ["static-analyze", str(self.test_dir / "workspace"), "--format", "markdown",
+                                          "--no-cache"])
+        self.assertEqual(result.exit_code, 0, result.output)
+        self.assertIn("## `crates/token/src/lib.rs`", result.stdout)
+
+    def test_github_annotations(self):
+        """Test the workflow command format and severity mapping of GitHub Actions annotations"""
+        finding = Finding("unsafe_unwrap", Severity.MEDIUM, "Call to .unwrap(): may panic, 100%\nsafe?", line=8,
+                          column=9, line_end=9, file=os.path.join("src", "lib,v2.rs"), suggestion="Use `?`.")
+        self.assertEqual(generate_github_annotations([finding]),
+                         "::error file=src/lib%2Cv2.rs,line=8,endLine=9,col=9,title=unsafe_unwrap::"
+                         "Call to .unwrap(): may panic, 100%25%0Asafe? Use `?`.")
+
+        levels = {Severity.CRITICAL: "error", Severity.HIGH: "error", Severity.MEDIUM: "error",
+                  Severity.LOW: "warning", Severity.INFO: "notice"}
+        for severity, level in levels.items():
+            with self.subTest(severity=severity):
+                annotation = generate_github_annotations([Finding("detector", severity, "message", line=3,
+                                                                  file="/repo/src/lib.rs")], base_dir="/repo")
+                self.assertEqual(annotation, f"::{level} file=src/lib.rs,line=3,title=detector::message")
+
+        # Project-level issues have no location, and parse errors are errors
+        self.assertEqual(generate_github_annotations([Finding("reentrancy", Severity.HIGH, "message", line=0)]),
+                         "::error title=reentrancy::message")
+        parse_error = ParseError(message="unexpected `;`", line=3, column=13, file="broken.rs")
+        self.assertEqual(generate_github_annotations([], [parse_error]),
+                         "::error file=broken.rs,line=3,col=13,title=parse_error::Could not parse file: unexpected `;`")
+
+        result = CliRunner().invoke(cli, ["static-analyze", str(self.test_dir / "sarif_example.rs"), "--format",
+                                          "github", "--no-cache"])
+        self.assertEqual(result.exit_code, 0, result.output)
+        self.assertTrue(result.stdout.startswith("::notice file="))
+        self.assertIn("sarif_example.rs,line=12,col=9,title=unsafe_panic::Unsafe call to panic!()", result.stdout)
+
+    def test_diff_compares_two_reports(self):
+        """Test that diff classifies added and removed findings and keeps moved ones unchanged"""
+        with open(self.test_dir / "sarif_example.rs", 'r') as f:
+            code = f.read()
+        # Shift every line down, fix the unwrap and add an expect
+        changed = "// New header\n\n" + code.replace("input.parse::<u64>().unwrap()", "input.parse::<u64>().unwrap_or(0)")
+        changed = changed.replace("    pub fn reset", "    pub fn limit(&self, value: Option<u64>) -> u64 {\n"
+                                  "        value.expect(\"no limit\")\n    }\n\n    pub fn reset")
+
+        with tempfile.TemporaryDirectory() as root:
+            paths = []
+            for name, source in (("old.json", code), ("new.json", changed)):
+                paths.append(os.path.join(root, name))
+                with open(paths[-1], 'w') as f:
+                    f.write(generate_json_report(self.analyzer.analyze(source).findings))
+            result = CliRunner().invoke(cli, ["diff", *paths, "--format", "json"])
+            text = CliRunner().invoke(cli, ["diff", *paths])
+            with open(paths[0], 'w') as f:
+                f.write("{}")
+            unsupported = CliRunner().invoke(cli, ["diff", *paths])
+
+        self.assertEqual(result.exit_code, 0, result.output)
+        report = json.loads(result.stdout)
+        self.assertEqual(report["summary"], {"added": 2, "removed": 1, "unchanged": 3})
+        self.assertEqual({(entry["detector_id"], entry["line"]) for entry in report["added"]},
+                         {("unsafe_expect", 14), ("public_could_be_external", 13)})
+        self.assertEqual([(entry["detector_id"], entry["line"]) for entry in report["removed"]],
+                         [("unsafe_unwrap", 8)])
+        moved = next(entry for entry in report["unchanged"] if entry["detector_id"] == "unsafe_panic")
+        self.assertEqual((moved["line"], moved["previous_line"]), (18, 12))
+        self.assertEqual(moved["fingerprint"], fingerprint(load_json_report(json.dumps(
+            {"schema_version": JSON_SCHEMA_VERSION, "findings": [moved]}))[0]))
+
+        self.assertEqual(text.exit_code, 0, text.output)
+        self.assertIn("Removed (1):\n  [Medium] unsafe_unwrap at line 8", text.stdout)
+        self.assertIn("  [High] unsafe_panic at line 18 (was line 12)", text.stdout)
+        self.assertIn("2 added, 1 removed, 3 unchanged", text.stdout)
+        self.assertEqual(unsupported.exit_code, 1)
+        self.assertIn("Unsupported JSON report schema version", unsupported.output)
+
+    def test_min_confidence_drops_heuristic_findings(self):
+        """Test that findings carry their detector's confidence and that --min-confidence filters on it"""
+        path = str(self.test_dir / "sarif_example.rs")
+        findings = self.analyzer.analyze_file(path).findings
+        confidences = {finding.detector_id: finding.confidence for finding in findings}
+        self.assertEqual(confidences, {"unsafe_unwrap": Confidence.HIGH, "unsafe_panic": Confidence.HIGH,
+                                       "public_could_be_external": Confidence.LOW})
+        self.assertEqual(Confidence.parse("medium"), Confidence.MEDIUM)
+        self.assertEqual([finding.detector_id for finding in filter_findings(findings, None, Confidence.MEDIUM)],
+                         ["unsafe_unwrap", "unsafe_panic"])
+
+        runner = CliRunner()
+        result = runner.invoke(cli, ["static-analyze", path, "--format", "json", "--no-cache", "--min-confidence", "high"])
+        self.assertEqual(result.exit_code, 0, result.output)
+        reported = json.loads(result.stdout)["findings"]
+        self.assertEqual({(entry["detector_id"], entry["confidence"]) for entry in reported},
+                         {("unsafe_unwrap", "High"), ("unsafe_panic", "High")})
+
+        text = runner.invoke(cli, ["static-analyze", path, "--no-cache", "--min-confidence", "high"])
+        self.assertEqual(text.exit_code, 0, text.output)
+        self.assertIn("unsafe_panic", text.stdout)
+        self.assertNotIn("public_could_be_external", text.stdout)
+        self.assertIn("Found 2 total issues", text.stdout)
+
+    def test_unreleased_reentrancy_guard_detector(self):
+        """Test that exits skipping the release of a manual reentrancy guard are reported"""
+        with open(self.test_dir / "unreleased_lock_example.rs", 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "unreleased_reentrancy_guard"]
+
+        # The early Err and Ok returns and the never released status are reported, released exits are not
+        self.assertEqual([(issue["line_start"], issue["line_end"]) for issue in issues], [(21, 24), (33, 35), (45, 47)])
+        self.assertTrue(all(issue["severity"] == "High" for issue in issues))
+        self.assertIn("'withdraw' locks reentrancy guard 'locked' on line 21", issues[0]["description"])
+        self.assertIn("'sweep' locks reentrancy guard 'status'", issues[2]["description"])
+        for name in ("safe_withdraw", "safe_transfer", "safe_claim"):
+            self.assertFalse(any(f"'{name}'" in issue["description"] for issue in issues), name)
+
+        # A `?` between the lock and its release is an exit as well
+        with open(self.test_dir / "reentrancy_example.rs", 'r') as f:
+            issues = [issue for issue in self.analyzer.analyze(f.read()).issues
+                      if issue["type"] == "unreleased_reentrancy_guard"]
+        self.assertEqual([issue["line_end"] for issue in issues], [60])
+        self.assertIn("propagates an error with `?`", issues[0]["description"])
+
+    def test_payable_constructor_mismatch_detector(self):
+        """Test that payable constructors are reported when their Ether is neither recorded nor withdrawable"""
+        with open(self.test_dir / "payable_constructor_example.rs", 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "payable_constructor_mismatch"]
+
+        # Crowdsale's constructor and Registry's deploy are reported, Treasury records its deposit
+        self.assertEqual([issue["line_start"] for issue in issues], [16, 34])
+        self.assertTrue(all(issue["severity"] == "Low" for issue in issues))
+        self.assertIn("'deploy' is `#[payable]`", issues[1]["description"])
+        # The runtime locked Ether detector leaves constructors to this one
+        self.assertFalse([issue for issue in results.issues if issue["type"] == "locked_ether"])
+
+        # A value read marks an unannotated constructor, and any outbound transfer makes the Ether withdrawable
+        reads = code.replace("    #[payable]\n    pub fn deploy", "    pub fn deploy")
+        issues = [issue for issue in self.analyzer.analyze(reads).issues if issue["type"] == "payable_constructor_mismatch"]
+        self.assertIn("'deploy' reads msg_value on line 34", issues[1]["description"])
+        withdraws = code + "\n#[public]\nimpl Treasury {\n    pub fn withdraw(&mut self) {\n" \
+                           "        transfer_eth(msg::sender(), self.initial_deposit.get());\n    }\n}\n"
+        self.assertFalse([issue for issue in self.analyzer.analyze(withdraws).issues
+                          if issue["type"] == "payable_constructor_mismatch"])
+
+        sol_code = "sol! {\n    contract Vault {\n        address owner;\n        uint256 deposit;\n\n" \
+                   "        constructor() payable {\n            owner = msg.sender;\n        }\n    }\n}\n"
+        issues = [issue for issue in self.analyzer.analyze(sol_code).issues
+                  if issue["type"] == "payable_constructor_mismatch"]
+        self.assertEqual([issue["line_start"] for issue in issues], [6])
+        recorded = sol_code.replace("owner = msg.sender;", "owner = msg.sender;\n            deposit = msg.value;")
+        self.assertFalse([issue for issue in self.analyzer.analyze(recorded).issues
+                          if issue["type"] == "payable_constructor_mismatch"])
+
+    def test_shared_traversal_matches_per_detector_runs(self):
+        """Test that node-hook detectors report the same findings in one traversal as on their own"""
+        detectors = [detector for detector in DetectorRegistry.with_builtin_detectors() if detector.node_types]
+        self.assertGreater(len(detectors), 1)
+        for path in sorted(self.test_dir.glob("*.rs")):
+            code = path.read_text()
+            context = AnalysisContext(tree=generate_rust_ast(code), code=code, file_path=path.name)
+            outcomes = run_node_detectors(detectors, context)
+            for detector in detectors:
+                self.assertEqual([finding.to_dict() for finding in outcomes[detector.id].result()],
+                                 [finding.to_dict() for finding in detector.run(context)])
+
+        # A detector failing mid-traversal loses its findings without affecting the others
+        registry = DetectorRegistry.with_builtin_detectors()
+
+        def fail(node, code, results):
+            results.add_issue("unsafe_unwrap", "Medium", "partial", 1, 1, "", "")
+            raise RuntimeError("broken hook")
+
+        registry.get("unsafe_unwrap").visit_node = fail
+        self.analyzer.registry = registry
+        with open(self.test_dir / "panic_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        self.assertEqual(results.errors, [{"detector": "unsafe_unwrap", "message": "broken hook"}])
+        self.assertFalse(any(issue["type"] == "unsafe_unwrap" for issue in results.issues))
+        self.assertTrue(any(issue["type"] == "unsafe_panic" for issue in results.issues))
+
+    def test_analysis_context_derives_facts_once(self):
+        """Test that the context computes each derived fact on first request and shares it across detectors"""
+        with open(self.test_dir / "public_external_example.rs", 'r') as f:
+            code = f.read()
+        context = AnalysisContext.from_source(code, "public_external_example.rs")
+        self.assertIs(context.source_map, SourceMap.of(code))
+
+        with mock.patch("stylus_analyzer.detectors.detector_base.build_call_graphs",
+                        wraps=detector_base.build_call_graphs) as build:
+            self.assertIs(context.call_graphs(), context.call_graphs())
+            self.assertEqual(build.call_count, 1)
+
+        # Every storage detector of an analysis reads the same layout
+        with mock.patch("stylus_analyzer.detectors.detector_base.collect_storage_layout",
+                        wraps=detector_base.collect_storage_layout) as collect:
+            results = self.analyzer.analyze(code)
+            self.assertEqual(collect.call_count, 1)
+        self.assertTrue(any(issue["type"] == "public_could_be_external" for issue in results.issues))
+        self.assertTrue(all(detector.context is None for detector in self.analyzer.detectors))
+
+    def test_require_after_state_change_detector(self):
+        """Test that parameter checks following a storage write from the same parameters are reported"""
+        with open(self.test_dir / "late_validation_example.rs", 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "require_after_state_change"]
+
+        # burn checks the balance after decrementing it, transfer_ownership checks the owner after storing it
+        self.assertEqual([(issue["line_start"], issue["line_end"]) for issue in issues], [(20, 22), (30, 30)])
+        self.assertTrue(all(issue["severity"] == "Low" for issue in issues))
+        self.assertIn("validates 'amount' only after writing storage field 'balances' on line 19",
+                      issues[0]["description"])
+
+        # A write inside a closure may never run before the check
+        closure = code.replace("        self.owner.set(new_owner);\n",
+                               "        let set = |owner: Address| self.owner.set(owner);\n")
+        issues = [issue for issue in self.analyzer.analyze(closure).issues
+                  if issue["type"] == "require_after_state_change"]
+        self.assertEqual([issue["line_start"] for issue in issues], [20])
+
+    def test_mapping_default_assumption_detector(self):
+        """Test that subtractions from mapping entries read without a guard are reported"""
+        with open(self.test_dir / "mapping_default_example.rs", 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "mapping_default_assumption"]
+
+        # transfer_from and burn subtract unchecked entries, the sol! allowance is decremented before any check
+        self.assertEqual([issue["line_start"] for issue in issues], [19, 26, 69])
+        self.assertTrue(all(issue["severity"] == "Info" and issue["confidence"] == "Low" for issue in issues))
+        self.assertIn("'allowance', read from 'allowances' on line 18", issues[0]["description"])
+        self.assertIn("'allowance[from][msg.sender]'", issues[2]["description"])
+
+        # A comparison of the entry before the subtraction counts as an existence check
+        guarded = code.replace("            allowance[from][msg.sender] -= value;\n",
+                               "            require(allowance[from][msg.sender] >= value);\n"
+                               "            allowance[from][msg.sender] -= value;\n")
+        issues = [issue for issue in self.analyzer.analyze(guarded).issues
+                  if issue["type"] == "mapping_default_assumption"]
+        self.assertEqual([issue["line_start"] for issue in issues], [19, 26])
+
+    def test_cli_detector_selection(self):
+        """Test that --detectors and --exclude-detectors pick the detectors that run, over the config file"""
+        with tempfile.TemporaryDirectory() as root:
+            contract = Path(root) / "lib.rs"
+            contract.write_text((self.test_dir / "panic_example.rs").read_text() +
+                                "\nfn head(values: Vec<u32>) -> u32 {\n    *values.first().unwrap()\n}\n")
+
+            def detector_ids(*options):
+                result = CliRunner().invoke(cli, ["static-analyze", str(contract), "--format", "json",
+                                                  "--no-cache", *options])
+                self.assertEqual(result.exit_code, 0, result.output)
+                return {finding.detector_id for finding in load_json_report(result.stdout)}
+
+            everything = detector_ids()
+            self.assertTrue({"unsafe_panic", "unsafe_unwrap"} < everything)
+            self.assertEqual(detector_ids("--detectors", "unsafe_panic, unsafe_unwrap"), {"unsafe_panic", "unsafe_unwrap"})
+            self.assertEqual(detector_ids("--detectors", "unsafe_panic", "--detectors", "unsafe_unwrap",
+                                          "--exclude-detectors", "unsafe_panic"), {"unsafe_unwrap"})
+            self.assertEqual(detector_ids("--exclude-detectors", "unsafe_panic,unsafe_unwrap"),
+                             everything - {"unsafe_panic", "unsafe_unwrap"})
+
+            # The selection replaces the config's allowlist and runs detectors the config disables
+            (Path(root) / CONFIG_FILE_NAME).write_text(
+                '[detectors]\nenabled = ["unsafe_panic"]\ndisabled = ["unsafe_unwrap"]\n')
+            self.assertEqual(detector_ids(), {"unsafe_panic"})
+            self.assertEqual(detector_ids("--detectors", "unsafe_unwrap"), {"unsafe_unwrap"})
+
+            result = CliRunner().invoke(cli, ["static-analyze", str(contract), "--no-cache",
+                                              "--detectors", "unsafe_panic,no_such_detector"])
+            self.assertNotEqual(result.exit_code, 0)
+            self.assertIn("Unknown detector id(s): no_such_detector", result.output)
+            self.assertIn("Valid ids: ", result.output)
+            self.assertIn("unsafe_unwrap", result.output)
+
+    def test_security_report_maps_cwes_and_matches_schema(self):
+        """Test the CWE ids of some detectors and that the security report validates against its schema"""
+        registry = DetectorRegistry.with_builtin_detectors()
+        self.assertEqual(registry.get("missing_access_control").cwe, 284)
+        self.assertEqual(registry.get("unchecked_arithmetic").cwe, 190)
+        self.assertEqual(registry.get("constructor_integer_overflow").cwe, 190)
+        self.assertEqual(registry.get("reentrancy").cwe, 841)
+        self.assertEqual(registry.get("unchecked_transfer").cwe, 252)
+        self.assertIsNone(registry.get("clone_in_loop").cwe)
+
+        json_types = {"object": dict, "array": list, "string": str, "integer": int, "null": type(None)}
+
+        def validate(instance, schema, path="report"):
+            types = schema["type"] if isinstance(schema["type"], list) else [schema["type"]]
+            self.assertTrue(any(isinstance(instance, json_types[name]) for name in types), path)
+            if "enum" in schema:
+                self.assertIn(instance, schema["enum"], path)
+            if isinstance(instance, dict):
+                for key in schema.get("required", []):
+                    self.assertIn(key, instance, path)
+                for key, property_schema in schema.get("properties", {}).items():
+                    if key in instance:
+                        validate(instance[key], property_schema, f"{path}.{key}")
+            elif isinstance(instance, list) and "items" in schema:
+                for index, item in enumerate(instance):
+                    validate(item, schema["items"], f"{path}[{index}]")
+
+        contract_path = self.test_dir / "access_control_example.rs"
+        result = CliRunner().invoke(cli, ["static-analyze", str(contract_path), "--format", "security-report",
+                                          "--no-cache"])
+        self.assertEqual(result.exit_code, 0, result.output)
+        report = json.loads(result.stdout)
+        validate(report, SECURITY_REPORT_SCHEMA)
+
+        findings = report["findings"]
+        access_control = [finding for finding in findings if finding["detector_id"] == "missing_access_control"]
+        self.assertTrue(access_control)
+        self.assertEqual({finding["cwe"] for finding in access_control}, {"CWE-284"})
+        self.assertEqual(access_control[0]["title"], "Missing access control")
+        self.assertEqual(access_control[0]["location"]["file"], str(contract_path).replace(os.sep, "/"))
+        self.assertEqual(len({finding["id"] for finding in findings}), len(findings))
+
+    def test_keccak_encode_packed_dynamic_detector(self):
+        """Test that hashes of packed dynamic values are reported at the hashing call, and delimited ones are not"""
+        contract_path = self.test_dir / "keccak_packed_example.rs"
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code, str(contract_path))
+        issues = [issue for issue in results.issues if issue["type"] == "keccak_encode_packed_dynamic"]
+        self.assertEqual([(issue["line_start"], issue["line_end"]) for issue in issues],
+                         [(19, 19), (26, 26), (33, 33), (39, 39)])
+        self.assertTrue(all(issue["severity"] == "Medium" for issue in issues))
+        self.assertEqual(issues[2]["code_snippet"], "hasher.update(&packed)")
+        self.assertIn("from line 31", issues[2]["description"])
+
+        # Dropping the delimiter makes the concatenation unsafe
+        undelimited = code.replace("[a.as_bytes(), &delimiter, b.as_bytes()]", "[a.as_bytes(), b.as_bytes()]")
+        results = self.analyzer.analyze(undelimited)
+        self.assertIn(46, [issue["line_start"] for issue in results.issues
+                           if issue["type"] == "keccak_encode_packed_dynamic"])
+
+    def test_unvalidated_external_data_decode_detector(self):
+        """Test that decodes of call return data and calldata need an earlier length check"""
+        contract_path = self.test_dir / "external_decode_example.rs"
+        with open(contract_path, 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code, str(contract_path))
+        issues = [issue for issue in results.issues if issue["type"] == "unvalidated_external_data_decode"]
+        self.assertEqual(sorted(issue["line_start"] for issue in issues), [14, 37, 53])
+        self.assertTrue(all(issue["severity"] == "Low" for issue in issues))
+
+        # Checking the length of other bytes doesn't guard the decode
+        other_check = code.replace("if return_data.is_empty() {", "if calldata.is_empty() {")
+        issues = [issue for issue in self.analyzer.analyze(other_check).issues
+                  if issue["type"] == "unvalidated_external_data_decode"]
+        self.assertEqual(sorted(issue["line_start"] for issue in issues), [14, 37, 47, 53])
+
+    def test_storage_struct_resolved_across_crate_modules(self):
+        """Test that an impl in one module is joined to the #[storage] struct it imports from another module"""
+        crate = self.test_dir / "split_contract" / "src"
+        token_path = str(crate / "token.rs")
+        with open(token_path, 'r') as f:
+            code = f.read()
+
+        layout = AnalysisContext.from_source(code, token_path).storage_layout()
+        self.assertEqual(layout.structs, [])
+        self.assertEqual([(struct.name, struct.file) for struct in layout.imported],
+                         [("Token", os.path.abspath(crate / "state.rs"))])
+        self.assertEqual(list(layout.fields), ["total_supply", "cap", "balances"])
+        self.assertEqual((layout.get_field("cap").struct, layout.get_field("cap").type), ("Token", "StorageU256"))
+        self.assertEqual(AnalysisContext.from_source(code).storage_layout().imported, [])
+
+        results = StaticAnalyzer().analyze(code, token_path)
+        uninitialized = [issue for issue in results.issues if issue["type"] == "uninitialized_storage_field"]
+        self.assertEqual([(issue["line_start"], "cap" in issue["description"]) for issue in uninitialized],
+                         [(10, True)])
+
+        # Editing the module declaring the struct changes the cache key of the module implementing it
+        with tempfile.TemporaryDirectory() as root:
+            shutil.copytree(self.test_dir / "split_contract", os.path.join(root, "split_contract"))
+            copied_token = os.path.join(root, "split_contract", "src", "token.rs")
+            cache = AnalysisCache(os.path.join(root, "cache"))
+            key = cache.key(code, copied_token, self.analyzer)
+            with open(os.path.join(root, "split_contract", "src", "state.rs"), 'a') as f:
+                f.write("\n// Edited\n")
+            self.assertNotEqual(cache.key(code, copied_token, self.analyzer), key)
+
+    def _configured_issues(self, code, detector_id, options):
+        """Get the lines a detector reports in code when configured by an `[options.<detector id>]` table"""
+        with tempfile.TemporaryDirectory() as root:
+            config_path = Path(root) / CONFIG_FILE_NAME
+            config_path.write_text(f"[options.{detector_id}]\n{options}\n")
+            analyzer = StaticAnalyzer()
+            load_config(str(config_path)).apply(analyzer.registry)
+        return [issue["line_start"] for issue in analyzer.analyze(code).issues if issue["type"] == detector_id]
+
+    def test_fallback_with_logic_detector(self):
+        """Test that fallbacks with statements are reported and empty ones are not"""
+        with open(self.test_dir / "fallback_logic_example.rs", 'r') as f:
+            code = f.read()
+        results = self.analyzer.analyze(code)
+        fallbacks = [issue for issue in results.issues if issue["type"] == "fallback_with_logic"]
+        self.assertEqual(sorted((issue["line_start"], issue["line_end"]) for issue in fallbacks),
+                         [(15, 19), (40, 44), (56, 58)])
+        self.assertTrue(all(issue["severity"] == "Low" for issue in fallbacks))
+
+        # The empty fallbacks of other fixtures are fine
+        for name in ("unsafe_transfer_example.rs", "receive_sol_example.rs", "receive_locked_example.rs"):
+            with open(self.test_dir / name, 'r') as f:
+                other = self.analyzer.analyze(f.read())
+            self.assertFalse([issue for issue in other.issues if issue["type"] == "fallback_with_logic"], name)
+
+        # A higher threshold only keeps the fallback with more statements than it
+        detector = FallbackWithLogicDetector(max_statements=2)
+        results = StaticAnalysisResult()
+        detector.detect(generate_rust_ast(code), code, results)
+        self.assertEqual([issue["line_start"] for issue in results.issues], [15])
+
+        # The threshold can be set in the config file
+        self.assertEqual(self._configured_issues(code, "fallback_with_logic", "max_statements = 2"), [15])
+
+    def test_integer_underflow_on_subtraction_detector(self):
+        """Test that unsigned subtractions without an earlier comparison of their operands are reported"""
+        def underflows(name):
+            with open(self.test_dir / name, 'r') as f:
+                results = self.analyzer.analyze(f.read())
+            return [issue for issue in results.issues if issue["type"] == "integer_underflow_on_subtraction"]
+
+        # The allowance and balance decrements of transferFrom and burn; transfer requires the balance first
+        token = underflows("token.rs")
+        self.assertEqual([issue["line_start"] for issue in token], [52, 53, 66, 67])
+        self.assertTrue(all(issue["severity"] == "Medium" for issue in token))
+        self.assertIn("'allowance[from][msg.sender]'", token[1]["description"])
+
+        # Comparisons in either order, guard macros and zero checks guard the subtraction
+        self.assertEqual([issue["line_start"] for issue in underflows("underflow_example.rs")], [22, 30])
+
+    def test_quiet_and_verbose_logging(self):
+        """Test that --quiet prints nothing without findings and --verbose logs per-file and per-detector timings"""
+        self.addCleanup(configure_logging, logging_utils.DEFAULT, {})
+        with tempfile.TemporaryDirectory() as root:
+            clean = os.path.join(root, "clean.rs")
+            with open(clean, 'w') as f:
+                f.write("pub fn identity(value: u8) -> u8 {\n    value\n}\n")
+
+            result = CliRunner().invoke(cli, ["static-analyze", clean, "--quiet", "--no-cache"])
+            self.assertEqual(result.exit_code, 0, result.output)
+            self.assertEqual(result.output, "")
+
+            # Findings are still printed, without the progress and summary around them
+            result = CliRunner().invoke(cli, ["static-analyze", str(self.test_dir / "unwrap_example.rs"), "--quiet",
+                                              "--no-cache"])
+            self.assertIn("unsafe_unwrap", result.stdout)
+            self.assertNotIn("Analysis completed", result.output)
+            self.assertNotIn("Analysis Summary", result.output)
+
+            result = CliRunner().invoke(cli, ["static-analyze", root, "--verbose", "--no-cache", "-j", "1",
+                                              "--exclude", "skipped.rs"])
+            self.assertEqual(result.exit_code, 0, result.output)
+            self.assertIn(f"Analyzed {clean} in ", result.stderr)
+            self.assertRegex(result.stderr, r"DEBUG -   unsafe_unwrap: \d+\.\d+ ms")
+
+            with open(os.path.join(root, "skipped.rs"), 'w') as f:
+                f.write("pub fn skipped() {}\n")
+            result = CliRunner().invoke(cli, ["static-analyze", root, "--verbose", "--no-cache", "-j", "1",
+                                              "--exclude", "skipped.rs"])
+            self.assertIn("skipped.rs: it matches an --exclude glob", result.stderr)
+
+            result = CliRunner().invoke(cli, ["static-analyze", clean, "--quiet", "--verbose"])
+            self.assertNotEqual(result.exit_code, 0)
+
+        # STYLUS_ANALYZER_LOG sets the level of all logs or of single modules
+        self.assertEqual(logging_utils.parse_log_filter("warning,cache=debug,stylus_analyzer.lsp=off"),
+                         (logging.WARNING, {"stylus_analyzer.cache": logging.DEBUG,
+                                            "stylus_analyzer.lsp": logging.CRITICAL + 1}))
+        configure_logging(logging_utils.VERBOSE, {logging_utils.LOG_FILTER_ENV: "info,cache=debug"})
+        self.assertEqual(logging.getLogger("stylus_analyzer").level, logging.INFO)
+        self.assertTrue(logging.getLogger("stylus_analyzer.cache").isEnabledFor(logging.DEBUG))
+        with self.assertRaises(ValueError):
+            logging_utils.parse_log_filter("loud")
+
+    def test_constructor_external_call_detector(self):
+        """Test that constructors calling other contracts are reported unless the target is a constant"""
+        with open(self.test_dir / "constructor_call_example.rs", 'r') as f:
+            code = f.read()
+        results = self.analyzer.analyze(code)
+        calls = [issue for issue in results.issues if issue["type"] == "constructor_external_call"]
+        self.assertEqual([issue["line_start"] for issue in calls], [25, 48, 85])
+        self.assertTrue(all(issue["severity"] == "Medium" for issue in calls))
+        self.assertIn("through helper 'notify'", calls[1]["description"])
+
+        # Calls made after deployment are left to the reentrancy detector
+        with open(self.test_dir / "reentrancy_example.rs", 'r') as f:
+            other = self.analyzer.analyze(f.read())
+        self.assertFalse([issue for issue in other.issues if issue["type"] == "constructor_external_call"])
+
+    def test_lossy_u256_to_address_detector(self):
+        """Test that addresses built from narrowed integers or short slices are reported"""
+        with open(self.test_dir / "lossy_address_example.rs", 'r') as f:
+            code = f.read()
+        results = self.analyzer.analyze(code)
+        lossy = [issue for issue in results.issues if issue["type"] == "lossy_u256_to_address"]
+        self.assertEqual([issue["line_start"] for issue in lossy], [17, 24, 29, 34, 39])
+        self.assertTrue(all(issue["severity"] == "Medium" for issue in lossy))
+        self.assertIn("first 20 bytes of the 32-byte word", lossy[0]["description"])
+        self.assertIn("16 bytes of `data`", lossy[3]["description"])
+
+    def _collect_item_flags(self, code):
+        """Map the structs, impls and methods of a file to their Stylus attribute flags"""
+        tree = generate_rust_ast(code)
+        flags = {}
+
+        def visit(node, owner):
+            for child in node.named_children:
+                if child.type in ("struct_item", "function_item"):
+                    name = child.child_by_field_name("name").text.decode()
+                    flags[f"{owner}::{name}" if owner else name] = (child, get_item_flags(child, code))
+                elif child.type == "impl_item":
+                    name = child.child_by_field_name("type").text.decode()
+                    flags[f"impl {name}"] = (child, get_item_flags(child, code))
+                    visit(child.child_by_field_name("body"), name)
+        visit(tree.root_node, None)
+        return flags
+
+    def test_stylus_attribute_flags(self):
+        """Test that namespaced, aliased, conditional and legacy Stylus attributes set the same flags"""
+        with open(self.test_dir / "sdk_attributes_example.rs", 'r') as f:
+            code = f.read()
+        flags = {name: item_flags for name, (_, item_flags) in self._collect_item_flags(code).items()}
+        expected = {
+            "Vault": dict(storage=True, entrypoint=True, external_callable=False, payable=False),
+            "impl Vault": dict(storage=False, external_callable=True, payable=False),
+            "Vault::deposit": dict(external_callable=True, payable=True),
+            "Vault::deposits": dict(external_callable=True, payable=False),
+            "Vault::helper": dict(external_callable=False, payable=False),
+            "Registry": dict(storage=True, entrypoint=False),
+            "impl Registry": dict(external_callable=True),
+            "Registry::register": dict(external_callable=True, payable=True),
+            "Registry::reset": dict(external_callable=True, payable=True),
+            "Snapshot": dict(storage=False),
+            "Ledger": dict(storage=True),
+            "impl Ledger": dict(external_callable=False),
+            "Ledger::entries": dict(external_callable=False),
+        }
+        self.assertEqual(set(flags), set(expected))
+        for name, values in expected.items():
+            for flag, value in values.items():
+                self.assertEqual(getattr(flags[name], flag), value, f"{name}: {flag}")
+        self.assertIn("external", flags["impl Vault"].attributes)
+        self.assertIn("serde::storage", flags["Snapshot"].attributes)
+
+        # The storage layout and detectors see the same items
+        results = self.analyzer.analyze(code)
+        self.assertFalse([issue for issue in results.issues if issue["type"] == "msg_value_non_payable"])
+
+    def test_stylus_attribute_flags_of_fixtures(self):
+        """Test that the flags of every fixture item match the bare attributes written above it"""
+        def written(node):
+            names = set()
+            sibling = node.prev_sibling
+            while sibling is not None and sibling.type in ("attribute_item", "line_comment", "block_comment"):
+                if sibling.type == "attribute_item":
+                    names.add(sibling.text.decode().strip("#[] ").split("(")[0])
+                sibling = sibling.prev_sibling
+            return names
+
+        for path in sorted(self.test_dir.rglob("*.rs")):
+            if "target" in path.parts or path.name == "sdk_attributes_example.rs":
+                continue
+            code = path.read_text()
+            for name, (node, flags) in self._collect_item_flags(code).items():
+                attributes = written(node)
+                message = f"{path.name}: {name}"
+                self.assertEqual(flags.payable, "payable" in attributes, message)
+                if node.type == "struct_item":
+                    # `#[public]` and `#[external]` on a struct mark it as storage too
+                    self.assertEqual(flags.storage, bool(attributes & {"storage", "entrypoint", "public", "external"}),
+                                     message)
+                elif node.type == "impl_item":
+                    self.assertEqual(flags.external_callable, bool(attributes & {"public", "external"}), message)
+                elif node.parent.type == "declaration_list" and node.parent.parent.type == "impl_item":
+                    impl_public = bool(written(node.parent.parent) & {"public", "external"})
+                    public = bool(attributes & {"public", "external"}) or \
+                        (impl_public and any(child.type == "visibility_modifier" for child in node.children))
+                    self.assertEqual(flags.external_callable, public, message)
+
+    def test_public_mut_without_reentrancy_guard_detector(self):
+        """Test that methods moving Ether and calling out without a guard are reported"""
+        with open(self.test_dir / "missing_guard_example.rs", 'r') as f:
+            code = f.read()
+        results = self.analyzer.analyze(code)
+        unguarded = [issue for issue in results.issues if issue["type"] == "public_mut_without_reentrancy_guard"]
+        self.assertEqual([(issue["line_start"], issue["line_end"]) for issue in unguarded],
+                         [(24, 29), (32, 38), (41, 46)])
+        self.assertTrue(all(issue["severity"] == "Medium" for issue in unguarded))
+        self.assertIn("is `#[payable]`", unguarded[0]["description"])
+        self.assertIn("at line 36", unguarded[1]["description"])
+
+        # The reentrancy example moves tokens, not Ether
+        with open(self.test_dir / "reentrancy_example.rs", 'r') as f:
+            other = self.analyzer.analyze(f.read())
+        self.assertFalse([issue for issue in other.issues if issue["type"] == "public_mut_without_reentrancy_guard"])
+
+    def test_report_order_is_independent_of_file_order(self):
+        """Test that reports list findings in the same order however the files were listed"""
+        names = ["sarif_example.rs", "missing_guard_example.rs", "lossy_address_example.rs", "unwrap_example.rs"]
+        with tempfile.TemporaryDirectory() as root:
+            for name in names:
+                shutil.copy(self.test_dir / name, os.path.join(root, name))
+            paths = [os.path.join(root, name) for name in names]
+
+            reports = {}
+            for output_format in ("json", "sarif", "github"):
+                for seed in range(3):
+                    shuffled = list(paths)
+                    random.Random(seed).shuffle(shuffled)
+                    with mock.patch("stylus_analyzer.cli._collect_contract_files", return_value=shuffled):
+                        result = CliRunner().invoke(cli, ["static-analyze", root, "--format", output_format,
+                                                          "--no-cache", "--jobs", str(seed + 1)])
+                    self.assertEqual(result.exit_code, 0, result.output)
+                    report = result.stdout
+                    if output_format == "json":
+                        report = json.loads(report)
+                        del report["summary"]["duration_seconds"]
+                    reports.setdefault(output_format, []).append(report)
+                self.assertTrue(all(report == reports[output_format][0] for report in reports[output_format]))
+
+        findings = reports["json"][0]["findings"]
+        self.assertEqual(len({finding["file"] for finding in findings}), len(names))
+        keys = [(finding["file"], finding["line"], finding["column"], finding["detector_id"]) for finding in findings]
+        self.assertEqual(keys, sorted(keys))
+
+    def test_missing_require_on_transfer_amount_detector(self):
+        """Test that Transfer amounts never compared to zero, or never checked at all, are reported"""
+        with open(self.test_dir / "transfer_amount_example.rs", 'r') as f:
+            code = f.read()
+
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "missing_require_on_transfer_amount"]
+        self.assertEqual([issue["line_start"] for issue in issues], [28, 80])
+        self.assertIn("never compared to zero, so", issues[0]["description"])
+        self.assertIn("bounded by a balance or limit check", issues[1]["description"])
+        self.assertTrue(all(issue["severity"] == "Info" for issue in issues))
+
+        # Without flagging zero amounts, only the value nobody checks is reported
+        detector = MissingRequireOnTransferAmountDetector(flag_zero_amount=False)
+        results = StaticAnalysisResult()
+        detector.detect(generate_rust_ast(code), code, results)
+        self.assertEqual([issue["line_start"] for issue in results.issues], [80])
+        self.assertEqual(self._configured_issues(code, "missing_require_on_transfer_amount",
+                                                 "flag_zero_amount = false"), [80])
+
+        sol_code = """sol! {
+    contract Token {
+        function transfer(address to, uint256 value) public returns (bool) {
+            require(balanceOf[msg.sender] >= value, "balance");
+            balanceOf[msg.sender] -= value;
+            balanceOf[to] += value;
+            emit Transfer(msg.sender, to, value);
+            return true;
+        }
+
+        function mint(address to, uint256 amount) external {
+            require(amount > 0, "zero amount");
+            emit Transfer(address(0), to, amount);
+        }
+
+        function _move(address to, uint256 amount) internal {
+            emit Transfer(address(0), to, amount);
+        }
+    }
+}
+"""
+        results = self.analyzer.analyze(sol_code)
+        issues = [issue for issue in results.issues if issue["type"] == "missing_require_on_transfer_amount"]
+        self.assertEqual([issue["line_start"] for issue in issues], [7])
+        results = StaticAnalysisResult()
+        detector.detect(generate_rust_ast(sol_code), sol_code, results)
+        self.assertFalse(results.issues)
+
+    def test_profile_times_every_enabled_detector(self):
+        """Test that --profile reports the accumulated time of every enabled detector that ran"""
+        names = ["sarif_example.rs", "unwrap_example.rs", "transfer_amount_example.rs"]
+        with tempfile.TemporaryDirectory() as root:
+            for name in names:
+                shutil.copy(self.test_dir / name, os.path.join(root, name))
+
+            # Timings come back from worker processes and add up per detector
+            profile = DetectorProfile()
+            for result in self.analyzer.analyze_files(sorted(os.path.join(root, name) for name in names),
+                                                      jobs=2).values():
+                profile.add(result)
+            self.assertEqual(set(profile.times), set(self.analyzer.registry.enabled_ids()))
+            self.assertTrue(all(runs == len(names) for runs in profile.runs.values()))
+            times = [elapsed for _, elapsed, _ in profile.entries()]
+            self.assertEqual(times, sorted(times, reverse=True))
+            self.assertAlmostEqual(profile.total, sum(times))
+
+            for output_format in ("text", "json"):
+                # Profiling re-analyzes the files instead of reading cached results without timings
+                with mock.patch("stylus_analyzer.cli.AnalysisCache") as cache:
+                    result = CliRunner().invoke(cli, ["static-analyze", root, "--profile", "--format", output_format,
+                                                      "--detectors", "unsafe_unwrap,unsafe_panic"])
+                self.assertEqual(result.exit_code, 0, result.output)
+                self.assertFalse(cache.called)
+                self.assertIn("===== Detector Profile =====", result.stderr)
+                self.assertNotIn("Detector Profile", result.stdout)
+                table = result.stderr.split("===== Detector Profile =====")[1].splitlines()[2:]
+                rows = [line.split()[0] for line in table if line.strip()]
+                self.assertEqual(sorted(rows), ["Total", "unsafe_panic", "unsafe_unwrap"])
+
+    def test_hardcoded_gas_or_value_in_call_detector(self):
+        """Test that call builders and sol! calls with a literal gas limit or value are reported"""
+        with open(self.test_dir / "hardcoded_gas_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "hardcoded_gas_or_value_in_call"]
+        self.assertEqual([issue["line_start"] for issue in issues], [25, 31, 37, 43, 43])
+        self.assertIn("fixed gas limit of `2300`", issues[0]["description"])
+        self.assertIn("`2300` through `STIPEND`", issues[1]["description"])
+        self.assertIn("hardcoded value of `U256::from(1_000_000_000u64)`", issues[2]["description"])
+        self.assertTrue(all(issue["severity"] == "Low" for issue in issues))
+
+        sol_code = """sol! {
+    contract Payer {
+        function pay(address payable to, uint256 amount) public {
+            to.transfer(amount);
+        }
+
+        function ping(address to) public {
+            (bool ok, ) = to.call{gas: 2300, value: 1 ether}("");
+            require(ok, "call failed");
+        }
+
+        function forward(address to, uint256 amount) public {
+            (bool ok, ) = to.call{value: amount}("");
+            require(ok, "call failed");
+        }
+
+        function payToken(IERC20 token, address to, uint256 amount) public {
+            token.transfer(to, amount);
+        }
+    }
+}
+"""
+        results = self.analyzer.analyze(sol_code)
+        issues = [issue for issue in results.issues if issue["type"] == "hardcoded_gas_or_value_in_call"]
+        self.assertEqual([issue["line_start"] for issue in issues], [4, 8, 8])
+        self.assertIn("2300 gas stipend of `.transfer()`", issues[0]["description"])
+        self.assertIn("hardcoded value of `1 ether`", issues[2]["description"])
+
+    def test_junit_report_matches_golden_file(self):
+        """Test that the JUnit report for a small fixture matches the checked-in expected document"""
+        with open(self.test_dir / "sarif_example.rs", 'r') as f:
+            code = f.read()
+
+        registry = DetectorRegistry.with_builtin_detectors()
+        detectors = [registry.get("unsafe_unwrap"), registry.get("unsafe_panic")]
+        context = AnalysisContext(tree=generate_rust_ast(code), code=code, file_path="test_contracts/sarif_example.rs")
+        findings = [finding for detector in detectors for finding in detector.run(context)]
+
+        junit = generate_junit_report(findings)
+        with open(self.test_dir / "sarif_example.junit.xml", 'r') as f:
+            self.assertEqual(junit, f.read())
+
+        # Special characters are escaped and characters XML can't hold are dropped
+        finding = Finding("detector", Severity.LOW, "a <b> & \"c\" 'd' ]]>\x01\x1b", line=3, file="src/lib.rs",
+                          suggestion="Use `&mut`.")
+        parse_error = ParseError(message="unexpected `<`", line=2, column=5, file="src/broken.rs")
+        root = ET.fromstring(generate_junit_report([finding], [parse_error]))
+        self.assertEqual((root.get("tests"), root.get("failures"), root.get("errors")), ("2", "1", "1"))
+        self.assertEqual([suite.get("name") for suite in root], ["src/broken.rs", "src/lib.rs"])
+        failure = root.find("testsuite[@name='src/lib.rs']/testcase/failure")
+        self.assertEqual(failure.get("message"), "a <b> & \"c\" 'd' ]]>")
+        self.assertIn("Suggestion: Use `&mut`.", failure.text)
+        error = root.find("testsuite[@name='src/broken.rs']/testcase")
+        self.assertEqual((error.get("name"), error.find("error").get("message")),
+                         ("parse_error", "Could not parse file: unexpected `<`"))
+
+        result = CliRunner().invoke(cli, ["static-analyze", str(self.test_dir / "workspace"), "--format", "junit",
+                                          "--no-cache"])
+        self.assertEqual(result.exit_code, 0, result.output)
+        self.assertIn('<testsuite name="crates/token/src/lib.rs"', result.stdout)
+
+    def test_signed_unsigned_comparison_detector(self):
+        """Test that expressions and conversions mixing signed and unsigned integers are reported"""
+        with open(self.test_dir / "signed_unsigned_example.rs", 'r') as f:
+            results = self.analyzer.analyze(f.read())
+        issues = [issue for issue in results.issues if issue["type"] == "signed_unsigned_comparison"]
+        self.assertEqual([issue["line_start"] for issue in issues], [18, 23, 28, 33])
+        self.assertIn("`.into_raw()` in function 'exceeds'", issues[0]["description"])
+        self.assertIn("Comparison mixing a signed and an unsigned integer with `>`", issues[1]["description"])
+        self.assertIn("`I256::from_raw`", issues[2]["description"])
+        self.assertIn("negative values wrap to huge ones", issues[3]["description"])
+        self.assertTrue(all(issue["severity"] == "Low" for issue in issues))
+
+        sol_code = """sol! {
+    contract Position {
+        function exceeds(int256 delta, uint256 threshold) public returns (bool) {
+            return uint256(delta) > threshold;
+        }
+
+        function isLarge(int256 delta, uint256 threshold) public returns (bool) {
+            return delta > threshold;
+        }
+
+        function isAbove(int256 delta, int256 threshold) public returns (bool) {
+            return delta > threshold;
+        }
+    }
+}
+"""
+        results = self.analyzer.analyze(sol_code)
+        issues = [issue for issue in results.issues if issue["type"] == "signed_unsigned_comparison"]
+        self.assertEqual([issue["line_start"] for issue in issues], [4, 8])
+        self.assertIn("`uint256(delta)`", issues[0]["description"])
+        self.assertIn("`delta > threshold`", issues[1]["description"])
+
+    def test_guard_extraction(self):
+        """Test that sol! requires and Rust early returns, asserts and conditions become guards of their function"""
+        code = """impl Vault {
+    pub fn withdraw(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        if to == Address::ZERO {
+            return Err(b"zero address".to_vec());
+        }
+        assert_eq!(msg::sender(), self.owner.get(), "not the owner");
+        if amount > U256::ZERO {
+            self.balance.set(self.balance.get() - amount);
+        }
+        Ok(())
+    }
+}
+
+sol! {
+    contract Vault {
+        function withdraw(address to, uint256 amount) public {
+            require(to != address(0), "zero address");
+            if (amount > balance) { revert("insufficient balance"); }
+            balance -= amount;
+        }
+    }
+}
+"""
+        context = AnalysisContext.from_source(code)
+        function_node = next(function.node for function in context.iter_functions() if function.language == "rust")
+        guards = context.guards(function_node)
+        self.assertEqual([(guard.condition, guard.kind, guard.line) for guard in guards],
+                         [("to == Address::ZERO", "revert", 3), ("msg::sender() == self.owner.get()", "assert", 6),
+                          ("amount > U256::ZERO", "condition", 7)])
+        self.assertEqual(code[guards[0].span[0]:guards[0].span[1]].splitlines()[-1].strip(), "}")
+        self.assertIs(context.guards(function_node), guards)
+        self.assertEqual([guard.reverts for guard in guards], [True, True, False])
+
+        sol_function = context.macro_functions()[0]
+        self.assertEqual([(guard.condition, guard.kind, guard.line) for guard in context.guards(sol_function)],
+                         [("to != address(0)", "require", 17), ("amount > balance", "revert", 18)])
+        self.assertEqual(extract_guards(sol_function, code), context.guards(sol_function))
+
+    def test_overpowered_owner_detector(self):
+        """Test that owner-gated functions writing the supply or any account's balance are disclosed"""
+        with open(self.test_dir / "overpowered_owner_example.rs", 'r') as f:
+            code = f.read()
+        results = self.analyzer.analyze(code)
+        issues = [issue for issue in results.issues if issue["type"] == "overpowered_owner"]
+        self.assertEqual([issue["line_start"] for issue in issues], [18, 28, 35])
+        self.assertIn("Function 'mint' can only be called by the owner and writes 'total_supply', 'balances'",
+                      issues[0]["description"])
+        # The burn helper's writes count as the gated function's own
+        self.assertIn("writes 'balances', 'total_supply'", issues[1]["description"])
+        self.assertTrue(all(issue["severity"] == "Info" for issue in issues))
+
+        # Only the configured fields count as powerful
+        detector = OverpoweredOwnerDetector(powerful_fields=["total_supply"])
+        results = StaticAnalysisResult()
+        detector.detect(generate_rust_ast(code), code, results)
+        self.assertEqual([issue["line_start"] for issue in results.issues], [18, 28])
+        self.assertEqual(self._configured_issues(code, "overpowered_owner", 'powerful_fields = ["total_supply"]'),
+                         [18, 28])
+
+        sol_code = """sol! {
+    contract Token {
+        function mint(address to, uint256 amount) public onlyOwner {
+            totalSupply += amount;
+            balanceOf[to] += amount;
+        }
+
+        function seize(address from, uint256 amount) external {
+            require(msg.sender == owner, "not the owner");
+            balanceOf[from] -= amount;
+            balanceOf[owner] += amount;
+        }
+
+        function setFee(uint256 fee) public onlyOwner {
+            fee = fee;
+        }
+
+        function burn(uint256 amount) public {
+            balanceOf[msg.sender] -= amount;
+            totalSupply -= amount;
+        }
+    }
+}
+"""
+        results = self.analyzer.analyze(sol_code)
+        issues = [issue for issue in results.issues if issue["type"] == "overpowered_owner"]
+        self.assertEqual([issue["line_start"] for issue in issues], [3, 8])
+        self.assertIn("sol! function 'seize'", issues[1]["description"])
+
 
 if __name__ == "__main__":
-    unittest.main() 
+    unittest.main()
@@ -0,0 +1,133 @@
+"""
+Discovery of the contract files of Cargo workspaces and packages
+"""
+import glob
+import os
+from typing import Any, Dict, List, Optional
+
+try:
+    import tomllib
+except ImportError:  # Python < 3.11
+    import tomli as tomllib
+
+from stylus_analyzer.file_filter import FileFilter
+from stylus_analyzer.file_utils import find_rust_contracts
+
+MANIFEST_NAME = "Cargo.toml"
+
+
+class WorkspaceError(ValueError):
+    """Raised for Cargo manifests that can't be read or resolved"""
+
+
+def load_manifest(manifest_path: str) -> Dict[str, Any]:
+    """
+    Parse a Cargo.toml
+
+    Raises:
+        WorkspaceError: If the file can't be read or isn't valid TOML
+    """
+    try:
+        with open(manifest_path, 'rb') as f:
+            return tomllib.load(f)
+    except OSError as e:
+        raise WorkspaceError(f"{manifest_path}: {e.strerror}") from None
+    except tomllib.TOMLDecodeError as e:
+        raise WorkspaceError(f"{manifest_path}: invalid TOML: {e}") from None
+
+
+def find_workspace_manifest(target: str) -> Optional[str]:
+    """
+    Get the manifest to resolve an analysis target through
+
+    That is the target itself when it is a Cargo.toml, or the Cargo.toml of a
+    directory declaring a `[workspace]`. Other directories are searched for
+    `.rs` files as they are.
+    """
+    if os.path.isfile(target):
+        return target if os.path.basename(target) == MANIFEST_NAME else None
+    manifest_path = os.path.join(target, MANIFEST_NAME)
+    if os.path.isfile(manifest_path):
+        try:
+            if "workspace" in load_manifest(manifest_path):
+                return manifest_path
+        except WorkspaceError:
+            return None
+    return None
+
+
+def find_workspace_contracts(manifest_path: str) -> List[str]:
+    """
+    Find the contract files of a Cargo workspace or package
+
+    A workspace's crates are its `[workspace].members`, which may be glob
+    patterns such as `crates/*`, minus the directories matched by
+    `[workspace].exclude`. The `src/**/*.rs` files of each crate with a
+    Cargo.toml are collected, including the root package's own when the
+    manifest also has a `[package]`.
+
+    Returns:
+        The contract files, sorted
+
+    Raises:
+        WorkspaceError: If the manifest can't be parsed or names a member without a Cargo.toml
+    """
+    manifest = load_manifest(manifest_path)
+    # Kept relative when the manifest path is, like the paths found in a directory
+    root = os.path.normpath(os.path.dirname(manifest_path) or os.curdir)
+    workspace = manifest.get("workspace", {})
+    crates = [root] if "package" in manifest or "workspace" not in manifest else []
+
+    excluded = set()
+    for pattern in workspace.get("exclude", []):
+        excluded.update(os.path.normpath(path) for path in glob.glob(os.path.join(root, pattern)))
+    for pattern in workspace.get("members", []):
+        matches = sorted(glob.glob(os.path.join(root, pattern)))
+        if not matches and not glob.has_magic(pattern):
+            raise WorkspaceError(f"{manifest_path}: workspace member '{pattern}' does not exist")
+        for member in matches:
+            member = os.path.normpath(member)
+            if member in excluded or not os.path.isdir(member):
+                continue
+            if not os.path.isfile(os.path.join(member, MANIFEST_NAME)):
+                if glob.has_magic(pattern):
+                    continue
+                raise WorkspaceError(f"{manifest_path}: workspace member '{pattern}' has no {MANIFEST_NAME}")
+            if member not in crates:
+                crates.append(member)
+
+    files = set()
+    for crate in crates:
+        files.update(glob.glob(os.path.join(crate, "src", "**", "*.rs"), recursive=True))
+    return sorted(files)
+
+
+def collect_contract_files(target: str, file_filter: Optional[FileFilter] = None) -> List[str]:
+    """
+    Get the contract files an analysis target stands for
+
+    Cargo.toml files and workspace roots are resolved to their crates'
+    sources, other directories are searched for `.rs` files, and a single
+    file is analyzed on its own. The files found in a directory or
+    workspace are narrowed down by `file_filter` when given; a file named
+    as the target is always analyzed.
+
+    Raises:
+        WorkspaceError: If the target's manifest can't be resolved
+    """
+    manifest_path = find_workspace_manifest(target)
+    if manifest_path:
+        files = find_workspace_contracts(manifest_path)
+        return file_filter.apply(files, contract_root(target)) if file_filter else files
+    if os.path.isdir(target):
+        return sorted(file_filter.walk(target)) if file_filter else sorted(find_rust_contracts(target))
+    return [target]
+
+
+def contract_root(target: str) -> Optional[str]:
+    """Get the directory reported file paths are relative to, or None for a single contract file"""
+    if os.path.isdir(target):
+        return target
+    if find_workspace_manifest(target):
+        return os.path.dirname(target) or "."
+    return None
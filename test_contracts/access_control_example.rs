@@ -0,0 +1,61 @@
+// Example contract with public methods that skip the caller check
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, msg, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Treasury {
+    owner: StorageAddress,
+    fee: StorageU256,
+    balances: StorageMap<Address, StorageU256>,
+}
+
+#[public]
+impl Treasury {
+    // Vulnerable: anyone can change the fee
+    pub fn set_fee(&mut self, fee: U256) {
+        self.fee.set(fee);
+    }
+
+    // Safe: only the owner can change the owner
+    pub fn set_owner(&mut self, new_owner: Address) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.owner.get() {
+            return Err(b"not owner".to_vec());
+        }
+        self.owner.set(new_owner);
+        Ok(())
+    }
+
+    // Safe: guarded by a helper
+    pub fn reset_fee(&mut self) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+        self.fee.set(U256::ZERO);
+        Ok(())
+    }
+
+    // Safe: callers only update their own balance
+    pub fn withdraw(&mut self, amount: U256) {
+        let balance = self.balances.get(msg::sender());
+        self.balances.setter(msg::sender()).set(balance - amount);
+    }
+
+    // Allowlisted: deposits are permissionless
+    pub fn deposit(&mut self, account: Address, amount: U256) {
+        self.balances.setter(account).set(amount);
+    }
+
+    // View function: never reported
+    pub fn fee(&self) -> U256 {
+        self.fee.get()
+    }
+
+    fn only_owner(&self) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.owner.get() {
+            return Err(b"not owner".to_vec());
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,32 @@
+// Example contract with an approve that overwrites the allowance
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, msg, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Allowances {
+    allowances: StorageMap<Address, StorageMap<Address, StorageU256>>,
+}
+
+#[public]
+impl Allowances {
+    // Racy: overwrites the previous allowance
+    pub fn approve(&mut self, spender: Address, value: U256) -> bool {
+        self.allowances.setter(msg::sender()).setter(spender).set(value);
+        true
+    }
+
+    // Safe: requires resetting the allowance to zero first
+    pub fn approve_from_zero(&mut self, spender: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let owner = msg::sender();
+        let current = self.allowances.getter(owner).get(spender);
+        if !(current == U256::ZERO || value == U256::ZERO) {
+            return Err(b"reset allowance first".to_vec());
+        }
+        self.allowances.setter(owner).setter(spender).set(value);
+        Ok(true)
+    }
+}
@@ -0,0 +1,63 @@
+// Example contract cloning heap values inside loops
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::{string::String, vec::Vec};
+use stylus_sdk::{alloy_primitives::{Address, U256}, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Registry {
+    labels: StorageMap<Address, StorageString>,
+    payloads: StorageVec<StorageBytes>,
+}
+
+#[public]
+impl Registry {
+    pub fn label_all(&mut self, accounts: Vec<Address>, label: String) {
+        for account in accounts.iter() {
+            // Unsafe: the same label is copied on every iteration
+            self.labels.setter(*account).set_str(label.clone());
+        }
+    }
+
+    pub fn store_all(&mut self, data: Vec<u8>, count: U256) {
+        let mut i = U256::ZERO;
+        while i < count {
+            // Unsafe: the payload is copied inside a closure in the loop body
+            let push = |store: &mut Self| store.payloads.grow().set_bytes(data.to_vec());
+            push(self);
+            i += U256::from(1);
+        }
+    }
+
+    pub fn tag_all(&mut self, accounts: Vec<Address>) {
+        for account in accounts {
+            // Unsafe: a constant string is allocated on every iteration
+            self.labels.setter(account).set_str("member".to_string());
+        }
+    }
+
+    pub fn copy_labels(&mut self, labels: Vec<String>, accounts: Vec<Address>) {
+        for (index, label) in labels.iter().enumerate() {
+            // Safe: each iteration clones a different loop variable
+            self.labels.setter(accounts[index]).set_str(label.clone());
+            // Safe: closure parameters differ between calls
+            let _names: Vec<String> = labels.iter().map(|name| name.clone()).collect();
+        }
+        accounts.iter().for_each(|account| {
+            // Safe: not inside a loop body
+            let _ = account.clone();
+        });
+    }
+
+    pub fn build_prefixes(&mut self, accounts: Vec<Address>) {
+        let mut prefix = String::new();
+        for account in accounts {
+            // Safe: the prefix grows every iteration
+            prefix.push('a');
+            self.labels.setter(account).set_str(prefix.clone());
+        }
+    }
+}
@@ -0,0 +1,50 @@
+// Example contract with storage fields that never change after deployment
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, msg, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct FeeVault {
+    owner: StorageAddress,
+    fee_bps: StorageU256,
+    treasury: StorageAddress,
+    decimals: StorageU8,
+    total: StorageU256,
+    deposits: StorageMap<Address, StorageU256>,
+}
+
+#[public]
+impl FeeVault {
+    #[constructor]
+    pub fn constructor(&mut self, fee_bps: U256, treasury: Address) {
+        self.owner.set(msg::sender());
+        // Candidates: set once here and only read afterwards
+        self.fee_bps.set(fee_bps);
+        self.treasury.set(treasury);
+        // Not a candidate: never read
+        self.decimals.set(18);
+        self.deposits.setter(treasury).set(U256::ZERO);
+    }
+
+    pub fn deposit(&mut self, amount: U256) {
+        let fee = amount * self.fee_bps.get() / U256::from(10_000);
+        let treasury = self.treasury.get();
+        let credited = self.deposits.get(treasury);
+        self.deposits.setter(treasury).set(credited + fee);
+        self.total.set(self.total.get() + amount - fee);
+    }
+
+    pub fn quote(&self, amount: U256) -> U256 {
+        amount * self.fee_bps.get() / U256::from(10_000)
+    }
+
+    // Not a candidate: the owner can be changed
+    pub fn transfer_ownership(&mut self, new_owner: Address) {
+        if msg::sender() == self.owner.get() {
+            self.owner.set(new_owner);
+        }
+    }
+}
@@ -0,0 +1,98 @@
+// Example contracts whose constructors call other contracts before their storage is set up
+
+use stylus_sdk::{alloy_primitives::{address, Address, U256}, call::{call, Call}, msg, prelude::*};
+use stylus_sdk::storage::{StorageAddress, StorageU256};
+
+sol_interface! {
+    interface IRegistry {
+        function register(address owner) external;
+    }
+}
+
+const FACTORY: Address = address!("0x1111111111111111111111111111111111111111");
+
+#[storage]
+pub struct Vault {
+    owner: StorageAddress,
+    registry: StorageAddress,
+}
+
+#[public]
+impl Vault {
+    // Unsafe: the registry can call back into the vault before the owner is set
+    #[constructor]
+    pub fn constructor(&mut self, registry: Address) -> Result<(), Vec<u8>> {
+        IRegistry::new(registry).register(Call::new_in(self), msg::sender())?;
+        self.owner.set(msg::sender());
+        self.registry.set(registry);
+        Ok(())
+    }
+}
+
+#[storage]
+pub struct Pool {
+    owner: StorageAddress,
+    supply: StorageU256,
+}
+
+#[public]
+impl Pool {
+    // Unsafe: the deploy entrypoint notifies a caller-chosen hook through a helper
+    pub fn deploy(&mut self, hook: Address, supply: U256) -> Result<(), Vec<u8>> {
+        self.notify(hook)?;
+        self.supply.set(supply);
+        Ok(())
+    }
+
+    fn notify(&mut self, hook: Address) -> Result<(), Vec<u8>> {
+        call(Call::new_in(self), hook, &[])?;
+        Ok(())
+    }
+}
+
+#[storage]
+pub struct Token {
+    owner: StorageAddress,
+    factory: StorageAddress,
+}
+
+#[public]
+impl Token {
+    // Safe: the factory is a compile-time constant
+    #[constructor]
+    pub fn constructor(&mut self) -> Result<(), Vec<u8>> {
+        self.owner.set(msg::sender());
+        let factory = IRegistry::new(FACTORY);
+        factory.register(Call::new_in(self), msg::sender())?;
+        self.factory.set(FACTORY);
+        Ok(())
+    }
+
+    // Safe: runs after deployment
+    pub fn register(&mut self, registry: Address) -> Result<(), Vec<u8>> {
+        IRegistry::new(registry).register(Call::new_in(self), self.owner.get())?;
+        Ok(())
+    }
+}
+
+sol! {
+    contract Escrow {
+        address constant ORACLE = 0x2222222222222222222222222222222222222222;
+        address public owner;
+
+        // Unsafe: the arbiter runs before the owner is set
+        constructor(address arbiter) {
+            IArbiter(arbiter).onDeploy(msg.sender);
+            owner = msg.sender;
+        }
+    }
+
+    contract Feed {
+        address constant ORACLE = 0x2222222222222222222222222222222222222222;
+
+        // Safe: the oracle is a constant
+        constructor() {
+            IOracle(ORACLE).subscribe();
+        }
+    }
+}
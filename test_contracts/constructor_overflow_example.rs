@@ -0,0 +1,54 @@
+// Example contracts computing their initial supply from unbounded parameters
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::U256, msg, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Token {
+    decimals: StorageU8,
+    total_supply: StorageU256,
+    cap: StorageU256,
+}
+
+#[public]
+impl Token {
+    // Unsafe: 10^decimals overflows for large decimals
+    #[constructor]
+    pub fn constructor(&mut self, initial_supply: U256, decimals: u8) {
+        let scale = U256::from(10).pow(U256::from(decimals));
+        self.decimals.set(U8::from(decimals));
+        self.total_supply.set(initial_supply * scale);
+    }
+
+    // Unsafe: two unbounded factors are multiplied
+    pub fn init(&mut self, units: U256, price: U256) {
+        self.cap.set(units * price);
+    }
+
+    // Safe: the exponent is bounded first
+    pub fn initialize(&mut self, initial_supply: U256, decimals: u8) -> Result<(), Vec<u8>> {
+        if decimals > 18 {
+            return Err(b"too many decimals".to_vec());
+        }
+        let supply = initial_supply * U256::from(10).pow(U256::from(decimals));
+        self.total_supply.set(supply);
+        Ok(())
+    }
+
+    // Not a constructor: left to the unchecked arithmetic check
+    pub fn scale(&self, amount: U256, decimals: u8) -> U256 {
+        amount * U256::from(10).pow(U256::from(decimals))
+    }
+}
+
+pub struct SafeToken;
+
+impl SafeToken {
+    // Safe: checked variants revert instead of overflowing
+    pub fn new(initial_supply: U256, decimals: u8) -> Option<U256> {
+        U256::from(10).checked_pow(U256::from(decimals))?.checked_mul(initial_supply)
+    }
+}
@@ -0,0 +1,75 @@
+// Example contract with statements that can never run
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, evm, msg, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Registry {
+    owner: StorageAddress,
+    total: StorageU256,
+    paused: StorageBool,
+}
+
+#[public]
+impl Registry {
+    // Unsafe: the storage write after the early return never happens
+    pub fn set_total(&mut self, total: U256) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.owner.get() {
+            return Err(b"not the owner".to_vec());
+        }
+        return Ok(());
+        self.total.set(total);
+        Ok(())
+    }
+
+    // Unsafe: both arms return, so the pause flag is never set
+    pub fn pause(&mut self) -> Result<(), Vec<u8>> {
+        if msg::sender() == self.owner.get() {
+            return Ok(());
+        } else {
+            return Err(b"not the owner".to_vec());
+        }
+        self.paused.set(true);
+        Ok(())
+    }
+
+    // Unsafe: nothing runs after a revert
+    pub fn reset(&mut self) {
+        evm::revert(b"disabled");
+        self.total.set(U256::ZERO);
+    }
+
+    // Safe: the early return is conditional
+    pub fn set_owner(&mut self, owner: Address) -> Result<(), Vec<u8>> {
+        if owner == Address::ZERO {
+            return Err(b"zero address".to_vec());
+        }
+        self.owner.set(owner);
+        Ok(())
+    }
+
+    // Safe: only one arm diverges
+    pub fn unpause(&mut self) -> Result<(), Vec<u8>> {
+        if self.paused.get() {
+            self.paused.set(false);
+        } else {
+            return Err(b"not paused".to_vec());
+        }
+        Ok(())
+    }
+}
+
+impl Registry {
+    // Unsafe: code after panic!() is dead
+    fn checked_total(&self) -> U256 {
+        if self.paused.get() {
+            panic!("paused");
+            let total = self.total.get();
+            return total;
+        }
+        self.total.get()
+    }
+}
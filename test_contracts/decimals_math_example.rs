@@ -0,0 +1,54 @@
+// Example contract mixing decimals-scaled and raw token amounts
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, msg, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Token {
+    decimals: StorageU8,
+    total_supply: StorageU256,
+    cap: StorageU256,
+    balances: StorageMap<Address, StorageU256>,
+}
+
+#[public]
+impl Token {
+    #[constructor]
+    pub fn constructor(&mut self, initial_supply: U256, cap: U256, decimals: u8) {
+        self.decimals.set(U8::from(decimals));
+        let unit = U256::from(10).pow(U256::from(decimals));
+        self.total_supply.set(initial_supply * unit);
+        self.cap.set(cap * U256::from(10).pow(U256::from(decimals)));
+        self.balances.insert(msg::sender(), initial_supply * unit);
+    }
+
+    // Unsafe: the supply is in base units, the minted amount is not scaled
+    pub fn mint(&mut self, to: Address, amount: U256) {
+        let supply = self.total_supply.get() + amount;
+        self.total_supply.set(supply);
+        let balance = self.balances.get(to);
+        self.balances.insert(to, balance + amount);
+    }
+
+    // Unsafe: same mix through a checked subtraction
+    pub fn burn(&mut self, from: Address, amount: U256) -> Result<(), Vec<u8>> {
+        let supply = self.total_supply.get().checked_sub(amount).ok_or(b"underflow".to_vec())?;
+        self.total_supply.set(supply);
+        Ok(())
+    }
+
+    // Safe: the amount is scaled before it is added
+    pub fn mint_whole(&mut self, to: Address, tokens: U256) {
+        let amount = tokens * U256::from(10).pow(U256::from(self.decimals.get()));
+        self.total_supply.set(self.total_supply.get() + amount);
+        self.balances.insert(to, self.balances.get(to) + amount);
+    }
+
+    // Safe: compared, not added
+    pub fn can_mint(&self, amount: U256) -> bool {
+        amount <= self.cap.get()
+    }
+}
@@ -0,0 +1,89 @@
+// Example contract delegatecalling into caller-controlled targets
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{
+    abi::Bytes,
+    alloy_primitives::{address, Address},
+    alloy_sol_types::sol,
+    call::{delegate_call, RawCall},
+    msg,
+    prelude::*,
+};
+
+const LIBRARY: Address = address!("0x1111111111111111111111111111111111111111");
+
+sol! {
+    contract Proxy {
+        address public implementation;
+
+        // Unsafe: anyone can replace the implementation
+        function upgrade(address newImplementation) public {
+            implementation = newImplementation;
+        }
+
+        // Unsafe: runs the unprotected implementation
+        function forward(bytes calldata data) public {
+            (bool success, bytes memory result) = implementation.delegatecall(data);
+            require(success, "delegatecall failed");
+        }
+
+        // Unsafe: delegatecalls a caller-supplied target
+        function execute(address target, bytes calldata data) public {
+            (bool success, bytes memory result) = target.delegatecall(data);
+            require(success, "delegatecall failed");
+        }
+    }
+}
+
+#[storage]
+#[entrypoint]
+pub struct Executor {
+    owner: StorageAddress,
+    module: StorageAddress,
+    plugin: StorageAddress,
+}
+
+#[public]
+impl Executor {
+    // Unsafe: the target is a parameter
+    pub fn exec(&mut self, target: Address, data: Bytes) -> Result<Vec<u8>, Vec<u8>> {
+        let result = unsafe { delegate_call(self, target, &data)? };
+        Ok(result)
+    }
+
+    // Safe: only the owner can change the module
+    pub fn set_module(&mut self, module: Address) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.owner.get() {
+            return Err(b"not owner".to_vec());
+        }
+        self.module.set(module);
+        Ok(())
+    }
+
+    // Unsafe: anyone can change the plugin
+    pub fn set_plugin(&mut self, plugin: Address) {
+        self.plugin.set(plugin);
+    }
+
+    // Safe: the module is owner-gated
+    pub fn run_module(&mut self, data: Bytes) -> Result<Vec<u8>, Vec<u8>> {
+        let module = self.module.get();
+        let result = unsafe { delegate_call(self, module, &data)? };
+        Ok(result)
+    }
+
+    // Unsafe: the plugin can be set by anyone
+    pub fn run_plugin(&mut self, data: Bytes) -> Result<Vec<u8>, Vec<u8>> {
+        let plugin = self.plugin.get();
+        let result = unsafe { RawCall::new_delegate().call(plugin, &data)? };
+        Ok(result)
+    }
+
+    // Safe: the target is a compile-time constant
+    pub fn run_library(&mut self, data: Bytes) -> Result<Vec<u8>, Vec<u8>> {
+        let result = unsafe { delegate_call(self, LIBRARY, &data)? };
+        Ok(result)
+    }
+}
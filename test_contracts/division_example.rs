@@ -0,0 +1,44 @@
+// Example contract dividing before multiplying
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::U256, alloy_sol_types::sol, prelude::*};
+
+sol! {
+    contract Rewards {
+        function reward(uint256 amount, uint256 rate) public returns (uint256) {
+            return amount / 1000 * rate;
+        }
+    }
+}
+
+#[storage]
+#[entrypoint]
+pub struct Vesting {
+    total: StorageU256,
+    duration: StorageU256,
+}
+
+#[public]
+impl Vesting {
+    // Imprecise: the division rounds before the multiplication
+    pub fn vested(&self, elapsed: U256) -> U256 {
+        self.total.get() / self.duration.get() * elapsed
+    }
+
+    // Imprecise: parenthesized division on the right
+    pub fn share(&self, amount: U256, parts: U256, weight: U256) -> U256 {
+        weight * (amount / parts)
+    }
+
+    // Correct order
+    pub fn vested_precise(&self, elapsed: U256) -> U256 {
+        self.total.get() * elapsed / self.duration.get()
+    }
+
+    // Floating point code is not affected
+    fn ratio(a: f64, b: f64, c: f64) -> f64 {
+        a / b * c
+    }
+}
@@ -0,0 +1,48 @@
+// Example contract whose methods clash on their exported ABI selector
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, msg, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Token {
+    balances: StorageMap<Address, StorageU256>,
+}
+
+#[public]
+impl Token {
+    pub fn balance_of(&self, owner: Address) -> U256 {
+        self.balances.get(owner)
+    }
+
+    // Unsafe: also exported as balanceOf(address)
+    #[allow(non_snake_case)]
+    pub fn balanceOf(&self, account: Address) -> U256 {
+        self.balances.get(account) * U256::from(2)
+    }
+
+    pub fn transfer(&mut self, to: Address, amount: U256) -> bool {
+        let sender = msg::sender();
+        let balance = self.balances.get(sender);
+        self.balances.setter(sender).set(balance - amount);
+        let received = self.balances.get(to);
+        self.balances.setter(to).set(received + amount);
+        true
+    }
+
+    // Unsafe: renamed to the selector of transfer(address,uint256)
+    #[selector(name = "transfer")]
+    pub fn send_tokens(&mut self, recipient: Address, value: U256) -> bool {
+        self.transfer(recipient, value)
+    }
+
+    // Safe: exported as transferBatch(address[],uint256)
+    pub fn transfer_batch(&mut self, recipients: Vec<Address>, amount: U256) -> bool {
+        for recipient in recipients {
+            self.transfer(recipient, amount);
+        }
+        true
+    }
+}
@@ -0,0 +1,83 @@
+// Example contract with match arms that swallow errors
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, alloy_sol_types::sol, evm, prelude::*};
+
+sol_interface! {
+    interface IERC20 {
+        function transfer(address to, uint256 value) external returns (bool);
+    }
+}
+
+sol! {
+    event TransferFailed(address to, uint256 amount);
+}
+
+#[storage]
+#[entrypoint]
+pub struct Distributor {
+    paid: StorageMap<Address, StorageU256>,
+}
+
+#[public]
+impl Distributor {
+    // Unsafe: both arms are empty
+    pub fn pay(&mut self, token: IERC20, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        match token.transfer(&mut *self, to, amount) {
+            Ok(_) => {}
+            Err(_) => {}
+        }
+        self.paid.insert(to, amount);
+        Ok(())
+    }
+
+    // Unsafe: the error arm only holds a comment
+    pub fn pay_quietly(&mut self, token: IERC20, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        match token.transfer(&mut *self, to, amount) {
+            Ok(sent) => self.record(to, amount, sent),
+            Err(e) => {
+                // Nothing to do
+            }
+        }
+        Ok(())
+    }
+
+    // Unsafe: the wildcard catches the errors and does nothing
+    pub fn pay_or_skip(&mut self, token: IERC20, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        match token.transfer(&mut *self, to, amount) {
+            Ok(sent) => self.record(to, amount, sent),
+            _ => (),
+        }
+        Ok(())
+    }
+
+    // Safe: the error is logged
+    pub fn pay_logged(&mut self, token: IERC20, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        match token.transfer(&mut *self, to, amount) {
+            Ok(sent) => self.record(to, amount, sent),
+            Err(_) => evm::log(TransferFailed { to, amount }),
+        }
+        Ok(())
+    }
+
+    // Safe: the error is returned
+    pub fn pay_strict(&mut self, token: IERC20, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        match token.transfer(&mut *self, to, amount) {
+            Ok(sent) => self.record(to, amount, sent),
+            Err(e) => return Err(e.into()),
+        }
+        Ok(())
+    }
+}
+
+impl Distributor {
+    fn record(&mut self, to: Address, amount: U256, sent: bool) {
+        // Safe: a missing Option value is not an error
+        match sent.then_some(amount) {
+            Some(value) => self.paid.insert(to, value),
+            None => {}
+        }
+    }
+}
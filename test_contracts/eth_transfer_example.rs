@@ -0,0 +1,87 @@
+// Example contract with gated and ungated Ether transfers
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, alloy_sol_types::sol, call::{call, transfer_eth, Call}, msg, prelude::*};
+
+sol! {
+    contract Bank {
+        address public owner;
+        mapping(address => uint256) balances;
+
+        // Unsafe: anyone can send the whole balance anywhere
+        function drain(address to) public {
+            payable(to).transfer(address(this).balance);
+        }
+
+        // Refund of the caller's own balance
+        function refund() public {
+            uint256 amount = balances[msg.sender];
+            balances[msg.sender] = 0;
+            payable(msg.sender).transfer(amount);
+        }
+
+        // Safe: guarded by a modifier
+        function sweep(address to) public onlyOwner {
+            payable(to).transfer(address(this).balance);
+        }
+    }
+}
+
+#[storage]
+#[entrypoint]
+pub struct Vault {
+    owner: StorageAddress,
+    balances: StorageMap<Address, StorageU256>,
+}
+
+#[public]
+impl Vault {
+    // Unsafe: arbitrary recipient and amount
+    pub fn sweep(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        transfer_eth(to, amount)?;
+        Ok(())
+    }
+
+    // Low: the caller withdraws their own recorded balance
+    pub fn withdraw(&mut self) -> Result<(), Vec<u8>> {
+        let sender = msg::sender();
+        let balance = self.balances.get(sender);
+        self.balances.setter(sender).set(U256::ZERO);
+        transfer_eth(sender, balance)?;
+        Ok(())
+    }
+
+    // Safe: only the owner can move funds
+    pub fn admin_withdraw(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.owner.get() {
+            return Err(b"not owner".to_vec());
+        }
+        transfer_eth(to, amount)?;
+        Ok(())
+    }
+
+    // Unsafe: the transfer happens in an unguarded helper
+    pub fn pay(&mut self, to: Address) -> Result<(), Vec<u8>> {
+        self.send_out(to, U256::from(1000))
+    }
+
+    // Unsafe: a call carrying value to an arbitrary target
+    pub fn forward(&mut self, target: Address, amount: U256) -> Result<(), Vec<u8>> {
+        call(Call::new_in(self).value(amount), target, &[])?;
+        Ok(())
+    }
+
+    // Safe: the call carries no value
+    pub fn ping(&mut self, target: Address) -> Result<(), Vec<u8>> {
+        call(Call::new_in(self).value(U256::ZERO), target, &[])?;
+        Ok(())
+    }
+}
+
+impl Vault {
+    fn send_out(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        transfer_eth(to, amount)
+    }
+}
@@ -0,0 +1,51 @@
+// Example contract using expect() where an error should be returned
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Registry {
+    entries: StorageVec<StorageAddress>,
+    fee: StorageU256,
+}
+
+#[public]
+impl Registry {
+    // Unsafe: panics when the index is out of range
+    pub fn entry(&self, index: U256) -> Address {
+        let index = index.try_into().expect("index too large");
+        self.entries.get(index).expect("no entry at index")
+    }
+
+    // Unsafe: only reported once, by the unwrap check
+    pub fn first_fee(&self, fees: Vec<Option<U256>>) -> U256 {
+        fees.first().expect("no fees").unwrap()
+    }
+
+    // Safe: returns an error instead
+    pub fn checked_entry(&self, index: U256) -> Result<Address, Vec<u8>> {
+        let index: usize = index.try_into().map_err(|_| b"index too large".to_vec())?;
+        self.entries.get(index).ok_or(b"no entry at index".to_vec())
+    }
+}
+
+impl Registry {
+    // Internal helpers are not externally reachable
+    fn fee_of(&self, fees: &[U256]) -> U256 {
+        *fees.first().expect("no fees")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_fee() {
+        let fee: U256 = "1".parse().expect("valid fee");
+        assert_eq!(fee, U256::from(1));
+    }
+}
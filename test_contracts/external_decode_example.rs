@@ -0,0 +1,77 @@
+// Example functions decoding call return data and calldata with and without checking its length
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use alloy_sol_types::{sol_data::{Address as SOLAddress, Bool as SOLBool, Uint}, SolType};
+use stylus_sdk::{abi::Bytes, alloy_primitives::{Address, U256}, call::{static_call, Call}, prelude::*};
+
+sol! {
+    contract LegacyVault {
+        // Unsafe: empty return data from a token without a return value makes the decode revert
+        function unsafeTransferERC20(address token, address to, uint256 amount) public {
+            (bool success, bytes memory returnData) = token.call(abi.encodeWithSignature("transfer(address,uint256)", to, amount));
+            require(success && abi.decode(returnData, (bool)), "ERC20 transfer failed");
+        }
+
+        // Safe: empty return data is accepted before decoding
+        function safeTransferERC20(address token, address to, uint256 amount) public {
+            (bool success, bytes memory returnData) = token.call(abi.encodeWithSignature("transfer(address,uint256)", to, amount));
+            require(success && (returnData.length == 0 || abi.decode(returnData, (bool))), "ERC20 transfer failed");
+        }
+    }
+}
+
+#[storage]
+#[entrypoint]
+pub struct Vault {
+    owner: StorageAddress,
+    limit: StorageU256,
+}
+
+#[public]
+impl Vault {
+    // Unsafe: the return data of the static call is decoded as is
+    pub fn token_balance(&self, token: Address, calldata: Vec<u8>) -> Result<U256, Vec<u8>> {
+        let return_data = static_call(Call::new(), token, &calldata)?;
+        let balance = <Uint<256> as SolType>::abi_decode(&return_data, true).map_err(|_| b"bad return".to_vec())?;
+        Ok(balance)
+    }
+
+    // Safe: empty return data is rejected first
+    pub fn checked_token_balance(&self, token: Address, calldata: Vec<u8>) -> Result<U256, Vec<u8>> {
+        let return_data = static_call(Call::new(), token, &calldata)?;
+        if return_data.is_empty() {
+            return Err(b"empty return".to_vec());
+        }
+        let balance = <Uint<256> as SolType>::abi_decode(&return_data, true).map_err(|_| b"bad return".to_vec())?;
+        Ok(balance)
+    }
+
+    // Unsafe: the caller's payload is decoded without checking its size
+    pub fn configure(&mut self, payload: Bytes) -> Result<(), Vec<u8>> {
+        let (owner, limit) = <(SOLAddress, Uint<256>) as SolType>::abi_decode(&payload, true)
+            .map_err(|_| b"bad payload".to_vec())?;
+        self.owner.set(owner);
+        self.limit.set(limit);
+        Ok(())
+    }
+
+    // Safe: the payload must hold both words
+    pub fn checked_configure(&mut self, payload: Bytes) -> Result<(), Vec<u8>> {
+        if payload.len() < 64 {
+            return Err(b"short payload".to_vec());
+        }
+        let (owner, limit) = <(SOLAddress, Uint<256>) as SolType>::abi_decode(&payload, true)
+            .map_err(|_| b"bad payload".to_vec())?;
+        self.owner.set(owner);
+        self.limit.set(limit);
+        Ok(())
+    }
+
+    // Safe: the bytes were encoded by the contract itself
+    pub fn round_trip(&self, flag: bool) -> bool {
+        let encoded = <SOLBool as SolType>::abi_encode(&flag);
+        <SOLBool as SolType>::abi_decode(&encoded, true).unwrap_or(false)
+    }
+}
@@ -0,0 +1,78 @@
+// Example fallbacks with and without logic in their body
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use stylus_sdk::{alloy_primitives::{Address, U256}, call::delegate_call, msg, prelude::*};
+
+sol! {
+    contract Forwarder {
+        address public implementation;
+        uint256 public calls;
+
+        // Unsafe: every unknown selector is counted and forwarded
+        fallback() external payable {
+            calls += 1;
+            (bool success, ) = implementation.delegatecall(msg.data);
+            require(success, "forward failed");
+        }
+    }
+
+    contract Sink {
+        // Safe: the fallback only accepts the call
+        fallback() external payable {}
+    }
+}
+
+#[storage]
+#[entrypoint]
+pub struct Wallet {
+    implementation: StorageAddress,
+    deposits: StorageMap<Address, StorageU256>,
+}
+
+#[public]
+impl Wallet {
+    // Unsafe: unknown calls credit a deposit
+    #[fallback]
+    #[payable]
+    pub fn fallback(&mut self, _calldata: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+        let deposited = self.deposits.get(msg::sender());
+        self.deposits.setter(msg::sender()).set(deposited + msg::value());
+        Ok(Vec::new())
+    }
+}
+
+#[storage]
+pub struct Proxy {
+    implementation: StorageAddress,
+}
+
+#[public]
+impl Proxy {
+    // Unsafe: unknown calls are delegated to the implementation
+    #[fallback]
+    pub fn fallback(&mut self, calldata: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+        unsafe { delegate_call(self, self.implementation.get(), calldata) }
+    }
+}
+
+#[storage]
+pub struct Receiver {
+    received: StorageU256,
+}
+
+#[public]
+impl Receiver {
+    // Safe: the fallback only returns empty bytes
+    #[fallback]
+    #[payable]
+    pub fn fallback(&mut self, _calldata: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    pub fn received(&self) -> U256 {
+        self.received.get()
+    }
+}
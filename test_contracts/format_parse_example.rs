@@ -0,0 +1,43 @@
+// Example contract parsing formatted strings back into values
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::string::{String, ToString};
+use stylus_sdk::{alloy_primitives::U256, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Converter {
+    value: StorageU256,
+}
+
+#[public]
+impl Converter {
+    pub fn scaled(&self, whole: u64) -> U256 {
+        // Unsafe: the formatted text is parsed back and unwrapped
+        format!("{}000000", whole).parse::<U256>().unwrap()
+    }
+
+    pub fn from_label(&self, id: u32) -> u64 {
+        // Unsafe: a formatted local is parsed with expect
+        let label = format!("{}{}", id, id);
+        label.parse().expect("valid number")
+    }
+
+    pub fn doubled(&self, value: u64) -> u64 {
+        // Unsafe: to_string round trip
+        value.to_string().parse::<u64>().unwrap() * 2
+    }
+
+    pub fn checked(&self, whole: u64) -> Result<U256, Vec<u8>> {
+        // Safe: the parse error is propagated
+        let parsed = format!("{}000", whole).parse::<U256>().map_err(|_| b"parse".to_vec())?;
+        Ok(parsed)
+    }
+
+    pub fn parse_input(&self, input: String) -> u64 {
+        // Safe: not a formatted string
+        input.parse().unwrap_or_default()
+    }
+}
@@ -0,0 +1,59 @@
+// Example contract with literal addresses in its logic
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{address, Address, U256}, alloy_sol_types::sol, call::transfer_eth, msg, prelude::*};
+
+// Unsafe: privileged account that can never be rotated
+const ADMIN: Address = address!("0x5B38Da6a701c568545dCfcB03FcB875f56beddC4");
+
+// Safe: ArbSys precompile
+const ARB_SYS: Address = address!("0x0000000000000000000000000000000000000064");
+
+sol! {
+    function sweep() {
+        // Unsafe: funds always go to a fixed account
+        payable(address(0xAb8483F64d9C6d1EcF9b849Ae677dD3315835cb2)).transfer(address(this).balance);
+    }
+}
+
+#[storage]
+#[entrypoint]
+pub struct Treasury {
+    owner: StorageAddress,
+    fee: StorageU256,
+}
+
+#[public]
+impl Treasury {
+    pub fn set_fee(&mut self, fee: U256) -> Result<(), Vec<u8>> {
+        if msg::sender() != ADMIN {
+            return Err(b"not admin".to_vec());
+        }
+        self.fee.set(fee);
+        Ok(())
+    }
+
+    pub fn withdraw(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        // Unsafe: leftover test recipient
+        let recipient = Address::from([0x11u8; 20]);
+        transfer_eth(recipient, amount)?;
+        Ok(())
+    }
+
+    pub fn is_dev(&self) -> bool {
+        // Unsafe: parsed literal compared against the caller
+        msg::sender() == "0x4B20993Bc481177ec7E8f571ceCaE8A9e22C02db".parse::<Address>().unwrap()
+    }
+
+    pub fn is_system(&self) -> bool {
+        // Safe: zero address and precompiles are ignored
+        msg::sender() == Address::ZERO || msg::sender() == ARB_SYS
+    }
+
+    pub fn default_owner(&self) -> Address {
+        // Safe: only returned, never used in control flow
+        address!("0x78731D3Ca6b7E34aC0F824c42a7cC18A495cabaB")
+    }
+}
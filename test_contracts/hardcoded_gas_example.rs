@@ -0,0 +1,58 @@
+// Example contract making external calls with hardcoded gas limits and values
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, call::{call, Call, RawCall}, prelude::*};
+use stylus_sdk::storage::StorageU256;
+
+// The stipend Solidity's transfer and send forward
+const STIPEND: u64 = 2300;
+
+sol_interface! {
+    interface IReceiver {
+        function onPayment(uint256 amount) external;
+    }
+}
+
+#[storage]
+#[entrypoint]
+pub struct Payer {
+    payments: StorageU256,
+}
+
+#[public]
+impl Payer {
+    // Vulnerable: 2300 gas isn't enough for a contract recipient
+    pub fn pay(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        call(Call::new_in(self).gas(2300).value(amount), to, &[])?;
+        Ok(())
+    }
+
+    // Vulnerable: the same stipend through a constant
+    pub fn notify(&mut self, receiver: IReceiver, amount: U256) -> Result<(), Vec<u8>> {
+        receiver.on_payment(Call::new_in(self).gas(STIPEND), amount)?;
+        Ok(())
+    }
+
+    // Vulnerable: a hardcoded tip of 1 gwei
+    pub fn tip(&mut self, to: Address) -> Result<(), Vec<u8>> {
+        call(Call::new_in(self).value(U256::from(1_000_000_000u64)), to, &[])?;
+        Ok(())
+    }
+
+    // Vulnerable: a raw call with a literal value and gas limit
+    pub fn ping(&mut self, to: Address) -> Result<Vec<u8>, Vec<u8>> {
+        unsafe { RawCall::new_with_value(U256::from(1)).gas(5000).call(to, &[]) }
+    }
+
+    // Safe: the caller chooses the gas limit and the value
+    pub fn forward(&mut self, to: Address, amount: U256, gas: u64) -> Result<(), Vec<u8>> {
+        call(Call::new_in(self).gas(gas).value(amount), to, &[])?;
+        Ok(())
+    }
+
+    // Safe: sends no Ether and forwards all gas
+    pub fn poke(&mut self, to: Address) -> Result<(), Vec<u8>> {
+        call(Call::new_in(self).value(U256::ZERO), to, &[])?;
+        self.payments.set(self.payments.get() + U256::from(1));
+        Ok(())
+    }
+}
@@ -0,0 +1,97 @@
+// Example contract dropping the Result and Option values of calls
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, call::transfer_eth, msg, prelude::*};
+
+sol_interface! {
+    interface IOracle {
+        function update(uint256 price) external;
+        function latest() external view returns (uint256);
+    }
+}
+
+#[storage]
+#[entrypoint]
+pub struct Treasury {
+    owner: StorageAddress,
+    balances: StorageMap<Address, StorageU256>,
+    oracle: StorageAddress,
+}
+
+#[public]
+impl Treasury {
+    // Unsafe: the helper's error is dropped
+    pub fn deposit(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        self.credit(msg::sender(), amount);
+        Ok(())
+    }
+
+    // Unsafe: a failed payout goes unnoticed
+    pub fn payout(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+        transfer_eth(to, amount);
+        Ok(())
+    }
+
+    // Unsafe: the oracle call's error is dropped
+    pub fn push_price(&mut self, oracle: IOracle, price: U256) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+        oracle.update(&mut *self, price);
+        Ok(())
+    }
+
+    // Intentional: explicitly discarded, reported as Info
+    pub fn refresh(&mut self, oracle: IOracle) -> Result<(), Vec<u8>> {
+        let _ = oracle.latest(&*self);
+        Ok(())
+    }
+
+    // Unsafe, but left to the empty catch arm detector: the error arm swallows the failure
+    pub fn sync(&mut self, oracle: IOracle) -> Result<(), Vec<u8>> {
+        match oracle.latest(&*self) {
+            Ok(price) => self.store_price(price),
+            Err(_) => {}
+        }
+        Ok(())
+    }
+
+    // Safe: results are propagated, bound or checked
+    pub fn withdraw(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        self.debit(msg::sender(), amount)?;
+        let remaining = self.balance_after(msg::sender(), amount);
+        if remaining.is_none() {
+            return Err(b"insufficient balance".to_vec());
+        }
+        transfer_eth(msg::sender(), amount)
+    }
+}
+
+impl Treasury {
+    fn only_owner(&self) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.owner.get() {
+            return Err(b"not the owner".to_vec());
+        }
+        Ok(())
+    }
+
+    fn credit(&mut self, account: Address, amount: U256) -> Result<(), Vec<u8>> {
+        let balance = self.balances.get(account);
+        let updated = balance.checked_add(amount).ok_or(b"overflow".to_vec())?;
+        self.balances.insert(account, updated);
+        Ok(())
+    }
+
+    fn debit(&mut self, account: Address, amount: U256) -> Result<(), Vec<u8>> {
+        let remaining = self.balance_after(account, amount).ok_or(b"insufficient balance".to_vec())?;
+        self.balances.insert(account, remaining);
+        Ok(())
+    }
+
+    fn balance_after(&self, account: Address, amount: U256) -> Option<U256> {
+        self.balances.get(account).checked_sub(amount)
+    }
+
+    fn store_price(&mut self, _price: U256) {}
+}
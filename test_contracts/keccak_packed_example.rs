@@ -0,0 +1,68 @@
+// Example identifiers hashed from the packed encoding of dynamically-sized values
+
+extern crate alloc;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use alloy_sol_types::{sol_data::{Address as SOLAddress, Bytes as SOLBytes, String as SOLString, Uint}, SolType};
+use sha3::{Digest, Keccak256};
+use stylus_sdk::{alloy_primitives::{keccak256, Address, B256, U256}, crypto::keccak, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Registry;
+
+#[public]
+impl Registry {
+    // Unsafe: ("a", "bc") and ("ab", "c") hash to the same id
+    pub fn token_id(&self, name: String, symbol: String) -> B256 {
+        keccak(<(SOLString, SOLString) as SolType>::abi_encode_packed(&(name, symbol)))
+    }
+
+    // Unsafe: the packed bytes are bound to a local before hashing
+    pub fn record_id(&self, label: String, data: Vec<u8>) -> B256 {
+        type Record = (SOLString, SOLBytes);
+        let encoded = Record::abi_encode_packed(&(label, data));
+        keccak256(&encoded)
+    }
+
+    // Unsafe: manual concatenation hashed with a Keccak256 hasher
+    pub fn pair_hash(&self, first: String, second: String) -> [u8; 32] {
+        let packed = [first.as_bytes(), second.as_bytes()].concat();
+        let mut hasher = Keccak256::new();
+        hasher.update(&packed);
+        hasher.finalize().into()
+    }
+
+    // Unsafe: byte vectors concatenated and hashed in one expression
+    pub fn blob_hash(&self, left: Vec<u8>, right: Vec<u8>) -> B256 {
+        keccak256([left.as_slice(), right.as_slice()].concat())
+    }
+
+    // Safe: a zero byte separates the strings
+    pub fn safe_encode_packed_with_delimiter(&self, a: String, b: String) -> B256 {
+        let delimiter = [0u8];
+        let packed_data = [a.as_bytes(), &delimiter, b.as_bytes()].concat();
+        keccak256(&packed_data)
+    }
+
+    // Safe: only one value is dynamically-sized
+    pub fn owner_label(&self, owner: Address, label: String) -> B256 {
+        keccak256([label.as_bytes(), owner.as_slice()].concat())
+    }
+
+    // Safe: fixed-size values can't shift into each other
+    pub fn deposit_id(&self, owner: Address, amount: U256) -> B256 {
+        keccak(<(SOLAddress, Uint<256>) as SolType>::abi_encode_packed(&(owner, amount)))
+    }
+
+    // Safe: the standard encoding pads and length-prefixes every value
+    pub fn padded_id(&self, name: String, symbol: String) -> B256 {
+        keccak(<(SOLString, SOLString) as SolType>::abi_encode(&(name, symbol)))
+    }
+
+    // Safe: the packed bytes are returned, not hashed
+    pub fn packed(&self, name: String, symbol: String) -> Vec<u8> {
+        <(SOLString, SOLString) as SolType>::abi_encode_packed(&(name, symbol))
+    }
+}
@@ -0,0 +1,51 @@
+// Example functions that validate their inputs only after writing storage
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, evm, msg, prelude::*};
+use stylus_sdk::storage::{StorageAddress, StorageMap, StorageU256};
+
+#[storage]
+#[entrypoint]
+pub struct Token {
+    balances: StorageMap<Address, StorageU256>,
+    total_supply: StorageU256,
+    owner: StorageAddress,
+}
+
+#[public]
+impl Token {
+    // Unsafe: the balance is decremented before it is checked
+    pub fn burn(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        let balance = self.balances.get(msg::sender());
+        self.balances.setter(msg::sender()).set(balance - amount);
+        if balance < amount {
+            return Err(b"insufficient balance".to_vec());
+        }
+        self.total_supply.set(self.total_supply.get() - amount);
+        Ok(())
+    }
+
+    // Unsafe: the new owner is stored before it is validated
+    pub fn transfer_ownership(&mut self, new_owner: Address) {
+        self.owner.set(new_owner);
+        assert!(new_owner != Address::ZERO, "zero owner");
+    }
+
+    // Safe: the balance is checked before it is decremented
+    pub fn safe_burn(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        let balance = self.balances.get(msg::sender());
+        if balance < amount {
+            return Err(b"insufficient balance".to_vec());
+        }
+        self.balances.setter(msg::sender()).set(balance - amount);
+        self.total_supply.set(self.total_supply.get() - amount);
+        Ok(())
+    }
+
+    // Safe: the later check doesn't involve the inputs of the write
+    pub fn mint(&mut self, to: Address, amount: U256) {
+        self.balances.setter(to).set(self.balances.get(to) + amount);
+        if msg::sender() != self.owner.get() {
+            evm::revert(b"not owner");
+        }
+    }
+}
@@ -0,0 +1,55 @@
+// Example contract reading collection lengths in loop conditions
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::U256, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Queue {
+    items: StorageVec<StorageU256>,
+}
+
+#[public]
+impl Queue {
+    // Inefficient: reads the storage length on every iteration
+    pub fn sum(&self) -> U256 {
+        let mut total = U256::ZERO;
+        let mut i = 0;
+        while i < self.items.len() {
+            total += self.items.get(i).unwrap_or_default();
+            i += 1;
+        }
+        total
+    }
+
+    // Inefficient: recomputes the length of a local vector
+    pub fn count_nonzero(&self, values: Vec<U256>) -> u32 {
+        let mut count = 0;
+        let mut i = 0;
+        while i < values.len() {
+            if values[i] != U256::ZERO {
+                count += 1;
+            }
+            i += 1;
+        }
+        count
+    }
+
+    // Fine: the queue shrinks inside the loop
+    pub fn drain(&mut self) {
+        while self.items.len() > 0 {
+            self.items.pop();
+        }
+    }
+
+    // Fine: a for range is evaluated once
+    pub fn sum_for(&self) -> U256 {
+        let mut total = U256::ZERO;
+        for i in 0..self.items.len() {
+            total += self.items.get(i).unwrap_or_default();
+        }
+        total
+    }
+}
@@ -0,0 +1,62 @@
+// Example contract building addresses from truncated integers and byte slices
+
+use stylus_sdk::{alloy_primitives::{keccak256, Address, U160, U256}, prelude::*};
+use stylus_sdk::storage::StorageAddress;
+
+#[storage]
+#[entrypoint]
+pub struct Router {
+    recipient: StorageAddress,
+    pool: StorageAddress,
+}
+
+#[public]
+impl Router {
+    // Vulnerable: the first 20 bytes of the word are its high bytes, not the address
+    pub fn set_recipient(&mut self, word: U256) {
+        let recipient = Address::from_slice(&word.to_be_bytes::<32>()[..20]);
+        self.recipient.set(recipient);
+    }
+
+    // Vulnerable: the cast keeps only the low 64 bits of the value
+    pub fn set_pool(&mut self, value: U256) {
+        let low = value.as_limbs()[0] as u64;
+        self.pool.set(Address::from(U160::from(low)));
+    }
+
+    // Vulnerable: wrapping_to silently drops the high 96 bits
+    pub fn recipient_of(&self, value: U256) -> Address {
+        Address::from(value.wrapping_to::<U160>())
+    }
+
+    // Vulnerable: 16 bytes are left-padded into an address
+    pub fn from_prefix(&self, data: Vec<u8>) -> Address {
+        Address::left_padding_from(&data[4..20])
+    }
+
+    // Vulnerable: a u64 id can't be an address
+    pub fn account(&self, id: u64) -> Address {
+        Address::from(U160::from(id))
+    }
+
+    // Safe: the last 20 bytes of the word
+    pub fn set_recipient_word(&mut self, word: U256) {
+        self.recipient.set(Address::from_word(word.into()));
+    }
+
+    // Safe: CREATE2 addresses are the last 20 bytes of the hash
+    pub fn create2_address(&self, salt: [u8; 32]) -> Address {
+        Address::from_slice(&keccak256(salt)[12..])
+    }
+
+    // Safe: 20 bytes after the selector
+    pub fn decode_target(&self, data: Vec<u8>) -> Address {
+        Address::from_slice(&data[4..24])
+    }
+
+    // Safe: the value is checked to fit in 160 bits
+    pub fn checked_account(&self, value: U256) -> Result<Address, Vec<u8>> {
+        let value: U160 = value.checked_to().ok_or(b"value too large".to_vec())?;
+        Ok(Address::from(value))
+    }
+}
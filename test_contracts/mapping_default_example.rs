@@ -0,0 +1,76 @@
+// Example token functions subtracting from mapping entries that may never have been set
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, alloy_sol_types::sol, msg, prelude::*};
+use stylus_sdk::storage::{StorageMap, StorageU256};
+
+#[storage]
+#[entrypoint]
+pub struct Token {
+    balances: StorageMap<Address, StorageU256>,
+    allowances: StorageMap<Address, StorageMap<Address, StorageU256>>,
+    total_supply: StorageU256,
+}
+
+#[public]
+impl Token {
+    // Unsafe: a spender that was never approved reads an allowance of zero
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let allowance = self.allowances.get(from).get(msg::sender());
+        self.allowances.setter(from).setter(msg::sender()).set(allowance - value);
+        self.move_tokens(from, to, value)?;
+        Ok(true)
+    }
+
+    // Unsafe: the balance is read and decremented in one expression
+    pub fn burn(&mut self, value: U256) {
+        self.balances.setter(msg::sender()).set(self.balances.get(msg::sender()) - value);
+        self.total_supply.set(self.total_supply.get() - value);
+    }
+
+    // Safe: the allowance is checked before it is decremented
+    pub fn safe_transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let allowance = self.allowances.get(from).get(msg::sender());
+        if allowance < value {
+            return Err(b"insufficient allowance".to_vec());
+        }
+        self.allowances.setter(from).setter(msg::sender()).set(allowance - value);
+        self.move_tokens(from, to, value)?;
+        Ok(true)
+    }
+
+    // Safe: checked_sub reverts on a missing entry
+    pub fn checked_burn(&mut self, value: U256) -> Result<(), Vec<u8>> {
+        let balance = self.balances.get(msg::sender());
+        let remaining = balance.checked_sub(value).ok_or(b"insufficient balance".to_vec())?;
+        self.balances.setter(msg::sender()).set(remaining);
+        Ok(())
+    }
+}
+
+impl Token {
+    fn move_tokens(&mut self, from: Address, to: Address, value: U256) -> Result<(), Vec<u8>> {
+        let balance = self.balances.get(from);
+        if balance < value {
+            return Err(b"insufficient balance".to_vec());
+        }
+        self.balances.setter(from).set(balance - value);
+        self.balances.setter(to).set(self.balances.get(to) + value);
+        Ok(())
+    }
+}
+
+sol! {
+    contract LegacyToken {
+        mapping(address => uint256) balanceOf;
+        mapping(address => mapping(address => uint256)) allowance;
+
+        // Unsafe: a never-approved allowance underflows
+        function transferFrom(address from, address to, uint256 value) public returns (bool) {
+            allowance[from][msg.sender] -= value;
+            require(balanceOf[from] >= value, "insufficient balance");
+            balanceOf[from] -= value;
+            balanceOf[to] += value;
+            return true;
+        }
+    }
+}
@@ -0,0 +1,48 @@
+// Example contract changing state without emitting events
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, alloy_sol_types::sol, evm, msg, prelude::*};
+
+sol! {
+    event OwnerChanged(address indexed previous, address indexed next);
+}
+
+#[storage]
+#[entrypoint]
+pub struct Market {
+    owner: StorageAddress,
+    price: StorageU256,
+    locked: StorageBool,
+}
+
+#[public]
+impl Market {
+    #[constructor]
+    pub fn constructor(&mut self) {
+        self.owner.set(msg::sender());
+    }
+
+    // Missing event: the price changes silently
+    pub fn set_price(&mut self, price: U256) {
+        self.price.set(price);
+    }
+
+    // Fine: the change is logged
+    pub fn set_owner(&mut self, next: Address) {
+        let previous = self.owner.get();
+        self.owner.set(next);
+        evm::log(OwnerChanged { previous, next });
+    }
+
+    // Fine: only toggles the reentrancy lock
+    pub fn lock(&mut self) {
+        self.locked.set(true);
+    }
+
+    // Fine: read only
+    pub fn price(&self) -> U256 {
+        self.price.get()
+    }
+}
@@ -0,0 +1,91 @@
+// Example contract with payable and Ether-moving methods that call out with and without a reentrancy guard
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, call::{call, transfer_eth, Call}, msg, prelude::*};
+use stylus_sdk::storage::{StorageBool, StorageMap, StorageU256};
+
+sol_interface! {
+    interface IHook {
+        function onDeposit(address from, uint256 amount) external;
+    }
+}
+
+#[storage]
+#[entrypoint]
+pub struct Vault {
+    deposits: StorageMap<Address, StorageU256>,
+    locked: StorageBool,
+    status: StorageU256,
+}
+
+#[public]
+impl Vault {
+    // Unsafe: payable and notifies a caller-chosen hook without a guard
+    #[payable]
+    pub fn deposit_and_notify(&mut self, hook: IHook) -> Result<(), Vec<u8>> {
+        let sender = msg::sender();
+        self.deposits.setter(sender).set(msg::value());
+        hook.on_deposit(Call::new_in(self), sender, msg::value())?;
+        Ok(())
+    }
+
+    // Unsafe: sends Ether through a helper, even though storage is written first
+    pub fn withdraw(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        let sender = msg::sender();
+        let balance = self.deposits.get(sender);
+        self.deposits.setter(sender).set(balance - amount);
+        self.pay(sender, amount)?;
+        Ok(())
+    }
+
+    // Unsafe: the guard is only set after the call
+    pub fn forward(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        call(Call::new_in(self).value(amount), to, &[])?;
+        self.locked.set(true);
+        self.locked.set(false);
+        Ok(())
+    }
+
+    // Safe: guarded by the locked flag
+    #[payable]
+    pub fn guarded_deposit(&mut self, hook: IHook) -> Result<(), Vec<u8>> {
+        if self.locked.get() {
+            return Err(b"reentrant call".to_vec());
+        }
+        self.locked.set(true);
+        hook.on_deposit(Call::new_in(self), msg::sender(), msg::value())?;
+        self.locked.set(false);
+        Ok(())
+    }
+
+    // Safe: guarded through enter/exit helpers on the status field
+    pub fn guarded_withdraw(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        self.enter()?;
+        transfer_eth(msg::sender(), amount)?;
+        self.exit();
+        Ok(())
+    }
+
+    // Safe: moves no Ether
+    pub fn notify(&mut self, hook: IHook) -> Result<(), Vec<u8>> {
+        hook.on_deposit(Call::new_in(self), msg::sender(), U256::ZERO)?;
+        Ok(())
+    }
+}
+
+impl Vault {
+    fn pay(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        transfer_eth(to, amount)
+    }
+
+    fn enter(&mut self) -> Result<(), Vec<u8>> {
+        if self.status.get() == U256::from(2) {
+            return Err(b"reentrant call".to_vec());
+        }
+        self.status.set(U256::from(2));
+        Ok(())
+    }
+
+    fn exit(&mut self) {
+        self.status.set(U256::from(1));
+    }
+}
@@ -0,0 +1,78 @@
+// Example contract with caller checks in read-only getters
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, alloy_sol_types::sol, msg, prelude::*};
+
+sol! {
+    contract Registry {
+        address public owner;
+        uint256 private fee;
+
+        // Unsafe: a view function can't hide the fee
+        function getFee() external view returns (uint256) {
+            require(msg.sender == owner, "not the owner");
+            return fee;
+        }
+    }
+}
+
+#[storage]
+#[entrypoint]
+pub struct Vault {
+    owner: StorageAddress,
+    secret: StorageU256,
+    reads: StorageU256,
+    balances: StorageMap<Address, StorageU256>,
+}
+
+#[public]
+impl Vault {
+    // Unsafe: the gated getter protects nothing
+    pub fn secret(&self) -> Result<U256, Vec<u8>> {
+        if msg::sender() != self.owner.get() {
+            return Err(b"not the owner".to_vec());
+        }
+        Ok(self.secret.get())
+    }
+
+    // Unsafe: gated through the owner helper
+    pub fn audit_secret(&self) -> Result<U256, Vec<u8>> {
+        self.only_owner()?;
+        Ok(self.secret.get())
+    }
+
+    // Unsafe: a read-only method writing storage
+    pub fn read_and_count(&self) -> U256 {
+        self.reads.set(self.reads.get() + U256::from(1));
+        self.secret.get()
+    }
+
+    // Safe: the caller only picks which balance to read
+    pub fn my_balance(&self) -> U256 {
+        self.balances.get(msg::sender())
+    }
+
+    // Safe: comparing the caller without restricting it
+    pub fn is_owner(&self) -> bool {
+        msg::sender() == self.owner.get()
+    }
+
+    // Safe: the gated method changes state
+    pub fn set_secret(&mut self, secret: U256) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+        self.secret.set(secret);
+        Ok(())
+    }
+}
+
+impl Vault {
+    // Safe: the guard helper itself
+    fn only_owner(&self) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.owner.get() {
+            return Err(b"not the owner".to_vec());
+        }
+        Ok(())
+    }
+}
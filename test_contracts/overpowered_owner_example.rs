@@ -0,0 +1,78 @@
+// Example token whose owner can mint, burn and move the tokens of any account
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, msg, prelude::*};
+use stylus_sdk::storage::{StorageAddress, StorageMap, StorageU256};
+
+#[storage]
+#[entrypoint]
+pub struct Token {
+    owner: StorageAddress,
+    total_supply: StorageU256,
+    balances: StorageMap<Address, StorageU256>,
+    fee: StorageU256,
+}
+
+#[public]
+impl Token {
+    // Reported: the owner can mint any amount to anyone
+    pub fn mint(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.owner.get() {
+            return Err(b"not the owner".to_vec());
+        }
+        self.total_supply.set(self.total_supply.get() + amount);
+        self.balances.setter(to).set(self.balances.get(to) + amount);
+        Ok(())
+    }
+
+    // Reported: the owner can burn the tokens of any account, through a helper
+    pub fn burn_from(&mut self, account: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+        self.burn(account, amount);
+        Ok(())
+    }
+
+    // Reported: the owner can move funds between any accounts
+    pub fn force_transfer(&mut self, from: Address, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        let sender = msg::sender();
+        assert_eq!(sender, self.owner.get(), "not the owner");
+        self.balances.setter(from).set(self.balances.get(from) - amount);
+        self.balances.setter(to).set(self.balances.get(to) + amount);
+        Ok(())
+    }
+
+    // Not reported: the owner only changes a parameter
+    pub fn set_fee(&mut self, fee: U256) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+        self.fee.set(fee);
+        Ok(())
+    }
+
+    // Not reported: the owner burns their own tokens
+    pub fn burn_own(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        self.only_owner()?;
+        self.balances.setter(msg::sender()).set(self.balances.get(msg::sender()) - amount);
+        Ok(())
+    }
+
+    // Not reported: anyone can move their own balance, with no owner involved
+    pub fn transfer(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        let sender = msg::sender();
+        self.balances.setter(sender).set(self.balances.get(sender) - amount);
+        self.balances.setter(to).set(self.balances.get(to) + amount);
+        Ok(())
+    }
+}
+
+impl Token {
+    fn only_owner(&self) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.owner.get() {
+            return Err(b"not the owner".to_vec());
+        }
+        Ok(())
+    }
+
+    fn burn(&mut self, account: Address, amount: U256) {
+        self.balances.setter(account).set(self.balances.get(account) - amount);
+        self.total_supply.set(self.total_supply.get() - amount);
+    }
+}
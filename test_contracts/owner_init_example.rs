@@ -0,0 +1,67 @@
+// Example contracts whose constructors forget to assign the owner
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, alloy_sol_types::sol, msg, prelude::*};
+
+sol! {
+    contract Ownable {
+        address public owner;
+        uint256 public fee;
+
+        // Unsafe: the owner is never assigned
+        constructor(uint256 _fee) {
+            fee = _fee;
+        }
+    }
+
+    contract Governed {
+        address public governance;
+
+        // Safe: the deployer becomes governance
+        constructor() {
+            governance = msg.sender;
+        }
+    }
+}
+
+#[storage]
+#[entrypoint]
+pub struct Pool {
+    owner: StorageAddress,
+    admin: StorageAddress,
+    fee: StorageU256,
+}
+
+#[public]
+impl Pool {
+    // Unsafe: admin is set to a constant instead of the deployer
+    #[constructor]
+    pub fn constructor(&mut self, fee: U256) {
+        let deployer = msg::sender();
+        self.owner.set(deployer);
+        self.admin.set(Address::ZERO);
+        self.fee.set(fee);
+    }
+}
+
+#[storage]
+pub struct Staking {
+    owner: StorageAddress,
+}
+
+#[public]
+impl Staking {
+    // Safe: the owner is a constructor parameter, set through a helper
+    #[constructor]
+    pub fn constructor(&mut self, owner: Address) {
+        self.transfer_ownership(owner);
+    }
+}
+
+impl Staking {
+    fn transfer_ownership(&mut self, next: Address) {
+        self.owner.set(next);
+    }
+}
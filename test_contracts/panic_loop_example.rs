@@ -0,0 +1,65 @@
+// Example contract with aborting macros in loops and public methods
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Payouts {
+    balances: StorageMap<Address, StorageU256>,
+    total: StorageU256,
+}
+
+#[public]
+impl Payouts {
+    // Unsafe: aborts in the middle of the loop
+    pub fn pay_all(&mut self, recipients: Vec<Address>, amount: U256) -> Result<(), Vec<u8>> {
+        for recipient in recipients {
+            assert_ne!(recipient, Address::ZERO);
+            let balance = self.balances.get(recipient);
+            self.balances.insert(recipient, balance + amount);
+        }
+        Ok(())
+    }
+
+    // Unsafe: aborts in a public method
+    pub fn set_total(&mut self, total: U256) {
+        assert!(total > U256::ZERO, "total must be positive");
+        self.total.set(total);
+    }
+
+    // Unsafe: not implemented yet
+    pub fn migrate(&mut self) -> Result<(), Vec<u8>> {
+        todo!()
+    }
+
+    // Safe: returns an error instead
+    pub fn checked_set_total(&mut self, total: U256) -> Result<(), Vec<u8>> {
+        if total == U256::ZERO {
+            return Err(b"total must be positive".to_vec());
+        }
+        self.total.set(total);
+        Ok(())
+    }
+}
+
+impl Payouts {
+    // Safe: internal invariant check outside loops
+    fn check_invariant(&self) {
+        assert_eq!(self.total.get(), self.total.get());
+    }
+
+    // Unsafe: internal helper aborting inside a loop
+    fn sum(&self, recipients: &[Address]) -> U256 {
+        let mut sum = U256::ZERO;
+        for recipient in recipients {
+            match self.balances.get(*recipient) {
+                balance if balance > U256::ZERO => sum += balance,
+                _ => unreachable!(),
+            }
+        }
+        sum
+    }
+}
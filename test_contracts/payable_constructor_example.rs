@@ -0,0 +1,62 @@
+// Example contracts whose constructors accept Ether that nothing accounts for or withdraws
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, msg, prelude::*};
+use stylus_sdk::storage::{StorageAddress, StorageU256};
+
+#[storage]
+pub struct Crowdsale {
+    owner: StorageAddress,
+}
+
+#[public]
+impl Crowdsale {
+    // Unsafe: the deploy-time Ether is neither recorded nor withdrawable
+    #[constructor]
+    #[payable]
+    pub fn constructor(&mut self, owner: Address) {
+        self.owner.set(owner);
+    }
+
+    pub fn owner(&self) -> Address {
+        self.owner.get()
+    }
+}
+
+#[storage]
+pub struct Registry {
+    fee: StorageU256,
+}
+
+#[public]
+impl Registry {
+    // Unsafe: the deploy entrypoint checks the value it receives but drops it
+    #[payable]
+    pub fn deploy(&mut self, fee: U256) -> Result<(), Vec<u8>> {
+        if msg::value() < fee {
+            return Err(b"fee not paid".to_vec());
+        }
+        self.fee.set(fee);
+        Ok(())
+    }
+}
+
+#[storage]
+pub struct Treasury {
+    initial_deposit: StorageU256,
+}
+
+#[public]
+impl Treasury {
+    // Safe: the deploy-time Ether is recorded
+    #[constructor]
+    #[payable]
+    pub fn constructor(&mut self) {
+        let deposit = msg::value();
+        self.initial_deposit.set(deposit);
+    }
+
+    // Safe: not payable and never looks at the value
+    pub fn init(&mut self) {
+        self.initial_deposit.set(U256::ZERO);
+    }
+}
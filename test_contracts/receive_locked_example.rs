@@ -0,0 +1,35 @@
+// Example contract that accepts Ether only through receive and fallback
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, msg, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Tipjar {
+    owner: StorageAddress,
+    tips: StorageMap<Address, StorageU256>,
+}
+
+#[public]
+impl Tipjar {
+    // Unsafe: plain transfers are accepted but can never leave
+    #[receive]
+    pub fn receive(&mut self) -> Result<(), Vec<u8>> {
+        let sender = msg::sender();
+        let tipped = self.tips.get(sender);
+        self.tips.setter(sender).set(tipped + msg::value());
+        Ok(())
+    }
+
+    #[fallback]
+    #[payable]
+    pub fn fallback(&mut self, _calldata: &[u8]) -> Result<Vec<u8>, Vec<u8>> {
+        Ok(Vec::new())
+    }
+
+    pub fn tips_of(&self, account: Address) -> U256 {
+        self.tips.get(account)
+    }
+}
@@ -0,0 +1,26 @@
+// Example sol! contract whose receive and fallback are the only payable entrypoints
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::alloy_sol_types::sol;
+
+sol! {
+    contract Sink {
+        address public owner;
+
+        constructor() {
+            owner = msg.sender;
+        }
+
+        function setOwner(address newOwner) public {
+            require(msg.sender == owner, "Not owner");
+            owner = newOwner;
+        }
+
+        // Unsafe: Ether received here can never be sent out
+        receive() external payable {}
+
+        fallback() external payable {}
+    }
+}
@@ -0,0 +1,60 @@
+// Example contract reading the same storage slot repeatedly
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, msg, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Pool {
+    reserve: StorageU256,
+    fee: StorageU256,
+    balances: StorageMap<Address, StorageU256>,
+}
+
+#[public]
+impl Pool {
+    // Inefficient: the reserve is loaded three times
+    pub fn quote(&self, amount: U256) -> U256 {
+        if self.reserve.get() < amount {
+            return U256::ZERO;
+        }
+        amount * self.reserve.get() / (self.reserve.get() + amount)
+    }
+
+    // Inefficient: the same mapping entry is read twice
+    pub fn can_withdraw(&self, amount: U256) -> bool {
+        self.balances.get(msg::sender()) >= amount && self.balances.get(msg::sender()) > U256::ZERO
+    }
+
+    // Fine: different keys and a write in between
+    pub fn bump(&mut self, a: Address, b: Address) {
+        let first = self.balances.get(a);
+        let second = self.balances.get(b);
+        let fee = self.fee.get();
+        self.fee.set(fee + U256::from(1));
+        let updated = self.fee.get();
+        self.balances.setter(a).set(first + second + updated);
+    }
+
+    // Fine: the internal call may change the reserve
+    pub fn sync(&mut self) -> U256 {
+        let before = self.reserve.get();
+        self.refresh();
+        self.reserve.get() - before
+    }
+
+    // Fine: only one branch runs
+    pub fn pick(&self, high: bool) -> U256 {
+        if high {
+            self.fee.get()
+        } else {
+            self.fee.get() / U256::from(2)
+        }
+    }
+
+    fn refresh(&mut self) {
+        self.reserve.set(U256::from(100));
+    }
+}
@@ -0,0 +1,65 @@
+// Example contract that demonstrates reentrancy-prone state updates
+// Storage is updated only after the external token transfer returns
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    call::Call,
+    msg,
+    prelude::*,
+};
+
+sol_interface! {
+    interface IERC20 {
+        function transfer(address to, uint256 value) external returns (bool);
+    }
+}
+
+#[storage]
+#[entrypoint]
+pub struct Vault {
+    balance: StorageU256,
+    token: StorageAddress,
+    locked: StorageBool,
+}
+
+#[public]
+impl Vault {
+    // VULNERABLE: tokens are transferred before the balance is decremented
+    pub fn withdraw(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        let token = IERC20::new(self.token.get());
+        token.transfer(Call::new_in(self), to, amount)?;
+        self.balance.set(self.balance.get() - amount);
+        Ok(())
+    }
+
+    // VULNERABLE: the balance is cleared in a branch that runs after the call
+    pub fn withdraw_all(&mut self, token: IERC20, to: Address) -> Result<(), Vec<u8>> {
+        let amount = self.balance.get();
+        let success = token.transfer(Call::new_in(self), to, amount)?;
+        if success {
+            self.balance -= amount;
+        }
+        Ok(())
+    }
+
+    // SAFE: checks-effects-interactions ordering
+    pub fn safe_withdraw(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.balance.set(self.balance.get() - amount);
+        let token = IERC20::new(self.token.get());
+        token.transfer(Call::new_in(self), to, amount)?;
+        Ok(())
+    }
+
+    // SAFE: protected by a reentrancy lock set before the call
+    pub fn guarded_withdraw(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.locked.set(true);
+        let token = IERC20::new(self.token.get());
+        token.transfer(Call::new_in(self), to, amount)?;
+        self.balance.set(self.balance.get() - amount);
+        self.locked.set(false);
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+// Example contract with reverts that give no reason
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, alloy_sol_types::sol, evm, msg, prelude::*};
+
+sol! {
+    error Unauthorized(address caller);
+
+    contract Escrow {
+        address public owner;
+        uint256 public released;
+
+        // Unsafe: require without a message
+        function release(uint256 amount) external {
+            require(msg.sender == owner);
+            released += amount;
+        }
+
+        // Unsafe: bare revert
+        function cancel() external {
+            if (msg.sender != owner) {
+                revert();
+            }
+        }
+
+        // Safe: the reason is given
+        function refund(uint256 amount) external {
+            require(amount <= released, "nothing to refund");
+            released -= amount;
+        }
+    }
+}
+
+#[storage]
+#[entrypoint]
+pub struct Vault {
+    owner: StorageAddress,
+    balances: StorageMap<Address, StorageU256>,
+}
+
+#[public]
+impl Vault {
+    // Unsafe: an empty error payload
+    pub fn withdraw(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        let balance = self.balances.get(msg::sender());
+        if amount > balance {
+            return Err(Vec::new());
+        }
+        self.balances.insert(msg::sender(), balance - amount);
+        Ok(())
+    }
+
+    // Unsafe: an empty vector as the tail expression
+    pub fn close(&mut self) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.owner.get() {
+            Err(vec![])
+        } else {
+            Ok(())
+        }
+    }
+
+    // Safe: the error explains the failure
+    pub fn set_owner(&mut self, owner: Address) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.owner.get() {
+            return Err(b"not the owner".to_vec());
+        }
+        self.owner.set(owner);
+        Ok(())
+    }
+}
+
+impl Vault {
+    // Unsafe: reverting with empty data
+    fn halt(&self) {
+        evm::revert(b"");
+    }
+}
@@ -0,0 +1,14 @@
+// Small contract used for the SARIF and Markdown golden files
+
+use stylus_sdk::prelude::*;
+
+#[public]
+impl Counter {
+    pub fn parse_limit(&self, input: String) -> u64 {
+        input.parse::<u64>().unwrap()
+    }
+
+    pub fn reset(&mut self) {
+        panic!("reset is not supported");
+    }
+}
@@ -0,0 +1,66 @@
+// Example contract spelling the Stylus attributes the way different SDK versions and crates do
+
+use stylus_sdk::prelude::{payable as stylus_payable, public as export};
+use stylus_sdk::prelude as sdk;
+use stylus_sdk::{alloy_primitives::U256, msg, prelude::*};
+use stylus_sdk::storage::StorageU256;
+
+// SDK 0.4 and 0.5 spelling of #[storage]
+#[solidity_storage]
+#[entrypoint]
+pub struct Vault {
+    deposits: StorageU256,
+}
+
+// Namespaced attributes
+#[stylus_sdk::prelude::external]
+impl Vault {
+    #[stylus_sdk::prelude::payable]
+    pub fn deposit(&mut self) {
+        self.deposits.set(self.deposits.get() + msg::value());
+    }
+
+    pub fn deposits(&self) -> U256 {
+        self.deposits.get()
+    }
+
+    fn helper(&self) -> U256 {
+        self.deposits.get()
+    }
+}
+
+#[sdk::storage]
+pub struct Registry {
+    count: StorageU256,
+}
+
+// Attributes imported under another name
+#[export]
+impl Registry {
+    #[stylus_payable]
+    pub fn register(&mut self) {
+        self.count.set(self.count.get() + U256::from(1));
+    }
+
+    #[cfg_attr(feature = "payable-reset", payable)]
+    pub fn reset(&mut self) {
+        self.count.set(U256::ZERO);
+    }
+}
+
+// Attributes of other crates are not Stylus attributes
+#[serde::storage]
+pub struct Snapshot {
+    count: U256,
+}
+
+#[cfg_attr(not(feature = "export-abi"), storage)]
+pub struct Ledger {
+    entries: StorageU256,
+}
+
+impl Ledger {
+    pub fn entries(&self) -> U256 {
+        self.entries.get()
+    }
+}
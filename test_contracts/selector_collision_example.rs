@@ -0,0 +1,45 @@
+// Example contract with two signatures that hash to the same selector
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, FixedBytes, U256}, msg, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Proxy {
+    owner: StorageAddress,
+    supply: StorageU256,
+    slots: StorageMap<FixedBytes<16>, StorageBool>,
+}
+
+#[public]
+impl Proxy {
+    // burn(uint256) has selector 0x42966c68
+    pub fn burn(&mut self, amount: U256) {
+        let supply = self.supply.get();
+        self.supply.set(supply - amount);
+    }
+
+    pub fn owner(&self) -> Address {
+        self.owner.get()
+    }
+}
+
+#[external]
+impl Proxy {
+    // Unsafe: collate_propagate_storage(bytes16) also has selector 0x42966c68
+    #[selector(name = "collate_propagate_storage")]
+    pub fn collate(&mut self, slot: FixedBytes<16>) {
+        if msg::sender() == self.owner.get() {
+            self.slots.setter(slot).set(true);
+        }
+    }
+
+    // Safe: burnFrom(address,uint256) has its own selector
+    pub fn burn_from(&mut self, account: Address, amount: U256) {
+        if account == msg::sender() {
+            self.burn(amount);
+        }
+    }
+}
@@ -0,0 +1,41 @@
+// Example contract whose transfer inflates balances on self-transfers
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Ledger {
+    balances: StorageMap<Address, StorageU256>,
+}
+
+#[public]
+impl Ledger {
+    // Vulnerable: with from == to the credit overwrites the debit
+    pub fn move_funds(&mut self, from: Address, to: Address, amount: U256) {
+        let from_balance = self.balances.get(from);
+        let to_balance = self.balances.get(to);
+        self.balances.setter(from).set(from_balance - amount);
+        self.balances.setter(to).set(to_balance + amount);
+    }
+
+    // Safe: self-transfers are rejected
+    pub fn move_checked(&mut self, from: Address, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        if from == to {
+            return Err(b"self transfer".to_vec());
+        }
+        let from_balance = self.balances.get(from);
+        let to_balance = self.balances.get(to);
+        self.balances.setter(from).set(from_balance - amount);
+        self.balances.setter(to).set(to_balance + amount);
+        Ok(())
+    }
+
+    // Not a transfer: only credits
+    pub fn credit(&mut self, to: Address, amount: U256) {
+        let balance = self.balances.get(to);
+        self.balances.setter(to).set(balance + amount);
+    }
+}
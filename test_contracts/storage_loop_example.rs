@@ -0,0 +1,52 @@
+// Example contract writing storage inside loops
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Accumulator {
+    total: StorageU256,
+    count: StorageU256,
+    balances: StorageMap<Address, StorageU256>,
+    history: StorageVec<StorageU256>,
+}
+
+#[public]
+impl Accumulator {
+    // Inefficient: writes the same slot on every iteration
+    pub fn sum(&mut self, values: Vec<U256>) {
+        for value in values {
+            self.total.set(self.total.get() + value);
+        }
+    }
+
+    // Inefficient: compound assignment and a while loop
+    pub fn count_up(&mut self, times: u32) {
+        let mut i = 0;
+        while i < times {
+            self.count += U256::from(1);
+            i += 1;
+        }
+    }
+
+    // Fine: every iteration writes a different slot
+    pub fn airdrop(&mut self, users: Vec<Address>, amount: U256) {
+        for user in users {
+            let mut balance = self.balances.setter(user);
+            balance.set(amount);
+            self.history.push(amount);
+        }
+    }
+
+    // Fine: accumulates locally and writes once
+    pub fn sum_cached(&mut self, values: Vec<U256>) {
+        let mut total = self.total.get();
+        for value in values {
+            total += value;
+        }
+        self.total.set(total);
+    }
+}
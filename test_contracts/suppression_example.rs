@@ -0,0 +1,23 @@
+// Example contract with inline suppression comments
+// stylus-analyzer-disable-file unsafe_expect
+
+use stylus_sdk::prelude::*;
+
+#[public]
+impl Registry {
+    pub fn lookup(&self, input: String) -> u64 {
+        // Suppressed: only unsafe_unwrap on the next line
+        // stylus-analyzer-disable-next-line unsafe_unwrap -- input is validated by the caller
+        let first = input.parse::<u64>().unwrap();
+        let second = input.parse::<u64>().unwrap(); // stylus-analyzer-disable-line
+        let third = input.parse::<u64>().unwrap();
+        let fourth = input.parse::<u64>().expect("number");
+        first + second + third + fourth
+    }
+
+    pub fn halt(&self) {
+        // Unused: suppresses another detector than the one reporting
+        // stylus-analyzer-disable-next-line unsafe_unwrap
+        panic!("halted");
+    }
+}
@@ -0,0 +1,51 @@
+// Example contract whose logic depends on the block timestamp
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, alloy_sol_types::sol, block, call::transfer_eth, evm, prelude::*};
+
+sol! {
+    event Claimed(address indexed account, uint256 timestamp);
+}
+
+#[storage]
+#[entrypoint]
+pub struct Auction {
+    deadline: StorageU256,
+    beneficiary: StorageAddress,
+    last_update: StorageU256,
+}
+
+#[public]
+impl Auction {
+    // Timestamp dependent: the deadline check gates the payout
+    pub fn claim(&mut self) -> Result<(), Vec<u8>> {
+        if U256::from(block::timestamp()) < self.deadline.get() {
+            return Err(b"auction still running".to_vec());
+        }
+        transfer_eth(self.beneficiary.get(), U256::from(100))?;
+        Ok(())
+    }
+
+    // Timestamp dependent through a local
+    pub fn is_open(&self) -> bool {
+        let now = U256::from(block::timestamp());
+        match now < self.deadline.get() {
+            true => true,
+            false => false,
+        }
+    }
+
+    // Harmless: only records and logs the time
+    pub fn touch(&mut self, account: Address) {
+        let now = U256::from(block::timestamp());
+        self.last_update.set(now);
+        evm::log(Claimed { account, timestamp: now });
+    }
+
+    // Harmless: returns the timestamp
+    pub fn now(&self) -> u64 {
+        block::timestamp()
+    }
+}
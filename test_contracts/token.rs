@@ -0,0 +1,71 @@
+// Simple Token Contract
+// A basic ERC-20 like token defined through the sol! macro
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    prelude::*,
+};
+
+sol! {
+    contract SimpleToken {
+        string public name = "Simple Token";
+        string public symbol = "STK";
+        uint8 public decimals;
+        uint256 public totalSupply;
+        // address public owner;
+
+        mapping(address => uint256) public balanceOf;
+        mapping(address => mapping(address => uint256)) public allowance;
+
+        event Transfer(address indexed from, address indexed to, uint256 value);
+        event Approval(address indexed owner, address indexed spender, uint256 value);
+
+        constructor(uint256 _initialSupply, uint8 _decimals) {
+            // owner = msg.sender;
+            decimals = _decimals;
+            totalSupply = _initialSupply * 10 ** uint256(_decimals);
+            balanceOf[msg.sender] = totalSupply;
+            emit Transfer(address(0), msg.sender, totalSupply);
+        }
+
+        function transfer(address to, uint256 value) public returns (bool) {
+            require(to != address(0), "Invalid recipient");
+            require(balanceOf[msg.sender] >= value, "Insufficient balance");
+            balanceOf[msg.sender] -= value;
+            balanceOf[to] += value;
+            emit Transfer(msg.sender, to, value);
+            return true;
+        }
+
+        function approve(address spender, uint256 value) public returns (bool) {
+            allowance[msg.sender][spender] = value;
+            emit Approval(msg.sender, spender, value);
+            return true;
+        }
+
+        function transferFrom(address from, address to, uint256 value) public returns (bool) {
+            require(to != address(0), "Invalid recipient");
+            balanceOf[from] -= value;
+            allowance[from][msg.sender] -= value;
+            balanceOf[to] += value;
+            emit Transfer(from, to, value);
+            return true;
+        }
+
+        function mint(address to, uint256 value) public {
+            totalSupply += value;
+            balanceOf[to] += value;
+            emit Transfer(address(0), to, value);
+        }
+
+        function burn(address from, uint256 value) public {
+            balanceOf[from] -= value;
+            totalSupply -= value;
+            emit Transfer(from, address(0), value);
+        }
+    }
+}
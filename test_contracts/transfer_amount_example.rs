@@ -0,0 +1,82 @@
+// Example token emitting Transfer events with and without checking the amount
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, alloy_sol_types::sol, evm, msg, prelude::*};
+use stylus_sdk::storage::{StorageMap, StorageU256};
+
+sol! {
+    event Transfer(address indexed from, address indexed to, uint256 value);
+}
+
+#[storage]
+#[entrypoint]
+pub struct Token {
+    balances: StorageMap<Address, StorageU256>,
+    total_supply: StorageU256,
+}
+
+#[public]
+impl Token {
+    // Reported: the balance check bounds the value, but a zero value goes through
+    pub fn transfer(&mut self, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        let sender = msg::sender();
+        let balance = self.balances.get(sender);
+        if balance < value {
+            return Err(b"insufficient balance".to_vec());
+        }
+        self.balances.setter(sender).set(balance - value);
+        self.balances.setter(to).set(self.balances.get(to) + value);
+        evm::log(Transfer { from: sender, to, value });
+        Ok(true)
+    }
+
+    // Safe: any amount is rejected when zero
+    pub fn mint(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        if amount == U256::ZERO {
+            return Err(b"zero amount".to_vec());
+        }
+        self.total_supply.set(self.total_supply.get() + amount);
+        self.balances.setter(to).set(self.balances.get(to) + amount);
+        evm::log(Transfer { from: Address::ZERO, to, value: amount });
+        Ok(())
+    }
+
+    // Safe: the zero check happens before the helper emits the event
+    pub fn burn(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        if amount.is_zero() {
+            return Err(b"zero amount".to_vec());
+        }
+        self.burn_from(msg::sender(), amount)
+    }
+
+    // The caller of move_balance checks nothing
+    pub fn transfer_from(&mut self, from: Address, to: Address, value: U256) -> Result<bool, Vec<u8>> {
+        self.move_balance(from, to, value);
+        Ok(true)
+    }
+
+    // Not checked: the value of the event is the whole balance, not a parameter
+    pub fn sweep(&mut self, to: Address) {
+        let value = self.balances.get(msg::sender());
+        self.balances.setter(msg::sender()).set(U256::ZERO);
+        self.balances.setter(to).set(self.balances.get(to) + value);
+        evm::log(Transfer { from: msg::sender(), to, value });
+    }
+}
+
+impl Token {
+    fn burn_from(&mut self, from: Address, amount: U256) -> Result<(), Vec<u8>> {
+        let balance = self.balances.get(from);
+        let remaining = balance.checked_sub(amount).ok_or(b"insufficient balance".to_vec())?;
+        self.balances.setter(from).set(remaining);
+        self.total_supply.set(self.total_supply.get() - amount);
+        evm::log(Transfer { from, to: Address::ZERO, value: amount });
+        Ok(())
+    }
+
+    // Reported: neither this helper nor its caller validates the value
+    fn move_balance(&mut self, from: Address, to: Address, value: U256) {
+        self.balances.setter(from).set(self.balances.get(from) - value);
+        self.balances.setter(to).set(self.balances.get(to) + value);
+        evm::log(Transfer { from, to, value });
+    }
+}
@@ -0,0 +1,45 @@
+// Example contract authenticating with the transaction origin
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::Address, msg, prelude::*, tx};
+
+#[storage]
+#[entrypoint]
+pub struct Wallet {
+    owner: StorageAddress,
+    paused: StorageBool,
+}
+
+#[public]
+impl Wallet {
+    // Vulnerable: a contract called by the owner passes this check
+    pub fn set_owner(&mut self, new_owner: Address) -> Result<(), Vec<u8>> {
+        if tx::origin() != self.owner.get() {
+            return Err(b"not owner".to_vec());
+        }
+        self.owner.set(new_owner);
+        Ok(())
+    }
+
+    // Vulnerable: same mistake through an assertion
+    pub fn pause(&mut self) {
+        assert!(tx::origin() == self.owner.get(), "not owner");
+        self.paused.set(true);
+    }
+
+    // Safe: authenticates the immediate caller
+    pub fn unpause(&mut self) -> Result<(), Vec<u8>> {
+        if msg::sender() != self.owner.get() {
+            return Err(b"not owner".to_vec());
+        }
+        self.paused.set(false);
+        Ok(())
+    }
+
+    // Not a guard: only reports the origin
+    pub fn origin(&self) -> Address {
+        tx::origin()
+    }
+}
@@ -0,0 +1,55 @@
+// Example contract looping over caller-supplied input
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, call::transfer_eth, prelude::*};
+
+const MAX_RECIPIENTS: usize = 50;
+
+#[storage]
+#[entrypoint]
+pub struct Distributor {
+    balances: StorageMap<Address, StorageU256>,
+    rounds: StorageU256,
+}
+
+#[public]
+impl Distributor {
+    // Vulnerable: writes storage for every caller-supplied address
+    pub fn airdrop(&mut self, recipients: Vec<Address>, amount: U256) {
+        for recipient in recipients.iter() {
+            self.balances.setter(*recipient).set(amount);
+        }
+    }
+
+    // Vulnerable: sends ether n times
+    pub fn payout(&mut self, to: Address, n: u64) -> Result<(), Vec<u8>> {
+        let mut i = 0;
+        while i < n {
+            transfer_eth(to, U256::from(1))?;
+            i += 1;
+        }
+        Ok(())
+    }
+
+    // Fine: the input is capped before the loop
+    pub fn capped_airdrop(&mut self, recipients: Vec<Address>, amount: U256) -> Result<(), Vec<u8>> {
+        if recipients.len() > MAX_RECIPIENTS {
+            return Err(b"too many recipients".to_vec());
+        }
+        for recipient in recipients {
+            self.balances.setter(recipient).set(amount);
+        }
+        Ok(())
+    }
+
+    // Fine: pure computation over the input
+    pub fn sum(&self, values: Vec<U256>) -> U256 {
+        let mut total = U256::ZERO;
+        for value in values {
+            total += value;
+        }
+        total
+    }
+}
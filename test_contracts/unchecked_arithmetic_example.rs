@@ -0,0 +1,51 @@
+// Example contract with unchecked arithmetic on U256 values
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::U256, prelude::*};
+
+const SCALE: u64 = 10 * 1000;
+
+#[storage]
+#[entrypoint]
+pub struct Counter {
+    total: StorageU256,
+    fee_bps: StorageU256,
+}
+
+#[public]
+impl Counter {
+    // Vulnerable: the addition wraps around on overflow
+    pub fn add(&mut self, amount: U256) {
+        let total = self.total.get();
+        self.total.set(total + amount);
+    }
+
+    // Vulnerable: compound subtraction underflows
+    pub fn sub(&mut self, amount: U256) {
+        let mut total = self.total.get();
+        total -= amount;
+        self.total.set(total);
+    }
+
+    // Safe: checked arithmetic
+    pub fn checked_add(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        let total = self.total.get().checked_add(amount).ok_or(Vec::new())?;
+        self.total.set(total);
+        Ok(())
+    }
+
+    // Reviewed: the fee is bounded by 10_000 basis points
+    pub fn fee(&self, amount: U256) -> U256 {
+        // SAFE: amount is capped by the caller
+        {
+            amount * self.fee_bps.get() / U256::from(10_000)
+        }
+    }
+
+    // Not reported: no fixed-width integers involved
+    pub fn scale(&self) -> u64 {
+        SCALE
+    }
+}
@@ -0,0 +1,64 @@
+// Example contract with low-level calls whose result is discarded
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{
+    alloy_primitives::{Address, U256},
+    alloy_sol_types::sol,
+    call::{call, delegate_call, Call, RawCall},
+    prelude::*,
+};
+
+sol! {
+    contract RawCalls {
+        // Unsafe: the call result is ignored
+        function forward(address target, bytes calldata data) public {
+            target.call(data);
+        }
+
+        // Safe: the success flag is required
+        function forwardChecked(address target, bytes calldata data) public {
+            (bool success, bytes memory result) = target.call(data);
+            require(success, "call failed");
+        }
+
+        // Unsafe: the success flag is bound but never checked
+        function forwardUnchecked(address target, bytes calldata data) public {
+            (bool ok, bytes memory result) = target.delegatecall(data);
+            emit Forwarded(target);
+        }
+    }
+}
+
+#[storage]
+#[entrypoint]
+pub struct Forwarder {
+    last: StorageU256,
+}
+
+#[public]
+impl Forwarder {
+    // Unsafe: the call result is dropped in statement position
+    pub fn ping(&mut self, target: Address, data: Vec<u8>) {
+        call(Call::new_in(self), target, &data);
+    }
+
+    // Unsafe: the call result is bound to `_`
+    pub fn ping_ignored(&mut self, target: Address, data: Vec<u8>) {
+        let _ = unsafe { RawCall::new().call(target, &data) };
+        let _ = delegate_call(self, target, &data);
+    }
+
+    // Unsafe: the error is turned into an `Option` and dropped
+    pub fn ping_ok(&mut self, target: Address, data: Vec<u8>) {
+        call(Call::new_in(self), target, &data).ok();
+    }
+
+    // Safe: the error is propagated
+    pub fn ping_checked(&mut self, target: Address, data: Vec<u8>) -> Result<Vec<u8>, Vec<u8>> {
+        let result = call(Call::new_in(self), target, &data)?;
+        self.last.set(U256::from(result.len()));
+        Ok(result)
+    }
+}
@@ -0,0 +1,65 @@
+// Example subtractions from unsigned values with and without a bounds check
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use alloc::vec::Vec;
+use stylus_sdk::{alloy_primitives::{Address, U256}, msg, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Escrow {
+    deposits: StorageMap<Address, StorageU256>,
+    total: StorageU256,
+    pending: StorageU64,
+}
+
+#[public]
+impl Escrow {
+    // Unsafe: refunding more than was deposited wraps the deposit around
+    pub fn refund(&mut self, account: Address, amount: U256) {
+        let deposit = self.deposits.get(account);
+        self.deposits.setter(account).set(deposit - amount);
+    }
+
+    // Unsafe: the guard compares the amount with another value
+    pub fn release(&mut self, amount: U256, limit: U256) -> Result<(), Vec<u8>> {
+        if amount > limit {
+            return Err(b"over the limit".to_vec());
+        }
+        self.total.set(self.total.get() - amount);
+        Ok(())
+    }
+
+    // Safe: the deposit is compared before it is decremented
+    pub fn withdraw(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        let deposit = self.deposits.get(msg::sender());
+        if deposit < amount {
+            return Err(b"insufficient deposit".to_vec());
+        }
+        self.deposits.setter(msg::sender()).set(deposit - amount);
+        Ok(())
+    }
+
+    // Safe: the guard reads the field the subtraction reads
+    pub fn settle(&mut self, amount: U256) {
+        assert!(self.total.get() >= amount, "insufficient total");
+        self.total.set(self.total.get() - amount);
+    }
+
+    // Safe: the counter is checked against zero before it is decremented
+    pub fn complete(&mut self) {
+        let pending = self.pending.get();
+        if pending == 0 {
+            return;
+        }
+        self.pending.set(pending - 1);
+    }
+
+    // Safe: checked_sub reverts instead of wrapping
+    pub fn cancel(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        let remaining = self.total.get().checked_sub(amount).ok_or(b"insufficient total".to_vec())?;
+        self.total.set(remaining);
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+// Example contracts whose constructors leave important fields unset
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, alloc::string::String, msg, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Vault {
+    owner: StorageAddress,
+    treasury: StorageAddress,
+    fee_bps: StorageU256,
+    total_deposits: StorageU256,
+    paused: StorageBool,
+    balances: StorageMap<Address, StorageU256>,
+}
+
+#[public]
+impl Vault {
+    // Unsafe: owner and fee_bps are never set
+    #[constructor]
+    pub fn constructor(&mut self, treasury: Address) {
+        self.treasury.set(treasury);
+    }
+}
+
+#[public]
+struct Registry {
+    admin: Storage<Address>,
+    label: Storage<String>,
+    count: Storage<U256>,
+}
+
+#[external]
+impl Registry {
+    // Unsafe: label is only allocated, never given a value
+    pub fn new() -> Self {
+        let mut instance = Self {
+            admin: Storage::new(),
+            label: Storage::new(),
+            count: Storage::new(),
+        };
+        instance.admin.initialize(msg::sender());
+        instance
+    }
+}
+
+#[storage]
+pub struct Config {
+    owner: StorageAddress,
+    oracle: StorageAddress,
+}
+
+#[public]
+impl Config {
+    // Safe: the owner is set through a helper
+    #[constructor]
+    pub fn constructor(&mut self, oracle: Address) {
+        self.set_owner();
+        self.oracle.set(oracle);
+    }
+}
+
+impl Config {
+    fn set_owner(&mut self) {
+        self.owner.set(msg::sender());
+    }
+}
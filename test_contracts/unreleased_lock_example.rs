@@ -0,0 +1,95 @@
+// Manual reentrancy guards that some exit paths leave locked
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, msg, prelude::*};
+use stylus_sdk::storage::{StorageBool, StorageMap, StorageU256};
+
+#[storage]
+#[entrypoint]
+pub struct Vault {
+    balances: StorageMap<Address, StorageU256>,
+    locked: StorageBool,
+    status: StorageU256,
+}
+
+#[public]
+impl Vault {
+    // Vulnerable: the early return skips unlocking
+    pub fn withdraw(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        if self.locked.get() {
+            return Err(b"reentrant call".to_vec());
+        }
+        self.locked.set(true);
+        let balance = self.balances.get(msg::sender());
+        if amount > balance {
+            return Err(b"insufficient balance".to_vec());
+        }
+        self.balances.setter(msg::sender()).set(balance - amount);
+        self.locked.set(false);
+        Ok(())
+    }
+
+    // Vulnerable: returns successfully with the lock still held
+    pub fn deposit(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        self.locked.set(true);
+        if amount == U256::ZERO {
+            return Ok(());
+        }
+        let balance = self.balances.get(msg::sender());
+        self.balances.setter(msg::sender()).set(balance + amount);
+        self.locked.set(false);
+        Ok(())
+    }
+
+    // Vulnerable: the lock is never cleared
+    pub fn sweep(&mut self, to: Address) -> Result<(), Vec<u8>> {
+        self.status.set(U256::from(2));
+        self.balances.setter(to).set(U256::ZERO);
+        Ok(())
+    }
+
+    // Safe: every exit after the lock clears it first
+    pub fn safe_withdraw(&mut self, amount: U256) -> Result<(), Vec<u8>> {
+        if self.locked.get() {
+            return Err(b"reentrant call".to_vec());
+        }
+        self.locked.set(true);
+        let balance = self.balances.get(msg::sender());
+        if amount > balance {
+            self.locked.set(false);
+            return Err(b"insufficient balance".to_vec());
+        }
+        self.balances.setter(msg::sender()).set(balance - amount);
+        self.locked.set(false);
+        Ok(())
+    }
+
+    // Safe: the helper releases the lock, and the closure's return is not an exit
+    pub fn safe_transfer(&mut self, to: Address, amount: U256) -> Result<(), Vec<u8>> {
+        self.status.set(U256::from(2));
+        let capped = |value: U256| -> U256 { if value > amount { return amount; } value };
+        let balance = self.balances.get(to);
+        self.balances.setter(to).set(balance + capped(balance));
+        self.exit();
+        Ok(())
+    }
+
+    // Safe: both branches of the tail release the lock
+    pub fn safe_claim(&mut self, to: Address) -> Result<U256, Vec<u8>> {
+        self.locked.set(true);
+        let balance = self.balances.get(to);
+        if balance > U256::ZERO {
+            self.balances.setter(to).set(U256::ZERO);
+            self.locked.set(false);
+            Ok(balance)
+        } else {
+            self.locked.set(false);
+            Err(b"nothing to claim".to_vec())
+        }
+    }
+}
+
+impl Vault {
+    fn exit(&mut self) {
+        self.status.set(U256::from(1));
+    }
+}
@@ -0,0 +1,49 @@
+// Example contract with narrowing integer conversions
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::U256, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Registry {
+    deadline: StorageU256,
+    count: StorageU64,
+    entries: StorageVec<StorageU256>,
+}
+
+#[public]
+impl Registry {
+    // Vulnerable: truncates the high 64 bits
+    pub fn shrink(&self, value: u128) -> u64 {
+        value as u64
+    }
+
+    // Vulnerable: panics if the deadline doesn't fit in a u64
+    pub fn deadline(&self) -> u64 {
+        self.deadline.get().to::<u64>()
+    }
+
+    // Vulnerable: panics if the index doesn't fit in a usize
+    pub fn entry(&self, index: U256) -> U256 {
+        let idx = index.try_as_usize().unwrap();
+        self.entries.get(idx).unwrap_or_default()
+    }
+
+    // Vulnerable: unwrapped try_into conversion
+    pub fn small(&self, value: U256) -> u32 {
+        let small: u32 = value.try_into().unwrap();
+        small
+    }
+
+    // Safe: widening cast
+    pub fn widen(&self, flag: u8) -> u64 {
+        flag as u64
+    }
+
+    // Safe: the stored count already is a u64
+    pub fn count(&self) -> u64 {
+        self.count.get().to::<u64>()
+    }
+}
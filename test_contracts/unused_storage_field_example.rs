@@ -0,0 +1,49 @@
+// Example contract with storage fields that are never used
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, msg, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Vault {
+    owner: StorageAddress,
+    balances: StorageMap<Address, StorageU256>,
+    // Unsafe: left over from an earlier version
+    legacy_rate: StorageU256,
+    // Unsafe: read but never written, so always zero
+    fee: StorageU256,
+    // Possibly fine: only written
+    last_depositor: StorageAddress,
+    // Safe: written and read through a setter
+    deposits: StorageMap<Address, StorageU256>,
+}
+
+#[public]
+impl Vault {
+    #[constructor]
+    pub fn constructor(&mut self) {
+        self.owner.set(msg::sender());
+    }
+
+    pub fn deposit(&mut self, amount: U256) {
+        let fee = self.fee.get();
+        let mut balance = self.balances.setter(msg::sender());
+        let current = balance.get();
+        balance.set(current + amount - fee);
+        self.last_depositor.set(msg::sender());
+        let mut count = self.deposits.setter(msg::sender());
+        count.set(count.get() + U256::from(1));
+    }
+
+    pub fn balance_of(&self, account: Address) -> U256 {
+        self.balances.get(account)
+    }
+}
+
+impl Vault {
+    fn is_owner(&self) -> bool {
+        msg::sender() == self.owner.get()
+    }
+}
@@ -0,0 +1,55 @@
+// Example contract drawing winners from predictable randomness
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{
+    alloy_primitives::{keccak256, Address, U256},
+    block, msg,
+    prelude::*,
+};
+
+#[storage]
+#[entrypoint]
+pub struct Lottery {
+    players: StorageVec<StorageAddress>,
+    winner: StorageAddress,
+    deadline: StorageU256,
+}
+
+#[public]
+impl Lottery {
+    // Vulnerable: the winner index is derived from block data
+    pub fn draw(&mut self) {
+        let seed = keccak256(block::timestamp().to_be_bytes());
+        let random = U256::from_be_bytes(seed.0);
+        let index = random % U256::from(self.players.len());
+        let winner = self.players.get(index).unwrap_or_default();
+        self.winner.set(winner);
+    }
+
+    // Vulnerable: a coin flip on the block number
+    pub fn flip(&mut self) -> bool {
+        if block::number() % 2 == 0 {
+            self.winner.set(msg::sender());
+            return true;
+        }
+        false
+    }
+
+    // Not randomness: a plain deadline check
+    pub fn is_open(&self) -> bool {
+        if U256::from(block::timestamp()) < self.deadline.get() {
+            return true;
+        }
+        false
+    }
+
+    // Not randomness: authentication on the caller
+    pub fn is_winner(&self) -> bool {
+        if msg::sender() == self.winner.get() {
+            return true;
+        }
+        false
+    }
+}
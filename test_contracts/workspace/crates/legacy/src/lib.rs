@@ -0,0 +1,4 @@
+// Excluded from the workspace, so never analyzed through it
+pub fn legacy(value: Option<u64>) -> u64 {
+    value.unwrap()
+}
@@ -0,0 +1,17 @@
+// Token crate of the workspace fixture
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::U256, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Token {
+    total_supply: StorageU256,
+}
+
+#[public]
+impl Token {
+    pub fn parse_amount(&self, input: String) -> U256 {
+        U256::from(input.parse::<u64>().unwrap())
+    }
+}
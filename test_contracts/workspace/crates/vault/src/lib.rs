@@ -0,0 +1,19 @@
+// Vault crate of the workspace fixture
+extern crate alloc;
+
+mod logic;
+
+use stylus_sdk::{alloy_primitives::U256, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Vault {
+    locked: StorageU256,
+}
+
+#[public]
+impl Vault {
+    pub fn unlock(&mut self) {
+        panic!("unlocking is not supported");
+    }
+}
@@ -0,0 +1,4 @@
+// Helpers of the vault crate
+pub fn first(values: Vec<u64>) -> u64 {
+    *values.first().unwrap()
+}
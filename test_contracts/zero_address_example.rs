@@ -0,0 +1,46 @@
+// Example contract storing addresses without a zero-address check
+
+#![cfg_attr(not(feature = "export-abi"), no_main)]
+extern crate alloc;
+
+use stylus_sdk::{alloy_primitives::{Address, U256}, call::transfer_eth, prelude::*};
+
+#[storage]
+#[entrypoint]
+pub struct Admin {
+    owner: StorageAddress,
+    treasury: StorageAddress,
+    credits: StorageMap<Address, StorageU256>,
+}
+
+#[public]
+impl Admin {
+    // Vulnerable: the owner can be set to the zero address
+    pub fn set_owner(&mut self, new_owner: Address) {
+        self.owner.set(new_owner);
+    }
+
+    // Vulnerable: credits the zero address
+    pub fn credit(&mut self, account: Address, amount: U256) {
+        self.credits.setter(account).set(amount);
+    }
+
+    // Vulnerable: sends ether to an unchecked recipient
+    pub fn pay(&mut self, recipient: Address, amount: U256) -> Result<(), Vec<u8>> {
+        transfer_eth(recipient, amount)
+    }
+
+    // Safe: validated before it is stored
+    pub fn set_treasury(&mut self, treasury: Address) -> Result<(), Vec<u8>> {
+        if treasury == Address::ZERO {
+            return Err(b"zero address".to_vec());
+        }
+        self.treasury.set(treasury);
+        Ok(())
+    }
+
+    // Opted out by name in the tests
+    pub fn set_delegate(&mut self, delegate: Address) {
+        self.treasury.set(delegate);
+    }
+}